@@ -0,0 +1,251 @@
+//! Timings of the build pipeline stages on synthetic resources.
+//!
+//! Run with `cargo bench --bench pipeline`; `cargo bench --bench
+//! pipeline -- strings` only runs the cases whose name contains
+//! `strings`. Each case prints the min / median / max time of its
+//! samples, the throughput in resources per second and the heap
+//! allocations of one run: compare them with the numbers of the base
+//! branch to spot regressions.
+//!
+//! The `large/` cases run 100 000 resources through the whole build, so
+//! quadratic behavior (a table rebuilt per resource, a string grown one
+//! byte at a time) shows up as a jump in time instead of going
+//! unnoticed.
+
+#[path = "../tests/support/mod.rs"]
+mod support;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::time::{Duration, Instant};
+
+use r_resources::generator::analysis;
+use r_resources::generator::generation::{self, EmitOptions};
+use r_resources::generator::input::{self, RawResourceFile};
+use r_resources::generator::ir::{
+    Ordering, ResourceGraph, ResourceGraphBuilder,
+};
+use r_resources::generator::parsing;
+use r_resources::{
+    build_with_plan, BuildPlan, LazyStatics, ReferenceStyle,
+};
+
+/// Time spent sampling each case, after one warm-up run
+const TARGET: Duration = Duration::from_secs(2);
+const MAX_SAMPLES: usize = 50;
+
+/// The system allocator, counting the allocations
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, AtomicOrdering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, AtomicOrdering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+struct Fixture {
+    name: &'static str,
+    /// Number of resources, for the throughput
+    resources: usize,
+    xml: String,
+}
+
+fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture {
+            name: "strings_10k",
+            resources: 10_000,
+            xml: support::strings(10_000),
+        },
+        Fixture {
+            name: "scalars_10k",
+            resources: 10_000,
+            xml: support::scalars(10_000),
+        },
+        Fixture {
+            // 4 + 16 + 64 + 256 + 1024 + 4096 namespaces
+            name: "namespaces_6x4",
+            resources: 5_460,
+            xml: support::namespaces(6, 4),
+        },
+        Fixture {
+            name: "templates_2k",
+            resources: 2_000,
+            xml: support::templates(2_000, 6),
+        },
+    ]
+}
+
+fn main() {
+    let filter =
+        std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let mut bencher = Bencher { filter };
+
+    for fixture in fixtures() {
+        bench_fixture(&mut bencher, &fixture);
+    }
+
+    // Whole build of 100k resources split over 10 files
+    let tmp = tempfile::tempdir().unwrap();
+    let files: Vec<(String, String)> = (0..10)
+        .map(|i| {
+            (
+                format!("values_{i}.xml"),
+                prefixed(i, &support::strings(10_000)),
+            )
+        })
+        .collect();
+    let files: Vec<(&str, String)> = files
+        .iter()
+        .map(|(name, xml)| (name.as_str(), xml.clone()))
+        .collect();
+    let res_dir = support::write_res_dir(tmp.path(), &files);
+    let plan = BuildPlan::new(res_dir, None, "debug");
+    bencher.bench("large/build_100k", 100_000, || {
+        black_box(build_with_plan(&plan).unwrap());
+    });
+}
+
+fn bench_fixture(bencher: &mut Bencher, fixture: &Fixture) {
+    let n = fixture.resources;
+    let raw = [raw_file(Path::new("values.xml"), &fixture.xml)];
+    bencher.bench(&format!("parse/{}", fixture.name), n, || {
+        black_box(parsing::parse_raw_files(&raw));
+    });
+
+    let tmp = tempfile::tempdir().unwrap();
+    let res_dir = support::write_res_dir(
+        tmp.path(),
+        &[("values.xml", fixture.xml.clone())],
+    );
+    let plan = BuildPlan::new(res_dir, None, "debug");
+    bencher.bench(&format!("load/{}", fixture.name), n, || {
+        black_box(input::load_resources(&plan).unwrap());
+    });
+
+    let (parsed, _) = parsing::parse_raw_files(&raw);
+    bencher.bench(&format!("graph/{}", fixture.name), n, || {
+        black_box(ResourceGraphBuilder::from_parsed_files_for_profile(
+            &parsed, "debug",
+        ));
+    });
+
+    let graph = graph(&raw);
+    bencher.bench(&format!("validate/{}", fixture.name), n, || {
+        black_box(analysis::validate(&graph));
+    });
+
+    bencher.bench(&format!("generate/{}", fixture.name), n, || {
+        black_box(generate(&graph));
+    });
+}
+
+fn raw_file(path: &Path, xml: &str) -> RawResourceFile {
+    RawResourceFile {
+        path: PathBuf::from(path),
+        contents: xml.to_string(),
+        is_test: false,
+        locale: None,
+    }
+}
+
+fn graph(raw: &[RawResourceFile]) -> ResourceGraph {
+    let (parsed, _) = parsing::parse_raw_files(raw);
+    ResourceGraphBuilder::from_parsed_files_for_profile(
+        &parsed, "debug",
+    )
+}
+
+fn generate(graph: &ResourceGraph) -> String {
+    let options = EmitOptions {
+        profile: "debug",
+        lookup: true,
+        provenance_root: None,
+        typed_modules: true,
+        raw_assets: &[],
+        hot_reload: false,
+        registries: false,
+        json: false,
+        regex: false,
+        flatten_aliases: None,
+        reference_style: ReferenceStyle::Alias,
+        testable: false,
+        source_files: &[],
+        generated_at: 0,
+        ordering: Ordering::Alphabetical,
+        lazy: LazyStatics::LazyLock,
+    };
+    generation::emit(graph, &[], &options).rust
+}
+
+/// Renames the strings of a generated file so files do not clash
+fn prefixed(file: usize, xml: &str) -> String {
+    xml.replace("name=\"s_", &format!("name=\"f{file}_"))
+}
+
+struct Bencher {
+    filter: Option<String>,
+}
+
+impl Bencher {
+    fn bench(
+        &mut self,
+        name: &str,
+        resources: usize,
+        mut f: impl FnMut(),
+    ) {
+        if self
+            .filter
+            .as_ref()
+            .is_some_and(|filter| !name.contains(filter))
+        {
+            return;
+        }
+        let before = ALLOCATIONS.load(AtomicOrdering::Relaxed);
+        f();
+        let allocations =
+            ALLOCATIONS.load(AtomicOrdering::Relaxed) - before;
+        let mut samples = Vec::new();
+        let started = Instant::now();
+        while samples.len() < MAX_SAMPLES
+            && (samples.len() < 3 || started.elapsed() < TARGET)
+        {
+            let start = Instant::now();
+            f();
+            samples.push(start.elapsed());
+        }
+        samples.sort();
+        let median = samples[samples.len() / 2];
+        println!(
+            "{name:<28} min {:>10.3?}  median {:>10.3?}  max {:>10.3?}  \
+             {:>12.0} resources/s {allocations:>10} allocs ({} samples)",
+            samples[0],
+            median,
+            samples[samples.len() - 1],
+            resources as f64 / median.as_secs_f64(),
+            samples.len(),
+        );
+    }
+}
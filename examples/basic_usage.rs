@@ -6,6 +6,12 @@ fn main() {
     println!("=== R Resources Demo ===\n");
 
     println!("  App Name: {}", r::APP_NAME);
+    println!("  About: {}", r::ABOUT);
+    println!(
+        "  Typed access: {} / {}",
+        string::APP_NAME,
+        int::MAX_RETRIES
+    );
     println!("  Max Retries: {}", r::MAX_RETRIES);
     println!("  Timeout: {}ms", r::TIMEOUT_MS);
     println!("  Rate: {}", r::RATE);
@@ -14,9 +20,20 @@ fn main() {
 
     println!("  Auth Title: {}", r::auth::TITLE);
     println!("  Auth Error Message: {}", r::auth::error::CREDENTIALS);
+    println!("  Non-ASCII: {} / {}", r::i18n::CAFE, r::i18n::WELCOME_JA);
+    println!(
+        "  Paris to London: {:.0} km, bearing {:.0}°",
+        r::map::PARIS.haversine_distance_m(&r::map::LONDON) / 1000.0,
+        r::map::PARIS.initial_bearing_deg(&r::map::LONDON)
+    );
+    println!("  Install Dir: {}", r::INSTALL_DIR);
+    println!("  Version Pattern: {}", r::VERSION_PATTERN);
     println!("  Big Number: {:?}", r::BIG_NUMBER.to_string());
     println!("  Auto Big Number: {:?}", r::AUTO_BIG_NUMBER.to_string());
     println!("  Auto Big Decimal: {:?}", r::AUTO_BIG_DECIMAL.to_string());
 
     println!("  Welcome Message: {}", r::welcome_message("John", r_resources::BigDecimal::from_str("10").unwrap()));
+
+    println!("  Lookup auth/title: {:?}", r::lookup::string("auth/title"));
+    println!("  Key auth/title: {:?}", r::StringKey::from_name("auth/title").map(|key| key.as_str()));
 }
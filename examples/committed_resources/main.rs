@@ -0,0 +1,32 @@
+//! Resources generated ahead of time with `r-res`, for crates that
+//! cannot run a build script.
+//!
+//! `resources.rs` is committed next to this file, and regenerated
+//! whenever `res/` changes:
+//!
+//! ```bash
+//! cargo run --features cli --bin r-res -- generate \
+//!     --res-dir examples/committed_resources/res \
+//!     --out examples/committed_resources/resources.rs --module res
+//! ```
+//!
+//! CI runs the same command with `--check`, which fails when the
+//! committed file is stale.
+
+// `pub` like an included `r`, so the typed re-exports are not unused
+// imports; `res` adds a module level
+#[allow(clippy::excessive_nesting)]
+pub mod resources;
+
+use resources::res::r;
+
+fn main() {
+    println!("=== Committed Resources Demo ===\n");
+    println!("App: {}", r::APP_NAME);
+    println!("Workers: {}", r::WORKERS);
+    println!("Accent: {}", r::ui::ACCENT);
+    println!(
+        "Looked up at runtime: {:?}",
+        r::lookup::try_number_i64("workers")
+    );
+}
@@ -2,18 +2,76 @@
 //!
 //! This module performs validations on the resource graph, including:
 //! - Duplicate detection (with configurable warnings/errors)
+//! - Values rejected by their type (`-1` for a `u32`, `1e999`)
+//! - Profile attribute validation and exhaustiveness across profiles
+//! - Locale coverage (translations without a default, missing translations)
+//! - Key enum variant collisions
+//! - Collisions with items generated at the root of `r::`
+//! - Namespaces named after generated modules, primitive types or
+//!   `crate`/`self`/`super`
+//! - Names renamed because they start with a digit
+//! - Resources no source file refers to (opt-in, see [`unused`])
 //! - Reference resolution (future)
 //! - Interpolation analysis (future)
 //!
 //! All validations return structured `AnalysisResult` with separate warnings and errors.
+//! Each one carries the file and line it comes from when known, for the
+//! report of [`crate::generator::diag`].
 
-use crate::generator::ir::{ResourceGraph, ResourceKey};
+pub mod unused;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::generator::diag::{Diagnostic, Location};
+use crate::generator::generation;
+use crate::generator::parsing::ParserError;
+
+use crate::generator::ir::profile::{self, ProfileSpec};
+use crate::generator::utils::{
+    camel_case_identifier, const_identifier, sanitize_identifier,
+    starts_with_digit, GENERATED_ROOT_CONSTS, GENERATED_ROOT_TYPES,
+    GENERATED_TOP_MODULES, PATH_KEYWORDS, PRIMITIVE_TYPES,
+};
+use crate::generator::ir::types::{
+    pointer_width_error, DEFAULT_POINTER_WIDTH, POINTER_WIDTHS,
+};
+use crate::generator::ir::{
+    Pattern, PatternError, ProfileVariant, ResourceGraph, ResourceKey,
+    ResourceKind, ResourceNode, ResourceValue,
+};
+
+/// Cargo profiles always checked for exhaustiveness
+const CARGO_PROFILES: [&str; 2] = ["debug", "release"];
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Fields are used in Display/Error implementations
 pub struct AnalysisError {
     pub message: String,
     pub key: Option<ResourceKey>,
+    pub kind: AnalysisErrorKind,
+    /// Where the error is, shown before the message
+    pub location: Option<Location>,
+}
+
+/// What an [`AnalysisError`] reports, for callers converting it into
+/// their own error type
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AnalysisErrorKind {
+    #[default]
+    Other,
+    /// A value rejected by its type, at `file:line`, and why
+    InvalidValue {
+        file: PathBuf,
+        line: Option<usize>,
+        reason: String,
+    },
+    /// An `@string/` path that names no string
+    UnresolvedReference(String),
+    /// A key defined more than once
+    Duplicate,
+    /// Malformed XML, or an element the reader rejected
+    Parse,
 }
 
 impl AnalysisError {
@@ -24,14 +82,38 @@ impl AnalysisError {
         Self {
             message: message.into(),
             key,
+            kind: AnalysisErrorKind::Other,
+            location: None,
         }
     }
+
+    #[must_use]
+    pub fn with_kind(mut self, kind: AnalysisErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    #[must_use]
+    pub fn with_location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        highlight_name(
+            Diagnostic::error(self.message.clone())
+                .at(self.location.clone()),
+            self.key.as_ref(),
+        )
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct AnalysisWarning {
     pub message: String,
     pub key: Option<ResourceKey>,
+    /// Where the warning is, shown before the message
+    pub location: Option<Location>,
 }
 
 impl AnalysisWarning {
@@ -42,8 +124,49 @@ impl AnalysisWarning {
         Self {
             message: message.into(),
             key,
+            location: None,
         }
     }
+
+    #[must_use]
+    pub fn with_location(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        highlight_name(
+            Diagnostic::warning(self.message.clone())
+                .at(self.location.clone()),
+            self.key.as_ref(),
+        )
+    }
+}
+
+/// Points the caret of `diagnostic` at the `name` attribute of `key`
+fn highlight_name(
+    diagnostic: Diagnostic,
+    key: Option<&ResourceKey>,
+) -> Diagnostic {
+    match key {
+        Some(key) => {
+            diagnostic.with_highlight(format!("name=\"{}\"", key.name))
+        }
+        None => diagnostic,
+    }
+}
+
+impl From<ParserError> for AnalysisError {
+    fn from(error: ParserError) -> Self {
+        let ParserError::Xml {
+            path,
+            line,
+            message,
+        } = error;
+        Self::new(message, None)
+            .with_kind(AnalysisErrorKind::Parse)
+            .with_location(Some(Location::new(path, line)))
+    }
 }
 
 #[derive(Debug, Default)]
@@ -60,16 +183,55 @@ impl AnalysisResult {
 }
 
 /// Validation options
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ValidationOptions {
     /// If true, duplicate warnings become errors
     pub treat_duplicates_as_errors: bool,
+    /// If true, missing translations become errors
+    pub require_complete_translations: bool,
+    /// If true, reserved namespace names are warnings instead of errors
+    pub allow_reserved_names: bool,
+    /// Width `isize`/`usize` numbers must fit, 32 bits when unset
+    pub target_pointer_width: Option<u32>,
+    /// If true, strings starting with a malformed reference are errors
+    pub strict_references: bool,
+    /// If true, the names of `r::registry` are checked for collisions
+    pub registries: bool,
+    /// Separator of the root aliases, whose names are checked for
+    /// collisions when set
+    pub flatten_separator: Option<String>,
+    /// If true, the overridable getters are checked for collisions
+    pub testable: bool,
+    /// If true, overridable resources are reported as hot-reloaded
+    pub hot_reload: bool,
+    /// If true, `<regex>` statics are compiled by the `regex` crate,
+    /// which reads the syntax the build does not check
+    pub regex: bool,
 }
 
 /// Validates the resource graph and returns warnings and errors found.
 ///
 /// Currently checks:
 /// - Duplicates (same key defined multiple times) → warnings (or errors if option enabled)
+/// - Resources, namespaces, registry entries, root aliases or
+///   overridable getters generating the same identifier → errors
+/// - Empty or unbalanced `cfg` predicates, `cfg` variants of
+///   different types → errors; the first `cfg` → a warning that rustc
+///   checks the rest
+/// - Invalid `profile` attributes (e.g. `dev,!dev`) → errors
+/// - Keys defined for some profiles but not others → warnings
+/// - Translations without a default definition → warnings
+/// - Untranslated strings/templates, one report per locale → warnings (or errors)
+/// - `<doc>` elements with nothing to document → warnings
+/// - `@string/`/`@number/` references to deprecated resources, or to
+///   resources under a `cfg` the referring one does not share →
+///   warnings
+/// - `<regex>` syntax beyond the checked subset, without the `regex`
+///   feature → warnings
+/// - `isize`/`usize` numbers beyond the target pointer width → errors
+/// - Strings starting with a malformed reference (`@strig/x`) → warnings (or errors)
+/// - Test resources get the value, duplicate and profile checks of their
+///   own graph; shadowing a default resource is expected
 #[allow(dead_code)] // Reserved for future use
 pub fn validate(graph: &ResourceGraph) -> AnalysisResult {
     validate_with_options(graph, ValidationOptions::default())
@@ -82,8 +244,430 @@ pub fn validate_with_options(
 ) -> AnalysisResult {
     let mut result = AnalysisResult::default();
 
+    check_invalid_values(graph, &mut result);
+    check_duplicates(graph, &options, &mut result);
+    check_cfg(graph, &mut result);
+    check_profiles(graph, &mut result);
+
+    for (locale, translation) in graph.translations() {
+        check_invalid_values(translation, &mut result);
+        check_duplicates(translation, &options, &mut result);
+        check_cfg(translation, &mut result);
+        check_profiles(translation, &mut result);
+        check_malformed_references(
+            translation,
+            &options,
+            &mut result,
+        );
+        check_translation(graph, locale, translation, &mut result);
+        check_deprecated_references(graph, translation, &mut result);
+        check_cfg_references(graph, translation, &mut result);
+    }
+    result
+        .warnings
+        .extend(generation::check_localized_accessors(graph));
+    if let Some(tests) = graph.tests() {
+        check_invalid_values(tests, &mut result);
+        check_duplicates(tests, &options, &mut result);
+        check_cfg(tests, &mut result);
+        check_profiles(tests, &mut result);
+        check_malformed_references(tests, &options, &mut result);
+        check_digit_names(tests, &mut result);
+        check_reserved_names(tests, &options, &mut result);
+        check_deprecated_references(graph, tests, &mut result);
+        check_cfg_references(graph, tests, &mut result);
+    }
+    report_missing_translations(graph, &options, &mut result);
+    check_key_collisions(graph, &mut result);
+    check_generated_identifiers(graph, &options, &mut result);
+    check_generated_names(graph, &mut result);
+    check_reserved_names(graph, &options, &mut result);
+    check_digit_names(graph, &mut result);
+    check_unused_docs(graph, &mut result);
+    check_deprecated_references(graph, graph, &mut result);
+    check_cfg_references(graph, graph, &mut result);
+    check_unchecked_regexes(graph, &options, &mut result);
+    check_malformed_references(graph, &options, &mut result);
+    let bits = options
+        .target_pointer_width
+        .unwrap_or(DEFAULT_POINTER_WIDTH);
+    if POINTER_WIDTHS.contains(&bits) {
+        check_pointer_width(graph, bits, &mut result);
+        if let Some(tests) = graph.tests() {
+            check_pointer_width(tests, bits, &mut result);
+        }
+    } else {
+        result.errors.push(AnalysisError::new(
+            format!(
+                "target_pointer_width: {bits} is not 16, 32 or 64 bits"
+            ),
+            None,
+        ));
+    }
+    locate(graph, &mut result);
+
+    result
+}
+
+/// File and line of the definition of `key`, looked up in the default
+/// resources, then the test resources and the translations
+fn definition(graph: &ResourceGraph, key: &ResourceKey) -> Option<Location> {
+    let node = graph
+        .get(key)
+        .or_else(|| graph.tests().and_then(|tests| tests.get(key)))
+        .or_else(|| {
+            graph
+                .translations()
+                .values()
+                .find_map(|translation| translation.get(key))
+        })?;
+    let line = node.origin.line.map(|line| line as usize);
+    Some(Location::new(node.origin.file.to_path_buf(), line))
+}
+
+/// Points the errors and warnings about a resource at its definition
+fn locate(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    for error in &mut result.errors {
+        if let (None, Some(key)) = (&error.location, &error.key) {
+            error.location = definition(graph, key);
+        }
+    }
+    for warning in &mut result.warnings {
+        if let (None, Some(key)) = (&warning.location, &warning.key) {
+            warning.location = definition(graph, key);
+        }
+    }
+}
+
+/// Warns about the references of `own` (the default resources, a
+/// translation or the tests) to deprecated resources, its own or the
+/// default ones; deprecated resources may refer to each other
+fn check_deprecated_references(
+    graph: &ResourceGraph,
+    own: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (from, to) in own.references() {
+        let Some(referrer) = own.get(from) else { continue };
+        let target = own.get(to).or_else(|| graph.get(to));
+        let Some(note) =
+            target.and_then(|n| n.meta.deprecated.as_ref())
+        else {
+            continue;
+        };
+        if referrer.meta.deprecated.is_some() {
+            continue;
+        }
+        let line = referrer.origin.line.map(|line| line as usize);
+        let file = referrer.origin.file.to_path_buf();
+        result.warnings.push(
+            AnalysisWarning::new(
+                format!(
+                    "'{}' refers to the deprecated '{}': {note}",
+                    from.full_name(),
+                    to.full_name()
+                ),
+                Some(from.clone()),
+            )
+            .with_location(Some(Location::new(file, line))),
+        );
+    }
+}
+
+/// Warns about the references of `own` to resources under a `cfg`
+/// predicate the referring resource does not have: the value of the
+/// first definition is copied whatever the target
+fn check_cfg_references(
+    graph: &ResourceGraph,
+    own: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for (from, to) in own.references() {
+        let Some(referrer) = own.get(from) else { continue };
+        let target = own.get(to).or_else(|| graph.get(to));
+        let Some(predicate) = target.and_then(|n| n.meta.cfg.as_ref())
+        else {
+            continue;
+        };
+        if referrer.meta.cfg.as_ref() == Some(predicate) {
+            continue;
+        }
+        let line = referrer.origin.line.map(|line| line as usize);
+        let file = referrer.origin.file.to_path_buf();
+        result.warnings.push(
+            AnalysisWarning::new(
+                format!(
+                    "'{}' refers to '{}', defined under `cfg({predicate})`: its first definition is copied for every target",
+                    from.full_name(),
+                    to.full_name()
+                ),
+                Some(from.clone()),
+            )
+            .with_location(Some(Location::new(file, line))),
+        );
+    }
+}
+
+/// Warns about the `<regex>` using `regex` crate syntax the build
+/// cannot check (`\b`, `(?i)`), unless the `regex` crate compiles
+/// them
+fn check_unchecked_regexes(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    if options.regex {
+        return;
+    }
+    for (key, nodes) in graph.nodes() {
+        let Some(ResourceValue::Regex(source)) =
+            nodes.first().map(|node| &node.value)
+        else {
+            continue;
+        };
+        let parsed = Pattern::new(source);
+        let Err(PatternError::Unsupported(reason)) = parsed else {
+            continue;
+        };
+        result.warnings.push(AnalysisWarning::new(
+            format!(
+                "Regex '{}' is not checked at build time: {reason}",
+                key.full_name()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// Reports `<doc>` elements that were not followed by a resource or
+/// namespace
+fn check_unused_docs(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    for (file, line) in graph.unused_docs() {
+        result.warnings.push(
+            AnalysisWarning::new(
+                "`<doc>` is not followed by a resource or namespace and is ignored",
+                None,
+            )
+            .with_location(Some(Location::new(file.clone(), Some(*line)))),
+        );
+    }
+}
+
+/// Reports the strings starting with an `@` that did not read as a
+/// reference (warnings, or errors if enabled)
+fn check_malformed_references(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    for (key, reason) in graph.malformed_references() {
+        let message = format!(
+            "Suspicious value for '{}': {reason}",
+            key.full_name()
+        );
+        let location = graph.get(key).map(|node| {
+            let line = node.origin.line.map(|line| line as usize);
+            Location::new(node.origin.file.to_path_buf(), line)
+        });
+        if options.strict_references {
+            result.errors.push(
+                AnalysisError::new(message, Some(key.clone()))
+                    .with_location(location),
+            );
+        } else {
+            result.warnings.push(
+                AnalysisWarning::new(message, Some(key.clone()))
+                    .with_location(location),
+            );
+        }
+    }
+}
+
+/// Reports the `isize` and `usize` numbers, or bounds, that don't fit
+/// in a pointer of `bits` bits
+fn check_pointer_width(
+    graph: &ResourceGraph,
+    bits: u32,
+    result: &mut AnalysisResult,
+) {
     for (key, nodes) in graph.nodes() {
-        if nodes.len() > 1 {
+        for node in nodes {
+            let ResourceValue::Number(number) = &node.value else {
+                continue;
+            };
+            let bounds = node.meta.range.iter().flat_map(|range| {
+                [&range.min, &range.max].into_iter().flatten()
+            });
+            let Some(reason) = std::iter::once(number)
+                .chain(bounds)
+                .find_map(|number| pointer_width_error(number, bits))
+            else {
+                continue;
+            };
+            let line = node.origin.line.map(|line| line as usize);
+            result.errors.push(
+                AnalysisError::new(
+                    format!(
+                        "Invalid value for '{}': {reason}; set `BuildOptions::target_pointer_width` if the crate only builds for wider targets",
+                        key.full_name()
+                    ),
+                    Some(key.clone()),
+                )
+                .with_location(Some(Location::new(
+                    node.origin.file.to_path_buf(),
+                    line,
+                ))),
+            );
+        }
+    }
+}
+
+/// Reports definitions whose value their type rejected
+fn check_invalid_values(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for invalid in graph.invalid_resources() {
+        // References are checked on the graph, which knows the line
+        let location = match invalid.line {
+            Some(_) => None,
+            None => definition(graph, &invalid.key),
+        }
+        .unwrap_or_else(|| {
+            Location::new(invalid.file.clone(), invalid.line)
+        });
+        let kind = match &invalid.reference {
+            Some(reference) => AnalysisErrorKind::UnresolvedReference(
+                reference.clone(),
+            ),
+            None => AnalysisErrorKind::InvalidValue {
+                file: invalid.file.clone(),
+                line: invalid.line,
+                reason: invalid.message.clone(),
+            },
+        };
+        result.errors.push(
+            AnalysisError::new(
+                format!(
+                    "Invalid value for '{}': {}",
+                    invalid.key.full_name(),
+                    invalid.message
+                ),
+                Some(invalid.key.clone()),
+            )
+            .with_kind(kind)
+            .with_location(Some(location)),
+        );
+    }
+}
+
+/// Why the `cfg` predicate `predicate` can't be emitted verbatim, if
+/// so: it must be non-empty with balanced brackets outside of string
+/// literals
+fn cfg_syntax_error(predicate: &str) -> Option<String> {
+    if predicate.trim().is_empty() {
+        return Some("the `cfg` predicate is empty".into());
+    }
+    let mut open = Vec::new();
+    let mut chars = predicate.chars();
+    while let Some(c) = chars.next() {
+        let close = match c {
+            '"' => {
+                let mut escaped = false;
+                let closed = chars.by_ref().any(|c| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                });
+                if !closed {
+                    return Some(format!(
+                        "unterminated string in the `cfg` predicate '{predicate}'"
+                    ));
+                }
+                continue;
+            }
+            '(' | '[' | '{' => {
+                open.push(c);
+                continue;
+            }
+            ')' => '(',
+            ']' => '[',
+            '}' => '{',
+            _ => continue,
+        };
+        if open.pop() != Some(close) {
+            return Some(format!(
+                "unbalanced `{c}` in the `cfg` predicate '{predicate}'"
+            ));
+        }
+    }
+    open.last().map(|c| {
+        format!("unclosed `{c}` in the `cfg` predicate '{predicate}'")
+    })
+}
+
+/// Reports `cfg` predicates the generated code can't hold and `cfg`
+/// variants of different types, then warns once that rustc checks the
+/// predicates themselves
+fn check_cfg(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    let mut first = None;
+    for (key, nodes) in graph.nodes() {
+        for node in nodes {
+            let Some(predicate) = &node.meta.cfg else { continue };
+            first.get_or_insert(key);
+            let Some(reason) = cfg_syntax_error(predicate) else {
+                continue;
+            };
+            let line = node.origin.line.map(|line| line as usize);
+            result.errors.push(
+                AnalysisError::new(
+                    format!(
+                        "Invalid value for '{}': {reason}",
+                        key.full_name()
+                    ),
+                    Some(key.clone()),
+                )
+                .with_location(Some(Location::new(
+                    node.origin.file.to_path_buf(),
+                    line,
+                ))),
+            );
+        }
+        let mixed = graph.has_cfg_variants(key)
+            && nodes.windows(2).any(|pair| {
+                pair[0].kind != pair[1].kind
+                    || std::mem::discriminant(&pair[0].value)
+                        != std::mem::discriminant(&pair[1].value)
+            });
+        if mixed {
+            result.errors.push(AnalysisError::new(
+                format!(
+                    "The `cfg` variants of '{}' must have the same type",
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+    if let Some(key) = first {
+        result.warnings.push(AnalysisWarning::new(
+            format!(
+                "The `cfg` predicates (first on '{}') are only checked for balanced brackets; rustc checks the names and values they test",
+                key.full_name()
+            ),
+            Some(key.clone()),
+        ));
+    }
+}
+
+/// Reports keys defined more than once (warnings, or errors if enabled)
+fn check_duplicates(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    for (key, nodes) in graph.nodes() {
+        if graph.has_duplicates(key) {
             // Duplicate detected - list all files where it's defined
             let file_list: Vec<String> = nodes
                 .iter()
@@ -100,20 +684,390 @@ pub fn validate_with_options(
             );
 
             if options.treat_duplicates_as_errors {
-                result.errors.push(AnalysisError::new(
+                result.errors.push(
+                    AnalysisError::new(message, Some(key.clone()))
+                        .with_kind(AnalysisErrorKind::Duplicate),
+                );
+            } else {
+                result.warnings.push(AnalysisWarning::new(
                     message,
                     Some(key.clone()),
                 ));
+            }
+        }
+    }
+}
+
+/// Rejects root namespaces and resources that would clash with the
+/// modules, types and constants generated in `r::`
+fn check_generated_names(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut reported = BTreeSet::new();
+    for key in graph.nodes().keys() {
+        let (name, ident, generated) = match key.namespace.first() {
+            Some(ns) => (
+                ns.as_str(),
+                sanitize_identifier(ns),
+                &GENERATED_ROOT_TYPES[..],
+            ),
+            None => (
+                &*key.name,
+                const_identifier(&key.name),
+                &GENERATED_ROOT_CONSTS[..],
+            ),
+        };
+        if !generated.contains(&ident.as_str())
+            || !reported.insert(ident.clone())
+        {
+            continue;
+        }
+        let (what, (key, location)) = if key.namespace.is_empty() {
+            ("Resource", (key.clone(), None))
+        } else {
+            ("Namespace", namespace_site(graph, key, 0))
+        };
+        result.errors.push(
+            AnalysisError::new(
+                format!(
+                    "{what} '{name}' collides with the generated `r::{ident}`; please rename it"
+                ),
+                Some(key),
+            )
+            .with_location(location),
+        );
+    }
+}
+
+/// Key and location an error about the namespace of `key` at `depth`
+/// points at: its first `<ns>` element, named like a resource of the
+/// parent namespace, or `key` itself (located at its definition) for
+/// the namespace of a file name
+fn namespace_site(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    depth: usize,
+) -> (ResourceKey, Option<Location>) {
+    let path = &key.namespace[..=depth];
+    match graph.namespace_origin(path) {
+        Some((file, line)) => (
+            ResourceKey::new(path[..depth].to_vec(), &path[depth]),
+            Some(Location::new(file.to_path_buf(), Some(*line))),
+        ),
+        None => (key.clone(), None),
+    }
+}
+
+/// Why the namespace `ns` at `depth` (0 for a root namespace) cannot
+/// be used as a module name, if it cannot
+fn reserved_reason(ns: &str, depth: usize) -> Option<String> {
+    let ident = sanitize_identifier(ns);
+    if depth == 0 && GENERATED_TOP_MODULES.contains(&ident.as_str()) {
+        Some(format!("collides with the generated `{ident}` module"))
+    } else if PRIMITIVE_TYPES.contains(&ident.as_str()) {
+        Some(format!("shadows the primitive type `{ident}`"))
+    } else if PATH_KEYWORDS.contains(&ns) {
+        Some(format!("is the path keyword `{ns}`"))
+    } else {
+        None
+    }
+}
+
+/// Rejects namespaces whose module would shadow a generated module, a
+/// primitive type or a path keyword (warnings if allowed)
+fn check_reserved_names(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    let mut reported = BTreeSet::new();
+    for (key, nodes) in graph.nodes() {
+        for (depth, ns) in key.namespace.iter().enumerate() {
+            let path = key.namespace[..=depth].join("/");
+            let Some(reason) = reserved_reason(ns, depth) else {
+                continue;
+            };
+            if !reported.insert(path.clone()) {
+                continue;
+            }
+            let (key, location) = namespace_site(graph, key, depth);
+            let file = location
+                .as_ref()
+                .map(|location| location.file.clone())
+                .or_else(|| {
+                    let node = nodes.first()?;
+                    Some(node.origin.file.to_path_buf())
+                })
+                .unwrap_or_default();
+            let message = format!(
+                "Namespace '{path}' in {} {reason}; please rename it (or set `BuildOptions::allow_reserved_names`)",
+                file.display()
+            );
+            if options.allow_reserved_names {
+                result.warnings.push(
+                    AnalysisWarning::new(message, Some(key))
+                        .with_location(location),
+                );
             } else {
-                result.warnings.push(AnalysisWarning::new(
-                    message,
+                result.errors.push(
+                    AnalysisError::new(message, Some(key))
+                        .with_location(location),
+                );
+            }
+        }
+    }
+}
+
+/// Lists names starting with a digit, which are generated with a `_`
+/// prefix (`2fa_hint` → `_2FA_HINT`)
+fn check_digit_names(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    let mut renamed = BTreeSet::new();
+    let mut first_key = None;
+    for key in graph.nodes().keys() {
+        for ns in key.namespace.iter().filter(|ns| starts_with_digit(ns)) {
+            renamed.insert(format!(
+                "namespace '{ns}' → `{}`",
+                sanitize_identifier(ns)
+            ));
+        }
+        if starts_with_digit(&key.name) {
+            renamed.insert(format!(
+                "'{}' → `{}`",
+                key.full_name(),
+                const_identifier(&key.name)
+            ));
+        }
+        if !renamed.is_empty() {
+            first_key.get_or_insert_with(|| key.clone());
+        }
+    }
+
+    if renamed.is_empty() {
+        return;
+    }
+    let renamed: Vec<String> = renamed.into_iter().collect();
+    result.warnings.push(AnalysisWarning::new(
+        format!(
+            "Names starting with a digit are prefixed with `_`: {}",
+            renamed.join(", ")
+        ),
+        first_key,
+    ));
+}
+
+/// Reports the resources and namespaces whose generated items would
+/// share a name in one module, before any code is written
+fn check_generated_identifiers(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    use generation::{
+        check_flat_aliases, check_identifier_collisions,
+        check_overridable, check_registry_names,
+    };
+    let errors = &mut result.errors;
+    errors.extend(check_identifier_collisions(graph));
+    if options.registries {
+        errors.extend(check_registry_names(graph));
+    }
+    if let Some(separator) = &options.flatten_separator {
+        errors.extend(check_flat_aliases(graph, separator));
+    }
+    if options.testable {
+        errors.extend(check_overridable(graph, options.hot_reload));
+    }
+}
+
+/// Warns when names of one type flatten to the same key enum variant
+/// (`auth/title_x` and `auth_title/x` are both `AuthTitleX`)
+fn check_key_collisions(
+    graph: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    let mut variants: BTreeMap<(String, String), Vec<&ResourceKey>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        // Templates share the string enum
+        let category = match &node.kind {
+            ResourceKind::Template => format!("{:?}", ResourceKind::String),
+            kind => format!("{kind:?}"),
+        };
+        let variant = camel_case_identifier(&key.full_name());
+        variants.entry((category, variant)).or_default().push(key);
+    }
+
+    for ((_, variant), keys) in variants {
+        if keys.len() < 2 {
+            continue;
+        }
+        let names: Vec<String> =
+            keys.iter().map(|key| format!("'{}'", key.full_name())).collect();
+        result.warnings.push(AnalysisWarning::new(
+            format!(
+                "Resources {} map to the same key enum variant '{variant}'; numeric suffixes are added",
+                names.join(", ")
+            ),
+            Some(keys[1].clone()),
+        ));
+    }
+}
+
+/// Warns about translated keys that have no default definition
+fn check_translation(
+    base: &ResourceGraph,
+    locale: &str,
+    translation: &ResourceGraph,
+    result: &mut AnalysisResult,
+) {
+    for key in translation.nodes().keys() {
+        if base.get(key).is_none() {
+            result.warnings.push(AnalysisWarning::new(
+                format!(
+                    "Resource '{}' is translated for locale '{locale}' but has no default definition",
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+}
+
+/// Base strings and templates without a translation, grouped by locale.
+///
+/// Keys marked `translatable="false"` are ignored.
+pub fn missing_translations(
+    graph: &ResourceGraph,
+) -> BTreeMap<String, Vec<ResourceKey>> {
+    let mut missing = BTreeMap::new();
+    for (locale, translation) in graph.translations() {
+        let keys: Vec<ResourceKey> = graph
+            .nodes()
+            .iter()
+            .filter(|(key, nodes)| {
+                is_translatable(graph, key, nodes)
+                    && translation.get(key).is_none()
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        if !keys.is_empty() {
+            missing.insert(locale.clone(), keys);
+        }
+    }
+    missing
+}
+
+fn is_translatable(
+    graph: &ResourceGraph,
+    key: &ResourceKey,
+    nodes: &[ResourceNode],
+) -> bool {
+    graph.is_translatable(key)
+        && nodes.first().is_some_and(|n| {
+            matches!(n.kind, ResourceKind::String | ResourceKind::Template)
+        })
+}
+
+/// One warning (or error in strict mode) per locale listing missing keys
+fn report_missing_translations(
+    graph: &ResourceGraph,
+    options: &ValidationOptions,
+    result: &mut AnalysisResult,
+) {
+    for (locale, keys) in missing_translations(graph) {
+        let names: Vec<String> =
+            keys.iter().map(ResourceKey::full_name).collect();
+        let message = format!(
+            "Locale '{locale}' is missing {} translation(s): {}",
+            keys.len(),
+            names.join(", ")
+        );
+        if options.require_complete_translations {
+            result.errors.push(AnalysisError::new(message, None));
+        } else {
+            result.warnings.push(AnalysisWarning::new(message, None));
+        }
+    }
+}
+
+/// Validates `profile` attributes and warns about keys missing in some profiles
+fn check_profiles(graph: &ResourceGraph, result: &mut AnalysisResult) {
+    let mut known: BTreeSet<String> =
+        CARGO_PROFILES.iter().map(|p| p.to_string()).collect();
+    let mut invalid_keys = BTreeSet::new();
+
+    for (key, variants) in graph.profile_variants() {
+        for variant in variants {
+            if let Err(error) = collect_profile_names(variant, &mut known)
+            {
+                result.errors.push(AnalysisError::new(
+                    format!(
+                        "Invalid profile for resource '{}' in {}: {error}",
+                        key.full_name(),
+                        variant.file.display()
+                    ),
                     Some(key.clone()),
                 ));
+                invalid_keys.insert(key);
             }
         }
     }
 
-    result
+    for (key, variants) in graph.profile_variants() {
+        // `!release` leaves the key out of a profile on purpose
+        if invalid_keys.contains(key)
+            || variants.iter().all(|v| v.specs.is_empty())
+            || variants.iter().any(is_negated)
+        {
+            continue;
+        }
+        let missing: Vec<&str> = known
+            .iter()
+            .map(String::as_str)
+            .filter(|p| {
+                !variants.iter().any(|v| profile::is_active(&v.specs, p))
+            })
+            .collect();
+        if missing.is_empty() {
+            continue;
+        }
+        let first = &variants[0];
+        let location =
+            Location::new(first.file.to_path_buf(), first.line);
+        result.warnings.push(
+            AnalysisWarning::new(
+                format!(
+                    "Resource '{}' is not defined for profile(s): {}. Add a profile=\"default\" fallback or a definition for each profile",
+                    key.full_name(),
+                    missing.join(", ")
+                ),
+                Some(key.clone()),
+            )
+            .with_location(Some(location)),
+        );
+    }
+}
+
+/// Whether `variant` excludes a profile with `!`
+fn is_negated(variant: &ProfileVariant) -> bool {
+    variant.specs.iter().any(|raw| {
+        ProfileSpec::parse(raw)
+            .is_ok_and(|spec| !spec.exclude.is_empty())
+    })
+}
+
+/// Adds the profile names mentioned by `variant` to `known`
+fn collect_profile_names(
+    variant: &ProfileVariant,
+    known: &mut BTreeSet<String>,
+) -> Result<(), String> {
+    for raw in &variant.specs {
+        let spec = ProfileSpec::parse(raw)?;
+        known.extend(spec.names().map(str::to_string));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -138,6 +1092,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("First".to_string()),
+                meta: Default::default(),
             }],
         );
         let parsed2 = ParsedResourceFile::new(
@@ -147,6 +1102,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Second".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -187,6 +1143,7 @@ mod tests {
                     name: "title".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Hello".to_string()),
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "count".to_string(),
@@ -195,6 +1152,7 @@ mod tests {
                         value: "42".to_string(),
                         explicit_type: None,
                     },
+                    meta: Default::default(),
                 },
             ],
         );
@@ -206,4 +1164,488 @@ mod tests {
         assert!(result.warnings.is_empty());
         assert!(result.errors.is_empty());
     }
+
+    fn profiled(name: &str, value: &str, profile: &str) -> ParsedResource {
+        let mut resource = ParsedResource::string(name, value);
+        resource.meta.profiles = vec![profile.to_string()];
+        resource
+    }
+
+    #[test]
+    fn rejects_conflicting_profiles() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![profiled("title", "x", "dev,!dev")],
+        );
+        let graph =
+            ResourceGraphBuilder::from_parsed_files_for_profile(
+                &[parsed], "dev",
+            );
+        let result = validate(&graph);
+
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("title"));
+        assert!(result.errors[0]
+            .message
+            .contains("both included and excluded"));
+    }
+
+    #[test]
+    fn warns_when_key_missing_for_some_profiles() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                profiled("api_url", "local", "debug"),
+                profiled("api_url", "staging", "staging"),
+            ],
+        );
+        let graph =
+            ResourceGraphBuilder::from_parsed_files_for_profile(
+                &[parsed], "debug",
+            );
+        let result = validate(&graph);
+
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("api_url"));
+        assert!(result.warnings[0].message.contains("release"));
+        assert!(!result.warnings[0].message.contains("staging"));
+    }
+
+    #[test]
+    fn profile_warning_points_at_the_first_definition() {
+        let mut local = profiled("api_url", "local", "debug");
+        local.meta.line = Some(4);
+        let mut staging = profiled("api_url", "staging", "staging");
+        staging.meta.line = Some(5);
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![local, staging],
+        );
+        // Neither definition is selected for `release`
+        let graph =
+            ResourceGraphBuilder::from_parsed_files_for_profile(
+                &[parsed], "release",
+            );
+        let result = validate(&graph);
+
+        assert_eq!(result.warnings.len(), 1);
+        let location = result.warnings[0].location.as_ref().unwrap();
+        assert_eq!(location.file, PathBuf::from("values.xml"));
+        assert_eq!(location.line, Some(4));
+    }
+
+    #[test]
+    fn no_profile_warning_with_default_or_negation() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                profiled("api_url", "local", "default"),
+                profiled("api_url", "prod", "release"),
+                profiled("log_level", "verbose", "!release"),
+                profiled("log_level", "quiet", "release"),
+                profiled("debug_panel", "on", "!release"),
+            ],
+        );
+        let graph =
+            ResourceGraphBuilder::from_parsed_files_for_profile(
+                &[parsed], "release",
+            );
+        let result = validate(&graph);
+
+        assert!(result.is_empty());
+    }
+
+    fn locale_file(
+        locale: &str,
+        resources: Vec<ParsedResource>,
+    ) -> ParsedResourceFile {
+        ParsedResourceFile::new(
+            PathBuf::from(format!("values-{locale}.xml")),
+            false,
+            resources,
+        )
+        .with_locale(Some(locale.to_string()))
+    }
+
+    fn template(name: &str, text: &str) -> ParsedResource {
+        ParsedResource {
+            name: name.to_string(),
+            kind: ParsedKind::Template,
+            value: ScalarValue::Template {
+                text: text.to_string(),
+                params: Vec::new(),
+            },
+            meta: Default::default(),
+        }
+    }
+
+    fn translation_fixture() -> ResourceGraph {
+        let mut brand = ParsedResource::string("brand", "Acme");
+        brand.meta.translatable = false;
+        let base = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("greeting", "Hello"),
+                ParsedResource::string("farewell", "Bye"),
+                template("welcome", "Welcome %1$s"),
+                ParsedResource::bool("enabled", true),
+                brand,
+            ],
+        );
+        let fr = locale_file(
+            "fr",
+            vec![ParsedResource::string("greeting", "Bonjour")],
+        );
+        let de = locale_file(
+            "de",
+            vec![
+                ParsedResource::string("greeting", "Hallo"),
+                ParsedResource::string("farewell", "Tschüss"),
+                template("welcome", "Willkommen %1$s"),
+            ],
+        );
+        ResourceGraphBuilder::from_parsed_files(&[base, fr, de])
+    }
+
+    #[test]
+    fn translations_of_another_shape_are_reported() {
+        let base = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("title", "Home"),
+                template("welcome", "Welcome %1$s"),
+            ],
+        );
+        let pt = locale_file(
+            "pt-BR",
+            vec![
+                template("title", "Início de %1$s"),
+                template("welcome", "Bem-vindo %1$s, %2$s"),
+            ],
+        );
+        let graph =
+            ResourceGraphBuilder::from_parsed_files(&[base, pt]);
+        let result = validate(&graph);
+
+        let warnings: Vec<&AnalysisWarning> = result
+            .warnings
+            .iter()
+            .filter(|w| w.message.contains("`r::localized::`"))
+            .collect();
+        let messages: Vec<&str> =
+            warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Translation of 'title' for locale 'pt-BR' is generated as a function of `(arg1: &str)`, but the default is a constant; `r::localized::` returns the default text for this locale",
+                "Translation of 'welcome' for locale 'pt-BR' is generated as a function of `(arg1: &str, arg2: &str)`, but the default is a function of `(arg1: &str)`; `r::localized::` returns the default text for this locale",
+            ]
+        );
+        let location = warnings[0].location.as_ref().unwrap();
+        assert_eq!(location.file, PathBuf::from("values-pt-BR.xml"));
+    }
+
+    #[test]
+    fn missing_translations_grouped_by_locale() {
+        let graph = translation_fixture();
+        let missing = missing_translations(&graph);
+
+        assert_eq!(missing.len(), 1);
+        let names: Vec<String> =
+            missing["fr"].iter().map(ResourceKey::full_name).collect();
+        // Booleans and `translatable="false"` are not reported
+        assert_eq!(names, vec!["farewell", "welcome"]);
+
+        let result = validate(&graph);
+        assert!(result.errors.is_empty());
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0]
+            .message
+            .contains("Locale 'fr' is missing 2 translation(s)"));
+    }
+
+    #[test]
+    fn strict_mode_turns_missing_translations_into_errors() {
+        let graph = translation_fixture();
+        let options = ValidationOptions {
+            require_complete_translations: true,
+            ..Default::default()
+        };
+        let result = validate_with_options(&graph, options);
+
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("farewell, welcome"));
+    }
+
+    #[test]
+    fn warns_about_key_enum_collisions() {
+        let resource = |name: &str, kind, value| ParsedResource {
+            name: name.to_string(),
+            kind,
+            value,
+            meta: Default::default(),
+        };
+        let text = |s: &str| ScalarValue::Text(s.to_string());
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                resource("auth/title_x", ParsedKind::String, text("a")),
+                resource("auth_title/x", ParsedKind::String, text("b")),
+                // Different type: a separate enum
+                resource(
+                    "auth_title_x",
+                    ParsedKind::Number,
+                    ScalarValue::Number {
+                        value: "1".to_string(),
+                        explicit_type: None,
+                    },
+                ),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let result = validate(&graph);
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert_eq!(
+            result.warnings[0].message,
+            "Resources 'auth/title_x', 'auth_title/x' map to the same key enum variant 'AuthTitleX'; numeric suffixes are added"
+        );
+    }
+
+    #[test]
+    fn rejects_names_of_generated_items() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("lookup/title", "a"),
+                ParsedResource::string("lookup/body", "b"),
+                ParsedResource::string("Locale/name", "c"),
+                ParsedResource::string("build-profile", "d"),
+                ParsedResource::string("meta/version", "g"),
+                // Nested or differently cased: fine
+                ParsedResource::string("ui/lookup/title", "e"),
+                ParsedResource::string("Lookup/title", "f"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let messages: Vec<String> = validate(&graph)
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Resource 'build-profile' collides with the generated `r::BUILD_PROFILE`; please rename it",
+                "Namespace 'Locale' collides with the generated `r::Locale`; please rename it",
+                "Namespace 'lookup' collides with the generated `r::lookup`; please rename it",
+                "Namespace 'meta' collides with the generated `r::meta`; please rename it",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_reserved_namespace_names() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("string/title", "a"),
+                ParsedResource::string("string/body", "b"),
+                ParsedResource::string("ui/u32/max", "c"),
+                ParsedResource::string("ui/self/name", "d"),
+                // Only root namespaces can be confused with a module
+                ParsedResource::string("ui/color/accent", "e"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let messages: Vec<String> = validate(&graph)
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Namespace 'string' in values.xml collides with the generated `string` module; please rename it (or set `BuildOptions::allow_reserved_names`)",
+                "Namespace 'ui/self' in values.xml is the path keyword `self`; please rename it (or set `BuildOptions::allow_reserved_names`)",
+                "Namespace 'ui/u32' in values.xml shadows the primitive type `u32`; please rename it (or set `BuildOptions::allow_reserved_names`)",
+            ]
+        );
+
+        let allowed = validate_with_options(
+            &graph,
+            ValidationOptions {
+                allow_reserved_names: true,
+                ..ValidationOptions::default()
+            },
+        );
+        assert!(allowed.errors.is_empty());
+        assert_eq!(allowed.warnings.len(), 3);
+    }
+
+    #[test]
+    fn reserved_namespaces_point_at_their_ns_element() {
+        let mut file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![ParsedResource::string("ui/u32/max", "c")],
+        );
+        file.namespace_lines = vec![
+            (vec!["ui".to_string()], 2),
+            (vec!["ui".to_string(), "u32".to_string()], 3),
+        ];
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let result = validate(&graph);
+        let diagnostic = result.errors[0].to_diagnostic();
+        assert_eq!(
+            diagnostic.location.map(|location| location.to_string()),
+            Some("values.xml:3".to_string())
+        );
+        assert_eq!(diagnostic.highlight.as_deref(), Some("name=\"u32\""));
+    }
+
+    #[test]
+    fn lists_names_starting_with_digits() {
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("2fa_hint", "a"),
+                ParsedResource::string("404", "b"),
+                ParsedResource::string("3d/title", "c"),
+                ParsedResource::string("3d/body", "d"),
+                ParsedResource::string("v2", "e"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let result = validate(&graph);
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        let messages: Vec<&str> =
+            result.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Names starting with a digit are prefixed with `_`: '2fa_hint' → `_2FA_HINT`, '404' → `_404`, namespace '3d' → `_3d`"
+            ]
+        );
+    }
+
+    #[test]
+    fn cfg_predicates_need_balanced_brackets() {
+        assert_eq!(
+            cfg_syntax_error(r#"all(unix, not(target_env = "gnu"))"#),
+            None
+        );
+        assert_eq!(cfg_syntax_error(r#"feature = "a)b""#), None);
+        assert_eq!(
+            cfg_syntax_error("  ").as_deref(),
+            Some("the `cfg` predicate is empty")
+        );
+        assert_eq!(
+            cfg_syntax_error("unix)] fn f() {} #[cfg(unix")
+                .as_deref(),
+            Some("unbalanced `)` in the `cfg` predicate 'unix)] fn f() {} #[cfg(unix'")
+        );
+        assert_eq!(
+            cfg_syntax_error("any(unix, windows").as_deref(),
+            Some("unclosed `(` in the `cfg` predicate 'any(unix, windows'")
+        );
+        assert_eq!(
+            cfg_syntax_error(r#"feature = "a"#).as_deref(),
+            Some(r#"unterminated string in the `cfg` predicate 'feature = "a'"#)
+        );
+    }
+
+    #[test]
+    fn cfg_variants_are_not_duplicates() {
+        let resource = |name: &str, value: &str, cfg: &str| {
+            let mut resource = ParsedResource::string(name, value);
+            resource.meta.cfg = Some(cfg.to_string());
+            resource
+        };
+        let mut number = ParsedResource::number("driver", "3", None);
+        number.meta.cfg = Some("windows".to_string());
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                resource("driver", "alsa", "unix"),
+                resource("driver", "wasapi", "not(unix)"),
+                resource("backend", "x64", "any(unix"),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let result = validate(&graph);
+        let errors: Vec<&str> =
+            result.errors.iter().map(|e| &*e.message).collect();
+        assert_eq!(
+            errors,
+            ["Invalid value for 'backend': unclosed `(` in the `cfg` predicate 'any(unix'"]
+        );
+        let warnings: Vec<&str> =
+            result.warnings.iter().map(|w| &*w.message).collect();
+        assert_eq!(
+            warnings,
+            ["The `cfg` predicates (first on 'backend') are only checked for balanced brackets; rustc checks the names and values they test"]
+        );
+
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![resource("driver", "alsa", "unix"), number],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+        let result = validate(&graph);
+        assert_eq!(
+            result.errors[0].message,
+            "The `cfg` variants of 'driver' must have the same type"
+        );
+    }
+
+    #[test]
+    fn references_to_cfg_resources_need_the_same_predicate() {
+        let resource = |name: &str, value: &str, cfg: Option<&str>| {
+            let mut resource = ParsedResource::string(name, value);
+            resource.meta.cfg = cfg.map(str::to_string);
+            resource
+        };
+        let file = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                resource("driver", "alsa", Some("unix")),
+                resource("driver", "wasapi", Some("not(unix)")),
+                resource("label", "@string/driver audio", None),
+                resource("unix_label", "@string/driver", Some("unix")),
+            ],
+        );
+        let graph = ResourceGraphBuilder::from_parsed_files(&[file]);
+
+        let warnings: Vec<String> = validate(&graph)
+            .warnings
+            .into_iter()
+            .map(|w| w.message)
+            .filter(|message| message.contains("refers to"))
+            .collect();
+        assert_eq!(
+            warnings,
+            ["'label' refers to 'driver', defined under `cfg(unix)`: its first definition is copied for every target"]
+        );
+    }
 }
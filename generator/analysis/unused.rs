@@ -0,0 +1,325 @@
+//! Resources that no source file refers to.
+//!
+//! Sources are not parsed: the identifiers and string literals of every
+//! `.rs` file are collected, comments left out. A resource counts as
+//! used when its generated item (`TITLE`, or `welcome` for a template
+//! function) appears along with every module of its namespace (`auth`),
+//! when its key enum variant (`AuthTitle`) appears, or when a string
+//! literal holds its qualified name (`lookup::string("auth/title")`).
+//!
+//! Tokens ignore layout, so multi-line `use` lists and renames
+//! (`use r::auth::{TITLE as LOGIN_TITLE};`) count as uses. Resources
+//! only looked up by names built at runtime can be marked
+//! `keep="true"`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::generator::ir::types::template_signature;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceValue,
+};
+use crate::generator::utils::{
+    camel_case_identifier, const_identifier, sanitize_identifier,
+};
+
+use super::AnalysisWarning;
+
+/// Identifiers and string literals of a source tree
+#[derive(Debug, Default)]
+pub struct SourceTokens {
+    idents: HashSet<String>,
+    strings: HashSet<String>,
+}
+
+impl SourceTokens {
+    /// Tokens of every `.rs` file under `dir`
+    pub fn scan_dir(dir: &Path) -> io::Result<Self> {
+        let mut tokens = Self::default();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let (dirs, files): (Vec<PathBuf>, Vec<PathBuf>) =
+                std::fs::read_dir(&dir)?
+                    .map(|entry| entry.map(|e| e.path()))
+                    .collect::<io::Result<Vec<_>>>()?
+                    .into_iter()
+                    .partition(|path| path.is_dir());
+            pending.extend(dirs);
+            let sources = files
+                .iter()
+                .filter(|path| path.extension().is_some_and(|e| e == "rs"));
+            for path in sources {
+                tokens.scan(&std::fs::read_to_string(path)?);
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Adds the tokens of one source file
+    pub fn scan(&mut self, source: &str) {
+        let chars: Vec<char> = source.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            i = self.token(&chars, i);
+        }
+    }
+
+    /// Reads the token starting at `i`, returning the index after it
+    fn token(&mut self, chars: &[char], i: usize) -> usize {
+        let rest = &chars[i..];
+        match rest {
+            ['/', '/', ..] => skip_line(chars, i),
+            ['/', '*', ..] => skip_block_comment(chars, i + 2),
+            ['"', ..] => self.string(chars, i + 1),
+            ['b', '"', ..] => self.string(chars, i + 2),
+            ['r', '#' | '"', ..] | ['b', 'r', '#' | '"', ..] => {
+                self.raw_string_or_ident(chars, i)
+            }
+            ['\'', '\\', ..] => skip_char_literal(chars, i + 2),
+            ['\'', _, '\'', ..] => i + 3,
+            [c, ..] if is_ident_start(*c) => self.ident(chars, i),
+            _ => i + 1,
+        }
+    }
+
+    fn ident(&mut self, chars: &[char], start: usize) -> usize {
+        let end = start
+            + chars[start..]
+                .iter()
+                .take_while(|c| c.is_alphanumeric() || **c == '_')
+                .count();
+        self.idents.insert(chars[start..end].iter().collect());
+        end
+    }
+
+    /// `r#ident`, `r"..."` and `r#"..."#` (with an optional `b`)
+    fn raw_string_or_ident(
+        &mut self,
+        chars: &[char],
+        i: usize,
+    ) -> usize {
+        let r = if chars[i] == 'b' { i + 1 } else { i };
+        let hashes =
+            chars[r + 1..].iter().take_while(|c| **c == '#').count();
+        let quote = r + 1 + hashes;
+        if chars.get(quote) != Some(&'"') {
+            // Raw identifier: `r#type` is recorded as `type`
+            return match chars.get(quote) {
+                Some(c) if hashes == 1 && is_ident_start(*c) => {
+                    self.ident(chars, quote)
+                }
+                _ => self.ident(chars, i),
+            };
+        }
+        let closing: Vec<char> = std::iter::once('"')
+            .chain((0..hashes).map(|_| '#'))
+            .collect();
+        let start = quote + 1;
+        let end = (start..chars.len())
+            .find(|&j| chars[j..].starts_with(&closing))
+            .unwrap_or(chars.len());
+        self.strings.insert(chars[start..end].iter().collect());
+        (end + closing.len()).min(chars.len())
+    }
+
+    /// String literal whose content starts at `start`; escapes are kept
+    /// as written, which is enough to match resource names
+    fn string(&mut self, chars: &[char], start: usize) -> usize {
+        let mut j = start;
+        while j < chars.len() && chars[j] != '"' {
+            j += if chars[j] == '\\' { 2 } else { 1 };
+        }
+        let end = j.min(chars.len());
+        self.strings.insert(chars[start..end].iter().collect());
+        end + 1
+    }
+
+    /// Whether `key`, whose generated item is `item`, is referred to
+    fn uses(&self, key: &ResourceKey, item: &str) -> bool {
+        let module_used = |ns: &String| {
+            self.idents.contains(unraw(&sanitize_identifier(ns)))
+        };
+        (self.idents.contains(unraw(item))
+            && key.namespace.iter().all(module_used))
+            || self.idents.contains(unraw(&camel_case_identifier(
+                &key.full_name(),
+            )))
+            || self.strings.contains(&key.full_name())
+    }
+}
+
+/// Reports the resources of `graph` that `tokens` never mention, one
+/// warning per file; keys marked `keep="true"` are skipped
+pub fn check_unused(
+    graph: &ResourceGraph,
+    tokens: &SourceTokens,
+) -> Vec<AnalysisWarning> {
+    let mut unused: BTreeMap<&Path, Vec<String>> = BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        let item = match &node.value {
+            ResourceValue::Template { text, params }
+                if template_signature(text, params).is_some() =>
+            {
+                sanitize_identifier(&key.name)
+            }
+            _ => const_identifier(&key.name),
+        };
+        if graph.is_kept(key) || tokens.uses(key, &item) {
+            continue;
+        }
+        unused
+            .entry(&*node.origin.file)
+            .or_default()
+            .push(key.full_name());
+    }
+
+    unused
+        .into_iter()
+        .map(|(file, mut names)| {
+            names.sort();
+            AnalysisWarning::new(
+                format!(
+                    "{} unused resource(s) in {}: {} (mark them `keep=\"true\"` if they are looked up at runtime)",
+                    names.len(),
+                    file.display(),
+                    names.join(", ")
+                ),
+                None,
+            )
+        })
+        .collect()
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
+
+fn skip_line(chars: &[char], i: usize) -> usize {
+    (i..chars.len())
+        .find(|&j| chars[j] == '\n')
+        .unwrap_or(chars.len())
+}
+
+/// Skips a possibly nested `/* */` comment whose content starts at `i`
+fn skip_block_comment(chars: &[char], mut i: usize) -> usize {
+    let mut depth = 1;
+    while i < chars.len() && depth > 0 {
+        match &chars[i..] {
+            ['*', '/', ..] => depth -= 1,
+            ['/', '*', ..] => depth += 1,
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        i += 2;
+    }
+    i
+}
+
+/// Skips an escaped char literal (`'\n'`, `'\u{e9}'`) after its `\`
+fn skip_char_literal(chars: &[char], i: usize) -> usize {
+    (i + 1..chars.len())
+        .find(|&j| chars[j] == '\'')
+        .map_or(chars.len(), |j| j + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKind, ResourceNode, ResourceOrigin,
+    };
+
+    fn graph(names: &[(&str, &str)]) -> ResourceGraph {
+        let mut graph = ResourceGraph::default();
+        for (name, file) in names {
+            graph.insert(
+                ResourceKey::from_path(name),
+                ResourceNode::new(
+                    ResourceKind::String,
+                    ResourceValue::String("x".into()),
+                    ResourceOrigin::new(
+                        PathBuf::from(file),
+                        false,
+                    ),
+                ),
+            );
+        }
+        graph
+    }
+
+    fn unused(graph: &ResourceGraph, source: &str) -> Vec<String> {
+        let mut tokens = SourceTokens::default();
+        tokens.scan(source);
+        check_unused(graph, &tokens)
+            .into_iter()
+            .map(|w| w.message)
+            .collect()
+    }
+
+    #[test]
+    fn unused_resources_are_grouped_by_file() {
+        let graph = graph(&[
+            ("app_name", "values.xml"),
+            ("auth/title", "values.xml"),
+            ("auth/submit", "auth.xml"),
+            ("legacy", "values.xml"),
+        ]);
+        let messages = unused(
+            &graph,
+            "fn main() { println!(\"{}\", r::APP_NAME); }",
+        );
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].starts_with(
+            "1 unused resource(s) in auth.xml: auth/submit"
+        ));
+        assert!(messages[1].starts_with(
+            "2 unused resource(s) in values.xml: auth/title, legacy"
+        ));
+    }
+
+    #[test]
+    fn renames_enums_and_lookups_count_as_uses() {
+        let graph = graph(&[
+            ("auth/title", "values.xml"),
+            ("auth/submit", "values.xml"),
+            ("welcome", "values.xml"),
+        ]);
+        let source =
+            "use r::auth::{\n    TITLE as LOGIN_TITLE,\n};\n\
+                      let key = r::StringKey::AuthSubmit;\n\
+                      let text = r::lookup::string(\"welcome\");";
+        assert!(unused(&graph, source).is_empty());
+    }
+
+    #[test]
+    fn comments_and_other_namespaces_do_not_count() {
+        let graph = graph(&[("auth/title", "values.xml")]);
+        assert_eq!(
+            unused(&graph, "// r::auth::TITLE\n/* TITLE auth */")
+                .len(),
+            1
+        );
+        // `TITLE` of another namespace
+        assert_eq!(
+            unused(&graph, "let t = r::home::TITLE;").len(),
+            1
+        );
+        assert!(unused(&graph, "let t = r#\"x\"#; r::auth::TITLE")
+            .is_empty());
+    }
+
+    #[test]
+    fn kept_resources_are_not_reported() {
+        let mut graph = graph(&[("dynamic", "values.xml")]);
+        graph.mark_kept(ResourceKey::from_path("dynamic"));
+        assert!(unused(&graph, "").is_empty());
+    }
+}
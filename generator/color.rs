@@ -0,0 +1,141 @@
+//! Hex color parsing shared by validation and emission.
+//!
+//! Accepted forms, case-insensitive: `#RGB`, `#RRGGBB` and `#AARRGGBB`
+//! (alpha first, as on Android).
+
+/// Why a color literal was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorError {
+    /// The value does not start with `#`
+    MissingHash,
+    /// Number of hex digits other than 3, 6 or 8
+    InvalidLength(usize),
+    /// A character that is not a hex digit
+    InvalidDigit(char),
+}
+
+impl std::fmt::Display for ColorError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::MissingHash => write!(f, "colors must start with '#'"),
+            Self::InvalidLength(len) => write!(
+                f,
+                "expected 3, 6 or 8 hex digits (#RGB, #RRGGBB or #AARRGGBB), found {len}"
+            ),
+            Self::InvalidDigit(c) => {
+                write!(f, "'{c}' is not a hex digit")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ColorError {}
+
+/// Parses a hex color into `(r, g, b, a)`; `#RGB` and `#RRGGBB` are
+/// opaque
+pub fn parse_hex_color(
+    value: &str,
+) -> Result<(u8, u8, u8, u8), ColorError> {
+    let digits =
+        value.strip_prefix('#').ok_or(ColorError::MissingHash)?;
+    if let Some(c) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(ColorError::InvalidDigit(c));
+    }
+
+    let digits = match digits.len() {
+        3 => expand_shorthand(digits),
+        6 | 8 => digits.to_string(),
+        len => return Err(ColorError::InvalidLength(len)),
+    };
+    // Only ASCII hex digits are left, so every pair parses
+    let byte = |i: usize| {
+        u8::from_str_radix(&digits[i..i + 2], 16).unwrap_or_default()
+    };
+    Ok(if digits.len() == 8 {
+        (byte(2), byte(4), byte(6), byte(0))
+    } else {
+        (byte(0), byte(2), byte(4), 255)
+    })
+}
+
+/// Expands `#RGB` to `#RRGGBB`; other values are returned unchanged
+pub fn canonical_hex_color(value: &str) -> String {
+    match value.strip_prefix('#') {
+        Some(digits) if digits.len() == 3 => {
+            format!("#{}", expand_shorthand(digits))
+        }
+        _ => value.to_string(),
+    }
+}
+
+fn expand_shorthand(digits: &str) -> String {
+    digits.chars().flat_map(|c| [c, c]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_form() {
+        assert_eq!(
+            parse_hex_color("#FF5722"),
+            Ok((0xFF, 0x57, 0x22, 255))
+        );
+        assert_eq!(
+            parse_hex_color("#ff5722"),
+            Ok((0xFF, 0x57, 0x22, 255))
+        );
+        assert_eq!(parse_hex_color("#f00"), Ok((0xFF, 0, 0, 255)));
+        assert_eq!(
+            parse_hex_color("#80FF5722"),
+            Ok((0xFF, 0x57, 0x22, 0x80))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        assert_eq!(
+            parse_hex_color("#GG5722"),
+            Err(ColorError::InvalidDigit('G'))
+        );
+        assert_eq!(
+            parse_hex_color("#FF572"),
+            Err(ColorError::InvalidLength(5))
+        );
+        assert_eq!(
+            parse_hex_color("#"),
+            Err(ColorError::InvalidLength(0))
+        );
+        assert_eq!(
+            parse_hex_color("FF5722"),
+            Err(ColorError::MissingHash)
+        );
+        assert_eq!(
+            parse_hex_color("#FF 722"),
+            Err(ColorError::InvalidDigit(' '))
+        );
+    }
+
+    #[test]
+    fn shorthand_expands_to_six_digits() {
+        assert_eq!(canonical_hex_color("#f0a"), "#ff00aa");
+        assert_eq!(canonical_hex_color("#FF5722"), "#FF5722");
+        assert_eq!(canonical_hex_color("#80FF5722"), "#80FF5722");
+    }
+
+    #[test]
+    fn errors_are_readable() {
+        assert_eq!(
+            ColorError::InvalidLength(5).to_string(),
+            "expected 3, 6 or 8 hex digits (#RGB, #RRGGBB or #AARRGGBB), found 5"
+        );
+        assert_eq!(
+            ColorError::InvalidDigit('G').to_string(),
+            "'G' is not a hex digit"
+        );
+    }
+}
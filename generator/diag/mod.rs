@@ -0,0 +1,138 @@
+//! Diagnostics shared by the build script and `r-res`.
+//!
+//! Every stage records its problems instead of stopping at the first
+//! one: parse errors of all the files, invalid values, and validation
+//! errors end up in one list, reported at the end by [`render`] with
+//! the offending lines. On one line, a diagnostic reads
+//! `error: res/values.xml:3: concat 'url' has no parts`.
+
+pub mod render;
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// How bad a [`Diagnostic`] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// Resource file, and 1-based line when known
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+}
+
+impl Location {
+    pub fn new(
+        file: impl Into<PathBuf>,
+        line: Option<usize>,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            line,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.file.display())?;
+        match self.line {
+            Some(line) => write!(f, ":{line}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// One problem found by the build, where it is when known
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub location: Option<Location>,
+    /// Text of the line to put the caret under (`name="port"`)
+    pub highlight: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            location: None,
+            highlight: None,
+        }
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(message)
+        }
+    }
+
+    #[must_use]
+    pub fn at(mut self, location: Option<Location>) -> Self {
+        self.location = location;
+        self
+    }
+
+    #[must_use]
+    pub fn with_highlight(mut self, text: impl Into<String>) -> Self {
+        self.highlight = Some(text.into());
+        self
+    }
+
+    /// `res/values.xml:3: message`, without the severity (for
+    /// `cargo:warning=`, which adds its own)
+    pub fn located_message(&self) -> String {
+        match &self.location {
+            Some(location) => format!("{location}: {}", self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// `error: res/values.xml:3: message`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity, self.located_message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics_show_their_location() {
+        let located = Diagnostic::error("concat 'url' has no parts")
+            .at(Some(Location::new("res/values.xml", Some(3))));
+        assert_eq!(
+            located.to_string(),
+            "error: res/values.xml:3: concat 'url' has no parts"
+        );
+        let file_only = Diagnostic::warning("nothing to import")
+            .at(Some(Location::new("res/strings.xml", None)));
+        assert_eq!(
+            file_only.to_string(),
+            "warning: res/strings.xml: nothing to import"
+        );
+        assert_eq!(
+            Diagnostic::error("boom").to_string(),
+            "error: boom"
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! rustc-style rendering of diagnostics.
+//!
+//! ```text
+//! error: Invalid value for 'port': '-1' does not fit in u16
+//!  --> res/values.xml:3
+//!   |
+//! 3 |     <number name="port" type="u16">-1</number>
+//!   |             ^^^^^^^^^^^
+//!
+//! 1 error(s)
+//! ```
+//!
+//! Diagnostics are grouped by file, in line order, and the line of
+//! each one is shown with a caret under its resource's `name`
+//! attribute (the whole line when it has none). Colors are ANSI escapes,
+//! used on a terminal unless `NO_COLOR` is set.
+
+use std::collections::BTreeMap;
+use std::io::IsTerminal as _;
+use std::path::{Path, PathBuf};
+
+use super::{Diagnostic, Severity};
+
+/// Whether [`render`] writes ANSI colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub color: bool,
+}
+
+impl Style {
+    /// No escapes, for tests and logs
+    #[allow(dead_code)] // For library users and tests, not builds
+    pub const PLAIN: Self = Self { color: false };
+
+    /// Colors when stderr is a terminal and `NO_COLOR` is unset
+    pub fn for_stderr() -> Self {
+        Self {
+            color: std::io::stderr().is_terminal()
+                && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+
+    fn paint(self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+const BOLD: &str = "1";
+const GUTTER: &str = "1;34";
+
+fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "1;31",
+        Severity::Warning => "1;33",
+    }
+}
+
+/// Prints `diagnostics` to stderr, with the lines of their files
+pub fn eprint(diagnostics: &[Diagnostic]) {
+    eprint!(
+        "{}",
+        render(diagnostics, Style::for_stderr(), |path| {
+            std::fs::read_to_string(path).ok()
+        })
+    );
+}
+
+/// `diagnostics` with their source lines, read by `source`, grouped
+/// by file (diagnostics without one last), then the counts
+pub fn render(
+    diagnostics: &[Diagnostic],
+    style: Style,
+    mut source: impl FnMut(&Path) -> Option<String>,
+) -> String {
+    let mut by_file: BTreeMap<Option<&PathBuf>, Vec<&Diagnostic>> =
+        BTreeMap::new();
+    for diagnostic in diagnostics {
+        let file = diagnostic.location.as_ref().map(|l| &l.file);
+        by_file.entry(file).or_default().push(diagnostic);
+    }
+    // `None` sorts first; unlocated diagnostics go after the files
+    let unlocated = by_file.remove(&None).unwrap_or_default();
+
+    let mut out = String::new();
+    for (file, mut group) in by_file {
+        let text = file.and_then(|file| source(file));
+        let lines: Vec<&str> = text
+            .as_deref()
+            .map(|t| t.lines().collect())
+            .unwrap_or_default();
+        group.sort_by_key(|d| {
+            d.location.as_ref().and_then(|l| l.line)
+        });
+        for diagnostic in group {
+            render_one(&mut out, diagnostic, &lines, style);
+        }
+    }
+    for diagnostic in unlocated {
+        render_one(&mut out, diagnostic, &[], style);
+    }
+    out.push_str(&summary(diagnostics, style));
+    out
+}
+
+fn render_one(
+    out: &mut String,
+    diagnostic: &Diagnostic,
+    lines: &[&str],
+    style: Style,
+) {
+    let color = severity_color(diagnostic.severity);
+    out.push_str(
+        &style.paint(color, &diagnostic.severity.to_string()),
+    );
+    out.push_str(
+        &style.paint(BOLD, &format!(": {}", diagnostic.message)),
+    );
+    out.push('\n');
+    let Some(location) = &diagnostic.location else {
+        out.push('\n');
+        return;
+    };
+    let code = location.line.and_then(|line| {
+        Some((line, *lines.get(line.checked_sub(1)?)?))
+    });
+    let width = code.map_or(0, |(line, _)| line.to_string().len());
+    let pad = " ".repeat(width);
+    out.push_str(&format!(
+        "{pad}{} {location}\n",
+        style.paint(GUTTER, "-->")
+    ));
+    if let Some((line, text)) = code {
+        let text = text.trim_end();
+        let (start, len) =
+            span(text, diagnostic.highlight.as_deref());
+        let bar = style.paint(GUTTER, "|");
+        out.push_str(&format!(
+            "{pad} {bar}\n{} {bar} {text}\n{pad} {bar} {}{}\n",
+            style.paint(GUTTER, &line.to_string()),
+            " ".repeat(start),
+            style.paint(color, &"^".repeat(len)),
+        ));
+    }
+    out.push('\n');
+}
+
+/// Start and length in characters of the caret under `text`: the
+/// `highlight` when the line contains it, else the line without its
+/// indentation
+fn span(text: &str, highlight: Option<&str>) -> (usize, usize) {
+    let chars = |s: &str| s.chars().count();
+    if let Some(at) = highlight.and_then(|h| text.find(h)) {
+        return (
+            chars(&text[..at]),
+            chars(highlight.unwrap_or_default()),
+        );
+    }
+    let content = text.trim_start();
+    let start = chars(text) - chars(content);
+    (start, chars(content).max(1))
+}
+
+/// `2 error(s), 1 warning(s)`, empty without diagnostics
+fn summary(diagnostics: &[Diagnostic], style: Style) -> String {
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let (errors, warnings) =
+        (count(Severity::Error), count(Severity::Warning));
+    let text = match (errors, warnings) {
+        (0, 0) => return String::new(),
+        (_, 0) => format!("{errors} error(s)"),
+        (0, _) => format!("{warnings} warning(s)"),
+        _ => format!("{errors} error(s), {warnings} warning(s)"),
+    };
+    format!("{}\n", style.paint(BOLD, &text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::diag::Location;
+
+    const SOURCE: &str = "<resources>\n\
+        \x20   <number name=\"port\" type=\"u16\">-1</number>\n\
+        \x20   <color name=\"accent\"></color>\n\
+        </resources>";
+
+    fn at(line: usize) -> Option<Location> {
+        Some(Location::new("res/values.xml", Some(line)))
+    }
+
+    fn render_plain(diagnostics: &[Diagnostic]) -> String {
+        render(diagnostics, Style::PLAIN, |_| {
+            Some(SOURCE.to_string())
+        })
+    }
+
+    #[test]
+    fn carets_point_at_the_name_or_the_line() {
+        let diagnostics = [
+            Diagnostic::error("color 'accent' has an empty value")
+                .at(at(3)),
+            Diagnostic::error("'-1' does not fit in u16")
+                .at(at(2))
+                .with_highlight("name=\"port\""),
+            Diagnostic::warning("2 unused resource(s)"),
+        ];
+
+        assert_eq!(
+            render_plain(&diagnostics),
+            "error: '-1' does not fit in u16\n \
+             --> res/values.xml:2\n  \
+               |\n\
+             2 |     <number name=\"port\" type=\"u16\">-1</number>\n  \
+               |             ^^^^^^^^^^^\n\
+             \n\
+             error: color 'accent' has an empty value\n \
+             --> res/values.xml:3\n  \
+               |\n\
+             3 |     <color name=\"accent\"></color>\n  \
+               |     ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^\n\
+             \n\
+             warning: 2 unused resource(s)\n\
+             \n\
+             2 error(s), 1 warning(s)\n"
+        );
+    }
+
+    #[test]
+    fn unknown_lines_only_show_the_location() {
+        let diagnostics = [
+            Diagnostic::error("gone").at(at(40)),
+            Diagnostic::error("no line")
+                .at(Some(Location::new("res/values.xml", None))),
+        ];
+        let rendered = render_plain(&diagnostics);
+
+        assert!(rendered
+            .contains("error: gone\n--> res/values.xml:40\n\n"));
+        assert!(rendered
+            .contains("error: no line\n--> res/values.xml\n\n"));
+    }
+
+    #[test]
+    fn colors_are_ansi_escapes() {
+        let rendered = render(
+            &[Diagnostic::warning("careful").at(at(3))],
+            Style { color: true },
+            |_| Some(SOURCE.to_string()),
+        );
+
+        assert!(rendered.starts_with(
+            "\x1b[1;33mwarning\x1b[0m\x1b[1m: careful\x1b[0m\n"
+        ));
+        assert!(rendered.contains(
+            "\x1b[1;33m^^^^^^^^^^^^^^^^^^^^^^^^^^^^^\x1b[0m"
+        ));
+        assert!(rendered.ends_with("\x1b[1m1 warning(s)\x1b[0m\n"));
+        assert_eq!(render(&[], Style { color: true }, |_| None), "");
+    }
+}
@@ -0,0 +1,434 @@
+//! Differences between two resource sets (`r-res diff`).
+//!
+//! Compares the primary resource of each name in two graphs, built
+//! from two directories or one directory under two profiles:
+//!
+//! ```text
+//! + auth/title = "Sign in" (string)
+//! - legacy_banner = "Old" (string)
+//! ~ welcome: "Hi" -> "Hello" (string)
+//! ! TYPE CHANGED max_retries: i64 -> f64 ("3" -> "3.5")
+//! ! SIGNATURE CHANGED greeting: (name: &str) -> (name: &str, count: i64)
+//! 1 added, 1 removed, 3 changed
+//! ```
+//!
+//! A type change is one of the generated item, so `<number>3</number>`
+//! becoming `<number>3.5</number>` is one (`i64` to `f64`), and a
+//! template taking other parameters changes its signature even when
+//! its text stays the same. Test resources are left out.
+
+use std::collections::BTreeMap;
+
+use crate::generator::ir::types::{
+    date_text, money_text, template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberValue, Ordering, ResourceGraph, ResourceNode, ResourceValue,
+};
+use crate::generator::utils::json_string;
+
+/// How a resource differs between the two sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Only in the new set
+    Added,
+    /// Only in the old set
+    Removed,
+    /// Same type, other value
+    Changed,
+    /// The generated item has another type
+    TypeChanged,
+    /// A template function takes other parameters
+    SignatureChanged,
+}
+
+impl ChangeKind {
+    /// Name of the change in the JSON output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+            Self::TypeChanged => "type_changed",
+            Self::SignatureChanged => "signature_changed",
+        }
+    }
+}
+
+/// A resource as compared: its type, value and template signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// `string`, `template`, `i64`, `color`...
+    pub ty: &'static str,
+    /// The value as written, bytes in hex
+    pub value: String,
+    /// Parameters of a template function, e.g. `name: &str`
+    pub signature: Option<String>,
+}
+
+/// One resource that differs, with its old and new entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Qualified name, e.g. `auth/title`
+    pub name: String,
+    pub kind: ChangeKind,
+    pub old: Option<Entry>,
+    pub new: Option<Entry>,
+}
+
+/// Type of the item generated for `value`
+fn type_label(value: &ResourceValue) -> &'static str {
+    match value {
+        ResourceValue::String(_) => "string",
+        ResourceValue::Template { .. } => "template",
+        ResourceValue::Number(NumberValue::Int(_)) => "i64",
+        ResourceValue::Number(NumberValue::Float(_)) => "f64",
+        ResourceValue::Number(NumberValue::BigDecimal(_)) => {
+            "bigdecimal"
+        }
+        ResourceValue::Number(NumberValue::Typed { ty, .. }) => {
+            ty.as_str()
+        }
+        ResourceValue::Bool(_) => "bool",
+        ResourceValue::Color(_) => "color",
+        ResourceValue::LatLng { .. } => "latlng",
+        ResourceValue::Position { .. } => "position",
+        ResourceValue::Semver { .. } => "semver",
+        ResourceValue::IpAddr(_) => "ip",
+        ResourceValue::SocketAddr(_) => "socket_addr",
+        ResourceValue::Regex(_) => "regex",
+        ResourceValue::Uuid(_) => "uuid",
+        ResourceValue::DateTime { .. } => "datetime",
+        ResourceValue::Date { .. } => "date",
+        ResourceValue::Money { .. } => "money",
+        ResourceValue::Char(_) => "char",
+        ResourceValue::Grapheme(_) => "grapheme",
+        ResourceValue::Bytes(_) => "bytes",
+    }
+}
+
+/// Text of `value`, equal for two values only when they are
+fn value_text(value: &ResourceValue) -> String {
+    match value {
+        ResourceValue::String(text)
+        | ResourceValue::Template { text, .. }
+        | ResourceValue::Color(text)
+        | ResourceValue::Semver { text, .. }
+        | ResourceValue::Regex(text)
+        | ResourceValue::DateTime { text, .. }
+        | ResourceValue::Grapheme(text)
+        | ResourceValue::Number(NumberValue::BigDecimal(text))
+        | ResourceValue::Number(NumberValue::Typed {
+            literal: text,
+            ..
+        }) => text.clone(),
+        ResourceValue::Number(NumberValue::Int(i)) => i.to_string(),
+        ResourceValue::Number(NumberValue::Float(f)) => {
+            format!("{f:?}")
+        }
+        ResourceValue::Bool(value) => value.to_string(),
+        ResourceValue::LatLng { lat: a, lng: b }
+        | ResourceValue::Position { x: a, y: b } => {
+            format!("{a:?}, {b:?}")
+        }
+        ResourceValue::IpAddr(ip) => ip.to_string(),
+        ResourceValue::SocketAddr(addr) => addr.to_string(),
+        ResourceValue::Uuid(value) => uuid_text(*value),
+        ResourceValue::Date { year, month, day } => {
+            date_text(*year, *month, *day)
+        }
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => money_text(*minor_units, *scale, currency),
+        ResourceValue::Char(c) => c.to_string(),
+        ResourceValue::Bytes(bytes) => {
+            bytes.iter().map(|b| format!("{b:02x}")).collect()
+        }
+    }
+}
+
+fn entry(node: &ResourceNode) -> Entry {
+    let signature = match &node.value {
+        ResourceValue::Template { text, params } => {
+            template_signature(text, params).map(|s| s.params)
+        }
+        _ => None,
+    };
+    Entry {
+        ty: type_label(&node.value),
+        value: value_text(&node.value),
+        signature,
+    }
+}
+
+/// Entry of every non-test resource of `graph`, by qualified name
+fn entries(graph: &ResourceGraph) -> BTreeMap<String, Entry> {
+    graph
+        .ordered_nodes(Ordering::Alphabetical)
+        .into_iter()
+        .filter(|(_, node)| !node.origin.is_test)
+        .map(|(key, node)| (key.full_name(), entry(node)))
+        .collect()
+}
+
+/// How `old` became `new`, `None` when they are equal
+fn change_kind(old: &Entry, new: &Entry) -> Option<ChangeKind> {
+    if old.ty != new.ty {
+        Some(ChangeKind::TypeChanged)
+    } else if old.signature != new.signature {
+        Some(ChangeKind::SignatureChanged)
+    } else if old.value != new.value {
+        Some(ChangeKind::Changed)
+    } else {
+        None
+    }
+}
+
+/// Resources that differ from `old` to `new`, sorted by name
+pub fn diff_graphs(
+    old: &ResourceGraph,
+    new: &ResourceGraph,
+) -> Vec<Change> {
+    let old = entries(old);
+    let mut new = entries(new);
+    let mut changes = Vec::new();
+    for (name, old) in old {
+        let new = new.remove(&name);
+        let kind = match &new {
+            None => ChangeKind::Removed,
+            Some(new) => match change_kind(&old, new) {
+                Some(kind) => kind,
+                None => continue,
+            },
+        };
+        changes.push(Change {
+            name,
+            kind,
+            old: Some(old),
+            new,
+        });
+    }
+    changes.extend(new.into_iter().map(|(name, new)| Change {
+        name,
+        kind: ChangeKind::Added,
+        old: None,
+        new: Some(new),
+    }));
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+/// One line per change and a summary, as printed by `r-res diff`
+pub fn text(changes: &[Change]) -> String {
+    let mut out = String::new();
+    let mut counts = [0; 3];
+    for change in changes {
+        let name = &change.name;
+        let line = match (change.kind, &change.old, &change.new) {
+            (ChangeKind::Added, _, Some(new)) => {
+                counts[0] += 1;
+                format!("+ {name} = {:?} ({})", new.value, new.ty)
+            }
+            (ChangeKind::Removed, Some(old), _) => {
+                counts[1] += 1;
+                format!("- {name} = {:?} ({})", old.value, old.ty)
+            }
+            (kind, Some(old), Some(new)) => {
+                counts[2] += 1;
+                changed_line(name, kind, old, new)
+            }
+            _ => continue,
+        };
+        out.push_str(&line);
+        out.push('\n');
+    }
+    if changes.is_empty() {
+        out.push_str("no differences\n");
+    } else {
+        out.push_str(&format!(
+            "{} added, {} removed, {} changed\n",
+            counts[0], counts[1], counts[2]
+        ));
+    }
+    out
+}
+
+fn changed_line(
+    name: &str,
+    kind: ChangeKind,
+    old: &Entry,
+    new: &Entry,
+) -> String {
+    match kind {
+        ChangeKind::TypeChanged => format!(
+            "! TYPE CHANGED {name}: {} -> {} ({:?} -> {:?})",
+            old.ty, new.ty, old.value, new.value
+        ),
+        ChangeKind::SignatureChanged => format!(
+            "! SIGNATURE CHANGED {name}: ({}) -> ({})",
+            old.signature.as_deref().unwrap_or_default(),
+            new.signature.as_deref().unwrap_or_default()
+        ),
+        _ => format!(
+            "~ {name}: {:?} -> {:?} ({})",
+            old.value, new.value, new.ty
+        ),
+    }
+}
+
+fn json_entry(out: &mut String, entry: &Entry) {
+    out.push_str("{\"type\":");
+    json_string(out, entry.ty);
+    out.push_str(",\"value\":");
+    json_string(out, &entry.value);
+    if let Some(signature) = &entry.signature {
+        out.push_str(",\"signature\":");
+        json_string(out, signature);
+    }
+    out.push('}');
+}
+
+/// The changes as a JSON array of `{"name", "change", "old", "new"}`
+/// objects, `old` or `new` being `null` when the resource is missing
+pub fn json(changes: &[Change]) -> String {
+    let mut out = String::from("[");
+    for (i, change) in changes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str("{\"name\":");
+        json_string(&mut out, &change.name);
+        out.push_str(",\"change\":");
+        json_string(&mut out, change.kind.as_str());
+        for (field, entry) in
+            [("old", &change.old), ("new", &change.new)]
+        {
+            out.push_str(&format!(",\"{field}\":"));
+            match entry {
+                Some(entry) => json_entry(&mut out, entry),
+                None => out.push_str("null"),
+            }
+        }
+        out.push('}');
+    }
+    out.push_str("]\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKey, ResourceKind, ResourceOrigin, TemplateParam,
+        TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn graph(resources: Vec<(&str, ResourceValue)>) -> ResourceGraph {
+        let mut graph = ResourceGraph::default();
+        for (path, value) in resources {
+            graph.insert(
+                ResourceKey::from_path(path),
+                ResourceNode::new(
+                    ResourceKind::String,
+                    value,
+                    ResourceOrigin::new(
+                        PathBuf::from("values.xml"),
+                        path.starts_with("tests/"),
+                    ),
+                ),
+            );
+        }
+        graph
+    }
+
+    fn string(text: &str) -> ResourceValue {
+        ResourceValue::String(text.into())
+    }
+
+    fn template(text: &str, params: &[&str]) -> ResourceValue {
+        ResourceValue::Template {
+            text: text.into(),
+            params: params
+                .iter()
+                .map(|name| TemplateParam {
+                    name: name.to_string(),
+                    value: TemplateParamValue::String,
+                })
+                .collect(),
+        }
+    }
+
+    fn changes() -> Vec<Change> {
+        let old = graph(vec![
+            ("auth/legacy", string("Old")),
+            ("greeting", template("Hi {name}", &["name"])),
+            (
+                "max_retries",
+                ResourceValue::Number(NumberValue::Int(3)),
+            ),
+            ("same", string("Same")),
+            ("tests/fixture", string("a")),
+            ("welcome", string("Hi")),
+        ]);
+        let new = graph(vec![
+            ("auth/title", string("Sign \"in\"")),
+            (
+                "greeting",
+                template("Hi {name} {day}", &["name", "day"]),
+            ),
+            (
+                "max_retries",
+                ResourceValue::Number(NumberValue::Float(3.5)),
+            ),
+            ("same", string("Same")),
+            ("welcome", string("Hello")),
+        ]);
+        diff_graphs(&old, &new)
+    }
+
+    #[test]
+    fn changes_are_classified_by_name() {
+        let kinds: Vec<(String, ChangeKind)> = changes()
+            .into_iter()
+            .map(|change| (change.name, change.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("auth/legacy".to_string(), ChangeKind::Removed),
+                ("auth/title".into(), ChangeKind::Added),
+                ("greeting".into(), ChangeKind::SignatureChanged),
+                ("max_retries".into(), ChangeKind::TypeChanged),
+                ("welcome".into(), ChangeKind::Changed),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_flags_type_and_signature_changes() {
+        assert_eq!(
+            text(&changes()),
+            "- auth/legacy = \"Old\" (string)\n\
+             + auth/title = \"Sign \\\"in\\\"\" (string)\n\
+             ! SIGNATURE CHANGED greeting: (name: &str) -> (name: &str, day: &str)\n\
+             ! TYPE CHANGED max_retries: i64 -> f64 (\"3\" -> \"3.5\")\n\
+             ~ welcome: \"Hi\" -> \"Hello\" (string)\n\
+             1 added, 1 removed, 3 changed\n"
+        );
+        assert_eq!(text(&[]), "no differences\n");
+    }
+
+    #[test]
+    fn json_lists_both_entries() {
+        let changes = changes();
+        assert_eq!(
+            json(&changes[1..3]),
+            r#"[{"name":"auth/title","change":"added","old":null,"new":{"type":"string","value":"Sign \"in\""}},{"name":"greeting","change":"signature_changed","old":{"type":"template","value":"Hi {name}","signature":"name: &str"},"new":{"type":"template","value":"Hi {name} {day}","signature":"name: &str, day: &str"}}]"#
+                .to_string()
+                + "\n"
+        );
+    }
+}
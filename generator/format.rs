@@ -0,0 +1,536 @@
+//! Canonical layout of resource files (`r-res fmt`).
+//!
+//! ```xml
+//! <resources>
+//!   <!-- Comments and <doc> stay with the element they precede -->
+//!   <number name="retries" type="u32" min="0">3</number>
+//!   <ns name="auth">
+//!     <string name="title">Sign in</string>
+//!   </ns>
+//! </resources>
+//! ```
+//!
+//! `<resources>` and `<ns>` are indented by two spaces per level,
+//! one element per line, keeping at most one blank line where the
+//! file had some. Attributes are ordered `name`, `type`, `spec`, then
+//! the others alphabetically; their values are kept as written.
+//! Everything inside a resource element (text, CDATA, entities,
+//! template parameters) is copied untouched, so no value can change.
+//! With [`FormatOptions::sort`], the children of each `<resources>`
+//! and `<ns>` are sorted by name, each with the comments and `<doc>`
+//! before it.
+//!
+//! The input and the output are both parsed, and the output is only
+//! returned when they hold the same resources.
+
+use std::collections::BTreeMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::generator::input::RawResourceFile;
+use crate::generator::parsing::{
+    parse_raw_files, ParsedResource, ParserError,
+};
+
+/// Elements whose children are laid out again
+const CONTAINERS: [&str; 2] = ["resources", "ns"];
+/// Attributes written first, in this order
+const LEADING_ATTRIBUTES: [&str; 3] = ["name", "type", "spec"];
+/// Indentation of one level
+const INDENT: &str = "  ";
+
+/// How [`format_file`] lays out a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Sort the children of each `<resources>` and `<ns>` by name
+    pub sort: bool,
+}
+
+/// A resource file and its canonical layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedFile {
+    pub path: std::path::PathBuf,
+    pub formatted: String,
+    /// `false` when the file is already formatted
+    pub changed: bool,
+}
+
+/// Markup between the children of a container
+#[derive(Debug)]
+enum Node<'a> {
+    /// `<resources>` or `<ns>`, with its opening tag
+    Container {
+        open: String,
+        tag: String,
+        children: Vec<Child<'a>>,
+    },
+    /// Any other element, its content kept as written
+    Element {
+        open: String,
+        tag: String,
+        content: Option<&'a str>,
+    },
+    /// Comments, declarations and stray text, as written
+    Verbatim(&'a str),
+}
+
+/// A node and whether a blank line preceded it
+#[derive(Debug)]
+struct Child<'a> {
+    node: Node<'a>,
+    blank_before: bool,
+}
+
+impl Child<'_> {
+    fn is_doc(&self) -> bool {
+        matches!(
+            &self.node,
+            Node::Element { tag, .. } if tag == "doc"
+        )
+    }
+
+    fn is_comment(&self) -> bool {
+        matches!(
+            self.node,
+            Node::Verbatim(text) if text.starts_with("<!--")
+        )
+    }
+}
+
+impl Node<'_> {
+    /// `name` of an element, `None` for what only annotates the next
+    /// one (comments, `<doc>`)
+    fn sort_name(&self) -> Option<&str> {
+        let open = match self {
+            Node::Container { open, .. } => open,
+            Node::Element { open, tag, .. } if tag != "doc" => open,
+            _ => return None,
+        };
+        let start = open.find(" name=\"")? + " name=\"".len();
+        let len = open[start..].find('"')?;
+        Some(&open[start..start + len])
+    }
+}
+
+/// Formats the resource file `raw`, failing when it is not
+/// well-formed or when the formatted file would hold other resources
+pub fn format_file(
+    raw: &RawResourceFile,
+    options: &FormatOptions,
+) -> Result<String, ParserError> {
+    let before = resources_of(raw)?;
+    let error = |message: String| ParserError::Xml {
+        path: raw.path.clone(),
+        line: None,
+        message,
+    };
+    let formatted =
+        format_xml(&raw.contents, options).map_err(error)?;
+    let output = RawResourceFile {
+        contents: formatted.clone(),
+        ..raw.clone()
+    };
+    if resources_of(&output)? != before {
+        return Err(error(
+            "formatting would change the resources of the file; \
+             please report it with the file"
+                .to_string(),
+        ));
+    }
+    Ok(formatted)
+}
+
+/// What a file defines, whatever its layout: resources by name, in
+/// the order of their definitions, then the sorted namespace docs,
+/// config namespaces and rejected elements
+type Definitions = (
+    BTreeMap<String, Vec<ParsedResource>>,
+    Vec<(Vec<String>, String)>,
+    Vec<Vec<String>>,
+    Vec<String>,
+);
+
+/// Definitions of `raw`, an error when it is not well-formed
+fn resources_of(
+    raw: &RawResourceFile,
+) -> Result<Definitions, ParserError> {
+    let (mut files, errors) =
+        parse_raw_files(std::slice::from_ref(raw));
+    let Some(file) = files.pop() else {
+        return Err(errors.into_iter().next().unwrap_or_else(|| {
+            ParserError::Xml {
+                path: raw.path.clone(),
+                line: None,
+                message: "cannot be parsed".to_string(),
+            }
+        }));
+    };
+    let mut resources: BTreeMap<String, Vec<ParsedResource>> =
+        BTreeMap::new();
+    for mut resource in file.resources {
+        resource.meta.line = None;
+        resources
+            .entry(resource.name.clone())
+            .or_default()
+            .push(resource);
+    }
+    let mut docs = file.namespace_docs;
+    docs.sort();
+    let mut config = file.config_namespaces;
+    config.sort();
+    let mut rejected: Vec<String> = errors
+        .into_iter()
+        .map(|ParserError::Xml { message, .. }| message)
+        .collect();
+    rejected.sort();
+    Ok((resources, docs, config, rejected))
+}
+
+/// A container being read, until its closing tag
+struct OpenContainer<'a> {
+    open: String,
+    tag: String,
+    children: Vec<Child<'a>>,
+    blank_before: bool,
+}
+
+/// Lays out `xml` canonically, without checking its resources
+fn format_xml(
+    xml: &str,
+    options: &FormatOptions,
+) -> Result<String, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<OpenContainer<'_>> = Vec::new();
+    let mut document = Vec::new();
+    let mut blank_before = false;
+    loop {
+        let start = position(&reader);
+        let event = reader.read_event().map_err(|e| e.to_string())?;
+        let raw = &xml[start..position(&reader)];
+        let node = match event {
+            Event::Eof => break,
+            Event::Text(_) if raw.trim().is_empty() => {
+                blank_before |= raw.matches('\n').count() > 1;
+                continue;
+            }
+            Event::Start(e) if is_container(&e) => {
+                stack.push(OpenContainer {
+                    open: open_tag(&e, false)?,
+                    tag: tag_of(&e),
+                    children: Vec::new(),
+                    blank_before: std::mem::take(&mut blank_before),
+                });
+                continue;
+            }
+            Event::Start(e) => {
+                let span = reader
+                    .read_to_end(e.to_end().name())
+                    .map_err(|e| e.to_string())?;
+                let content =
+                    &xml[to_index(span.start)..to_index(span.end)];
+                Node::Element {
+                    open: open_tag(&e, false)?,
+                    tag: tag_of(&e),
+                    content: Some(content),
+                }
+            }
+            Event::Empty(e) => Node::Element {
+                open: open_tag(&e, true)?,
+                tag: tag_of(&e),
+                content: None,
+            },
+            Event::End(_) => {
+                let mut container =
+                    stack.pop().ok_or("unexpected closing tag")?;
+                if options.sort {
+                    sort_children(
+                        &mut container.children,
+                        container.tag == "ns",
+                    );
+                }
+                blank_before = container.blank_before;
+                Node::Container {
+                    open: container.open,
+                    tag: container.tag,
+                    children: container.children,
+                }
+            }
+            _ => Node::Verbatim(raw.trim()),
+        };
+        let siblings = match stack.last_mut() {
+            Some(container) => &mut container.children,
+            None => &mut document,
+        };
+        siblings.push(Child { node, blank_before });
+        blank_before = false;
+    }
+    let mut out = String::new();
+    render(&mut out, &document, 0);
+    Ok(out)
+}
+
+fn is_container(e: &BytesStart<'_>) -> bool {
+    CONTAINERS.contains(&tag_of(e).as_str())
+}
+
+/// Byte offset of `reader` in the text it reads
+fn position(reader: &Reader<&[u8]>) -> usize {
+    to_index(reader.buffer_position())
+}
+
+fn to_index(position: u64) -> usize {
+    usize::try_from(position).unwrap_or(usize::MAX)
+}
+
+fn tag_of(e: &BytesStart<'_>) -> String {
+    String::from_utf8_lossy(e.name().as_ref()).into_owned()
+}
+
+/// `<tag name=".." type=".." a=".." b="..">`, or `/>` for `empty`
+fn open_tag(
+    e: &BytesStart<'_>,
+    empty: bool,
+) -> Result<String, String> {
+    let mut attributes = Vec::new();
+    for attribute in e.attributes() {
+        let attribute = attribute.map_err(|e| e.to_string())?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref())
+            .into_owned();
+        // A value quoted with `'` may contain `"`
+        let value = String::from_utf8_lossy(&attribute.value)
+            .replace('"', "&quot;");
+        attributes.push((key, value));
+    }
+    attributes.sort_by_cached_key(|(key, _)| {
+        let rank = LEADING_ATTRIBUTES
+            .iter()
+            .position(|leading| leading == key)
+            .unwrap_or(LEADING_ATTRIBUTES.len());
+        (rank, key.clone())
+    });
+    let mut tag = format!("<{}", tag_of(e));
+    for (key, value) in attributes {
+        tag.push_str(&format!(" {key}=\"{value}\""));
+    }
+    tag.push_str(if empty { "/>" } else { ">" });
+    Ok(tag)
+}
+
+/// Sorts `children` by name, each keeping the comments and `<doc>`
+/// before it; what follows the last element stays last, and so does
+/// the header of an `<ns>`, its comments up to the first `<doc>`
+/// documenting the namespace itself
+fn sort_children(children: &mut Vec<Child<'_>>, is_ns: bool) {
+    let header = if is_ns { header_len(children) } else { 0 };
+    let has_doc = children[..header].iter().any(Child::is_doc);
+    let mut groups: Vec<(usize, Vec<Child<'_>>)> = Vec::new();
+    let mut pending = Vec::new();
+    for child in children.drain(header..) {
+        let is_element = child.node.sort_name().is_some();
+        pending.push(child);
+        if is_element {
+            let group = std::mem::take(&mut pending);
+            groups.push((groups.len(), group));
+        }
+    }
+    groups.sort_by(|(_, a), (_, b)| group_name(a).cmp(group_name(b)));
+    // The docs of the first element would document the namespace
+    if is_ns
+        && !has_doc
+        && groups.first().is_some_and(|(_, group)| group.len() > 1)
+    {
+        groups.sort_by_key(|(index, _)| *index);
+    }
+    children.extend(groups.into_iter().flat_map(|(_, group)| group));
+    children.extend(pending);
+}
+
+/// Number of nodes before the first element of an `<ns>` that must
+/// stay first: up to its first `<doc>`, or else its first comment
+fn header_len(children: &[Child<'_>]) -> usize {
+    let leading = children
+        .iter()
+        .position(|child| child.node.sort_name().is_some())
+        .unwrap_or(children.len());
+    let leading = &children[..leading];
+    leading
+        .iter()
+        .position(Child::is_doc)
+        .or_else(|| leading.iter().position(Child::is_comment))
+        .map_or(0, |index| index + 1)
+}
+
+/// Name of the element ending `group`
+fn group_name<'a>(group: &'a [Child<'_>]) -> &'a str {
+    group
+        .last()
+        .and_then(|child| child.node.sort_name())
+        .unwrap_or_default()
+}
+
+/// Writes `children` at `depth`, a blank line before those that had
+/// one, unless they come first
+fn render(out: &mut String, children: &[Child<'_>], depth: usize) {
+    let pad = INDENT.repeat(depth);
+    for (index, child) in children.iter().enumerate() {
+        if child.blank_before && index > 0 {
+            out.push('\n');
+        }
+        match &child.node {
+            Node::Container {
+                open,
+                tag,
+                children,
+            } if children.is_empty() => {
+                out.push_str(&format!("{pad}{open}</{tag}>\n"));
+            }
+            Node::Container {
+                open,
+                tag,
+                children,
+            } => {
+                out.push_str(&format!("{pad}{open}\n"));
+                render(out, children, depth + 1);
+                out.push_str(&format!("{pad}</{tag}>\n"));
+            }
+            Node::Element {
+                open,
+                tag,
+                content: Some(content),
+            } => {
+                out.push_str(&format!(
+                    "{pad}{open}{content}</{tag}>\n"
+                ));
+            }
+            Node::Element { open, .. } => {
+                out.push_str(&format!("{pad}{open}\n"));
+            }
+            Node::Verbatim(text) => {
+                out.push_str(&format!("{pad}{text}\n"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn format(xml: &str, sort: bool) -> Result<String, String> {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.to_string(),
+            false,
+        );
+        format_file(&raw, &FormatOptions { sort })
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn files_are_indented_by_two_spaces() {
+        let xml = "<?xml version=\"1.0\"?>\n\
+                   <resources>\n\
+                   \t<ns  name=\"auth\" >  <string name=\"title\">Sign in</string>\n\
+                   \t</ns>\n\n\n\
+                   \x20       <!-- Limits -->\n\
+                   <number min='0' type=\"u32\" name=\"retries\">3</number>\n\
+                   <ns name=\"empty\"/>\n\
+                   </resources>";
+        assert_eq!(
+            format(xml, false).unwrap(),
+            "<?xml version=\"1.0\"?>\n\
+             <resources>\n\
+             \x20 <ns name=\"auth\">\n\
+             \x20   <string name=\"title\">Sign in</string>\n\
+             \x20 </ns>\n\
+             \n\
+             \x20 <!-- Limits -->\n\
+             \x20 <number name=\"retries\" type=\"u32\" min=\"0\">3</number>\n\
+             \x20 <ns name=\"empty\"/>\n\
+             </resources>\n"
+        );
+    }
+
+    #[test]
+    fn values_are_copied_untouched() {
+        let xml = "<resources>\n\
+                   <string name=\"code\"><![CDATA[if a < b {  }]]></string>\n\
+                   <string name=\"padded\">  two  spaces &amp; a tab\t</string>\n\
+                   <template name=\"greet\" trim=\"none\">\n\
+                   \x20     <string name=\"user\"/>\n\
+                   \x20     Hi {user}\n\
+                   \x20   </template>\n\
+                   <string name=\"quoted\" note='say \"hi\"'>x</string>\n\
+                   </resources>\n";
+        let formatted = format(xml, false).unwrap();
+        assert!(formatted.contains(
+            "  <string name=\"code\"><![CDATA[if a < b {  }]]></string>\n"
+        ));
+        assert!(formatted.contains(
+            "  <string name=\"padded\">  two  spaces &amp; a tab\t</string>\n"
+        ));
+        assert!(formatted.contains(
+            "  <template name=\"greet\" trim=\"none\">\n      <string name=\"user\"/>\n      Hi {user}\n    </template>\n"
+        ));
+        assert!(formatted.contains("note=\"say &quot;hi&quot;\""));
+        assert_eq!(format(&formatted, false).unwrap(), formatted);
+    }
+
+    #[test]
+    fn sorting_keeps_comments_with_their_element() {
+        let xml = "<resources>\n\
+                   \x20 <!-- Last -->\n\
+                   \x20 <string name=\"zeta\">z</string>\n\
+                   \x20 <doc>The first one</doc>\n\
+                   \x20 <string name=\"alpha\">a</string>\n\
+                   \x20 <ns name=\"mid\">\n\
+                   \x20   <bool name=\"on\">true</bool>\n\
+                   \x20   <bool name=\"off\">false</bool>\n\
+                   \x20 </ns>\n\
+                   \x20 <!-- Trailing -->\n\
+                   </resources>\n";
+        assert_eq!(
+            format(xml, true).unwrap(),
+            "<resources>\n\
+             \x20 <doc>The first one</doc>\n\
+             \x20 <string name=\"alpha\">a</string>\n\
+             \x20 <ns name=\"mid\">\n\
+             \x20   <bool name=\"off\">false</bool>\n\
+             \x20   <bool name=\"on\">true</bool>\n\
+             \x20 </ns>\n\
+             \x20 <!-- Last -->\n\
+             \x20 <string name=\"zeta\">z</string>\n\
+             \x20 <!-- Trailing -->\n\
+             </resources>\n"
+        );
+    }
+
+    #[test]
+    fn namespaces_keep_the_docs_of_their_resources() {
+        // Sorted, the doc of `alpha` would document `ui` instead
+        let xml = "<resources>\n\
+                   \x20 <ns name=\"ui\">\n\
+                   \x20   <string name=\"zeta\">z</string>\n\
+                   \x20   <doc>The first one</doc>\n\
+                   \x20   <string name=\"alpha\">a</string>\n\
+                   \x20 </ns>\n\
+                   </resources>\n";
+        assert_eq!(format(xml, true).unwrap(), xml);
+        let documented = xml.replace(
+            "<ns name=\"ui\">\n",
+            "<ns name=\"ui\">\n    <doc>Screens</doc>\n",
+        );
+        assert!(format(&documented, true).unwrap().contains(
+            "<doc>Screens</doc>\n    <doc>The first one</doc>\n    <string name=\"alpha\">"
+        ));
+    }
+
+    #[test]
+    fn malformed_files_are_errors() {
+        let error = format("<resources>\n<string name=\"a\">", false)
+            .unwrap_err();
+        assert!(error.starts_with("values.xml:"), "{error}");
+    }
+}
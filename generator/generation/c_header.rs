@@ -0,0 +1,406 @@
+//! C header of the resources, for FFI consumers.
+//!
+//! Writes a `resources.h` with one macro per resource, the namespaces
+//! flattened into the name:
+//!
+//! ```c
+//! #define R_APP_NAME "My App"
+//! #define R_MAX_RETRIES 3
+//! #define R_AUTH_TITLE "Login"
+//! ```
+//!
+//! - strings are C literals, non-ASCII text as UTF-8 `\xHH` escapes
+//! - booleans are `1` or `0`; numbers get the suffix of their type
+//!   (`3000000000LL`, `255u`, `1.5f`)
+//! - coordinates and positions define `_LAT`/`_LNG` and `_X`/`_Y`
+//! - templates with placeholders and `BigDecimal` values have no C
+//!   form: a comment names them instead
+//! - names colliding once flattened get a numeric suffix
+//!   (`R_AUTH_TITLE2`)
+//!
+//! The header is guarded by `R_RESOURCES_H`. Translations and test
+//! resources are not exported.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::exports::{doc_comment, doc_lines};
+use crate::generator::ir::types::{
+    date_text, hex_text, integer_value, money_text,
+    template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberType, NumberValue, ResourceGraph, ResourceKey,
+    ResourceNode, ResourceValue,
+};
+use crate::generator::utils::unique_suffixed;
+
+const HEADER: &str =
+    "/* Generated by r-resources. Do not edit by hand. */\n\
+    \n\
+    #ifndef R_RESOURCES_H\n\
+    #define R_RESOURCES_H\n";
+
+const FOOTER: &str = "\n#endif /* R_RESOURCES_H */\n";
+
+/// Renders the header of `graph`
+pub fn c_header(graph: &ResourceGraph) -> String {
+    let mut body = String::new();
+    let mut used = HashSet::new();
+    let mut math = false;
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        let name = unique_suffixed(macro_name(key), &mut used);
+        let deprecated = node.meta.deprecated.as_ref().map(|note| {
+            format!("@deprecated {note}").trim_end().to_string()
+        });
+        doc_comment(
+            &mut body,
+            doc_lines(node.meta.doc.as_deref()).chain(deprecated),
+            "",
+        );
+        for line in definitions(&name, node) {
+            math |= line.contains("INFINITY") || line.contains("NAN");
+            let _ = writeln!(body, "{line}");
+        }
+    }
+
+    let mut out = String::from(HEADER);
+    if math {
+        // `INFINITY` and `NAN`
+        out.push_str("\n#include <math.h>\n");
+    }
+    if !body.is_empty() {
+        out.push('\n');
+        out.push_str(&body);
+    }
+    out.push_str(FOOTER);
+    out
+}
+
+/// Writes the header of `graph` to `path`, creating its parent
+/// directories
+pub fn export_c_header(
+    graph: &ResourceGraph,
+    path: &Path,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, c_header(graph))
+}
+
+/// `#define` lines of a resource, or the comment of a skipped one
+fn definitions(name: &str, node: &ResourceNode) -> Vec<String> {
+    let constant = |value: String| define(name, &value);
+    let skipped = |reason: &str| format!("/* {name}: {reason} */");
+    match &node.value {
+        ResourceValue::String(text) | ResourceValue::Regex(text) => {
+            vec![constant(string_literal(text))]
+        }
+        ResourceValue::Template { text, params } => {
+            match template_signature(text, params) {
+                Some(_) => vec![skipped("template, not exported")],
+                None => vec![constant(string_literal(text))],
+            }
+        }
+        ResourceValue::Number(NumberValue::BigDecimal(_)) => {
+            vec![skipped("BigDecimal, not exported")]
+        }
+        ResourceValue::Number(number) => match number_literal(number)
+        {
+            Some(literal) => vec![constant(literal)],
+            None => vec![skipped("invalid number, not exported")],
+        },
+        ResourceValue::Bool(value) => {
+            vec![constant(u8::from(*value).to_string())]
+        }
+        ResourceValue::Color(hex) => {
+            vec![constant(string_literal(hex))]
+        }
+        ResourceValue::LatLng { lat, lng } => vec![
+            define_part(name, "LAT", *lat),
+            define_part(name, "LNG", *lng),
+        ],
+        ResourceValue::Position { x, y } => vec![
+            define_part(name, "X", *x),
+            define_part(name, "Y", *y),
+        ],
+        ResourceValue::Semver { text, .. } => {
+            vec![constant(string_literal(text))]
+        }
+        ResourceValue::IpAddr(ip) => {
+            vec![constant(string_literal(&ip.to_string()))]
+        }
+        ResourceValue::SocketAddr(addr) => {
+            vec![constant(string_literal(&addr.to_string()))]
+        }
+        ResourceValue::Uuid(value) => {
+            vec![constant(string_literal(&uuid_text(*value)))]
+        }
+        ResourceValue::DateTime { text, .. } => {
+            vec![constant(string_literal(text))]
+        }
+        ResourceValue::Date { year, month, day } => {
+            let text = date_text(*year, *month, *day);
+            vec![constant(string_literal(&text))]
+        }
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => {
+            let text = money_text(*minor_units, *scale, currency);
+            vec![constant(string_literal(&text))]
+        }
+        ResourceValue::Char(c) => {
+            vec![constant(string_literal(&c.to_string()))]
+        }
+        ResourceValue::Grapheme(text) => {
+            vec![constant(string_literal(text))]
+        }
+        ResourceValue::Bytes(bytes) => {
+            vec![constant(string_literal(&hex_text(bytes)))]
+        }
+    }
+}
+
+/// `#define` line; negative values are parenthesized for use in
+/// expressions
+fn define(name: &str, value: &str) -> String {
+    if value.starts_with('-') {
+        format!("#define {name} ({value})")
+    } else {
+        format!("#define {name} {value}")
+    }
+}
+
+/// `#define` of one coordinate of a resource (`R_MAP_PARIS_LAT`)
+fn define_part(name: &str, part: &str, value: f64) -> String {
+    define(&format!("{name}_{part}"), &double(value))
+}
+
+/// `R_` followed by the namespaces and the name, in upper case
+fn macro_name(key: &ResourceKey) -> String {
+    let mut name = String::from("R");
+    let namespace = key.namespace.iter().map(String::as_str);
+    for segment in namespace.chain([&*key.name]) {
+        name.push('_');
+        name.extend(segment.chars().map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        }));
+    }
+    name
+}
+
+/// C literal of a number, suffixed by its type
+fn number_literal(number: &NumberValue) -> Option<String> {
+    Some(match number {
+        NumberValue::Int(i) => integer(i128::from(*i), false),
+        NumberValue::Float(f) => double(*f),
+        NumberValue::BigDecimal(_) => return None,
+        NumberValue::Typed { literal, ty } if ty.is_float() => {
+            let value =
+                literal.replace('_', "").parse::<f64>().ok()?;
+            match ty {
+                NumberType::F32 => float(value),
+                _ => double(value),
+            }
+        }
+        NumberValue::Typed { literal, ty } => {
+            let unsigned = matches!(
+                ty,
+                NumberType::U8
+                    | NumberType::U16
+                    | NumberType::U32
+                    | NumberType::U64
+                    | NumberType::Usize
+            );
+            integer(integer_value(literal)?, unsigned)
+        }
+    })
+}
+
+/// Integer literal, `LL` suffixed beyond 32 bits
+fn integer(value: i128, unsigned: bool) -> String {
+    if value == i128::from(i64::MIN) {
+        // `9223372036854775808` does not fit a `long long`
+        return "-9223372036854775807LL - 1".to_string();
+    }
+    let long = i32::try_from(value).is_err();
+    let suffix = match (unsigned, long) {
+        (true, true) => "ULL",
+        (true, false) => "u",
+        (false, true) => "LL",
+        (false, false) => "",
+    };
+    format!("{value}{suffix}")
+}
+
+fn double(value: f64) -> String {
+    if value.is_nan() {
+        "NAN".to_string()
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("{sign}INFINITY")
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn float(value: f64) -> String {
+    if value.is_finite() {
+        #[allow(clippy::cast_possible_truncation)] // `type="f32"`
+        let value = value as f32;
+        format!("{value:?}f")
+    } else {
+        double(value)
+    }
+}
+
+/// C string literal of `text`: bytes outside printable ASCII become
+/// `\xHH` escapes, and the literal is split after one when a hex
+/// digit follows (`"\xc3\xa9" "e"`, since `\xa9e` is one escape)
+fn string_literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    let mut after_hex = false;
+    let mut previous = '\0';
+    for &byte in text.as_bytes() {
+        let c = char::from(byte);
+        if after_hex && c.is_ascii_hexdigit() {
+            out.push_str("\" \"");
+        }
+        after_hex = false;
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            // `??x` sequences are trigraphs
+            '?' if previous == '?' => out.push_str("\\?"),
+            ' '..='~' => out.push(c),
+            _ => {
+                let _ = write!(out, "\\x{byte:02x}");
+                after_hex = true;
+            }
+        }
+        previous = c;
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKind, ResourceOrigin, TemplateParam,
+        TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("/app/res/values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(
+            string_literal("My \"App\""),
+            "\"My \\\"App\\\"\""
+        );
+        assert_eq!(string_literal("Café"), "\"Caf\\xc3\\xa9\"");
+        assert_eq!(string_literal("éa"), "\"\\xc3\\xa9\" \"a\"");
+        assert_eq!(string_literal("??="), "\"?\\?=\"");
+        assert_eq!(string_literal("C:\\tmp\n"), "\"C:\\\\tmp\\n\"");
+    }
+
+    #[test]
+    fn numbers_get_their_suffix() {
+        assert_eq!(integer(3, false), "3");
+        assert_eq!(integer(3_000_000_000, false), "3000000000LL");
+        assert_eq!(integer(255, true), "255u");
+        assert_eq!(define("R_MIN", "-5"), "#define R_MIN (-5)");
+        assert_eq!(
+            number_literal(&NumberValue::Typed {
+                literal: "1.5".into(),
+                ty: NumberType::F32,
+            })
+            .as_deref(),
+            Some("1.5f")
+        );
+    }
+
+    #[test]
+    fn namespaces_are_flattened() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("My App".into()),
+        );
+        insert(&mut graph, "debug", ResourceValue::Bool(true));
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Login".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/greeting",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: vec![TemplateParam {
+                    name: "name".into(),
+                    value: TemplateParamValue::String,
+                }],
+            },
+        );
+        insert(
+            &mut graph,
+            "map/paris",
+            ResourceValue::LatLng {
+                lat: 48.8566,
+                lng: 2.3522,
+            },
+        );
+
+        assert_eq!(
+            c_header(&graph),
+            "/* Generated by r-resources. Do not edit by hand. */\n\
+             \n\
+             #ifndef R_RESOURCES_H\n\
+             #define R_RESOURCES_H\n\
+             \n\
+             #define R_APP_NAME \"My App\"\n\
+             #define R_DEBUG 1\n\
+             /* R_AUTH_GREETING: template, not exported */\n\
+             #define R_AUTH_TITLE \"Login\"\n\
+             #define R_MAP_PARIS_LAT 48.8566\n\
+             #define R_MAP_PARIS_LNG 2.3522\n\
+             \n\
+             #endif /* R_RESOURCES_H */\n"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Helpers shared by the exports to other languages (TypeScript,
+//! Kotlin): the namespace tree of the default resources and `/** */`
+//! doc comments.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use crate::generator::ir::{ResourceGraph, ResourceKey, ResourceNode};
+
+/// Resources and sub-namespaces of one namespace, sorted by name
+#[derive(Default)]
+pub struct ResourceTree<'a> {
+    /// First definition of each resource
+    pub resources: Vec<(&'a ResourceKey, &'a ResourceNode)>,
+    pub children: BTreeMap<&'a str, ResourceTree<'a>>,
+    /// `<doc>` of the namespace
+    pub doc: Option<&'a str>,
+}
+
+impl<'a> ResourceTree<'a> {
+    /// Tree of the default resources of `graph`, without those under
+    /// a `cfg` predicate, whose value depends on the target
+    pub fn new(graph: &'a ResourceGraph) -> Self {
+        let mut root = Self::default();
+        for (key, nodes) in graph.nodes() {
+            if graph.is_conditional(key) {
+                continue;
+            }
+            let Some(node) = nodes.first() else { continue };
+            let namespace = root.namespace(&key.namespace);
+            namespace.resources.push((key, node));
+        }
+        for (namespace, doc) in graph.namespace_docs() {
+            // Namespaces without any resource are not exported
+            if let Some(tree) = root.existing(namespace) {
+                tree.doc = Some(doc);
+            }
+        }
+        root
+    }
+
+    fn namespace(&mut self, path: &'a [String]) -> &mut Self {
+        path.iter().fold(self, |tree, segment| {
+            tree.children.entry(segment).or_default()
+        })
+    }
+
+    fn existing(&mut self, path: &[String]) -> Option<&mut Self> {
+        if path.is_empty() {
+            return None;
+        }
+        path.iter().try_fold(self, |tree, segment| {
+            tree.children.get_mut(segment.as_str())
+        })
+    }
+}
+
+/// `/** ... */` comment, on one line when there is a single line and
+/// nothing without lines
+pub fn doc_comment(
+    out: &mut String,
+    lines: impl IntoIterator<Item = String>,
+    pad: &str,
+) {
+    // `*/` would end the comment early
+    let lines: Vec<String> = lines
+        .into_iter()
+        .map(|line| line.replace("*/", "*\\/"))
+        .collect();
+    match lines.as_slice() {
+        [] => {}
+        [line] => {
+            let _ = writeln!(out, "{pad}/** {line} */");
+        }
+        lines => {
+            let _ = writeln!(out, "{pad}/**");
+            for line in lines {
+                let _ = writeln!(out, "{pad} * {line}");
+            }
+            let _ = writeln!(out, "{pad} */");
+        }
+    }
+}
+
+/// Lines of a `<doc>` text
+pub fn doc_lines(
+    doc: Option<&str>,
+) -> impl Iterator<Item = String> + '_ {
+    doc.into_iter().flat_map(str::lines).map(str::to_string)
+}
@@ -0,0 +1,456 @@
+//! Flattened aliases of namespaced items (`BuildOptions::flatten_aliases`).
+//!
+//! Next to the nested modules, every namespaced item is re-exported at
+//! the root of `r::` with its namespaces joined into the name:
+//!
+//! ```rust,ignore
+//! assert_eq!(r::UI_COLORS_PRIMARY, r::ui::colors::PRIMARY);
+//! r::auth_greeting("Ada"); // r::auth::greeting
+//! ```
+//!
+//! Constants and statics join upper-cased, template functions
+//! lower-cased; the separator is `_` unless
+//! `BuildOptions::flatten_separator` says otherwise. An alias taking
+//! the name of a root item, of a root namespace or of another alias
+//! fails the build.
+//!
+//! With `ReferenceStyle::Copy`, strings, numbers, bools and colors
+//! get a constant of their own instead, holding the same value, with
+//! a doc line and a `#[deprecated]` of its own:
+//!
+//! ```rust,ignore
+//! /// Copy of [`ui::colors::PRIMARY`]
+//! pub const UI_COLORS_PRIMARY: &str = "#3366FF";
+//! ```
+//!
+//! Their companion constants (`_MIN`, `_MAX`) and the other items,
+//! `BigDecimal` statics and template functions among them, keep a
+//! `pub use`.
+
+use crate::generator::analysis::AnalysisError;
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::{
+    NumberValue, ResourceGraph, ResourceKey, ResourceNode,
+    ResourceValue, TypeRegistry,
+};
+use crate::generator::options::ReferenceStyle;
+use crate::generator::utils::{
+    const_identifier, sanitize_identifier, GENERATED_ROOT_CONSTS,
+    GENERATED_ROOT_TYPES,
+};
+
+use super::emitter::deprecated_attribute;
+use super::scope::{
+    add_item, collision_error, is_function, item_identifier,
+    resource_identifiers, Scope,
+};
+
+/// Kind of the alias sources in collision errors
+const ALIAS: &str = "flattened alias";
+
+/// `(alias, path from r::)` of each item generated for `key`, empty at
+/// the root
+fn aliases(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    separator: &str,
+) -> Vec<(String, String)> {
+    if key.namespace.is_empty() {
+        return Vec::new();
+    }
+    let module: Vec<String> = key
+        .namespace
+        .iter()
+        .map(|ns| sanitize_identifier(ns))
+        .collect();
+    let module = module.join("::");
+    if is_function(node) {
+        let joined = format!(
+            "{}{separator}{}",
+            key.namespace.join(separator),
+            key.name
+        );
+        let alias = sanitize_identifier(&joined.to_lowercase());
+        let item = item_identifier(key, node);
+        return vec![(alias, format!("{module}::{item}"))];
+    }
+    let prefix = const_identifier(&format!(
+        "{}{separator}",
+        key.namespace.join(separator)
+    ));
+    resource_identifiers(key, node)
+        .into_iter()
+        .map(|item| {
+            (format!("{prefix}{item}"), format!("{module}::{item}"))
+        })
+        .collect()
+}
+
+/// `true` for the items `ReferenceStyle::Copy` copies: strings,
+/// bools, colors and numbers other than `BigDecimal` statics
+fn is_copyable(node: &ResourceNode) -> bool {
+    match &node.value {
+        ResourceValue::String(_)
+        | ResourceValue::Bool(_)
+        | ResourceValue::Color(_) => true,
+        ResourceValue::Number(number) => {
+            !matches!(number, NumberValue::BigDecimal(_))
+        }
+        _ => false,
+    }
+}
+
+/// `node` as a constant named `alias`, documented as a copy of the
+/// item at `path`
+fn copied_item(
+    registry: &TypeRegistry,
+    node: &ResourceNode,
+    alias: &str,
+    path: &str,
+) -> Option<String> {
+    let ty = registry
+        .all()
+        .iter()
+        .find(|ty| ty.resource_kind() == node.kind)?;
+    // The bounds, widenings and checks stay with the original
+    let mut copy = node.clone();
+    copy.meta.range = None;
+    copy.meta.asserts.clear();
+    copy.meta.widen.clear();
+    let item =
+        ty.emit_rust(&ResourceKey::from_path(alias), &copy, 4)?;
+    let mut code = format!("    /// Copy of [`{path}`]\n");
+    if let Some(note) = &node.meta.deprecated {
+        code.push_str(&deprecated_attribute("    ", note));
+    }
+    code.push_str(&item);
+    Some(code)
+}
+
+/// Emits the aliases of the namespaced items of `graph`, inside `r`:
+/// `pub use` re-exports, or copies of the values with
+/// `ReferenceStyle::Copy`
+pub(super) fn emit_flat_aliases(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    separator: &str,
+    style: ReferenceStyle,
+) {
+    let mut lines = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        // Re-exporting a deprecated item warns like using it, a copy
+        // is deprecated itself
+        let allow = if node.meta.deprecated.is_some()
+            || graph.has_duplicates(key)
+        {
+            "#[allow(deprecated)] "
+        } else {
+            ""
+        };
+        // A copy would hold the value of one `cfg` variant only
+        let cfg = graph.cfg_predicate(key);
+        let allow = match &cfg {
+            Some(predicate) => format!("#[cfg({predicate})] {allow}"),
+            None => allow.to_owned(),
+        };
+        let item = format!("::{}", item_identifier(key, node));
+        let copy = style == ReferenceStyle::Copy
+            && is_copyable(node)
+            && cfg.is_none();
+        for (alias, path) in aliases(key, node, separator) {
+            let copied = (copy && path.ends_with(&item))
+                .then(|| copied_item(registry, node, &alias, &path))
+                .flatten();
+            let line = copied.unwrap_or_else(|| {
+                format!(
+                    "    {allow}pub use self::{path} as {alias};\n"
+                )
+            });
+            lines.push((alias, line));
+        }
+    }
+    if lines.is_empty() {
+        return;
+    }
+    lines.sort();
+    let _ = writeln!(
+        code,
+        "\n    // Namespaced items under their flattened names"
+    );
+    for (_, line) in lines {
+        code.push_str(&line);
+    }
+}
+
+/// Rejects aliases taking the name of another item of `r::`
+pub fn check_flat_aliases(
+    graph: &ResourceGraph,
+    separator: &str,
+) -> Vec<AnalysisError> {
+    let mut scope = Scope::new();
+    let mut aliased = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        let origin = &node.origin;
+        if let Some(ns) = key.namespace.first() {
+            let source = ("namespace", ns.clone());
+            add_item(
+                &mut scope,
+                sanitize_identifier(ns),
+                source,
+                origin,
+            );
+        } else {
+            for ident in resource_identifiers(key, node) {
+                let source = ("resource", key.name.to_string());
+                add_item(&mut scope, ident, source, origin);
+            }
+        }
+        for (alias, _) in aliases(key, node, separator) {
+            let source = (ALIAS, key.full_name());
+            add_item(&mut scope, alias.clone(), source, origin);
+            aliased.push((alias, key));
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (alias, key) in aliased {
+        let root =
+            GENERATED_ROOT_CONSTS.iter().chain(&GENERATED_ROOT_TYPES);
+        if root.into_iter().any(|item| *item == alias) {
+            errors.push(AnalysisError::new(
+                format!(
+                    "Flattened alias `{alias}` of '{}' collides with the generated `r::{alias}`; please rename it or disable flatten_aliases",
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ));
+        }
+    }
+    // Only the collisions with an alias are new, the others are
+    // reported with the identifiers of `r`
+    for (ident, sources) in &scope {
+        let alias = sources.keys().find(|(kind, _)| *kind == ALIAS);
+        let Some((_, name)) = alias.filter(|_| sources.len() > 1)
+        else {
+            continue;
+        };
+        let mut error = collision_error("r", ident, &[], sources);
+        error.key = Some(ResourceKey::from_path(name));
+        errors.push(error);
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NodeMeta, NumberRange, NumberType, ResourceKind,
+        ResourceOrigin, TemplateParam, TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        file: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from(file),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn string(graph: &mut ResourceGraph, path: &str, file: &str) {
+        insert(graph, path, file, ResourceValue::String("x".into()));
+    }
+
+    fn greeting() -> ResourceValue {
+        ResourceValue::Template {
+            text: "Hi {name}".into(),
+            params: vec![TemplateParam {
+                name: "name".into(),
+                value: TemplateParamValue::String,
+            }],
+        }
+    }
+
+    fn alias(
+        code: &mut String,
+        graph: &ResourceGraph,
+        separator: &str,
+    ) {
+        let registry = TypeRegistry::default();
+        let style = ReferenceStyle::Alias;
+        emit_flat_aliases(code, graph, &registry, separator, style);
+    }
+
+    fn messages(
+        graph: &ResourceGraph,
+        separator: &str,
+    ) -> Vec<String> {
+        check_flat_aliases(graph, separator)
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[test]
+    fn items_are_reexported_under_joined_names() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "title", "a.xml");
+        string(&mut graph, "ui/colors/primary", "a.xml");
+        insert(&mut graph, "auth/Greeting", "a.xml", greeting());
+        graph.insert(
+            ResourceKey::from_path("net/pool"),
+            ResourceNode {
+                meta: NodeMeta {
+                    deprecated: Some("use ui/colors/primary".into()),
+                    range: Some(NumberRange {
+                        min: Some(NumberValue::Int(1)),
+                        max: None,
+                    }),
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(
+                    ResourceKind::Number,
+                    ResourceValue::Number(NumberValue::Int(4)),
+                    ResourceOrigin::new(
+                        PathBuf::from("a.xml"),
+                        false,
+                    ),
+                )
+            },
+        );
+        let mut code = String::new();
+        emit_flat_aliases(
+            &mut code,
+            &graph,
+            &TypeRegistry::default(),
+            "_",
+            ReferenceStyle::Alias,
+        );
+
+        assert_eq!(
+            code,
+            "\n    // Namespaced items under their flattened names\n    \
+             #[allow(deprecated)] pub use self::net::POOL as NET_POOL;\n    \
+             #[allow(deprecated)] pub use self::net::POOL_MIN as NET_POOL_MIN;\n    \
+             pub use self::ui::colors::PRIMARY as UI_COLORS_PRIMARY;\n    \
+             pub use self::auth::Greeting as auth_greeting;\n"
+        );
+        assert!(messages(&graph, "_").is_empty());
+    }
+
+    #[test]
+    fn copies_hold_the_value_of_the_item() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "ui/accent",
+            "a.xml",
+            ResourceValue::String("#3366FF".into()),
+        );
+        insert(&mut graph, "auth/greeting", "a.xml", greeting());
+        graph.insert(
+            ResourceKey::from_path("net/pool"),
+            ResourceNode {
+                meta: NodeMeta {
+                    deprecated: Some("use net/size".into()),
+                    range: Some(NumberRange {
+                        min: Some(NumberValue::Int(1)),
+                        max: None,
+                    }),
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(
+                    ResourceKind::Number,
+                    ResourceValue::Number(NumberValue::Typed {
+                        literal: "4".into(),
+                        ty: NumberType::U8,
+                    }),
+                    ResourceOrigin::new(
+                        PathBuf::from("a.xml"),
+                        false,
+                    ),
+                )
+            },
+        );
+        let big = ResourceValue::Number(NumberValue::BigDecimal(
+            "0.1000000000000000000001".into(),
+        ));
+        insert(&mut graph, "net/rate", "a.xml", big);
+        let mut code = String::new();
+        emit_flat_aliases(
+            &mut code,
+            &graph,
+            &TypeRegistry::default(),
+            "_",
+            ReferenceStyle::Copy,
+        );
+
+        assert_eq!(
+            code,
+            "\n    // Namespaced items under their flattened names\n    \
+             /// Copy of [`net::POOL`]\n    \
+             #[deprecated(note = \"use net/size\")]\n    \
+             pub const NET_POOL: u8 = 4;\n    \
+             #[allow(deprecated)] pub use self::net::POOL_MIN as NET_POOL_MIN;\n    \
+             pub use self::net::RATE as NET_RATE;\n    \
+             /// Copy of [`ui::ACCENT`]\n    \
+             pub const UI_ACCENT: &str = \"#3366FF\";\n    \
+             pub use self::auth::greeting as auth_greeting;\n"
+        );
+    }
+
+    #[test]
+    fn separators_are_sanitized() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "ui/title", "a.xml");
+        let mut code = String::new();
+        alias(&mut code, &graph, "__");
+        assert!(
+            code.contains("pub use self::ui::TITLE as UI__TITLE;")
+        );
+
+        code.clear();
+        alias(&mut code, &graph, "-");
+        assert!(code.contains("pub use self::ui::TITLE as UI_TITLE;"));
+    }
+
+    #[test]
+    fn aliases_collide_with_root_items_and_each_other() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "ui_title", "a.xml");
+        string(&mut graph, "ui/title", "b.xml");
+        string(&mut graph, "a_b/c", "a.xml");
+        string(&mut graph, "a/b_c", "b.xml");
+        string(&mut graph, "build/profile", "b.xml");
+
+        assert_eq!(
+            messages(&graph, "_"),
+            vec![
+                "Flattened alias `BUILD_PROFILE` of 'build/profile' collides with the generated `r::BUILD_PROFILE`; please rename it or disable flatten_aliases",
+                "Identifier collision in `r`: flattened alias 'a/b_c' (b.xml), flattened alias 'a_b/c' (a.xml) all generate `A_B_C`; rename one of them",
+                "Identifier collision in `r`: flattened alias 'ui/title' (b.xml), resource 'ui_title' (a.xml) all generate `UI_TITLE`; rename one of them",
+            ]
+        );
+        assert_eq!(
+            check_flat_aliases(&graph, "_")[2].key,
+            Some(ResourceKey::from_path("ui/title"))
+        );
+        // A separator the names do not use keeps them apart
+        assert!(messages(&graph, "__").is_empty());
+    }
+}
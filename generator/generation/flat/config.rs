@@ -0,0 +1,293 @@
+//! Config structs of the `<ns struct="true">` namespaces.
+//!
+//! ```xml
+//! <ns name="http" struct="true">
+//!     <number name="timeout_ms">500</number>
+//!     <string name="user_agent">demo/1.0</string>
+//! </ns>
+//! ```
+//!
+//! generates, next to `pub mod http`:
+//!
+//! ```rust,ignore
+//! /// Resources of [`http`] as one value, see [`HTTP`]
+//! #[derive(Clone, Copy, Debug, PartialEq)]
+//! pub struct HttpConfig {
+//!     pub timeout_ms: i64,
+//!     pub user_agent: &'static str,
+//! }
+//!
+//! /// Resources of [`http`]
+//! pub const HTTP: HttpConfig = HttpConfig {
+//!     timeout_ms: http::TIMEOUT_MS,
+//!     user_agent: http::USER_AGENT,
+//! };
+//! ```
+//!
+//! Fields follow the items of the namespace, in its order, regexes
+//! as their `_PATTERN` text. Templates with placeholders and
+//! `BigDecimal` statics have no `const` value to copy and are skipped,
+//! as are nested namespaces, with a warning.
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::{
+    NumberValue, ResourceGraph, ResourceKey, ResourceNode,
+    ResourceValue,
+};
+use crate::generator::utils::{
+    camel_case_identifier, const_identifier, sanitize_identifier,
+};
+
+use super::scope::{is_function, item_identifier};
+use super::tree::NamespaceNode;
+
+/// `HTTP` constant and `HttpConfig` struct of the namespace `ns_name`
+pub(super) fn config_identifiers(ns_name: &str) -> [String; 2] {
+    [
+        const_identifier(ns_name),
+        format!("{}Config", camel_case_identifier(ns_name)),
+    ]
+}
+
+/// Type of the config field of `node`, `None` for the items with no
+/// `const` value
+pub(super) fn field_type(
+    node: &ResourceNode,
+) -> Option<&'static str> {
+    Some(match &node.value {
+        ResourceValue::String(_)
+        | ResourceValue::Color(_)
+        | ResourceValue::Regex(_)
+        | ResourceValue::Grapheme(_) => "&'static str",
+        ResourceValue::Number(NumberValue::Int(_)) => "i64",
+        ResourceValue::Number(NumberValue::Float(_)) => "f64",
+        ResourceValue::Number(NumberValue::Typed { ty, .. }) => {
+            ty.as_str()
+        }
+        ResourceValue::Bool(_) => "bool",
+        ResourceValue::LatLng { .. } => "r_resources::LatLng",
+        ResourceValue::Position { .. } => "r_resources::Position",
+        ResourceValue::Semver { .. } => "r_resources::Version",
+        ResourceValue::IpAddr(_) => "std::net::IpAddr",
+        ResourceValue::SocketAddr(_) => "std::net::SocketAddr",
+        ResourceValue::Uuid(_) => "u128",
+        ResourceValue::DateTime { .. } => "i64",
+        ResourceValue::Date { .. } => "r_resources::Date",
+        ResourceValue::Money { .. } => "r_resources::Money",
+        ResourceValue::Char(_) => "char",
+        ResourceValue::Bytes(_) => "&'static [u8]",
+        ResourceValue::Template { .. } if !is_function(node) => {
+            "&'static str"
+        }
+        ResourceValue::Number(NumberValue::BigDecimal(_))
+        | ResourceValue::Template { .. } => return None,
+    })
+}
+
+/// Field name of an item: `timeout_ms`, `promo_ends_unix`,
+/// `username_pattern`
+pub(super) fn field_name(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> String {
+    sanitize_identifier(&item_identifier(key, node).to_lowercase())
+}
+
+/// Emits the struct and constant of the config namespace `ns_name`,
+/// `node`, in its parent module
+pub(super) fn emit_config_struct(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    ns_name: &str,
+    node: &NamespaceNode,
+    indent: usize,
+) {
+    let pad = " ".repeat(indent);
+    let module = sanitize_identifier(ns_name);
+    let [constant, name] = config_identifiers(ns_name);
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    let mut deprecated = false;
+    for key in &node.resource_keys {
+        let Some(resource) = graph.get(key) else { continue };
+        let Some(ty) = field_type(resource) else { continue };
+        if graph.is_conditional(key) {
+            continue;
+        }
+        let field = field_name(key, resource);
+        let item = item_identifier(key, resource);
+        fields.push(format!(
+            "{pad}    /// [`{module}::{item}`]\n{pad}    pub {field}: {ty},\n"
+        ));
+        values.push(format!("{pad}    {field}: {module}::{item},\n"));
+        deprecated |= resource.meta.deprecated.is_some()
+            || graph.has_duplicates(key);
+    }
+    let allow = if deprecated {
+        format!("{pad}#[allow(deprecated)]\n")
+    } else {
+        String::new()
+    };
+    let _ = write!(
+        code,
+        "{pad}/// Resources of [`{module}`] as one value, see [`{constant}`]\n\
+         {pad}#[derive(Clone, Copy, Debug, PartialEq)]\n\
+         {pad}pub struct {name} {{\n{}{pad}}}\n\n\
+         {pad}/// Resources of [`{module}`]\n\
+         {allow}{pad}pub const {constant}: {name} = {name} {{\n{}{pad}}};\n",
+        fields.concat(),
+        values.concat(),
+    );
+}
+
+/// Warnings about the items of the config namespaces of `graph` left
+/// out of their struct
+pub fn config_warnings(graph: &ResourceGraph) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for namespace in graph.config_namespaces() {
+        let Some(ns_name) = namespace.last() else { continue };
+        let [_, name] = config_identifiers(ns_name);
+        let mut nested = Vec::new();
+        for (key, nodes) in graph.nodes() {
+            let Some(node) = nodes.first() else { continue };
+            let rest = key.namespace.strip_prefix(&namespace[..]);
+            let Some(rest) = rest else { continue };
+            match rest.first() {
+                Some(child) if !nested.contains(child) => {
+                    nested.push(child.clone());
+                    let mut path = namespace.clone();
+                    path.push(child.clone());
+                    warnings.push(format!(
+                        "Namespace '{}' is not a field of `{name}`; only the resources right inside a struct namespace are",
+                        path.join("/")
+                    ));
+                }
+                None if field_type(node).is_none() => {
+                    warnings.push(format!(
+                        "Resource '{}' is not a field of `{name}`: template functions and BigDecimal numbers have no constant value",
+                        key.full_name()
+                    ));
+                }
+                None if graph.is_conditional(key) => {
+                    warnings.push(format!(
+                        "Resource '{}' is not a field of `{name}`: it only exists under its `cfg` predicate",
+                        key.full_name()
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKind, ResourceOrigin, TemplateParam,
+        TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn http() -> ResourceGraph {
+        let mut graph = ResourceGraph::default();
+        let number =
+            |value| ResourceValue::Number(NumberValue::Int(value));
+        insert(&mut graph, "http/timeout_ms", number(500));
+        insert(&mut graph, "http/retries", number(3));
+        insert(
+            &mut graph,
+            "http/type",
+            ResourceValue::String("json".into()),
+        );
+        insert(
+            &mut graph,
+            "http/greeting",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: vec![TemplateParam {
+                    name: "name".into(),
+                    value: TemplateParamValue::String,
+                }],
+            },
+        );
+        insert(&mut graph, "http/tls/port", number(443));
+        insert(
+            &mut graph,
+            "http/tls/strict",
+            ResourceValue::Bool(true),
+        );
+        graph.mark_config_namespace(vec!["http".to_string()]);
+        graph
+    }
+
+    #[test]
+    fn structs_list_the_fields_of_their_namespace() {
+        let graph = http();
+        let mut tree = NamespaceNode::default();
+        for key in graph.nodes().keys() {
+            tree.insert_key(key);
+        }
+        let mut code = String::new();
+        emit_config_struct(
+            &mut code,
+            &graph,
+            "http",
+            &tree.children["http"],
+            4,
+        );
+        assert_eq!(
+            code,
+            "    /// Resources of [`http`] as one value, see [`HTTP`]\n    \
+             #[derive(Clone, Copy, Debug, PartialEq)]\n    \
+             pub struct HttpConfig {\n    \
+             \x20   /// [`http::RETRIES`]\n    \
+             \x20   pub retries: i64,\n    \
+             \x20   /// [`http::TIMEOUT_MS`]\n    \
+             \x20   pub timeout_ms: i64,\n    \
+             \x20   /// [`http::TYPE`]\n    \
+             \x20   pub r#type: &'static str,\n    \
+             }\n\n    \
+             /// Resources of [`http`]\n    \
+             pub const HTTP: HttpConfig = HttpConfig {\n    \
+             \x20   retries: http::RETRIES,\n    \
+             \x20   timeout_ms: http::TIMEOUT_MS,\n    \
+             \x20   r#type: http::TYPE,\n    \
+             };\n"
+        );
+    }
+
+    #[test]
+    fn skipped_items_are_reported() {
+        assert_eq!(
+            config_warnings(&http()),
+            [
+                "Resource 'http/greeting' is not a field of `HttpConfig`: template functions and BigDecimal numbers have no constant value",
+                "Namespace 'http/tls' is not a field of `HttpConfig`; only the resources right inside a struct namespace are",
+            ]
+        );
+        assert_eq!(
+            config_identifiers("rate_limits"),
+            ["RATE_LIMITS".to_string(), "RateLimitsConfig".into()]
+        );
+    }
+}
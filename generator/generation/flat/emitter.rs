@@ -1,11 +1,21 @@
 //! Code emission for flat module generation
 
 use crate::generator::analysis::AnalysisWarning;
-use crate::generator::ir::{ResourceGraph, ResourceKey, ResourceNode, TypeRegistry};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::generation::EmitOptions;
+use crate::generator::options::LazyStatics;
+use crate::generator::ir::types::{range_doc, regex_static};
+use crate::generator::ir::{
+    Ordering, ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+    TypeRegistry,
+};
+use crate::generator::utils::{display_path, escape_str, sanitize_identifier};
 use std::collections::HashMap;
-use std::fmt::Write as _;
+use std::path::Path;
 
+use super::config::emit_config_struct;
+use super::hot_reload;
+use super::testable::overridable_getter;
 use super::tree::{build_namespace_tree, sort_namespace_tree, NamespaceNode};
 
 /// Context for code generation
@@ -13,6 +23,16 @@ struct GenerationContext<'a> {
     graph: &'a ResourceGraph,
     registry: &'a TypeRegistry,
     duplicate_info: &'a HashMap<ResourceKey, String>,
+    /// Root that provenance paths are relative to, `None` to omit them
+    provenance_root: Option<&'a Path>,
+    /// Profile of the hot-reload accessors, `None` to omit them
+    hot_reload: Option<&'a str>,
+    /// Emit the `LazyLock<regex::Regex>` statics of regexes
+    regex: bool,
+    /// Emit the getters of the `overridable="true"` resources
+    testable: bool,
+    /// Type of the `BigDecimal` and regex statics
+    lazy: LazyStatics,
 }
 
 /// Parameters for emitting a single resource
@@ -23,37 +43,145 @@ struct ResourceEmitParams<'a> {
     indent: usize,
 }
 
-/// Generates the `r` module with nested namespace structure
-pub fn generate_r_module(
+/// Emits the `r` module with nested namespace structure
+pub fn emit_r_module(
+    code: &mut dyn CodeSink,
     graph: &ResourceGraph,
     registry: &TypeRegistry,
     warnings: &[AnalysisWarning],
-) -> String {
+    options: &EmitOptions<'_>,
+) {
     let mut tree = build_namespace_tree(graph);
-    sort_namespace_tree(&mut tree);
+    sort_namespace_tree(&mut tree, graph, options.ordering);
 
     // Build a map of keys to their warning messages (for duplicate info)
-    let duplicate_info: HashMap<_, _> = warnings
-        .iter()
-        .filter_map(|w| {
-            w.key.as_ref().map(|k| (k.clone(), w.message.clone()))
-        })
-        .collect();
+    let mut duplicate_info = HashMap::new();
+    for warning in warnings {
+        let Some(key) = &warning.key else { continue };
+        if graph.has_duplicates(key) {
+            duplicate_info
+                .entry(key.clone())
+                .or_insert_with(|| warning.message.clone());
+        }
+    }
 
     let ctx = GenerationContext {
         graph,
         registry,
         duplicate_info: &duplicate_info,
+        provenance_root: options.provenance_root,
+        hot_reload: options.hot_reload.then_some(options.profile),
+        regex: options.regex,
+        testable: options.testable,
+        lazy: options.lazy,
     };
 
-    let mut code = String::from("\npub mod r {\n    use std::str::FromStr;\n");
-    emit_namespace_tree(&mut code, &tree, &ctx, 4);
+    code.push_str("\npub mod r {\n");
+    if let Some(doc) = &tree.doc {
+        code.push_str(&doc_comment("    ", "//!", doc));
+    }
+    let _ = writeln!(
+        code,
+        "    /// Build profile the resources were generated for\n    \
+         pub const BUILD_PROFILE: &str = \"{}\";",
+        escape_str(options.profile)
+    );
+    emit_namespace_tree(code, &tree, &ctx, 4);
+    if let Some(separator) = options.flatten_aliases {
+        super::aliases::emit_flat_aliases(
+            code,
+            graph,
+            registry,
+            separator,
+            options.reference_style,
+        );
+    }
+    super::raw::emit_raw_module(
+        code,
+        options.raw_assets,
+        options.provenance_root,
+        4,
+    );
+    super::keys::emit_key_enums(code, graph);
+    if options.lookup {
+        super::lookup::emit_lookup(code, graph);
+    }
+    super::meta::emit_meta(code, graph, options);
+    if options.json {
+        super::json::emit_to_json(code, graph);
+    }
+    if !graph.translations().is_empty() {
+        let emitter = GraphEmitter {
+            graph,
+            registry,
+            provenance_root: options.provenance_root,
+            ordering: options.ordering,
+            lazy: options.lazy,
+        };
+        super::locale::emit_locales(code, &emitter);
+    }
     code.push_str("}\n");
-    code
+}
+
+/// Emits the resources of a graph, without duplicate annotations
+pub(super) struct GraphEmitter<'a> {
+    pub(super) graph: &'a ResourceGraph,
+    pub(super) registry: &'a TypeRegistry,
+    pub(super) provenance_root: Option<&'a Path>,
+    pub(super) ordering: Ordering,
+    pub(super) lazy: LazyStatics,
+}
+
+impl GraphEmitter<'_> {
+    /// Emits the namespace tree of the graph
+    pub(super) fn tree(&self, code: &mut dyn CodeSink, indent: usize) {
+        let mut tree = build_namespace_tree(self.graph);
+        sort_namespace_tree(&mut tree, self.graph, self.ordering);
+        let duplicate_info = HashMap::new();
+        let ctx = GenerationContext {
+            graph: self.graph,
+            registry: self.registry,
+            duplicate_info: &duplicate_info,
+            provenance_root: self.provenance_root,
+            hot_reload: None,
+            regex: false,
+            testable: false,
+            lazy: self.lazy,
+        };
+        emit_namespace_tree(code, &tree, &ctx, indent);
+    }
+
+    /// Emits the primary definition of `key`
+    pub(super) fn resource(
+        &self,
+        code: &mut dyn CodeSink,
+        key: &ResourceKey,
+        indent: usize,
+    ) {
+        let Some(node) = self.graph.get(key) else { return };
+        let duplicate_info = HashMap::new();
+        let ctx = GenerationContext {
+            graph: self.graph,
+            registry: self.registry,
+            duplicate_info: &duplicate_info,
+            provenance_root: self.provenance_root,
+            hot_reload: None,
+            regex: false,
+            testable: false,
+            lazy: self.lazy,
+        };
+        let params = ResourceEmitParams {
+            key,
+            node,
+            warning_message: None,
+            indent,
+        };
+        emit_resource(code, &params, &ctx);
+    }
 }
 
 fn emit_namespace_tree(
-    code: &mut String,
+    code: &mut dyn CodeSink,
     node: &NamespaceNode,
     ctx: &GenerationContext<'_>,
     indent: usize,
@@ -66,62 +194,298 @@ fn emit_namespace_tree(
             pad,
             sanitize_identifier(ns_name)
         );
+        if let Some(doc) = &child.doc {
+            code.push_str(&doc_comment(&format!("{pad}    "), "//!", doc));
+        }
         emit_namespace_tree(code, child, ctx, indent + 4);
         let _ = writeln!(code, "{}}}", pad);
+        if child.config {
+            let graph = ctx.graph;
+            emit_config_struct(code, graph, ns_name, child, indent);
+        }
     }
 
     for key in &node.resource_keys {
-        if let Some(all_nodes) = ctx.graph.get_all(key) {
-            let warning_message = ctx.duplicate_info.get(key);
-
-            // Only emit the first node (priority), duplicates are ignored but warned
-            if let Some(first_node) = all_nodes.first() {
-                let params = ResourceEmitParams {
-                    key,
-                    node: first_node,
-                    warning_message,
-                    indent,
-                };
-                emit_resource(code, &params, ctx);
-            }
-            // Note: Duplicate nodes are not generated, only the first one is kept
+        let warning_message = ctx.duplicate_info.get(key);
+
+        // Only the first node (priority) of duplicates is emitted,
+        // but every `cfg` variant is; rustc keeps the one that holds
+        for node in ctx.graph.emitted_nodes(key) {
+            let params = ResourceEmitParams {
+                key,
+                node,
+                warning_message,
+                indent,
+            };
+            emit_resource(code, &params, ctx);
         }
     }
 }
 
+/// Emits a resource; every item of one with a `cfg` predicate, its
+/// bounds and accessors included, only exists under the predicate
 fn emit_resource(
-    code: &mut String,
+    code: &mut dyn CodeSink,
     params: &ResourceEmitParams<'_>,
     ctx: &GenerationContext<'_>,
 ) {
+    let Some(predicate) = &params.node.meta.cfg else {
+        emit_definition(code, params, ctx);
+        return;
+    };
+    let mut items = String::new();
+    emit_definition(&mut items, params, ctx);
     let pad = " ".repeat(params.indent);
+    code.push_str(&cfg_items(&items, &pad, predicate));
+}
 
-    // Add warning annotation for duplicates with file information
-    if let Some(warning) = params.warning_message {
+fn emit_definition(
+    code: &mut dyn CodeSink,
+    params: &ResourceEmitParams<'_>,
+    ctx: &GenerationContext<'_>,
+) {
+    let pad = " ".repeat(params.indent);
+
+    // `<doc>` text, constraints and provenance, one paragraph each
+    let paragraphs: Vec<String> = [
+        params.node.meta.doc.as_ref().map(|doc| doc_comment(&pad, "///", doc)),
+        params.node.meta.range.as_ref().map(|range| {
+            format!("{pad}/// {}\n", range_doc(range))
+        }),
+        params.node.meta.pattern.as_ref().map(|pattern| {
+            format!("{pad}/// Pattern: {}\n", code_span(pattern))
+        }),
+        ctx.provenance_root.map(|root| {
+            provenance_doc(&pad, params.key, params.node, root)
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    code.push_str(&paragraphs.join(&format!("{pad}///\n")));
+
+    let mut attributes = String::new();
+    if let Some(note) = &params.node.meta.deprecated {
+        attributes.push_str(&deprecated_attribute(&pad, note));
+    } else if let Some(warning) = params.warning_message {
+        // Add warning annotation for duplicates with file information
         // Extract just the relevant part of the warning for the note
         let note = if warning.len() > 100 {
             format!("{}...", &warning[..100])
         } else {
             warning.clone()
         };
-        code.push_str(&format!(
-            "{pad}#[deprecated(note = \"{}\")]\n",
-            note.replace('"', "\\\"")
-        ));
+        attributes.push_str(&deprecated_attribute(&pad, &note));
         // Add allow for dead_code with a message
-        code.push_str(&format!(
+        attributes.push_str(&format!(
             "{pad}#[allow(dead_code)] // WARNING: Duplicate resource - only first definition is used\n"
         ));
     }
+    code.push_str(&attributes);
+    emit_items(code, params, ctx, &attributes);
+    if ctx.testable && ctx.graph.is_overridable(params.key) {
+        let (key, node) = (params.key, params.node);
+        let getter = overridable_getter(key, node, &attributes, &pad);
+        code.push_str(&getter);
+    }
+}
+
+/// Emits the constant of a resource, then the items derived from it
+/// (regex static, hot-reload accessor), each after `attributes`
+fn emit_items(
+    code: &mut dyn CodeSink,
+    params: &ResourceEmitParams<'_>,
+    ctx: &GenerationContext<'_>,
+    attributes: &str,
+) {
+    let pad = " ".repeat(params.indent);
+    if ctx.regex && matches!(params.node.value, ResourceValue::Regex(_)) {
+        emit_value(code, params, ctx);
+        code.push_str(attributes);
+        code.push_str(&regex_static(params.key, &pad, ctx.lazy));
+        return;
+    }
+    let Some(profile) = ctx.hot_reload else {
+        emit_value(code, params, ctx);
+        return;
+    };
+    let (key, node) = (params.key, params.node);
+    match hot_reload::template_function(key, node, profile, &pad) {
+        Some(function) => code.push_str(&function),
+        None => emit_value(code, params, ctx),
+    }
+    if let Some(accessor) =
+        hot_reload::string_accessor(key, node, profile, &pad)
+    {
+        code.push_str(attributes);
+        code.push_str(&accessor);
+    }
+}
 
+/// Emits the constant or function of a resource through its type
+fn emit_value(
+    code: &mut dyn CodeSink,
+    params: &ResourceEmitParams<'_>,
+    ctx: &GenerationContext<'_>,
+) {
     // Find the type handler by matching ResourceKind
-    for ty in ctx.registry.all() {
-        if ty.resource_kind() == params.node.kind {
-            if let Some(rust_code) = ty.emit_rust(params.key, params.node, params.indent) {
-                code.push_str(&rust_code);
+    let node = params.node;
+    let Some(ty) = ctx
+        .registry
+        .all()
+        .iter()
+        .find(|ty| ty.resource_kind() == node.kind)
+    else {
+        return;
+    };
+    let Some(rust_code) = ty.emit_rust_with_lazy(
+        params.key,
+        node,
+        params.indent,
+        ctx.lazy,
+    ) else {
+        return;
+    };
+    code.push_str(&rust_code);
+}
+
+/// `<doc>` text as `///` or `//!` comment lines
+pub(super) fn doc_comment(pad: &str, marker: &str, doc: &str) -> String {
+    doc.lines()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{pad}{marker}\n")
+            } else {
+                format!("{pad}{marker} {line}\n")
             }
-            return;
+        })
+        .collect()
+}
+
+/// `text` as inline code, fenced with more backticks than it contains
+fn code_span(text: &str) -> String {
+    let mut fence = String::from("`");
+    while text.contains(fence.as_str()) {
+        fence.push('`');
+    }
+    let pad = if fence.len() > 1 { " " } else { "" };
+    format!("{fence}{pad}{text}{pad}{fence}")
+}
+
+/// `code` with a `#[cfg(predicate)]` line before each of its items
+/// at the indentation `pad`
+fn cfg_items(code: &str, pad: &str, predicate: &str) -> String {
+    const ITEMS: [&str; 5] =
+        ["pub ", "pub(", "fn ", "static ", "thread_local!"];
+    let mut out = String::with_capacity(code.len());
+    for line in code.split_inclusive('\n') {
+        let item = line.strip_prefix(pad).is_some_and(|rest| {
+            ITEMS.iter().any(|item| rest.starts_with(item))
+        });
+        if item {
+            out.push_str(&format!("{pad}#[cfg({predicate})]\n"));
         }
+        out.push_str(line);
     }
+    out
+}
+
+/// `#[deprecated(note = "...")]` line
+pub(super) fn deprecated_attribute(pad: &str, note: &str) -> String {
+    format!("{pad}#[deprecated(note = \"{}\")]\n", escape_str(note))
 }
 
+/// Doc line telling where a resource was defined, e.g.
+/// ``/// Defined in `res/config.xml` (namespace `ui/colors`)``
+fn provenance_doc(
+    pad: &str,
+    key: &ResourceKey,
+    node: &ResourceNode,
+    root: &Path,
+) -> String {
+    let file = display_path(&node.origin.file, root);
+    if key.namespace.is_empty() {
+        format!("{pad}/// Defined in `{file}`\n")
+    } else {
+        format!(
+            "{pad}/// Defined in `{file}` (namespace `{}`)\n",
+            key.namespace.join("/")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{ResourceKind, ResourceOrigin, ResourceValue};
+    use std::path::PathBuf;
+
+    fn node(file: &str) -> ResourceNode {
+        ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("x".to_string()),
+            ResourceOrigin::new(PathBuf::from(file), false),
+        )
+    }
+
+    #[test]
+    fn provenance_is_relative_to_res_dir() {
+        let root = Path::new("/home/me/app/res");
+        let doc = provenance_doc(
+            "    ",
+            &ResourceKey::from_path("ui/colors/primary"),
+            &node("/home/me/app/res/config.xml"),
+            root,
+        );
+        assert_eq!(
+            doc,
+            "    /// Defined in `res/config.xml` (namespace `ui/colors`)\n"
+        );
+
+        let doc = provenance_doc(
+            "",
+            &ResourceKey::from_path("title"),
+            &node("/home/me/app/res/tests/values.xml"),
+            root,
+        );
+        assert_eq!(doc, "/// Defined in `res/tests/values.xml`\n");
+    }
+
+    #[test]
+    fn provenance_outside_root_keeps_path() {
+        assert_eq!(
+            display_path(Path::new("other/values.xml"), Path::new("res")),
+            "other/values.xml"
+        );
+    }
+
+    #[test]
+    fn cfg_predicates_precede_every_item() {
+        let items = "    /// Retries\n    \
+                     pub const RETRIES: u8 = 3;\n    \
+                     pub const RETRIES_MAX: u8 = 5;\n    \
+                     #[cfg(test)]\n    \
+                     thread_local! {\n        \
+                     static CELL: u8 = 0;\n    \
+                     }\n";
+        assert_eq!(
+            cfg_items(items, "    ", "unix"),
+            "    /// Retries\n    \
+             #[cfg(unix)]\n    \
+             pub const RETRIES: u8 = 3;\n    \
+             #[cfg(unix)]\n    \
+             pub const RETRIES_MAX: u8 = 5;\n    \
+             #[cfg(test)]\n    \
+             #[cfg(unix)]\n    \
+             thread_local! {\n        \
+             static CELL: u8 = 0;\n    \
+             }\n"
+        );
+    }
+
+    #[test]
+    fn code_spans_fence_backticks() {
+        assert_eq!(code_span(r"[a-z]+\d"), r"`[a-z]+\d`");
+        assert_eq!(code_span("a`b"), "`` a`b ``");
+    }
+}
@@ -0,0 +1,202 @@
+//! Accessors of the `hot-reload` feature.
+//!
+//! Next to each string constant, a function of the same name in
+//! lower case returns the value re-read from its XML file:
+//!
+//! ```rust,ignore
+//! r::APP_NAME   // &str, as compiled
+//! r::app_name() // Cow<'static, str>, edited text in debug builds
+//! ```
+//!
+//! Template functions try the edited text first too. The reloading
+//! itself is `r_resources::hot_reload`, which returns the compiled
+//! values in release builds, so the accessors inline to them.
+//!
+//! Unlike constants, the accessors have a body, one level below deep
+//! namespaces: they allow `clippy::excessive_nesting`.
+
+use crate::generator::ir::types::template_format_call;
+use crate::generator::ir::{
+    ResourceKey, ResourceNode, ResourceValue, TemplateParam,
+};
+use crate::generator::utils::{
+    const_identifier, sanitize_identifier, string_literal,
+};
+
+/// `r_resources::hot_reload` arguments locating a resource:
+/// file, qualified name and profile
+fn location(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    profile: &str,
+) -> String {
+    format!(
+        "{}, {}, {}",
+        string_literal(&node.origin.file.to_string_lossy()),
+        string_literal(&key.full_name()),
+        string_literal(profile)
+    )
+}
+
+/// Accessor of a string (or of a template without placeholders), `None`
+/// for other resources
+pub(super) fn string_accessor(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    profile: &str,
+    pad: &str,
+) -> Option<String> {
+    match &node.value {
+        ResourceValue::String(_) => {}
+        ResourceValue::Template { text, params }
+            if template_format_call(text, params).is_none() => {}
+        _ => return None,
+    }
+    let const_name = const_identifier(&key.name);
+    Some(format!(
+        "{pad}/// [`{const_name}`], re-read from its file when edited \
+         (debug builds)\n\
+         {pad}#[allow(deprecated, clippy::excessive_nesting)]\n\
+         {pad}pub fn {}() -> std::borrow::Cow<'static, str> {{\n\
+         {pad}    r_resources::hot_reload::string({}, {const_name})\n\
+         {pad}}}\n",
+        sanitize_identifier(&key.name.to_lowercase()),
+        location(key, node, profile),
+    ))
+}
+
+/// Template function trying the edited text before the compiled one,
+/// `None` for templates emitted as constants
+pub(super) fn template_function(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    profile: &str,
+    pad: &str,
+) -> Option<String> {
+    let ResourceValue::Template { text, params } = &node.value else {
+        return None;
+    };
+    let (signature, format_call) =
+        template_format_call(text, params)?;
+    Some(format!(
+        "{pad}#[allow(clippy::excessive_nesting)]\n\
+         {pad}pub fn {}({}) -> String {{\n\
+         {pad}    r_resources::hot_reload::template(\n\
+         {pad}        {},\n\
+         {pad}        &[{}],\n\
+         {pad}    )\n\
+         {pad}    .unwrap_or_else(|| {format_call})\n\
+         {pad}}}\n",
+        sanitize_identifier(&key.name),
+        signature.params,
+        location(key, node, profile),
+        template_args(&signature.args, params),
+    ))
+}
+
+/// `(name, &value)` pairs of the template parameters; positional
+/// templates name them like their arguments (`arg1`, `arg2`, ...)
+fn template_args(args: &str, params: &[TemplateParam]) -> String {
+    let names: Vec<&str> = if params.is_empty() {
+        args.split(", ").collect()
+    } else {
+        params.iter().map(|p| p.name.as_str()).collect()
+    };
+    names
+        .into_iter()
+        .map(|name| {
+            format!(
+                "({}, &{} as &dyn std::fmt::Display)",
+                string_literal(name),
+                sanitize_identifier(name)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKind, ResourceOrigin, TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn node(value: ResourceValue) -> ResourceNode {
+        ResourceNode::new(
+            ResourceKind::String,
+            value,
+            ResourceOrigin::new(
+                PathBuf::from("/app/res/values.xml"),
+                false,
+            ),
+        )
+    }
+
+    #[test]
+    fn strings_get_a_lower_case_accessor() {
+        let code = string_accessor(
+            &ResourceKey::from_path("auth/Title"),
+            &node(ResourceValue::String("Login".into())),
+            "debug",
+            "",
+        )
+        .unwrap();
+        assert_eq!(
+            code,
+            "/// [`TITLE`], re-read from its file when edited (debug builds)\n\
+             #[allow(deprecated, clippy::excessive_nesting)]\n\
+             pub fn title() -> std::borrow::Cow<'static, str> {\n    \
+             r_resources::hot_reload::string(\"/app/res/values.xml\", \
+             \"auth/Title\", \"debug\", TITLE)\n\
+             }\n"
+        );
+        assert!(string_accessor(
+            &ResourceKey::from_path("enabled"),
+            &node(ResourceValue::Bool(true)),
+            "debug",
+            "",
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn templates_fall_back_to_the_compiled_text() {
+        let value = ResourceValue::Template {
+            text: "Hi {name}".into(),
+            params: vec![TemplateParam {
+                name: "name".into(),
+                value: TemplateParamValue::String,
+            }],
+        };
+        let code = template_function(
+            &ResourceKey::from_path("greeting"),
+            &node(value),
+            "debug",
+            "",
+        )
+        .unwrap();
+        assert!(code.contains("pub fn greeting(name: &str) -> String {"));
+        assert!(code.contains(
+            "&[(\"name\", &name as &dyn std::fmt::Display)],"
+        ));
+        assert!(code.contains(
+            ".unwrap_or_else(|| format!(\"Hi {}\", name))"
+        ));
+
+        let positional = ResourceValue::Template {
+            text: "%1$s of %2$d".into(),
+            params: Vec::new(),
+        };
+        let code = template_function(
+            &ResourceKey::from_path("page"),
+            &node(positional),
+            "debug",
+            "",
+        )
+        .unwrap();
+        assert!(code
+            .contains("(\"arg2\", &arg2 as &dyn std::fmt::Display)"));
+    }
+}
@@ -0,0 +1,298 @@
+//! `r::to_json()` of the `serde` feature: every resource in one JSON
+//! object nested like the namespaces.
+//!
+//! ```rust,ignore
+//! r::to_json() // {"app_name":"My App","auth":{"title":"Login"},...}
+//! ```
+//!
+//! - colors are hex strings, `BigDecimal` numbers strings with their
+//!   digits, and non-finite floats the strings `NaN`, `inf` and `-inf`
+//! - coordinates are `{"lat":..,"lng":..}` and `{"x":..,"y":..}`
+//! - templates are `{"template":"Hello {name}!","params":"name: &str"}`
+//!   with the parameters of their function
+//!
+//! The document is written at build time from the resolved values, so
+//! `to_json()` only copies a string literal; nothing is built at
+//! startup.
+
+use std::fmt::Write as _;
+
+use crate::generator::generation::exports::ResourceTree;
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::types::{
+    date_text, hex_text, integer_value, money_text,
+    template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberValue, ResourceGraph, ResourceNode, ResourceValue,
+};
+use crate::generator::utils::{json_string, string_literal};
+
+/// Emits `r::to_json()` for `graph`
+pub(super) fn emit_to_json(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+) {
+    let _ = writeln!(
+        code,
+        "\n    /// Every resource as a JSON object nested like the \
+         namespaces,\n\
+         \x20   /// with the values of this build\n\
+         \x20   #[must_use]\n\
+         \x20   pub fn to_json() -> String {{\n\
+         \x20       String::from({})\n\
+         \x20   }}",
+        string_literal(&json(graph))
+    );
+}
+
+/// JSON document of the default resources of `graph`
+fn json(graph: &ResourceGraph) -> String {
+    let mut out = String::new();
+    write_object(&mut out, &ResourceTree::new(graph));
+    out
+}
+
+fn write_object(out: &mut String, tree: &ResourceTree<'_>) {
+    out.push('{');
+    let mut first = true;
+    let mut separate = |out: &mut String| {
+        if !std::mem::take(&mut first) {
+            out.push(',');
+        }
+    };
+    for (key, node) in &tree.resources {
+        separate(out);
+        json_string(out, &key.name);
+        out.push(':');
+        write_value(out, node);
+    }
+    for (name, child) in &tree.children {
+        separate(out);
+        json_string(out, name);
+        out.push(':');
+        write_object(out, child);
+    }
+    out.push('}');
+}
+
+fn write_value(out: &mut String, node: &ResourceNode) {
+    match &node.value {
+        ResourceValue::String(text)
+        | ResourceValue::Color(text)
+        | ResourceValue::Semver { text, .. }
+        | ResourceValue::Regex(text)
+        | ResourceValue::DateTime { text, .. }
+        | ResourceValue::Grapheme(text) => {
+            json_string(out, text);
+        }
+        ResourceValue::IpAddr(ip) => json_string(out, &ip.to_string()),
+        ResourceValue::SocketAddr(addr) => {
+            json_string(out, &addr.to_string());
+        }
+        ResourceValue::Uuid(value) => {
+            json_string(out, &uuid_text(*value));
+        }
+        ResourceValue::Date { year, month, day } => {
+            json_string(out, &date_text(*year, *month, *day));
+        }
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => {
+            let text = money_text(*minor_units, *scale, currency);
+            json_string(out, &text);
+        }
+        ResourceValue::Char(c) => json_string(out, &c.to_string()),
+        ResourceValue::Bytes(bytes) => {
+            json_string(out, &hex_text(bytes));
+        }
+        ResourceValue::Template { text, params } => {
+            match template_signature(text, params) {
+                Some(signature) => {
+                    out.push_str("{\"template\":");
+                    json_string(out, text);
+                    out.push_str(",\"params\":");
+                    json_string(out, &signature.params);
+                    out.push('}');
+                }
+                None => json_string(out, text),
+            }
+        }
+        ResourceValue::Number(number) => write_number(out, number),
+        ResourceValue::Bool(value) => {
+            let _ = write!(out, "{value}");
+        }
+        ResourceValue::LatLng { lat, lng } => {
+            out.push_str("{\"lat\":");
+            write_float(out, *lat);
+            out.push_str(",\"lng\":");
+            write_float(out, *lng);
+            out.push('}');
+        }
+        ResourceValue::Position { x, y } => {
+            out.push_str("{\"x\":");
+            write_float(out, *x);
+            out.push_str(",\"y\":");
+            write_float(out, *y);
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, number: &NumberValue) {
+    match number {
+        NumberValue::Int(i) => {
+            let _ = write!(out, "{i}");
+        }
+        NumberValue::Float(f) => write_float(out, *f),
+        NumberValue::BigDecimal(raw) => {
+            json_string(out, &raw.replace('_', ""));
+        }
+        NumberValue::Typed { literal, ty } if ty.is_float() => {
+            match literal.replace('_', "").parse::<f64>() {
+                Ok(value) => write_float(out, value),
+                Err(_) => json_string(out, literal),
+            }
+        }
+        NumberValue::Typed { literal, .. } => {
+            match integer_value(literal) {
+                Some(value) => {
+                    let _ = write!(out, "{value}");
+                }
+                None => json_string(out, literal),
+            }
+        }
+    }
+}
+
+/// Number, or a string for the values JSON has no number for
+fn write_float(out: &mut String, value: f64) {
+    if value.is_nan() {
+        out.push_str("\"NaN\"");
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        let _ = write!(out, "\"{sign}inf\"");
+    } else {
+        let _ = write!(out, "{value:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberType, ResourceKey, ResourceKind, ResourceOrigin,
+        TemplateParam, TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn objects_mirror_the_namespaces() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("My \"App\"\n".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Login".into()),
+        );
+        insert(
+            &mut graph,
+            "ui/accent",
+            ResourceValue::Color("#FF5722".into()),
+        );
+        insert(&mut graph, "debug", ResourceValue::Bool(false));
+
+        assert_eq!(
+            json(&graph),
+            r##"{"app_name":"My \"App\"\n","debug":false,"auth":{"title":"Login"},"ui":{"accent":"#FF5722"}}"##
+        );
+    }
+
+    #[test]
+    fn numbers_keep_their_value() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "mask",
+            ResourceValue::Number(NumberValue::Typed {
+                literal: "0xFF".into(),
+                ty: NumberType::U8,
+            }),
+        );
+        insert(
+            &mut graph,
+            "big",
+            ResourceValue::Number(NumberValue::BigDecimal(
+                "1234567890123456789012.5".into(),
+            )),
+        );
+        insert(
+            &mut graph,
+            "limit",
+            ResourceValue::Number(NumberValue::Float(f64::INFINITY)),
+        );
+        insert(
+            &mut graph,
+            "map/origin",
+            ResourceValue::Position { x: 0.0, y: -1.5 },
+        );
+
+        assert_eq!(
+            json(&graph),
+            r#"{"big":"1234567890123456789012.5","limit":"inf","mask":255,"map":{"origin":{"x":0.0,"y":-1.5}}}"#
+        );
+    }
+
+    #[test]
+    fn templates_list_their_parameters() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "greeting",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: vec![TemplateParam {
+                    name: "name".into(),
+                    value: TemplateParamValue::String,
+                }],
+            },
+        );
+        insert(
+            &mut graph,
+            "plain",
+            ResourceValue::Template {
+                text: "No placeholder".into(),
+                params: Vec::new(),
+            },
+        );
+
+        assert_eq!(
+            json(&graph),
+            r#"{"greeting":{"template":"Hi {name}","params":"name: &str"},"plain":"No placeholder"}"#
+        );
+    }
+}
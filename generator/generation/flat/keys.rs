@@ -0,0 +1,228 @@
+//! Key enums for the flat `r::` module.
+//!
+//! Generates one enum per value type (`StringKey`, `NumberKey`,
+//! `FloatKey`, `BoolKey`, `ColorKey`) with a variant per resource, so
+//! keys can be stored and matched exhaustively:
+//!
+//! ```rust,ignore
+//! let key = r::StringKey::from_name("auth/title").unwrap();
+//! assert_eq!(key, r::StringKey::AuthTitle);
+//! assert_eq!(key.name(), "auth/title");
+//! assert_eq!(key.as_str(), r::auth::TITLE);
+//! ```
+//!
+//! Namespaced names flatten into `CamelCase` variants; names flattening
+//! to the same variant get a numeric suffix (see analysis warnings).
+//! Types covered by each enum match the `r::lookup` tables.
+
+use std::fmt::Write as _;
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::ResourceGraph;
+use crate::generator::utils::{escape_str, unique_variants};
+
+use super::lookup::{sorted_entries, table_entry};
+
+/// Enums, indexed like the lookup tables: (enum, accessor, value type)
+const KEY_ENUMS: [(&str, &str, &str); 5] = [
+    ("StringKey", "as_str", "&'static str"),
+    ("NumberKey", "value", "i64"),
+    ("FloatKey", "value", "f64"),
+    ("BoolKey", "value", "bool"),
+    ("ColorKey", "as_str", "&'static str"),
+];
+
+/// A resource in a key enum: (qualified name, constant expression)
+type KeyEntry = (String, String);
+
+/// Emits the key enums for `graph`; enums without resources are skipped
+pub(super) fn emit_key_enums(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+) {
+    let mut groups: [Vec<KeyEntry>; 5] = Default::default();
+    for (name, key, node) in sorted_entries(graph) {
+        if let Some((index, expr)) = table_entry(key, node, "") {
+            groups[index].push((name, expr));
+        }
+    }
+
+    for (spec, entries) in KEY_ENUMS.iter().zip(&groups) {
+        if !entries.is_empty() {
+            emit_key_enum(code, spec, entries);
+        }
+    }
+}
+
+fn emit_key_enum(
+    code: &mut dyn CodeSink,
+    (enum_name, accessor, ty): &(&str, &str, &str),
+    entries: &[KeyEntry],
+) {
+    let variants = unique_variants(
+        entries.iter().map(|(name, _)| name.as_str()),
+    );
+
+    let mut decl = String::new();
+    let mut values = String::new();
+    let mut names = String::new();
+    let mut from_name = String::new();
+    for ((name, expr), variant) in entries.iter().zip(&variants) {
+        let name = escape_str(name);
+        let _ = writeln!(decl, "        {variant},");
+        let _ = writeln!(
+            values,
+            "                Self::{variant} => {expr},"
+        );
+        let _ = writeln!(
+            names,
+            "                Self::{variant} => \"{name}\","
+        );
+        let _ = writeln!(
+            from_name,
+            "                \"{name}\" => Some(Self::{variant}),"
+        );
+    }
+
+    let all = variants
+        .iter()
+        .map(|variant| format!("Self::{variant}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = write!(
+        code,
+        r#"    /// Type-safe keys of the `{ty}` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum {enum_name} {{
+{decl}    }}
+
+    #[allow(deprecated)]
+    impl {enum_name} {{
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[{all}];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn {accessor}(&self) -> {ty} {{
+            match self {{
+{values}            }}
+        }}
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {{
+            match self {{
+{names}            }}
+        }}
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {{
+            match name {{
+{from_name}                _ => None,
+            }}
+        }}
+    }}
+"#
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberValue, ResourceKey, ResourceKind, ResourceNode,
+        ResourceOrigin, ResourceValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn enums_code(graph: &ResourceGraph) -> String {
+        let mut code = String::new();
+        emit_key_enums(&mut code, graph);
+        code
+    }
+
+    #[test]
+    fn string_key_enum_lists_every_string() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("App".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("T".into()),
+        );
+        let code = enums_code(&graph);
+
+        assert!(code.contains("pub enum StringKey {\n        AppName,\n        AuthTitle,\n    }"));
+        assert!(code.contains(
+            "pub const ALL: &'static [Self] = &[Self::AppName, Self::AuthTitle];"
+        ));
+        assert!(code.contains("Self::AuthTitle => auth::TITLE,"));
+        assert!(code.contains("Self::AuthTitle => \"auth/title\","));
+        assert!(
+            code.contains("\"auth/title\" => Some(Self::AuthTitle),")
+        );
+        assert!(code
+            .contains("pub const fn as_str(&self) -> &'static str"));
+        // No numbers, no NumberKey
+        assert!(!code.contains("NumberKey"));
+    }
+
+    #[test]
+    fn numbers_get_their_own_enum() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "max_retries",
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        let code = enums_code(&graph);
+
+        assert!(code.contains(
+            "pub enum NumberKey {\n        MaxRetries,\n    }"
+        ));
+        assert!(code.contains("pub const fn value(&self) -> i64"));
+        assert!(!code.contains("StringKey"));
+    }
+
+    #[test]
+    fn colliding_variants_get_suffixes() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "auth/title_x",
+            ResourceValue::String("a".into()),
+        );
+        insert(
+            &mut graph,
+            "auth_title/x",
+            ResourceValue::String("b".into()),
+        );
+        let code = enums_code(&graph);
+
+        assert!(code.contains("Self::AuthTitleX => auth::TITLE_X,"));
+        assert!(code.contains("Self::AuthTitleX2 => auth_title::X,"));
+    }
+}
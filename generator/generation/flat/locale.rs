@@ -0,0 +1,338 @@
+//! Locale support for the flat `r::` module.
+//!
+//! When locale-qualified files (`values-fr.xml`) exist, the `r` module also
+//! gets:
+//! - a `Locale` enum (`Locale::Default` plus one variant per locale)
+//! - `locale::<code>` modules holding the translated resources
+//! - `localized::` accessors taking a `Locale` and falling back to the
+//!   default resources when a translation is missing
+
+use crate::generator::analysis::AnalysisWarning;
+use crate::generator::diag::Location;
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::types::{
+    template_signature, TemplateSignature,
+};
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+use crate::generator::utils::{
+    camel_case_identifier, const_identifier, escape_keyword,
+    sanitize_identifier,
+};
+
+use super::emitter::{deprecated_attribute, GraphEmitter};
+use super::tree::{
+    build_namespace_tree, sort_namespace_tree, NamespaceNode,
+};
+
+/// How a translatable resource is exposed in the generated code
+#[derive(PartialEq, Eq)]
+enum Accessor {
+    /// `pub const NAME: &str`
+    Const(String),
+    /// `pub fn name(...) -> String`
+    Function {
+        name: String,
+        signature: TemplateSignature,
+    },
+}
+
+fn accessor(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Option<Accessor> {
+    let constant = const_identifier(&key.name);
+    match &node.value {
+        ResourceValue::String(_) => Some(Accessor::Const(constant)),
+        ResourceValue::Template { text, params } => {
+            Some(match template_signature(text, params) {
+                Some(signature) => Accessor::Function {
+                    name: sanitize_identifier(&key.name),
+                    signature,
+                },
+                None => Accessor::Const(constant),
+            })
+        }
+        _ => None,
+    }
+}
+
+impl Accessor {
+    /// What the accessor is, for diagnostics
+    fn describe(&self) -> String {
+        match self {
+            Self::Const(_) => "a constant".to_string(),
+            Self::Function { signature, .. } => {
+                format!("a function of `({})`", signature.params)
+            }
+        }
+    }
+}
+
+/// Warns about the translations `r::localized::` leaves out because
+/// their accessor differs from the default one (a template
+/// translating a string, or other template parameters); it returns
+/// the default text for those locales
+pub fn check_localized_accessors(
+    graph: &ResourceGraph,
+) -> Vec<AnalysisWarning> {
+    let mut warnings = Vec::new();
+    for (locale, translation) in graph.translations() {
+        for (key, nodes) in translation.nodes() {
+            let Some(node) = nodes.first() else { continue };
+            let Some(base) = graph.get(key) else { continue };
+            if graph.is_conditional(key) {
+                continue;
+            }
+            let Some(expected) = accessor(key, base) else {
+                continue;
+            };
+            let found = accessor(key, node);
+            if found.as_ref() == Some(&expected) {
+                continue;
+            }
+            let found = found.map_or_else(
+                || "not a string or template".to_string(),
+                |found| format!("generated as {}", found.describe()),
+            );
+            let line = node.origin.line.map(|line| line as usize);
+            let file = node.origin.file.to_path_buf();
+            warnings.push(
+                AnalysisWarning::new(
+                    format!(
+                        "Translation of '{}' for locale '{locale}' is {found}, but the default is {}; `r::localized::` returns the default text for this locale",
+                        key.full_name(),
+                        expected.describe()
+                    ),
+                    Some(key.clone()),
+                )
+                .with_location(Some(Location::new(file, line))),
+            );
+        }
+    }
+    warnings
+}
+
+/// Rust identifiers for a locale code: (`PtBr` variant, `pt_br` module)
+fn locale_idents(code: &str) -> (String, String) {
+    let code = code.to_lowercase();
+    (camel_case_identifier(&code), sanitize_identifier(&code))
+}
+
+/// Emits the `Locale` enum, `locale::` and `localized::` modules of
+/// the graph of `emitter`
+pub(super) fn emit_locales(
+    code: &mut dyn CodeSink,
+    emitter: &GraphEmitter<'_>,
+) {
+    let graph = emitter.graph;
+    let locales: Vec<(&str, String, String)> = graph
+        .translations()
+        .keys()
+        .map(|code| {
+            let (variant, module) = locale_idents(code);
+            (code.as_str(), variant, module)
+        })
+        .collect();
+
+    emit_locale_enum(code, &locales);
+
+    code.push_str("    pub mod locale {\n");
+    for (locale, _, module) in &locales {
+        let _ = writeln!(code, "        pub mod {module} {{");
+        let translations = GraphEmitter {
+            graph: &graph.translations()[*locale],
+            ..*emitter
+        };
+        translations.tree(code, 12);
+        code.push_str("        }\n");
+    }
+    code.push_str("    }\n");
+
+    let mut tree = build_namespace_tree(graph);
+    sort_namespace_tree(&mut tree, graph, emitter.ordering);
+    let ctx = LocalizedContext {
+        graph,
+        locales: &locales,
+    };
+    // Accessors may forward to deprecated resources
+    code.push_str("    #[allow(deprecated)]\n    pub mod localized {\n");
+    emit_localized_tree(code, &tree, &ctx, 1);
+    code.push_str("    }\n");
+}
+
+fn emit_locale_enum(
+    code: &mut dyn CodeSink,
+    locales: &[(&str, String, String)],
+) {
+    let variants: String = locales
+        .iter()
+        .map(|(_, variant, _)| format!("        {variant},\n"))
+        .collect();
+    let all: String = locales
+        .iter()
+        .map(|(_, variant, _)| format!(", Self::{variant}"))
+        .collect();
+    let codes: String = locales
+        .iter()
+        .map(|(locale, variant, _)| {
+            format!(
+                "                Self::{variant} => \"{locale}\",\n"
+            )
+        })
+        .collect();
+
+    let _ = write!(
+        code,
+        r#"    /// Locales with translated resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub enum Locale {{
+        /// Default resources
+        #[default]
+        Default,
+{variants}    }}
+
+    impl Locale {{
+        /// Every supported locale, `Default` first
+        pub const ALL: &'static [Self] = &[Self::Default{all}];
+
+        /// Locale code (e.g. `"fr"`), or `"default"`
+        #[must_use]
+        pub const fn code(self) -> &'static str {{
+            match self {{
+                Self::Default => "default",
+{codes}            }}
+        }}
+
+        /// Parses a locale code such as `"fr"` or `"pt-BR"`
+        #[must_use]
+        pub fn from_code(code: &str) -> Option<Self> {{
+            Self::ALL.iter().copied().find(|l| l.code() == code)
+        }}
+    }}
+"#
+    );
+}
+
+struct LocalizedContext<'a> {
+    graph: &'a ResourceGraph,
+    locales: &'a [(&'a str, String, String)],
+}
+
+fn emit_localized_tree(
+    code: &mut dyn CodeSink,
+    node: &NamespaceNode,
+    ctx: &LocalizedContext<'_>,
+    depth: usize,
+) {
+    let pad = " ".repeat(4 * (depth + 1));
+    for (ns_name, child) in &node.children {
+        let _ = writeln!(
+            code,
+            "{pad}pub mod {} {{",
+            sanitize_identifier(ns_name)
+        );
+        emit_localized_tree(code, child, ctx, depth + 1);
+        let _ = writeln!(code, "{pad}}}");
+    }
+
+    for key in &node.resource_keys {
+        emit_localized_accessor(code, key, ctx, depth);
+    }
+}
+
+fn emit_localized_accessor(
+    code: &mut dyn CodeSink,
+    key: &ResourceKey,
+    ctx: &LocalizedContext<'_>,
+    depth: usize,
+) {
+    let Some(node) = ctx.graph.get(key) else {
+        return;
+    };
+    if ctx.graph.is_conditional(key) {
+        return;
+    }
+    let Some(base) = accessor(key, node) else {
+        return;
+    };
+    let pad = " ".repeat(4 * (depth + 1));
+    let root = "super::".repeat(depth);
+    let ns_path: String = key
+        .namespace
+        .iter()
+        .map(|ns| format!("{}::", sanitize_identifier(ns)))
+        .collect();
+
+    // Locales whose translation has the same shape as the default
+    let translated: Vec<(&String, &String)> = ctx
+        .locales
+        .iter()
+        .filter(|(locale, _, _)| {
+            ctx.graph.translations()[*locale]
+                .get(key)
+                .and_then(|n| accessor(key, n))
+                .is_some_and(|a| a == base)
+        })
+        .map(|(_, variant, module)| (variant, module))
+        .collect();
+
+    let (fn_name, params, ret, item) = match &base {
+        Accessor::Const(name) => (
+            escape_keyword(name.to_lowercase()),
+            String::new(),
+            "&'static str",
+            name.clone(),
+        ),
+        Accessor::Function { name, signature } => (
+            name.clone(),
+            format!(", {}", signature.params),
+            "String",
+            format!("{name}({})", signature.args),
+        ),
+    };
+
+    if let Some(note) = &node.meta.deprecated {
+        code.push_str(&deprecated_attribute(&pad, note));
+    }
+    let _ = writeln!(
+        code,
+        "{pad}pub fn {fn_name}(locale: {root}Locale{params}) -> {ret} {{"
+    );
+    if translated.is_empty() {
+        let _ = writeln!(code, "{pad}    let _ = locale;");
+        let _ = writeln!(code, "{pad}    {root}{ns_path}{item}");
+    } else {
+        let _ = writeln!(code, "{pad}    match locale {{");
+        for (variant, module) in translated {
+            let _ = writeln!(
+                code,
+                "{pad}        {root}Locale::{variant} => {root}locale::{module}::{ns_path}{item},"
+            );
+        }
+        let _ = writeln!(
+            code,
+            "{pad}        _ => {root}{ns_path}{item},"
+        );
+        let _ = writeln!(code, "{pad}    }}");
+    }
+    let _ = writeln!(code, "{pad}}}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_idents_are_valid_rust() {
+        assert_eq!(
+            locale_idents("fr"),
+            ("Fr".to_string(), "fr".to_string())
+        );
+        assert_eq!(
+            locale_idents("pt-BR"),
+            ("PtBr".to_string(), "pt_br".to_string())
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Runtime lookup tables for the flat `r::` module.
+//!
+//! Generates `r::lookup` with one sorted static table per value type and
+//! functions binary-searching them by qualified name (`auth/title`):
+//!
+//! ```rust,ignore
+//! r::lookup::string("auth/title")      // Some("Login")
+//! r::lookup::number_i64("max_retries") // Some(3)
+//! r::lookup::names()                   // every resource name
+//! r::lookup::try_string("max_retries")  // Err(RError::TypeMismatch)
+//! ```
+//!
+//! Each getter has a `try_` variant returning `r_resources::RError`:
+//! `ResourceNotFound` for unknown names, `TypeMismatch` for names of
+//! another type.
+//!
+//! Table entries point at the generated constants, so values are not
+//! duplicated. Template functions and `BigDecimal` numbers only appear
+//! in `names()`.
+
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::types::template_signature;
+use crate::generator::ir::{
+    NumberType, NumberValue, ResourceGraph, ResourceKey,
+    ResourceNode, ResourceValue,
+};
+use crate::generator::utils::{
+    const_identifier, escape_str, sanitize_identifier,
+};
+
+/// Value tables, in emission order: (function, table, value type,
+/// described type of `RError::TypeMismatch`)
+const TABLES: [(&str, &str, &str, &str); 5] = [
+    ("string", "STRINGS", "&str", "string"),
+    ("number_i64", "NUMBERS_I64", "i64", "i64 number"),
+    ("number_f64", "NUMBERS_F64", "f64", "f64 number"),
+    ("bool", "BOOLS", "bool", "bool"),
+    ("color", "COLORS", "&str", "color"),
+];
+
+/// Index into [`TABLES`] and the constant expression for a node, with
+/// the constant path starting with `prefix` (e.g. `super::`)
+pub(super) fn table_entry(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    prefix: &str,
+) -> Option<(usize, String)> {
+    let path = const_path(key, prefix);
+    match &node.value {
+        ResourceValue::String(_) => Some((0, path)),
+        ResourceValue::Template { text, params } => {
+            template_signature(text, params)
+                .is_none()
+                .then_some((0, path))
+        }
+        ResourceValue::Number(number) => number_entry(number, path),
+        ResourceValue::Bool(_) => Some((3, path)),
+        ResourceValue::Color(_) => Some((4, path)),
+        // No lookup table for geometry types, versions, addresses,
+        // regexes, UUIDs, dates, amounts, characters and bytes
+        ResourceValue::LatLng { .. }
+        | ResourceValue::Position { .. }
+        | ResourceValue::Semver { .. }
+        | ResourceValue::IpAddr(_)
+        | ResourceValue::SocketAddr(_)
+        | ResourceValue::Regex(_)
+        | ResourceValue::Uuid(_)
+        | ResourceValue::DateTime { .. }
+        | ResourceValue::Date { .. }
+        | ResourceValue::Money { .. }
+        | ResourceValue::Char(_)
+        | ResourceValue::Grapheme(_)
+        | ResourceValue::Bytes(_) => None,
+    }
+}
+
+fn number_entry(
+    number: &NumberValue,
+    path: String,
+) -> Option<(usize, String)> {
+    match number {
+        NumberValue::Int(_) => Some((1, path)),
+        NumberValue::Float(_) => Some((2, path)),
+        NumberValue::BigDecimal(_) => None,
+        NumberValue::Typed { ty, .. } => match ty {
+            NumberType::I64 => Some((1, path)),
+            // u64 and usize may not fit
+            NumberType::U64 | NumberType::Usize => None,
+            NumberType::F64 => Some((2, path)),
+            NumberType::F32 => Some((2, format!("{path} as f64"))),
+            _ => Some((1, format!("{path} as i64"))),
+        },
+    }
+}
+
+/// Path of the generated constant, relative to `prefix`
+fn const_path(key: &ResourceKey, prefix: &str) -> String {
+    let mut path = String::from(prefix);
+    for ns in key.namespace.iter() {
+        path.push_str(&sanitize_identifier(ns));
+        path.push_str("::");
+    }
+    path.push_str(&const_identifier(&key.name));
+    path
+}
+
+/// Primary node of every key with its qualified name, sorted by byte
+/// order of the name; resources under a `cfg` predicate are left
+/// out, as the tables can't name items that may not exist
+pub(super) fn sorted_entries(
+    graph: &ResourceGraph,
+) -> Vec<(String, &ResourceKey, &ResourceNode)> {
+    let mut entries: Vec<(String, &ResourceKey, &ResourceNode)> =
+        graph
+            .nodes()
+            .iter()
+            .filter(|(key, _)| !graph.is_conditional(key))
+            .filter_map(|(key, nodes)| {
+                nodes.first().map(|node| (key.full_name(), key, node))
+            })
+            .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Emits the `lookup` module for `graph`
+pub(super) fn emit_lookup(code: &mut dyn CodeSink, graph: &ResourceGraph) {
+    // Tables are binary-searched by qualified name
+    let entries = sorted_entries(graph);
+
+    let mut tables: [Vec<String>; 5] = Default::default();
+    for (name, key, node) in &entries {
+        if let Some((index, expr)) = table_entry(key, node, "super::") {
+            tables[index].push(format!(
+                "            (\"{}\", {expr}),\n",
+                escape_str(name)
+            ));
+        }
+    }
+
+    code.push_str(
+        "    /// Runtime lookup of resources by qualified name (`auth/title`)\n\
+         \x20   #[allow(deprecated)]\n\
+         \x20   pub mod lookup {\n",
+    );
+    for (table, rows) in TABLES.iter().zip(&tables) {
+        emit_table(code, table, rows);
+    }
+
+    let _ = writeln!(
+        code,
+        "        static NAMES: [&str; {}] = [",
+        entries.len()
+    );
+    for (name, _, _) in &entries {
+        let _ = writeln!(
+            code,
+            "            \"{}\",",
+            escape_str(name)
+        );
+    }
+    code.push_str(
+        r#"        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+"#,
+    );
+}
+
+fn emit_table(
+    code: &mut dyn CodeSink,
+    (function, table, ty, described): &(&str, &str, &str, &str),
+    rows: &[String],
+) {
+    let _ = writeln!(
+        code,
+        "        static {table}: [(&str, {ty}); {}] = [",
+        rows.len()
+    );
+    rows.iter().for_each(|row| code.push_str(row));
+    let returned = ty.replace('&', "&'static ");
+    let _ = write!(
+        code,
+        r#"        ];
+
+        #[must_use]
+        pub fn {function}(name: &str) -> Option<{returned}> {{
+            find(&{table}, name)
+        }}
+
+        /// Same as `{function}`, with the reason of a failed lookup
+        pub fn try_{function}(
+            name: &str,
+        ) -> Result<{returned}, r_resources::RError> {{
+            {function}(name).ok_or_else(|| not_found(name, "{described}"))
+        }}
+
+"#
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{ResourceKind, ResourceOrigin};
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn lookup_code(graph: &ResourceGraph) -> String {
+        let mut code = String::new();
+        emit_lookup(&mut code, graph);
+        code
+    }
+
+    #[test]
+    fn tables_are_sorted_by_qualified_name() {
+        let mut graph = ResourceGraph::default();
+        insert(&mut graph, "zeta", ResourceValue::String("z".into()));
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("t".into()),
+        );
+        insert(
+            &mut graph,
+            "Bravo",
+            ResourceValue::String("b".into()),
+        );
+        let code = lookup_code(&graph);
+
+        let position = |row: &str| code.find(row).expect(row);
+        let auth = position("(\"auth/title\", super::auth::TITLE)");
+        let bravo = position("(\"Bravo\", super::BRAVO)");
+        let zeta = position("(\"zeta\", super::ZETA)");
+        assert!(bravo < auth && auth < zeta, "{code}");
+        assert!(code.contains("static STRINGS: [(&str, &str); 3]"));
+        assert!(code.contains("static NAMES: [&str; 3]"));
+    }
+
+    #[test]
+    fn values_go_to_their_type_table() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "max",
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        insert(
+            &mut graph,
+            "ratio",
+            ResourceValue::Number(NumberValue::Float(0.5)),
+        );
+        insert(
+            &mut graph,
+            "port",
+            ResourceValue::Number(NumberValue::Typed {
+                literal: "8080".into(),
+                ty: NumberType::U16,
+            }),
+        );
+        insert(
+            &mut graph,
+            "price",
+            ResourceValue::Number(NumberValue::BigDecimal(
+                "1.5".into(),
+            )),
+        );
+        insert(&mut graph, "enabled", ResourceValue::Bool(true));
+        insert(
+            &mut graph,
+            "accent",
+            ResourceValue::Color("#FFF".into()),
+        );
+        insert(
+            &mut graph,
+            "welcome",
+            ResourceValue::Template {
+                text: "Hi %1$s".into(),
+                params: Vec::new(),
+            },
+        );
+        let code = lookup_code(&graph);
+
+        assert!(code.contains("(\"max\", super::MAX),"));
+        assert!(code.contains("(\"port\", super::PORT as i64),"));
+        assert!(code.contains("static NUMBERS_I64: [(&str, i64); 2]"));
+        assert!(code.contains("static NUMBERS_F64: [(&str, f64); 1]"));
+        assert!(code.contains("static BOOLS: [(&str, bool); 1]"));
+        assert!(code.contains("static COLORS: [(&str, &str); 1]"));
+        assert!(code.contains("static STRINGS: [(&str, &str); 0]"));
+        // Not in a value table, but listed
+        assert!(!code.contains("super::PRICE"));
+        assert!(!code.contains("super::WELCOME"));
+        assert!(code.contains("static NAMES: [&str; 7]"));
+        assert!(code.contains("            \"price\",\n"));
+        assert!(code.contains(
+            "bool(name).ok_or_else(|| not_found(name, \"bool\"))"
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+//! `r::meta`, what the binary was built with.
+//!
+//! ```rust,ignore
+//! r::meta::GENERATED_AT_UNIX // 1760400000, 0 with `deterministic`
+//! r::meta::RESOURCE_COUNT    // 12
+//! r::meta::PROFILE           // "release"
+//! r::meta::SOURCE_FILES      // ["res/strings.xml", "res/values.xml"]
+//! r::meta::GENERATOR_VERSION // "0.7.0"
+//! r::meta::summary()         // [("string", 2), ...], with registries
+//! ```
+//!
+//! Resources are counted once per qualified name, whatever their value
+//! or namespace, the way the typed modules list them: duplicates and
+//! profile variants count once, test resources and translations not
+//! at all.
+
+use std::collections::BTreeMap;
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::generation::EmitOptions;
+use crate::generator::ir::ResourceGraph;
+use crate::generator::utils::escape_str;
+
+use super::typed::{typed_module, TYPED_MODULES};
+
+/// Types counted by `summary()` that have no typed module
+const UNTYPED: [&str; 1] = ["uuid"];
+
+/// Number of resources of `graph` in each typed module, every module
+/// of [`TYPED_MODULES`] included, then in each of [`UNTYPED`]
+fn type_counts(graph: &ResourceGraph) -> Vec<(&'static str, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for nodes in graph.nodes().values() {
+        if let Some(node) = nodes.first() {
+            // UUIDs are the only resources without a typed module
+            let module = typed_module(node).unwrap_or(UNTYPED[0]);
+            *counts.entry(module).or_default() += 1;
+        }
+    }
+    TYPED_MODULES
+        .iter()
+        .chain(&UNTYPED)
+        .map(|module| {
+            (*module, counts.get(module).copied().unwrap_or(0))
+        })
+        .collect()
+}
+
+/// Emits `r::meta`, with `summary()` when registries are enabled
+pub(super) fn emit_meta(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    options: &EmitOptions<'_>,
+) {
+    let counts = type_counts(graph);
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    let files: Vec<String> = options
+        .source_files
+        .iter()
+        .map(|file| format!("\"{}\"", escape_str(file)))
+        .collect();
+    let _ = writeln!(
+        code,
+        "\n    /// How these resources were generated\n\
+         \x20   pub mod meta {{\n\
+         \x20       /// Unix time of the build, `0` for deterministic builds\n\
+         \x20       pub const GENERATED_AT_UNIX: i64 = {};\n\
+         \x20       /// Number of resources, counted once per qualified name\n\
+         \x20       pub const RESOURCE_COUNT: usize = {total};\n\
+         \x20       /// Profile the resources were selected for\n\
+         \x20       pub const PROFILE: &str = \"{}\";\n\
+         \x20       /// Resource files read, relative and sorted\n\
+         \x20       pub const SOURCE_FILES: &[&str] = &[{}];\n\
+         \x20       /// Version of `r-resources` that generated the code\n\
+         \x20       pub const GENERATOR_VERSION: &str = \"{}\";",
+        options.generated_at,
+        escape_str(options.profile),
+        files.join(", "),
+        env!("CARGO_PKG_VERSION"),
+    );
+    if options.registries {
+        let rows: Vec<String> = counts
+            .iter()
+            .map(|(module, count)| format!("(\"{module}\", {count})"))
+            .collect();
+        let _ = writeln!(
+            code,
+            "\x20       /// Number of resources per type, named like the \
+             typed modules\n\
+             \x20       /// (`(\"string\", 12)`), in a fixed order\n\
+             \x20       #[must_use]\n\
+             \x20       pub const fn summary() -> &'static [(&'static str, usize)] {{\n\
+             \x20           &[{}]\n\
+             \x20       }}",
+            rows.join(", ")
+        );
+    }
+    let _ = writeln!(code, "    }}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        Ordering, ResourceKey, ResourceKind, ResourceNode,
+        ResourceOrigin, ResourceValue,
+    };
+    use crate::generator::options::{LazyStatics, ReferenceStyle};
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn options<'a>(
+        source_files: &'a [String],
+        registries: bool,
+    ) -> EmitOptions<'a> {
+        EmitOptions {
+            profile: "release",
+            lookup: false,
+            provenance_root: None,
+            typed_modules: true,
+            raw_assets: &[],
+            hot_reload: false,
+            registries,
+            json: false,
+            regex: false,
+            flatten_aliases: None,
+            reference_style: ReferenceStyle::Alias,
+            testable: false,
+            source_files,
+            generated_at: 1_700_000_000,
+            ordering: Ordering::Alphabetical,
+            lazy: LazyStatics::LazyLock,
+        }
+    }
+
+    fn graph() -> ResourceGraph {
+        let mut graph = ResourceGraph::default();
+        insert(&mut graph, "a", ResourceValue::String("a".into()));
+        insert(&mut graph, "ui/b", ResourceValue::String("b".into()));
+        insert(&mut graph, "ui/b", ResourceValue::String("c".into()));
+        insert(&mut graph, "on", ResourceValue::Bool(true));
+        insert(
+            &mut graph,
+            "hi",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: Vec::new(),
+            },
+        );
+        graph
+    }
+
+    #[test]
+    fn constants_describe_the_build() {
+        let files =
+            ["res/a.xml".to_string(), "res/b.xml".to_string()];
+        let mut code = String::new();
+        emit_meta(&mut code, &graph(), &options(&files, false));
+
+        for line in [
+            "pub const GENERATED_AT_UNIX: i64 = 1700000000;",
+            "pub const RESOURCE_COUNT: usize = 4;",
+            "pub const PROFILE: &str = \"release\";",
+            "pub const SOURCE_FILES: &[&str] = &[\"res/a.xml\", \"res/b.xml\"];",
+        ] {
+            assert!(code.contains(line), "missing `{line}` in {code}");
+        }
+        assert!(code.contains(&format!(
+            "pub const GENERATOR_VERSION: &str = \"{}\";",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(!code.contains("summary"));
+    }
+
+    #[test]
+    fn summary_counts_every_type() {
+        let mut code = String::new();
+        emit_meta(&mut code, &graph(), &options(&[], true));
+
+        assert!(code.contains(
+            "&[(\"string\", 2), (\"int\", 0), (\"float\", 0), \
+             (\"decimal\", 0), (\"boolean\", 1), (\"color\", 0), \
+             (\"template\", 1), (\"latlng\", 0), (\"position\", 0), \
+             (\"semver\", 0), (\"ipaddr\", 0), (\"socketaddr\", 0), \
+             (\"datetime\", 0), (\"date\", 0), (\"money\", 0), \
+             (\"character\", 0), (\"binary\", 0), (\"uuid\", 0)]"
+        ));
+        assert!(
+            code.contains("pub const SOURCE_FILES: &[&str] = &[];")
+        );
+    }
+}
@@ -19,8 +19,32 @@
 //! }
 //! ```
 
+mod aliases;
+mod config;
 mod emitter;
+mod hot_reload;
+mod json;
+mod keys;
+mod locale;
+mod lookup;
+mod meta;
+mod raw;
+mod registries;
+mod report;
+mod scope;
+mod test_resources;
+mod testable;
 mod tree;
+mod typed;
 
-pub use emitter::generate_r_module;
+pub use aliases::check_flat_aliases;
+pub use config::config_warnings;
+pub use emitter::emit_r_module;
+pub use locale::check_localized_accessors;
+pub use registries::check_registry_names;
+pub use report::{BuildReport, CountingSink};
+pub use scope::check_identifier_collisions;
+pub use test_resources::emit_tests_module;
+pub use testable::check_overridable;
+pub use typed::emit_typed_modules;
 
@@ -0,0 +1,108 @@
+//! `r::raw`: the files of `res/raw/`, embedded with `include_bytes!`.
+//!
+//! ```rust,ignore
+//! r::raw::SPLASH_PNG      // &[u8]
+//! r::raw::LICENSE_TXT_STR // &str, for text files
+//! ```
+//!
+//! Paths are absolute, since the generated file lives in `OUT_DIR`.
+
+use std::path::Path;
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::input::RawAsset;
+use crate::generator::utils::{display_path, string_literal};
+
+/// Emits `pub mod raw` at `indent`, nothing without assets
+pub(super) fn emit_raw_module(
+    code: &mut dyn CodeSink,
+    assets: &[RawAsset],
+    provenance_root: Option<&Path>,
+    indent: usize,
+) {
+    if assets.is_empty() {
+        return;
+    }
+    let pad = " ".repeat(indent);
+    let _ = writeln!(code, "\n{pad}/// Files of `res/raw/`");
+    let _ = writeln!(code, "{pad}pub mod raw {{");
+    for asset in assets {
+        let source = match provenance_root {
+            Some(root) => display_path(&asset.path, root),
+            None => asset
+                .path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        };
+        let path = string_literal(&asset.path.to_string_lossy());
+        let _ = writeln!(
+            code,
+            "{pad}    /// Embedded from `{source}` ({} bytes)\n\
+             {pad}    pub const {}: &[u8] = include_bytes!({path});",
+            asset.size, asset.ident
+        );
+        if let Some(ident) = asset.str_ident() {
+            let _ = writeln!(
+                code,
+                "{pad}    /// Text of `{source}`\n\
+                 {pad}    pub const {ident}: &str = include_str!({path});",
+            );
+        }
+    }
+    let _ = writeln!(code, "{pad}}}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn text_files_get_a_str_constant() {
+        let assets = [
+            RawAsset {
+                path: PathBuf::from("/app/res/raw/LICENSE.txt"),
+                ident: "LICENSE_TXT".into(),
+                text: true,
+                size: 3,
+            },
+            RawAsset {
+                path: PathBuf::from("/app/res/raw/splash.png"),
+                ident: "SPLASH_PNG".into(),
+                text: false,
+                size: 4,
+            },
+        ];
+        let mut code = String::new();
+        emit_raw_module(
+            &mut code,
+            &assets,
+            Some(Path::new("/app/res")),
+            4,
+        );
+
+        assert_eq!(
+            code,
+            r#"
+    /// Files of `res/raw/`
+    pub mod raw {
+        /// Embedded from `res/raw/LICENSE.txt` (3 bytes)
+        pub const LICENSE_TXT: &[u8] = include_bytes!("/app/res/raw/LICENSE.txt");
+        /// Text of `res/raw/LICENSE.txt`
+        pub const LICENSE_TXT_STR: &str = include_str!("/app/res/raw/LICENSE.txt");
+        /// Embedded from `res/raw/splash.png` (4 bytes)
+        pub const SPLASH_PNG: &[u8] = include_bytes!("/app/res/raw/splash.png");
+    }
+"#
+        );
+    }
+
+    #[test]
+    fn no_module_without_assets() {
+        let mut code = String::new();
+        emit_raw_module(&mut code, &[], None, 4);
+        assert!(code.is_empty());
+    }
+}
@@ -0,0 +1,336 @@
+//! Per-type registries, for debug screens and diffing.
+//!
+//! With `BuildOptions::registries`, each typed module and each of its
+//! namespace modules gets an `ALL` table of the resources below it,
+//! sorted by qualified name, plus `r::meta::summary()` with the counts
+//! per type (see `meta`):
+//!
+//! ```rust,ignore
+//! string::ALL       // [("app_name", APP_NAME), ("auth/title", auth::TITLE)]
+//! string::auth::ALL // [("auth/title", TITLE)]
+//! template::ALL     // ["auth/greeting"], names only
+//! r::meta::summary() // [("string", 2), ("int", 0), ...]
+//! ```
+//!
+//! Integers are listed as `i128` and floats as `f64`, so every typed
+//! number fits; `BigDecimal` statics are listed by reference.
+
+use crate::generator::analysis::AnalysisError;
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::{
+    NumberType, NumberValue, ResourceGraph, ResourceNode,
+    ResourceValue,
+};
+use crate::generator::options::LazyStatics;
+use crate::generator::utils::{escape_str, sanitize_identifier};
+
+use super::scope::item_identifier;
+use super::tree::NamespaceNode;
+use super::typed::typed_module;
+
+/// Name of the tables, next to the re-exported items
+const REGISTRY: &str = "ALL";
+
+/// Typed module of an `ALL` table, with the type of its statics
+#[derive(Clone, Copy)]
+pub(super) struct Registry<'a> {
+    pub(super) module: &'a str,
+    pub(super) lazy: LazyStatics,
+}
+
+/// Element type of the `ALL` table of a typed module, `None` for
+/// `template`, which lists names only
+fn entry_type(registry: Registry<'_>) -> Option<String> {
+    if registry.module == "decimal" {
+        let lazy = registry.lazy.path();
+        return Some(format!("&{lazy}<r_resources::BigDecimal>"));
+    }
+    Some(match registry.module {
+        "string" | "color" => "&str",
+        "int" => "i128",
+        "float" => "f64",
+        "boolean" => "bool",
+        "latlng" => "r_resources::LatLng",
+        "position" => "r_resources::Position",
+        "semver" => "r_resources::Version",
+        "ipaddr" => "std::net::IpAddr",
+        "socketaddr" => "std::net::SocketAddr",
+        "datetime" => "i64",
+        "date" => "r_resources::Date",
+        "money" => "r_resources::Money",
+        "character" => "char",
+        "binary" => "&[u8]",
+        _ => return None,
+    }
+    .to_owned())
+}
+
+/// Item path converted to the element type of its table
+fn entry_value(node: &ResourceNode, path: String) -> String {
+    match &node.value {
+        ResourceValue::Number(NumberValue::Int(_)) => {
+            format!("{path} as i128")
+        }
+        ResourceValue::Number(NumberValue::BigDecimal(_)) => {
+            format!("&{path}")
+        }
+        ResourceValue::Number(NumberValue::Typed { ty, .. }) => {
+            match ty {
+                NumberType::F64 => path,
+                NumberType::F32 => format!("{path} as f64"),
+                _ => format!("{path} as i128"),
+            }
+        }
+        _ => path,
+    }
+}
+
+/// Qualified names and node of the resources below `node`, with their
+/// path relative to it (`auth::TITLE`), sorted by name
+fn subtree_entries<'a>(
+    graph: &'a ResourceGraph,
+    node: &NamespaceNode,
+    prefix: &str,
+    entries: &mut Vec<(String, String, &'a ResourceNode)>,
+) {
+    for key in &node.resource_keys {
+        if graph.is_conditional(key) {
+            continue;
+        }
+        if let Some(resource) = graph.get(key) {
+            let path =
+                format!("{prefix}{}", item_identifier(key, resource));
+            entries.push((key.full_name(), path, resource));
+        }
+    }
+    for (ns_name, child) in &node.children {
+        let prefix =
+            format!("{prefix}{}::", sanitize_identifier(ns_name));
+        subtree_entries(graph, child, &prefix, entries);
+    }
+}
+
+/// Emits the `ALL` table of `node`, one of the modules of the typed
+/// module of `registry`
+pub(super) fn emit_registry(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    registry: Registry<'_>,
+    node: &NamespaceNode,
+    pad: &str,
+) {
+    let module = registry.module;
+    let mut entries = Vec::new();
+    subtree_entries(graph, node, "", &mut entries);
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let Some(ty) = entry_type(registry) else {
+        let _ = writeln!(
+            code,
+            "{pad}/// Qualified names of the templates of this module\n\
+             {pad}pub const {REGISTRY}: &[&str] = &["
+        );
+        for (name, _, _) in &entries {
+            let _ =
+                writeln!(code, "{pad}    \"{}\",", escape_str(name));
+        }
+        let _ = writeln!(code, "{pad}];");
+        return;
+    };
+    // `BigDecimal` values are statics, which a `const` cannot refer to
+    let item = if module == "decimal" {
+        "static"
+    } else {
+        "const"
+    };
+    let _ = writeln!(
+        code,
+        "{pad}/// Resources of this module, by qualified name\n\
+         {pad}#[allow(deprecated)]\n\
+         {pad}pub {item} {REGISTRY}: &[(&str, {ty})] = &["
+    );
+    for (name, path, resource) in entries {
+        let _ = writeln!(
+            code,
+            "{pad}    (\"{}\", {}),",
+            escape_str(&name),
+            entry_value(resource, path)
+        );
+    }
+    let _ = writeln!(code, "{pad}];");
+}
+
+/// Rejects resources that would shadow the `ALL` table of their
+/// typed module
+pub fn check_registry_names(
+    graph: &ResourceGraph,
+) -> Vec<AnalysisError> {
+    graph
+        .nodes()
+        .iter()
+        .filter_map(|(key, nodes)| {
+            let node = nodes.first()?;
+            if item_identifier(key, node) != REGISTRY {
+                return None;
+            }
+            let mut module = typed_module(node)?.to_string();
+            for ns in key.namespace.iter() {
+                module.push_str("::");
+                module.push_str(&sanitize_identifier(ns));
+            }
+            Some(AnalysisError::new(
+                format!(
+                    "Resource '{}' collides with the generated `{module}::{REGISTRY}` registry; please rename it or disable registries",
+                    key.full_name()
+                ),
+                Some(key.clone()),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKey, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn registry(module: &str) -> Registry<'_> {
+        Registry {
+            module,
+            lazy: LazyStatics::LazyLock,
+        }
+    }
+
+    fn tree(graph: &ResourceGraph) -> NamespaceNode {
+        let mut tree = NamespaceNode::default();
+        for key in graph.nodes().keys() {
+            tree.insert_key(key);
+        }
+        tree
+    }
+
+    #[test]
+    fn tables_list_the_subtree_by_qualified_name() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Login".into()),
+        );
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("Demo".into()),
+        );
+        let mut code = String::new();
+        emit_registry(
+            &mut code,
+            &graph,
+            registry("string"),
+            &tree(&graph),
+            "",
+        );
+
+        assert_eq!(
+            code,
+            "/// Resources of this module, by qualified name\n\
+             #[allow(deprecated)]\n\
+             pub const ALL: &[(&str, &str)] = &[\n    \
+                 (\"app_name\", APP_NAME),\n    \
+                 (\"auth/title\", auth::TITLE),\n\
+             ];\n"
+        );
+    }
+
+    #[test]
+    fn numbers_are_widened() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "max",
+            ResourceValue::Number(NumberValue::Typed {
+                literal: "255".into(),
+                ty: NumberType::U8,
+            }),
+        );
+        insert(
+            &mut graph,
+            "retries",
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        let mut code = String::new();
+        emit_registry(
+            &mut code,
+            &graph,
+            registry("int"),
+            &tree(&graph),
+            "",
+        );
+
+        assert!(code.contains("pub const ALL: &[(&str, i128)] = &["));
+        assert!(code.contains("(\"max\", MAX as i128),"));
+        assert!(code.contains("(\"retries\", RETRIES as i128),"));
+    }
+
+    #[test]
+    fn templates_are_listed_by_name() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "auth/greeting",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: Vec::new(),
+            },
+        );
+        let mut code = String::new();
+        emit_registry(
+            &mut code,
+            &graph,
+            registry("template"),
+            &tree(&graph),
+            "",
+        );
+
+        assert!(code.contains(
+            "pub const ALL: &[&str] = &[\n    \"auth/greeting\",\n];"
+        ));
+    }
+
+    #[test]
+    fn resources_named_all_are_rejected() {
+        let mut graph = ResourceGraph::default();
+        insert(&mut graph, "ui/all", ResourceValue::Bool(true));
+        insert(&mut graph, "every", ResourceValue::Bool(true));
+
+        let messages: Vec<String> = check_registry_names(&graph)
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["Resource 'ui/all' collides with the generated `boolean::ui::ALL` registry; please rename it or disable registries"]
+        );
+    }
+}
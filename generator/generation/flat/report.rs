@@ -0,0 +1,326 @@
+//! Usage and size report of `BuildOptions::report`.
+//!
+//! Built from the graph the emitters were given (after profile
+//! selection, reference resolution and widening) and from the code
+//! they wrote, streamed through a [`CountingSink`]:
+//!
+//! ```text
+//! r-resources report (profile release)
+//! resources: 14 (string: 9, int: 3, binary: 2)
+//! namespaces: r: 5, r::auth: 6, r::ui: 3
+//! string data: 2097321 bytes
+//! largest: r::SCHEMA_JSON (2097152 bytes), r::raw::LOGO_PNG (4096 bytes), ...
+//! generated constants: 31
+//! ```
+//!
+//! Resources are counted once per qualified name, like
+//! `r::meta::RESOURCE_COUNT`; translations add to the string data and
+//! the largest resources, test resources count as generated constants
+//! only.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::input::RawAsset;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+use crate::generator::utils::sanitize_identifier;
+
+use super::scope::item_identifier;
+use super::typed::typed_module;
+
+/// Number of resources listed as the largest
+const LARGEST: usize = 10;
+
+/// Sink counting the `pub const` and `pub static` items written to
+/// the sink it wraps
+pub struct CountingSink<'a> {
+    inner: &'a mut dyn CodeSink,
+    /// Start of the line being written
+    line: String,
+    constants: usize,
+}
+
+impl<'a> CountingSink<'a> {
+    pub fn new(inner: &'a mut dyn CodeSink) -> Self {
+        Self {
+            inner,
+            line: String::new(),
+            constants: 0,
+        }
+    }
+
+    /// Number of constants and statics written
+    pub fn finish(mut self) -> usize {
+        self.count_line();
+        self.constants
+    }
+
+    fn count_line(&mut self) {
+        let item = self.line.trim_start();
+        let constant = item.starts_with("pub static ")
+            || (item.starts_with("pub const ")
+                && !item.starts_with("pub const fn "));
+        self.constants += usize::from(constant);
+        self.line.clear();
+    }
+}
+
+impl fmt::Write for CountingSink<'_> {
+    fn write_str(&mut self, code: &str) -> fmt::Result {
+        let mut rest = code;
+        while let Some(end) = rest.find('\n') {
+            self.line.push_str(&rest[..end]);
+            self.count_line();
+            rest = &rest[end + 1..];
+        }
+        self.line.push_str(rest);
+        self.inner.write_str(code)
+    }
+}
+
+impl CodeSink for CountingSink<'_> {}
+
+/// What a build generated, see the module documentation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildReport {
+    pub profile: String,
+    /// Resources per typed module name (`string`, `int`, `uuid`),
+    /// most numerous first
+    pub types: Vec<(&'static str, usize)>,
+    /// Resources per module path (`r::auth`)
+    pub namespaces: BTreeMap<String, usize>,
+    /// Bytes of the strings, templates and regexes
+    pub string_bytes: u64,
+    /// Item paths and sizes in bytes of the largest strings, byte
+    /// arrays and raw files, largest first
+    pub largest: Vec<(String, u64)>,
+    /// `pub const` and `pub static` items of the generated code
+    pub constants: usize,
+}
+
+/// Bytes embedded by `node`, `None` for values without a payload
+fn payload_bytes(node: &ResourceNode) -> Option<u64> {
+    let bytes = match &node.value {
+        ResourceValue::String(text)
+        | ResourceValue::Regex(text)
+        | ResourceValue::Grapheme(text)
+        | ResourceValue::Template { text, .. } => text.len(),
+        ResourceValue::Bytes(bytes) => bytes.len(),
+        _ => return None,
+    };
+    Some(bytes as u64)
+}
+
+/// Item path, size and whether it is text, of each resource of
+/// `graph` embedding bytes; `locale` is appended to translations
+fn payloads(
+    graph: &ResourceGraph,
+    locale: Option<&str>,
+) -> Vec<(String, u64, bool)> {
+    graph
+        .nodes()
+        .iter()
+        .filter_map(|(key, nodes)| {
+            let node = nodes.first()?;
+            let bytes = payload_bytes(node)?;
+            let path = item_path(key, node);
+            let path = match locale {
+                Some(locale) => format!("{path} ({locale})"),
+                None => path,
+            };
+            let text = !matches!(node.value, ResourceValue::Bytes(_));
+            Some((path, bytes, text))
+        })
+        .collect()
+}
+
+/// `r::auth::TITLE`, the path of the item of `key`
+fn item_path(key: &ResourceKey, node: &ResourceNode) -> String {
+    let mut path = module_path(key);
+    path.push_str("::");
+    path.push_str(&item_identifier(key, node));
+    path
+}
+
+/// `r`, or `r::auth::errors` for a namespaced resource
+fn module_path(key: &ResourceKey) -> String {
+    let mut path = String::from("r");
+    for ns in key.namespace.iter() {
+        path.push_str("::");
+        path.push_str(&sanitize_identifier(ns));
+    }
+    path
+}
+
+impl BuildReport {
+    /// Report of `graph` and the `raw_assets` embedded with it, whose
+    /// generated code had `constants` constants
+    pub fn new(
+        graph: &ResourceGraph,
+        raw_assets: &[RawAsset],
+        profile: &str,
+        constants: usize,
+    ) -> Self {
+        let mut types: BTreeMap<&'static str, usize> =
+            BTreeMap::new();
+        let mut namespaces = BTreeMap::new();
+        for (key, nodes) in graph.nodes() {
+            let Some(node) = nodes.first() else { continue };
+            let module = typed_module(node).unwrap_or("uuid");
+            *types.entry(module).or_default() += 1;
+            *namespaces.entry(module_path(key)).or_default() += 1;
+        }
+        let mut items = payloads(graph, None);
+        for (locale, translation) in graph.translations() {
+            items.extend(payloads(translation, Some(locale)));
+        }
+        let string_bytes = items
+            .iter()
+            .filter(|(_, _, text)| *text)
+            .map(|(_, bytes, _)| bytes)
+            .sum();
+        let mut largest: Vec<(String, u64)> = items
+            .into_iter()
+            .map(|(path, bytes, _)| (path, bytes))
+            .chain(raw_assets.iter().map(|asset| {
+                (format!("r::raw::{}", asset.ident), asset.size)
+            }))
+            .collect();
+        largest.sort_by(|a, b| {
+            b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))
+        });
+        largest.truncate(LARGEST);
+        let mut types: Vec<_> = types.into_iter().collect();
+        types.sort_by(|a, b| {
+            b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0))
+        });
+        Self {
+            profile: profile.to_owned(),
+            types,
+            namespaces,
+            string_bytes,
+            largest,
+            constants,
+        }
+    }
+
+    /// The report as text, one line per section
+    pub fn lines(&self) -> Vec<String> {
+        let total: usize = self.types.iter().map(|(_, n)| n).sum();
+        let list = |items: Vec<String>| {
+            if items.is_empty() {
+                "none".to_owned()
+            } else {
+                items.join(", ")
+            }
+        };
+        let types = self
+            .types
+            .iter()
+            .map(|(ty, count)| format!("{ty}: {count}"))
+            .collect();
+        let namespaces = self
+            .namespaces
+            .iter()
+            .map(|(module, count)| format!("{module}: {count}"))
+            .collect();
+        let largest = self
+            .largest
+            .iter()
+            .map(|(path, bytes)| format!("{path} ({bytes} bytes)"))
+            .collect();
+        vec![
+            format!("r-resources report (profile {})", self.profile),
+            format!("resources: {total} ({})", list(types)),
+            format!("namespaces: {}", list(namespaces)),
+            format!("string data: {} bytes", self.string_bytes),
+            format!("largest: {}", list(largest)),
+            format!("generated constants: {}", self.constants),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberValue, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn constants_are_counted_across_writes() {
+        let mut code = String::new();
+        let mut sink = CountingSink::new(&mut code);
+        sink.push_str("pub mod r {\n    pub co");
+        sink.push_str("nst A: i64 = 1;\n    pub const fn a() {}\n");
+        sink.push_str("    pub static B: u8 = 2;\n}\n");
+        sink.push_str("pub const C: u8 = 3;");
+        assert_eq!(sink.finish(), 3);
+        assert!(code.ends_with("pub const C: u8 = 3;"));
+    }
+
+    #[test]
+    fn reports_count_types_namespaces_and_sizes() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "title",
+            ResourceValue::String("Hi".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/blob",
+            ResourceValue::String("x".repeat(100)),
+        );
+        insert(
+            &mut graph,
+            "auth/retries",
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        insert(
+            &mut graph,
+            "auth/key",
+            ResourceValue::Bytes(vec![0; 16]),
+        );
+        let raw = RawAsset {
+            path: PathBuf::from("res/raw/logo.png"),
+            ident: "LOGO_PNG".into(),
+            text: false,
+            size: 50,
+        };
+        let report = BuildReport::new(&graph, &[raw], "release", 7);
+        assert_eq!(
+            report.lines(),
+            [
+                "r-resources report (profile release)",
+                "resources: 4 (string: 2, binary: 1, int: 1)",
+                "namespaces: r: 1, r::auth: 3",
+                "string data: 102 bytes",
+                "largest: r::auth::BLOB (100 bytes), r::raw::LOGO_PNG (50 bytes), r::auth::KEY (16 bytes), r::TITLE (2 bytes)",
+                "generated constants: 7",
+            ]
+        );
+    }
+}
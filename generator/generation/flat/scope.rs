@@ -0,0 +1,413 @@
+//! Identifier collision checks for the flat `r::` module.
+//!
+//! Sanitization can map different resource names to the same Rust
+//! identifier (`foo-bar` and `foo.bar` both become `FOO_BAR`). Such
+//! collisions are reported before any code is generated, instead of
+//! surfacing as a rustc error inside `OUT_DIR`.
+//!
+//! Constants, statics, template functions and child modules of one
+//! module are checked as a single scope, so a template `auth()` next to
+//! a `<ns name="auth">` is reported too.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::generator::analysis::AnalysisError;
+use crate::generator::diag::Location;
+use crate::generator::ir::types::template_signature;
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::utils::{
+    const_identifier, sanitize_identifier,
+};
+
+use super::config::config_identifiers;
+
+/// Origin of a generated item: (`resource`/`namespace`/`config
+/// struct`, name) → file and line of the resource adding it
+pub(super) type Sources =
+    BTreeMap<(&'static str, String), (Arc<Path>, Option<u32>)>;
+
+/// Generated identifiers of one module
+pub(super) type Scope = BTreeMap<String, Sources>;
+
+/// Checks every module of `graph`, of its translations and of its test
+/// resources
+pub fn check_identifier_collisions(
+    graph: &ResourceGraph,
+) -> Vec<AnalysisError> {
+    let mut errors = check_graph(graph, "r");
+    for (locale, translation) in graph.translations() {
+        let root = format!(
+            "r::locale::{}",
+            sanitize_identifier(&locale.to_lowercase())
+        );
+        errors.extend(check_graph(translation, &root));
+    }
+    if let Some(tests) = graph.tests() {
+        errors.extend(check_graph(tests, "r_tests"));
+    }
+    errors
+}
+
+fn check_graph(
+    graph: &ResourceGraph,
+    root: &str,
+) -> Vec<AnalysisError> {
+    let mut scopes: BTreeMap<&[String], Scope> = BTreeMap::new();
+    // Modules already added to their parent, by the first resource
+    let mut modules: HashSet<&[String]> = HashSet::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        let origin = &node.origin;
+        let scope = scopes.entry(&key.namespace).or_default();
+        for ident in resource_identifiers(key, node) {
+            let source = ("resource", key.name.to_string());
+            add_item(scope, ident, source, origin);
+        }
+        // Each namespace level is a child module of its parent
+        for (depth, ns) in key.namespace.iter().enumerate() {
+            if !modules.insert(&key.namespace[..=depth]) {
+                continue;
+            }
+            add_item(
+                scopes
+                    .entry(&key.namespace[..depth])
+                    .or_default(),
+                sanitize_identifier(ns),
+                ("namespace", ns.clone()),
+                origin,
+            );
+            let path = &key.namespace[..=depth];
+            if graph.config_namespaces().contains(path) {
+                let parent = scopes.entry(&path[..depth]);
+                add_config_struct(parent.or_default(), ns, origin);
+            }
+        }
+    }
+
+    scopes
+        .iter()
+        .flat_map(|(namespace, scope)| {
+            let module = module_path(root, namespace);
+            scope.iter().filter(|(_, sources)| sources.len() > 1).map(
+                move |(ident, sources)| {
+                    collision_error(
+                        &module, ident, namespace, sources,
+                    )
+                },
+            )
+        })
+        .collect()
+}
+
+pub(super) fn add_item(
+    scope: &mut Scope,
+    ident: String,
+    source: (&'static str, String),
+    origin: &ResourceOrigin,
+) {
+    scope
+        .entry(ident)
+        .or_default()
+        .entry(source)
+        .or_insert_with(|| (Arc::clone(&origin.file), origin.line));
+}
+
+/// Adds the `HTTP` constant and `HttpConfig` struct of the config
+/// namespace `ns` to the scope of its parent
+fn add_config_struct(
+    scope: &mut Scope,
+    ns: &str,
+    origin: &ResourceOrigin,
+) {
+    for ident in config_identifiers(ns) {
+        let source = ("config struct", ns.to_string());
+        add_item(scope, ident, source, origin);
+    }
+}
+
+/// Identifier generated for a resource: a function for templates with
+/// placeholders, the `USERNAME_PATTERN` text of a regex, the
+/// `PROMO_ENDS_UNIX` seconds of a date-time, a constant or static
+/// otherwise
+pub(super) fn item_identifier(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> String {
+    if is_function(node) {
+        sanitize_identifier(&key.name)
+    } else if matches!(node.value, ResourceValue::Regex(_)) {
+        format!("{}_PATTERN", const_identifier(&key.name))
+    } else if matches!(node.value, ResourceValue::DateTime { .. }) {
+        format!("{}_UNIX", const_identifier(&key.name))
+    } else {
+        const_identifier(&key.name)
+    }
+}
+
+/// Whether `node` generates a function: a template with placeholders
+pub(super) fn is_function(node: &ResourceNode) -> bool {
+    matches!(
+        &node.value,
+        ResourceValue::Template { text, params }
+            if template_signature(text, params).is_some()
+    )
+}
+
+/// Identifiers generated for a resource: its item, plus the
+/// `POOL_SIZE_MIN`/`POOL_SIZE_MAX` bounds a number sets, the
+/// `APP_VERSION_STR` text of a version, the `_BYTES` and `_STR` of a
+/// UUID, the `_STR` of a date-time, the `CACHE_SIZE_USIZE` widenings
+/// of an integer and the `USERNAME` static of a regex, reserved with
+/// or without the `regex` feature
+pub(super) fn resource_identifiers(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> Vec<String> {
+    let ident = item_identifier(key, node);
+    let mut idents = Vec::new();
+    if let Some(range) = &node.meta.range {
+        let bounds = [("MIN", &range.min), ("MAX", &range.max)];
+        for (suffix, _) in bounds.iter().filter(|b| b.1.is_some()) {
+            idents.push(format!("{ident}_{suffix}"));
+        }
+    }
+    for ty in &node.meta.widen {
+        idents.push(format!("{ident}_{}", ty.to_ascii_uppercase()));
+    }
+    if matches!(node.value, ResourceValue::Uuid(_)) {
+        idents.push(format!("{ident}_BYTES"));
+    }
+    if matches!(
+        node.value,
+        ResourceValue::Semver { .. } | ResourceValue::Uuid(_)
+    ) {
+        idents.push(format!("{ident}_STR"));
+    }
+    if matches!(node.value, ResourceValue::Regex(_)) {
+        idents.push(const_identifier(&key.name));
+    }
+    if matches!(node.value, ResourceValue::DateTime { .. }) {
+        idents.push(format!("{}_STR", const_identifier(&key.name)));
+    }
+    idents.push(ident);
+    idents
+}
+
+fn module_path(root: &str, namespace: &[String]) -> String {
+    std::iter::once(root.to_string())
+        .chain(namespace.iter().map(|ns| sanitize_identifier(ns)))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+pub(super) fn collision_error(
+    module: &str,
+    ident: &str,
+    namespace: &[String],
+    sources: &Sources,
+) -> AnalysisError {
+    let locations: Vec<Location> = sources
+        .values()
+        .map(|(file, line)| {
+            let line = line.map(|line| line as usize);
+            Location::new(file.to_path_buf(), line)
+        })
+        .collect();
+    let described: Vec<String> = sources
+        .keys()
+        .zip(&locations)
+        .map(|((kind, name), location)| {
+            format!("{kind} '{name}' ({location})")
+        })
+        .collect();
+    let key = sources
+        .keys()
+        .next()
+        .map(|(_, name)| ResourceKey::new(namespace.to_vec(), name));
+    // At the first source, the one of the key
+    let location = locations.into_iter().next();
+    AnalysisError::new(
+        format!(
+            "Identifier collision in `{module}`: {} all generate `{ident}`; rename one of them",
+            described.join(", ")
+        ),
+        key,
+    )
+    .with_location(location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::generator::ir::TemplateParam;
+    use crate::generator::ir::{
+        NodeMeta, NumberRange, NumberValue, ResourceKind,
+        ResourceOrigin,
+    };
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        file: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from(file),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn string(graph: &mut ResourceGraph, path: &str, file: &str) {
+        insert(graph, path, file, ResourceValue::String("x".into()));
+    }
+
+    fn messages(graph: &ResourceGraph) -> Vec<String> {
+        check_identifier_collisions(graph)
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[test]
+    fn distinct_names_do_not_collide() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "auth/title", "a.xml");
+        string(&mut graph, "auth/body", "a.xml");
+        string(&mut graph, "title", "b.xml");
+        assert!(messages(&graph).is_empty());
+    }
+
+    #[test]
+    fn sanitized_consts_collide() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "ui/foo-bar", "a.xml");
+        string(&mut graph, "ui/foo.bar", "b.xml");
+        assert_eq!(
+            messages(&graph),
+            vec![
+                "Identifier collision in `r::ui`: resource 'foo-bar' (a.xml), resource 'foo.bar' (b.xml) all generate `FOO_BAR`; rename one of them"
+            ]
+        );
+    }
+
+    #[test]
+    fn non_ascii_names_collide_with_sanitized_ones() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "Cr\u{e8}me", "a.xml");
+        string(&mut graph, "CR\u{c8}ME", "b.xml");
+        let errors = messages(&graph);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`CR\u{c8}ME`"));
+    }
+
+    #[test]
+    fn sanitized_namespaces_collide() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "foo-bar/a", "a.xml");
+        string(&mut graph, "foo.bar/b", "b.xml");
+        assert_eq!(
+            messages(&graph),
+            vec![
+                "Identifier collision in `r`: namespace 'foo-bar' (a.xml), namespace 'foo.bar' (b.xml) all generate `foo_bar`; rename one of them"
+            ]
+        );
+    }
+
+    #[test]
+    fn config_structs_collide_in_the_parent_module() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "http/timeout", "a.xml");
+        string(&mut graph, "http", "b.xml");
+        graph.mark_config_namespace(vec!["http".to_string()]);
+        assert_eq!(
+            messages(&graph),
+            vec![
+                "Identifier collision in `r`: config struct 'http' (a.xml), resource 'http' (b.xml) all generate `HTTP`; rename one of them"
+            ]
+        );
+    }
+
+    #[test]
+    fn template_function_collides_with_child_module() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "auth/title", "a.xml");
+        insert(
+            &mut graph,
+            "auth",
+            "b.xml",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: vec![TemplateParam {
+                    name: "name".into(),
+                    value: crate::generator::ir::TemplateParamValue::String,
+                }],
+            },
+        );
+        assert_eq!(
+            messages(&graph),
+            vec![
+                "Identifier collision in `r`: namespace 'auth' (a.xml), resource 'auth' (b.xml) all generate `auth`; rename one of them"
+            ]
+        );
+    }
+
+    #[test]
+    fn number_bounds_collide_with_their_names() {
+        let mut graph = ResourceGraph::default();
+        graph.insert(
+            ResourceKey::from_path("pool_size"),
+            ResourceNode {
+                meta: NodeMeta {
+                    range: Some(NumberRange {
+                        min: None,
+                        max: Some(NumberValue::Int(512)),
+                    }),
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(
+                    ResourceKind::Number,
+                    ResourceValue::Number(NumberValue::Int(64)),
+                    ResourceOrigin::new(
+                        PathBuf::from("a.xml"),
+                        false,
+                    ),
+                )
+            },
+        );
+        string(&mut graph, "pool_size_max", "b.xml");
+        // Only the bounds that are set are generated
+        string(&mut graph, "pool_size_min", "b.xml");
+        assert_eq!(
+            messages(&graph),
+            vec![
+                "Identifier collision in `r`: resource 'pool_size' (a.xml), resource 'pool_size_max' (b.xml) all generate `POOL_SIZE_MAX`; rename one of them"
+            ]
+        );
+    }
+
+    #[test]
+    fn translations_are_checked() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "a-b", "values.xml");
+        let fr = graph.translation_mut("fr");
+        string(fr, "a-b", "values-fr.xml");
+        string(fr, "a.b", "values-fr.xml");
+        let errors = messages(&graph);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .starts_with("Identifier collision in `r::locale::fr`"));
+    }
+}
@@ -0,0 +1,188 @@
+//! `r_tests::`, the resources of `res/tests/`.
+//!
+//! The module is wrapped in `#[cfg(test)]`, so fixtures only exist in
+//! test builds, without any environment setup. Each of its modules
+//! glob re-exports the matching `r::` module: a test resource shadows
+//! the default resource of the same name, and the other default
+//! resources stay reachable:
+//!
+//! ```rust,ignore
+//! assert_eq!(r_tests::auth::TITLE, "Test login"); // overridden
+//! assert_eq!(r_tests::auth::SUBMIT, r::auth::SUBMIT);
+//! ```
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::generation::EmitOptions;
+use crate::generator::ir::{ResourceGraph, TypeRegistry};
+use crate::generator::utils::sanitize_identifier;
+
+use super::emitter::{doc_comment, GraphEmitter};
+use super::tree::{
+    build_namespace_tree, sort_namespace_tree, NamespaceNode,
+};
+
+/// Emits `r_tests` when `graph` has test resources
+pub fn emit_tests_module(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    registry: &TypeRegistry,
+    options: &EmitOptions<'_>,
+) {
+    let ordering = options.ordering;
+    let Some(tests) = graph.tests() else {
+        return;
+    };
+    let mut tree = build_namespace_tree(tests);
+    sort_namespace_tree(&mut tree, tests, ordering);
+    let defaults = build_namespace_tree(graph);
+
+    let emitter = ModuleEmitter(GraphEmitter {
+        graph: tests,
+        registry,
+        provenance_root: options.provenance_root,
+        ordering,
+        lazy: options.lazy,
+    });
+    code.push_str("\n#[cfg(test)]\npub mod r_tests {\n");
+    emitter.emit(code, &tree, Some(&defaults), &[]);
+    code.push_str("}\n");
+}
+
+struct ModuleEmitter<'a>(GraphEmitter<'a>);
+
+impl ModuleEmitter<'_> {
+    /// Emits the body of the module at `path`; `defaults` is the
+    /// matching `r::` module, if there is one
+    fn emit(
+        &self,
+        code: &mut dyn CodeSink,
+        node: &NamespaceNode,
+        defaults: Option<&NamespaceNode>,
+        path: &[String],
+    ) {
+        let indent = 4 * (path.len() + 1);
+        let pad = " ".repeat(indent);
+        if defaults.is_some() {
+            // One `super::` per module between this one and the
+            // include site
+            let supers = "super::".repeat(path.len() + 1);
+            let modules: String =
+                path.iter().map(|m| format!("{m}::")).collect();
+            let _ =
+                writeln!(code, "{pad}pub use {supers}r::{modules}*;");
+        }
+
+        for (ns_name, child) in &node.children {
+            let module = sanitize_identifier(ns_name);
+            let _ = writeln!(code, "{pad}pub mod {module} {{");
+            if let Some(doc) = &child.doc {
+                code.push_str(&doc_comment(
+                    &format!("{pad}    "),
+                    "//!",
+                    doc,
+                ));
+            }
+            let mut child_path = path.to_vec();
+            child_path.push(module);
+            self.emit(
+                code,
+                child,
+                defaults.and_then(|d| d.children.get(ns_name)),
+                &child_path,
+            );
+            let _ = writeln!(code, "{pad}}}");
+        }
+
+        for key in &node.resource_keys {
+            self.0.resource(code, key, indent);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        Ordering, ResourceKey, ResourceKind, ResourceNode,
+        ResourceOrigin, ResourceValue,
+    };
+    use crate::generator::options::{LazyStatics, ReferenceStyle};
+    use std::path::PathBuf;
+
+    fn string(graph: &mut ResourceGraph, path: &str, text: &str) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String(text.to_string()),
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn tests_code(graph: &ResourceGraph) -> String {
+        let options = EmitOptions {
+            profile: "debug",
+            lookup: false,
+            provenance_root: None,
+            typed_modules: false,
+            raw_assets: &[],
+            hot_reload: false,
+            registries: false,
+            json: false,
+            regex: false,
+            flatten_aliases: None,
+            reference_style: ReferenceStyle::Alias,
+            testable: false,
+            source_files: &[],
+            generated_at: 0,
+            ordering: Ordering::Alphabetical,
+            lazy: LazyStatics::LazyLock,
+        };
+        let mut code = String::new();
+        emit_tests_module(
+            &mut code,
+            graph,
+            &TypeRegistry::default(),
+            &options,
+        );
+        code
+    }
+
+    #[test]
+    fn test_modules_reexport_their_defaults() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "auth/title", "Login");
+        string(&mut graph, "auth/submit", "Sign in");
+        let tests = graph.tests_mut();
+        string(tests, "auth/title", "Test login");
+        string(tests, "fixtures/user", "alice");
+
+        assert_eq!(
+            tests_code(&graph),
+            "\n#[cfg(test)]\npub mod r_tests {\n    \
+                 pub use super::r::*;\n    \
+                 pub mod auth {\n        \
+                     pub use super::super::r::auth::*;\n        \
+                     pub const TITLE: &str = \"Test login\";\n    \
+                 }\n    \
+                 pub mod fixtures {\n        \
+                     pub const USER: &str = \"alice\";\n    \
+                 }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn nothing_without_test_resources() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "title", "Hello");
+        assert_eq!(
+            tests_code(&graph),
+            ""
+        );
+    }
+}
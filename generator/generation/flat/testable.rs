@@ -0,0 +1,250 @@
+//! Overridable getters of `BuildOptions::testable`.
+//!
+//! Next to the constant of each `overridable="true"` resource, a getter
+//! of the same name in lower case returns it, or the value a test set
+//! on its thread:
+//!
+//! ```rust,ignore
+//! r::MAX_RETRIES   // 3, always
+//! r::max_retries() // 3, a `const fn` outside of `cfg(test)`
+//!
+//! #[test]
+//! fn gives_up_after_one_retry() {
+//!     let _guard = r::set_max_retries_for_test(1);
+//!     assert_eq!(r::max_retries(), 1); // back to 3 when dropped
+//! }
+//! ```
+//!
+//! The override lives in a `#[cfg(test)]` `thread_local!` holding an
+//! [`Override`](crate::Override), so tests running in parallel never
+//! see each other's values. Templates with placeholders and
+//! `BigDecimal` statics have no `const` value to override and are
+//! rejected, as are strings that already have a hot-reload accessor
+//! of that name.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generator::analysis::AnalysisError;
+use crate::generator::ir::{ResourceGraph, ResourceKey, ResourceNode};
+use crate::generator::utils::sanitize_identifier;
+
+use super::config::{field_name, field_type};
+use super::hot_reload::string_accessor;
+use super::scope::{item_identifier, resource_identifiers};
+
+/// `max_retries` getter, `set_max_retries_for_test` setter and
+/// `MAX_RETRIES_OVERRIDE` thread-local of a resource
+fn getter_identifiers(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> [String; 3] {
+    let getter = field_name(key, node);
+    let setter =
+        format!("set_{}_for_test", getter.trim_start_matches("r#"));
+    let cell = format!("{}_OVERRIDE", item_identifier(key, node));
+    [getter, setter, cell]
+}
+
+/// Getters and setter of the overridable resource `key`, after its
+/// constant; each getter repeats the `attributes` of the constant
+pub(super) fn overridable_getter(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    attributes: &str,
+    pad: &str,
+) -> String {
+    let Some(ty) = field_type(node) else {
+        return String::new();
+    };
+    let item = item_identifier(key, node);
+    let [getter, setter, cell] = getter_identifiers(key, node);
+    format!(
+        "{pad}#[cfg(test)]\n\
+         {pad}thread_local! {{\n\
+         {pad}    static {cell}: r_resources::Override<{ty}> = const {{ r_resources::Override::new() }};\n\
+         {pad}}}\n\
+         {pad}/// [`{item}`], or its override in tests (`{setter}`)\n\
+         {attributes}{pad}#[cfg(not(test))]\n\
+         {pad}#[must_use]\n\
+         {pad}#[allow(deprecated)]\n\
+         {pad}pub const fn {getter}() -> {ty} {{\n\
+         {pad}    {item}\n\
+         {pad}}}\n\
+         {pad}/// [`{item}`], or its override on this thread\n\
+         {attributes}{pad}#[cfg(test)]\n\
+         {pad}#[must_use]\n\
+         {pad}#[allow(deprecated, clippy::excessive_nesting)]\n\
+         {pad}pub fn {getter}() -> {ty} {{\n\
+         {pad}    r_resources::Override::get_or(&{cell}, {item})\n\
+         {pad}}}\n\
+         {pad}/// Makes [`{getter}`] return `value` on this thread until the guard is dropped\n\
+         {pad}#[cfg(test)]\n\
+         {pad}#[allow(clippy::excessive_nesting)]\n\
+         {pad}pub fn {setter}(value: {ty}) -> r_resources::OverrideGuard<{ty}> {{\n\
+         {pad}    r_resources::Override::set(&{cell}, value)\n\
+         {pad}}}\n"
+    )
+}
+
+/// Identifiers of each module of `graph`: items, bounds and child
+/// modules
+fn module_identifiers(
+    graph: &ResourceGraph,
+) -> BTreeMap<&[String], BTreeSet<String>> {
+    let mut modules: BTreeMap<&[String], BTreeSet<String>> =
+        BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else { continue };
+        let items = modules.entry(&key.namespace[..]).or_default();
+        items.extend(resource_identifiers(key, node));
+        for (depth, ns) in key.namespace.iter().enumerate() {
+            modules
+                .entry(&key.namespace[..depth])
+                .or_default()
+                .insert(sanitize_identifier(ns));
+        }
+    }
+    modules
+}
+
+/// Why the overridable resource `key` can't have a getter, if so
+fn overridable_error(
+    key: &ResourceKey,
+    node: &ResourceNode,
+    taken: &BTreeSet<String>,
+    hot_reload: bool,
+) -> Option<String> {
+    let name = key.full_name();
+    if field_type(node).is_none() {
+        return Some(format!(
+            "Resource '{name}' is marked overridable, but template functions and BigDecimal numbers have no constant value to override"
+        ));
+    }
+    let idents = getter_identifiers(key, node);
+    if hot_reload && string_accessor(key, node, "", "").is_some() {
+        return Some(format!(
+            "Resource '{name}' is marked overridable, but its hot-reload accessor is already `{}()`; please disable hot reload or remove `overridable`",
+            idents[0]
+        ));
+    }
+    let ident = idents.iter().find(|ident| taken.contains(*ident))?;
+    Some(format!(
+        "Overridable resource '{name}' generates `{ident}`, which collides with another item of its module; please rename one of them"
+    ))
+}
+
+/// Rejects the `overridable="true"` resources whose getter can't be
+/// generated: no constant value, or an identifier already taken
+pub fn check_overridable(
+    graph: &ResourceGraph,
+    hot_reload: bool,
+) -> Vec<AnalysisError> {
+    let modules = module_identifiers(graph);
+    let empty = BTreeSet::new();
+    graph
+        .nodes()
+        .iter()
+        .filter(|(key, _)| graph.is_overridable(key))
+        .filter_map(|(key, nodes)| {
+            let node = nodes.first()?;
+            let module = &key.namespace[..];
+            let taken = modules.get(module).unwrap_or(&empty);
+            let message =
+                overridable_error(key, node, taken, hot_reload)?;
+            Some(AnalysisError::new(message, Some(key.clone())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberValue, ResourceKind, ResourceOrigin, ResourceValue,
+        TemplateParam, TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn node(value: ResourceValue) -> ResourceNode {
+        ResourceNode::new(
+            ResourceKind::String,
+            value,
+            ResourceOrigin::new(
+                PathBuf::from("values.xml"),
+                false,
+            ),
+        )
+    }
+
+    fn overridable(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        let key = ResourceKey::from_path(path);
+        graph.insert(key.clone(), node(value));
+        graph.mark_overridable(key);
+    }
+
+    #[test]
+    fn getters_read_the_override_in_tests_only() {
+        let key = ResourceKey::from_path("net/max_retries");
+        let node = node(ResourceValue::Number(NumberValue::Int(3)));
+        let code = overridable_getter(&key, &node, "", "    ");
+        for line in [
+            "    #[cfg(test)]\n    thread_local! {\n        static MAX_RETRIES_OVERRIDE: r_resources::Override<i64> = const { r_resources::Override::new() };\n    }\n",
+            "    #[cfg(not(test))]\n    #[must_use]\n    #[allow(deprecated)]\n    pub const fn max_retries() -> i64 {\n        MAX_RETRIES\n    }\n",
+            "    pub fn max_retries() -> i64 {\n        r_resources::Override::get_or(&MAX_RETRIES_OVERRIDE, MAX_RETRIES)\n    }\n",
+            "    pub fn set_max_retries_for_test(value: i64) -> r_resources::OverrideGuard<i64> {\n        r_resources::Override::set(&MAX_RETRIES_OVERRIDE, value)\n    }\n",
+        ] {
+            assert!(code.contains(line), "missing `{line}` in {code}");
+        }
+    }
+
+    #[test]
+    fn getters_without_a_value_or_a_name_are_rejected() {
+        let mut graph = ResourceGraph::default();
+        overridable(
+            &mut graph,
+            "greet",
+            ResourceValue::Template {
+                text: "Hi {name}".into(),
+                params: vec![TemplateParam {
+                    name: "name".into(),
+                    value: TemplateParamValue::String,
+                }],
+            },
+        );
+        overridable(
+            &mut graph,
+            "limit",
+            ResourceValue::Number(NumberValue::Int(1)),
+        );
+        graph.insert(
+            ResourceKey::from_path("limit_override"),
+            node(ResourceValue::Bool(true)),
+        );
+        overridable(
+            &mut graph,
+            "title",
+            ResourceValue::String("Title".into()),
+        );
+        let messages = |hot_reload| -> Vec<String> {
+            check_overridable(&graph, hot_reload)
+                .into_iter()
+                .map(|e| e.message)
+                .collect()
+        };
+        assert_eq!(
+            messages(false),
+            [
+                "Resource 'greet' is marked overridable, but template functions and BigDecimal numbers have no constant value to override",
+                "Overridable resource 'limit' generates `LIMIT_OVERRIDE`, which collides with another item of its module; please rename one of them",
+            ]
+        );
+        assert_eq!(
+            messages(true)[2],
+            "Resource 'title' is marked overridable, but its hot-reload accessor is already `title()`; please disable hot reload or remove `overridable`"
+        );
+    }
+}
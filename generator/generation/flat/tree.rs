@@ -1,21 +1,23 @@
 //! Namespace tree construction and sorting
 
-use crate::generator::ir::{ResourceGraph, ResourceKey};
+use crate::generator::ir::{Ordering, ResourceGraph, ResourceKey};
 use std::collections::BTreeMap;
 
 #[derive(Default)]
 pub(super) struct NamespaceNode {
     pub(super) children: BTreeMap<String, NamespaceNode>,
     pub(super) resource_keys: Vec<ResourceKey>,
+    /// `<doc>` of the namespace, emitted as `//!` lines
+    pub(super) doc: Option<String>,
+    /// `struct="true"`: also generated as a config struct
+    pub(super) config: bool,
 }
 
-/// Builds a namespace tree from the resource graph
-pub(super) fn build_namespace_tree(graph: &ResourceGraph) -> NamespaceNode {
-    let mut root = NamespaceNode::default();
-    // Only use the first node for each key (primary), duplicates are handled separately
-    for key in graph.nodes().keys() {
-        let mut current = &mut root;
-        for ns_part in &key.namespace {
+impl NamespaceNode {
+    /// Adds `key` under the modules of its namespace
+    pub(super) fn insert_key(&mut self, key: &ResourceKey) {
+        let mut current = self;
+        for ns_part in key.namespace.iter() {
             current = current
                 .children
                 .entry(ns_part.clone())
@@ -23,14 +25,63 @@ pub(super) fn build_namespace_tree(graph: &ResourceGraph) -> NamespaceNode {
         }
         current.resource_keys.push(key.clone());
     }
+}
+
+/// Builds a namespace tree from the resource graph
+pub(super) fn build_namespace_tree(graph: &ResourceGraph) -> NamespaceNode {
+    let mut root = NamespaceNode::default();
+    // Only use the first node for each key (primary), duplicates are handled separately
+    for key in graph.nodes().keys() {
+        root.insert_key(key);
+    }
+    for (namespace, doc) in graph.namespace_docs() {
+        let mut current = Some(&mut root);
+        for ns_part in namespace {
+            current =
+                current.and_then(|node| node.children.get_mut(ns_part));
+        }
+        // Namespaces without any active resource are not generated;
+        // the root's doc is the one of `r`
+        if let Some(node) = current {
+            node.doc = Some(doc.clone());
+        }
+    }
+    for namespace in graph.config_namespaces() {
+        if let Some(node) = child_at(&mut root, namespace) {
+            node.config = true;
+        }
+    }
     root
 }
 
-/// Sorts the namespace tree recursively
-pub(super) fn sort_namespace_tree(node: &mut NamespaceNode) {
-    node.resource_keys.sort_by(|a, b| a.name.cmp(&b.name));
+/// Node of `namespace` below `root`, `None` if it has no resource
+fn child_at<'a>(
+    root: &'a mut NamespaceNode,
+    namespace: &[String],
+) -> Option<&'a mut NamespaceNode> {
+    namespace
+        .iter()
+        .try_fold(root, |node, ns| node.children.get_mut(ns))
+}
+
+/// Sorts the resources of every namespace in `ordering`, the
+/// positions being those of `graph`; namespaces stay sorted by name
+pub(super) fn sort_namespace_tree(
+    node: &mut NamespaceNode,
+    graph: &ResourceGraph,
+    ordering: Ordering,
+) {
+    match ordering {
+        Ordering::Alphabetical => {
+            node.resource_keys.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        Ordering::Source => {
+            node.resource_keys
+                .sort_by_key(|key| graph.source_index(key));
+        }
+    }
     for child in node.children.values_mut() {
-        sort_namespace_tree(child);
+        sort_namespace_tree(child, graph, ordering);
     }
 }
 
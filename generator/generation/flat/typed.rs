@@ -0,0 +1,255 @@
+//! Type-organized modules next to `r::`.
+//!
+//! Projects written against the typed access style keep working:
+//! `string::APP_NAME`, `int::MAX_RETRIES` and `color::ui::ACCENT`
+//! re-export the items of `r::` with the same namespace nesting, so both
+//! paths name the same constant or function:
+//!
+//! ```rust,ignore
+//! assert_eq!(string::auth::TITLE, r::auth::TITLE);
+//! ```
+//!
+//! Numbers are split by their generated Rust type: integers in `int::`,
+//! floats in `float::`, `BigDecimal` statics in `decimal::`. Bools live
+//! in `boolean::`, as a `bool` module would shadow the primitive type;
+//! coordinates in `latlng::` and `position::`, versions in `semver::`,
+//! addresses in `ipaddr::` and `socketaddr::`, date-times and dates in
+//! `datetime::` (their `_UNIX`) and `date::`, amounts in `money::` and
+//! chars in `character::`, for the same reason as `boolean::`, and
+//! bytes in `binary::`, clear of the `bytes` crate. Regexes and
+//! graphemes are strings, regexes as their `_PATTERN` text. UUIDs have
+//! no module, as a `uuid` module would shadow the `uuid` crate.
+//! Paths start with `super::`, so the code works wherever it is
+//! included.
+
+use std::collections::BTreeMap;
+
+use crate::generator::generation::sink::CodeSink;
+use crate::generator::ir::{
+    NumberValue, Ordering, ResourceGraph, ResourceNode, ResourceValue,
+};
+use crate::generator::options::LazyStatics;
+use crate::generator::utils::sanitize_identifier;
+
+use super::registries::Registry;
+use super::scope::item_identifier;
+use super::tree::{sort_namespace_tree, NamespaceNode};
+
+/// Module names, in emission order
+pub(super) const TYPED_MODULES: [&str; 17] = [
+    "string", "int", "float", "decimal", "boolean", "color",
+    "template", "latlng", "position", "semver", "ipaddr",
+    "socketaddr", "datetime", "date", "money", "character", "binary",
+];
+
+/// Emits one module per resource type that has resources, with
+/// `ALL` tables when `registries` is set
+pub fn emit_typed_modules(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    registries: bool,
+    ordering: Ordering,
+    lazy: LazyStatics,
+) {
+    let mut trees: BTreeMap<&str, NamespaceNode> = BTreeMap::new();
+    for (key, nodes) in graph.nodes() {
+        let Some(module) = nodes.first().and_then(typed_module) else {
+            continue;
+        };
+        trees.entry(module).or_default().insert_key(key);
+    }
+
+    for module in TYPED_MODULES {
+        let Some(tree) = trees.get_mut(module) else {
+            continue;
+        };
+        sort_namespace_tree(tree, graph, ordering);
+        let _ = writeln!(code, "\npub mod {module} {{");
+        let registry =
+            registries.then_some(Registry { module, lazy });
+        emit_reexports(code, graph, tree, &[], registry);
+        code.push_str("}\n");
+    }
+}
+
+/// Typed module holding `node`, `None` for UUIDs
+pub(super) fn typed_module(node: &ResourceNode) -> Option<&'static str> {
+    Some(match &node.value {
+        ResourceValue::String(_) | ResourceValue::Regex(_) => {
+            "string"
+        }
+        ResourceValue::Number(NumberValue::Int(_)) => "int",
+        ResourceValue::Number(NumberValue::Float(_)) => "float",
+        ResourceValue::Number(NumberValue::BigDecimal(_)) => {
+            "decimal"
+        }
+        ResourceValue::Number(NumberValue::Typed { ty, .. }) => {
+            if ty.is_float() {
+                "float"
+            } else {
+                "int"
+            }
+        }
+        ResourceValue::Bool(_) => "boolean",
+        ResourceValue::Color(_) => "color",
+        ResourceValue::Template { .. } => "template",
+        ResourceValue::LatLng { .. } => "latlng",
+        ResourceValue::Position { .. } => "position",
+        ResourceValue::Semver { .. } => "semver",
+        ResourceValue::IpAddr(_) => "ipaddr",
+        ResourceValue::SocketAddr(_) => "socketaddr",
+        ResourceValue::DateTime { .. } => "datetime",
+        ResourceValue::Date { .. } => "date",
+        ResourceValue::Money { .. } => "money",
+        ResourceValue::Char(_) => "character",
+        ResourceValue::Grapheme(_) => "string",
+        ResourceValue::Bytes(_) => "binary",
+        ResourceValue::Uuid(_) => return None,
+    })
+}
+
+/// Emits the re-exports of `node`, followed by its `ALL` table for
+/// `Some(typed module)` in `registry`
+fn emit_reexports(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    node: &NamespaceNode,
+    path: &[String],
+    registry: Option<Registry<'_>>,
+) {
+    let pad = "    ".repeat(path.len() + 1);
+    for (ns_name, child) in &node.children {
+        let module = sanitize_identifier(ns_name);
+        let _ = writeln!(code, "{pad}pub mod {module} {{");
+        let mut child_path = path.to_vec();
+        child_path.push(module);
+        emit_reexports(code, graph, child, &child_path, registry);
+        let _ = writeln!(code, "{pad}}}");
+    }
+
+    // One `super::` per module between this one and the include site
+    let supers = "super::".repeat(path.len() + 1);
+    let modules: String =
+        path.iter().map(|module| format!("{module}::")).collect();
+    for key in &node.resource_keys {
+        let Some(resource) = graph.get(key) else {
+            continue;
+        };
+        let ident = item_identifier(key, resource);
+        if let Some(predicate) = graph.cfg_predicate(key) {
+            let _ = writeln!(code, "{pad}#[cfg({predicate})]");
+        }
+        if resource.meta.deprecated.is_some()
+            || graph.has_duplicates(key)
+        {
+            // Re-exporting is not a use; callers still get the warning
+            let _ = writeln!(code, "{pad}#[allow(deprecated)]");
+        }
+        let _ = writeln!(
+            code,
+            "{pad}pub use {supers}r::{modules}{ident};"
+        );
+    }
+    if let Some(registry) = registry {
+        super::registries::emit_registry(
+            code, graph, registry, node, &pad,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberType, ResourceKey, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn typed_code(graph: &ResourceGraph) -> String {
+        let mut code = String::new();
+        emit_typed_modules(
+            &mut code,
+            graph,
+            false,
+            Ordering::Alphabetical,
+            LazyStatics::LazyLock,
+        );
+        code
+    }
+
+    #[test]
+    fn resources_are_grouped_by_type() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("Demo".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Login".into()),
+        );
+        insert(
+            &mut graph,
+            "max_retries",
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        insert(
+            &mut graph,
+            "ratio",
+            ResourceValue::Number(NumberValue::Typed {
+                literal: "0.5".into(),
+                ty: NumberType::F32,
+            }),
+        );
+        insert(
+            &mut graph,
+            "ui/accent",
+            ResourceValue::Color("#FF5722".into()),
+        );
+
+        assert_eq!(
+            typed_code(&graph),
+            "\npub mod string {\n    \
+                 pub mod auth {\n        \
+                     pub use super::super::r::auth::TITLE;\n    \
+                 }\n    \
+                 pub use super::r::APP_NAME;\n\
+             }\n\
+             \npub mod int {\n    pub use super::r::MAX_RETRIES;\n}\n\
+             \npub mod float {\n    pub use super::r::RATIO;\n}\n\
+             \npub mod color {\n    \
+                 pub mod ui {\n        \
+                     pub use super::super::r::ui::ACCENT;\n    \
+                 }\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn empty_graph_emits_nothing() {
+        assert_eq!(
+            typed_code(&ResourceGraph::default()),
+            ""
+        );
+    }
+}
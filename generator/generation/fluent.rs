@@ -0,0 +1,405 @@
+//! Fluent (`.ftl`) export.
+//!
+//! Writes string and template resources as Fluent messages so they can
+//! be handed to a Fluent-based localization pipeline:
+//!
+//! - namespaces become prefixed message ids (`auth/title` → `auth-title`)
+//! - `{name}` template parameters become `{ $name }` variables and
+//!   `%1$s` placeholders become `{ $arg1 }`, matching the generated
+//!   function arguments
+//! - braces, line-leading `[`, `*`, `.` and significant whitespace are
+//!   escaped with string literals; multi-line values use a block layout
+//!
+//! Other resource kinds (numbers, bools, colors, arrays) are skipped.
+
+use std::path::{Path, PathBuf};
+
+use crate::generator::ir::{
+    ResourceGraph, ResourceKey, ResourceKind, ResourceValue,
+};
+
+/// File name of the export for resources without a locale
+pub const DEFAULT_FILE: &str = "default.ftl";
+
+const HEADER: &str =
+    "### Generated by r-resources. Do not edit by hand.\n";
+
+/// Renders the resources of `graph` (without its translations) as `.ftl`
+pub fn fluent(graph: &ResourceGraph) -> String {
+    let mut out = String::from(HEADER);
+    for (key, nodes) in graph.nodes() {
+        let Some(node) = nodes.first() else {
+            continue;
+        };
+        let pattern = match (&node.kind, &node.value) {
+            (ResourceKind::String, ResourceValue::String(text)) => {
+                pattern(text, &[])
+            }
+            (
+                ResourceKind::Template,
+                ResourceValue::Template { text, params },
+            ) => {
+                let names: Vec<&str> =
+                    params.iter().map(|p| p.name.as_str()).collect();
+                pattern(text, &names)
+            }
+            _ => continue,
+        };
+        out.push('\n');
+        out.push_str(&message(key, &pattern));
+    }
+    out
+}
+
+/// Writes `default.ftl` and one `<locale>.ftl` per translation into
+/// `dir`, returning the written paths
+pub fn export_fluent(
+    graph: &ResourceGraph,
+    dir: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir)?;
+    let mut files = vec![(DEFAULT_FILE.to_string(), fluent(graph))];
+    for (locale, translation) in graph.translations() {
+        files.push((format!("{locale}.ftl"), fluent(translation)));
+    }
+
+    let mut written = Vec::with_capacity(files.len());
+    for (name, contents) in files {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Fluent message id for a key: namespace segments joined with `-`
+pub fn message_id(key: &ResourceKey) -> String {
+    key.namespace
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(&*key.name))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Formats one message; multi-line patterns go on indented lines
+fn message(key: &ResourceKey, lines: &[String]) -> String {
+    let id = message_id(key);
+    match lines {
+        [single] => format!("{id} = {single}\n"),
+        _ => {
+            let mut out = format!("{id} =\n");
+            for line in lines {
+                if !line.is_empty() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                }
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+/// Converts resource text into escaped Fluent pattern lines
+fn pattern(text: &str, params: &[&str]) -> Vec<String> {
+    let raw_lines: Vec<&str> = text.split('\n').collect();
+    let last = raw_lines.len() - 1;
+    let multiline = last > 0;
+    raw_lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            // Blank lines only survive between content lines
+            let keep_blank = multiline && i != 0 && i != last;
+            pattern_line(line, params, multiline, keep_blank)
+        })
+        .collect()
+}
+
+/// Escapes a single line of text.
+///
+/// Fluent trims leading and trailing whitespace around a pattern and
+/// dedents block lines, so such whitespace is kept as a string literal.
+fn pattern_line(
+    line: &str,
+    params: &[&str],
+    block: bool,
+    keep_blank: bool,
+) -> String {
+    if line.is_empty() {
+        return if keep_blank {
+            String::new()
+        } else {
+            "{\"\"}".to_string()
+        };
+    }
+
+    let body = line.trim_start_matches([' ', '\t']);
+    let leading = &line[..line.len() - body.len()];
+    let content = body.trim_end_matches([' ', '\t']);
+    let trailing = &body[content.len()..];
+
+    let mut out = String::new();
+    if !leading.is_empty() {
+        out.push_str(&literal(leading));
+    } else if block && content.starts_with(['[', '*', '.']) {
+        // These would start a variant or an attribute
+        out.push_str(&literal(&content[..1]));
+        out.push_str(&placeables(&content[1..], params));
+        out.push_str(&trailing_literal(trailing));
+        return out;
+    }
+    out.push_str(&placeables(content, params));
+    out.push_str(&trailing_literal(trailing));
+    out
+}
+
+fn trailing_literal(trailing: &str) -> String {
+    if trailing.is_empty() {
+        String::new()
+    } else {
+        literal(trailing)
+    }
+}
+
+/// Converts placeholders to variables and escapes literal braces
+fn placeables(text: &str, params: &[&str]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some((variable, len)) = variable_at(rest, params) {
+            out.push_str(&format!("{{ ${variable} }}"));
+            rest = &rest[len..];
+            continue;
+        }
+        match c {
+            '{' | '}' => out.push_str(&literal(&c.to_string())),
+            _ => out.push(c),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Recognizes a `{name}` or `%N$x` placeholder at the start of `text`,
+/// returning the variable name and the placeholder length
+fn variable_at(
+    text: &str,
+    params: &[&str],
+) -> Option<(String, usize)> {
+    if !params.is_empty() {
+        return params.iter().find_map(|name| {
+            let placeholder = format!("{{{name}}}");
+            text.starts_with(&placeholder)
+                .then(|| (name.to_string(), placeholder.len()))
+        });
+    }
+
+    let rest = text.strip_prefix('%')?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let spec = rest[digits..].strip_prefix('$')?;
+    if digits == 0 || !spec.starts_with(['s', 'd', 'f', 'x', 'X']) {
+        return None;
+    }
+    let number: usize = rest[..digits].parse().ok()?;
+    Some((format!("arg{number}"), digits + 3))
+}
+
+/// Fluent string literal placeable, e.g. `{"{"}`
+fn literal(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("{{\"{escaped}\"}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NumberValue, ResourceNode, ResourceOrigin, TemplateParam,
+        TemplateParamValue,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        kind: ResourceKind,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                kind,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn string(graph: &mut ResourceGraph, path: &str, text: &str) {
+        insert(
+            graph,
+            path,
+            ResourceKind::String,
+            ResourceValue::String(text.to_string()),
+        );
+    }
+
+    fn template(
+        graph: &mut ResourceGraph,
+        path: &str,
+        text: &str,
+        params: &[&str],
+    ) {
+        insert(
+            graph,
+            path,
+            ResourceKind::Template,
+            ResourceValue::Template {
+                text: text.to_string(),
+                params: params
+                    .iter()
+                    .map(|name| TemplateParam {
+                        name: name.to_string(),
+                        value: TemplateParamValue::String,
+                    })
+                    .collect(),
+            },
+        );
+    }
+
+    fn fixture() -> ResourceGraph {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "app_name", "My App");
+        string(&mut graph, "auth/title", "Sign in");
+        string(&mut graph, "auth/errors/locked", "Account locked");
+        template(
+            &mut graph,
+            "welcome",
+            "Hello {name}, you have {count} messages",
+            &["name", "count"],
+        );
+        template(&mut graph, "legacy", "%1$s of %2$d", &[]);
+        string(&mut graph, "json", r#"Use {"key": "\d"}"#);
+        string(&mut graph, "terms", "First line\n\n* bullet\n.end");
+        string(&mut graph, "padded", "  centered  ");
+        string(&mut graph, "empty", "");
+        insert(
+            &mut graph,
+            "max_retries",
+            ResourceKind::Number,
+            ResourceValue::Number(NumberValue::Int(3)),
+        );
+        insert(
+            &mut graph,
+            "enabled",
+            ResourceKind::Bool,
+            ResourceValue::Bool(true),
+        );
+        graph
+    }
+
+    #[test]
+    fn snapshot_of_representative_fixture() {
+        let expected = r#"### Generated by r-resources. Do not edit by hand.
+
+app_name = My App
+
+empty = {""}
+
+json = Use {"{"}"key": "\d"{"}"}
+
+legacy = { $arg1 } of { $arg2 }
+
+padded = {"  "}centered{"  "}
+
+terms =
+    First line
+
+    {"*"} bullet
+    {"."}end
+
+welcome = Hello { $name }, you have { $count } messages
+
+auth-title = Sign in
+
+auth-errors-locked = Account locked
+"#;
+        assert_eq!(fluent(&fixture()), expected);
+    }
+
+    #[test]
+    fn snapshot_escapes_edges_of_multiline_values() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "poem", "\n  indented\n[not a variant]\n");
+        string(&mut graph, "quote", "say \"{\\}\"");
+
+        let expected = r#"### Generated by r-resources. Do not edit by hand.
+
+poem =
+    {""}
+    {"  "}indented
+    {"["}not a variant]
+    {""}
+
+quote = say "{"{"}\{"}"}"
+"#;
+        assert_eq!(fluent(&graph), expected);
+    }
+
+    #[test]
+    fn message_ids_join_namespaces() {
+        assert_eq!(
+            message_id(&ResourceKey::from_path("auth/title")),
+            "auth-title"
+        );
+        assert_eq!(
+            message_id(&ResourceKey::from_path("a/b/c_d")),
+            "a-b-c_d"
+        );
+    }
+
+    #[test]
+    fn unknown_braces_in_named_templates_are_escaped() {
+        assert_eq!(
+            placeables("{name} {other}", &["name"]),
+            r#"{ $name } {"{"}other{"}"}"#
+        );
+    }
+
+    #[test]
+    fn percent_without_placeholder_is_kept() {
+        assert_eq!(placeables("100% of %1$q", &[]), "100% of %1$q");
+    }
+
+    #[test]
+    fn export_writes_default_and_locale_files() {
+        let mut graph = fixture();
+        string(
+            graph.translation_mut("fr"),
+            "auth/title",
+            "Connexion",
+        );
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("ftl");
+        let written = export_fluent(&graph, &dir).unwrap();
+
+        assert_eq!(
+            written,
+            vec![dir.join("default.ftl"), dir.join("fr.ftl")]
+        );
+        let fr = std::fs::read_to_string(dir.join("fr.ftl")).unwrap();
+        assert_eq!(
+            fr,
+            "### Generated by r-resources. Do not edit by hand.\n\n\
+             auth-title = Connexion\n"
+        );
+    }
+}
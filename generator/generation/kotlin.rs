@@ -0,0 +1,597 @@
+//! Kotlin `object R` of the resources, for Android shells sharing
+//! them with a Rust core.
+//!
+//! Writes an `R.kt` with nested objects mirroring the `r::` namespace
+//! tree:
+//!
+//! ```kotlin
+//! package com.example.res
+//!
+//! object R {
+//!     const val appName = "My App"
+//!     const val maxRetries = 3L
+//!     const val primary = 0xFFFF5722L
+//!     fun greeting(name: String): String = "Hello ${name}"
+//!     object auth {
+//!         const val title = "Login"
+//!     }
+//! }
+//! ```
+//!
+//! - members are camelCased (`app_name` → `appName`); Kotlin
+//!   keywords (`object`, `when`) and names starting with a digit are
+//!   escaped with backticks, and names colliding once converted get a
+//!   numeric suffix (`appName2`)
+//! - numbers keep the type of the Rust constant (`Long` by default,
+//!   `UByte` for `u8`, ...) and `BigDecimal`s are
+//!   `java.math.BigDecimal` values
+//! - colors are `0xAARRGGBB` `Long` literals, as on Android
+//! - coordinates and positions use the `R.LatLng` and `R.Position`
+//!   data classes, only declared when used
+//! - `<doc>` text becomes KDoc, deprecations `@Deprecated`
+//!
+//! Translations and test resources are not exported.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::exports::{doc_comment, doc_lines, ResourceTree};
+use crate::generator::color::parse_hex_color;
+use crate::generator::ir::types::{
+    date_text, hex_text, integer_value, money_text,
+    template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberType, NumberValue, ResourceGraph, ResourceNode,
+    ResourceValue, TemplateParam, TemplateParamValue,
+};
+use crate::generator::utils::{lower_camel_case, unique_suffixed};
+
+/// Objects are named like namespaces, in `camelCase`
+const HEADER: &str =
+    "// Generated by r-resources. Do not edit by hand.\n\
+    @file:Suppress(\"ClassName\")\n";
+
+/// Hard keywords, which cannot name a member without backticks
+const KEYWORDS: [&str; 28] = [
+    "as", "break", "class", "continue", "do", "else", "false", "for",
+    "fun", "if", "in", "interface", "is", "null", "object", "package",
+    "return", "super", "this", "throw", "true", "try", "typealias",
+    "typeof", "val", "var", "when", "while",
+];
+
+const LAT_LNG_CLASS: &str =
+    "data class LatLng(val lat: Double, val lng: Double)";
+const POSITION_CLASS: &str =
+    "data class Position(val x: Double, val y: Double)";
+
+/// Renders `R.kt` for `graph`, in `package` when given
+pub fn kotlin(
+    graph: &ResourceGraph,
+    package: Option<&str>,
+) -> String {
+    let mut out = String::from(HEADER);
+    if let Some(package) = package {
+        let _ = writeln!(out, "\npackage {package}");
+    }
+    out.push_str("\nobject R {\n");
+    let values =
+        graph.nodes().values().filter_map(|nodes| nodes.first());
+    let (mut lat_lng, mut position) = (false, false);
+    for node in values {
+        lat_lng |= matches!(node.value, ResourceValue::LatLng { .. });
+        position |=
+            matches!(node.value, ResourceValue::Position { .. });
+    }
+    for (used, class) in
+        [(lat_lng, LAT_LNG_CLASS), (position, POSITION_CLASS)]
+    {
+        if used {
+            let _ = writeln!(out, "    {class}");
+        }
+    }
+    render_object(&mut out, &ResourceTree::new(graph), 1);
+    out.push_str("}\n");
+    out
+}
+
+/// Writes `R.kt` for `graph` to `path`, creating its parent
+/// directories
+pub fn export_kotlin(
+    graph: &ResourceGraph,
+    package: Option<&str>,
+    path: &Path,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, kotlin(graph, package))
+}
+
+/// Members of `namespace`: its resources, then its nested objects
+fn render_object(
+    out: &mut String,
+    namespace: &ResourceTree<'_>,
+    depth: usize,
+) {
+    let pad = "    ".repeat(depth);
+    let mut used = HashSet::new();
+    for (key, node) in &namespace.resources {
+        let name =
+            unique_suffixed(lower_camel_case(&key.name), &mut used);
+        doc_comment(out, doc_lines(node.meta.doc.as_deref()), &pad);
+        if let Some(note) = &node.meta.deprecated {
+            let _ = writeln!(
+                out,
+                "{pad}@Deprecated({})",
+                string_literal(note)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{pad}{}",
+            member(&identifier(&name), node)
+        );
+    }
+    for (segment, child) in &namespace.children {
+        let name =
+            unique_suffixed(lower_camel_case(segment), &mut used);
+        doc_comment(out, doc_lines(child.doc), &pad);
+        let _ = writeln!(out, "{pad}object {} {{", identifier(&name));
+        render_object(out, child, depth + 1);
+        let _ = writeln!(out, "{pad}}}");
+    }
+}
+
+/// Declaration of a resource
+fn member(name: &str, node: &ResourceNode) -> String {
+    match &node.value {
+        ResourceValue::String(text) | ResourceValue::Regex(text) => {
+            format!("const val {name} = {}", string_literal(text))
+        }
+        ResourceValue::Template { text, params } => {
+            template(name, text, params)
+        }
+        ResourceValue::Number(number) => number_member(name, number),
+        ResourceValue::Bool(value) => {
+            format!("const val {name} = {value}")
+        }
+        ResourceValue::Color(hex) => {
+            format!("const val {name} = {}", color_literal(hex))
+        }
+        ResourceValue::LatLng { lat, lng } => format!(
+            "val {name} = LatLng({}, {})",
+            double(*lat),
+            double(*lng)
+        ),
+        ResourceValue::Position { x, y } => {
+            format!(
+                "val {name} = Position({}, {})",
+                double(*x),
+                double(*y)
+            )
+        }
+        ResourceValue::Semver { text, .. } => {
+            format!("const val {name} = {}", string_literal(text))
+        }
+        ResourceValue::IpAddr(ip) => format!(
+            "const val {name} = {}",
+            string_literal(&ip.to_string())
+        ),
+        ResourceValue::SocketAddr(addr) => format!(
+            "const val {name} = {}",
+            string_literal(&addr.to_string())
+        ),
+        ResourceValue::Uuid(value) => format!(
+            "const val {name} = {}",
+            string_literal(&uuid_text(*value))
+        ),
+        ResourceValue::DateTime { text, .. } => {
+            format!("const val {name} = {}", string_literal(text))
+        }
+        ResourceValue::Date { year, month, day } => format!(
+            "const val {name} = {}",
+            string_literal(&date_text(*year, *month, *day))
+        ),
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => {
+            let text = money_text(*minor_units, *scale, currency);
+            format!("const val {name} = {}", string_literal(&text))
+        }
+        ResourceValue::Char(c) => format!(
+            "const val {name} = {}",
+            string_literal(&c.to_string())
+        ),
+        ResourceValue::Grapheme(text) => {
+            format!("const val {name} = {}", string_literal(text))
+        }
+        ResourceValue::Bytes(bytes) => format!(
+            "const val {name} = {}",
+            string_literal(&hex_text(bytes))
+        ),
+    }
+}
+
+/// Function returning a string template, or a constant for a template
+/// without placeholders
+fn template(
+    name: &str,
+    text: &str,
+    params: &[TemplateParam],
+) -> String {
+    let Some(signature) = template_signature(text, params) else {
+        return format!(
+            "const val {name} = {}",
+            string_literal(text)
+        );
+    };
+    // Placeholders are substituted in the escaped text (`$` is `\$`)
+    let mut body = string_literal(text);
+    let mut arguments = Vec::new();
+    if params.is_empty() {
+        // Positional placeholders, named like the Rust arguments
+        for (i, arg) in signature.args.split(", ").enumerate() {
+            for conversion in ['s', 'd'] {
+                body = body.replace(
+                    &format!("%{}\\${conversion}", i + 1),
+                    &format!("${{{arg}}}"),
+                );
+            }
+            arguments.push(format!("{arg}: String"));
+        }
+    } else {
+        for param in params {
+            let ident = identifier(&lower_camel_case(&param.name));
+            body = body.replace(
+                &format!("{{{}}}", param.name),
+                &format!("${{{ident}}}"),
+            );
+            arguments
+                .push(format!("{ident}: {}", parameter_type(param)));
+        }
+    }
+    format!("fun {name}({}): String = {body}", arguments.join(", "))
+}
+
+/// Kotlin type of a template parameter, matching the Rust signature
+fn parameter_type(param: &TemplateParam) -> &'static str {
+    match &param.value {
+        TemplateParamValue::String | TemplateParamValue::Color => {
+            "String"
+        }
+        TemplateParamValue::Bool => "Boolean",
+        TemplateParamValue::Number { explicit_type } => {
+            match explicit_type.as_deref() {
+                Some("bigdecimal") => "java.math.BigDecimal",
+                Some("i8") => "Byte",
+                Some("i16") => "Short",
+                Some("i32") => "Int",
+                Some("u8") => "UByte",
+                Some("u16") => "UShort",
+                Some("u32") => "UInt",
+                Some("u64" | "usize") => "ULong",
+                Some("isize") => "Long",
+                Some("f32") => "Float",
+                Some("f64") => "Double",
+                _ => "Long",
+            }
+        }
+    }
+}
+
+/// Declaration of a number, typed like the Rust constant
+fn number_member(name: &str, number: &NumberValue) -> String {
+    match number {
+        NumberValue::Int(i) => {
+            format!("const val {name} = {}", long(i128::from(*i)))
+        }
+        NumberValue::Float(f) => {
+            format!("const val {name} = {}", double(*f))
+        }
+        NumberValue::BigDecimal(raw) => format!(
+            "val {name} = java.math.BigDecimal({})",
+            string_literal(raw)
+        ),
+        NumberValue::Typed { literal, ty } => {
+            match typed_literal(literal, ty) {
+                Some(value) => format!(
+                    "const val {name}: {} = {value}",
+                    kotlin_type(ty)
+                ),
+                // Rejected by the number validation, kept readable
+                None => format!(
+                    "const val {name} = {}",
+                    string_literal(literal)
+                ),
+            }
+        }
+    }
+}
+
+fn kotlin_type(ty: &NumberType) -> &'static str {
+    match ty {
+        NumberType::I8 => "Byte",
+        NumberType::I16 => "Short",
+        NumberType::I32 => "Int",
+        NumberType::I64 => "Long",
+        NumberType::U8 => "UByte",
+        NumberType::U16 => "UShort",
+        NumberType::U32 => "UInt",
+        NumberType::U64 | NumberType::Usize => "ULong",
+        NumberType::Isize => "Long",
+        NumberType::F32 => "Float",
+        NumberType::F64 => "Double",
+    }
+}
+
+/// Kotlin literal of a number of type `ty`
+fn typed_literal(literal: &str, ty: &NumberType) -> Option<String> {
+    if ty.is_float() {
+        let value = literal.replace('_', "").parse::<f64>().ok()?;
+        return Some(match ty {
+            NumberType::F32 => float(value),
+            _ => double(value),
+        });
+    }
+    let value = integer_value(literal)?;
+    Some(match ty {
+        NumberType::I64 | NumberType::Isize => long(value),
+        // `-2147483648` is the negation of a `Long` literal
+        NumberType::I32 if value == i128::from(i32::MIN) => {
+            "Int.MIN_VALUE".to_string()
+        }
+        NumberType::U8 | NumberType::U16 | NumberType::U32 => {
+            format!("{value}u")
+        }
+        NumberType::U64 | NumberType::Usize => format!("{value}uL"),
+        _ => value.to_string(),
+    })
+}
+
+fn long(value: i128) -> String {
+    if value == i128::from(i64::MIN) {
+        "Long.MIN_VALUE".to_string()
+    } else {
+        format!("{value}L")
+    }
+}
+
+fn double(value: f64) -> String {
+    if value.is_nan() {
+        "Double.NaN".to_string()
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "NEGATIVE" } else { "POSITIVE" };
+        format!("Double.{sign}_INFINITY")
+    } else {
+        format!("{value:?}")
+    }
+}
+
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "Float.NaN".to_string()
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "NEGATIVE" } else { "POSITIVE" };
+        format!("Float.{sign}_INFINITY")
+    } else {
+        #[allow(clippy::cast_possible_truncation)] // `type="f32"`
+        let value = value as f32;
+        format!("{value:?}f")
+    }
+}
+
+/// `0xAARRGGBB` literal of a hex color; `#RGB` and `#RRGGBB` are
+/// opaque
+fn color_literal(hex: &str) -> String {
+    match parse_hex_color(hex) {
+        Ok((r, g, b, a)) => {
+            format!("0x{a:02X}{r:02X}{g:02X}{b:02X}L")
+        }
+        // Rejected by the color validation, kept readable
+        Err(_) => string_literal(hex),
+    }
+}
+
+/// Kotlin identifier of a `camelCase` name: keywords and names that
+/// are not identifiers are quoted with backticks
+fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let valid =
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_');
+    if valid && !KEYWORDS.contains(&name) {
+        name.to_string()
+    } else {
+        format!("`{name}`")
+    }
+}
+
+/// Double-quoted string literal; `$` is escaped so that only the
+/// template placeholders are interpolated
+fn string_literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '$' => out.push_str("\\$"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        NodeMeta, ResourceKey, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn node(value: ResourceValue) -> ResourceNode {
+        ResourceNode::new(
+            ResourceKind::String,
+            value,
+            ResourceOrigin::new(
+                PathBuf::from("/app/res/values.xml"),
+                false,
+            ),
+        )
+    }
+
+    #[test]
+    fn identifiers_escape_keywords() {
+        assert_eq!(
+            identifier(&lower_camel_case("object")),
+            "`object`"
+        );
+        assert_eq!(identifier(&lower_camel_case("when")), "`when`");
+        assert_eq!(
+            identifier(&lower_camel_case("2fa_hint")),
+            "`2faHint`"
+        );
+        assert_eq!(
+            identifier(&lower_camel_case("app_name")),
+            "appName"
+        );
+        // Soft keywords are plain identifiers
+        assert_eq!(identifier("data"), "data");
+    }
+
+    #[test]
+    fn values_keep_their_type() {
+        let member = |value| member("x", &node(value));
+        assert_eq!(
+            member(ResourceValue::String("Cost: $5 \"net\"".into())),
+            "const val x = \"Cost: \\$5 \\\"net\\\"\""
+        );
+        assert_eq!(
+            member(ResourceValue::Color("#FF5722".into())),
+            "const val x = 0xFFFF5722L"
+        );
+        assert_eq!(
+            member(ResourceValue::Color("#80FF5722".into())),
+            "const val x = 0x80FF5722L"
+        );
+        assert_eq!(
+            member(ResourceValue::Number(NumberValue::Int(3))),
+            "const val x = 3L"
+        );
+        assert_eq!(
+            member(ResourceValue::Number(NumberValue::Typed {
+                literal: "0xFF".into(),
+                ty: NumberType::U8,
+            })),
+            "const val x: UByte = 255u"
+        );
+        assert_eq!(
+            member(ResourceValue::Number(NumberValue::Typed {
+                literal: "4096".into(),
+                ty: NumberType::Usize,
+            })),
+            "const val x: ULong = 4096uL"
+        );
+        assert_eq!(
+            member(ResourceValue::Number(NumberValue::Typed {
+                literal: "1.5".into(),
+                ty: NumberType::F32,
+            })),
+            "const val x: Float = 1.5f"
+        );
+        assert_eq!(
+            member(ResourceValue::Number(NumberValue::BigDecimal(
+                "1.25".into()
+            ))),
+            "val x = java.math.BigDecimal(\"1.25\")"
+        );
+    }
+
+    #[test]
+    fn templates_use_string_templates() {
+        let named = template(
+            "greeting",
+            "Hi {name}, you owe ${count}",
+            &[
+                TemplateParam {
+                    name: "name".into(),
+                    value: TemplateParamValue::String,
+                },
+                TemplateParam {
+                    name: "count".into(),
+                    value: TemplateParamValue::Number {
+                        explicit_type: Some("u32".into()),
+                    },
+                },
+            ],
+        );
+        assert_eq!(
+            named,
+            "fun greeting(name: String, count: UInt): String = \
+             \"Hi ${name}, you owe \\$${count}\""
+        );
+        assert_eq!(
+            template("page", "%1$s of %2$d", &[]),
+            "fun page(arg1: String, arg2: String): String = \
+             \"${arg1} of ${arg2}\""
+        );
+    }
+
+    #[test]
+    fn objects_mirror_the_namespaces() {
+        let mut graph = ResourceGraph::default();
+        graph.insert(
+            ResourceKey::from_path("auth/object"),
+            node(ResourceValue::String("Login".into())),
+        );
+        graph.insert(
+            ResourceKey::from_path("map/paris"),
+            node(ResourceValue::LatLng {
+                lat: 48.8566,
+                lng: 2.3522,
+            }),
+        );
+        graph.insert(
+            ResourceKey::from_path("skip"),
+            ResourceNode {
+                meta: NodeMeta {
+                    deprecated: Some("use next".into()),
+                    ..NodeMeta::default()
+                },
+                ..node(ResourceValue::Bool(true))
+            },
+        );
+
+        assert_eq!(
+            kotlin(&graph, Some("com.example.res")),
+            "// Generated by r-resources. Do not edit by hand.\n\
+             @file:Suppress(\"ClassName\")\n\
+             \n\
+             package com.example.res\n\
+             \n\
+             object R {\n    \
+             data class LatLng(val lat: Double, val lng: Double)\n    \
+             @Deprecated(\"use next\")\n    \
+             const val skip = true\n    \
+             object auth {\n        \
+             const val `object` = \"Login\"\n    \
+             }\n    \
+             object map {\n        \
+             val paris = LatLng(48.8566, 2.3522)\n    \
+             }\n\
+             }\n"
+        );
+    }
+}
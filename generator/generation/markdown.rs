@@ -0,0 +1,319 @@
+//! Markdown catalog of the resources.
+//!
+//! Writes one table per resource type, with the name, namespace, value,
+//! `<doc>` text, source file and profiles of every default resource, for
+//! browsing outside of the code:
+//!
+//! - colors show their hex value, templates with placeholders their
+//!   generated signature followed by the text
+//! - `|`, backticks and other Markdown punctuation are escaped, and
+//!   line breaks become `<br>`, so values never break a table
+//! - rows follow the sorted qualified names, or the declaration order
+//!   with `Ordering::Source`, so the output only changes when the
+//!   resources do
+//!
+//! Translations and test resources are not listed.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::generator::ir::types::{
+    date_text, money_text, template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberValue, Ordering, ResourceGraph, ResourceKey, ResourceNode,
+    ResourceValue,
+};
+use crate::generator::utils::display_path;
+
+const HEADER: &str = "# Resources\n\n\
+    <!-- Generated by r-resources. Do not edit by hand. -->\n";
+
+/// Tables, in output order
+const SECTIONS: [&str; 15] = [
+    "Strings",
+    "Templates",
+    "Numbers",
+    "Booleans",
+    "Colors",
+    "Coordinates",
+    "Positions",
+    "Versions",
+    "Addresses",
+    "Patterns",
+    "Identifiers",
+    "Dates",
+    "Prices",
+    "Characters",
+    "Binary",
+];
+
+/// Renders the catalog of `graph`, rows in `ordering`; source files
+/// are shown relative to `root` (`res/values.xml`)
+pub fn markdown(
+    graph: &ResourceGraph,
+    root: &Path,
+    ordering: Ordering,
+) -> String {
+    let mut rows: [Vec<String>; 7] = Default::default();
+    for (key, node) in graph.ordered_nodes(ordering) {
+        let (section, value) = section_and_value(key, node);
+        rows[section].push(format!(
+            "| {} | {} | {value} | {} | {} | {} |\n",
+            code(&key.name),
+            code(&key.namespace.join("/")),
+            node.meta.doc.as_deref().map(cell).unwrap_or_default(),
+            code(&display_path(&node.origin.file, root)),
+            profiles(graph, key),
+        ));
+    }
+
+    let mut out = String::from(HEADER);
+    for (title, rows) in SECTIONS.iter().zip(&rows) {
+        if rows.is_empty() {
+            continue;
+        }
+        let _ = write!(
+            out,
+            "\n## {title} ({})\n\n\
+             | Name | Namespace | Value | Doc | Source | Profiles |\n\
+             | --- | --- | --- | --- | --- | --- |\n",
+            rows.len()
+        );
+        rows.iter().for_each(|row| out.push_str(row));
+    }
+    out
+}
+
+/// Writes the catalog of `graph` to `path`, creating its parent
+/// directories
+pub fn export_markdown(
+    graph: &ResourceGraph,
+    root: &Path,
+    ordering: Ordering,
+    path: &Path,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, markdown(graph, root, ordering))
+}
+
+/// Index into [`SECTIONS`] and the escaped value cell of a node
+fn section_and_value(
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> (usize, String) {
+    match &node.value {
+        ResourceValue::String(text) => (0, cell(text)),
+        ResourceValue::Template { text, params } => {
+            let value = match template_signature(text, params) {
+                Some(signature) => format!(
+                    "{} {}",
+                    code(&format!(
+                        "{}({})",
+                        key.name, signature.params
+                    )),
+                    cell(text)
+                ),
+                None => cell(text),
+            };
+            (1, value)
+        }
+        ResourceValue::Number(number) => (2, number_value(number)),
+        ResourceValue::Bool(value) => (3, value.to_string()),
+        ResourceValue::Color(hex) => (4, code(hex)),
+        ResourceValue::LatLng { lat, lng } => {
+            (5, format!("{lat:?}, {lng:?}"))
+        }
+        ResourceValue::Position { x, y } => {
+            (6, format!("{x:?}, {y:?}"))
+        }
+        ResourceValue::Semver { text, .. } => (7, code(text)),
+        ResourceValue::IpAddr(ip) => (8, code(&ip.to_string())),
+        ResourceValue::SocketAddr(addr) => (8, code(&addr.to_string())),
+        ResourceValue::Regex(source) => (9, code(source)),
+        ResourceValue::Uuid(value) => (10, code(&uuid_text(*value))),
+        ResourceValue::DateTime { text, .. } => (11, code(text)),
+        ResourceValue::Date { year, month, day } => {
+            (11, code(&date_text(*year, *month, *day)))
+        }
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => (12, code(&money_text(*minor_units, *scale, currency))),
+        ResourceValue::Char(c) => (13, code(&c.to_string())),
+        ResourceValue::Grapheme(text) => (13, code(text)),
+        ResourceValue::Bytes(bytes) => {
+            (14, format!("{} bytes", bytes.len()))
+        }
+    }
+}
+
+fn number_value(number: &NumberValue) -> String {
+    match number {
+        NumberValue::Int(i) => i.to_string(),
+        NumberValue::Float(f) => format!("{f:?}"),
+        NumberValue::BigDecimal(raw) => {
+            format!("{} (BigDecimal)", cell(raw))
+        }
+        NumberValue::Typed { literal, ty } => {
+            format!("{} ({})", cell(literal), code(ty.as_str()))
+        }
+    }
+}
+
+/// Profile attributes of the definitions of `key`, empty when none has
+/// one
+fn profiles(graph: &ResourceGraph, key: &ResourceKey) -> String {
+    let Some(variants) = graph.profile_variants().get(key) else {
+        return String::new();
+    };
+    if variants.iter().all(|v| v.specs.is_empty()) {
+        return String::new();
+    }
+    let mut labels: Vec<String> = Vec::new();
+    for variant in variants {
+        let label = match variant.specs.as_slice() {
+            [] => "any".to_string(),
+            specs => specs.join(" + "),
+        };
+        if !labels.contains(&label) {
+            labels.push(label);
+        }
+    }
+    cell(&labels.join(", "))
+}
+
+/// Escapes text for a table cell
+fn cell(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '|' | '`' | '*' | '_' | '[' | ']' | '<' | '>' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("<br>"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Code span for a table cell; text with backticks or line breaks is
+/// escaped as plain text instead
+fn code(text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    if text.contains(['`', '\n']) {
+        return cell(text);
+    }
+    // Pipes end the cell even inside a code span
+    format!("`{}`", text.replace('|', "\\|"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ProfileVariant, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("/app/res/values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn values_are_escaped() {
+        assert_eq!(cell("a | b"), "a \\| b");
+        assert_eq!(cell("use `r::X`"), "use \\`r::X\\`");
+        assert_eq!(cell("line\nnext <b>"), "line<br>next \\<b\\>");
+        assert_eq!(code("#FF5722"), "`#FF5722`");
+        assert_eq!(code("a|b"), "`a\\|b`");
+        assert_eq!(code("a`b"), "a\\`b");
+    }
+
+    #[test]
+    fn one_table_per_type() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Log | in".into()),
+        );
+        insert(
+            &mut graph,
+            "ui/accent",
+            ResourceValue::Color("#FF5722".into()),
+        );
+        graph.record_profile_variant(
+            ResourceKey::from_path("ui/accent"),
+            ProfileVariant {
+                specs: vec!["dev,staging".into()],
+                file: Path::new("/app/res/values.xml").into(),
+                line: None,
+            },
+        );
+
+        assert_eq!(
+            markdown(
+                &graph,
+                Path::new("/app/res"),
+                Ordering::Alphabetical
+            ),
+            "# Resources\n\n\
+             <!-- Generated by r-resources. Do not edit by hand. -->\n\
+             \n## Strings (1)\n\n\
+             | Name | Namespace | Value | Doc | Source | Profiles |\n\
+             | --- | --- | --- | --- | --- | --- |\n\
+             | `title` | `auth` | Log \\| in |  | `res/values.xml` |  |\n\
+             \n## Colors (1)\n\n\
+             | Name | Namespace | Value | Doc | Source | Profiles |\n\
+             | --- | --- | --- | --- | --- | --- |\n\
+             | `accent` | `ui` | `#FF5722` |  | `res/values.xml` | dev,staging |\n"
+        );
+    }
+    #[test]
+    fn rows_can_follow_the_declaration_order() {
+        let mut graph = ResourceGraph::default();
+        for name in ["zebra", "apple"] {
+            let value = ResourceValue::String("x".into());
+            insert(&mut graph, name, value);
+        }
+        if let Some(node) =
+            graph.get_mut(&ResourceKey::from_path("apple"))
+        {
+            node.origin.index = 1;
+        }
+        let names = |ordering| {
+            let catalog =
+                markdown(&graph, Path::new("/app/res"), ordering);
+            let row = |name| catalog.find(name).expect(name);
+            (row("`zebra`"), row("`apple`"))
+        };
+
+        let (zebra, apple) = names(Ordering::Alphabetical);
+        assert!(apple < zebra);
+        let (zebra, apple) = names(Ordering::Source);
+        assert!(zebra < apple);
+    }
+}
@@ -3,28 +3,116 @@
 //! This module transforms the `ResourceGraph` into generated Rust code.
 //! Currently supports:
 //! - Flat module generation (`r::` namespace structure)
+//! - `r_tests::` for the test resources, in test builds only
+//! - `r::raw` embedding the files of `res/raw/`
+//! - Fluent (`.ftl`) export of strings and templates
+//! - Markdown catalog of every resource
+//! - TypeScript definitions for web frontends
+//! - Kotlin `object R` for Android shells
+//! - C header of `#define`s for FFI consumers
 //!
 //! Future generators can be added (e.g., hierarchical, JSON export, etc.)
 
+pub mod c_header;
+mod exports;
 mod flat;
+pub mod fluent;
+pub mod kotlin;
+pub mod markdown;
+pub mod sink;
+pub mod typescript;
 
-use crate::generator::analysis::{self, AnalysisError};
-use crate::generator::ir::{ResourceGraph, TypeRegistry};
+use std::path::Path;
 
+use crate::generator::analysis;
+use crate::generator::input::RawAsset;
+use crate::generator::ir::{Ordering, ResourceGraph, TypeRegistry};
+use crate::generator::options::{LazyStatics, ReferenceStyle};
+
+pub use flat::{BuildReport, CountingSink};
+// Run by the analysis, any collision fails the build before emitting
+pub(crate) use flat::{
+    check_flat_aliases, check_identifier_collisions,
+    check_localized_accessors, check_overridable,
+    check_registry_names,
+};
+pub use sink::{CodeSink, FileSink};
+
+#[allow(dead_code)] // Read by the string entry points, not by builds
 pub struct OutputArtifacts {
     pub rust: String,
+    /// `file:line: message` of each warning
     pub warnings: Vec<String>,
 }
 
+/// Settings affecting the generated code
+#[derive(Debug, Clone, Copy)]
+pub struct EmitOptions<'a> {
+    /// Profile baked into `r::BUILD_PROFILE`
+    pub profile: &'a str,
+    /// Emit the `r::lookup` runtime tables
+    pub lookup: bool,
+    /// Resource root for the `/// Defined in ...` doc lines (paths are
+    /// shown relative to it); `None` omits them
+    pub provenance_root: Option<&'a Path>,
+    /// Emit `string::`, `int::`, ... next to `r::`
+    pub typed_modules: bool,
+    /// Files of `res/raw/`, embedded in `r::raw`
+    pub raw_assets: &'a [RawAsset],
+    /// Emit the accessors of the `hot-reload` feature
+    pub hot_reload: bool,
+    /// Emit the `ALL` tables of the typed modules and
+    /// `r::meta::summary()`
+    pub registries: bool,
+    /// Emit `r::to_json()`, for the `serde` feature
+    pub json: bool,
+    /// Emit the `LazyLock<regex::Regex>` statics of `<regex>`, for the
+    /// `regex` feature
+    pub regex: bool,
+    /// Re-export the namespaced items at the root of `r::`, joining
+    /// the namespaces with this separator
+    pub flatten_aliases: Option<&'a str>,
+    /// Whether the flattened aliases re-export or copy the items
+    pub reference_style: ReferenceStyle,
+    /// Emit the overridable getters of the `overridable="true"`
+    /// resources
+    pub testable: bool,
+    /// Resource files listed in `r::meta::SOURCE_FILES`
+    pub source_files: &'a [String],
+    /// Unix time baked into `r::meta::GENERATED_AT_UNIX`
+    pub generated_at: i64,
+    /// Order of the items of each module
+    pub ordering: Ordering,
+    /// Type of the `BigDecimal` and regex statics
+    pub lazy: LazyStatics,
+}
+
+/// Generates the code of `graph` as one string, see [`emit_to`]
+#[allow(dead_code)] // String entry point, for tests
 pub fn emit(
     graph: &ResourceGraph,
     analysis_warnings: &[analysis::AnalysisWarning],
-) -> Result<OutputArtifacts, Vec<AnalysisError>> {
+    options: &EmitOptions<'_>,
+) -> OutputArtifacts {
+    let mut rust = String::new();
+    let warnings =
+        emit_to(&mut rust, graph, analysis_warnings, options);
+    OutputArtifacts { rust, warnings }
+}
+
+/// Streams the code of `graph` into `code`, returning the warnings to
+/// report; identifier collisions are errors of the analysis, which
+/// must have passed
+pub fn emit_to(
+    code: &mut dyn CodeSink,
+    graph: &ResourceGraph,
+    analysis_warnings: &[analysis::AnalysisWarning],
+    options: &EmitOptions<'_>,
+) -> Vec<String> {
     let registry = TypeRegistry::default();
-    let mut rust_code = String::new();
 
     // Generate main R struct
-    rust_code.push_str(
+    code.push_str(
         r#"
 pub struct R;
 
@@ -44,17 +132,21 @@ impl R {
     );
 
     // Generate flat r:: module with duplicate warnings
-    rust_code.push_str(&flat::generate_r_module(
-        graph,
-        &registry,
-        analysis_warnings,
-    ));
-
-    Ok(OutputArtifacts {
-        rust: rust_code,
-        warnings: analysis_warnings
-            .iter()
-            .map(|w| w.message.clone())
-            .collect(),
-    })
+    flat::emit_r_module(code, graph, &registry, analysis_warnings, options);
+    if options.typed_modules {
+        flat::emit_typed_modules(
+            code,
+            graph,
+            options.registries,
+            options.ordering,
+            options.lazy,
+        );
+    }
+    flat::emit_tests_module(code, graph, &registry, options);
+
+    analysis_warnings
+        .iter()
+        .map(|w| w.to_diagnostic().located_message())
+        .chain(flat::config_warnings(graph))
+        .collect()
 }
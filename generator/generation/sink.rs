@@ -0,0 +1,95 @@
+//! Destinations of the generated code.
+//!
+//! Emitters append to a [`CodeSink`] piece by piece instead of
+//! returning strings, so builds stream the code to `r_generated.rs`
+//! through a [`FileSink`] and never hold all of it in memory. A
+//! `String` is a sink too, for tests and the string entry points
+//! ([`emit`](super::emit)).
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write as _};
+use std::path::Path;
+
+/// Output of the emitters; `fmt::Write` gives `write!` and `writeln!`
+pub trait CodeSink: fmt::Write {
+    /// Appends `code`. Sinks that can fail keep the error for later
+    /// (see [`FileSink::finish`]), so emitters never handle it.
+    fn push_str(&mut self, code: &str) {
+        let _ = self.write_str(code);
+    }
+}
+
+impl CodeSink for String {
+    fn push_str(&mut self, code: &str) {
+        String::push_str(self, code);
+    }
+}
+
+/// Buffered sink writing to a file
+pub struct FileSink {
+    writer: BufWriter<File>,
+    /// First write error; later writes are skipped
+    error: Option<io::Error>,
+}
+
+impl FileSink {
+    /// Creates (or truncates) `path`, creating its parent directories
+    pub fn create(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(Self {
+            writer: BufWriter::new(File::create(path)?),
+            error: None,
+        })
+    }
+
+    /// Flushes the file, or returns the first error met while writing
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.writer.flush(),
+        }
+    }
+}
+
+impl fmt::Write for FileSink {
+    fn write_str(&mut self, code: &str) -> fmt::Result {
+        if self.error.is_some() {
+            return Err(fmt::Error);
+        }
+        self.writer.write_all(code.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+impl CodeSink for FileSink {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emit(code: &mut dyn CodeSink) {
+        code.push_str("pub mod r {\n");
+        let _ = writeln!(code, "    pub const A: i64 = {};", 1);
+        code.push_str("}\n");
+    }
+
+    #[test]
+    fn file_and_string_sinks_get_the_same_code() {
+        let mut string = String::new();
+        emit(&mut string);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out/r_generated.rs");
+        let mut file = FileSink::create(&path).unwrap();
+        emit(&mut file);
+        file.finish().unwrap();
+
+        assert_eq!(std::fs::read_to_string(path).unwrap(), string);
+        assert_eq!(string, "pub mod r {\n    pub const A: i64 = 1;\n}\n");
+    }
+}
@@ -0,0 +1,478 @@
+//! TypeScript definitions of the resources, for web frontends.
+//!
+//! Writes a `resources.ts` exporting one `as const` object mirroring
+//! the `r::` namespace tree:
+//!
+//! ```ts
+//! export const r = {
+//!   appName: "My App",
+//!   primary: "#FF5722",
+//!   greeting(name: string, count: number): string {
+//!     return `Hello ${name}, ${count} new messages`;
+//!   },
+//!   auth: {
+//!     title: "Login",
+//!   },
+//! } as const;
+//! ```
+//!
+//! - names are camelCased (`app_name` → `appName`); names colliding
+//!   once converted get a numeric suffix (`appName2`)
+//! - templates with placeholders become typed methods returning a
+//!   template literal, the others plain strings
+//! - integers outside `Number.MAX_SAFE_INTEGER` and `BigDecimal`
+//!   values are kept as strings, with a comment
+//! - `<doc>` text (of resources and namespaces) and deprecations
+//!   become JSDoc comments
+//!
+//! Translations and test resources are not exported.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use super::exports::{doc_comment, doc_lines, ResourceTree};
+use crate::generator::ir::types::{
+    date_text, hex_text, integer_value, money_text,
+    template_signature, uuid_text,
+};
+use crate::generator::ir::{
+    NumberValue, ResourceGraph, ResourceNode, ResourceValue,
+    TemplateParam, TemplateParamValue,
+};
+use crate::generator::utils::{lower_camel_case, unique_suffixed};
+
+const HEADER: &str =
+    "// Generated by r-resources. Do not edit by hand.\n\n";
+
+/// Largest integer a JavaScript `number` holds exactly
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// Words that cannot name a function parameter
+const RESERVED: [&str; 38] = [
+    "arguments", "await", "break", "case", "catch", "class", "const",
+    "continue", "debugger", "default", "delete", "do", "else", "enum",
+    "eval", "export", "extends", "false", "finally", "for",
+    "function", "if", "implements", "import", "in", "instanceof",
+    "interface", "let", "new", "null", "return", "super", "switch",
+    "this", "throw", "true", "try", "while",
+];
+
+/// Renders the definitions of `graph`
+pub fn typescript(graph: &ResourceGraph) -> String {
+    let mut out = String::from(HEADER);
+    out.push_str("export const r = {\n");
+    render_namespace(&mut out, &ResourceTree::new(graph), 1);
+    out.push_str("} as const;\n\nexport type R = typeof r;\n");
+    out
+}
+
+/// Writes the definitions of `graph` to `path`, creating its parent
+/// directories
+pub fn export_typescript(
+    graph: &ResourceGraph,
+    path: &Path,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, typescript(graph))
+}
+
+/// Properties of `namespace`: its resources, then its sub-namespaces
+fn render_namespace(
+    out: &mut String,
+    namespace: &ResourceTree<'_>,
+    depth: usize,
+) {
+    let pad = "  ".repeat(depth);
+    let mut used = HashSet::new();
+    for (key, node) in &namespace.resources {
+        let name = unique_property(&key.name, &mut used);
+        let deprecated = node.meta.deprecated.as_deref();
+        jsdoc(out, node.meta.doc.as_deref(), deprecated, &pad);
+        out.push_str(&property(&name, node, &pad));
+    }
+    for (segment, child) in &namespace.children {
+        let name = unique_property(segment, &mut used);
+        jsdoc(out, child.doc, None, &pad);
+        let _ = writeln!(out, "{pad}{}: {{", property_name(&name));
+        render_namespace(out, child, depth + 1);
+        let _ = writeln!(out, "{pad}}},");
+    }
+}
+
+/// JSDoc of `<doc>` text and a deprecation note
+fn jsdoc(
+    out: &mut String,
+    doc: Option<&str>,
+    deprecated: Option<&str>,
+    pad: &str,
+) {
+    let deprecated = deprecated.map(|note| {
+        format!("@deprecated {note}").trim_end().to_string()
+    });
+    doc_comment(out, doc_lines(doc).chain(deprecated), pad);
+}
+
+/// Property (or method) of a resource, with its trailing comma
+fn property(name: &str, node: &ResourceNode, pad: &str) -> String {
+    let name = property_name(name);
+    match &node.value {
+        ResourceValue::String(text) | ResourceValue::Regex(text) => {
+            format!("{pad}{name}: {},\n", string_literal(text))
+        }
+        ResourceValue::Template { text, params } => {
+            template(&name, text, params, pad)
+        }
+        ResourceValue::Number(number) => match number_literal(number)
+        {
+            Ok(literal) => format!("{pad}{name}: {literal},\n"),
+            Err((literal, reason)) => {
+                format!("{pad}{name}: {literal}, // {reason}\n")
+            }
+        },
+        ResourceValue::Bool(value) => {
+            format!("{pad}{name}: {value},\n")
+        }
+        ResourceValue::Color(hex) => {
+            format!("{pad}{name}: {},\n", string_literal(hex))
+        }
+        ResourceValue::LatLng { lat, lng } => format!(
+            "{pad}{name}: {{ lat: {}, lng: {} }},\n",
+            float(*lat),
+            float(*lng)
+        ),
+        ResourceValue::Position { x, y } => format!(
+            "{pad}{name}: {{ x: {}, y: {} }},\n",
+            float(*x),
+            float(*y)
+        ),
+        ResourceValue::Semver { text, .. } => {
+            format!("{pad}{name}: {},\n", string_literal(text))
+        }
+        ResourceValue::IpAddr(ip) => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&ip.to_string())
+        ),
+        ResourceValue::SocketAddr(addr) => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&addr.to_string())
+        ),
+        ResourceValue::Uuid(value) => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&uuid_text(*value))
+        ),
+        ResourceValue::DateTime { text, .. } => {
+            format!("{pad}{name}: {},\n", string_literal(text))
+        }
+        ResourceValue::Date { year, month, day } => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&date_text(*year, *month, *day))
+        ),
+        ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } => {
+            let text = money_text(*minor_units, *scale, currency);
+            format!("{pad}{name}: {},\n", string_literal(&text))
+        }
+        ResourceValue::Char(c) => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&c.to_string())
+        ),
+        ResourceValue::Grapheme(text) => {
+            format!("{pad}{name}: {},\n", string_literal(text))
+        }
+        ResourceValue::Bytes(bytes) => format!(
+            "{pad}{name}: {},\n",
+            string_literal(&hex_text(bytes))
+        ),
+    }
+}
+
+/// Typed method returning a template literal, or a string for a
+/// template without placeholders
+fn template(
+    name: &str,
+    text: &str,
+    params: &[TemplateParam],
+    pad: &str,
+) -> String {
+    let Some(signature) = template_signature(text, params) else {
+        return format!("{pad}{name}: {},\n", string_literal(text));
+    };
+    let mut body = template_literal_text(text);
+    let mut arguments = Vec::new();
+    if params.is_empty() {
+        // Positional placeholders, named like the Rust arguments
+        for (i, arg) in signature.args.split(", ").enumerate() {
+            for conversion in ['s', 'd'] {
+                body = body.replace(
+                    &format!("%{}${conversion}", i + 1),
+                    &format!("${{{arg}}}"),
+                );
+            }
+            arguments.push(format!("{arg}: string"));
+        }
+    } else {
+        for param in params {
+            let ident = parameter_identifier(&param.name);
+            body = body.replace(
+                &format!("{{{}}}", param.name),
+                &format!("${{{ident}}}"),
+            );
+            arguments
+                .push(format!("{ident}: {}", parameter_type(param)));
+        }
+    }
+    format!(
+        "{pad}{name}({}): string {{\n\
+         {pad}  return `{body}`;\n\
+         {pad}}},\n",
+        arguments.join(", ")
+    )
+}
+
+/// TypeScript type of a template parameter
+fn parameter_type(param: &TemplateParam) -> &'static str {
+    match &param.value {
+        TemplateParamValue::String | TemplateParamValue::Color => {
+            "string"
+        }
+        TemplateParamValue::Bool => "boolean",
+        TemplateParamValue::Number { explicit_type } => {
+            match explicit_type.as_deref() {
+                Some("bigdecimal") => "string",
+                _ => "number",
+            }
+        }
+    }
+}
+
+/// Literal of a number, or a string literal and the reason why when a
+/// `number` would lose precision
+fn number_literal(
+    number: &NumberValue,
+) -> Result<String, (String, &'static str)> {
+    const UNSAFE: &str = "beyond Number.MAX_SAFE_INTEGER";
+    match number {
+        NumberValue::Int(i) => {
+            if i128::from(*i).abs() <= MAX_SAFE_INTEGER {
+                Ok(i.to_string())
+            } else {
+                Err((string_literal(&i.to_string()), UNSAFE))
+            }
+        }
+        NumberValue::Float(f) => Ok(float(*f)),
+        NumberValue::BigDecimal(raw) => {
+            Err((string_literal(raw), "BigDecimal"))
+        }
+        NumberValue::Typed { literal, ty } => {
+            let digits = literal.replace('_', "");
+            if ty.is_float() {
+                return digits.parse::<f64>().map(float).map_err(
+                    |_| (string_literal(literal), ty.as_str()),
+                );
+            }
+            match integer_value(literal) {
+                Some(i) if i.abs() <= MAX_SAFE_INTEGER => {
+                    Ok(i.to_string())
+                }
+                Some(i) => {
+                    Err((string_literal(&i.to_string()), UNSAFE))
+                }
+                None => Err((string_literal(literal), ty.as_str())),
+            }
+        }
+    }
+}
+
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("{sign}Infinity")
+    } else {
+        format!("{value:?}")
+    }
+}
+
+/// Property assigned to `name` in a namespace (`camelCase`, suffixed
+/// when already taken)
+fn unique_property(name: &str, used: &mut HashSet<String>) -> String {
+    unique_suffixed(lower_camel_case(name), used)
+}
+
+/// Property name, quoted when it is not an identifier
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+/// Identifier of a template parameter: `camelCase`, with a `_` suffix
+/// for reserved words and a `_` prefix before digits
+fn parameter_identifier(name: &str) -> String {
+    let ident = lower_camel_case(name);
+    if RESERVED.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else if is_identifier(&ident) {
+        ident
+    } else {
+        format!("_{ident}")
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Double-quoted string literal
+fn string_literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Escapes text for a template literal, before the placeholders are
+/// substituted
+fn template_literal_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKey, ResourceKind, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn insert(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: ResourceValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("/app/res/values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    #[test]
+    fn names_are_camel_cased() {
+        assert_eq!(property_name("2fa_hint"), "\"2fa_hint\"");
+        assert_eq!(parameter_identifier("default"), "default_");
+
+        let mut used = HashSet::new();
+        assert_eq!(unique_property("app_name", &mut used), "appName");
+        assert_eq!(unique_property("appName", &mut used), "appName2");
+    }
+
+    #[test]
+    fn object_mirrors_the_namespaces() {
+        let mut graph = ResourceGraph::default();
+        insert(
+            &mut graph,
+            "app_name",
+            ResourceValue::String("My \"App\"".into()),
+        );
+        insert(
+            &mut graph,
+            "primary",
+            ResourceValue::Color("#FF5722".into()),
+        );
+        insert(
+            &mut graph,
+            "auth/title",
+            ResourceValue::String("Login".into()),
+        );
+        insert(
+            &mut graph,
+            "greeting",
+            ResourceValue::Template {
+                text: "Hello {name}, ${count} `new`".into(),
+                params: vec![
+                    TemplateParam {
+                        name: "name".into(),
+                        value: TemplateParamValue::String,
+                    },
+                    TemplateParam {
+                        name: "count".into(),
+                        value: TemplateParamValue::Number {
+                            explicit_type: None,
+                        },
+                    },
+                ],
+            },
+        );
+        insert(
+            &mut graph,
+            "huge",
+            ResourceValue::Number(NumberValue::Int(i64::MAX)),
+        );
+
+        assert_eq!(
+            typescript(&graph),
+            "// Generated by r-resources. Do not edit by hand.\n\n\
+             export const r = {\n  \
+             appName: \"My \\\"App\\\"\",\n  \
+             greeting(name: string, count: number): string {\n    \
+             return `Hello ${name}, \\$${count} \\`new\\``;\n  \
+             },\n  \
+             huge: \"9223372036854775807\", // beyond Number.MAX_SAFE_INTEGER\n  \
+             primary: \"#FF5722\",\n  \
+             auth: {\n    \
+             title: \"Login\",\n  \
+             },\n\
+             } as const;\n\n\
+             export type R = typeof r;\n"
+        );
+    }
+
+    #[test]
+    fn positional_templates_take_strings() {
+        let code = template("page", "Page", &[], "");
+        assert_eq!(code, "page: \"Page\",\n");
+
+        let code = template("page", "%1$s of %2$d", &[], "");
+        assert_eq!(
+            code,
+            "page(arg1: string, arg2: string): string {\n  \
+             return `${arg1} of ${arg2}`;\n\
+             },\n"
+        );
+    }
+}
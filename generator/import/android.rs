@@ -0,0 +1,895 @@
+//! Android `res/values*/` files to this crate's XML
+//! (`r-res import-android`).
+//!
+//! - `<string>` keeps the text Android would display: `\'`, `\"`,
+//!   `\n`, `\t` and `\uXXXX` escapes are resolved, double quotes keep
+//!   whitespace, and whitespace outside quotes collapses
+//! - strings with format arguments (`%1$s`, `%d`, `%.2f`) become
+//!   `<template>`s with the parameters `arg1..argN`: `%s` is a
+//!   `<string>`, `%d` a `<number>`, `%f` a `<number type="f64">` and
+//!   `%b` a `<bool>`; `%%` and `%n` become `%` and a line break
+//! - `<integer>`, `<bool>` and `<color>`, also written as
+//!   `<item type="...">`, map to `<number>`, `<bool>` and `<color>`
+//! - `values-fr/strings.xml` and `values-pt-rBR/strings.xml` become the
+//!   translations `strings-fr.xml` and `strings-pt-BR.xml`
+//!
+//! Everything else is reported: `<plurals>`, `<string-array>`,
+//! `<dimen>`, references to other resource types, markup inside strings
+//! and non-locale qualifiers (`values-night/`). Skipped elements leave a
+//! comment in the converted file so they can be ported by hand.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::Reader;
+
+const HEADER: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+    <!-- Imported from Android resources by `r-res import-android` -->\n\
+    <resources>\n";
+
+/// One Android resource file in this crate's XML
+#[derive(Debug, Default, PartialEq)]
+pub struct Conversion {
+    pub xml: String,
+    /// Number of resources converted
+    pub resources: usize,
+    /// What was skipped or changed, one message per construct
+    pub warnings: Vec<String>,
+}
+
+/// A converted file of an Android resource directory
+#[derive(Debug)]
+pub struct ImportedFile {
+    /// Android file, relative to the imported directory
+    /// (`values-fr/strings.xml`)
+    pub source: PathBuf,
+    /// File name in the resources directory (`strings-fr.xml`)
+    pub target: PathBuf,
+    pub conversion: Conversion,
+}
+
+/// Converted files of an Android `res/` directory
+#[derive(Debug, Default)]
+pub struct AndroidImport {
+    pub files: Vec<ImportedFile>,
+    /// Directories that were not imported, with the reason
+    pub skipped: Vec<String>,
+}
+
+impl AndroidImport {
+    /// Writes the files with at least one resource into `res_dir`,
+    /// returning their paths; existing files are only replaced with
+    /// `overwrite`
+    pub fn write_to(
+        &self,
+        res_dir: &Path,
+        overwrite: bool,
+    ) -> io::Result<Vec<PathBuf>> {
+        let files: Vec<(&ImportedFile, PathBuf)> = self
+            .files
+            .iter()
+            .filter(|file| file.conversion.resources > 0)
+            .map(|file| (file, res_dir.join(&file.target)))
+            .collect();
+        if let Some((_, existing)) =
+            files.iter().find(|(_, path)| !overwrite && path.exists())
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", existing.display()),
+            ));
+        }
+        fs::create_dir_all(res_dir)?;
+        for (file, path) in &files {
+            fs::write(path, &file.conversion.xml)?;
+        }
+        Ok(files.into_iter().map(|(_, path)| path).collect())
+    }
+}
+
+/// Converts the `values*/` directories of an Android `res/` directory;
+/// other directories (`drawable/`, `layout/`, ...) are ignored
+pub fn import_dir(android_res: &Path) -> io::Result<AndroidImport> {
+    let mut import = AndroidImport::default();
+    for dir in sorted_entries(android_res)? {
+        let name =
+            dir.file_name().unwrap_or_default().to_string_lossy();
+        let Some(qualifier) = name.strip_prefix("values") else {
+            continue;
+        };
+        if !dir.is_dir() {
+            continue;
+        }
+        let Some(locale) = values_locale(qualifier) else {
+            import.skipped.push(format!(
+                "{name}/ was not imported: only locale qualifiers are supported"
+            ));
+            continue;
+        };
+        import_values_dir(
+            &mut import,
+            &dir,
+            &name,
+            locale.as_deref(),
+        )?;
+    }
+    Ok(import)
+}
+
+fn import_values_dir(
+    import: &mut AndroidImport,
+    dir: &Path,
+    dir_name: &str,
+    locale: Option<&str>,
+) -> io::Result<()> {
+    let files = sorted_entries(dir)?
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|e| e == "xml"));
+    for path in files {
+        let stem =
+            path.file_stem().unwrap_or_default().to_string_lossy();
+        let source = Path::new(dir_name)
+            .join(path.file_name().unwrap_or_default());
+        let conversion = convert(&fs::read_to_string(&path)?)
+            .map_err(|message| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {message}", source.display()),
+                )
+            })?;
+        let target = match locale {
+            Some(locale) => format!("{stem}-{locale}.xml"),
+            None => format!("{stem}.xml"),
+        };
+        import.files.push(ImportedFile {
+            source,
+            target: PathBuf::from(target),
+            conversion,
+        });
+    }
+    Ok(())
+}
+
+fn sorted_entries(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// Locale of a `values` directory from what follows `values`: `Some(None)`
+/// for `values/`, `None` for other qualifiers (`-night`, `-v21`)
+fn values_locale(qualifier: &str) -> Option<Option<String>> {
+    if qualifier.is_empty() {
+        return Some(None);
+    }
+    qualifier
+        .strip_prefix('-')
+        .and_then(android_locale)
+        .map(Some)
+}
+
+/// `fr` → `fr`, `pt-rBR` → `pt-BR`, `b+sr+Latn` → `sr-Latn`
+fn android_locale(qualifier: &str) -> Option<String> {
+    let (language, region) = match qualifier.strip_prefix("b+") {
+        Some(tag) => match tag.split_once('+') {
+            Some((language, region)) => (language, Some(region)),
+            None => (tag, None),
+        },
+        None => match qualifier.split_once("-r") {
+            Some((language, region)) => (language, Some(region)),
+            None => (qualifier, None),
+        },
+    };
+    let is_language = (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase());
+    let is_region = |region: &str| {
+        (2..=4).contains(&region.len())
+            && region.chars().all(|c| c.is_ascii_alphanumeric())
+    };
+    match region {
+        _ if !is_language => None,
+        None => Some(language.to_string()),
+        Some(region) if is_region(region) => {
+            Some(format!("{language}-{region}"))
+        }
+        Some(_) => None,
+    }
+}
+
+/// Converts the contents of one Android resource file
+pub fn convert(xml: &str) -> Result<Conversion, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut converter = Converter::default();
+    let mut in_resources = false;
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| xml_error(&reader, &e))?
+        {
+            Event::Start(e) if in_resources => {
+                converter.element(&mut reader, &e)?;
+            }
+            Event::Start(e) if e.name().as_ref() == b"resources" => {
+                in_resources = true;
+            }
+            Event::Start(e) => {
+                return Err(format!(
+                    "expected <resources>, found <{}>",
+                    String::from_utf8_lossy(e.name().as_ref())
+                ));
+            }
+            Event::Empty(e) if in_resources => converter.empty(&e),
+            Event::Comment(comment) if in_resources => {
+                converter.line(&format!(
+                    "<!--{}-->",
+                    String::from_utf8_lossy(&comment)
+                ));
+            }
+            Event::End(_) => in_resources = false,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(converter.finish())
+}
+
+fn xml_error(
+    reader: &Reader<&[u8]>,
+    error: &quick_xml::Error,
+) -> String {
+    format!("XML error at byte {}: {error}", reader.buffer_position())
+}
+
+/// Text of an element, with the names of the markup tags inside it
+#[derive(Default)]
+struct Content {
+    text: String,
+    markup: Vec<String>,
+}
+
+impl Content {
+    /// Reads up to the end of the element that was just opened
+    fn read(reader: &mut Reader<&[u8]>) -> Result<Self, String> {
+        let mut content = Self::default();
+        let mut depth = 0usize;
+        loop {
+            match reader
+                .read_event()
+                .map_err(|e| xml_error(reader, &e))?
+            {
+                Event::Text(text) => content.text.push_str(
+                    &text.decode().map_err(|e| e.to_string())?,
+                ),
+                Event::CData(cdata) => content
+                    .text
+                    .push_str(&String::from_utf8_lossy(&cdata)),
+                Event::GeneralRef(reference) => {
+                    content.text.push_str(&resolve(&reference)?);
+                }
+                Event::Start(tag) => {
+                    depth += 1;
+                    content.markup(&tag);
+                }
+                Event::Empty(tag) => content.markup(&tag),
+                Event::End(_) if depth == 0 => return Ok(content),
+                Event::End(_) => depth -= 1,
+                Event::Eof => {
+                    return Err("unexpected end of file".to_string())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Records a markup tag; `<xliff:g>` only annotates its text for
+    /// translators and is dropped silently
+    fn markup(&mut self, tag: &BytesStart<'_>) {
+        let name =
+            String::from_utf8_lossy(tag.name().as_ref()).into_owned();
+        if name != "xliff:g" && !self.markup.contains(&name) {
+            self.markup.push(name);
+        }
+    }
+}
+
+/// Resolves `&amp;`, `&#233;`, ...
+fn resolve(reference: &BytesRef<'_>) -> Result<String, String> {
+    let name =
+        String::from_utf8_lossy(reference.as_ref()).into_owned();
+    if let Ok(Some(ch)) = reference.resolve_char_ref() {
+        return Ok(ch.to_string());
+    }
+    resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .ok_or_else(|| format!("unknown entity `&{name};`"))
+}
+
+#[derive(Default)]
+struct Converter {
+    body: String,
+    resources: usize,
+    warnings: Vec<String>,
+}
+
+impl Converter {
+    fn element(
+        &mut self,
+        reader: &mut Reader<&[u8]>,
+        e: &BytesStart<'_>,
+    ) -> Result<(), String> {
+        let tag = element_kind(e);
+        if !matches!(
+            tag.as_str(),
+            "string" | "integer" | "bool" | "color"
+        ) {
+            reader
+                .read_to_end(e.to_end().name())
+                .map_err(|e| xml_error(reader, &e))?;
+            self.unsupported(e);
+            return Ok(());
+        }
+        let content = Content::read(reader)?;
+        let name = self.resource_name(&tag, e);
+        for markup in &content.markup {
+            self.warnings.push(format!(
+                "{tag} '{name}': <{markup}> markup was dropped, its text is kept"
+            ));
+        }
+        self.resource(&tag, &name, e, &content.text);
+        Ok(())
+    }
+
+    /// Self-closing element: an empty resource
+    fn empty(&mut self, e: &BytesStart<'_>) {
+        let tag = element_kind(e);
+        if matches!(
+            tag.as_str(),
+            "string" | "integer" | "bool" | "color"
+        ) {
+            let name = self.resource_name(&tag, e);
+            self.resource(&tag, &name, e, "");
+        } else {
+            self.unsupported(e);
+        }
+    }
+
+    fn resource(
+        &mut self,
+        tag: &str,
+        name: &str,
+        e: &BytesStart<'_>,
+        text: &str,
+    ) {
+        let value = text.trim();
+        match tag {
+            "string" => return self.string(name, e, text),
+            _ if value.starts_with('@') => {
+                let reason =
+                    format!("references another resource ({value})");
+                return self.skip(tag, name, &reason);
+            }
+            _ => {}
+        }
+        let (tag, value) = match tag {
+            "integer" => ("number", value.to_string()),
+            "bool" => ("bool", value.to_string()),
+            _ => ("color", android_color(value)),
+        };
+        self.line(&format!(
+            "<{tag} name=\"{}\">{}</{tag}>",
+            escape(name),
+            escape(&value)
+        ));
+        self.resources += 1;
+    }
+
+    fn string(&mut self, name: &str, e: &BytesStart<'_>, raw: &str) {
+        let text = android_text(raw);
+        let attributes = match attribute(e, "translatable").as_deref()
+        {
+            Some("false") => " translatable=\"false\"",
+            _ => "",
+        };
+        let arguments = match attribute(e, "formatted").as_deref() {
+            Some("false") => Ok(None),
+            _ => FormatArgs::parse(&text),
+        };
+        let template = match arguments {
+            Ok(Some(template)) => template,
+            Ok(None) => {
+                return self.plain_string(name, attributes, &text)
+            }
+            Err(reason) => return self.skip("string", name, &reason),
+        };
+        for spec in &template.lossy {
+            self.warnings.push(format!(
+                "string '{name}': `{spec}` becomes a plain parameter, its formatting is lost"
+            ));
+        }
+        self.template(name, attributes, &template);
+    }
+
+    fn plain_string(
+        &mut self,
+        name: &str,
+        attributes: &str,
+        text: &str,
+    ) {
+        let name = escape(name);
+        let line = match text {
+            "" => format!("<string name=\"{name}\"{attributes}/>"),
+            // Values are trimmed unless whitespace is preserved
+            _ if text.trim() != text => format!(
+                "<string name=\"{name}\"{attributes} xml:space=\"preserve\">{}</string>",
+                escape(text)
+            ),
+            _ => format!(
+                "<string name=\"{name}\"{attributes}>{}</string>",
+                escape(text)
+            ),
+        };
+        self.line(&line);
+        self.resources += 1;
+    }
+
+    fn template(
+        &mut self,
+        name: &str,
+        attributes: &str,
+        template: &FormatArgs,
+    ) {
+        let name = escape(name);
+        let params: Vec<String> = template
+            .params
+            .iter()
+            .map(|(index, kind)| kind.element(*index))
+            .collect();
+        let text = &template.text;
+        if text.contains('\n') || text.trim() != text {
+            // Kept verbatim on one line, as indentation would be text
+            self.line(&format!(
+                "<template name=\"{name}\"{attributes} trim=\"none\">{}{}</template>",
+                params.concat(),
+                escape(text)
+            ));
+        } else {
+            self.line(&format!(
+                "<template name=\"{name}\"{attributes}>"
+            ));
+            for param in params {
+                self.line(&format!("    {param}"));
+            }
+            self.line(&format!("    {}", escape(text)));
+            self.line("</template>");
+        }
+        self.resources += 1;
+    }
+
+    /// Name of a resource; Android allows `.`, which becomes `_`
+    fn resource_name(
+        &mut self,
+        tag: &str,
+        e: &BytesStart<'_>,
+    ) -> String {
+        let name = attribute(e, "name").unwrap_or_default();
+        let renamed: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        if renamed != name {
+            self.warnings.push(format!(
+                "{tag} '{name}' is renamed to '{renamed}'"
+            ));
+        }
+        renamed
+    }
+
+    fn unsupported(&mut self, e: &BytesStart<'_>) {
+        let tag =
+            String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let name = attribute(e, "name").unwrap_or_default();
+        let reason = match tag.as_str() {
+            "string-array" | "integer-array" | "array" => {
+                "arrays are not supported"
+            }
+            "plurals" => "plurals are not supported",
+            _ => "the element is not supported",
+        };
+        self.skip(&tag, &name, reason);
+    }
+
+    fn skip(&mut self, tag: &str, name: &str, reason: &str) {
+        let message =
+            format!("{tag} '{name}' was not imported: {reason}");
+        // `--` cannot appear in a comment
+        self.line(&format!(
+            "<!-- {} -->",
+            message.replace("--", "- -")
+        ));
+        self.warnings.push(message);
+    }
+
+    fn line(&mut self, line: &str) {
+        let _ = writeln!(self.body, "    {line}");
+    }
+
+    fn finish(self) -> Conversion {
+        Conversion {
+            xml: format!("{HEADER}{}</resources>\n", self.body),
+            resources: self.resources,
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// Tag of an element, with `<item type="bool">` read as `<bool>`
+fn element_kind(e: &BytesStart<'_>) -> String {
+    match e.name().as_ref() {
+        b"item" => attribute(e, "type").unwrap_or_default(),
+        name => String::from_utf8_lossy(name).into_owned(),
+    }
+}
+
+fn attribute(e: &BytesStart<'_>, name: &str) -> Option<String> {
+    let attr = e.try_get_attribute(name).ok()??;
+    attr.unescape_value().ok().map(|value| value.into_owned())
+}
+
+/// Text Android displays for a string: escapes are resolved,
+/// whitespace outside double quotes collapses to one space and is
+/// trimmed, and the quotes themselves are dropped
+fn android_text(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut quoted = false;
+    let mut space = false;
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() && !quoted {
+            space = true;
+            continue;
+        }
+        if std::mem::take(&mut space) && !out.is_empty() {
+            out.push(' ');
+        }
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => push_escape(&mut out, &mut chars),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Pushes the character escaped by the `\` just read
+fn push_escape(out: &mut String, chars: &mut std::str::Chars<'_>) {
+    match chars.next() {
+        Some('n') => out.push('\n'),
+        Some('t') => out.push('\t'),
+        Some('u') => {
+            let hex: String = chars.clone().take(4).collect();
+            let escaped = u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .filter(|_| hex.len() == 4);
+            match escaped {
+                Some(c) => {
+                    out.push(c);
+                    chars.nth(3);
+                }
+                None => out.push('u'),
+            }
+        }
+        // `\'`, `\"`, `\\`, `\@`, `\?`
+        Some(c) => out.push(c),
+        None => {}
+    }
+}
+
+/// `#ARGB` is expanded to `#AARRGGBB`, other values are kept
+fn android_color(value: &str) -> String {
+    match value.strip_prefix('#') {
+        Some(digits) if digits.len() == 4 => {
+            let doubled: String =
+                digits.chars().flat_map(|c| [c, c]).collect();
+            format!("#{doubled}")
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Escapes text and attribute values; line breaks become `&#10;` so
+/// values stay on one line
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Template parameter type of a format conversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParamKind {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl ParamKind {
+    fn of(conversion: char) -> Self {
+        match conversion {
+            'b' | 'B' => Self::Bool,
+            'f' | 'e' | 'E' | 'g' | 'G' => Self::Float,
+            'd' | 'x' | 'X' | 'o' => Self::Int,
+            _ => Self::String,
+        }
+    }
+
+    fn element(self, index: usize) -> String {
+        match self {
+            Self::String => format!("<string name=\"arg{index}\"/>"),
+            Self::Int => format!("<number name=\"arg{index}\"/>"),
+            Self::Float => {
+                format!("<number name=\"arg{index}\" type=\"f64\"/>")
+            }
+            Self::Bool => format!("<bool name=\"arg{index}\"/>"),
+        }
+    }
+}
+
+/// A string with its format arguments replaced by `{argN}`
+#[derive(Debug, Default, PartialEq)]
+struct FormatArgs {
+    text: String,
+    params: BTreeMap<usize, ParamKind>,
+    /// Conversions whose flags, width, precision or radix are lost
+    lossy: Vec<String>,
+}
+
+impl FormatArgs {
+    /// `None` when `text` has no format arguments, in which case `%%`
+    /// is kept as written, like Android does
+    fn parse(text: &str) -> Result<Option<Self>, String> {
+        let mut args = Self::default();
+        let mut next = 1;
+        let mut rest = text;
+        while let Some(pos) = rest.find('%') {
+            args.text.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+            let Some(spec) = FormatSpec::parse(rest) else {
+                // A lone `%` (`50% off`) is text
+                args.text.push('%');
+                rest = &rest[1..];
+                continue;
+            };
+            rest = &rest[spec.text.len()..];
+            args.push(&spec, &mut next)?;
+        }
+        args.text.push_str(rest);
+        if args.params.is_empty() {
+            return Ok(None);
+        }
+        if text.contains(['{', '}']) {
+            return Err(
+                "braces cannot be mixed with format arguments"
+                    .to_string(),
+            );
+        }
+        Ok(Some(args))
+    }
+
+    fn push(
+        &mut self,
+        spec: &FormatSpec<'_>,
+        next: &mut usize,
+    ) -> Result<(), String> {
+        match spec.conversion {
+            '%' => self.text.push('%'),
+            'n' => self.text.push('\n'),
+            conversion => {
+                let index = spec.index.unwrap_or_else(|| {
+                    *next += 1;
+                    *next - 1
+                });
+                let kind = ParamKind::of(conversion);
+                if self.params.insert(index, kind).is_some() {
+                    return Err(format!(
+                        "argument {index} is used more than once"
+                    ));
+                }
+                if !spec.plain || "xXoeEgGS".contains(conversion) {
+                    self.lossy.push(spec.text.to_string());
+                }
+                let _ = write!(self.text, "{{arg{index}}}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `%[index$][flags][width][.precision]conversion`
+struct FormatSpec<'a> {
+    text: &'a str,
+    index: Option<usize>,
+    conversion: char,
+    /// Whether there are no flags, width or precision
+    plain: bool,
+}
+
+impl<'a> FormatSpec<'a> {
+    /// Parses the specifier at the start of `text`, which starts with
+    /// `%`
+    fn parse(text: &'a str) -> Option<Self> {
+        let body = &text[1..];
+        let digits = body.len()
+            - body
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        let (index, body) = match body[digits..].strip_prefix('$') {
+            Some(rest) if digits > 0 => {
+                (Some(body[..digits].parse().ok()?), rest)
+            }
+            _ => (None, body),
+        };
+        // No space flag: `50% off` is text, not `% o`
+        let modifiers = body.len()
+            - body
+                .trim_start_matches(|c: char| {
+                    c.is_ascii_digit() || "-#+,(.".contains(c)
+                })
+                .len();
+        let conversion = body[modifiers..].chars().next()?;
+        if !"sSdxXofeEgGbBcn%".contains(conversion) {
+            return None;
+        }
+        let len = text.len() - body.len()
+            + modifiers
+            + conversion.len_utf8();
+        Some(Self {
+            text: &text[..len],
+            index,
+            conversion,
+            plain: modifiers == 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(xml: &str) -> String {
+        let conversion =
+            convert(&format!("<resources>{xml}</resources>"))
+                .unwrap();
+        conversion
+            .xml
+            .strip_prefix(HEADER)
+            .and_then(|xml| xml.strip_suffix("</resources>\n"))
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn android_escapes_quotes_and_whitespace() {
+        assert_eq!(android_text(r"Don\'t stop"), "Don't stop");
+        assert_eq!(android_text(r#"Say \"hi\""#), "Say \"hi\"");
+        assert_eq!(android_text(r"one\ntwo\ttab"), "one\ntwo\ttab");
+        assert_eq!(android_text(r"café \\ \@home"), "café \\ @home");
+        assert_eq!(android_text("  spread \n   out  "), "spread out");
+        assert_eq!(
+            android_text("\"  kept  \" as is"),
+            "  kept   as is"
+        );
+        assert_eq!(android_text("\"It's\""), "It's");
+    }
+
+    #[test]
+    fn format_arguments_become_parameters() {
+        let args = FormatArgs::parse("Hi %1$s, %2$d new (%3$.1f%%)")
+            .unwrap()
+            .unwrap();
+        assert_eq!(args.text, "Hi {arg1}, {arg2} new ({arg3}%)");
+        assert_eq!(
+            args.params.into_iter().collect::<Vec<_>>(),
+            [
+                (1, ParamKind::String),
+                (2, ParamKind::Int),
+                (3, ParamKind::Float)
+            ]
+        );
+        assert_eq!(args.lossy, ["%3$.1f"]);
+
+        let args = FormatArgs::parse("%s: %b").unwrap().unwrap();
+        assert_eq!(args.text, "{arg1}: {arg2}");
+        assert_eq!(FormatArgs::parse("50% off, 100%%"), Ok(None));
+        assert_eq!(
+            FormatArgs::parse("%1$s and %1$s"),
+            Err("argument 1 is used more than once".to_string())
+        );
+    }
+
+    #[test]
+    fn resources_are_converted() {
+        assert_eq!(
+            body(
+                "<string name=\"title\" translatable=\"false\">My &amp; App</string>\
+                 <string name=\"empty\"/>\
+                 <string name=\"greeting\">Hello <xliff:g id=\"n\">%1$s</xliff:g>!</string>\
+                 <integer name=\"max\">3</integer>\
+                 <item name=\"enabled\" type=\"bool\">true</item>\
+                 <color name=\"scrim\">#8000</color>"
+            ),
+            "    <string name=\"title\" translatable=\"false\">My &amp; App</string>\n\
+             \x20   <string name=\"empty\"/>\n\
+             \x20   <template name=\"greeting\">\n\
+             \x20       <string name=\"arg1\"/>\n\
+             \x20       Hello {arg1}!\n\
+             \x20   </template>\n\
+             \x20   <number name=\"max\">3</number>\n\
+             \x20   <bool name=\"enabled\">true</bool>\n\
+             \x20   <color name=\"scrim\">#88000000</color>\n"
+        );
+        assert_eq!(
+            body("<string name=\"lines\">%1$d\\nitems</string>"),
+            "    <template name=\"lines\" trim=\"none\"><number name=\"arg1\"/>{arg1}&#10;items</template>\n"
+        );
+    }
+
+    #[test]
+    fn unsupported_constructs_are_reported() {
+        let conversion = convert(
+            "<resources>\
+             <plurals name=\"songs\"><item quantity=\"one\">%d song</item></plurals>\
+             <string name=\"bold\">Be <b>bold</b></string>\
+             <color name=\"link\">@color/blue</color>\
+             </resources>",
+        )
+        .unwrap();
+        assert_eq!(conversion.resources, 1);
+        assert_eq!(
+            conversion.warnings,
+            [
+                "plurals 'songs' was not imported: plurals are not supported",
+                "string 'bold': <b> markup was dropped, its text is kept",
+                "color 'link' was not imported: references another resource (@color/blue)",
+            ]
+        );
+        assert!(conversion.xml.contains(
+            "<!-- plurals 'songs' was not imported: plurals are not supported -->"
+        ));
+    }
+
+    #[test]
+    fn locale_qualifiers() {
+        assert_eq!(values_locale(""), Some(None));
+        assert_eq!(values_locale("-fr"), Some(Some("fr".into())));
+        assert_eq!(
+            values_locale("-pt-rBR"),
+            Some(Some("pt-BR".into()))
+        );
+        assert_eq!(
+            values_locale("-b+sr+Latn"),
+            Some(Some("sr-Latn".into()))
+        );
+        assert_eq!(values_locale("-night"), None);
+        assert_eq!(values_locale("-v21"), None);
+        assert_eq!(values_locale("-fr-rCA-land"), None);
+    }
+}
@@ -0,0 +1,6 @@
+//! Converters from other resource formats to this crate's XML.
+//!
+//! They back the `r-res import-*` commands and are not part of the
+//! build: the converted files are meant to be reviewed and committed.
+
+pub mod android;
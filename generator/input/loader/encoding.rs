@@ -0,0 +1,219 @@
+//! Decoding of resource files to UTF-8.
+//!
+//! A leading byte order mark picks the encoding (UTF-8, UTF-16LE or
+//! UTF-16BE) and is dropped. Otherwise `<?xml` in UTF-16 is detected
+//! from its zero bytes, and other files use the `encoding` of their
+//! XML declaration: UTF-8 (the default), ISO-8859-1 or US-ASCII.
+//! Decoding is strict, so a byte that does not belong to the encoding
+//! fails the build at its offset instead of turning into `�`.
+
+use std::fs;
+use std::path::Path;
+
+use super::LoaderError;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// Why the bytes of a file could not be decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError {
+    /// Offset of the first undecodable byte in the file
+    pub offset: usize,
+    pub reason: String,
+}
+
+impl DecodeError {
+    fn invalid(encoding: &str, offset: usize) -> Self {
+        Self {
+            offset,
+            reason: format!("invalid {encoding}"),
+        }
+    }
+}
+
+/// Reads the resource file at `path` as UTF-8, see the module
+/// documentation
+pub fn read_xml_file(path: &Path) -> Result<String, LoaderError> {
+    let bytes = fs::read(path).map_err(|source| LoaderError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    decode_xml(&bytes).map_err(|error| LoaderError::Decode {
+        path: path.to_path_buf(),
+        offset: error.offset,
+        reason: error.reason,
+    })
+}
+
+/// `bytes` of an XML file decoded to UTF-8, without byte order mark
+pub fn decode_xml(bytes: &[u8]) -> Result<String, DecodeError> {
+    if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        return decode_utf8(rest, UTF8_BOM.len());
+    }
+    if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+        return decode_utf16(rest, 2, u16::from_le_bytes);
+    }
+    if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+        return decode_utf16(rest, 2, u16::from_be_bytes);
+    }
+    if bytes.starts_with(b"<\0?\0") {
+        return decode_utf16(bytes, 0, u16::from_le_bytes);
+    }
+    if bytes.starts_with(b"\0<\0?") {
+        return decode_utf16(bytes, 0, u16::from_be_bytes);
+    }
+    match declared_encoding(bytes).as_deref() {
+        None | Some("utf-8" | "utf8") => decode_utf8(bytes, 0),
+        Some("iso-8859-1" | "iso8859-1" | "latin1" | "latin-1") => {
+            Ok(bytes.iter().copied().map(char::from).collect())
+        }
+        Some("us-ascii" | "ascii") => {
+            match bytes.iter().position(|byte| !byte.is_ascii()) {
+                Some(at) => Err(DecodeError::invalid("ASCII", at)),
+                None => decode_utf8(bytes, 0),
+            }
+        }
+        Some(encoding @ ("utf-16" | "utf-16le" | "utf-16be")) => {
+            Err(DecodeError {
+                offset: 0,
+                reason: format!(
+                    "declared as {encoding}, but the file is not UTF-16 (no byte order mark)"
+                ),
+            })
+        }
+        Some(encoding) => Err(DecodeError {
+            offset: 0,
+            reason: format!(
+                "unsupported encoding '{encoding}' (expected UTF-8, UTF-16, ISO-8859-1 or US-ASCII)"
+            ),
+        }),
+    }
+}
+
+/// Lowercase `encoding` of the XML declaration starting `bytes`
+pub fn declared_encoding(bytes: &[u8]) -> Option<String> {
+    let rest = bytes.strip_prefix(b"<?xml")?;
+    let end = rest.windows(2).position(|pair| pair == b"?>")?;
+    let declaration = std::str::from_utf8(&rest[..end]).ok()?;
+    let (_, value) = declaration.split_once("encoding")?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote =
+        value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let value = &value[1..];
+    let end = value.find(quote)?;
+    Some(value[..end].to_ascii_lowercase())
+}
+
+/// `bytes` as UTF-8; `start` is their offset in the file
+fn decode_utf8(
+    bytes: &[u8],
+    start: usize,
+) -> Result<String, DecodeError> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text.to_owned()),
+        Err(error) => {
+            let offset = start + error.valid_up_to();
+            Err(DecodeError::invalid("UTF-8", offset))
+        }
+    }
+}
+
+/// `bytes` as UTF-16 units read by `unit`; `start` is their offset in
+/// the file
+fn decode_utf16(
+    bytes: &[u8],
+    start: usize,
+    unit: fn([u8; 2]) -> u16,
+) -> Result<String, DecodeError> {
+    let units = bytes.chunks_exact(2);
+    let odd = units.remainder();
+    let units = units.map(|pair| unit([pair[0], pair[1]]));
+    let mut text = String::with_capacity(bytes.len() / 2);
+    let mut offset = start;
+    for decoded in char::decode_utf16(units) {
+        let Ok(c) = decoded else {
+            return Err(DecodeError::invalid("UTF-16", offset));
+        };
+        text.push(c);
+        offset += 2 * c.len_utf16();
+    }
+    if odd.is_empty() {
+        Ok(text)
+    } else {
+        Err(DecodeError {
+            offset,
+            reason: "truncated UTF-16 (odd number of bytes)".into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn byte_order_marks_are_dropped() {
+        let xml = "<?xml version=\"1.0\"?><resources/>";
+        let mut bytes = UTF8_BOM.to_vec();
+        bytes.extend_from_slice(xml.as_bytes());
+        assert_eq!(decode_xml(&bytes).unwrap(), xml);
+
+        let mut bytes = UTF16LE_BOM.to_vec();
+        bytes.extend(utf16le(xml));
+        assert_eq!(decode_xml(&bytes).unwrap(), xml);
+
+        let mut bytes = UTF16BE_BOM.to_vec();
+        bytes.extend(xml.encode_utf16().flat_map(u16::to_be_bytes));
+        assert_eq!(decode_xml(&bytes).unwrap(), xml);
+        assert_eq!(decode_xml(&utf16le(xml)).unwrap(), xml);
+    }
+
+    #[test]
+    fn declared_encodings_are_honored() {
+        let latin1: &[u8] =
+            b"<?xml version='1.0' encoding='ISO-8859-1'?><a>Caf\xE9</a>";
+        assert_eq!(
+            decode_xml(latin1).unwrap(),
+            "<?xml version='1.0' encoding='ISO-8859-1'?><a>Café</a>"
+        );
+        let utf8 = "<a>Café</a>";
+        assert_eq!(decode_xml(utf8.as_bytes()).unwrap(), utf8);
+        let koi8: &[u8] =
+            b"<?xml version=\"1.0\" encoding=\"KOI8-R\"?><a/>";
+        assert_eq!(
+            decode_xml(koi8),
+            Err(DecodeError {
+                offset: 0,
+                reason: "unsupported encoding 'koi8-r' (expected UTF-8, UTF-16, ISO-8859-1 or US-ASCII)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn undecodable_bytes_are_located() {
+        let bytes = b"<?xml version=\"1.0\"?>\n<a>Caf\xE9</a>";
+        assert_eq!(
+            decode_xml(bytes),
+            Err(DecodeError::invalid("UTF-8", 28))
+        );
+        let mut bytes = UTF8_BOM.to_vec();
+        bytes.extend_from_slice(b"<a>\xFF</a>");
+        assert_eq!(
+            decode_xml(&bytes),
+            Err(DecodeError::invalid("UTF-8", 6))
+        );
+        let mut bytes = UTF16LE_BOM.to_vec();
+        bytes.extend(utf16le("<a>"));
+        bytes.extend_from_slice(&[0x00, 0xDC]);
+        assert_eq!(
+            decode_xml(&bytes),
+            Err(DecodeError::invalid("UTF-16", 8))
+        );
+    }
+}
@@ -7,6 +7,26 @@ pub enum LoaderError {
     MissingDirectory(PathBuf),
     Io { path: PathBuf, source: io::Error },
     NoXmlFilesFound { searched: PathBuf },
+    /// File of `res/raw/` above `BuildOptions::max_raw_file_size`
+    RawFileTooLarge {
+        path: PathBuf,
+        size: u64,
+        max_size: u64,
+    },
+    /// Files of `res/raw/` generating the same constant
+    RawNameCollision {
+        ident: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+    /// Text file of `res/raw/` that `include_str!` would reject
+    RawTextNotUtf8(PathBuf),
+    /// Resource file with bytes its encoding cannot decode
+    Decode {
+        path: PathBuf,
+        offset: usize,
+        reason: String,
+    },
 }
 
 impl fmt::Display for LoaderError {
@@ -33,6 +53,43 @@ impl fmt::Display for LoaderError {
                     searched.display()
                 )
             }
+            Self::RawFileTooLarge {
+                path,
+                size,
+                max_size,
+            } => write!(
+                f,
+                "'{}' is {size} bytes, above the limit of {max_size} \
+                 bytes; raise `BuildOptions::max_raw_file_size` to \
+                 embed it",
+                path.display()
+            ),
+            Self::RawNameCollision {
+                ident,
+                first,
+                second,
+            } => write!(
+                f,
+                "'{}' and '{}' both generate `r::raw::{ident}`; please \
+                 rename one of them",
+                first.display(),
+                second.display()
+            ),
+            Self::RawTextNotUtf8(path) => write!(
+                f,
+                "'{}' is not valid UTF-8 and cannot be embedded as \
+                 `&str`",
+                path.display()
+            ),
+            Self::Decode {
+                path,
+                offset,
+                reason,
+            } => write!(
+                f,
+                "cannot decode '{}': {reason} at byte {offset}",
+                path.display()
+            ),
         }
     }
 }
@@ -0,0 +1,79 @@
+use std::path::Path;
+
+/// Extracts the locale qualifier from a resource file name.
+///
+/// `values-fr.xml` → `fr`, `strings-pt-BR.xml` → `pt-BR`,
+/// `values.xml` → `None`. Android-style region qualifiers
+/// (`values-pt-rBR.xml`) are normalized to `pt-BR`.
+pub(super) fn locale_from_path(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let (prefix, qualifier) = stem.split_once('-')?;
+    if prefix.is_empty() {
+        return None;
+    }
+    parse_locale(qualifier)
+}
+
+fn parse_locale(qualifier: &str) -> Option<String> {
+    let mut parts = qualifier.split(['-', '_']);
+    let language = parts.next()?;
+    let is_language = (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase());
+    if !is_language {
+        return None;
+    }
+
+    let Some(region) = parts.next() else {
+        return Some(language.to_string());
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    let region = region
+        .strip_prefix('r')
+        .filter(|r| r.len() == 2)
+        .unwrap_or(region);
+    let is_region = (2..=4).contains(&region.len())
+        && region.chars().all(|c| c.is_ascii_alphanumeric());
+    is_region.then(|| format!("{language}-{region}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(name: &str) -> Option<String> {
+        locale_from_path(Path::new(name))
+    }
+
+    #[test]
+    fn detects_language_suffix() {
+        assert_eq!(locale("values-fr.xml").as_deref(), Some("fr"));
+        assert_eq!(locale("strings-de.xml").as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn detects_region_suffix() {
+        assert_eq!(
+            locale("values-pt-BR.xml").as_deref(),
+            Some("pt-BR")
+        );
+        assert_eq!(
+            locale("values-pt_BR.xml").as_deref(),
+            Some("pt-BR")
+        );
+        assert_eq!(
+            locale("values-pt-rBR.xml").as_deref(),
+            Some("pt-BR")
+        );
+    }
+
+    #[test]
+    fn ignores_non_locale_names() {
+        assert_eq!(locale("values.xml"), None);
+        assert_eq!(locale("values_api_errors.xml"), None);
+        assert_eq!(locale("values-errors.xml"), None);
+        assert_eq!(locale("values-FR.xml"), None);
+        assert_eq!(locale("-fr.xml"), None);
+    }
+}
@@ -1,25 +1,29 @@
+mod encoding;
 mod error;
-mod profile;
+mod locale;
 mod raw_file;
 mod scan;
 
+#[allow(unused_imports)] // Used by `r-res fmt`
+pub use encoding::declared_encoding;
+pub use encoding::read_xml_file;
 pub use error::LoaderError;
 pub use raw_file::RawResourceFile;
 
-use std::fs;
 use std::path::Path;
 
 use super::BuildPlan;
 use scan::collect_xml_files;
 
-/// Loads every XML file defined in the build plan, applying profile preprocessing.
+/// Loads every XML file defined in the build plan.
+///
+/// Profile filtering happens after parsing, when the resource graph is built.
 pub fn load_resources(
     plan: &BuildPlan,
 ) -> Result<Vec<RawResourceFile>, LoaderError> {
     let mut files = load_directory(
         &plan.resources_dir,
         false,
-        &plan.profile,
         true, /* strict */
     )?;
 
@@ -28,7 +32,6 @@ pub fn load_resources(
             let mut test_files = load_directory(
                 tests_dir,
                 true,
-                &plan.profile,
                 false, /* not strict */
             )?;
             files.append(&mut test_files);
@@ -41,7 +44,6 @@ pub fn load_resources(
 fn load_directory(
     dir: &Path,
     is_test: bool,
-    profile: &str,
     strict: bool,
 ) -> Result<Vec<RawResourceFile>, LoaderError> {
     if !dir.exists() {
@@ -65,14 +67,11 @@ fn load_directory(
 
     let mut loaded = Vec::with_capacity(xml_paths.len());
     for path in xml_paths {
-        let raw = fs::read_to_string(&path).map_err(|source| {
-            LoaderError::Io {
-                path: path.clone(),
-                source,
-            }
-        })?;
-        let filtered = profile::preprocess_xml(&raw, profile);
-        loaded.push(RawResourceFile::new(path, filtered, is_test));
+        let raw = read_xml_file(&path)?;
+        let locale = locale::locale_from_path(&path);
+        loaded.push(
+            RawResourceFile::new(path, raw, is_test).with_locale(locale),
+        );
     }
 
     Ok(loaded)
@@ -81,6 +80,7 @@ fn load_directory(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::tempdir;
 
     fn write_file(path: &Path, contents: &str) {
@@ -101,7 +101,7 @@ mod tests {
     }
 
     #[test]
-    fn load_files_and_apply_profile_filtering() {
+    fn load_files_keeps_contents_untouched() {
         let tmp = tempdir().unwrap();
         let res_dir = tmp.path().join("res");
         fs::create_dir_all(&res_dir).unwrap();
@@ -138,8 +138,8 @@ mod tests {
             .expect("prod file exists");
         assert!(prod_file.contents.contains("visible"));
         assert!(
-            !prod_file.contents.contains("hidden"),
-            "debug-only resource should be filtered out"
+            prod_file.contents.contains("hidden"),
+            "profile filtering happens after parsing"
         );
 
         let test_file = files
@@ -165,4 +165,23 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert!(!files[0].is_test);
     }
+
+    #[test]
+    fn undecodable_files_fail_at_their_offset() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        fs::create_dir_all(&res_dir).unwrap();
+        fs::write(res_dir.join("values.xml"), b"<a>Caf\xE9</a>")
+            .unwrap();
+
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let err = load_resources(&plan).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "cannot decode '{}': invalid UTF-8 at byte 6",
+                res_dir.join("values.xml").display()
+            )
+        );
+    }
 }
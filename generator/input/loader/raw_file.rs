@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
-/// Raw resource file loaded from disk and preprocessed for the selected profile.
+/// Raw resource file loaded from disk, contents untouched.
 #[derive(Debug, Clone)]
 pub struct RawResourceFile {
     pub path: PathBuf,
     pub contents: String,
     pub is_test: bool,
+    /// Locale qualifier from the file name (`values-fr.xml` → `fr`)
+    pub locale: Option<String>,
 }
 
 impl RawResourceFile {
@@ -18,6 +20,12 @@ impl RawResourceFile {
             path,
             contents,
             is_test,
+            locale: None,
         }
     }
+
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
+    }
 }
@@ -1,22 +1,28 @@
-//! Input layer: file discovery and preprocessing.
+//! Input layer: file discovery.
 //!
 //! This module handles:
 //! - Scanning directories for XML resource files
-//! - Profile-based preprocessing (filtering resources by build profile)
 //! - Test resource discovery (from `res/tests/` directory)
+//! - Files of `res/raw/`, embedded without parsing
+//! - The workspace root, for resources shared by workspace members
 //!
 //! The output is a list of `RawResourceFile` objects ready for parsing.
 
 pub mod loader;
+pub mod raw_assets;
+pub mod workspace;
 
 pub use loader::{load_resources, LoaderError, RawResourceFile};
+#[allow(unused_imports)] // Used by the hot-reload runtime
+pub use loader::read_xml_file;
+pub use raw_assets::RawAsset;
 
 pub struct BuildPlan {
     /// Root directory that contains runtime resources (default: `res/`).
     pub resources_dir: std::path::PathBuf,
     /// Optional directory for test-only resources.
     pub tests_resources_dir: Option<std::path::PathBuf>,
-    /// Cargo profile (debug/release) captured for preprocessing.
+    /// Resource profile (e.g. debug/release/staging) used to filter resources.
     pub profile: String,
 }
 
@@ -0,0 +1,224 @@
+//! Files of `res/raw/`, embedded as-is in `r::raw`.
+//!
+//! Every file directly under `res/raw/` becomes a `&[u8]` constant
+//! named after the file (`splash.png` → `SPLASH_PNG`); text files (see
+//! [`TEXT_EXTENSIONS`]) also get a `_STR: &str`. Hidden files
+//! (`.gitkeep`) and subdirectories are skipped.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::LoaderError;
+use crate::generator::utils::const_identifier;
+
+/// Directory of the raw files, inside the resources directory
+pub const RAW_DIR: &str = "raw";
+
+/// Largest embedded file by default (10 MiB), see
+/// `BuildOptions::max_raw_file_size`
+pub const DEFAULT_MAX_RAW_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Extensions of the files also embedded as `&str`
+pub const TEXT_EXTENSIONS: [&str; 18] = [
+    "txt", "md", "json", "toml", "yaml", "yml", "csv", "tsv", "html",
+    "htm", "css", "js", "svg", "xml", "sql", "glsl", "wgsl", "ron",
+];
+
+/// File of `res/raw/` to embed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawAsset {
+    /// Absolute path, as written in `include_bytes!`
+    pub path: PathBuf,
+    /// Name of the `&[u8]` constant (`SPLASH_PNG`)
+    pub ident: String,
+    /// Also embedded as `<ident>_STR: &str`
+    pub text: bool,
+    pub size: u64,
+}
+
+impl RawAsset {
+    /// Name of the `&str` constant of a text file
+    pub fn str_ident(&self) -> Option<String> {
+        self.text.then(|| format!("{}_STR", self.ident))
+    }
+}
+
+/// Embeddable files of `res/raw/` (none when the directory does not
+/// exist), sorted by name
+pub fn raw_files(
+    resources_dir: &Path,
+) -> Result<Vec<PathBuf>, LoaderError> {
+    let dir = resources_dir.join(RAW_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let io_error = |source| LoaderError::Io {
+        path: dir.clone(),
+        source,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let hidden = path.file_name().is_some_and(|name| {
+            name.to_string_lossy().starts_with('.')
+        });
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Loads the files of `res/raw/`, rejecting files above `max_size`
+/// bytes, text files that are not UTF-8 and names colliding once
+/// sanitized
+pub fn load_raw_assets(
+    resources_dir: &Path,
+    max_size: u64,
+) -> Result<Vec<RawAsset>, LoaderError> {
+    let mut assets = Vec::new();
+    // Constant name → file defining it
+    let mut seen: HashMap<String, PathBuf> = HashMap::new();
+    for path in raw_files(resources_dir)? {
+        let asset = raw_asset(&path, max_size)?;
+        let idents = std::iter::once(asset.ident.clone())
+            .chain(asset.str_ident());
+        for ident in idents {
+            if let Some(first) =
+                seen.insert(ident.clone(), path.clone())
+            {
+                return Err(LoaderError::RawNameCollision {
+                    ident,
+                    first,
+                    second: path,
+                });
+            }
+        }
+        assets.push(asset);
+    }
+    Ok(assets)
+}
+
+fn raw_asset(
+    path: &Path,
+    max_size: u64,
+) -> Result<RawAsset, LoaderError> {
+    let io_error = |source| LoaderError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let size = fs::metadata(path).map_err(io_error)?.len();
+    if size > max_size {
+        return Err(LoaderError::RawFileTooLarge {
+            path: path.to_path_buf(),
+            size,
+            max_size,
+        });
+    }
+    let text = path.extension().is_some_and(|ext| {
+        TEXT_EXTENSIONS
+            .contains(&ext.to_string_lossy().to_lowercase().as_str())
+    });
+    if text
+        && std::str::from_utf8(&fs::read(path).map_err(io_error)?)
+            .is_err()
+    {
+        return Err(LoaderError::RawTextNotUtf8(path.to_path_buf()));
+    }
+    let file_name =
+        path.file_name().unwrap_or_default().to_string_lossy();
+    Ok(RawAsset {
+        path: fs::canonicalize(path).map_err(io_error)?,
+        ident: const_identifier(&file_name),
+        text,
+        size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_raw(res_dir: &Path, name: &str, contents: &[u8]) {
+        let dir = res_dir.join(RAW_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(name), contents).unwrap();
+    }
+
+    #[test]
+    fn files_become_sorted_assets() {
+        let tmp = tempdir().unwrap();
+        write_raw(
+            tmp.path(),
+            "splash.png",
+            &[0x89, b'P', b'N', b'G'],
+        );
+        write_raw(tmp.path(), "LICENSE.txt", b"MIT");
+        write_raw(tmp.path(), ".gitkeep", b"");
+        fs::create_dir_all(tmp.path().join("raw/nested")).unwrap();
+
+        let assets =
+            load_raw_assets(tmp.path(), DEFAULT_MAX_RAW_FILE_SIZE)
+                .unwrap();
+        let idents: Vec<_> = assets
+            .iter()
+            .map(|a| (a.ident.as_str(), a.str_ident(), a.size))
+            .collect();
+        assert_eq!(
+            idents,
+            [
+                (
+                    "LICENSE_TXT",
+                    Some("LICENSE_TXT_STR".to_string()),
+                    3
+                ),
+                ("SPLASH_PNG", None, 4),
+            ]
+        );
+        assert!(assets.iter().all(|a| a.path.is_absolute()));
+    }
+
+    #[test]
+    fn missing_raw_dir_has_no_assets() {
+        let tmp = tempdir().unwrap();
+        assert_eq!(load_raw_assets(tmp.path(), 0).unwrap(), []);
+    }
+
+    #[test]
+    fn oversized_files_are_rejected() {
+        let tmp = tempdir().unwrap();
+        write_raw(tmp.path(), "intro.mp4", &[0; 16]);
+
+        let err = load_raw_assets(tmp.path(), 8).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("intro.mp4"), "{message}");
+        assert!(message.contains("16 bytes"), "{message}");
+        assert!(message.contains("max_raw_file_size"), "{message}");
+    }
+
+    #[test]
+    fn sanitized_name_collisions_are_rejected() {
+        let tmp = tempdir().unwrap();
+        write_raw(tmp.path(), "app-icon.png", b"a");
+        write_raw(tmp.path(), "app_icon.png", b"b");
+
+        let err = load_raw_assets(tmp.path(), 8).unwrap_err();
+        assert!(matches!(
+            &err,
+            LoaderError::RawNameCollision { ident, .. } if ident == "APP_ICON_PNG"
+        ));
+        assert!(err.to_string().contains("app-icon.png"));
+    }
+
+    #[test]
+    fn text_files_must_be_utf8() {
+        let tmp = tempdir().unwrap();
+        write_raw(tmp.path(), "notes.txt", &[0xFF, 0xFE]);
+
+        let err = load_raw_assets(tmp.path(), 8).unwrap_err();
+        assert!(matches!(err, LoaderError::RawTextNotUtf8(_)));
+    }
+}
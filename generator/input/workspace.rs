@@ -0,0 +1,110 @@
+//! Workspace root discovery, for resources shared by the members of a
+//! cargo workspace.
+//!
+//! The root is `CARGO_WORKSPACE_DIR` when set (cargo does not set it,
+//! but `[env]` in `.cargo/config.toml` can), otherwise the topmost
+//! directory above the crate whose `Cargo.toml` has a `[workspace]`
+//! table. A standalone crate is its own root.
+
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the workspace root
+pub const WORKSPACE_DIR_ENV_VAR: &str = "CARGO_WORKSPACE_DIR";
+
+/// Workspace root of the crate in `manifest_dir`, `env_override`
+/// first
+pub fn workspace_root(
+    manifest_dir: &Path,
+    env_override: Option<&Path>,
+) -> PathBuf {
+    if let Some(dir) =
+        env_override.filter(|d| !d.as_os_str().is_empty())
+    {
+        return manifest_dir.join(dir);
+    }
+    manifest_dir
+        .ancestors()
+        .filter(|dir| is_workspace_manifest(&dir.join("Cargo.toml")))
+        .last()
+        .unwrap_or(manifest_dir)
+        .to_path_buf()
+}
+
+/// Whether the manifest at `path` declares a `[workspace]` table
+/// (`[workspace.dependencies]` and the like count too)
+fn is_workspace_manifest(path: &Path) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|manifest| {
+        manifest.lines().any(|line| {
+            let line = line.trim_start();
+            line.starts_with("[workspace]")
+                || line.starts_with("[workspace.")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn manifest(dir: &Path, contents: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), contents).unwrap();
+    }
+
+    #[test]
+    fn members_resolve_to_the_workspace_root() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("app");
+        manifest(&root, "[workspace]\nmembers = [\"crates/*\"]\n");
+        let member = root.join("crates/ui");
+        manifest(&member, "[package]\nname = \"ui\"\n");
+
+        assert_eq!(workspace_root(&member, None), root);
+        assert_eq!(workspace_root(&root, None), root);
+    }
+
+    #[test]
+    fn the_topmost_workspace_wins() {
+        let tmp = tempdir().unwrap();
+        let root = tmp.path().join("app");
+        manifest(&root, "[workspace.dependencies]\n");
+        let nested = root.join("tools");
+        manifest(&nested, "[workspace]\n");
+        let member = nested.join("lint");
+        manifest(&member, "[package]\nname = \"lint\"\n");
+
+        assert_eq!(workspace_root(&member, None), root);
+    }
+
+    #[test]
+    fn standalone_crates_are_their_own_root() {
+        let tmp = tempdir().unwrap();
+        let krate = tmp.path().join("app");
+        manifest(&krate, "[package]\nname = \"app\"\n");
+
+        assert_eq!(workspace_root(&krate, None), krate);
+    }
+
+    #[test]
+    fn the_environment_overrides_the_lookup() {
+        let tmp = tempdir().unwrap();
+        let member = tmp.path().join("app/crates/ui");
+        manifest(&member, "[package]\nname = \"ui\"\n");
+
+        assert_eq!(
+            workspace_root(&member, Some(Path::new("/shared"))),
+            Path::new("/shared")
+        );
+        // Relative to the crate
+        assert_eq!(
+            workspace_root(&member, Some(Path::new("../.."))),
+            member.join("../..")
+        );
+        assert_eq!(
+            workspace_root(&member, Some(Path::new(""))),
+            member
+        );
+    }
+}
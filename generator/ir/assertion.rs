@@ -0,0 +1,477 @@
+//! `assert="..."` attributes, checked at build time and again by the
+//! compiler.
+//!
+//! ```xml
+//! <number name="cache_size" type="u32" assert="gt:0">256</number>
+//! <string name="api_key_prefix" assert="len_le:8,non_empty">sk_</string>
+//! ```
+//!
+//! Numbers take `gt`, `ge`, `lt`, `le` and `ne` followed by a number of
+//! their type, strings `len_le` and `len_ge` followed by a length in
+//! bytes (like `str::len`) and `non_empty`. The checks run once
+//! references and `<computed>` numbers are resolved, and each one is
+//! also emitted next to its constant:
+//!
+//! ```rust,ignore
+//! const _: () = assert!(CACHE_SIZE > 0, "cache_size: assert `gt:0` failed");
+//! ```
+//!
+//! so a hand-edited generated file still fails to compile when a value
+//! breaks its invariant.
+
+use std::cmp::Ordering;
+
+use crate::generator::parsing::{ParsedResource, ResourceKind};
+use crate::generator::utils::{const_identifier, escape_str};
+
+use super::model::{
+    InvalidResource, NumberValue, ResourceGraph, ResourceKey,
+    ResourceNode, ResourceValue,
+};
+use super::types::{compare_numbers, display_literal, number_like};
+
+/// One check of an `assert` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assertion {
+    /// Comparison with a number, as written
+    Compare(Comparison, String),
+    /// `len_le`: at most this many bytes
+    MaxLen(usize),
+    /// `len_ge`: at least this many bytes
+    MinLen(usize),
+    NonEmpty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Ne,
+}
+
+const COMPARISONS: &[(&str, Comparison)] = &[
+    ("gt", Comparison::Gt),
+    ("ge", Comparison::Ge),
+    ("lt", Comparison::Lt),
+    ("le", Comparison::Le),
+    ("ne", Comparison::Ne),
+];
+
+impl Comparison {
+    fn name(self) -> &'static str {
+        COMPARISONS
+            .iter()
+            .find(|(_, comparison)| *comparison == self)
+            .map_or("", |(name, _)| name)
+    }
+
+    fn operator(self) -> &'static str {
+        match self {
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Ne => "!=",
+        }
+    }
+
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Gt => ordering.is_gt(),
+            Self::Ge => ordering.is_ge(),
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Ne => ordering.is_ne(),
+        }
+    }
+}
+
+impl Assertion {
+    /// Checks of the `assert` attribute `list` (`gt:0,le:64`)
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        list.split(',')
+            .map(|spec| Self::parse(spec.trim()))
+            .collect()
+    }
+
+    fn parse(spec: &str) -> Result<Self, String> {
+        let (name, operand) = match spec.split_once(':') {
+            Some((name, operand)) => (name, Some(operand.trim())),
+            None => (spec, None),
+        };
+        let length = |operand: Option<&str>| {
+            operand.and_then(|o| o.parse::<usize>().ok()).ok_or_else(
+                || {
+                    format!(
+                        "`{spec}` needs a length, as in `{name}:8`"
+                    )
+                },
+            )
+        };
+        if let Some((_, comparison)) =
+            COMPARISONS.iter().find(|(known, _)| *known == name)
+        {
+            return match operand {
+                Some(number) if !number.is_empty() => {
+                    Ok(Self::Compare(*comparison, number.to_string()))
+                }
+                _ => Err(format!(
+                    "`{spec}` needs a number, as in `{name}:0`"
+                )),
+            };
+        }
+        match name {
+            "len_le" => length(operand).map(Self::MaxLen),
+            "len_ge" => length(operand).map(Self::MinLen),
+            "non_empty" if operand.is_none() => Ok(Self::NonEmpty),
+            "non_empty" => Err(format!("`{spec}` takes no value")),
+            _ => Err(format!(
+                "unknown assertion `{spec}` (expected gt, ge, lt, le, ne, \
+                 len_le, len_ge or non_empty)"
+            )),
+        }
+    }
+
+    /// The check as written in the attribute
+    pub fn spec(&self) -> String {
+        match self {
+            Self::Compare(comparison, number) => {
+                format!("{}:{number}", comparison.name())
+            }
+            Self::MaxLen(len) => format!("len_le:{len}"),
+            Self::MinLen(len) => format!("len_ge:{len}"),
+            Self::NonEmpty => "non_empty".to_string(),
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Self::Compare(..))
+    }
+
+    /// Why `value` fails the check, `None` when it holds
+    fn failure(&self, value: &ResourceValue) -> Option<String> {
+        let spec = self.spec();
+        match (self, value) {
+            (Self::Compare(comparison, text), ResourceValue::Number(n)) => {
+                let operand = match operand(n, text) {
+                    Ok(operand) => operand,
+                    Err(e) => return Some(format!("`{spec}`: {e}")),
+                };
+                let holds = compare_numbers(n, &operand)
+                    .is_some_and(|ordering| comparison.holds(ordering));
+                (!holds).then(|| {
+                    format!("{} fails `{spec}`", display_literal(n))
+                })
+            }
+            (Self::MaxLen(max), ResourceValue::String(text)) => {
+                (text.len() > *max).then(|| {
+                    format!(
+                        "{text:?} is {} bytes long and fails `{spec}`",
+                        text.len()
+                    )
+                })
+            }
+            (Self::MinLen(min), ResourceValue::String(text)) => {
+                (text.len() < *min).then(|| {
+                    format!(
+                        "{text:?} is {} bytes long and fails `{spec}`",
+                        text.len()
+                    )
+                })
+            }
+            (Self::NonEmpty, ResourceValue::String(text)) => text
+                .is_empty()
+                .then(|| format!("the empty string fails `{spec}`")),
+            _ => Some(format!("`{spec}` does not apply to this value")),
+        }
+    }
+
+    /// Condition of the `assert!` on the constant `name` of `value`,
+    /// written the way clippy wants it; `None` for the checks that
+    /// always hold, which clippy rejects
+    fn condition(
+        &self,
+        name: &str,
+        value: &ResourceValue,
+    ) -> Option<String> {
+        Some(match (self, value) {
+            (
+                Self::Compare(comparison, text),
+                ResourceValue::Number(n),
+            ) => {
+                let operand =
+                    display_literal(&operand(n, text).ok()?);
+                if *comparison == Comparison::Ge
+                    && operand == "0"
+                    && is_unsigned(n)
+                {
+                    return None;
+                }
+                format!("{name} {} {operand}", comparison.operator())
+            }
+            (Self::MaxLen(0), _) => format!("{name}.is_empty()"),
+            (Self::MaxLen(max), _) => {
+                format!("{name}.len() <= {max}")
+            }
+            (Self::MinLen(0), _) => return None,
+            (Self::MinLen(1), _) => format!("!{name}.is_empty()"),
+            (Self::MinLen(min), _) => {
+                format!("{name}.len() >= {min}")
+            }
+            (Self::NonEmpty, _) => format!("!{name}.is_empty()"),
+            _ => return None,
+        })
+    }
+}
+
+fn is_unsigned(value: &NumberValue) -> bool {
+    matches!(value, NumberValue::Typed { ty, .. } if ty.as_str().starts_with('u'))
+}
+
+/// Operand of a comparison with `value`, in its type
+fn operand(
+    value: &NumberValue,
+    text: &str,
+) -> Result<NumberValue, String> {
+    if matches!(value, NumberValue::BigDecimal(_)) {
+        return Err(
+            "decimals have no constant to assert on at compile time"
+                .to_string(),
+        );
+    }
+    number_like(value, text)
+}
+
+/// Checks of the `assert` attribute of `resource`, with a reason when
+/// they are malformed or do not apply to its type
+pub fn parse_assertions(
+    resource: &ParsedResource,
+) -> Result<Vec<Assertion>, String> {
+    let Some(list) = &resource.meta.assert else {
+        return Ok(Vec::new());
+    };
+    let assertions = Assertion::parse_list(list)?;
+    let numeric = match resource.kind {
+        ResourceKind::Number
+        | ResourceKind::ByteSize
+        | ResourceKind::Computed => true,
+        ResourceKind::String | ResourceKind::Concat => false,
+        _ => {
+            return Err(
+                "`assert` only applies to numbers and strings"
+                    .to_string(),
+            )
+        }
+    };
+    match assertions.iter().find(|a| a.is_numeric() != numeric) {
+        Some(wrong) if numeric => {
+            Err(format!("`{}` only applies to strings", wrong.spec()))
+        }
+        Some(wrong) => {
+            Err(format!("`{}` only applies to numbers", wrong.spec()))
+        }
+        None => Ok(assertions),
+    }
+}
+
+/// `const _: () = assert!(...);` items checking the constant of `key`
+pub fn assertion_items(
+    pad: &str,
+    key: &ResourceKey,
+    node: &ResourceNode,
+) -> String {
+    let name = const_identifier(&key.name);
+    node.meta.asserts
+        .iter()
+        .filter_map(|assertion| {
+            let condition = assertion.condition(&name, &node.value)?;
+            let message = escape_str(&format!(
+                "{}: assert `{}` failed",
+                key.full_name(),
+                assertion.spec()
+            ));
+            Some(format!(
+                "{pad}const _: () = assert!({condition}, \"{message}\");\n"
+            ))
+        })
+        .collect()
+}
+
+/// Records the resources whose value fails one of their assertions
+pub fn check_assertions(graph: &mut ResourceGraph) {
+    check_graph(graph);
+    for translation in graph.translations_mut() {
+        check_graph(translation);
+    }
+    if graph.tests().is_some() {
+        check_graph(graph.tests_mut());
+    }
+}
+
+fn check_graph(graph: &mut ResourceGraph) {
+    let mut invalid = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        for node in nodes {
+            let failures =
+                node.meta.asserts.iter().filter_map(|assertion| {
+                    assertion.failure(&node.value)
+                });
+            for message in failures {
+                invalid.push(InvalidResource {
+                    key: key.clone(),
+                    file: node.origin.file.to_path_buf(),
+                    line: node.origin.line.map(|line| line as usize),
+                    message,
+                    reference: None,
+                });
+            }
+        }
+    }
+    invalid.into_iter().for_each(|i| graph.record_invalid(i));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::model::{
+        NodeMeta, NumberType, ResourceOrigin,
+    };
+    use crate::generator::ir::ResourceKind as NodeKind;
+    use std::path::PathBuf;
+
+    fn node(value: ResourceValue, asserts: &str) -> ResourceNode {
+        ResourceNode {
+            meta: NodeMeta {
+                asserts: Assertion::parse_list(asserts).unwrap(),
+                ..NodeMeta::default()
+            },
+            ..ResourceNode::new(
+                NodeKind::Number,
+                value,
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            )
+        }
+    }
+
+    fn u32_node(literal: &str, asserts: &str) -> ResourceNode {
+        let value = ResourceValue::Number(NumberValue::Typed {
+            literal: literal.into(),
+            ty: NumberType::U32,
+        });
+        node(value, asserts)
+    }
+
+    fn failures(node: &ResourceNode) -> Vec<String> {
+        node.meta.asserts
+            .iter()
+            .filter_map(|a| a.failure(&node.value))
+            .collect()
+    }
+
+    #[test]
+    fn lists_are_parsed() {
+        assert_eq!(
+            Assertion::parse_list("gt:0, len_le:8,non_empty"),
+            Ok(vec![
+                Assertion::Compare(Comparison::Gt, "0".into()),
+                Assertion::MaxLen(8),
+                Assertion::NonEmpty,
+            ])
+        );
+        for (list, error) in [
+            ("gt", "`gt` needs a number, as in `gt:0`"),
+            (
+                "len_ge:x",
+                "`len_ge:x` needs a length, as in `len_ge:8`",
+            ),
+            ("non_empty:1", "`non_empty:1` takes no value"),
+            ("eq:3", "unknown assertion `eq:3`"),
+        ] {
+            let message = Assertion::parse_list(list).unwrap_err();
+            assert!(message.starts_with(error), "{message}");
+        }
+    }
+
+    #[test]
+    fn numbers_are_compared_in_their_type() {
+        assert!(
+            failures(&u32_node("256", "gt:0,le:256,ne:3")).is_empty()
+        );
+        assert_eq!(
+            failures(&u32_node("0", "gt:0,lt:10")),
+            ["0 fails `gt:0`"]
+        );
+        assert_eq!(
+            failures(&u32_node("1", "ge:-1")),
+            ["`ge:-1`: '-1' does not fit in u32"]
+        );
+        let float = node(
+            ResourceValue::Number(NumberValue::Float(0.5)),
+            "lt:1",
+        );
+        assert!(failures(&float).is_empty());
+    }
+
+    #[test]
+    fn strings_are_measured_in_bytes() {
+        let text = |s: &str, asserts| {
+            node(ResourceValue::String(s.into()), asserts)
+        };
+        assert!(
+            failures(&text("sk_", "len_le:8,non_empty")).is_empty()
+        );
+        assert_eq!(
+            failures(&text("été", "len_le:4,len_ge:2")),
+            ["\"été\" is 5 bytes long and fails `len_le:4`"]
+        );
+        assert_eq!(
+            failures(&text("", "non_empty")),
+            ["the empty string fails `non_empty`"]
+        );
+    }
+
+    #[test]
+    fn items_assert_on_the_constant() {
+        let key = ResourceKey::from_path("net/cache_size");
+        assert_eq!(
+            assertion_items("    ", &key, &u32_node("256", "gt:0,ne:7")),
+            "    const _: () = assert!(CACHE_SIZE > 0, \"net/cache_size: assert `gt:0` failed\");\n    \
+             const _: () = assert!(CACHE_SIZE != 7, \"net/cache_size: assert `ne:7` failed\");\n"
+        );
+        let float = node(
+            ResourceValue::Number(NumberValue::Float(0.5)),
+            "gt:0",
+        );
+        assert!(assertion_items("", &key, &float)
+            .contains("assert!(CACHE_SIZE > 0.0,"));
+        let text = node(
+            ResourceValue::String("sk_".into()),
+            "len_ge:2,non_empty",
+        );
+        assert!(assertion_items("", &key, &text).contains(
+            "assert!(CACHE_SIZE.len() >= 2, \"net/cache_size: assert `len_ge:2` failed\");\nconst _: () = assert!(!CACHE_SIZE.is_empty(),"
+        ));
+    }
+    #[test]
+    fn trivial_checks_are_written_for_clippy() {
+        let key = ResourceKey::from_path("label");
+        let text = node(
+            ResourceValue::String("sk_".into()),
+            "len_ge:0,len_ge:1,len_le:0",
+        );
+        assert_eq!(
+            assertion_items("", &key, &text),
+            "const _: () = assert!(!LABEL.is_empty(), \"label: assert `len_ge:1` failed\");\n\
+             const _: () = assert!(LABEL.is_empty(), \"label: assert `len_le:0` failed\");\n"
+        );
+        assert_eq!(
+            assertion_items("", &key, &u32_node("1", "ge:0")),
+            ""
+        );
+    }
+}
@@ -1,12 +1,57 @@
-use crate::generator::parsing::ParsedResourceFile;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
 
-use super::model::{ResourceGraph, ResourceKey};
+use crate::generator::parsing::{
+    ConcatPart, ParsedResource, ParsedResourceFile, ScalarValue,
+};
+
+use super::assertion::parse_assertions;
+use super::computed::ComputedNumber;
+use super::intern::Interner;
+use super::model::{
+    InvalidResource, NodeMeta, NumberValue, ProfileVariant,
+    ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
+    ResourceOrigin, ResourceValue,
+};
+use super::profile;
 use super::types::TypeRegistry;
 
 #[derive(Default)]
 pub struct ResourceGraphBuilder {
     graph: ResourceGraph,
     registry: TypeRegistry,
+    /// Selected profile; `None` keeps every resource regardless of profile
+    profile: Option<String>,
+    /// Nodes awaiting profile fallback resolution
+    pending: Vec<PendingNode>,
+    /// Definitions read so far, the index of the next one
+    next_index: usize,
+    /// Names and namespace paths of the keys built so far
+    interner: Interner,
+}
+
+/// Graph (test or not, locale) and key a node is inserted under
+type Slot = (bool, Option<String>, ResourceKey);
+
+/// A built node and whether it targets a specific profile
+struct PendingNode {
+    is_test: bool,
+    locale: Option<String>,
+    key: ResourceKey,
+    node: ResourceNode,
+    is_specific: bool,
+    /// Value filled in once the graph is complete, `node` holding a
+    /// placeholder until then
+    deferred: Option<Deferred>,
+}
+
+/// Value of a node that depends on the merged graph
+enum Deferred {
+    /// Expression of a `<computed>`
+    Computed(ComputedNumber),
+    /// Parts of a `<concat>`
+    Concat(Vec<ConcatPart>),
 }
 
 impl ResourceGraphBuilder {
@@ -17,28 +62,112 @@ impl ResourceGraphBuilder {
     #[allow(dead_code)] // Reserved for future use
     pub fn with_registry(registry: TypeRegistry) -> Self {
         Self {
-            graph: ResourceGraph::default(),
             registry,
+            ..Self::default()
         }
     }
 
+    #[allow(dead_code)] // Used in tests
     pub fn from_parsed_files(
         files: &[ParsedResourceFile],
+    ) -> ResourceGraph {
+        Self::new().build(files)
+    }
+
+    /// Builds the graph keeping only resources active for `profile`.
+    ///
+    /// A profile-specific definition wins over `profile="default"` (or
+    /// unprofiled) definitions of the same key.
+    pub fn from_parsed_files_for_profile(
+        files: &[ParsedResourceFile],
+        profile: &str,
     ) -> ResourceGraph {
         let mut builder = Self::new();
+        builder.profile = Some(profile.to_string());
+        builder.build(files)
+    }
+
+    fn build(mut self, files: &[ParsedResourceFile]) -> ResourceGraph {
         for file in files {
-            builder.ingest_file(file);
+            self.ingest_file(file);
         }
-        builder.graph
+        self.finish()
     }
 
     fn ingest_file(&mut self, file: &ParsedResourceFile) {
+        for (namespace, doc) in &file.namespace_docs {
+            self.target_graph(file.is_test, file.locale.as_deref())
+                .set_namespace_doc(namespace.clone(), doc.clone());
+        }
+        // Config structs group the default resources only
+        if !file.is_test && file.locale.is_none() {
+            for namespace in &file.config_namespaces {
+                self.graph.mark_config_namespace(namespace.clone());
+            }
+        }
+        for &line in &file.unused_docs {
+            self.graph.record_unused_doc(file.path.clone(), line);
+        }
+        // Shared by the origins and variants of every resource
+        let path: Arc<Path> = Arc::from(file.path.as_path());
+        let target =
+            self.target_graph(file.is_test, file.locale.as_deref());
+        for (namespace, line) in &file.namespace_lines {
+            let namespace = namespace.clone();
+            target.record_namespace_origin(namespace, &path, *line);
+        }
         for resource in &file.resources {
-            let key = ResourceKey::from_path(&resource.name);
-            let origin = super::ResourceOrigin::new(
-                file.path.clone(),
+            let index = self.next_index;
+            self.next_index += 1;
+            let specs = &resource.meta.profiles;
+            let key = self.interner.key(&resource.name);
+            self.target_graph(file.is_test, file.locale.as_deref()).record_profile_variant(
+                key.clone(),
+                ProfileVariant {
+                    specs: specs.clone(),
+                    file: Arc::clone(&path),
+                    line: resource.meta.line,
+                },
+            );
+            if !self.is_active(specs) {
+                continue;
+            }
+            if !resource.meta.translatable {
+                self.target_graph(file.is_test, file.locale.as_deref())
+                    .mark_untranslatable(key.clone());
+            }
+            if resource.meta.keep {
+                self.target_graph(file.is_test, file.locale.as_deref())
+                    .mark_kept(key.clone());
+            }
+            if resource.meta.overridable {
+                self.target_graph(file.is_test, file.locale.as_deref())
+                    .mark_overridable(key.clone());
+            }
+
+            let mut origin = super::ResourceOrigin::new(
+                Arc::clone(&path),
                 file.is_test,
             );
+            origin.profile = specs.last().cloned();
+            origin.line =
+                resource.meta.line.and_then(|l| u32::try_from(l).ok());
+            origin.index = index;
+
+            let asserts = match parse_assertions(resource) {
+                Ok(asserts) => asserts,
+                Err(message) => {
+                    self.target_graph(file.is_test, file.locale.as_deref())
+                        .record_invalid(InvalidResource {
+                            key,
+                            file: file.path.clone(),
+                            line: resource.meta.line,
+                            message,
+                            reference: None,
+                        });
+                    continue;
+                }
+            };
 
             // Map ParsedKind to type name
             let type_name = match resource.kind {
@@ -57,19 +186,224 @@ impl ResourceGraphBuilder {
                 crate::generator::parsing::ResourceKind::Template => {
                     "template"
                 }
+                crate::generator::parsing::ResourceKind::LatLng => {
+                    "latlng"
+                }
+                crate::generator::parsing::ResourceKind::Position => {
+                    "position"
+                }
+                crate::generator::parsing::ResourceKind::ByteSize => {
+                    "bytesize"
+                }
+                crate::generator::parsing::ResourceKind::Semver => {
+                    "semver"
+                }
+                crate::generator::parsing::ResourceKind::IpAddr => {
+                    "ipaddr"
+                }
+                crate::generator::parsing::ResourceKind::SocketAddr => {
+                    "socketaddr"
+                }
+                crate::generator::parsing::ResourceKind::Regex => "regex",
+                crate::generator::parsing::ResourceKind::Uuid => "uuid",
+                crate::generator::parsing::ResourceKind::DateTime => {
+                    "datetime"
+                }
+                crate::generator::parsing::ResourceKind::Date => "date",
+                crate::generator::parsing::ResourceKind::Money => {
+                    "money"
+                }
+                crate::generator::parsing::ResourceKind::Char => "char",
+                crate::generator::parsing::ResourceKind::Bytes => {
+                    "bytes"
+                }
+                crate::generator::parsing::ResourceKind::Computed
+                | crate::generator::parsing::ResourceKind::Concat => {
+                    self.ingest_deferred(file, resource, key, origin);
+                    continue;
+                }
             };
 
             let Some(ty) = self.registry.find_by_name(type_name) else {
                 continue;
             };
-            let Some(node) = ty.build_node(resource, origin) else {
+            if let Err(message) = ty.validate(resource) {
+                self.target_graph(file.is_test, file.locale.as_deref()).record_invalid(
+                    InvalidResource {
+                        key,
+                        file: file.path.clone(),
+                        line: resource.meta.line,
+                        message,
+                        reference: None,
+                    },
+                );
+                continue;
+            }
+            let Some(mut node) = ty.build_node(resource, origin) else {
                 continue;
             };
-            let is_duplicate = self.graph.insert(key, node);
+            // The attributes every type shares come from the file;
+            // a type may already have filled in its own `doc`
+            let meta = &mut node.meta;
+            meta.deprecated = resource.meta.deprecated.clone();
+            meta.doc =
+                meta.doc.take().or_else(|| resource.meta.doc.clone());
+            meta.asserts = asserts;
+            meta.cfg = resource.meta.cfg.clone();
+            self.pending.push(PendingNode {
+                is_test: file.is_test,
+                locale: file.locale.clone(),
+                key,
+                node,
+                is_specific: self.profile.is_some()
+                    && profile::is_specific(specs),
+                deferred: None,
+            });
+        }
+    }
+
+    /// Queues a `<computed>` or a `<concat>` as a placeholder whose
+    /// value is filled in once the graph is complete
+    fn ingest_deferred(
+        &mut self,
+        file: &ParsedResourceFile,
+        resource: &ParsedResource,
+        key: ResourceKey,
+        origin: ResourceOrigin,
+    ) {
+        let parsed = match &resource.value {
+            ScalarValue::Computed {
+                expression,
+                explicit_type,
+            } => ComputedNumber::parse(
+                expression,
+                explicit_type.as_deref(),
+                file.path.clone(),
+                resource.meta.line,
+            )
+            .map(Deferred::Computed),
+            ScalarValue::Concat(parts) => {
+                Ok(Deferred::Concat(parts.clone()))
+            }
+            _ => return,
+        };
+        let deferred = match parsed {
+            Ok(deferred) => deferred,
+            Err(message) => {
+                self.target_graph(file.is_test, file.locale.as_deref())
+                    .record_invalid(InvalidResource {
+                        key,
+                        file: file.path.clone(),
+                        line: resource.meta.line,
+                        message,
+                        reference: None,
+                    });
+                return;
+            }
+        };
+        let (kind, value) = match &deferred {
+            Deferred::Computed(_) => (
+                ResourceKind::Number,
+                ResourceValue::Number(NumberValue::Int(0)),
+            ),
+            Deferred::Concat(_) => (
+                ResourceKind::String,
+                ResourceValue::String(String::new()),
+            ),
+        };
+        let specs = &resource.meta.profiles;
+        self.pending.push(PendingNode {
+            is_test: file.is_test,
+            locale: file.locale.clone(),
+            key,
+            node: ResourceNode {
+                meta: NodeMeta {
+                    deprecated: resource.meta.deprecated.clone(),
+                    doc: resource.meta.doc.clone(),
+                    // Invalid assertions were reported by
+                    // `ingest_file`
+                    asserts: parse_assertions(resource)
+                        .unwrap_or_default(),
+                    cfg: resource.meta.cfg.clone(),
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(kind, value, origin)
+            },
+            is_specific: self.profile.is_some()
+                && profile::is_specific(specs),
+            deferred: Some(deferred),
+        });
+    }
+
+    fn is_active(&self, specs: &[String]) -> bool {
+        self.profile
+            .as_deref()
+            .is_none_or(|current| profile::is_active(specs, current))
+    }
+
+    /// Graph receiving resources of `locale` (`None` = default
+    /// resources), in the test graph for files of `res/tests/`
+    fn target_graph(
+        &mut self,
+        is_test: bool,
+        locale: Option<&str>,
+    ) -> &mut ResourceGraph {
+        let graph = if is_test {
+            self.graph.tests_mut()
+        } else {
+            &mut self.graph
+        };
+        match locale {
+            Some(locale) => graph.translation_mut(locale),
+            None => graph,
+        }
+    }
+
+    /// Drops fallback nodes shadowed by a profile-specific definition,
+    /// then evaluates the `<computed>` numbers, resolves `@string/`
+    /// references and `<concat>` parts against the final values and
+    /// checks the `pattern` of the strings
+    fn finish(mut self) -> ResourceGraph {
+        let specific_keys: HashSet<Slot> = self
+            .pending
+            .iter()
+            .filter(|p| p.is_specific)
+            .map(|p| (p.is_test, p.locale.clone(), p.key.clone()))
+            .collect();
+
+        let mut computed = Vec::new();
+        let mut concats = Vec::new();
+        for pending in std::mem::take(&mut self.pending) {
+            let slot = (pending.is_test, pending.locale, pending.key);
+            if !pending.is_specific && specific_keys.contains(&slot) {
+                continue;
+            }
+            let (is_test, locale, key) = slot;
+            let is_duplicate = self
+                .target_graph(is_test, locale.as_deref())
+                .insert(key.clone(), pending.node);
+            // Duplicate detected - will be reported as warning in analysis
             if is_duplicate {
-                // Duplicate detected - will be reported as warning in analysis
+                continue;
+            }
+            match pending.deferred {
+                Some(Deferred::Computed(number)) => {
+                    computed.push((is_test, locale, key, number));
+                }
+                Some(Deferred::Concat(parts)) => {
+                    concats.push((is_test, locale, key, parts));
+                }
+                None => {}
             }
         }
+        super::computed::evaluate_computed(&mut self.graph, computed);
+        super::interpolation::resolve_references(
+            &mut self.graph,
+            concats,
+        );
+        super::pattern::check_patterns(&mut self.graph);
+        super::assertion::check_assertions(&mut self.graph);
+        self.graph
     }
 }
 
@@ -94,6 +428,7 @@ mod tests {
                 name: "auth/title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Login".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -125,11 +460,13 @@ mod tests {
                         value: "3".to_string(),
                         explicit_type: None,
                     },
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "enabled".to_string(),
                     kind: ParsedKind::Bool,
                     value: ScalarValue::Bool(true),
+                    meta: Default::default(),
                 },
             ],
         );
@@ -168,6 +505,7 @@ mod tests {
                 name: "app_name".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("MyApp".to_string()),
+                meta: Default::default(),
             }],
         );
         let file2 = ParsedResourceFile::new(
@@ -177,6 +515,7 @@ mod tests {
                 name: "version".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("1.0.0".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -193,6 +532,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("First".to_string()),
+                meta: Default::default(),
             }],
         );
         let file2 = ParsedResourceFile::new(
@@ -202,6 +542,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Second".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -232,6 +573,7 @@ mod tests {
                 name: "primary_color".to_string(),
                 kind: ParsedKind::Color,
                 value: ScalarValue::Color("#FF0000".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -257,11 +599,13 @@ mod tests {
                     name: "auth/title".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Login".to_string()),
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "auth/error/message".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Invalid".to_string()),
+                    meta: Default::default(),
                 },
             ],
         );
@@ -271,8 +615,8 @@ mod tests {
 
         let key1 = ResourceKey::from_path("auth/title");
         let node1 = graph.get(&key1).expect("node exists");
-        assert_eq!(key1.namespace, vec!["auth"]);
-        assert_eq!(key1.name, "title");
+        assert_eq!(*key1.namespace, ["auth"]);
+        assert_eq!(&*key1.name, "title");
         match &node1.value {
             ResourceValue::String(value) => assert_eq!(value, "Login"),
             _ => panic!("expected String"),
@@ -280,8 +624,8 @@ mod tests {
 
         let key2 = ResourceKey::from_path("auth/error/message");
         let node2 = graph.get(&key2).expect("node exists");
-        assert_eq!(key2.namespace, vec!["auth", "error"]);
-        assert_eq!(key2.name, "message");
+        assert_eq!(*key2.namespace, ["auth", "error"]);
+        assert_eq!(&*key2.name, "message");
         match &node2.value {
             ResourceValue::String(value) => assert_eq!(value, "Invalid"),
             _ => panic!("expected String"),
@@ -297,12 +641,17 @@ mod tests {
                 name: "test_string".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Test".to_string()),
+                meta: Default::default(),
             }],
         );
 
         let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
         let key = ResourceKey::from_path("test_string");
-        let node = graph.get(&key).expect("node exists");
+        assert!(graph.get(&key).is_none(), "kept out of `r::`");
+        let node = graph
+            .tests()
+            .and_then(|tests| tests.get(&key))
+            .expect("node exists");
         assert!(node.origin.is_test);
     }
 
@@ -331,6 +680,7 @@ mod tests {
                     name: "valid_string".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("Valid".to_string()),
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "invalid_number".to_string(),
@@ -339,6 +689,7 @@ mod tests {
                         value: "not_a_number".to_string(),
                         explicit_type: None,
                     },
+                    meta: Default::default(),
                 },
             ],
         );
@@ -364,6 +715,7 @@ mod tests {
                     name: "app_name".to_string(),
                     kind: ParsedKind::String,
                     value: ScalarValue::Text("MyApp".to_string()),
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "max_retries".to_string(),
@@ -372,16 +724,19 @@ mod tests {
                         value: "3".to_string(),
                         explicit_type: None,
                     },
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "enabled".to_string(),
                     kind: ParsedKind::Bool,
                     value: ScalarValue::Bool(true),
+                    meta: Default::default(),
                 },
                 ParsedResource {
                     name: "primary_color".to_string(),
                     kind: ParsedKind::Color,
                     value: ScalarValue::Color("#FF0000".to_string()),
+                    meta: Default::default(),
                 },
             ],
         );
@@ -424,6 +779,7 @@ mod tests {
                 name: "title".to_string(),
                 kind: ParsedKind::String,
                 value: ScalarValue::Text("Hello".to_string()),
+                meta: Default::default(),
             }],
         );
 
@@ -434,4 +790,273 @@ mod tests {
         assert!(node.origin.file.ends_with("strings.xml"));
         assert!(!node.origin.is_test);
     }
+
+    fn profiled_string(
+        name: &str,
+        value: &str,
+        profiles: &[&str],
+    ) -> ParsedResource {
+        let mut resource = ParsedResource::string(name, value);
+        resource.meta.profiles =
+            profiles.iter().map(|p| p.to_string()).collect();
+        resource
+    }
+
+    fn string_value(graph: &ResourceGraph, name: &str) -> String {
+        let key = ResourceKey::from_path(name);
+        match &graph.get(&key).expect("node exists").value {
+            ResourceValue::String(value) => value.clone(),
+            _ => panic!("expected String"),
+        }
+    }
+
+    #[test]
+    fn profile_default_only_is_used() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![profiled_string("api_url", "default", &["default"])],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+            &[parsed], "staging",
+        );
+        assert_eq!(string_value(&graph, "api_url"), "default");
+        assert!(!graph.has_duplicates(&ResourceKey::from_path("api_url")));
+    }
+
+    #[test]
+    fn profile_specific_wins_over_default() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                profiled_string("api_url", "default", &["default"]),
+                profiled_string("api_url", "prod", &["release"]),
+                // Unprofiled sibling acts as a default too
+                profiled_string("api_url", "plain", &[]),
+            ],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+            &[parsed], "release",
+        );
+        let key = ResourceKey::from_path("api_url");
+        assert_eq!(string_value(&graph, "api_url"), "prod");
+        assert_eq!(graph.get_all(&key).unwrap().len(), 1);
+        assert_eq!(
+            graph.get(&key).unwrap().origin.profile.as_deref(),
+            Some("release")
+        );
+    }
+
+    #[test]
+    fn profile_default_used_when_specific_does_not_match() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                profiled_string("api_url", "prod", &["release"]),
+                profiled_string("api_url", "default", &["default"]),
+            ],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+            &[parsed], "debug",
+        );
+        let key = ResourceKey::from_path("api_url");
+        assert_eq!(string_value(&graph, "api_url"), "default");
+        assert_eq!(graph.get_all(&key).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn profile_inherited_from_namespace_filters_resources() {
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![profiled_string("debug_tools/url", "dbg", &["debug"])],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+            &[parsed], "release",
+        );
+        assert!(graph.nodes().is_empty());
+    }
+
+    #[test]
+    fn patterns_are_checked_on_resolved_values() {
+        let string = |name: &str, text: &str, pattern: Option<&str>| {
+            ParsedResource {
+                name: name.to_string(),
+                kind: ParsedKind::String,
+                value: ScalarValue::Text(text.to_string()),
+                meta: crate::generator::parsing::ResourceMeta {
+                    pattern: pattern.map(str::to_string),
+                    line: Some(3),
+                    ..Default::default()
+                },
+            }
+        };
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                string("region", "eu-west-1", None),
+                string(
+                    "default_region",
+                    "@string/region",
+                    Some(r"[a-z]{2}-[a-z]+-\d"),
+                ),
+                string("slug", "Not a slug", Some("[a-z-]+")),
+                string("broken", "x", Some("(ab")),
+            ],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
+        let invalid: Vec<_> = graph
+            .invalid_resources()
+            .iter()
+            .map(|i| (i.key.full_name(), i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(
+            invalid,
+            vec![
+                (
+                    "broken".to_string(),
+                    Some(3),
+                    "invalid pattern `(ab`: unclosed group"
+                ),
+                (
+                    "slug".to_string(),
+                    Some(3),
+                    "\"Not a slug\" does not match the pattern `[a-z-]+`"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn assertions_are_checked_on_resolved_values() {
+        let with_assert = |mut resource: ParsedResource, list: &str| {
+            resource.meta.assert = Some(list.to_string());
+            resource
+        };
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::string("prefix", "secret_"),
+                with_assert(
+                    ParsedResource::string("label", "@string/prefix"),
+                    "len_le:4",
+                ),
+                with_assert(
+                    ParsedResource::number("port", "0", Some("u16".into())),
+                    "gt:0",
+                ),
+                with_assert(
+                    ParsedResource::number("workers", "4", None),
+                    "len_ge:1",
+                ),
+                with_assert(ParsedResource::bool("debug", true), "ne:0"),
+            ],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
+        let invalid: Vec<_> = graph
+            .invalid_resources()
+            .iter()
+            .map(|i| (i.key.full_name(), i.message.as_str()))
+            .collect();
+        assert_eq!(
+            invalid,
+            vec![
+                (
+                    "workers".to_string(),
+                    "`len_ge:1` only applies to strings"
+                ),
+                (
+                    "debug".to_string(),
+                    "`assert` only applies to numbers and strings"
+                ),
+                (
+                    "label".to_string(),
+                    "\"secret_\" is 7 bytes long and fails `len_le:4`"
+                ),
+                ("port".to_string(), "0 fails `gt:0`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn computed_numbers_use_the_selected_profile() {
+        let computed = |name: &str, expression: &str| ParsedResource {
+            name: name.to_string(),
+            kind: ParsedKind::Computed,
+            value: ScalarValue::Computed {
+                expression: expression.to_string(),
+                explicit_type: Some("i64".into()),
+            },
+            meta: crate::generator::parsing::ResourceMeta {
+                line: Some(7),
+                ..Default::default()
+            },
+        };
+        let mut release =
+            ParsedResource::number("timeout_ms", "2000", None);
+        release.meta.profiles = vec!["release".into()];
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![
+                ParsedResource::number("timeout_ms", "1000", None),
+                release,
+                computed(
+                    "retry_budget_ms",
+                    "@number/timeout_ms * 3 + 500",
+                ),
+                computed("broken", "@number/timeout_ms *"),
+            ],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+            &[parsed], "release",
+        );
+        match &graph
+            .get(&ResourceKey::from_path("retry_budget_ms"))
+            .unwrap()
+            .value
+        {
+            ResourceValue::Number(NumberValue::Typed { literal, .. }) => {
+                assert_eq!(literal, "6500");
+            }
+            other => panic!("expected a typed number, got {other:?}"),
+        }
+        let invalid: Vec<_> = graph
+            .invalid_resources()
+            .iter()
+            .map(|i| (i.key.full_name(), i.line))
+            .collect();
+        assert_eq!(invalid, vec![("broken".to_string(), Some(7))]);
+    }
+
+    #[test]
+    fn unknown_transforms_are_invalid() {
+        let mut slug = ParsedResource::string("slug", "Demo");
+        slug.meta.transform = Some("slug,shout".into());
+        slug.meta.line = Some(4);
+        let parsed = ParsedResourceFile::new(
+            PathBuf::from("values.xml"),
+            false,
+            vec![slug],
+        );
+
+        let graph = ResourceGraphBuilder::from_parsed_files(&[parsed]);
+        let invalid = &graph.invalid_resources()[0];
+        assert_eq!(invalid.line, Some(4));
+        assert!(invalid
+            .message
+            .starts_with("unknown transform `shout` in `slug,shout`"));
+        assert!(graph.get(&ResourceKey::from_path("slug")).is_none());
+    }
 }
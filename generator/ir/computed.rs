@@ -0,0 +1,780 @@
+//! `<computed>` numbers, evaluated from other numbers at build time.
+//!
+//! ```xml
+//! <number name="timeout_ms">5000</number>
+//! <computed name="retry_budget_ms" type="i64">@number/timeout_ms * 3 + 500</computed>
+//! ```
+//!
+//! Expressions have `+ - * /`, parentheses, integer and float literals
+//! (with the usual `_` and `0x` forms) and `@number/path` references,
+//! other computed numbers included. They are evaluated once the graph
+//! is complete, so referenced numbers have their profile overrides
+//! applied, and the result becomes a plain number constant.
+//!
+//! Integer types are computed exactly (`/` truncates, as in Rust) and
+//! must not see a float operand; float types are computed as `f64`.
+//! Without a `type`, the result is an `i64`, or an `f64` as soon as a
+//! float is involved. Syntax errors, division by zero, results that do
+//! not fit the type, references to missing or non-number resources and
+//! cycles are recorded as invalid resources.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use super::model::{
+    InvalidResource, NumberType, NumberValue, ResourceGraph,
+    ResourceKey, ResourceValue,
+};
+use super::types::{
+    float_value, integer_value, parse_number_type, typed_float,
+    typed_integer,
+};
+
+const PREFIX: &str = "@number/";
+
+/// Parsed expression of a `<computed>`
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Int(i128),
+    Float(f64),
+    /// Qualified name after `@number/`
+    Reference(String),
+    Negate(Box<Expression>),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// Value of an expression while it is evaluated
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+/// A `<computed>` waiting for the rest of the graph
+#[derive(Debug, Clone)]
+pub struct ComputedNumber {
+    pub expression: Expression,
+    /// `None` to pick `i64` or `f64` from the operands
+    pub ty: Option<NumberType>,
+    pub file: PathBuf,
+    pub line: Option<usize>,
+}
+
+impl ComputedNumber {
+    /// Parses the expression and `type` of a `<computed>`
+    pub fn parse(
+        expression: &str,
+        explicit_type: Option<&str>,
+        file: PathBuf,
+        line: Option<usize>,
+    ) -> Result<Self, String> {
+        let ty = match explicit_type {
+            Some(name) => Some(parse_number_type(name)?.ok_or(
+                "computed numbers cannot be bigdecimal".to_string(),
+            )?),
+            None => None,
+        };
+        Ok(Self {
+            expression: Expression::parse(expression)?,
+            ty,
+            file,
+            line,
+        })
+    }
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+        };
+        let expression = parser.sum()?;
+        parser.skip_spaces();
+        match parser.peek() {
+            None => Ok(expression),
+            Some(c) => Err(parser.unexpected(c)),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Next operator among `operators`, consumed
+    fn operator(&mut self, operators: &[char]) -> Option<Operator> {
+        self.skip_spaces();
+        let c = self.peek().filter(|c| operators.contains(c))?;
+        self.pos += 1;
+        Some(match c {
+            '+' => Operator::Add,
+            '-' => Operator::Subtract,
+            '*' => Operator::Multiply,
+            _ => Operator::Divide,
+        })
+    }
+
+    fn unexpected(&self, c: char) -> String {
+        format!("unexpected `{c}` at column {}", self.pos + 1)
+    }
+
+    fn sum(&mut self) -> Result<Expression, String> {
+        let mut left = self.product()?;
+        while let Some(op) = self.operator(&['+', '-']) {
+            let right = self.product()?;
+            left = Expression::Binary(
+                Box::new(left),
+                op,
+                Box::new(right),
+            );
+        }
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expression, String> {
+        let mut left = self.unary()?;
+        while let Some(op) = self.operator(&['*', '/']) {
+            let right = self.unary()?;
+            left = Expression::Binary(
+                Box::new(left),
+                op,
+                Box::new(right),
+            );
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, String> {
+        match self.operator(&['+', '-']) {
+            Some(Operator::Subtract) => {
+                Ok(Expression::Negate(Box::new(self.unary()?)))
+            }
+            Some(_) => self.unary(),
+            None => self.operand(),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Expression, String> {
+        self.skip_spaces();
+        match self.peek() {
+            None => Err("unexpected end of expression".to_string()),
+            Some('(') => {
+                self.pos += 1;
+                let inner = self.sum()?;
+                self.skip_spaces();
+                if self.peek() != Some(')') {
+                    return Err(format!(
+                        "unclosed `(` before column {}",
+                        self.pos + 1
+                    ));
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some('@') => self.reference(),
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                self.literal()
+            }
+            Some(c) => Err(self.unexpected(c)),
+        }
+    }
+
+    /// `@number/path`, the path being `name(/name)*`
+    fn reference(&mut self) -> Result<Expression, String> {
+        let rest: String = self.chars[self.pos..].iter().collect();
+        let Some(after) = rest.strip_prefix(PREFIX) else {
+            return Err(format!(
+                "only `{PREFIX}` references are allowed at column {}",
+                self.pos + 1
+            ));
+        };
+        let path: String = after
+            .chars()
+            .take_while(|&c| {
+                c.is_alphanumeric() || c == '_' || c == '/'
+            })
+            .collect();
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            return Err(format!(
+                "`{PREFIX}` without a name at column {}",
+                self.pos + 1
+            ));
+        }
+        self.pos += PREFIX.len() + path.chars().count();
+        Ok(Expression::Reference(path.to_string()))
+    }
+
+    /// Integer or float literal, `_` separators and prefixes included
+    fn literal(&mut self) -> Result<Expression, String> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let exponent_sign = matches!(c, '+' | '-')
+                && matches!(self.chars[self.pos - 1], 'e' | 'E')
+                && !self.chars[start..].starts_with(&['0', 'x']);
+            if !(c.is_ascii_alphanumeric()
+                || c == '_'
+                || c == '.'
+                || exponent_sign)
+            {
+                break;
+            }
+            self.pos += 1;
+        }
+        let text: String =
+            self.chars[start..self.pos].iter().collect();
+        if let Some(value) = integer_value(&text) {
+            return Ok(Expression::Int(value));
+        }
+        text.replace('_', "")
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(Expression::Float)
+            .ok_or_else(|| format!("invalid number `{text}`"))
+    }
+}
+
+/// Why a computed number could not be evaluated
+enum EvalError {
+    Message(String),
+    /// Names of the cycle, starting and ending with the same one
+    Cycle(Vec<String>),
+}
+
+impl EvalError {
+    fn message(self) -> String {
+        match self {
+            Self::Message(message) => message,
+            Self::Cycle(names) => {
+                format!("reference cycle {}", names.join(" → "))
+            }
+        }
+    }
+}
+
+impl From<String> for EvalError {
+    fn from(message: String) -> Self {
+        Self::Message(message)
+    }
+}
+
+/// Evaluates the computed numbers of each graph (`None` = default,
+/// else `(is_test, locale)`) and stores their value in its node.
+/// Test resources and translations also see the numbers of the
+/// default resources.
+pub fn evaluate_computed(
+    graph: &mut ResourceGraph,
+    computed: Vec<(
+        bool,
+        Option<String>,
+        ResourceKey,
+        ComputedNumber,
+    )>,
+) {
+    let mut groups: BTreeMap<
+        (bool, Option<String>),
+        BTreeMap<String, (ResourceKey, ComputedNumber)>,
+    > = BTreeMap::new();
+    for (is_test, locale, key, number) in computed {
+        groups
+            .entry((is_test, locale))
+            .or_default()
+            .insert(key.full_name(), (key, number));
+    }
+    // The default resources first, the other graphs fall back to them
+    let defaults = groups.remove(&(false, None)).unwrap_or_default();
+    evaluate_graph(graph, &BTreeMap::new(), &defaults);
+    let base = number_values(graph);
+    for ((is_test, locale), group) in groups {
+        let target = if is_test {
+            graph.tests_mut()
+        } else {
+            &mut *graph
+        };
+        let target = match &locale {
+            Some(locale) => target.translation_mut(locale),
+            None => target,
+        };
+        evaluate_graph(target, &base, &group);
+    }
+}
+
+fn evaluate_graph(
+    graph: &mut ResourceGraph,
+    fallback: &BTreeMap<String, Option<NumberValue>>,
+    computed: &BTreeMap<String, (ResourceKey, ComputedNumber)>,
+) {
+    let mut values = fallback.clone();
+    values.extend(number_values(graph));
+    let mut evaluator = Evaluator {
+        values,
+        computed,
+        results: BTreeMap::new(),
+        stack: Vec::new(),
+        references: BTreeSet::new(),
+    };
+    let mut invalid = Vec::new();
+    for (name, (key, number)) in computed {
+        match evaluator.evaluate(name) {
+            Ok(value) => {
+                if let Some(node) = graph.get_mut(key) {
+                    node.value = ResourceValue::Number(value);
+                }
+            }
+            Err(error) => invalid.push(InvalidResource {
+                key: key.clone(),
+                file: number.file.clone(),
+                line: number.line,
+                message: error.message(),
+                reference: None,
+            }),
+        }
+    }
+    invalid.into_iter().for_each(|i| graph.record_invalid(i));
+    for (from, to) in evaluator.references {
+        let from = ResourceKey::from_path(&from);
+        graph.record_reference(from, ResourceKey::from_path(&to));
+    }
+}
+
+/// Value of the primary definition of every resource, `None` for those
+/// that are not numbers
+fn number_values(
+    graph: &ResourceGraph,
+) -> BTreeMap<String, Option<NumberValue>> {
+    graph
+        .nodes()
+        .iter()
+        .filter_map(|(key, nodes)| {
+            let value = match &nodes.first()?.value {
+                ResourceValue::Number(number) => Some(number.clone()),
+                _ => None,
+            };
+            Some((key.full_name(), value))
+        })
+        .collect()
+}
+
+struct Evaluator<'a> {
+    values: BTreeMap<String, Option<NumberValue>>,
+    computed: &'a BTreeMap<String, (ResourceKey, ComputedNumber)>,
+    results: BTreeMap<String, NumberValue>,
+    stack: Vec<String>,
+    /// (computed number being evaluated, number it references)
+    references: BTreeSet<(String, String)>,
+}
+
+impl Evaluator<'_> {
+    /// Value of the computed number `name`, as its constant
+    fn evaluate(
+        &mut self,
+        name: &str,
+    ) -> Result<NumberValue, EvalError> {
+        if let Some(value) = self.results.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(start) = self.stack.iter().position(|n| n == name)
+        {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(EvalError::Cycle(cycle));
+        }
+        let (_, number) = &self.computed[name];
+        self.stack.push(name.to_string());
+        let result = self.compute(&number.expression, number.ty);
+        self.stack.pop();
+        let value = constant(result?, number.ty)?;
+        self.results.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Value of the resource referenced as `@number/name`
+    fn reference(&mut self, name: &str) -> Result<Number, EvalError> {
+        if let Some(from) = self.stack.last() {
+            let reference = (from.clone(), name.to_string());
+            self.references.insert(reference);
+        }
+        let value = if self.computed.contains_key(name) {
+            self.evaluate(name)?
+        } else {
+            match self.values.get(name) {
+                Some(Some(value)) => value.clone(),
+                Some(None) => {
+                    return Err(EvalError::Message(format!(
+                        "`{PREFIX}{name}` is not a number"
+                    )))
+                }
+                None => {
+                    return Err(EvalError::Message(format!(
+                        "`{PREFIX}{name}` does not name a number"
+                    )))
+                }
+            }
+        };
+        operand(&value).ok_or_else(|| {
+            EvalError::Message(format!(
+                "`{PREFIX}{name}` is a BigDecimal, which computed numbers do not support"
+            ))
+        })
+    }
+
+    fn compute(
+        &mut self,
+        expression: &Expression,
+        ty: Option<NumberType>,
+    ) -> Result<Number, EvalError> {
+        let value = match expression {
+            Expression::Int(value) => Number::Int(*value),
+            Expression::Float(value) => Number::Float(*value),
+            Expression::Reference(name) => self.reference(name)?,
+            Expression::Negate(inner) => arithmetic(
+                Number::Int(0),
+                Operator::Subtract,
+                self.compute(inner, ty)?,
+            )?,
+            Expression::Binary(left, op, right) => {
+                let left = self.compute(left, ty)?;
+                let right = self.compute(right, ty)?;
+                arithmetic(left, *op, right)?
+            }
+        };
+        // Integer types compute exactly, float types as `f64`
+        match (value, ty) {
+            (Number::Int(value), Some(ty)) if ty.is_float() => {
+                Ok(Number::Float(value as f64))
+            }
+            (Number::Float(value), Some(ty)) if !ty.is_float() => {
+                Err(EvalError::Message(format!(
+                    "float operand {value:?} in an integer ({}) expression",
+                    ty.as_str()
+                )))
+            }
+            _ => Ok(value),
+        }
+    }
+}
+
+/// Operand form of a number resource, `None` for `BigDecimal`
+fn operand(value: &NumberValue) -> Option<Number> {
+    match value {
+        NumberValue::Int(value) => Some(Number::Int((*value).into())),
+        NumberValue::Float(value) => Some(Number::Float(*value)),
+        NumberValue::BigDecimal(_) => None,
+        NumberValue::Typed { literal, ty } if ty.is_float() => {
+            float_value(literal).map(Number::Float)
+        }
+        NumberValue::Typed { literal, .. } => {
+            integer_value(literal).map(Number::Int)
+        }
+    }
+}
+
+fn arithmetic(
+    left: Number,
+    op: Operator,
+    right: Number,
+) -> Result<Number, String> {
+    let as_float = |n: Number| match n {
+        Number::Int(value) => value as f64,
+        Number::Float(value) => value,
+    };
+    if let (Number::Int(a), Number::Int(b)) = (left, right) {
+        let result = match op {
+            Operator::Add => a.checked_add(b),
+            Operator::Subtract => a.checked_sub(b),
+            Operator::Multiply => a.checked_mul(b),
+            Operator::Divide if b == 0 => {
+                return Err("division by zero".to_string())
+            }
+            Operator::Divide => a.checked_div(b),
+        };
+        return result
+            .map(Number::Int)
+            .ok_or_else(|| "integer overflow".to_string());
+    }
+    let (a, b) = (as_float(left), as_float(right));
+    let result = match op {
+        Operator::Add => a + b,
+        Operator::Subtract => a - b,
+        Operator::Multiply => a * b,
+        Operator::Divide if b == 0.0 => {
+            return Err("division by zero".to_string())
+        }
+        Operator::Divide => a / b,
+    };
+    if result.is_finite() {
+        Ok(Number::Float(result))
+    } else {
+        Err("float overflow".to_string())
+    }
+}
+
+/// Constant for `value`, checked against the target type
+fn constant(
+    value: Number,
+    ty: Option<NumberType>,
+) -> Result<NumberValue, String> {
+    match (value, ty) {
+        (Number::Int(value), None) => i64::try_from(value)
+            .map(NumberValue::Int)
+            .map_err(|_| format!("{value} does not fit in i64")),
+        (Number::Float(value), None) => Ok(NumberValue::Float(value)),
+        (Number::Float(value), Some(NumberType::F32))
+            if value.abs() > f32::MAX.into() =>
+        {
+            Err(format!("{value:?} does not fit in f32"))
+        }
+        (Number::Float(value), Some(ty)) => {
+            Ok(typed_float(value, ty))
+        }
+        (Number::Int(value), Some(ty)) => {
+            let name = ty.as_str();
+            typed_integer(value, ty).ok_or_else(|| {
+                format!("{value} does not fit in {name}")
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKind, ResourceNode, ResourceOrigin,
+    };
+
+    fn number(
+        graph: &mut ResourceGraph,
+        path: &str,
+        value: NumberValue,
+    ) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::Number,
+                ResourceValue::Number(value),
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    /// Inserts `computed` as placeholders, evaluates them and returns
+    /// the values as Rust literals, or the errors of the invalid ones
+    fn evaluate(
+        graph: &mut ResourceGraph,
+        computed: &[(&str, &str, Option<&str>)],
+    ) -> Vec<Result<String, String>> {
+        let mut pending = Vec::new();
+        for (path, expression, ty) in computed {
+            number(graph, path, NumberValue::Int(0));
+            let number = ComputedNumber::parse(
+                expression,
+                *ty,
+                PathBuf::from("values.xml"),
+                Some(1),
+            )
+            .unwrap();
+            pending.push((
+                false,
+                None,
+                ResourceKey::from_path(path),
+                number,
+            ));
+        }
+        evaluate_computed(graph, pending);
+        computed
+            .iter()
+            .map(|(path, _, _)| {
+                let key = ResourceKey::from_path(path);
+                match graph
+                    .invalid_resources()
+                    .iter()
+                    .find(|i| i.key == key)
+                {
+                    Some(invalid) => Err(invalid.message.clone()),
+                    None => match &graph.get(&key).unwrap().value {
+                        ResourceValue::Number(value) => {
+                            Ok(literal(value))
+                        }
+                        _ => unreachable!(),
+                    },
+                }
+            })
+            .collect()
+    }
+
+    /// `15500i64` for typed constants, the bare literal otherwise
+    fn literal(value: &NumberValue) -> String {
+        match value {
+            NumberValue::Int(value) => value.to_string(),
+            NumberValue::Float(value) => format!("{value:?}"),
+            NumberValue::BigDecimal(raw) => raw.clone(),
+            NumberValue::Typed { literal, ty } => {
+                format!("{literal}{}", ty.as_str())
+            }
+        }
+    }
+
+    fn ok(literal: &str) -> Result<String, String> {
+        Ok(literal.to_string())
+    }
+
+    #[test]
+    fn expressions_follow_precedence() {
+        let mut graph = ResourceGraph::default();
+        number(&mut graph, "timeout_ms", NumberValue::Int(5000));
+        number(&mut graph, "net/factor", NumberValue::Float(1.5));
+        let results = evaluate(
+            &mut graph,
+            &[
+                (
+                    "budget",
+                    "@number/timeout_ms * 3 + 500",
+                    Some("i64"),
+                ),
+                ("grouped", "(1 + 2) * -(3 - 0x1_0)", None),
+                (
+                    "scaled",
+                    "@number/timeout_ms * @number/net/factor",
+                    None,
+                ),
+                ("ratio", "7 / 2", Some("f32")),
+                ("truncated", "7 / 2", Some("u8")),
+            ],
+        );
+        assert_eq!(
+            results,
+            vec![
+                ok("15500i64"),
+                ok("39"),
+                ok("7500.0"),
+                ok("3.5f32"),
+                ok("3u8"),
+            ]
+        );
+    }
+
+    #[test]
+    fn computed_numbers_can_reference_each_other() {
+        let mut graph = ResourceGraph::default();
+        let results = evaluate(
+            &mut graph,
+            &[
+                ("total", "@number/half * 2", None),
+                ("half", "21", None),
+                ("a", "@number/b + 1", None),
+                ("b", "@number/a + 1", None),
+            ],
+        );
+        assert_eq!(results[0], ok("42"));
+        assert_eq!(
+            results[2],
+            Err("reference cycle a → b → a".to_string())
+        );
+        assert_eq!(
+            results[3],
+            Err("reference cycle b → a → b".to_string())
+        );
+    }
+
+    #[test]
+    fn evaluation_errors() {
+        let mut graph = ResourceGraph::default();
+        graph.insert(
+            ResourceKey::from_path("title"),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String("Login".into()),
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+        number(&mut graph, "rate", NumberValue::Float(0.5));
+        let results = evaluate(
+            &mut graph,
+            &[
+                ("zero", "1 / (2 - 2)", None),
+                ("byte", "200 + 100", Some("u8")),
+                ("huge", "9223372036854775807 + 1", None),
+                ("text", "@number/title + 1", None),
+                ("missing", "@number/nope", None),
+                ("mixed", "@number/rate * 2", Some("i32")),
+            ],
+        );
+        let errors: Vec<String> =
+            results.into_iter().map(Result::unwrap_err).collect();
+        assert_eq!(
+            errors,
+            vec![
+                "division by zero",
+                "300 does not fit in u8",
+                "9223372036854775808 does not fit in i64",
+                "`@number/title` is not a number",
+                "`@number/nope` does not name a number",
+                "float operand 0.5 in an integer (i32) expression",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_expressions_are_errors() {
+        for (expression, error) in [
+            ("1 +", "unexpected end of expression"),
+            ("(1 + 2", "unclosed `(` before column 7"),
+            ("1 2", "unexpected `2` at column 3"),
+            (
+                "@string/x",
+                "only `@number/` references are allowed at column 1",
+            ),
+            ("1.2.3", "invalid number `1.2.3`"),
+            ("2 % 3", "unexpected `%` at column 3"),
+        ] {
+            assert_eq!(
+                Expression::parse(expression).unwrap_err(),
+                error,
+                "{expression}"
+            );
+        }
+        assert!(matches!(
+            Expression::parse("1e-3 * 2").unwrap(),
+            Expression::Binary(..)
+        ));
+        assert_eq!(
+            ComputedNumber::parse(
+                "1",
+                Some("bigdecimal"),
+                "x".into(),
+                None
+            )
+            .unwrap_err(),
+            "computed numbers cannot be bigdecimal"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! One shared copy of each name and namespace path of a build.
+//!
+//! Every resource of `auth/errors` has the same namespace path, and
+//! the translations of `auth/title` the same name as the default
+//! resource: the graph builder looks them up here instead of
+//! splitting and copying them per resource, so cloning a
+//! [`ResourceKey`] clones two pointers.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::model::ResourceKey;
+
+#[derive(Debug, Default)]
+pub struct Interner {
+    names: HashSet<Arc<str>>,
+    /// By the path as written, `auth/errors`
+    namespaces: HashMap<Box<str>, Arc<[String]>>,
+}
+
+impl Interner {
+    /// The shared copy of `name`
+    pub fn name(&mut self, name: &str) -> Arc<str> {
+        if let Some(name) = self.names.get(name) {
+            return Arc::clone(name);
+        }
+        let name: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&name));
+        name
+    }
+
+    /// The shared segments of the namespace path `path`
+    pub fn namespace(&mut self, path: &str) -> Arc<[String]> {
+        if let Some(namespace) = self.namespaces.get(path) {
+            return Arc::clone(namespace);
+        }
+        let namespace: Arc<[String]> = path
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.namespaces.insert(path.into(), Arc::clone(&namespace));
+        namespace
+    }
+
+    /// Same as [`ResourceKey::from_path`], sharing the name and the
+    /// namespace path
+    pub fn key(&mut self, path: &str) -> ResourceKey {
+        let (namespace, name) = match path.rsplit_once('/') {
+            Some((namespace, name)) if !name.is_empty() => {
+                (namespace, name)
+            }
+            // `auth/` names `auth`, as `from_path` skips empty parts
+            Some(_) => {
+                let key = ResourceKey::from_path(path);
+                return ResourceKey {
+                    namespace: self
+                        .namespace(&key.namespace.join("/")),
+                    name: self.name(&key.name),
+                };
+            }
+            None => ("", path),
+        };
+        ResourceKey {
+            namespace: self.namespace(namespace),
+            name: self.name(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_share_their_namespace_and_name() {
+        let mut interner = Interner::default();
+        let title = interner.key("auth/errors/title");
+        let body = interner.key("auth/errors/body");
+        assert!(Arc::ptr_eq(&title.namespace, &body.namespace));
+        assert!(Arc::ptr_eq(
+            &title.name,
+            &interner.key("auth/errors/title").name
+        ));
+        for path in
+            ["auth/errors/title", "title", "", "a//b", "auth/", "/x/"]
+        {
+            assert_eq!(
+                interner.key(path),
+                ResourceKey::from_path(path),
+                "{path}"
+            );
+        }
+    }
+}
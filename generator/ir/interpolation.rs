@@ -0,0 +1,699 @@
+//! `@string/name` references inside string values.
+//!
+//! `Welcome to @string/app_name!` is baked into a single constant at
+//! build time. References are resolved once the graph is complete, so
+//! profile overrides are already applied, and from an index keyed by
+//! qualified name (`auth/title`), so the result does not depend on the
+//! order files were loaded in.
+//!
+//! A path is matched against the longest existing name:
+//! `@string/base_url/v2` uses `base_url/v2` if defined, `base_url`
+//! followed by the literal `/v2` otherwise. Missing names and cycles
+//! are recorded as invalid resources, which analysis turns into errors.
+//!
+//! `@@` and `\@` stand for a literal `@` that never starts a reference
+//! (`@@string/x` stays `@string/x`). A string starting with any other
+//! `@` is recorded before expansion, so analysis can warn about typos
+//! such as `@strig/app_name` or `@string:app_name`.
+//!
+//! The `transform` chain of a string is applied to its expanded value,
+//! which is also the value other strings see when referencing it.
+//!
+//! `<concat>` parts are resolved by the same pass, so a concat can be
+//! referenced like any string and takes part in cycle detection. Its
+//! `<ref>`s name exactly one string: `<ref>@string/base_url</ref>`
+//! followed by `<lit>/v2</lit>` never reads as `base_url/v2`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::generator::parsing::ConcatPart;
+
+use super::model::{
+    InvalidResource, ResourceGraph, ResourceKey, ResourceValue,
+};
+use super::transform::{apply_chain, Transform};
+
+const PREFIX: &str = "@string/";
+
+/// Edits within which a type reads as a typo of `string`
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// Resolves the references and the `concats` of `graph` (`None` =
+/// default, else `(is_test, locale)`), then of each translation and of
+/// the test resources, whose names fall back to the default resources
+pub fn resolve_references(
+    graph: &mut ResourceGraph,
+    concats: Vec<(bool, Option<String>, ResourceKey, Vec<ConcatPart>)>,
+) {
+    record_malformed_references(graph);
+    graph.translations_mut().for_each(record_malformed_references);
+    if graph.tests().is_some() {
+        record_malformed_references(graph.tests_mut());
+    }
+    // Most tables have nothing to expand: skip copying their strings
+    let has_work = |graph: &ResourceGraph| {
+        graph.nodes().values().flatten().any(|node| {
+            matches!(&node.value, ResourceValue::String(text)
+                if text.contains('@')
+                    || !node.meta.transforms.is_empty())
+        })
+    };
+    if concats.is_empty()
+        && !has_work(graph)
+        && !graph.translations().values().any(has_work)
+        && !graph.tests().is_some_and(has_work)
+    {
+        return;
+    }
+    let mut groups: BTreeMap<(bool, Option<String>), Concats> =
+        BTreeMap::new();
+    for (is_test, locale, key, parts) in concats {
+        groups
+            .entry((is_test, locale))
+            .or_default()
+            .insert(key.full_name(), parts);
+    }
+    let base = string_values(graph);
+    let base_concats = groups.remove(&(false, None)).unwrap_or_default();
+    resolve_graph(graph, base.clone(), base_concats.clone());
+    let locales: Vec<String> =
+        graph.translations().keys().cloned().collect();
+    for locale in locales {
+        let own = groups.remove(&(false, Some(locale.clone())));
+        let translation = graph.translation_mut(&locale);
+        fall_back(translation, &base, &base_concats, own);
+    }
+    if graph.tests().is_some() {
+        let own = groups.remove(&(true, None));
+        fall_back(graph.tests_mut(), &base, &base_concats, own);
+    }
+}
+
+/// Resolves `graph` with the strings and concats of the default
+/// resources it does not redefine
+fn fall_back(
+    graph: &mut ResourceGraph,
+    base: &BTreeMap<String, StringValue>,
+    base_concats: &Concats,
+    own: Option<Concats>,
+) {
+    let own_values = string_values(graph);
+    let mut concats: Concats = base_concats
+        .iter()
+        .filter(|(name, _)| !own_values.contains_key(*name))
+        .map(|(name, parts)| (name.clone(), parts.clone()))
+        .collect();
+    concats.extend(own.unwrap_or_default());
+    let mut values = base.clone();
+    values.extend(own_values);
+    resolve_graph(graph, values, concats);
+}
+
+/// Records the strings of `graph` that start with a malformed
+/// reference, while their escapes are still visible
+fn record_malformed_references(graph: &mut ResourceGraph) {
+    let found: Vec<(ResourceKey, String)> = graph
+        .nodes()
+        .iter()
+        .filter_map(|(key, nodes)| {
+            let ResourceValue::String(text) = &nodes.first()?.value
+            else {
+                return None;
+            };
+            Some((key.clone(), malformed_reference(text)?))
+        })
+        .collect();
+    for (key, message) in found {
+        graph.record_malformed_reference(key, message);
+    }
+}
+
+/// Why `text`, starting with an `@` that is neither escaped nor a
+/// reference, does not read as one, with the likely fix; `None` for
+/// other strings
+pub fn malformed_reference(text: &str) -> Option<String> {
+    let rest = text.strip_prefix('@')?;
+    let is_reference = text
+        .strip_prefix(PREFIX)
+        .is_some_and(|after| !reference_path(after).is_empty());
+    if rest.starts_with('@') || is_reference {
+        return None;
+    }
+    let token = text.split_whitespace().next().unwrap_or(text);
+    let type_len = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let (ty, after) = rest.split_at(type_len);
+    let name = after
+        .strip_prefix(['/', ':', '.'])
+        .map_or("", reference_path);
+    let near_string = !ty.is_empty()
+        && distance(&ty.to_lowercase(), "string")
+            <= MAX_TYPO_DISTANCE;
+    let hint = match (near_string, name.is_empty()) {
+        (true, false) => format!("; did you mean `{PREFIX}{name}`?"),
+        (false, false) if after.starts_with('/') => format!(
+            "; only strings can be referenced, as `{PREFIX}...`"
+        ),
+        _ => String::new(),
+    };
+    Some(format!(
+        "`{token}` is not a reference{hint} (write `@@` for a literal `@`)"
+    ))
+}
+
+/// Levenshtein distance between `a` and `b`, in chars
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] =
+                substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Raw value and `transform` chain of a string
+type StringValue = (String, Vec<Transform>);
+
+/// Parts of the `<concat>`s of a graph, by qualified name
+type Concats = BTreeMap<String, Vec<ConcatPart>>;
+
+/// Primary definition of every string
+fn string_values(
+    graph: &ResourceGraph,
+) -> BTreeMap<String, StringValue> {
+    graph
+        .nodes()
+        .iter()
+        .filter_map(|(key, nodes)| {
+            let node = nodes.first()?;
+            match &node.value {
+                ResourceValue::String(text) => Some((
+                    key.full_name(),
+                    (text.clone(), node.meta.transforms.clone()),
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn resolve_graph(
+    graph: &mut ResourceGraph,
+    values: BTreeMap<String, StringValue>,
+    concats: Concats,
+) {
+    let mut resolver = Resolver {
+        values,
+        concats,
+        resolved: BTreeMap::new(),
+        stack: Vec::new(),
+        references: BTreeSet::new(),
+    };
+    let mut invalid = Vec::new();
+    for (key, nodes) in graph.nodes_mut() {
+        let is_concat = !resolver.concats.is_empty()
+            && resolver.concats.contains_key(&key.full_name());
+        for (index, node) in nodes.iter_mut().enumerate() {
+            let ResourceValue::String(text) = &mut node.value else {
+                continue;
+            };
+            if !text.contains('@')
+                && node.meta.transforms.is_empty()
+                && !(is_concat && index == 0)
+            {
+                continue;
+            }
+            // Later duplicates are expanded but never referenced
+            let result = if index == 0 {
+                resolver.resolve(&key.full_name())
+            } else {
+                let transforms = &node.meta.transforms;
+                resolver
+                    .expand(text)
+                    .map(|value| apply_chain(transforms, &value))
+            };
+            match result {
+                Ok(value) => *text = value,
+                Err(error) => invalid.push(InvalidResource {
+                    key: key.clone(),
+                    file: node.origin.file.to_path_buf(),
+                    line: None,
+                    message: error.to_string(),
+                    reference: match error {
+                        ResolveError::Missing(path) => Some(path),
+                        ResolveError::Cycle(_) => None,
+                    },
+                }),
+            }
+        }
+    }
+    invalid.into_iter().for_each(|i| graph.record_invalid(i));
+    for (from, to) in resolver.references {
+        let from = ResourceKey::from_path(&from);
+        graph.record_reference(from, ResourceKey::from_path(&to));
+    }
+}
+
+/// Why a value could not be expanded
+enum ResolveError {
+    /// `@string/` path naming no string
+    Missing(String),
+    /// Names of the cycle, starting and ending with the same one
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Missing(path) => {
+                write!(f, "`{path}` does not name a string")
+            }
+            Self::Cycle(names) => {
+                write!(f, "reference cycle {}", names.join(" → "))
+            }
+        }
+    }
+}
+
+struct Resolver {
+    values: BTreeMap<String, StringValue>,
+    concats: Concats,
+    resolved: BTreeMap<String, String>,
+    stack: Vec<String>,
+    /// (string being resolved, string it references)
+    references: BTreeSet<(String, String)>,
+}
+
+impl Resolver {
+    /// Records a reference of the string being resolved to `name`
+    fn note(&mut self, name: &str) {
+        if let Some(from) = self.stack.last() {
+            let reference = (from.clone(), name.to_string());
+            self.references.insert(reference);
+        }
+    }
+
+    /// Fully expanded and transformed value of the string `name`
+    fn resolve(
+        &mut self,
+        name: &str,
+    ) -> Result<String, ResolveError> {
+        if let Some(value) = self.resolved.get(name) {
+            return Ok(value.clone());
+        }
+        if let Some(start) = self.stack.iter().position(|n| n == name) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(ResolveError::Cycle(cycle));
+        }
+        let (raw, transforms) =
+            self.values.get(name).cloned().unwrap_or_default();
+        self.stack.push(name.to_string());
+        let result = match self.concats.get(name).cloned() {
+            Some(parts) => self.join(&parts),
+            None => self.expand(&raw),
+        };
+        self.stack.pop();
+        let value = apply_chain(&transforms, &result?);
+        self.resolved.insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Value of a `<concat>`: its literals verbatim and its references
+    /// expanded, in order
+    fn join(
+        &mut self,
+        parts: &[ConcatPart],
+    ) -> Result<String, ResolveError> {
+        let mut out = String::new();
+        for part in parts {
+            match part {
+                ConcatPart::Lit(text) => out.push_str(text),
+                ConcatPart::Ref(name) if self.values.contains_key(name) => {
+                    self.note(name);
+                    out.push_str(&self.resolve(name)?);
+                }
+                ConcatPart::Ref(name) => {
+                    return Err(ResolveError::Missing(format!(
+                        "{PREFIX}{name}"
+                    )))
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Replaces every reference in `text` by its expanded value and
+    /// every escaped `@` by a plain one
+    fn expand(
+        &mut self,
+        text: &str,
+    ) -> Result<String, ResolveError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(at) = rest.find(['@', '\\']) {
+            out.push_str(&rest[..at]);
+            rest = &rest[at..];
+            if rest.starts_with("@@") || rest.starts_with("\\@") {
+                out.push('@');
+                rest = &rest[2..];
+                continue;
+            }
+            let Some(after) = rest.strip_prefix(PREFIX) else {
+                // A lone `@` or `\`
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+                continue;
+            };
+            let path = reference_path(after);
+            if path.is_empty() {
+                out.push_str(PREFIX);
+                rest = after;
+                continue;
+            }
+            let parts: Vec<&str> = path.split('/').collect();
+            let name = (1..=parts.len())
+                .rev()
+                .map(|len| parts[..len].join("/"))
+                .find(|name| self.values.contains_key(name))
+                .ok_or_else(|| {
+                    ResolveError::Missing(format!("{PREFIX}{path}"))
+                })?;
+            self.note(&name);
+            out.push_str(&self.resolve(&name)?);
+            rest = &after[name.len()..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// Longest `name(/name)*` at the start of `text`; `-` and `.` only
+/// count when followed by another name character, so a sentence's
+/// final dot is not part of the path
+fn reference_path(text: &str) -> &str {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let is_name = |c: char| c.is_alphanumeric() || c == '_';
+    let mut end = 0;
+    for (i, &(offset, c)) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let continues = match c {
+            c if is_name(c) => true,
+            '-' | '.' => next.is_some_and(is_name),
+            '/' => end > 0 && next.is_some_and(is_name),
+            _ => false,
+        };
+        if !continues {
+            break;
+        }
+        end = offset + c.len_utf8();
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::{
+        ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    };
+    use std::path::PathBuf;
+
+    fn string(graph: &mut ResourceGraph, path: &str, text: &str) {
+        graph.insert(
+            ResourceKey::from_path(path),
+            ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String(text.to_string()),
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            ),
+        );
+    }
+
+    fn value(graph: &ResourceGraph, path: &str) -> String {
+        match &graph.get(&ResourceKey::from_path(path)).unwrap().value {
+            ResourceValue::String(text) => text.clone(),
+            other => panic!("expected a string, got {other:?}"),
+        }
+    }
+
+    fn errors(graph: &ResourceGraph) -> Vec<String> {
+        graph
+            .invalid_resources()
+            .iter()
+            .map(|i| format!("{}: {}", i.key.full_name(), i.message))
+            .collect()
+    }
+
+    #[test]
+    fn references_are_expanded_recursively() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "welcome", "Welcome to @string/app/title!");
+        string(&mut graph, "app/title", "@string/app/name Pro");
+        string(&mut graph, "app/name", "Demo");
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(value(&graph, "welcome"), "Welcome to Demo Pro!");
+        assert_eq!(value(&graph, "app/title"), "Demo Pro");
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn longest_existing_name_wins() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "base_url", "https://api.example.com");
+        string(&mut graph, "api_version", "v2");
+        string(
+            &mut graph,
+            "endpoint",
+            "@string/base_url/@string/api_version/users.",
+        );
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(
+            value(&graph, "endpoint"),
+            "https://api.example.com/v2/users."
+        );
+    }
+
+    #[test]
+    fn missing_names_and_cycles_are_errors() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "a", "@string/b");
+        string(&mut graph, "b", "x @string/a");
+        string(&mut graph, "c", "Hi @string/nope.");
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(
+            errors(&graph),
+            vec![
+                "a: reference cycle a → b → a",
+                "b: reference cycle b → a → b",
+                "c: `@string/nope` does not name a string",
+            ]
+        );
+    }
+
+    #[test]
+    fn transforms_apply_to_the_expanded_value() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "app_name", "  Café Crème!  ");
+        string(&mut graph, "app_slug", "@string/app_name");
+        string(&mut graph, "title", "@string/app_name");
+        string(
+            &mut graph,
+            "url",
+            "https://example.com/@string/app_slug",
+        );
+        for (path, chain) in
+            [("app_slug", "slug"), ("title", "trim,upper")]
+        {
+            let key = ResourceKey::from_path(path);
+            graph.get_mut(&key).unwrap().meta.transforms =
+                Transform::parse_chain(chain).unwrap();
+        }
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(value(&graph, "app_slug"), "café-crème");
+        assert_eq!(value(&graph, "title"), "CAFÉ CRÈME!");
+        assert_eq!(
+            value(&graph, "url"),
+            "https://example.com/café-crème"
+        );
+    }
+
+    #[test]
+    fn escaped_at_signs_are_literal() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "app_name", "Demo");
+        string(&mut graph, "email", "alice@example.com");
+        string(&mut graph, "path", "user@@domain/path");
+        string(&mut graph, "handle", "Follow us on @@rresources");
+        string(
+            &mut graph,
+            "mixed",
+            r"@string/app_name is at \@string/app_name",
+        );
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(value(&graph, "email"), "alice@example.com");
+        assert_eq!(value(&graph, "path"), "user@domain/path");
+        assert_eq!(value(&graph, "handle"), "Follow us on @rresources");
+        assert_eq!(
+            value(&graph, "mixed"),
+            "Demo is at @string/app_name"
+        );
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn malformed_references_suggest_the_syntax() {
+        for (text, message) in [
+            ("@strig/app_name", "`@strig/app_name` is not a reference; did you mean `@string/app_name`? (write `@@` for a literal `@`)"),
+            ("@string:app_name rocks", "`@string:app_name` is not a reference; did you mean `@string/app_name`? (write `@@` for a literal `@`)"),
+            ("@String/auth/title", "`@String/auth/title` is not a reference; did you mean `@string/auth/title`? (write `@@` for a literal `@`)"),
+            ("@color/primary", "`@color/primary` is not a reference; only strings can be referenced, as `@string/...` (write `@@` for a literal `@`)"),
+            ("@rresources", "`@rresources` is not a reference (write `@@` for a literal `@`)"),
+            ("@string/", "`@string/` is not a reference (write `@@` for a literal `@`)"),
+        ] {
+            assert_eq!(
+                malformed_reference(text).as_deref(),
+                Some(message)
+            );
+        }
+        for text in [
+            "@string/app_name",
+            "@@rresources",
+            r"\@rresources",
+            "Follow @rresources",
+            "alice@example.com",
+        ] {
+            assert_eq!(malformed_reference(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn malformed_references_are_recorded_before_expansion() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "app_name", "Demo");
+        string(&mut graph, "title", "@strig/app_name");
+        string(&mut graph, "handle", "@@rresources");
+        string(&mut graph, "subtitle", "@string/app_name");
+        resolve_references(&mut graph, Vec::new());
+
+        let names: Vec<String> = graph
+            .malformed_references()
+            .iter()
+            .map(|(key, _)| key.full_name())
+            .collect();
+        assert_eq!(names, ["title"]);
+        assert_eq!(value(&graph, "handle"), "@rresources");
+    }
+
+    type Concat = (bool, Option<String>, ResourceKey, Vec<ConcatPart>);
+
+    fn concat(path: &str, parts: &[(&str, &str)]) -> Concat {
+        let parts = parts
+            .iter()
+            .map(|(kind, text)| match *kind {
+                "ref" => ConcatPart::Ref(text.to_string()),
+                _ => ConcatPart::Lit(text.to_string()),
+            })
+            .collect();
+        (false, None, ResourceKey::from_path(path), parts)
+    }
+
+    #[test]
+    fn concats_join_their_parts() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "base_url", "https://api.example.com");
+        string(&mut graph, "base_url/v2", "never used");
+        string(&mut graph, "full_url", "");
+        string(&mut graph, "link", "See @string/full_url.");
+        let concats = vec![concat(
+            "full_url",
+            &[("ref", "base_url"), ("lit", "/v2/users")],
+        )];
+        resolve_references(&mut graph, concats);
+
+        assert_eq!(
+            value(&graph, "full_url"),
+            "https://api.example.com/v2/users"
+        );
+        assert_eq!(
+            value(&graph, "link"),
+            "See https://api.example.com/v2/users."
+        );
+        assert!(errors(&graph).is_empty());
+    }
+
+    #[test]
+    fn concat_references_are_checked() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "a", "");
+        string(&mut graph, "b", "x @string/a");
+        string(&mut graph, "c", "");
+        let concats = vec![
+            concat("a", &[("lit", "<"), ("ref", "b")]),
+            concat("c", &[("ref", "nope")]),
+        ];
+        resolve_references(&mut graph, concats);
+
+        assert_eq!(
+            errors(&graph),
+            vec![
+                "a: reference cycle a → b → a",
+                "b: reference cycle b → a → b",
+                "c: `@string/nope` does not name a string",
+            ]
+        );
+    }
+
+    #[test]
+    fn translations_see_default_concats() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "host", "example.com");
+        string(&mut graph, "url", "");
+        let fr = graph.translation_mut("fr");
+        string(fr, "host", "example.fr");
+        string(fr, "help", "Aide : @string/url");
+        let concats =
+            vec![concat("url", &[("lit", "https://"), ("ref", "host")])];
+        resolve_references(&mut graph, concats);
+
+        assert_eq!(value(&graph, "url"), "https://example.com");
+        assert_eq!(
+            value(&graph.translations()["fr"], "help"),
+            "Aide : https://example.fr"
+        );
+    }
+
+    #[test]
+    fn translations_fall_back_to_default_strings() {
+        let mut graph = ResourceGraph::default();
+        string(&mut graph, "app_name", "Demo");
+        string(&mut graph, "welcome", "Welcome to @string/app_name");
+        let fr = graph.translation_mut("fr");
+        string(fr, "welcome", "Bienvenue dans @string/app_name");
+        resolve_references(&mut graph, Vec::new());
+
+        assert_eq!(
+            value(&graph.translations()["fr"], "welcome"),
+            "Bienvenue dans Demo"
+        );
+    }
+}
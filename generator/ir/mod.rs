@@ -4,17 +4,29 @@
 //! before analysis and code generation. The `ResourceGraph` is a unified representation
 //! that stores all resources with their metadata (origin, profile, namespace).
 
+pub mod assertion;
 mod builder;
+mod computed;
+mod intern;
+mod interpolation;
 mod model;
+mod pattern;
+pub mod profile;
+pub mod pseudo;
+mod transform;
 pub mod types;
 
 pub use builder::ResourceGraphBuilder;
 pub use model::{
-    ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
-    ResourceOrigin, ResourceValue,
+    NodeMeta, Ordering, ProfileVariant, ResourceGraph, ResourceKey,
+    ResourceKind, ResourceNode, ResourceOrigin, ResourceValue,
 };
+pub use pattern::{Pattern, PatternError};
 pub use types::TypeRegistry;
 
 // Re-export commonly used types from model (for advanced usage)
 #[allow(unused_imports)] // Public API, may be used by consumers
-pub use model::{NumberType, NumberValue};
+pub use model::{
+    NumberRange, NumberType, NumberValue, TemplateParam,
+    TemplateParamValue,
+};
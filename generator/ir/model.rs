@@ -1,9 +1,48 @@
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::assertion::Assertion;
+use super::transform::Transform;
 
 #[derive(Debug, Default)]
 pub struct ResourceGraph {
     nodes: BTreeMap<ResourceKey, Vec<ResourceNode>>, // Multiple nodes per key to track duplicates
+    profile_variants: BTreeMap<ResourceKey, Vec<ProfileVariant>>, // Every definition, active or not
+    translations: BTreeMap<String, ResourceGraph>, // Locale-qualified resources (`values-fr.xml`)
+    untranslatable: BTreeSet<ResourceKey>, // Keys marked `translatable="false"`
+    kept: BTreeSet<ResourceKey>, // Keys marked `keep="true"`
+    overridable: BTreeSet<ResourceKey>, // Keys marked `overridable="true"`
+    invalid: Vec<InvalidResource>, // Definitions rejected by their type
+    namespace_docs: BTreeMap<Vec<String>, String>, // `<doc>` of documented namespaces
+    config_namespaces: BTreeSet<Vec<String>>, // Namespaces marked `struct="true"`
+    namespace_origins: BTreeMap<Vec<String>, (Arc<Path>, usize)>, // First `<ns>` of each namespace (file, line)
+    unused_docs: Vec<(PathBuf, usize)>, // `<doc>` elements nothing followed (file, line)
+    malformed_references: Vec<(ResourceKey, String)>, // Strings starting with a broken reference, and why
+    references: BTreeSet<(ResourceKey, ResourceKey)>, // Resolved `@string/`/`@number/` references (from, to)
+    tests: Option<Box<ResourceGraph>>, // Resources of `res/tests/`, emitted as `r_tests::`
+}
+
+/// Profile attributes of one definition of a key, whether or not it is
+/// active for the selected profile
+#[derive(Debug, Clone)]
+pub struct ProfileVariant {
+    pub specs: Vec<String>,
+    pub file: Arc<Path>,
+    /// 1-based line of the definition, when known
+    pub line: Option<usize>,
+}
+
+/// Definition whose value its type rejected (e.g. `-1` for a `u32`)
+#[derive(Debug, Clone)]
+pub struct InvalidResource {
+    pub key: ResourceKey,
+    pub file: PathBuf,
+    /// 1-based line of the definition, when known
+    pub line: Option<usize>,
+    pub message: String,
+    /// `@string/` path naming no string, when that is the problem
+    pub reference: Option<String>,
 }
 
 impl ResourceGraph {
@@ -22,12 +61,28 @@ impl ResourceGraph {
         &self.nodes
     }
 
+    /// Mutable access to the nodes, for in-place transforms
+    pub fn nodes_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&ResourceKey, &mut Vec<ResourceNode>)>
+    {
+        self.nodes.iter_mut()
+    }
+
     /// Get the first (primary) node for a key
     #[allow(dead_code)] // Used in tests
     pub fn get(&self, key: &ResourceKey) -> Option<&ResourceNode> {
         self.nodes.get(key).and_then(|nodes| nodes.first())
     }
 
+    /// Primary node of a key, mutable
+    pub fn get_mut(
+        &mut self,
+        key: &ResourceKey,
+    ) -> Option<&mut ResourceNode> {
+        self.nodes.get_mut(key).and_then(|nodes| nodes.first_mut())
+    }
+
     /// Get all nodes for a key (including duplicates)
     pub fn get_all(
         &self,
@@ -36,17 +91,290 @@ impl ResourceGraph {
         self.nodes.get(key).map(|v| v.as_slice())
     }
 
-    /// Check if a key has duplicates
-    #[allow(dead_code)] // Reserved for future use
+    /// Records the profile attributes of a definition
+    pub fn record_profile_variant(
+        &mut self,
+        key: ResourceKey,
+        variant: ProfileVariant,
+    ) {
+        self.profile_variants.entry(key).or_default().push(variant);
+    }
+
+    /// Profile attributes of every definition, including those filtered out
+    pub fn profile_variants(
+        &self,
+    ) -> &BTreeMap<ResourceKey, Vec<ProfileVariant>> {
+        &self.profile_variants
+    }
+
+    /// Records a definition whose value could not be converted
+    pub fn record_invalid(&mut self, invalid: InvalidResource) {
+        self.invalid.push(invalid);
+    }
+
+    /// Definitions rejected by their type, in file order
+    pub fn invalid_resources(&self) -> &[InvalidResource] {
+        &self.invalid
+    }
+
+    /// Sets the module doc of `namespace`; the first file documenting it
+    /// wins, except for the root module (the empty path), which joins
+    /// the docs of every file as paragraphs
+    pub fn set_namespace_doc(&mut self, namespace: Vec<String>, doc: String) {
+        if !namespace.is_empty() {
+            self.namespace_docs.entry(namespace).or_insert(doc);
+            return;
+        }
+        match self.namespace_docs.get_mut(&namespace) {
+            Some(docs) => {
+                docs.push_str("\n\n");
+                docs.push_str(&doc);
+            }
+            None => {
+                self.namespace_docs.insert(namespace, doc);
+            }
+        }
+    }
+
+    /// Module docs keyed by namespace path
+    pub fn namespace_docs(&self) -> &BTreeMap<Vec<String>, String> {
+        &self.namespace_docs
+    }
+
+    /// Groups the resources of `namespace` into a config struct
+    pub fn mark_config_namespace(&mut self, namespace: Vec<String>) {
+        self.config_namespaces.insert(namespace);
+    }
+
+    /// Namespaces marked `struct="true"`
+    pub fn config_namespaces(&self) -> &BTreeSet<Vec<String>> {
+        &self.config_namespaces
+    }
+
+    /// Records the `<ns>` element opening `namespace` at `line`,
+    /// keeping the first one
+    pub fn record_namespace_origin(
+        &mut self,
+        namespace: Vec<String>,
+        file: &Arc<Path>,
+        line: usize,
+    ) {
+        self.namespace_origins
+            .entry(namespace)
+            .or_insert_with(|| (Arc::clone(file), line));
+    }
+
+    /// File and line of the first `<ns>` element of `namespace`,
+    /// `None` for namespaces of a file name
+    pub fn namespace_origin(
+        &self,
+        namespace: &[String],
+    ) -> Option<&(Arc<Path>, usize)> {
+        self.namespace_origins.get(namespace)
+    }
+
+    /// Records a `<doc>` that no resource or namespace followed
+    pub fn record_unused_doc(&mut self, file: PathBuf, line: usize) {
+        self.unused_docs.push((file, line));
+    }
+
+    /// `<doc>` elements that were ignored, with their file and line
+    pub fn unused_docs(&self) -> &[(PathBuf, usize)] {
+        &self.unused_docs
+    }
+
+    /// Records a string starting with an `@` that is neither a
+    /// reference nor escaped
+    pub fn record_malformed_reference(
+        &mut self,
+        key: ResourceKey,
+        message: String,
+    ) {
+        self.malformed_references.push((key, message));
+    }
+
+    /// Records that a value of `from` was resolved from `to`
+    pub fn record_reference(
+        &mut self,
+        from: ResourceKey,
+        to: ResourceKey,
+    ) {
+        self.references.insert((from, to));
+    }
+
+    /// Resolved references, as (referring resource, referenced one)
+    pub fn references(
+        &self,
+    ) -> &BTreeSet<(ResourceKey, ResourceKey)> {
+        &self.references
+    }
+
+    /// Strings that look like a mistyped reference, with the fix
+    pub fn malformed_references(&self) -> &[(ResourceKey, String)] {
+        &self.malformed_references
+    }
+
+    /// Graph holding the resources of `locale`, created on first use
+    pub fn translation_mut(&mut self, locale: &str) -> &mut ResourceGraph {
+        self.translations.entry(locale.to_string()).or_default()
+    }
+
+    /// Per-locale graphs, keyed by locale code
+    pub fn translations(&self) -> &BTreeMap<String, ResourceGraph> {
+        &self.translations
+    }
+
+    /// Mutable access to the per-locale graphs
+    pub fn translations_mut(
+        &mut self,
+    ) -> impl Iterator<Item = &mut ResourceGraph> {
+        self.translations.values_mut()
+    }
+
+    /// Graph holding the test-only resources, created on first use
+    pub fn tests_mut(&mut self) -> &mut ResourceGraph {
+        self.tests.get_or_insert_with(Box::default)
+    }
+
+    /// Test-only resources, if any test file was loaded
+    pub fn tests(&self) -> Option<&ResourceGraph> {
+        self.tests.as_deref()
+    }
+
+    /// Marks a key as `translatable="false"`
+    pub fn mark_untranslatable(&mut self, key: ResourceKey) {
+        self.untranslatable.insert(key);
+    }
+
+    /// Returns false for keys marked `translatable="false"`
+    pub fn is_translatable(&self, key: &ResourceKey) -> bool {
+        !self.untranslatable.contains(key)
+    }
+
+    /// Marks a key as `keep="true"`
+    pub fn mark_kept(&mut self, key: ResourceKey) {
+        self.kept.insert(key);
+    }
+
+    /// Returns true for keys marked `keep="true"`
+    pub fn is_kept(&self, key: &ResourceKey) -> bool {
+        self.kept.contains(key)
+    }
+
+    /// Marks a key as `overridable="true"`
+    pub fn mark_overridable(&mut self, key: ResourceKey) {
+        self.overridable.insert(key);
+    }
+
+    /// Returns true for keys marked `overridable="true"`
+    pub fn is_overridable(&self, key: &ResourceKey) -> bool {
+        self.overridable.contains(key)
+    }
+
+    /// Check if a key has duplicates; variants that each have their
+    /// own `cfg` are not duplicates, rustc keeps the one that holds
     pub fn has_duplicates(&self, key: &ResourceKey) -> bool {
         self.nodes.get(key).is_some_and(|nodes| nodes.len() > 1)
+            && !self.has_cfg_variants(key)
+    }
+
+    /// Returns true when `key` is defined several times, each time
+    /// with a `cfg` predicate of its own
+    pub fn has_cfg_variants(&self, key: &ResourceKey) -> bool {
+        let Some(nodes) = self.nodes.get(key) else {
+            return false;
+        };
+        nodes.len() > 1
+            && nodes.iter().enumerate().all(|(index, node)| {
+                let earlier = &nodes[..index];
+                node.meta.cfg.is_some()
+                    && earlier
+                        .iter()
+                        .all(|n| n.meta.cfg != node.meta.cfg)
+            })
+    }
+
+    /// Nodes of `key` the generated code defines items for: every
+    /// `cfg` variant, or the primary node
+    pub fn emitted_nodes(
+        &self,
+        key: &ResourceKey,
+    ) -> &[ResourceNode] {
+        match self.get_all(key) {
+            Some(nodes) if self.has_cfg_variants(key) => nodes,
+            Some(nodes) => &nodes[..nodes.len().min(1)],
+            None => &[],
+        }
+    }
+
+    /// `cfg` predicate under which the items of `key` exist:
+    /// `any(...)` of the predicates of its variants, `None` when they
+    /// always exist
+    pub fn cfg_predicate(&self, key: &ResourceKey) -> Option<String> {
+        let nodes = self.emitted_nodes(key);
+        match nodes {
+            [node] => node.meta.cfg.clone(),
+            [] => None,
+            _ => {
+                let predicates: Vec<&str> = nodes
+                    .iter()
+                    .filter_map(|node| node.meta.cfg.as_deref())
+                    .collect();
+                Some(format!("any({})", predicates.join(", ")))
+            }
+        }
+    }
+
+    /// Returns true when the items of `key` only exist under a `cfg`
+    /// predicate, leaving it out of the tables naming every resource
+    pub fn is_conditional(&self, key: &ResourceKey) -> bool {
+        self.emitted_nodes(key)
+            .iter()
+            .any(|node| node.meta.cfg.is_some())
+    }
+
+    /// Position of the primary definition of `key` among the parsed
+    /// resources, last for unknown keys
+    pub fn source_index(&self, key: &ResourceKey) -> usize {
+        self.nodes
+            .get(key)
+            .and_then(|nodes| nodes.first())
+            .map_or(usize::MAX, |node| node.origin.index)
+    }
+
+    /// Primary node of every key, in `ordering`
+    pub fn ordered_nodes(
+        &self,
+        ordering: Ordering,
+    ) -> Vec<(&ResourceKey, &ResourceNode)> {
+        let mut nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .filter_map(|(key, nodes)| Some((key, nodes.first()?)))
+            .collect();
+        if ordering == Ordering::Source {
+            nodes.sort_by_key(|(_, node)| node.origin.index);
+        }
+        nodes
     }
 }
 
+/// Order of the resources in the generated code and the Markdown
+/// catalog
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ordering {
+    /// By name
+    #[default]
+    Alphabetical,
+    /// In declaration order, the files being read in path order
+    Source,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ResourceKey {
-    pub namespace: Vec<String>,
-    pub name: String,
+    /// Shared by the keys of the namespace in a built graph
+    pub namespace: Arc<[String]>,
+    pub name: Arc<str>,
 }
 
 impl ResourceKey {
@@ -56,8 +384,8 @@ impl ResourceKey {
         name: impl Into<String>,
     ) -> Self {
         Self {
-            namespace,
-            name: name.into(),
+            namespace: namespace.into(),
+            name: name.into().into(),
         }
     }
 
@@ -69,14 +397,14 @@ impl ResourceKey {
             .collect();
         let name = parts.pop().unwrap_or_default();
         Self {
-            namespace: parts,
-            name,
+            namespace: parts.into(),
+            name: name.into(),
         }
     }
 
     pub fn full_name(&self) -> String {
         if self.namespace.is_empty() {
-            return self.name.clone();
+            return self.name.to_string();
         }
         format!("{}/{}", self.namespace.join("/"), self.name)
     }
@@ -87,6 +415,52 @@ pub struct ResourceNode {
     pub kind: ResourceKind,
     pub value: ResourceValue,
     pub origin: ResourceOrigin,
+    /// Attributes of the definition besides its value
+    pub meta: NodeMeta,
+}
+
+impl ResourceNode {
+    /// Node of a definition without attributes
+    pub fn new(
+        kind: ResourceKind,
+        value: ResourceValue,
+        origin: ResourceOrigin,
+    ) -> Self {
+        Self {
+            kind,
+            value,
+            origin,
+            meta: NodeMeta::default(),
+        }
+    }
+}
+
+/// Attributes of a [`ResourceNode`]; the builder copies the ones
+/// every type shares (`deprecated`, `doc`, `assert`, `cfg`) from the
+/// parsed resource
+#[derive(Debug, Clone, Default)]
+pub struct NodeMeta {
+    /// Note of a `deprecated="..."` attribute
+    pub deprecated: Option<String>,
+    /// Text of the `<doc>` elements right before the definition
+    pub doc: Option<String>,
+    /// `min`/`max` attributes of a number
+    pub range: Option<NumberRange>,
+    /// `pattern` attribute of a string, checked once references are
+    /// resolved
+    pub pattern: Option<String>,
+    /// `transform` chain of a string, applied once references are
+    /// resolved
+    pub transforms: Vec<Transform>,
+    /// `assert` checks, on the resolved value and in the generated
+    /// code
+    pub asserts: Vec<Assertion>,
+    /// Integer types a typed integer is also generated as (`usize`),
+    /// each one lossless
+    pub widen: Vec<&'static str>,
+    /// `cfg` predicate the items of the resource are compiled under,
+    /// emitted verbatim
+    pub cfg: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -100,11 +474,23 @@ pub enum ResourceKind {
     Dimension,
     Array(String),
     Template,
+    LatLng,
+    Position,
+    Semver,
+    IpAddr,
+    SocketAddr,
+    Regex,
+    Uuid,
+    DateTime,
+    Date,
+    Money,
+    Char,
+    Bytes,
     Custom(String),
 }
 
 /// Represents the explicit Rust type requested via `<number type="...">`
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NumberType {
     I8,
     I16,
@@ -114,6 +500,10 @@ pub enum NumberType {
     U16,
     U32,
     U64,
+    /// Checked against `BuildOptions::target_pointer_width`
+    Isize,
+    /// Checked against `BuildOptions::target_pointer_width`
+    Usize,
     F32,
     F64,
 }
@@ -130,10 +520,18 @@ impl NumberType {
             Self::U16 => "u16",
             Self::U32 => "u32",
             Self::U64 => "u64",
+            Self::Isize => "isize",
+            Self::Usize => "usize",
             Self::F32 => "f32",
             Self::F64 => "f64",
         }
     }
+
+    /// `true` for `f32` and `f64`
+    #[must_use]
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
 }
 
 /// Represents a parsed numeric value
@@ -149,6 +547,13 @@ pub enum NumberValue {
     Typed { literal: String, ty: NumberType },
 }
 
+/// Bounds of a number, parsed with the type of its value
+#[derive(Debug, Clone, Default)]
+pub struct NumberRange {
+    pub min: Option<NumberValue>,
+    pub max: Option<NumberValue>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ResourceValue {
     String(String),
@@ -159,6 +564,44 @@ pub enum ResourceValue {
         text: String,
         params: Vec<TemplateParam>,
     },
+    /// Degrees, range-checked
+    LatLng { lat: f64, lng: f64 },
+    Position { x: f64, y: f64 },
+    /// Semantic version, with the text as written (build metadata
+    /// included)
+    Semver {
+        major: u64,
+        minor: u64,
+        patch: u64,
+        pre: String,
+        text: String,
+    },
+    IpAddr(std::net::IpAddr),
+    SocketAddr(std::net::SocketAddr),
+    /// Source of a `<regex>`, as written
+    Regex(String),
+    Uuid(u128),
+    /// UTC seconds since the Unix epoch, with the text as written
+    DateTime {
+        unix: i64,
+        text: String,
+    },
+    Date {
+        year: u16,
+        month: u8,
+        day: u8,
+    },
+    /// Amount in units of `10^-scale` of an ISO 4217 `currency`
+    Money {
+        minor_units: i64,
+        scale: u8,
+        currency: String,
+    },
+    Char(char),
+    /// Scalar values of a `<char grapheme="true">`, as a string
+    Grapheme(String),
+    /// Decoded `<bytes>`
+    Bytes(Vec<u8>),
     // TODO: add arrays, references, etc.
 }
 
@@ -179,19 +622,23 @@ pub enum TemplateParamValue {
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Fields reserved for future use (line, profile, is_test)
 pub struct ResourceOrigin {
-    pub file: PathBuf,
+    /// Shared by every resource of the file
+    pub file: Arc<Path>,
     pub line: Option<u32>,
     pub profile: Option<String>,
     pub is_test: bool,
+    /// Position among the definitions of the build, in file order
+    pub index: usize,
 }
 
 impl ResourceOrigin {
-    pub fn new(file: PathBuf, is_test: bool) -> Self {
+    pub fn new(file: impl Into<Arc<Path>>, is_test: bool) -> Self {
         Self {
-            file,
+            file: file.into(),
             line: None,
             profile: None,
             is_test,
+            index: 0,
         }
     }
 }
@@ -211,11 +658,11 @@ mod tests {
     fn graph_insert_single_node() {
         let mut graph = ResourceGraph::default();
         let key = ResourceKey::new(vec![], "test");
-        let node = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("value".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+        let node = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("value".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let is_duplicate = graph.insert(key.clone(), node);
         assert!(!is_duplicate);
@@ -227,16 +674,16 @@ mod tests {
     fn graph_insert_detects_duplicate() {
         let mut graph = ResourceGraph::default();
         let key = ResourceKey::new(vec![], "test");
-        let node1 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("first".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
-        };
-        let node2 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("second".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
-        };
+        let node1 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("first".to_string()),
+            ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+        );
+        let node2 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("second".to_string()),
+            ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+        );
 
         let is_dup1 = graph.insert(key.clone(), node1);
         assert!(!is_dup1);
@@ -250,16 +697,16 @@ mod tests {
     fn graph_get_returns_first_node() {
         let mut graph = ResourceGraph::default();
         let key = ResourceKey::new(vec![], "test");
-        let node1 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("first".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
-        };
-        let node2 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("second".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
-        };
+        let node1 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("first".to_string()),
+            ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+        );
+        let node2 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("second".to_string()),
+            ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+        );
 
         graph.insert(key.clone(), node1);
         graph.insert(key.clone(), node2);
@@ -275,16 +722,16 @@ mod tests {
     fn graph_get_all_returns_all_nodes() {
         let mut graph = ResourceGraph::default();
         let key = ResourceKey::new(vec![], "test");
-        let node1 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("first".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
-        };
-        let node2 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("second".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
-        };
+        let node1 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("first".to_string()),
+            ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+        );
+        let node2 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("second".to_string()),
+            ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+        );
 
         graph.insert(key.clone(), node1);
         graph.insert(key.clone(), node2);
@@ -305,16 +752,16 @@ mod tests {
     fn graph_has_duplicates() {
         let mut graph = ResourceGraph::default();
         let key = ResourceKey::new(vec![], "test");
-        let node1 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("first".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test1.xml"), false),
-        };
-        let node2 = ResourceNode {
-            kind: ResourceKind::String,
-            value: ResourceValue::String("second".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test2.xml"), false),
-        };
+        let node1 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("first".to_string()),
+            ResourceOrigin::new(PathBuf::from("test1.xml"), false),
+        );
+        let node2 = ResourceNode::new(
+            ResourceKind::String,
+            ResourceValue::String("second".to_string()),
+            ResourceOrigin::new(PathBuf::from("test2.xml"), false),
+        );
 
         graph.insert(key.clone(), node1);
         assert!(!graph.has_duplicates(&key));
@@ -323,6 +770,48 @@ mod tests {
         assert!(graph.has_duplicates(&key));
     }
 
+    #[test]
+    fn cfg_variants_are_not_duplicates() {
+        let node = |text: &str, cfg: Option<&str>| ResourceNode {
+            meta: NodeMeta {
+                cfg: cfg.map(str::to_string),
+                ..NodeMeta::default()
+            },
+            ..ResourceNode::new(
+                ResourceKind::String,
+                ResourceValue::String(text.to_string()),
+                ResourceOrigin::new(
+                    PathBuf::from("values.xml"),
+                    false,
+                ),
+            )
+        };
+        let mut graph = ResourceGraph::default();
+        let key = ResourceKey::new(vec![], "driver");
+        graph.insert(key.clone(), node("alsa", Some("unix")));
+        assert!(graph.is_conditional(&key));
+        let predicate = graph.cfg_predicate(&key);
+        assert_eq!(predicate.as_deref(), Some("unix"));
+
+        graph.insert(key.clone(), node("wasapi", Some("not(unix)")));
+        assert!(!graph.has_duplicates(&key));
+        assert_eq!(graph.emitted_nodes(&key).len(), 2);
+        assert_eq!(
+            graph.cfg_predicate(&key).as_deref(),
+            Some("any(unix, not(unix))")
+        );
+
+        // The same predicate twice, or a definition without one
+        graph.insert(key.clone(), node("oss", Some("unix")));
+        assert!(graph.has_duplicates(&key));
+        assert_eq!(graph.emitted_nodes(&key).len(), 1);
+        let other = ResourceKey::new(vec![], "title");
+        graph.insert(other.clone(), node("a", Some("unix")));
+        graph.insert(other.clone(), node("b", None));
+        assert!(graph.has_duplicates(&other));
+        assert!(graph.is_conditional(&other));
+    }
+
     #[test]
     fn graph_get_nonexistent_key() {
         let graph = ResourceGraph::default();
@@ -341,57 +830,57 @@ mod tests {
     #[test]
     fn resource_key_new() {
         let key = ResourceKey::new(vec!["ns1".to_string(), "ns2".to_string()], "name");
-        assert_eq!(key.namespace, vec!["ns1", "ns2"]);
-        assert_eq!(key.name, "name");
+        assert_eq!(*key.namespace, ["ns1", "ns2"]);
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_new_with_string() {
         let key = ResourceKey::new(vec![], "name");
-        assert_eq!(key.namespace, Vec::<String>::new());
-        assert_eq!(key.name, "name");
+        assert!(key.namespace.is_empty());
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_from_path_simple() {
         let key = ResourceKey::from_path("name");
-        assert_eq!(key.namespace, Vec::<String>::new());
-        assert_eq!(key.name, "name");
+        assert!(key.namespace.is_empty());
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_from_path_with_namespace() {
         let key = ResourceKey::from_path("ns1/ns2/name");
-        assert_eq!(key.namespace, vec!["ns1", "ns2"]);
-        assert_eq!(key.name, "name");
+        assert_eq!(*key.namespace, ["ns1", "ns2"]);
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_from_path_single_namespace() {
         let key = ResourceKey::from_path("ns/name");
-        assert_eq!(key.namespace, vec!["ns"]);
-        assert_eq!(key.name, "name");
+        assert_eq!(*key.namespace, ["ns"]);
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_from_path_with_empty_parts() {
         let key = ResourceKey::from_path("//ns1///name//");
-        assert_eq!(key.namespace, vec!["ns1"]);
-        assert_eq!(key.name, "name");
+        assert_eq!(*key.namespace, ["ns1"]);
+        assert_eq!(&*key.name, "name");
     }
 
     #[test]
     fn resource_key_from_path_empty() {
         let key = ResourceKey::from_path("");
-        assert_eq!(key.namespace, Vec::<String>::new());
-        assert_eq!(key.name, "");
+        assert!(key.namespace.is_empty());
+        assert_eq!(&*key.name, "");
     }
 
     #[test]
     fn resource_key_from_path_only_slashes() {
         let key = ResourceKey::from_path("///");
-        assert_eq!(key.namespace, Vec::<String>::new());
-        assert_eq!(key.name, "");
+        assert!(key.namespace.is_empty());
+        assert_eq!(&*key.name, "");
     }
 
     #[test]
@@ -474,6 +963,12 @@ mod tests {
         assert_eq!(NumberType::U64.as_str(), "u64");
     }
 
+    #[test]
+    fn number_type_as_str_pointer_sized() {
+        assert_eq!(NumberType::Isize.as_str(), "isize");
+        assert_eq!(NumberType::Usize.as_str(), "usize");
+    }
+
     #[test]
     fn number_type_as_str_f32() {
         assert_eq!(NumberType::F32.as_str(), "f32");
@@ -494,7 +989,7 @@ mod tests {
     #[test]
     fn resource_origin_new() {
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
-        assert_eq!(origin.file, PathBuf::from("test.xml"));
+        assert_eq!(*origin.file, *Path::new("test.xml"));
         assert_eq!(origin.line, None);
         assert_eq!(origin.profile, None);
         assert!(!origin.is_test);
@@ -580,9 +1075,9 @@ mod tests {
 
     #[test]
     fn number_value_float() {
-        let value = NumberValue::Float(3.14);
+        let value = NumberValue::Float(2.75);
         match value {
-            NumberValue::Float(f) => assert!((f - 3.14).abs() < 0.0001),
+            NumberValue::Float(f) => assert!((f - 2.75).abs() < 0.0001),
             _ => panic!("expected Float"),
         }
     }
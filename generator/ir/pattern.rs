@@ -0,0 +1,584 @@
+//! `pattern="..."` attributes of strings.
+//!
+//! ```xml
+//! <string name="default_region" pattern="[a-z]{2}-[a-z]+-\d">eu-west-1</string>
+//! ```
+//!
+//! The pattern must match the whole value, like the `pattern`
+//! attribute of an HTML `<input>`, and is checked once `@string/`
+//! references are resolved, so `@string/region` is checked as the text
+//! it stands for. Mismatches are recorded as invalid resources.
+//!
+//! The syntax is the common subset of regular expressions: literals,
+//! `.`, `[a-z]`/`[^...]` classes, `\d \w \s` (and `\D \W \S`),
+//! groups `(...)`/`(?:...)`, `|`, `^`/`$` and the `* + ? {n} {n,}
+//! {n,m}` quantifiers, lazy or not. Backreferences, lookaround and
+//! flags are rejected with an error instead of being misread; the
+//! syntax the `regex` crate has beyond the subset is told apart, for
+//! `<regex>` (see [`PatternError::Unsupported`]).
+
+use std::collections::BTreeSet;
+
+use super::model::{InvalidResource, ResourceGraph, ResourceValue};
+
+/// Escapes the `regex` crate knows and patterns do not (`\b`,
+/// `\p{L}`, `\x41`, ...)
+const REGEX_ESCAPES: &str = "abfvxuUpPBAz";
+
+/// Why a pattern was not compiled
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternError {
+    /// Not a regular expression, for the `regex` crate either
+    Invalid(String),
+    /// Valid for the `regex` crate, beyond the supported subset
+    /// (inline flags, named groups, `\b`, `\p{L}`)
+    Unsupported(String),
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Invalid(reason) | Self::Unsupported(reason) => {
+                write!(f, "{reason}")
+            }
+        }
+    }
+}
+
+impl From<String> for PatternError {
+    fn from(reason: String) -> Self {
+        Self::Invalid(reason)
+    }
+}
+
+impl From<&str> for PatternError {
+    fn from(reason: &str) -> Self {
+        Self::Invalid(reason.to_string())
+    }
+}
+
+/// Compiled `pattern` attribute
+#[derive(Debug)]
+pub struct Pattern {
+    alternatives: Vec<Vec<Node>>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Char(char),
+    /// `.`, any character but a newline
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Start,
+    End,
+    Group(Vec<Vec<Node>>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+#[derive(Debug)]
+enum ClassItem {
+    Range(char, char),
+    /// `\d`, `\w` or `\s`, negated for the uppercase forms
+    Shorthand(char),
+}
+
+impl Pattern {
+    /// Compiles `source`, with a reason when it is not a supported
+    /// regular expression
+    pub fn new(source: &str) -> Result<Self, PatternError> {
+        let mut parser = Parser {
+            chars: source.chars().collect(),
+            pos: 0,
+        };
+        let alternatives = parser.alternatives()?;
+        match parser.peek() {
+            None => Ok(Self { alternatives }),
+            Some(_) => Err("unmatched `)`".into()),
+        }
+    }
+
+    /// Whether the whole of `text` matches
+    pub fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        alternatives_ends(&self.alternatives, &text, 0)
+            .contains(&text.len())
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.pos += usize::from(c.is_some());
+        c
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        self.pos += usize::from(found);
+        found
+    }
+
+    /// `a|b|...` up to the end or a closing parenthesis
+    fn alternatives(
+        &mut self,
+    ) -> Result<Vec<Vec<Node>>, PatternError> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.eat('|') {
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<Node>, PatternError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            nodes.push(self.quantified(atom)?);
+        }
+        Ok(nodes)
+    }
+
+    fn atom(&mut self) -> Result<Node, PatternError> {
+        let Some(c) = self.next() else {
+            return Err("unexpected end of pattern".into());
+        };
+        Ok(match c {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '[' => self.class()?,
+            '(' => self.group()?,
+            '\\' => match self.escape()? {
+                ClassItem::Range(c, _) => Node::Char(c),
+                shorthand => Node::Class {
+                    items: vec![shorthand],
+                    negated: false,
+                },
+            },
+            '*' | '+' | '?' | '{' => {
+                let reason = format!("nothing to repeat before `{c}`");
+                return Err(reason.into());
+            }
+            c => Node::Char(c),
+        })
+    }
+
+    fn group(&mut self) -> Result<Node, PatternError> {
+        if self.eat('?') && !self.eat(':') {
+            let rest = &self.chars[self.pos..];
+            let lookaround = matches!(rest, ['=' | '!', ..])
+                || matches!(rest, ['<', '=' | '!', ..]);
+            return Err(if lookaround {
+                "lookaround is not supported".into()
+            } else {
+                PatternError::Unsupported(
+                    "inline flags and named groups are not supported"
+                        .to_string(),
+                )
+            });
+        }
+        let alternatives = self.alternatives()?;
+        if !self.eat(')') {
+            return Err("unclosed group".into());
+        }
+        Ok(Node::Group(alternatives))
+    }
+
+    /// Character class after its `[`
+    fn class(&mut self) -> Result<Node, PatternError> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        // A leading `]` is a literal
+        if self.eat(']') {
+            items.push(ClassItem::Range(']', ']'));
+        }
+        loop {
+            let item = match self.next() {
+                None => {
+                    return Err("unclosed character class".into())
+                }
+                Some(']') => {
+                    return Ok(Node::Class { items, negated })
+                }
+                Some('\\') => self.escape()?,
+                Some(c) => ClassItem::Range(c, c),
+            };
+            let ClassItem::Range(low, _) = item else {
+                items.push(item);
+                continue;
+            };
+            // `a-z`, but not a trailing `-` as in `[a-]`
+            let is_range = self.peek() == Some('-')
+                && self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|&c| c != ']');
+            if !is_range {
+                items.push(item);
+                continue;
+            }
+            self.pos += 1;
+            let high = match self.next() {
+                Some('\\') => match self.escape()? {
+                    ClassItem::Range(c, _) => c,
+                    ClassItem::Shorthand(s) => {
+                        let reason = format!("invalid range end `\\{s}`");
+                        return Err(reason.into());
+                    }
+                },
+                Some(c) => c,
+                None => {
+                    return Err("unclosed character class".into())
+                }
+            };
+            if low > high {
+                let reason = format!("invalid range `{low}-{high}`");
+                return Err(reason.into());
+            }
+            items.push(ClassItem::Range(low, high));
+        }
+    }
+
+    /// Escape after its `\`
+    fn escape(&mut self) -> Result<ClassItem, PatternError> {
+        let literal = |c| Ok(ClassItem::Range(c, c));
+        match self.next() {
+            None => Err("trailing `\\`".into()),
+            Some(c @ ('d' | 'D' | 'w' | 'W' | 's' | 'S')) => {
+                Ok(ClassItem::Shorthand(c))
+            }
+            Some('n') => literal('\n'),
+            Some('t') => literal('\t'),
+            Some('r') => literal('\r'),
+            // Escapes of the `regex` crate, backreferences are not
+            Some(c) if REGEX_ESCAPES.contains(c) => Err(
+                PatternError::Unsupported(format!(
+                    "unsupported escape `\\{c}`"
+                )),
+            ),
+            Some(c) if c.is_ascii_alphanumeric() => {
+                Err(format!("unsupported escape `\\{c}`").into())
+            }
+            Some(c) => literal(c),
+        }
+    }
+
+    /// `atom` followed by its quantifier, if any
+    fn quantified(
+        &mut self,
+        atom: Node,
+    ) -> Result<Node, PatternError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => return self.counted(atom),
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        self.repeat(atom, min, max)
+    }
+
+    /// `{n}`, `{n,}` or `{n,m}` after `atom`
+    fn counted(&mut self, atom: Node) -> Result<Node, PatternError> {
+        self.pos += 1;
+        let close = self.chars[self.pos..]
+            .iter()
+            .position(|&c| c == '}')
+            .ok_or("unclosed `{`")?;
+        let body: String =
+            self.chars[self.pos..self.pos + close].iter().collect();
+        self.pos += close + 1;
+        let count = |text: &str| {
+            text.parse::<usize>().map_err(|_| {
+                format!("invalid repetition `{{{body}}}`")
+            })
+        };
+        let (min, max) = match body.split_once(',') {
+            None => (count(&body)?, Some(count(&body)?)),
+            Some((min, "")) => (count(min)?, None),
+            Some((min, max)) => (count(min)?, Some(count(max)?)),
+        };
+        if max.is_some_and(|max| max < min) {
+            let reason = format!("invalid repetition `{{{body}}}`");
+            return Err(reason.into());
+        }
+        self.repeat(atom, min, max)
+    }
+
+    fn repeat(
+        &mut self,
+        atom: Node,
+        min: usize,
+        max: Option<usize>,
+    ) -> Result<Node, PatternError> {
+        if matches!(atom, Node::Start | Node::End) {
+            return Err("`^` and `$` cannot be repeated".into());
+        }
+        // Lazy quantifiers match the same texts
+        self.eat('?');
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err("nested quantifier".into());
+        }
+        Ok(Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        })
+    }
+}
+
+/// Positions where one of `alternatives` can end when started at `pos`
+fn alternatives_ends(
+    alternatives: &[Vec<Node>],
+    text: &[char],
+    pos: usize,
+) -> BTreeSet<usize> {
+    alternatives
+        .iter()
+        .flat_map(|sequence| {
+            sequence.iter().fold(
+                BTreeSet::from([pos]),
+                |starts, node| {
+                    starts
+                        .into_iter()
+                        .flat_map(|start| {
+                            node_ends(node, text, start)
+                        })
+                        .collect()
+                },
+            )
+        })
+        .collect()
+}
+
+fn node_ends(
+    node: &Node,
+    text: &[char],
+    pos: usize,
+) -> BTreeSet<usize> {
+    let matches_char = |accept: &dyn Fn(char) -> bool| {
+        text.get(pos)
+            .filter(|&&c| accept(c))
+            .map(|_| pos + 1)
+            .into_iter()
+            .collect()
+    };
+    match node {
+        Node::Char(expected) => matches_char(&|c| c == *expected),
+        Node::Any => matches_char(&|c| c != '\n'),
+        Node::Class { items, negated } => matches_char(&|c| {
+            items.iter().any(|item| item.contains(c)) != *negated
+        }),
+        Node::Start => {
+            (pos == 0).then_some(pos).into_iter().collect()
+        }
+        Node::End => {
+            (pos == text.len()).then_some(pos).into_iter().collect()
+        }
+        Node::Group(alternatives) => {
+            alternatives_ends(alternatives, text, pos)
+        }
+        Node::Repeat { node, min, max } => {
+            repeat_ends(node, *min, *max, text, pos)
+        }
+    }
+}
+
+/// Positions after `min..=max` repetitions of `node`, stopping once a
+/// repetition reaches no new position
+fn repeat_ends(
+    node: &Node,
+    min: usize,
+    max: Option<usize>,
+    text: &[char],
+    pos: usize,
+) -> BTreeSet<usize> {
+    let mut ends = BTreeSet::new();
+    let mut current = BTreeSet::from([pos]);
+    let mut count = 0;
+    loop {
+        if count >= min {
+            let before = ends.len();
+            ends.extend(current.iter().copied());
+            if count > min && ends.len() == before {
+                return ends;
+            }
+        }
+        if max == Some(count) || current.is_empty() {
+            return ends;
+        }
+        current = current
+            .into_iter()
+            .flat_map(|start| node_ends(node, text, start))
+            .collect();
+        count += 1;
+    }
+}
+
+impl ClassItem {
+    fn contains(&self, c: char) -> bool {
+        match *self {
+            Self::Range(low, high) => (low..=high).contains(&c),
+            Self::Shorthand(kind) => {
+                let matched = match kind.to_ascii_lowercase() {
+                    'd' => c.is_ascii_digit(),
+                    'w' => c.is_alphanumeric() || c == '_',
+                    _ => c.is_whitespace(),
+                };
+                matched != kind.is_ascii_uppercase()
+            }
+        }
+    }
+}
+
+/// Checks the resolved strings of `graph`, of each translation and of
+/// the test resources against their `pattern`
+pub fn check_patterns(graph: &mut ResourceGraph) {
+    check_graph(graph);
+    for translation in graph.translations_mut() {
+        check_graph(translation);
+    }
+    if graph.tests().is_some() {
+        check_graph(graph.tests_mut());
+    }
+}
+
+fn check_graph(graph: &mut ResourceGraph) {
+    let mut invalid = Vec::new();
+    for (key, nodes) in graph.nodes() {
+        for node in nodes {
+            let (ResourceValue::String(text), Some(source)) =
+                (&node.value, &node.meta.pattern)
+            else {
+                continue;
+            };
+            // Invalid patterns were reported when parsing the resource
+            let Ok(pattern) = Pattern::new(source) else {
+                continue;
+            };
+            if pattern.is_match(text) {
+                continue;
+            }
+            invalid.push(InvalidResource {
+                key: key.clone(),
+                file: node.origin.file.to_path_buf(),
+                line: node.origin.line.map(|line| line as usize),
+                message: format!(
+                    "{text:?} does not match the pattern `{source}`"
+                ),
+                reference: None,
+            });
+        }
+    }
+    invalid.into_iter().for_each(|i| graph.record_invalid(i));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Pattern::new(pattern).unwrap().is_match(text)
+    }
+
+    #[test]
+    fn the_whole_value_must_match() {
+        let region = r"[a-z]{2}-[a-z]+-\d";
+        assert!(matches(region, "eu-west-1"));
+        assert!(!matches(region, "eu-west-1b"));
+        assert!(!matches(region, "EU-west-1"));
+        assert!(!matches("abc", "xabc"));
+        assert!(matches("^abc$", "abc"));
+        assert!(matches("", ""));
+    }
+
+    #[test]
+    fn quantifiers_groups_and_alternatives() {
+        assert!(matches("a*", ""));
+        assert!(matches("a+b?", "aaab"));
+        assert!(!matches("a+", ""));
+        assert!(matches("(ab|cd){2,3}", "abcdab"));
+        assert!(!matches("(ab|cd){2,3}", "ab"));
+        assert!(!matches("(?:ab){2}", "ababab"));
+        assert!(matches("x{2,}", "xxxxx"));
+        assert!(matches("(a*)*b", "aaab"));
+        assert!(matches(".*?z", "abz"));
+        assert!(!matches(".", "\n"));
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert!(matches(r"[A-Za-z_][\w-]*", "api_key-2"));
+        assert!(matches(r"[^\s]+", "no-space"));
+        assert!(!matches(r"[^\s]+", "a b"));
+        assert!(matches(r"[a-]+", "a-a"));
+        assert!(matches(r"[]x]", "]"));
+        assert!(matches(r"\d+\.\d+", "1.25"));
+        assert!(!matches(r"\d+\.\d+", "1x25"));
+        assert!(matches(r"\S\D\W", "xy!"));
+        assert!(matches("caf[é]", "café"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_errors() {
+        for (pattern, error) in [
+            ("(ab", "unclosed group"),
+            ("ab)", "unmatched `)`"),
+            ("[a-z", "unclosed character class"),
+            ("[z-a]", "invalid range `z-a`"),
+            ("*a", "nothing to repeat before `*`"),
+            ("a{3,1}", "invalid repetition `{3,1}`"),
+            ("a{x}", "invalid repetition `{x}`"),
+            ("a**", "nested quantifier"),
+            (r"(a)\1", r"unsupported escape `\1`"),
+            ("(?=a)", "lookaround is not supported"),
+            ("(?<!a)b", "lookaround is not supported"),
+            ("a\\", "trailing `\\`"),
+        ] {
+            assert_eq!(
+                Pattern::new(pattern).unwrap_err(),
+                PatternError::Invalid(error.to_string()),
+                "{pattern}"
+            );
+        }
+    }
+
+    #[test]
+    fn regex_crate_syntax_is_unsupported() {
+        for (pattern, error) in [
+            (r"\bword\b", r"unsupported escape `\b`"),
+            (r"\p{L}+", r"unsupported escape `\p`"),
+            ("(?i)abc", "inline flags and named groups are not supported"),
+            ("(?P<year>\\d{4})", "inline flags and named groups are not supported"),
+        ] {
+            assert_eq!(
+                Pattern::new(pattern).unwrap_err(),
+                PatternError::Unsupported(error.to_string()),
+                "{pattern}"
+            );
+        }
+    }
+}
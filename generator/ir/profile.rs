@@ -0,0 +1,189 @@
+//! Profile resolution for parsed resources.
+//!
+//! Resources may carry `profile` attributes (on themselves or on an
+//! enclosing `<ns>`). A resource is active when every attribute in its
+//! chain matches the selected profile. `profile="default"` always
+//! matches, but loses against a profile-specific definition of the
+//! same key.
+//!
+//! A `profile` attribute is a comma-separated list: `profile="dev,staging"`
+//! matches either profile, `profile="!release"` matches every profile but
+//! `release`. Mixing both (`"dev,!test"`) means "one of the listed
+//! profiles, minus the negated ones".
+
+/// Profile value marking a fallback definition
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Parsed form of a single `profile` attribute
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProfileSpec {
+    /// Profiles listed without `!`
+    pub include: Vec<String>,
+    /// Profiles listed with `!`
+    pub exclude: Vec<String>,
+    /// `profile="default"`
+    pub is_default: bool,
+}
+
+impl ProfileSpec {
+    /// Parses a `profile` attribute, rejecting empty and conflicting lists
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut spec = Self::default();
+        for token in raw.split(',').map(str::trim) {
+            if token.is_empty() {
+                return Err(format!(
+                    "empty profile name in profile=\"{raw}\""
+                ));
+            }
+            if let Some(name) = token.strip_prefix('!') {
+                spec.exclude.push(name.trim().to_string());
+            } else if token == DEFAULT_PROFILE {
+                spec.is_default = true;
+            } else {
+                spec.include.push(token.to_string());
+            }
+        }
+        spec.check(raw)?;
+        Ok(spec)
+    }
+
+    fn check(&self, raw: &str) -> Result<(), String> {
+        if self.exclude.iter().any(|name| name.is_empty()) {
+            return Err(format!(
+                "empty profile name in profile=\"{raw}\""
+            ));
+        }
+        if self.is_default
+            && (!self.include.is_empty() || !self.exclude.is_empty())
+        {
+            return Err(format!(
+                "'{DEFAULT_PROFILE}' cannot be combined with other profiles in profile=\"{raw}\""
+            ));
+        }
+        if let Some(name) =
+            self.include.iter().find(|name| self.exclude.contains(name))
+        {
+            return Err(format!(
+                "profile '{name}' is both included and excluded in profile=\"{raw}\""
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns true if this spec selects `profile`
+    pub fn matches(&self, profile: &str) -> bool {
+        if self.is_default {
+            return true;
+        }
+        let included = self.include.is_empty()
+            || self.include.iter().any(|name| name == profile);
+        included && !self.exclude.iter().any(|name| name == profile)
+    }
+
+    /// Profile names mentioned by this spec
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.include
+            .iter()
+            .chain(&self.exclude)
+            .map(String::as_str)
+    }
+}
+
+/// Returns true if every profile attribute in `specs` matches `profile`.
+///
+/// Invalid attributes never match; they are reported during analysis.
+pub fn is_active(specs: &[String], profile: &str) -> bool {
+    specs.iter().all(|raw| {
+        ProfileSpec::parse(raw).is_ok_and(|spec| spec.matches(profile))
+    })
+}
+
+/// Returns true if `specs` targets a specific (non-default) profile
+pub fn is_specific(specs: &[String]) -> bool {
+    specs.iter().any(|raw| raw.trim() != DEFAULT_PROFILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn specs(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn unprofiled_is_always_active() {
+        assert!(is_active(&[], "release"));
+        assert!(!is_specific(&[]));
+    }
+
+    #[test]
+    fn default_is_active_but_not_specific() {
+        let default = specs(&["default"]);
+        assert!(is_active(&default, "debug"));
+        assert!(is_active(&default, "staging"));
+        assert!(!is_specific(&default));
+    }
+
+    #[test]
+    fn named_profile_must_match() {
+        let release = specs(&["release"]);
+        assert!(is_active(&release, "release"));
+        assert!(!is_active(&release, "debug"));
+        assert!(is_specific(&release));
+    }
+
+    #[test]
+    fn nested_profiles_must_all_match() {
+        let nested = specs(&["debug", "release"]);
+        assert!(!is_active(&nested, "debug"));
+        assert!(!is_active(&nested, "release"));
+
+        let inherited = specs(&["debug", "default"]);
+        assert!(is_active(&inherited, "debug"));
+        assert!(is_specific(&inherited));
+    }
+
+    #[test]
+    fn comma_list_matches_any_listed_profile() {
+        let list = specs(&["dev, staging"]);
+        assert!(is_active(&list, "dev"));
+        assert!(is_active(&list, "staging"));
+        assert!(!is_active(&list, "release"));
+    }
+
+    #[test]
+    fn negation_excludes_profile() {
+        let not_release = specs(&["!release"]);
+        assert!(is_active(&not_release, "debug"));
+        assert!(is_active(&not_release, "staging"));
+        assert!(!is_active(&not_release, "release"));
+        assert!(is_specific(&not_release));
+    }
+
+    #[test]
+    fn include_and_exclude_combine() {
+        let spec = ProfileSpec::parse("dev,staging,!dev-local").unwrap();
+        assert!(spec.matches("dev"));
+        assert!(!spec.matches("dev-local"));
+        assert!(!spec.matches("release"));
+        assert_eq!(
+            spec.names().collect::<Vec<_>>(),
+            vec!["dev", "staging", "dev-local"]
+        );
+    }
+
+    #[test]
+    fn conflicting_profiles_are_rejected() {
+        let err = ProfileSpec::parse("dev,!dev").unwrap_err();
+        assert!(err.contains("both included and excluded"));
+        assert!(!is_active(&specs(&["dev,!dev"]), "dev"));
+    }
+
+    #[test]
+    fn malformed_profiles_are_rejected() {
+        assert!(ProfileSpec::parse("dev,").is_err());
+        assert!(ProfileSpec::parse("!").is_err());
+        assert!(ProfileSpec::parse("default,release").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! Pseudo-localization of string resources.
+//!
+//! Rewrites strings and templates so untranslated or truncated text is
+//! easy to spot in a running app: `Welcome to {name}!` becomes
+//! `[Ŵéļçöɱé ţö {name}!~~~~]`. Letters are accented, the text is padded
+//! by ~30% with `~` and wrapped in brackets. Placeholders (`{name}`,
+//! `%1$s`, `%d`) are copied untouched.
+//!
+//! Only `String` and `Template` resources are rewritten; URL-like
+//! strings and keys marked `translatable="false"` are left alone.
+
+use super::model::{
+    ResourceGraph, ResourceKey, ResourceNode, ResourceValue,
+};
+
+/// Profile enabling pseudo-localization without touching `BuildOptions`
+pub const PSEUDO_PROFILE: &str = "pseudo";
+
+const ACCENTED_UPPER: [char; 26] = [
+    'Å', 'Ɓ', 'Ç', 'Đ', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Î', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ',
+    'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š', 'Ţ', 'Û', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+
+const ACCENTED_LOWER: [char; 26] = [
+    'å', 'ƀ', 'ç', 'đ', 'é', 'ƒ', 'ĝ', 'ĥ', 'î', 'ĵ', 'ķ', 'ļ', 'ɱ',
+    'ñ', 'ö', 'þ', 'ǫ', 'ŕ', 'š', 'ţ', 'û', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+
+/// Pseudo-localizes every eligible resource of `graph` and its
+/// translations
+pub fn pseudolocalize_graph(graph: &mut ResourceGraph) {
+    let untranslatable: Vec<ResourceKey> = graph
+        .nodes()
+        .keys()
+        .filter(|key| !graph.is_translatable(key))
+        .cloned()
+        .collect();
+    for (key, nodes) in graph.nodes_mut() {
+        if untranslatable.contains(key) {
+            continue;
+        }
+        nodes.iter_mut().for_each(pseudolocalize_node);
+    }
+    for translation in graph.translations_mut() {
+        pseudolocalize_graph(translation);
+    }
+    if graph.tests().is_some() {
+        pseudolocalize_graph(graph.tests_mut());
+    }
+}
+
+fn pseudolocalize_node(node: &mut ResourceNode) {
+    match &mut node.value {
+        ResourceValue::String(text)
+        | ResourceValue::Template { text, .. }
+            if !looks_like_url(text) =>
+        {
+            *text = pseudolocalize(text);
+        }
+        _ => {}
+    }
+}
+
+/// Transforms one string. Deterministic; empty strings stay empty.
+pub fn pseudolocalize(text: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::with_capacity(text.len() * 2 + 2);
+    let mut visible = 0;
+    let mut rest = text;
+    out.push('[');
+    while let Some(c) = rest.chars().next() {
+        let len = placeholder_len(rest);
+        if len > 0 {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+        out.push(accent(c));
+        visible += 1;
+        rest = &rest[c.len_utf8()..];
+    }
+    // Translations are often ~30% longer than English
+    out.push_str(&"~".repeat(padding(visible)));
+    out.push(']');
+    out
+}
+
+/// Padding added for `visible` characters (placeholders excluded)
+fn padding(visible: usize) -> usize {
+    (visible * 3).div_ceil(10)
+}
+
+fn accent(c: char) -> char {
+    match c {
+        'A'..='Z' => ACCENTED_UPPER[(c as u8 - b'A') as usize],
+        'a'..='z' => ACCENTED_LOWER[(c as u8 - b'a') as usize],
+        _ => c,
+    }
+}
+
+/// Length of the placeholder starting `text` (`{name}`, `%1$s`, `%d`),
+/// or 0 if there is none
+fn placeholder_len(text: &str) -> usize {
+    if let Some(rest) = text.strip_prefix('{') {
+        let name_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        return if name_len > 0 && rest[name_len..].starts_with('}') {
+            name_len + 2
+        } else {
+            0
+        };
+    }
+
+    let Some(rest) = text.strip_prefix('%') else {
+        return 0;
+    };
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let spec = if digits > 0 {
+        match rest[digits..].strip_prefix('$') {
+            Some(spec) => spec,
+            None => return 0,
+        }
+    } else {
+        rest
+    };
+    let positional = if digits > 0 { digits + 1 } else { 0 };
+    if spec.starts_with(['s', 'd', 'f', 'x', 'X']) {
+        positional + 2
+    } else if digits == 0 && spec.starts_with('%') {
+        2
+    } else {
+        0
+    }
+}
+
+fn looks_like_url(text: &str) -> bool {
+    text.contains("://") && !text.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::ir::model::{ResourceKind, ResourceOrigin};
+    use std::path::PathBuf;
+
+    fn node(value: ResourceValue) -> ResourceNode {
+        ResourceNode::new(
+            ResourceKind::String,
+            value,
+            ResourceOrigin::new(
+                PathBuf::from("values.xml"),
+                false,
+            ),
+        )
+    }
+
+    fn string_of(graph: &ResourceGraph, path: &str) -> String {
+        match &graph.get(&ResourceKey::from_path(path)).unwrap().value
+        {
+            ResourceValue::String(text)
+            | ResourceValue::Template { text, .. } => text.clone(),
+            other => panic!("unexpected value {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accents_pads_and_brackets() {
+        assert_eq!(pseudolocalize("Welcome"), "[Ŵéļçöɱé~~~]");
+        assert_eq!(pseudolocalize("Hi!"), "[Ĥî!~]");
+        assert_eq!(pseudolocalize(""), "");
+    }
+
+    #[test]
+    fn placeholders_survive_untouched() {
+        assert_eq!(
+            pseudolocalize("Welcome to {name}!"),
+            "[Ŵéļçöɱé ţö {name}!~~~~]"
+        );
+        assert_eq!(
+            pseudolocalize("%1$s sent %2$d files"),
+            "[%1$s šéñţ %2$d ƒîļéš~~~~]"
+        );
+        assert_eq!(pseudolocalize("100%% %s"), "[100%% %s~~]");
+        // Not placeholders: accented like any other text
+        assert_eq!(pseudolocalize("{a b}"), "[{å ƀ}~~]");
+    }
+
+    #[test]
+    fn padding_scales_with_length() {
+        assert_eq!(padding(1), 1);
+        assert_eq!(padding(10), 3);
+        assert_eq!(padding(100), 30);
+
+        let short = pseudolocalize("ab");
+        let long = pseudolocalize(&"ab".repeat(50));
+        assert_eq!(short.matches('~').count(), 1);
+        assert_eq!(long.matches('~').count(), 30);
+    }
+
+    #[test]
+    fn is_deterministic() {
+        assert_eq!(pseudolocalize("Save"), pseudolocalize("Save"));
+    }
+
+    #[test]
+    fn graph_skips_urls_untranslatable_and_other_kinds() {
+        let mut graph = ResourceGraph::default();
+        let insert = |graph: &mut ResourceGraph,
+                      path: &str,
+                      value| {
+            graph.insert(ResourceKey::from_path(path), node(value));
+        };
+        insert(
+            &mut graph,
+            "title",
+            ResourceValue::String("Title".into()),
+        );
+        insert(
+            &mut graph,
+            "api_url",
+            ResourceValue::String("https://example.com".into()),
+        );
+        insert(
+            &mut graph,
+            "brand",
+            ResourceValue::String("Acme".into()),
+        );
+        graph.mark_untranslatable(ResourceKey::from_path("brand"));
+        insert(
+            &mut graph,
+            "accent",
+            ResourceValue::Color("#FF0000".into()),
+        );
+        insert(
+            graph.translation_mut("fr"),
+            "title",
+            ResourceValue::String("Titre".into()),
+        );
+
+        pseudolocalize_graph(&mut graph);
+
+        assert_eq!(string_of(&graph, "title"), "[Ţîţļé~~]");
+        assert_eq!(
+            string_of(&graph, "api_url"),
+            "https://example.com"
+        );
+        assert_eq!(string_of(&graph, "brand"), "Acme");
+        assert!(matches!(
+            &graph.get(&ResourceKey::from_path("accent")).unwrap().value,
+            ResourceValue::Color(c) if c == "#FF0000"
+        ));
+        assert_eq!(
+            string_of(&graph.translations()["fr"], "title"),
+            "[Ţîţŕé~~]"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! `transform="..."` attributes of strings.
+//!
+//! ```xml
+//! <string name="app_slug" transform="slug">@string/app_name</string>
+//! ```
+//!
+//! The chain is comma-separated and applied left to right
+//! (`transform="trim,upper"`) on the value with its `@string/`
+//! references resolved, so `app_slug` follows `app_name`. A string
+//! referencing `@string/app_slug` sees the transformed text.
+
+/// One step of a `transform` chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    Upper,
+    Lower,
+    Trim,
+    /// Lowercase, runs of non-alphanumerics become one `-`
+    Slug,
+    /// `&`, `<`, `>`, `"` and `'` become entities
+    EscapeHtml,
+}
+
+const NAMES: &[(&str, Transform)] = &[
+    ("upper", Transform::Upper),
+    ("lower", Transform::Lower),
+    ("trim", Transform::Trim),
+    ("slug", Transform::Slug),
+    ("escape_html", Transform::EscapeHtml),
+];
+
+impl Transform {
+    /// Steps of the `transform` attribute `chain`
+    pub fn parse_chain(chain: &str) -> Result<Vec<Self>, String> {
+        chain
+            .split(',')
+            .map(|name| {
+                let name = name.trim();
+                NAMES
+                    .iter()
+                    .find(|(known, _)| *known == name)
+                    .map(|(_, transform)| *transform)
+                    .ok_or_else(|| {
+                        let known: Vec<&str> =
+                            NAMES.iter().map(|(n, _)| *n).collect();
+                        format!(
+                            "unknown transform `{name}` in `{chain}` \
+                             (expected one of {})",
+                            known.join(", ")
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::Trim => text.trim().to_string(),
+            Self::Slug => slug(text),
+            Self::EscapeHtml => escape_html(text),
+        }
+    }
+}
+
+/// `text` with every step of `chain` applied in order
+pub fn apply_chain(chain: &[Transform], text: &str) -> String {
+    chain
+        .iter()
+        .fold(text.to_string(), |text, step| step.apply(&text))
+}
+
+/// `Café & Crème!` → `café-crème`: letters keep their accents, and
+/// there is no `-` at either end
+fn slug(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut dash = false;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if dash && !out.is_empty() {
+                out.push('-');
+            }
+            dash = false;
+            out.extend(c.to_lowercase());
+        } else {
+            dash = true;
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(chain: &str, text: &str) -> String {
+        apply_chain(&Transform::parse_chain(chain).unwrap(), text)
+    }
+
+    #[test]
+    fn case_mapping_is_unicode_aware() {
+        assert_eq!(transform("upper", "straße déjà"), "STRASSE DÉJÀ");
+        assert_eq!(transform("lower", "ÉCOLE ΣΟΦΊΑ"), "école σοφία");
+    }
+
+    #[test]
+    fn slugs_keep_letters_and_collapse_the_rest() {
+        assert_eq!(
+            transform("slug", "  Café & Crème, déjà vu!  "),
+            "café-crème-déjà-vu"
+        );
+        assert_eq!(
+            transform("slug", "My App -- v2.0"),
+            "my-app-v2-0"
+        );
+        assert_eq!(transform("slug", "?!"), "");
+    }
+
+    #[test]
+    fn chains_apply_left_to_right() {
+        assert_eq!(transform("trim, upper", "  Demo "), "DEMO");
+        assert_eq!(
+            transform("slug,upper", "Café Crème"),
+            "CAFÉ-CRÈME"
+        );
+        assert_eq!(
+            transform("escape_html", r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn unknown_transforms_are_errors() {
+        assert_eq!(
+            Transform::parse_chain("trim,shout").unwrap_err(),
+            "unknown transform `shout` in `trim,shout` (expected one of \
+             upper, lower, trim, slug, escape_html)"
+        );
+        assert!(Transform::parse_chain("slug,,upper").is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::generator::ir::{
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::utils::const_identifier;
 
 pub struct BoolType;
 
@@ -27,11 +27,11 @@ impl ResourceType for BoolType {
         origin: ResourceOrigin,
     ) -> Option<ResourceNode> {
         if let ScalarValue::Bool(value) = &parsed.value {
-            Some(ResourceNode {
-                kind: ResourceKind::Bool,
-                value: ResourceValue::Bool(*value),
+            Some(ResourceNode::new(
+                ResourceKind::Bool,
+                ResourceValue::Bool(*value),
                 origin,
-            })
+            ))
         } else {
             None
         }
@@ -46,7 +46,7 @@ impl ResourceType for BoolType {
         if let ResourceValue::Bool(value) = &node.value {
             let pad = " ".repeat(indent);
             let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
+                const_identifier(&key.name);
             Some(format!(
                 "{pad}pub const {const_name}: bool = {value};\n"
             ))
@@ -94,6 +94,7 @@ mod tests {
             name: "enabled".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -112,6 +113,7 @@ mod tests {
             name: "disabled".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(false),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -128,6 +130,7 @@ mod tests {
             name: "not_bool".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Text("not a bool".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -143,6 +146,7 @@ mod tests {
             name: "not_bool".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("#FF0000".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -155,14 +159,14 @@ mod tests {
     fn test_emit_rust_true() {
         let handler = BoolType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "enabled".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Bool,
-            value: ResourceValue::Bool(true),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "enabled".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Bool,
+            ResourceValue::Bool(true),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const ENABLED: bool = true;"));
@@ -173,14 +177,14 @@ mod tests {
     fn test_emit_rust_false() {
         let handler = BoolType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "disabled".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Bool,
-            value: ResourceValue::Bool(false),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "disabled".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Bool,
+            ResourceValue::Bool(false),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const DISABLED: bool = false;"));
@@ -191,14 +195,14 @@ mod tests {
     fn test_emit_rust_wrong_value_type() {
         let handler = BoolType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "not_bool".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Bool,
-            value: ResourceValue::Color("#FF0000".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "not_bool".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Bool,
+            ResourceValue::Color("#FF0000".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4);
         assert!(result.is_none());
@@ -209,14 +213,14 @@ mod tests {
     fn test_emit_rust_indentation() {
         let handler = BoolType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "test_bool".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Bool,
-            value: ResourceValue::Bool(true),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "test_bool".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Bool,
+            ResourceValue::Bool(true),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
         // Should have 8 spaces of indentation
@@ -228,14 +232,14 @@ mod tests {
     fn test_emit_rust_namespaced() {
         let handler = BoolType;
         let key = ResourceKey {
-            namespace: vec!["settings".to_string(), "feature".to_string()],
-            name: "enabled".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Bool,
-            value: ResourceValue::Bool(true),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec!["settings".to_string(), "feature".to_string()].into(),
+            name: "enabled".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Bool,
+            ResourceValue::Bool(true),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const ENABLED: bool = true;"));
@@ -254,14 +258,14 @@ mod tests {
 
         for (name, expected_const) in test_cases {
             let key = ResourceKey {
-                namespace: vec![],
-                name: name.to_string(),
-            };
-            let node = ResourceNode {
-                kind: ModelResourceKind::Bool,
-                value: ResourceValue::Bool(true),
-                origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+                namespace: vec![].into(),
+                name: name.into(),
             };
+            let node = ResourceNode::new(
+                ModelResourceKind::Bool,
+                ResourceValue::Bool(true),
+                ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            );
 
             let result = handler.emit_rust(&key, &node, 4).unwrap();
             assert!(
@@ -287,6 +291,7 @@ mod tests {
                 name: name.to_string(),
                 kind: AstResourceKind::Bool,
                 value: ScalarValue::Bool(value),
+                meta: Default::default(),
             };
             let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -311,6 +316,7 @@ mod tests {
                 name: name.to_string(),
                 kind: AstResourceKind::Bool,
                 value: ScalarValue::Bool(value),
+                meta: Default::default(),
             };
             let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
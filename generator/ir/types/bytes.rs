@@ -0,0 +1,413 @@
+//! `<bytes>`, a binary blob written in base64 or hex and decoded at
+//! build time.
+//!
+//! ```xml
+//! <bytes name="signing_pubkey" encoding="base64">
+//!     MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=
+//! </bytes>
+//! <bytes name="magic" encoding="hex" sha256="...">89504e47</bytes>
+//! <!--
+//! pub const SIGNING_PUBKEY: &[u8] = &[
+//!     0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+//!     ...
+//! ];
+//! pub const MAGIC: &[u8] = &[0x89, 0x50, 0x4e, 0x47];
+//! -->
+//! ```
+//!
+//! Whitespace is dropped before decoding, so wrapped keys can be pasted
+//! as is; errors give the offset of the bad character in the text.
+//! `sha256` checks the decoded bytes against their digest. Blobs above
+//! [`MAX_BYTES`] belong in `res/raw/`.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+/// Largest decoded `<bytes>`, beyond which `res/raw/` is the better
+/// place
+const MAX_BYTES: usize = 64 * 1024;
+/// Bytes per line of a multi-line `&[...]`
+const BYTES_PER_LINE: usize = 12;
+const BASE64: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub struct BytesType;
+
+impl ResourceType for BytesType {
+    fn name(&self) -> &'static str {
+        "bytes"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["bytes"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Bytes
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Bytes {
+                text,
+                encoding,
+                sha256,
+            } => parse_bytes(text, encoding, sha256.as_deref())
+                .map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Bytes {
+            text,
+            encoding,
+            sha256,
+        } = &parsed.value
+        else {
+            return None;
+        };
+        let bytes =
+            parse_bytes(text, encoding, sha256.as_deref()).ok()?;
+        Some(ResourceNode::new(
+            ResourceKind::Bytes,
+            ResourceValue::Bytes(bytes),
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Bytes(bytes) = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        let literals: Vec<String> =
+            bytes.iter().map(|byte| format!("{byte:#04x}")).collect();
+        if bytes.len() <= BYTES_PER_LINE {
+            return Some(format!(
+                "{pad}pub const {name}: &[u8] = &[{}];\n",
+                literals.join(", ")
+            ));
+        }
+        let lines: String = literals
+            .chunks(BYTES_PER_LINE)
+            .map(|line| format!("{pad}    {},\n", line.join(", ")))
+            .collect();
+        Some(format!(
+            "{pad}pub const {name}: &[u8] = &[\n{lines}{pad}];\n"
+        ))
+    }
+}
+
+/// Lowercase hex digits of `bytes`
+pub fn hex_text(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Decoded bytes of `text`, checked against `sha256`
+fn parse_bytes(
+    text: &str,
+    encoding: &str,
+    sha256: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    // Offsets are in the text as written, whitespace included
+    let digits: Vec<(usize, u8)> = text
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(offset, c)| (offset, u8::try_from(c).unwrap_or(0xff)))
+        .collect();
+    let bytes = match encoding {
+        "base64" => decode_base64(text, &digits)?,
+        "hex" => decode_hex(text, &digits)?,
+        other => {
+            return Err(format!(
+                "encoding '{other}' is not base64 or hex"
+            ))
+        }
+    };
+    if bytes.len() > MAX_BYTES {
+        return Err(format!(
+            "decodes to {} bytes, more than the {MAX_BYTES} of a <bytes>; put the file in res/raw/",
+            bytes.len()
+        ));
+    }
+    if let Some(expected) = sha256 {
+        let expected = expected.trim().to_ascii_lowercase();
+        let actual = hex_text(&sha256_digest(&bytes));
+        if actual != expected {
+            return Err(format!(
+                "decoded bytes have SHA-256 {actual}, not {expected}"
+            ));
+        }
+    }
+    Ok(bytes)
+}
+
+/// Character of `text` at `offset`, for error messages
+fn char_at(text: &str, offset: usize) -> char {
+    text[offset..].chars().next().unwrap_or_default()
+}
+
+fn decode_hex(
+    text: &str,
+    digits: &[(usize, u8)],
+) -> Result<Vec<u8>, String> {
+    let value = |&(offset, digit): &(usize, u8)| {
+        char::from(digit).to_digit(16).ok_or_else(|| {
+            format!(
+                "'{}' at offset {offset} is not a hex digit",
+                char_at(text, offset)
+            )
+        })
+    };
+    let values =
+        digits.iter().map(value).collect::<Result<Vec<_>, _>>()?;
+    if !values.len().is_multiple_of(2) {
+        return Err(format!(
+            "{} hex digits do not make whole bytes",
+            values.len()
+        ));
+    }
+    // Both digits are below 16
+    Ok(values
+        .chunks(2)
+        .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+        .collect())
+}
+
+/// Standard base64, `=` padding optional
+fn decode_base64(
+    text: &str,
+    digits: &[(usize, u8)],
+) -> Result<Vec<u8>, String> {
+    let padding = digits
+        .iter()
+        .rev()
+        .take_while(|(_, digit)| *digit == b'=')
+        .count();
+    let data = &digits[..digits.len() - padding];
+    let mut sextets = Vec::with_capacity(data.len());
+    for &(offset, digit) in data {
+        let Some(value) = BASE64.iter().position(|c| *c == digit)
+        else {
+            return Err(format!(
+                "'{}' at offset {offset} is not a base64 character",
+                char_at(text, offset)
+            ));
+        };
+        sextets.push(value as u32);
+    }
+    // A lone sextet is 6 bits, less than a byte
+    let complete = (sextets.len() + padding).is_multiple_of(4);
+    if sextets.len() % 4 == 1
+        || padding > 2
+        || (padding > 0 && !complete)
+    {
+        return Err(format!(
+            "{} base64 characters do not make whole bytes",
+            digits.len()
+        ));
+    }
+    let mut bytes = Vec::with_capacity(sextets.len() * 3 / 4);
+    for chunk in sextets.chunks(4) {
+        let bits = chunk
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(4)
+            .fold(0, |bits, sextet| bits << 6 | sextet);
+        let [_, a, b, c] = bits.to_be_bytes();
+        bytes.extend_from_slice(&[a, b, c][..chunk.len() - 1]);
+    }
+    Ok(bytes)
+}
+
+/// SHA-256 of `data` (FIPS 180-4)
+fn sha256_digest(data: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b,
+        0x59f111f1, 0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01,
+        0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7,
+        0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc,
+        0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152,
+        0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+        0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819,
+        0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116, 0x1e376c08,
+        0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f,
+        0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+        0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f,
+        0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+    // `0x80`, zeros up to 56 mod 64, then the length in bits
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message
+        .extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([
+                word[0], word[1], word[2], word[3],
+            ]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7)
+                ^ w[i - 15].rotate_right(18)
+                ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17)
+                ^ w[i - 2].rotate_right(19)
+                ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] =
+            state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6)
+                ^ e.rotate_right(11)
+                ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2)
+                ^ a.rotate_right(13)
+                ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            (h, g, f, e) = (g, f, e, d.wrapping_add(t1));
+            (d, c, b, a) = (c, b, a, t1.wrapping_add(t2));
+        }
+        for (word, add) in
+            state.iter_mut().zip([a, b, c, d, e, f, g, h])
+        {
+            *word = word.wrapping_add(add);
+        }
+    }
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base64(text: &str) -> Result<Vec<u8>, String> {
+        parse_bytes(text, "base64", None)
+    }
+
+    #[test]
+    fn wrapped_base64_is_decoded() {
+        assert_eq!(base64("TWFu"), Ok(b"Man".to_vec()));
+        assert_eq!(base64("TWE="), Ok(b"Ma".to_vec()));
+        assert_eq!(base64("TWE"), Ok(b"Ma".to_vec()));
+        assert_eq!(base64("TQ=="), Ok(b"M".to_vec()));
+        assert_eq!(
+            base64("aGVs\n    bG8g\r\n\td29y bGQ="),
+            Ok(b"hello world".to_vec())
+        );
+        assert_eq!(
+            parse_bytes("89 50\n4E 47", "hex", None),
+            Ok(vec![0x89, 0x50, 0x4e, 0x47])
+        );
+    }
+
+    #[test]
+    fn bad_characters_are_reported_with_their_offset() {
+        assert_eq!(
+            base64("TWFu\n    TW!u"),
+            Err("'!' at offset 11 is not a base64 character".into())
+        );
+        assert_eq!(
+            base64("TWFu-_"),
+            Err("'-' at offset 4 is not a base64 character".into())
+        );
+        assert_eq!(
+            base64("TWFuT"),
+            Err("5 base64 characters do not make whole bytes".into())
+        );
+        assert_eq!(
+            base64("TQ==="),
+            Err("5 base64 characters do not make whole bytes".into())
+        );
+        assert_eq!(
+            parse_bytes("89 5é", "hex", None),
+            Err("'é' at offset 4 is not a hex digit".into())
+        );
+        assert_eq!(
+            parse_bytes("895", "hex", None),
+            Err("3 hex digits do not make whole bytes".into())
+        );
+        assert_eq!(
+            parse_bytes("00", "base-64", None),
+            Err("encoding 'base-64' is not base64 or hex".into())
+        );
+    }
+
+    #[test]
+    fn digests_are_checked() {
+        assert_eq!(
+            hex_text(&sha256_digest(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex_text(&sha256_digest(&[b'a'; 200])),
+            "c2a908d98f5df987ade41b5fce213067efbcc21ef2240212a41e54b5e7c28ae5"
+        );
+        let abc =
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(
+            parse_bytes("YWJj", "base64", Some(abc)),
+            Ok(b"abc".to_vec())
+        );
+        assert_eq!(
+            parse_bytes("YWJk", "base64", Some(abc)),
+            Err(format!(
+                "decoded bytes have SHA-256 a52d159f262b2c6ddb724a61840befc36eb30c88877a4030b65cbe86298449c9, not {}",
+                abc.to_ascii_lowercase()
+            ))
+        );
+    }
+
+    #[test]
+    fn large_blobs_are_rejected() {
+        let text = "00".repeat(MAX_BYTES + 1);
+        assert_eq!(
+            parse_bytes(&text, "hex", None),
+            Err("decodes to 65537 bytes, more than the 65536 of a <bytes>; put the file in res/raw/".into())
+        );
+    }
+}
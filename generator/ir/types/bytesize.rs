@@ -0,0 +1,240 @@
+//! `<bytesize>`, a human-readable size generated as a `u64` number of
+//! bytes.
+//!
+//! ```xml
+//! <bytesize name="max_upload">25MiB</bytesize>
+//! <!-- pub const MAX_UPLOAD: u64 = 26_214_400; -->
+//! ```
+//!
+//! `KB`, `MB`, `GB` and `TB` are powers of 1000, `KiB`, `MiB`, `GiB`
+//! and `TiB` powers of 1024; a bare number is in bytes (`B`).
+//! Fractions (`1.5GiB`) must come to a whole number of bytes. The node
+//! is a typed number: the typed modules, lookups and assertions treat
+//! it like any `u64`.
+
+use crate::generator::ir::model::{NumberType, NumberValue};
+use crate::generator::ir::types::number::NumberTypeHandler;
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    NodeMeta, ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+
+/// Suffixes and the number of bytes they stand for
+const UNITS: [(&str, u64); 9] = [
+    ("B", 1),
+    ("KB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+];
+
+pub struct ByteSizeType;
+
+impl ResourceType for ByteSizeType {
+    fn name(&self) -> &'static str {
+        "bytesize"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["bytesize"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Number
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::ByteSize(text) => {
+                parse_byte_size(text).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::ByteSize(text) = &parsed.value else {
+            return None;
+        };
+        let bytes = parse_byte_size(text).ok()?;
+        // The size as written, after the `<doc>` text
+        let written = format!("`{}` in bytes", text.trim());
+        let doc = match &parsed.meta.doc {
+            Some(doc) => format!("{doc}\n\n{written}"),
+            None => written,
+        };
+        Some(ResourceNode {
+            meta: NodeMeta {
+                doc: Some(doc),
+                ..NodeMeta::default()
+            },
+            ..ResourceNode::new(
+                ResourceKind::Number,
+                ResourceValue::Number(NumberValue::Typed {
+                    literal: group_digits(bytes),
+                    ty: NumberType::U64,
+                }),
+                origin,
+            )
+        })
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        NumberTypeHandler.emit_rust(key, node, indent)
+    }
+}
+
+/// Number of bytes of `25MiB`, `1.5 GB` or `512`
+fn parse_byte_size(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (number, unit) = (text[..split].trim_end(), &text[split..]);
+    let unit = if unit.is_empty() { "B" } else { unit };
+    let Some((_, multiple)) = UNITS.iter().find(|(u, _)| *u == unit)
+    else {
+        return Err(format!(
+            "'{text}' has unknown unit '{unit}' (expected B, KB, MB, GB, TB, KiB, MiB, GiB or TiB)"
+        ));
+    };
+    let invalid = || format!("'{text}' is not a valid byte size");
+    let (whole, fraction) =
+        number.split_once('.').unwrap_or((number, ""));
+    let digits = |part: &str| {
+        let part = part.replace('_', "");
+        part.bytes().all(|b| b.is_ascii_digit()).then_some(part)
+    };
+    let whole = digits(whole).filter(|w| !w.is_empty());
+    let (Some(whole), Some(fraction)) = (whole, digits(fraction))
+    else {
+        return Err(invalid());
+    };
+    // `1.5` is 15 over a scale of 10, multiplied before dividing to
+    // stay exact
+    let scale = 10u128
+        .checked_pow(fraction.len() as u32)
+        .ok_or_else(invalid)?;
+    let too_large = || format!("'{text}' does not fit in u64");
+    let scaled: u128 = format!("{whole}{fraction}")
+        .parse()
+        .map_err(|_| too_large())?;
+    let bytes = scaled
+        .checked_mul((*multiple).into())
+        .ok_or_else(too_large)?;
+    if !bytes.is_multiple_of(scale) {
+        return Err(format!(
+            "'{text}' is not a whole number of bytes"
+        ));
+    }
+    u64::try_from(bytes / scale).map_err(|_| too_large())
+}
+
+/// `26214400` → `26_214_400`, short numbers as they are
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    if digits.len() <= 4 {
+        return digits;
+    }
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push('_');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn byte_size(text: &str) -> ParsedResource {
+        ParsedResource {
+            name: "max_upload".to_string(),
+            kind: AstResourceKind::ByteSize,
+            value: ScalarValue::ByteSize(text.to_string()),
+            meta: Default::default(),
+        }
+    }
+
+    #[test]
+    fn units_are_decimal_or_binary() {
+        for (text, bytes) in [
+            ("512", 512),
+            ("512B", 512),
+            ("25MiB", 26_214_400),
+            ("25MB", 25_000_000),
+            ("1 KiB", 1024),
+            ("2GB", 2_000_000_000),
+            ("1.5GiB", 1_610_612_736),
+            ("0.5KB", 500),
+            ("1_000KiB", 1_024_000),
+            ("16TiB", 17_592_186_044_416),
+        ] {
+            assert_eq!(parse_byte_size(text), Ok(bytes), "{text}");
+        }
+    }
+
+    #[test]
+    fn invalid_sizes_are_rejected() {
+        assert_eq!(
+            parse_byte_size("1.5B"),
+            Err("'1.5B' is not a whole number of bytes".into())
+        );
+        assert_eq!(
+            parse_byte_size("0.001KiB"),
+            Err("'0.001KiB' is not a whole number of bytes".into())
+        );
+        assert_eq!(
+            parse_byte_size("20000000TiB"),
+            Err("'20000000TiB' does not fit in u64".into())
+        );
+        assert_eq!(
+            parse_byte_size("25mb"),
+            Err("'25mb' has unknown unit 'mb' (expected B, KB, MB, GB, TB, KiB, MiB, GiB or TiB)".into())
+        );
+        for text in ["MiB", "-1KB", ".5KB", "1.2.3MB", "1e3B"] {
+            assert!(parse_byte_size(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn sizes_emit_u64_constants() {
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = ByteSizeType
+            .build_node(&byte_size("25MiB"), origin)
+            .unwrap();
+        let key = ResourceKey::from_path("max_upload");
+
+        assert_eq!(node.meta.doc.as_deref(), Some("`25MiB` in bytes"));
+        assert_eq!(
+            ByteSizeType.emit_rust(&key, &node, 4).unwrap(),
+            "    pub const MAX_UPLOAD: u64 = 26_214_400;\n"
+        );
+        assert_eq!(group_digits(1024), "1024");
+        assert_eq!(group_digits(10_000), "10_000");
+    }
+}
@@ -0,0 +1,293 @@
+//! `<char>`, a single Unicode scalar value, or a short sequence of them
+//! with `grapheme="true"`.
+//!
+//! ```xml
+//! <char name="icon_search">\u{F002}</char>
+//! <char name="icon_home">U+F015</char>
+//! <char name="thumbs_up" grapheme="true">👍🏽</char>
+//! <!--
+//! pub const ICON_SEARCH: char = '\u{F002}';
+//! pub const ICON_HOME: char = '\u{F015}';
+//! pub const THUMBS_UP: &str = "\u{1F44D}\u{1F3FD}";
+//! -->
+//! ```
+//!
+//! The value is a literal character, `\u{...}` escapes (`\\` for a
+//! backslash) or space-separated `U+` code points. Anything but
+//! printable ASCII is generated as an escape, so private-use glyphs
+//! stay readable in the generated code. Graphemes are not segmented:
+//! any non-empty sequence is accepted.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+pub struct CharType;
+
+impl ResourceType for CharType {
+    fn name(&self) -> &'static str {
+        "char"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["char"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Char
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Char { text, grapheme } => {
+                parse_chars(text, *grapheme).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Char { text, grapheme } = &parsed.value
+        else {
+            return None;
+        };
+        let chars = parse_chars(text, *grapheme).ok()?;
+        let value = match chars.as_slice() {
+            [c] if !grapheme => ResourceValue::Char(*c),
+            _ => ResourceValue::Grapheme(chars.into_iter().collect()),
+        };
+        Some(ResourceNode::new(ResourceKind::Char, value, origin))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        match &node.value {
+            ResourceValue::Char(c) => Some(format!(
+                "{pad}pub const {name}: char = '{}';\n",
+                escaped(*c, '\'')
+            )),
+            ResourceValue::Grapheme(text) => {
+                let text: String =
+                    text.chars().map(|c| escaped(c, '"')).collect();
+                Some(format!(
+                    "{pad}pub const {name}: &str = \"{text}\";\n"
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `c` inside a literal quoted with `quote`: printable ASCII as is,
+/// anything else as `\u{F002}`
+fn escaped(c: char, quote: char) -> String {
+    match c {
+        '\\' => "\\\\".to_string(),
+        c if c == quote => format!("\\{c}"),
+        ' ' => " ".to_string(),
+        c if c.is_ascii_graphic() => c.to_string(),
+        c => format!("\\u{{{:X}}}", u32::from(c)),
+    }
+}
+
+/// Scalar value of the code point `value`
+fn scalar(value: u32) -> Result<char, String> {
+    match value {
+        0xD800..=0xDFFF => Err(format!(
+            "U+{value:04X} is a surrogate, not a Unicode scalar value"
+        )),
+        _ => char::from_u32(value)
+            .ok_or_else(|| format!("U+{value:X} is beyond U+10FFFF")),
+    }
+}
+
+/// Code point of 1 to 6 hex digits
+fn hex(digits: &str) -> Option<u32> {
+    let valid = (1..=6).contains(&digits.len())
+        && digits.bytes().all(|b| b.is_ascii_hexdigit());
+    valid
+        .then(|| u32::from_str_radix(digits, 16).ok())
+        .flatten()
+}
+
+/// `U+F002` or `U+1F44D U+1F3FD`
+fn code_points(text: &str) -> Result<Vec<char>, String> {
+    text.split_whitespace()
+        .map(|point| {
+            let digits = point
+                .strip_prefix("U+")
+                .or_else(|| point.strip_prefix("u+"))
+                .filter(|digits| digits.len() >= 4);
+            let value = digits.and_then(hex).ok_or_else(|| {
+                format!(
+                    "'{point}' is not a code point (U+ and 4 to 6 hex digits)"
+                )
+            })?;
+            scalar(value)
+        })
+        .collect()
+}
+
+/// Literal characters and `\u{...}` or `\\` escapes
+fn unescape(text: &str) -> Result<Vec<char>, String> {
+    let mut chars = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        if c != '\\' {
+            chars.push(c);
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('\\') {
+            chars.push('\\');
+            rest = after;
+            continue;
+        }
+        let escape = rest
+            .strip_prefix("u{")
+            .and_then(|body| body.split_once('}'));
+        let Some((digits, after)) = escape else {
+            let end = rest.chars().next().map_or(0, char::len_utf8);
+            return Err(format!(
+                "`\\{}` is not a `\\u{{...}}` escape",
+                &rest[..end]
+            ));
+        };
+        let value = hex(digits).ok_or_else(|| {
+            format!("`\\u{{{digits}}}` needs 1 to 6 hex digits")
+        })?;
+        chars.push(scalar(value)?);
+        rest = after;
+    }
+    Ok(chars)
+}
+
+/// Scalar values of a `<char>`, exactly one unless `grapheme`
+fn parse_chars(
+    text: &str,
+    grapheme: bool,
+) -> Result<Vec<char>, String> {
+    let chars = if text.starts_with("U+") || text.starts_with("u+") {
+        code_points(text)?
+    } else {
+        unescape(text)?
+    };
+    match chars.len() {
+        1 => Ok(chars),
+        0 => Err("expected a character".to_string()),
+        _ if grapheme => Ok(chars),
+        count => Err(format!(
+            "'{text}' is {count} Unicode scalars, not one; add grapheme=\"true\" for a sequence"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn emit(text: &str, grapheme: bool) -> String {
+        let parsed = ParsedResource {
+            name: "icon".to_string(),
+            kind: AstResourceKind::Char,
+            value: ScalarValue::Char {
+                text: text.to_string(),
+                grapheme,
+            },
+            meta: Default::default(),
+        };
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = CharType.build_node(&parsed, origin).unwrap();
+        let key = ResourceKey::from_path("icon");
+        CharType.emit_rust(&key, &node, 4).unwrap()
+    }
+
+    #[test]
+    fn every_notation_names_the_same_char() {
+        let expected = "    pub const ICON: char = '\\u{F002}';\n";
+        assert_eq!(emit("\u{F002}", false), expected);
+        assert_eq!(emit("\\u{F002}", false), expected);
+        assert_eq!(emit("\\u{f002}", false), expected);
+        assert_eq!(emit("U+F002", false), expected);
+        assert_eq!(
+            emit("a", false),
+            "    pub const ICON: char = 'a';\n"
+        );
+        assert_eq!(
+            emit("'", false),
+            "    pub const ICON: char = '\\'';\n"
+        );
+        assert_eq!(
+            emit("\\\\", false),
+            "    pub const ICON: char = '\\\\';\n"
+        );
+        assert_eq!(
+            emit("é", false),
+            "    pub const ICON: char = '\\u{E9}';\n"
+        );
+    }
+
+    #[test]
+    fn graphemes_keep_their_variation_selectors() {
+        // Heart and VARIATION SELECTOR-16, for the emoji presentation
+        assert_eq!(
+            emit("❤️", true),
+            "    pub const ICON: &str = \"\\u{2764}\\u{FE0F}\";\n"
+        );
+        assert_eq!(
+            emit("U+1F44D U+1F3FD", true),
+            "    pub const ICON: &str = \"\\u{1F44D}\\u{1F3FD}\";\n"
+        );
+        assert_eq!(
+            emit("\"", true),
+            "    pub const ICON: &str = \"\\\"\";\n"
+        );
+        assert_eq!(
+            parse_chars("❤️", false),
+            Err("'❤️' is 2 Unicode scalars, not one; add grapheme=\"true\" for a sequence".into())
+        );
+    }
+
+    #[test]
+    fn surrogates_and_bad_escapes_are_rejected() {
+        for (text, message) in [
+            ("\\u{D800}", "U+D800 is a surrogate, not a Unicode scalar value"),
+            ("U+DFFF", "U+DFFF is a surrogate, not a Unicode scalar value"),
+            ("\\u{110000}", "U+110000 is beyond U+10FFFF"),
+            ("\\u{}", "`\\u{}` needs 1 to 6 hex digits"),
+            ("\\u{1F44D1F}", "`\\u{1F44D1F}` needs 1 to 6 hex digits"),
+            ("\\uF002", "`\\u` is not a `\\u{...}` escape"),
+            ("\\n", "`\\n` is not a `\\u{...}` escape"),
+            ("\\", "`\\` is not a `\\u{...}` escape"),
+            ("U+F0", "'U+F0' is not a code point (U+ and 4 to 6 hex digits)"),
+            ("U+F00G", "'U+F00G' is not a code point (U+ and 4 to 6 hex digits)"),
+            ("ab", "'ab' is 2 Unicode scalars, not one; add grapheme=\"true\" for a sequence"),
+        ] {
+            assert_eq!(
+                parse_chars(text, false),
+                Err(message.to_string()),
+                "{text}"
+            );
+        }
+    }
+}
@@ -1,10 +1,11 @@
+use crate::generator::color::{canonical_hex_color, parse_hex_color};
 use crate::generator::ir::types::ResourceType;
 use crate::generator::ir::{
     ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::utils::{const_identifier, escape_str};
 
 pub struct ColorType;
 
@@ -21,17 +22,26 @@ impl ResourceType for ColorType {
         ResourceKind::Color
     }
 
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Color(value) => parse_hex_color(value)
+                .map(|_| ())
+                .map_err(|e| format!("invalid color '{value}': {e}")),
+            _ => Ok(()),
+        }
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
         origin: ResourceOrigin,
     ) -> Option<ResourceNode> {
         if let ScalarValue::Color(value) = &parsed.value {
-            Some(ResourceNode {
-                kind: ResourceKind::Color,
-                value: ResourceValue::Color(value.clone()),
+            Some(ResourceNode::new(
+                ResourceKind::Color,
+                ResourceValue::Color(canonical_hex_color(value)),
                 origin,
-            })
+            ))
         } else {
             None
         }
@@ -46,8 +56,8 @@ impl ResourceType for ColorType {
         if let ResourceValue::Color(value) = &node.value {
             let pad = " ".repeat(indent);
             let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
-            let escaped = value.escape_debug();
+                const_identifier(&key.name);
+            let escaped = escape_str(value);
             Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
         } else {
             None
@@ -93,6 +103,7 @@ mod tests {
             name: "primary_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("#FF0000".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -106,6 +117,45 @@ mod tests {
         assert_eq!(result.origin.file, origin.file);
     }
 
+    // Test validate against the accepted hex forms
+    #[test]
+    fn test_validate_hex_colors() {
+        let handler = ColorType;
+        let color = |value: &str| ParsedResource {
+            name: "accent".to_string(),
+            kind: AstResourceKind::Color,
+            value: ScalarValue::Color(value.to_string()),
+            meta: Default::default(),
+        };
+        assert!(handler.validate(&color("#f00")).is_ok());
+        assert!(handler.validate(&color("#FF5722")).is_ok());
+        assert!(handler.validate(&color("#80ff5722")).is_ok());
+        assert_eq!(
+            handler.validate(&color("#GG5722")),
+            Err("invalid color '#GG5722': 'G' is not a hex digit".into())
+        );
+        assert!(handler.validate(&color("red")).is_err());
+    }
+
+    // Test build_node expands the #RGB shorthand
+    #[test]
+    fn test_build_node_expands_shorthand() {
+        let handler = ColorType;
+        let parsed = ParsedResource {
+            name: "accent".to_string(),
+            kind: AstResourceKind::Color,
+            value: ScalarValue::Color("#f0a".to_string()),
+            meta: Default::default(),
+        };
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+
+        let result = handler.build_node(&parsed, origin).unwrap();
+        assert!(matches!(
+            result.value,
+            ResourceValue::Color(ref s) if s == "#ff00aa"
+        ));
+    }
+
     // Test build_node with different color formats
     #[test]
     fn test_build_node_rgb_color() {
@@ -114,6 +164,7 @@ mod tests {
             name: "bg_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("rgb(255, 0, 0)".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -132,6 +183,7 @@ mod tests {
             name: "text_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Color("red".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -150,6 +202,7 @@ mod tests {
             name: "not_color".to_string(),
             kind: AstResourceKind::Color,
             value: ScalarValue::Text("not a color".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -165,6 +218,7 @@ mod tests {
             name: "not_color".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -177,14 +231,14 @@ mod tests {
     fn test_emit_rust_color() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "primary_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("#FF0000".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "primary_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("#FF0000".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const PRIMARY_COLOR: &str"));
@@ -196,14 +250,14 @@ mod tests {
     fn test_emit_rust_rgb_color() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "bg_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("rgb(255, 0, 0)".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "bg_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("rgb(255, 0, 0)".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const BG_COLOR: &str"));
@@ -215,14 +269,14 @@ mod tests {
     fn test_emit_rust_named_color() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "text_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("blue".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "text_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("blue".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const TEXT_COLOR: &str"));
@@ -234,18 +288,18 @@ mod tests {
     fn test_emit_rust_color_with_special_chars() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "special_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("#FF\"test\"".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "special_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("#FF\"test\"".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const SPECIAL_COLOR: &str"));
-        // The value should be escaped (escape_debug escapes quotes)
+        // The value should be escaped (escape_str escapes quotes)
         // The escaped version should not contain raw quotes
         assert!(result.contains("#FF"));
         // Verify it's a valid string literal (contains quotes)
@@ -257,14 +311,14 @@ mod tests {
     fn test_emit_rust_wrong_value_type() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "not_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Bool(true),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "not_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Bool(true),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4);
         assert!(result.is_none());
@@ -275,14 +329,14 @@ mod tests {
     fn test_emit_rust_indentation() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "test_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("#000000".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "test_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("#000000".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
         // Should have 8 spaces of indentation
@@ -294,14 +348,14 @@ mod tests {
     fn test_emit_rust_namespaced() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec!["ui".to_string(), "theme".to_string()],
-            name: "primary".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("#FF0000".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec!["ui".to_string(), "theme".to_string()].into(),
+            name: "primary".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("#FF0000".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const PRIMARY: &str"));
@@ -313,14 +367,14 @@ mod tests {
     fn test_emit_rust_empty_color() {
         let handler = ColorType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "empty_color".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Color,
-            value: ResourceValue::Color("".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "empty_color".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Color,
+            ResourceValue::Color("".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const EMPTY_COLOR: &str"));
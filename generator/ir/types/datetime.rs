@@ -0,0 +1,395 @@
+//! `<datetime>` and `<date>`, checked calendar values.
+//!
+//! ```xml
+//! <datetime name="promo_ends">2025-12-31T23:59:59Z</datetime>
+//! <date name="launch">2025-06-01</date>
+//! <!--
+//! pub const PROMO_ENDS_UNIX: i64 = 1767225599;
+//! pub const PROMO_ENDS_STR: &str = "2025-12-31T23:59:59Z";
+//! pub const LAUNCH: r_resources::Date = r_resources::Date::new(2025, 6, 1);
+//! -->
+//! ```
+//!
+//! Date-times follow RFC 3339 (`T`, `t` or a space between the date
+//! and the time, `Z` or a `+02:00` offset) and are converted to UTC
+//! seconds since the Unix epoch; fractional and leap seconds are
+//! rejected, as `_UNIX` counts whole seconds. Dates are `YYYY-MM-DD`.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::{const_identifier, escape_str};
+
+pub struct DateTimeType;
+
+impl ResourceType for DateTimeType {
+    fn name(&self) -> &'static str {
+        "datetime"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["datetime"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::DateTime
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::DateTime(text) => {
+                parse_datetime(text).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::DateTime(text) = &parsed.value else {
+            return None;
+        };
+        let value = ResourceValue::DateTime {
+            unix: parse_datetime(text).ok()?,
+            text: text.trim().to_string(),
+        };
+        Some(ResourceNode::new(ResourceKind::DateTime, value, origin))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::DateTime { unix, text } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        Some(format!(
+            "{pad}pub const {name}_UNIX: i64 = {unix};\n\
+             {pad}/// [`{name}_UNIX`] as written\n\
+             {pad}pub const {name}_STR: &str = \"{}\";\n",
+            escape_str(text)
+        ))
+    }
+}
+
+pub struct DateType;
+
+impl ResourceType for DateType {
+    fn name(&self) -> &'static str {
+        "date"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["date"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Date
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Date(text) => {
+                let text = text.trim();
+                parse_date(text).map(|_| ()).map_err(|e| {
+                    format!("'{text}' is not a date: {e}")
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Date(text) = &parsed.value else {
+            return None;
+        };
+        let (year, month, day) = parse_date(text.trim()).ok()?;
+        let value = ResourceValue::Date { year, month, day };
+        Some(ResourceNode::new(ResourceKind::Date, value, origin))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Date { year, month, day } = node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        Some(format!(
+            "{pad}pub const {name}: r_resources::Date = r_resources::Date::new({year}, {month}, {day});\n"
+        ))
+    }
+}
+
+/// `2025-06-01`
+pub fn date_text(year: u16, month: u8, day: u8) -> String {
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Number of `len` ASCII digits starting at `start` of `text`
+fn digits(text: &str, start: usize, len: usize) -> Option<u32> {
+    let part = text.get(start..start + len)?;
+    if !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    part.parse().ok()
+}
+
+/// Checks that `min <= value <= max`, naming the field otherwise
+fn in_range(
+    what: &str,
+    value: u32,
+    min: u32,
+    max: u32,
+) -> Result<(), String> {
+    if (min..=max).contains(&value) {
+        return Ok(());
+    }
+    Err(format!(
+        "{what} {value:02} is out of range ({min:02} to {max:02})"
+    ))
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4)
+        && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Year, month and day of `YYYY-MM-DD`
+fn parse_date(text: &str) -> Result<(u16, u8, u8), String> {
+    let separators = text.len() == 10
+        && text.as_bytes()[4] == b'-'
+        && text.as_bytes()[7] == b'-';
+    let (Some(year), Some(month), Some(day), true) = (
+        digits(text, 0, 4),
+        digits(text, 5, 2),
+        digits(text, 8, 2),
+        separators,
+    ) else {
+        return Err("expected YYYY-MM-DD".to_string());
+    };
+    in_range("month", month, 1, 12)?;
+    // Both fit, by their number of digits and the range check
+    let (year, month) = (year as u16, month as u8);
+    let last = days_in_month(year, month);
+    if !(1..=u32::from(last)).contains(&day) {
+        return Err(format!(
+            "day {day:02} is out of range for {year:04}-{month:02} (01 to {last})"
+        ));
+    }
+    Ok((year, month, day as u8))
+}
+
+/// Days from 1970-01-01 to a valid date, negative before it
+fn days_since_epoch(year: u16, month: u8, day: u8) -> i64 {
+    // Years starting in March put the leap day last
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year =
+        (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5
+            + i64::from(day)
+            - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4
+        - year_of_era / 100
+        + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// UTC seconds since the Unix epoch of an RFC 3339 date-time
+fn parse_datetime(text: &str) -> Result<i64, String> {
+    let text = text.trim();
+    let invalid = |reason: String| {
+        format!("'{text}' is not an RFC 3339 date-time: {reason}")
+    };
+    let layout = || {
+        invalid(
+            "expected YYYY-MM-DDTHH:MM:SS followed by Z or an offset like +02:00"
+                .to_string(),
+        )
+    };
+    let (Some(date), Some(time)) = (text.get(..10), text.get(11..19))
+    else {
+        return Err(layout());
+    };
+    let (year, month, day) = parse_date(date).map_err(&invalid)?;
+    let time_separators =
+        time.as_bytes()[2] == b':' && time.as_bytes()[5] == b':';
+    let (Some(hour), Some(minute), Some(second), true, true) = (
+        digits(time, 0, 2),
+        digits(time, 3, 2),
+        digits(time, 6, 2),
+        time_separators,
+        matches!(text.as_bytes()[10], b'T' | b't' | b' '),
+    ) else {
+        return Err(layout());
+    };
+    in_range("hour", hour, 0, 23).map_err(&invalid)?;
+    in_range("minute", minute, 0, 59).map_err(&invalid)?;
+    in_range("second", second, 0, 59).map_err(&invalid)?;
+
+    let zone = &text[19..];
+    if zone.starts_with('.') {
+        return Err(invalid(
+            "fractional seconds are not supported, `_UNIX` counts whole seconds"
+                .to_string(),
+        ));
+    }
+    let offset = match zone {
+        "Z" | "z" => 0,
+        _ => {
+            let sign = match zone.as_bytes().first() {
+                Some(b'+') => 1,
+                Some(b'-') => -1,
+                _ => return Err(layout()),
+            };
+            let (6, Some(hours), Some(minutes)) =
+                (zone.len(), digits(zone, 1, 2), digits(zone, 4, 2))
+            else {
+                return Err(layout());
+            };
+            if zone.as_bytes()[3] != b':' {
+                return Err(layout());
+            }
+            in_range("offset hour", hours, 0, 23)
+                .map_err(&invalid)?;
+            in_range("offset minute", minutes, 0, 59)
+                .map_err(&invalid)?;
+            sign * i64::from(hours * 3600 + minutes * 60)
+        }
+    };
+    let seconds = i64::from(hour * 3600 + minute * 60 + second);
+    // `10:00+02:00` is 08:00 UTC
+    Ok(
+        days_since_epoch(year, month, day) * 86_400 + seconds
+            - offset,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leap_years_have_a_29th_of_february() {
+        for date in ["2024-02-29", "2000-02-29", "1996-02-29"] {
+            assert!(parse_date(date).is_ok(), "{date}");
+        }
+        assert_eq!(
+            parse_date("1900-02-29"),
+            Err("day 29 is out of range for 1900-02 (01 to 28)"
+                .into())
+        );
+        assert_eq!(
+            parse_date("2025-02-29"),
+            Err("day 29 is out of range for 2025-02 (01 to 28)"
+                .into())
+        );
+        assert_eq!(
+            parse_date("2025-04-31"),
+            Err("day 31 is out of range for 2025-04 (01 to 30)"
+                .into())
+        );
+        assert_eq!(
+            parse_date("2025-13-01"),
+            Err("month 13 is out of range (01 to 12)".into())
+        );
+        for date in
+            ["2025-6-01", "2025/06/01", "25-06-01", "2025-06-01T"]
+        {
+            assert_eq!(
+                parse_date(date),
+                Err("expected YYYY-MM-DD".into()),
+                "{date}"
+            );
+        }
+    }
+
+    #[test]
+    fn days_count_from_the_epoch() {
+        assert_eq!(days_since_epoch(1970, 1, 1), 0);
+        assert_eq!(days_since_epoch(1969, 12, 31), -1);
+        assert_eq!(days_since_epoch(2000, 3, 1), 11_017);
+        assert_eq!(days_since_epoch(2024, 2, 29), 19_782);
+        assert_eq!(days_since_epoch(2024, 3, 1), 19_783);
+        assert_eq!(days_since_epoch(0, 1, 1), -719_528);
+    }
+
+    #[test]
+    fn datetimes_are_converted_to_utc() {
+        for (text, unix) in [
+            ("1970-01-01T00:00:00Z", 0),
+            ("1969-12-31T23:59:59Z", -1),
+            ("2025-12-31T23:59:59Z", 1_767_225_599),
+            ("2025-12-31t23:59:59z", 1_767_225_599),
+            ("2025-12-31 23:59:59Z", 1_767_225_599),
+            ("2026-01-01T01:59:59+02:00", 1_767_225_599),
+            ("2025-12-31T18:29:59-05:30", 1_767_225_599),
+            ("2025-12-31T23:59:59-00:00", 1_767_225_599),
+            ("2024-02-29T12:00:00Z", 1_709_208_000),
+        ] {
+            assert_eq!(parse_datetime(text), Ok(unix), "{text}");
+        }
+    }
+
+    #[test]
+    fn malformed_datetimes_are_rejected() {
+        let layout = "expected YYYY-MM-DDTHH:MM:SS followed by Z or an offset like +02:00";
+        for (text, reason) in [
+            ("2025-12-31", layout),
+            ("2025-12-31T23:59:59", layout),
+            ("2025-12-31T23:59Z", layout),
+            ("2025-12-31T23:59:59+0200", layout),
+            ("2025-12-31X23:59:59Z", layout),
+            ("2025-02-29T00:00:00Z", "day 29 is out of range for 2025-02 (01 to 28)"),
+            ("2025-12-31T24:00:00Z", "hour 24 is out of range (00 to 23)"),
+            ("2025-12-31T23:59:60Z", "second 60 is out of range (00 to 59)"),
+            ("2025-12-31T23:59:59+24:00", "offset hour 24 is out of range (00 to 23)"),
+            (
+                "2025-12-31T23:59:59.5Z",
+                "fractional seconds are not supported, `_UNIX` counts whole seconds",
+            ),
+        ] {
+            assert_eq!(
+                parse_datetime(text),
+                Err(format!("'{text}' is not an RFC 3339 date-time: {reason}")),
+            );
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! `<latlng>` and `<position>`, generated as `r_resources::LatLng` and
+//! `r_resources::Position` constants.
+//!
+//! Both are attribute-only elements:
+//!
+//! ```xml
+//! <latlng name="hq" lat="48.8566" lng="2.3522"/>
+//! <position name="origin" x="0" y="-12.5"/>
+//! ```
+
+use crate::generator::ir::types::number::format_float64;
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+pub struct LatLngType;
+
+impl ResourceType for LatLngType {
+    fn name(&self) -> &'static str {
+        "latlng"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["latlng"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::LatLng
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::LatLng { lat, lng } => {
+                parse_lat_lng(lat, lng).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::LatLng { lat, lng } = &parsed.value else {
+            return None;
+        };
+        let (lat, lng) = parse_lat_lng(lat, lng).ok()?;
+        Some(ResourceNode::new(
+            ResourceKind::LatLng,
+            ResourceValue::LatLng { lat, lng },
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::LatLng { lat, lng } = node.value else {
+            return None;
+        };
+        Some(emit_const(key, "LatLng", lat, lng, indent))
+    }
+}
+
+pub struct PositionType;
+
+impl ResourceType for PositionType {
+    fn name(&self) -> &'static str {
+        "position"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["position"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Position
+    }
+
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Position { x, y } => {
+                parse_coordinate(x, "x coordinate")?;
+                parse_coordinate(y, "y coordinate").map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Position { x, y } = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode::new(
+            ResourceKind::Position,
+            ResourceValue::Position {
+                x: parse_coordinate(x, "x coordinate").ok()?,
+                y: parse_coordinate(y, "y coordinate").ok()?,
+            },
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Position { x, y } = node.value else {
+            return None;
+        };
+        Some(emit_const(key, "Position", x, y, indent))
+    }
+}
+
+/// Latitude and longitude in degrees; `|lat| > 90` and `|lng| > 180`
+/// are rejected
+fn parse_lat_lng(lat: &str, lng: &str) -> Result<(f64, f64), String> {
+    let lat = parse_coordinate(lat, "latitude")?;
+    let lng = parse_coordinate(lng, "longitude")?;
+    if lat.abs() > 90.0 {
+        return Err(format!(
+            "latitude {lat} is out of range (-90 to 90)"
+        ));
+    }
+    if lng.abs() > 180.0 {
+        return Err(format!(
+            "longitude {lng} is out of range (-180 to 180)"
+        ));
+    }
+    Ok((lat, lng))
+}
+
+/// Finite number from an attribute value
+fn parse_coordinate(value: &str, what: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("'{value}' is not a valid {what}"))
+}
+
+fn emit_const(
+    key: &ResourceKey,
+    ty: &str,
+    first: f64,
+    second: f64,
+    indent: usize,
+) -> String {
+    let pad = " ".repeat(indent);
+    let const_name = const_identifier(&key.name);
+    format!(
+        "{pad}pub const {const_name}: r_resources::{ty} = r_resources::{ty}::new({}, {});\n",
+        format_float64(first),
+        format_float64(second)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn latlng(lat: &str, lng: &str) -> ParsedResource {
+        ParsedResource {
+            name: "hq".to_string(),
+            kind: AstResourceKind::LatLng,
+            value: ScalarValue::LatLng {
+                lat: lat.to_string(),
+                lng: lng.to_string(),
+            },
+            meta: Default::default(),
+        }
+    }
+
+    fn position(x: &str, y: &str) -> ParsedResource {
+        ParsedResource {
+            name: "origin".to_string(),
+            kind: AstResourceKind::Position,
+            value: ScalarValue::Position {
+                x: x.to_string(),
+                y: y.to_string(),
+            },
+            meta: Default::default(),
+        }
+    }
+
+    fn emit(ty: &dyn ResourceType, parsed: &ParsedResource) -> String {
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = ty.build_node(parsed, origin).unwrap();
+        let key = ResourceKey::from_path(&parsed.name);
+        ty.emit_rust(&key, &node, 4).unwrap()
+    }
+
+    #[test]
+    fn latlng_emits_a_const_constructor() {
+        assert_eq!(
+            emit(&LatLngType, &latlng("48.8566", "2.3522")),
+            "    pub const HQ: r_resources::LatLng = r_resources::LatLng::new(48.8566, 2.3522);\n"
+        );
+        assert_eq!(
+            emit(&LatLngType, &latlng("-90", "180")),
+            "    pub const HQ: r_resources::LatLng = r_resources::LatLng::new(-90.0, 180.0);\n"
+        );
+    }
+
+    #[test]
+    fn latlng_rejects_out_of_range_coordinates() {
+        assert!(LatLngType.validate(&latlng("90", "-180")).is_ok());
+        assert_eq!(
+            LatLngType.validate(&latlng("90.5", "0")),
+            Err("latitude 90.5 is out of range (-90 to 90)".into())
+        );
+        assert_eq!(
+            LatLngType.validate(&latlng("0", "-181")),
+            Err("longitude -181 is out of range (-180 to 180)".into())
+        );
+        assert_eq!(
+            LatLngType.validate(&latlng("north", "0")),
+            Err("'north' is not a valid latitude".into())
+        );
+        assert_eq!(
+            LatLngType.validate(&latlng("0", "NaN")),
+            Err("'NaN' is not a valid longitude".into())
+        );
+    }
+
+    #[test]
+    fn position_emits_a_const_constructor() {
+        assert_eq!(
+            emit(&PositionType, &position("0", "-12.5")),
+            "    pub const ORIGIN: r_resources::Position = r_resources::Position::new(0.0, -12.5);\n"
+        );
+        assert_eq!(
+            PositionType.validate(&position("1", "inf")),
+            Err("'inf' is not a valid y coordinate".into())
+        );
+    }
+}
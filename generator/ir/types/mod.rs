@@ -3,14 +3,40 @@
 //! Pour ajouter un nouveau type, créez simplement un fichier `ir/types/your_type.rs`
 //! et implémentez le trait `ResourceType`.
 mod bool;
+mod bytes;
+mod bytesize;
+mod char;
 mod color;
+mod datetime;
+mod geometry;
+mod money;
+mod net;
 mod number;
+mod regex;
+mod semver;
 mod string;
 mod template;
+mod uuid;
+
+pub use bytes::hex_text;
+pub use datetime::date_text;
+pub use money::money_text;
+pub use number::{
+    compare_numbers, display_literal, float_value, integer_value,
+    number_like, parse_number_type, pointer_width_error, range_doc,
+    typed_float, typed_integer, widen_graph, DEFAULT_POINTER_WIDTH,
+    POINTER_WIDTHS,
+};
+pub use regex::regex_static;
+pub use template::{
+    template_format_call, template_signature, TemplateSignature,
+};
+pub use uuid::uuid_text;
 
 use crate::generator::ir::{
     ResourceKey, ResourceNode, ResourceOrigin,
 };
+use crate::generator::options::LazyStatics;
 use crate::generator::parsing::ParsedResource;
 
 /// Trait that each resource type must implement
@@ -25,6 +51,12 @@ pub trait ResourceType: Send + Sync {
     /// Returns the ResourceKind corresponding to this type
     fn resource_kind(&self) -> crate::generator::ir::ResourceKind;
 
+    /// Checks the parsed value before `build_node`; errors are reported
+    /// with the file and resource name
+    fn validate(&self, _parsed: &ParsedResource) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Converts a `ParsedResource` into a `ResourceNode`
     fn build_node(
         &self,
@@ -39,6 +71,18 @@ pub trait ResourceType: Send + Sync {
         node: &ResourceNode,
         indent: usize,
     ) -> Option<String>;
+
+    /// Generates Rust code for this type, with `lazy` as the type of
+    /// the statics initialized on first use
+    fn emit_rust_with_lazy(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        _lazy: LazyStatics,
+    ) -> Option<String> {
+        self.emit_rust(key, node, indent)
+    }
 }
 
 /// Global registry for resource types
@@ -93,6 +137,19 @@ impl Default for TypeRegistry {
         registry.register(Box::new(bool::BoolType));
         registry.register(Box::new(color::ColorType));
         registry.register(Box::new(template::TemplateType));
+        registry.register(Box::new(geometry::LatLngType));
+        registry.register(Box::new(geometry::PositionType));
+        registry.register(Box::new(bytesize::ByteSizeType));
+        registry.register(Box::new(semver::SemverType));
+        registry.register(Box::new(net::IpAddrType));
+        registry.register(Box::new(net::SocketAddrType));
+        registry.register(Box::new(regex::RegexType));
+        registry.register(Box::new(uuid::UuidType));
+        registry.register(Box::new(datetime::DateTimeType));
+        registry.register(Box::new(datetime::DateType));
+        registry.register(Box::new(money::MoneyType));
+        registry.register(Box::new(char::CharType));
+        registry.register(Box::new(bytes::BytesType));
         registry
     }
 }
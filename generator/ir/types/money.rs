@@ -0,0 +1,281 @@
+//! `<money>`, an amount of a currency generated as whole minor units.
+//!
+//! ```xml
+//! <money name="pro_plan" currency="USD" scale="2">9.99</money>
+//! <!--
+//! pub const PRO_PLAN: r_resources::Money = r_resources::Money::new(999, 2, "USD");
+//! -->
+//! ```
+//!
+//! `scale` is the number of decimal places of the minor unit, `2` when
+//! left out (cents); amounts with more decimal places than the scale
+//! are rejected rather than rounded, as are amounts beyond `i64` minor
+//! units.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+/// Scale of a `<money>` without a `scale` attribute
+const DEFAULT_SCALE: u8 = 2;
+/// Largest scale whose unit, `10^scale`, fits in an `i64`
+const MAX_SCALE: u8 = 18;
+
+pub struct MoneyType;
+
+impl ResourceType for MoneyType {
+    fn name(&self) -> &'static str {
+        "money"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["money"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Money
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Money {
+                amount,
+                currency,
+                scale,
+            } => parse_money(amount, currency, scale.as_deref())
+                .map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Money {
+            amount,
+            currency,
+            scale,
+        } = &parsed.value
+        else {
+            return None;
+        };
+        let (minor_units, scale) =
+            parse_money(amount, currency, scale.as_deref()).ok()?;
+        Some(ResourceNode::new(
+            ResourceKind::Money,
+            ResourceValue::Money {
+                minor_units,
+                scale,
+                currency: currency.clone(),
+            },
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Money {
+            minor_units,
+            scale,
+            currency,
+        } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        Some(format!(
+            "{pad}pub const {name}: r_resources::Money = r_resources::Money::new({minor_units}, {scale}, \"{currency}\");\n"
+        ))
+    }
+}
+
+/// `9.99 USD`, the way `r_resources::Money` displays
+pub fn money_text(
+    minor_units: i64,
+    scale: u8,
+    currency: &str,
+) -> String {
+    let digits = minor_units.unsigned_abs().to_string();
+    let scale = usize::from(scale);
+    // At least one digit before the point
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if minor_units < 0 { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{sign}{whole} {currency}")
+    } else {
+        format!("{sign}{whole}.{fraction} {currency}")
+    }
+}
+
+/// Minor units and scale of `amount` in `currency`
+fn parse_money(
+    amount: &str,
+    currency: &str,
+    scale: Option<&str>,
+) -> Result<(i64, u8), String> {
+    if currency.len() != 3
+        || !currency.bytes().all(|b| b.is_ascii_uppercase())
+    {
+        return Err(format!(
+            "currency '{currency}' is not an ISO 4217 code (three uppercase letters)"
+        ));
+    }
+    let scale = match scale.map(str::trim) {
+        None => DEFAULT_SCALE,
+        Some(text) => match text.parse::<u8>() {
+            Ok(scale) if scale <= MAX_SCALE => scale,
+            _ => {
+                return Err(format!(
+                    "scale '{text}' is not a number of decimal places (0 to {MAX_SCALE})"
+                ))
+            }
+        },
+    };
+    Ok((minor_units(amount.trim(), scale)?, scale))
+}
+
+/// `amount` in units of `10^-scale`, exactly
+fn minor_units(amount: &str, scale: u8) -> Result<i64, String> {
+    let (negative, unsigned) = match amount.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, amount),
+    };
+    let (whole, fraction) =
+        unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let is_digits = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit())
+    };
+    if !is_digits(whole)
+        || (unsigned.contains('.') && !is_digits(fraction))
+    {
+        return Err(format!("'{amount}' is not a decimal amount"));
+    }
+    if fraction.len() > usize::from(scale) {
+        return Err(format!(
+            "'{amount}' has more decimal places than the scale of {scale}"
+        ));
+    }
+    let padding = usize::from(scale) - fraction.len();
+    let digits = format!("{whole}{fraction}{}", "0".repeat(padding));
+    // Negative amounts reach one minor unit further, to `i64::MIN`
+    let overflow = || {
+        format!(
+            "'{amount}' overflows i64 minor units at scale {scale}"
+        )
+    };
+    let mut units: i64 = 0;
+    for digit in digits.bytes().map(|b| i64::from(b - b'0')) {
+        units = units
+            .checked_mul(10)
+            .and_then(|units| {
+                if negative {
+                    units.checked_sub(digit)
+                } else {
+                    units.checked_add(digit)
+                }
+            })
+            .ok_or_else(overflow)?;
+    }
+    Ok(units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn amounts_are_counted_in_minor_units() {
+        assert_eq!(
+            parse_money("9.99", "USD", Some("2")),
+            Ok((999, 2))
+        );
+        assert_eq!(parse_money(" 10 ", "EUR", None), Ok((1000, 2)));
+        assert_eq!(parse_money("9.9", "USD", None), Ok((990, 2)));
+        assert_eq!(
+            parse_money("1500", "JPY", Some("0")),
+            Ok((1500, 0))
+        );
+        assert_eq!(parse_money("-0.5", "USD", None), Ok((-50, 2)));
+        assert_eq!(
+            parse_money("0.00000001", "BTC", Some("8")),
+            Ok((1, 8))
+        );
+        assert_eq!(
+            parse_money("-92233720368547758.08", "USD", None),
+            Ok((i64::MIN, 2))
+        );
+    }
+
+    #[test]
+    fn amounts_display_with_their_scale() {
+        assert_eq!(money_text(999, 2, "USD"), "9.99 USD");
+        assert_eq!(money_text(-50, 2, "USD"), "-0.50 USD");
+        assert_eq!(money_text(1500, 0, "JPY"), "1500 JPY");
+        assert_eq!(money_text(1, 8, "BTC"), "0.00000001 BTC");
+        assert_eq!(
+            money_text(i64::MIN, 2, "USD"),
+            "-92233720368547758.08 USD"
+        );
+    }
+
+    #[test]
+    fn inexact_or_malformed_amounts_are_rejected() {
+        for (amount, currency, scale, message) in [
+            (
+                "9.999",
+                "USD",
+                Some("2"),
+                "'9.999' has more decimal places than the scale of 2",
+            ),
+            (
+                "1.5",
+                "JPY",
+                Some("0"),
+                "'1.5' has more decimal places than the scale of 0",
+            ),
+            (
+                "92233720368547758.08",
+                "USD",
+                None,
+                "'92233720368547758.08' overflows i64 minor units at scale 2",
+            ),
+            ("9,99", "EUR", None, "'9,99' is not a decimal amount"),
+            ("9.", "EUR", None, "'9.' is not a decimal amount"),
+            (".5", "EUR", None, "'.5' is not a decimal amount"),
+            ("+1", "EUR", None, "'+1' is not a decimal amount"),
+            (
+                "1",
+                "usd",
+                None,
+                "currency 'usd' is not an ISO 4217 code (three uppercase letters)",
+            ),
+            (
+                "1",
+                "USD",
+                Some("19"),
+                "scale '19' is not a number of decimal places (0 to 18)",
+            ),
+        ] {
+            assert_eq!(
+                parse_money(amount, currency, scale),
+                Err(message.to_string()),
+                "{amount}"
+            );
+        }
+    }
+}
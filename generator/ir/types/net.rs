@@ -0,0 +1,323 @@
+//! `<ipaddr>` and `<socketaddr>`, generated as `std::net::IpAddr` and
+//! `std::net::SocketAddr` constants.
+//!
+//! ```xml
+//! <ipaddr name="dns_primary">1.1.1.1</ipaddr>
+//! <ipaddr name="loopback_v6">::1</ipaddr>
+//! <socketaddr name="metrics_bind">0.0.0.0:9090</socketaddr>
+//! <socketaddr name="admin">[::1]:8080</socketaddr>
+//! ```
+//!
+//! IPv6 socket addresses are bracketed, as `SocketAddr` parses them.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+pub struct IpAddrType;
+
+impl ResourceType for IpAddrType {
+    fn name(&self) -> &'static str {
+        "ipaddr"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["ipaddr"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::IpAddr
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::IpAddr(text) => parse_ip(text).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::IpAddr(text) = &parsed.value else {
+            return None;
+        };
+        let value = ResourceValue::IpAddr(parse_ip(text).ok()?);
+        Some(ResourceNode::new(ResourceKind::IpAddr, value, origin))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::IpAddr(ip) = node.value else {
+            return None;
+        };
+        Some(emit_const(key, "IpAddr", &ip_expr(ip), indent))
+    }
+}
+
+pub struct SocketAddrType;
+
+impl ResourceType for SocketAddrType {
+    fn name(&self) -> &'static str {
+        "socketaddr"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["socketaddr"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::SocketAddr
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::SocketAddr(text) => {
+                parse_socket(text).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::SocketAddr(text) = &parsed.value else {
+            return None;
+        };
+        let value =
+            ResourceValue::SocketAddr(parse_socket(text).ok()?);
+        Some(ResourceNode::new(
+            ResourceKind::SocketAddr,
+            value,
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::SocketAddr(addr) = node.value else {
+            return None;
+        };
+        Some(emit_const(
+            key,
+            "SocketAddr",
+            &socket_expr(addr),
+            indent,
+        ))
+    }
+}
+
+/// IPv4 or IPv6 address
+fn parse_ip(text: &str) -> Result<IpAddr, String> {
+    let text = text.trim();
+    text.parse().map_err(|_| {
+        if text.starts_with('[') || text.contains("]:") {
+            format!("'{text}' is a socket address, use <socketaddr>")
+        } else {
+            format!("'{text}' is not a valid IP address")
+        }
+    })
+}
+
+/// `ip:port`, with the IPv6 addresses in brackets
+fn parse_socket(text: &str) -> Result<SocketAddr, String> {
+    let text = text.trim();
+    let Some((ip, port)) = text.rsplit_once(':') else {
+        return Err(format!("'{text}' is missing the ':port'"));
+    };
+    if port.parse::<u16>().is_err() {
+        return Err(match port.parse::<u64>() {
+            Ok(_) => {
+                format!("port {port} is out of range (0 to 65535)")
+            }
+            Err(_) => format!("'{port}' is not a valid port"),
+        });
+    }
+    if !ip.starts_with('[') && ip.contains(':') {
+        return Err(format!(
+            "'{text}' needs brackets around its IPv6 address ([{ip}]:{port})"
+        ));
+    }
+    text.parse().map_err(|_| {
+        format!("'{text}' is not a valid socket address")
+    })
+}
+
+fn ipv4_expr(ip: Ipv4Addr) -> String {
+    let [a, b, c, d] = ip.octets();
+    format!("std::net::Ipv4Addr::new({a}, {b}, {c}, {d})")
+}
+
+fn ipv6_expr(ip: Ipv6Addr) -> String {
+    let segments: Vec<String> = ip
+        .segments()
+        .iter()
+        .map(|segment| format!("{segment:#x}"))
+        .collect();
+    format!("std::net::Ipv6Addr::new({})", segments.join(", "))
+}
+
+fn ip_expr(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            format!("std::net::IpAddr::V4({})", ipv4_expr(ip))
+        }
+        IpAddr::V6(ip) => {
+            format!("std::net::IpAddr::V6({})", ipv6_expr(ip))
+        }
+    }
+}
+
+fn socket_expr(addr: SocketAddr) -> String {
+    match addr {
+        SocketAddr::V4(addr) => format!(
+            "std::net::SocketAddr::V4(std::net::SocketAddrV4::new({}, {}))",
+            ipv4_expr(*addr.ip()),
+            addr.port()
+        ),
+        SocketAddr::V6(addr) => format!(
+            "std::net::SocketAddr::V6(std::net::SocketAddrV6::new({}, {}, {}, {}))",
+            ipv6_expr(*addr.ip()),
+            addr.port(),
+            addr.flowinfo(),
+            addr.scope_id()
+        ),
+    }
+}
+
+fn emit_const(
+    key: &ResourceKey,
+    ty: &str,
+    expr: &str,
+    indent: usize,
+) -> String {
+    let pad = " ".repeat(indent);
+    let const_name = const_identifier(&key.name);
+    format!(
+        "{pad}pub const {const_name}: std::net::{ty} = {expr};\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn parsed(kind: AstResourceKind, text: &str) -> ParsedResource {
+        let value = match kind {
+            AstResourceKind::IpAddr => {
+                ScalarValue::IpAddr(text.into())
+            }
+            _ => ScalarValue::SocketAddr(text.into()),
+        };
+        ParsedResource {
+            name: "addr".to_string(),
+            kind,
+            value,
+            meta: Default::default(),
+        }
+    }
+
+    fn emit(
+        ty: &dyn ResourceType,
+        parsed: &ParsedResource,
+    ) -> String {
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = ty.build_node(parsed, origin).unwrap();
+        let key = ResourceKey::from_path("addr");
+        ty.emit_rust(&key, &node, 4).unwrap()
+    }
+
+    fn ip(text: &str) -> ParsedResource {
+        parsed(AstResourceKind::IpAddr, text)
+    }
+
+    fn socket(text: &str) -> ParsedResource {
+        parsed(AstResourceKind::SocketAddr, text)
+    }
+
+    #[test]
+    fn ip_addresses_emit_const_constructors() {
+        assert_eq!(
+            emit(&IpAddrType, &ip("1.1.1.1")),
+            "    pub const ADDR: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::new(1, 1, 1, 1));\n"
+        );
+        assert_eq!(
+            emit(&IpAddrType, &ip(" 2001:db8::1 ")),
+            "    pub const ADDR: std::net::IpAddr = std::net::IpAddr::V6(std::net::Ipv6Addr::new(0x2001, 0xdb8, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1));\n"
+        );
+    }
+
+    #[test]
+    fn socket_addresses_emit_const_constructors() {
+        assert_eq!(
+            emit(&SocketAddrType, &socket("0.0.0.0:9090")),
+            "    pub const ADDR: std::net::SocketAddr = std::net::SocketAddr::V4(std::net::SocketAddrV4::new(std::net::Ipv4Addr::new(0, 0, 0, 0), 9090));\n"
+        );
+        assert_eq!(
+            emit(&SocketAddrType, &socket("[::1]:8080")),
+            "    pub const ADDR: std::net::SocketAddr = std::net::SocketAddr::V6(std::net::SocketAddrV6::new(std::net::Ipv6Addr::new(0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1), 8080, 0, 0));\n"
+        );
+    }
+
+    #[test]
+    fn malformed_addresses_are_rejected() {
+        for (text, message) in [
+            ("1.1.1", "'1.1.1' is not a valid IP address"),
+            ("256.0.0.1", "'256.0.0.1' is not a valid IP address"),
+            (
+                "[::1]:80",
+                "'[::1]:80' is a socket address, use <socketaddr>",
+            ),
+        ] {
+            assert_eq!(
+                IpAddrType.validate(&ip(text)),
+                Err(message.into())
+            );
+        }
+        for (text, message) in [
+            ("0.0.0.0", "'0.0.0.0' is missing the ':port'"),
+            ("0.0.0.0:70000", "port 70000 is out of range (0 to 65535)"),
+            ("0.0.0.0:http", "'http' is not a valid port"),
+            (
+                "::1:8080",
+                "'::1:8080' needs brackets around its IPv6 address ([::1]:8080)",
+            ),
+            ("[::1:8080", "'[::1:8080' is not a valid socket address"),
+            ("localhost:80", "'localhost:80' is not a valid socket address"),
+        ] {
+            assert_eq!(
+                SocketAddrType.validate(&socket(text)),
+                Err(message.into()),
+                "{text}"
+            );
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use crate::generator::ir::model::{NumberType, NumberValue};
+use crate::generator::ir::model::{
+    NumberRange, NumberType, NumberValue,
+};
+use crate::generator::ir::assertion::assertion_items;
 use crate::generator::ir::types::ResourceType;
+use crate::generator::options::LazyStatics;
 use crate::generator::ir::{
-    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
-    ResourceValue,
+    NodeMeta, ResourceGraph, ResourceKey, ResourceKind, ResourceNode,
+    ResourceOrigin, ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::utils::const_identifier;
 use std::str::FromStr;
 
 pub struct NumberTypeHandler;
@@ -23,6 +27,22 @@ impl ResourceType for NumberTypeHandler {
         ResourceKind::Number
     }
 
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Number {
+                value,
+                explicit_type,
+            } => {
+                let explicit_type = explicit_type.as_deref();
+                let number = parse_number_value(value, explicit_type)?;
+                parse_range(&number, explicit_type, parsed)?;
+                parse_widen(&number, parsed.meta.widen.as_deref())
+                    .map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
@@ -33,14 +53,28 @@ impl ResourceType for NumberTypeHandler {
             explicit_type,
         } = &parsed.value
         {
+            let explicit_type = explicit_type.as_deref();
             let number_value =
-                parse_number_value(value, explicit_type.as_deref())
-                    .ok()?;
+                parse_number_value(value, explicit_type).ok()?;
+            let range =
+                parse_range(&number_value, explicit_type, parsed).ok()?;
+            let widen = parse_widen(
+                &number_value,
+                parsed.meta.widen.as_deref(),
+            )
+            .ok()?;
 
             Some(ResourceNode {
-                kind: ResourceKind::Number,
-                value: ResourceValue::Number(number_value),
-                origin,
+                meta: NodeMeta {
+                    range,
+                    widen,
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(
+                    ResourceKind::Number,
+                    ResourceValue::Number(number_value),
+                    origin,
+                )
             })
         } else {
             None
@@ -53,37 +87,348 @@ impl ResourceType for NumberTypeHandler {
         node: &ResourceNode,
         indent: usize,
     ) -> Option<String> {
-        if let ResourceValue::Number(number_value) = &node.value {
-            let pad = " ".repeat(indent);
-            let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
-
-            Some(match number_value {
-                NumberValue::Int(i) => format!(
-                    "{pad}pub const {const_name}: i64 = {i};\n"
-                ),
-                NumberValue::Float(f) => {
-                    let formatted = format_float(*f);
-                    format!("{pad}pub const {const_name}: f64 = {formatted};\n")
-                }
-                NumberValue::BigDecimal(raw) => {
-                    let literal = escape_literal(raw);
-                    format!(
-                        "{pad}pub static {const_name}: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {{\n\
-                        {pad}    r_resources::BigDecimal::from_str(\"{literal}\").expect(\"valid decimal literal\")\n\
-                        {pad}}});\n"
-                    )
-                }
-                NumberValue::Typed { literal, ty } => {
-                    format!(
-                        "{pad}pub const {const_name}: {} = {};\n",
-                        ty.as_str(),
-                        literal
-                    )
-                }
+        let lazy = LazyStatics::default();
+        self.emit_rust_with_lazy(key, node, indent, lazy)
+    }
+
+    fn emit_rust_with_lazy(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+        lazy: LazyStatics,
+    ) -> Option<String> {
+        let ResourceValue::Number(number_value) = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let const_name = const_identifier(&key.name);
+        let item =
+            |name: &str, value| number_item(&pad, name, value, lazy);
+        let mut code = item(&const_name, number_value);
+        code.push_str(&assertion_items(&pad, key, node));
+        let range = node.meta.range.as_ref();
+        let bounds = [
+            ("MIN", "Lower", range.and_then(|r| r.min.as_ref())),
+            ("MAX", "Upper", range.and_then(|r| r.max.as_ref())),
+        ];
+        for (suffix, side, bound) in bounds {
+            if let Some(bound) = bound {
+                code.push_str(&format!(
+                    "{pad}/// {side} bound of [`{const_name}`]\n"
+                ));
+                code.push_str(&item(
+                    &format!("{const_name}_{suffix}"),
+                    bound,
+                ));
+            }
+        }
+        for ty in &node.meta.widen {
+            let literal = display_literal(number_value);
+            code.push_str(&format!(
+                "{pad}/// [`{const_name}`] as a `{ty}`\n\
+                 {pad}pub const {const_name}_{}: {ty} = {literal};\n",
+                ty.to_ascii_uppercase()
+            ));
+        }
+        Some(code)
+    }
+}
+
+/// `pub const` (or `lazy` static for `BigDecimal`) named `name`
+fn number_item(
+    pad: &str,
+    name: &str,
+    value: &NumberValue,
+    lazy: LazyStatics,
+) -> String {
+    match value {
+        NumberValue::Int(i) => {
+            format!("{pad}pub const {name}: i64 = {i};\n")
+        }
+        NumberValue::Float(f) => {
+            let formatted = format_float(*f);
+            format!("{pad}pub const {name}: f64 = {formatted};\n")
+        }
+        NumberValue::BigDecimal(raw) => {
+            let literal = escape_literal(raw);
+            let lazy = lazy.path();
+            format!(
+                "{pad}pub static {name}: {lazy}<r_resources::BigDecimal> = {lazy}::new(|| {{\n\
+                {pad}    <r_resources::BigDecimal as std::str::FromStr>::from_str(\"{literal}\").expect(\"valid decimal literal\")\n\
+                {pad}}});\n"
+            )
+        }
+        NumberValue::Typed { literal, ty } => format!(
+            "{pad}pub const {name}: {} = {};\n",
+            ty.as_str(),
+            literal
+        ),
+    }
+}
+
+/// Integer types a `widen` attribute can name
+const WIDENINGS: [&str; 10] = [
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64",
+    "usize",
+];
+
+/// Width in bits and signedness of an integer type of [`WIDENINGS`],
+/// `isize` and `usize` counted as `pointer_bits`
+fn integer_width(ty: &str, pointer_bits: u32) -> (u32, bool) {
+    let (signed, bits) = ty.split_at(1);
+    let bits = match bits {
+        "size" => pointer_bits,
+        bits => bits.parse().unwrap_or(0),
+    };
+    (bits, signed == "i")
+}
+
+/// `true` when `to` holds every value of `from` on every target:
+/// never from signed to unsigned, and to a signed type only from a
+/// narrower unsigned one. Pointers are as wide as 64 bits when
+/// widened from, and as narrow as 32 bits when widened to.
+fn widens_losslessly(from: NumberType, to: &str) -> bool {
+    let (from_bits, from_signed) = integer_width(from.as_str(), 64);
+    let (to_bits, to_signed) = integer_width(to, 32);
+    match (from_signed, to_signed) {
+        (true, false) => false,
+        (false, true) => from_bits < to_bits,
+        _ => from_bits <= to_bits,
+    }
+}
+
+/// Parses the `widen` attribute (`usize,u64`) of a typed integer
+fn parse_widen(
+    value: &NumberValue,
+    widen: Option<&str>,
+) -> Result<Vec<&'static str>, String> {
+    let Some(widen) = widen else {
+        return Ok(Vec::new());
+    };
+    let ty = match value {
+        NumberValue::Typed { ty, .. } if !ty.is_float() => *ty,
+        _ => {
+            return Err("`widen` only applies to numbers with an \
+                 integer `type`"
+                .to_string())
+        }
+    };
+    let mut types = Vec::new();
+    for name in widen.split(',').map(str::trim) {
+        let Some(target) = WIDENINGS.iter().find(|t| **t == name)
+        else {
+            return Err(format!("'{name}' is not an integer type"));
+        };
+        if *target == ty.as_str() {
+            return Err(format!("the value already is a {name}"));
+        }
+        if !widens_losslessly(ty, target) {
+            return Err(format!(
+                "{} does not widen losslessly to {name} on every target",
+                ty.as_str()
+            ));
+        }
+        if !types.contains(target) {
+            types.push(*target);
+        }
+    }
+    Ok(types)
+}
+
+/// Adds the `widen` types of `BuildOptions` to every typed integer of
+/// `graph` they widen losslessly, after its own `widen` types
+pub fn widen_graph(
+    graph: &mut ResourceGraph,
+    widen: &[String],
+) -> Result<(), String> {
+    let mut targets = Vec::new();
+    for name in widen.iter().map(|name| name.trim()) {
+        match WIDENINGS.iter().find(|t| **t == name) {
+            Some(target) => targets.push(*target),
+            None => {
+                return Err(format!(
+                    "widen: '{name}' is not an integer type"
+                ))
+            }
+        }
+    }
+    for (_, nodes) in graph.nodes_mut() {
+        for node in nodes {
+            widen_node(node, &targets);
+        }
+    }
+    if graph.tests().is_some() {
+        widen_graph(graph.tests_mut(), widen)?;
+    }
+    Ok(())
+}
+
+/// Adds to `node`, when a typed integer, the `targets` it widens to
+/// losslessly
+fn widen_node(node: &mut ResourceNode, targets: &[&'static str]) {
+    let ResourceValue::Number(NumberValue::Typed { ty, .. }) =
+        &node.value
+    else {
+        return;
+    };
+    let ty = *ty;
+    for target in targets {
+        if !ty.is_float()
+            && *target != ty.as_str()
+            && widens_losslessly(ty, target)
+            && !node.meta.widen.contains(target)
+        {
+            node.meta.widen.push(target);
+        }
+    }
+}
+
+/// Parses the `min`/`max` attributes of `parsed` with the type of
+/// `value` and checks that `value` lies between them
+fn parse_range(
+    value: &NumberValue,
+    explicit_type: Option<&str>,
+    parsed: &ParsedResource,
+) -> Result<Option<NumberRange>, String> {
+    let (min, max) = (&parsed.meta.min, &parsed.meta.max);
+    if min.is_none() && max.is_none() {
+        return Ok(None);
+    }
+    let type_hint = explicit_type.unwrap_or(type_name(value));
+    let parse_bound = |attr: &str, bound: &Option<String>| {
+        bound
+            .as_deref()
+            .map(|text| {
+                parse_number_value(text, Some(type_hint))
+                    .map_err(|e| format!("{attr} '{}': {e}", text.trim()))
             })
-        } else {
+            .transpose()
+    };
+    let range = NumberRange {
+        min: parse_bound("min", min)?,
+        max: parse_bound("max", max)?,
+    };
+
+    let checked = |number: &NumberValue| {
+        magnitude(number).ok_or_else(|| {
+            format!(
+                "{} cannot be compared with min/max",
+                display_literal(number)
+            )
+        })
+    };
+    let low = range.min.as_ref().map(checked).transpose()?;
+    let high = range.max.as_ref().map(checked).transpose()?;
+    if let (Some(low), Some(high)) = (&low, &high) {
+        if low > high {
+            return Err(format!(
+                "min is greater than max in `{}`",
+                range_text(&range)
+            ));
+        }
+    }
+    let at = checked(value)?;
+    if low.is_some_and(|low| at < low)
+        || high.is_some_and(|high| at > high)
+    {
+        return Err(format!(
+            "value {} is outside `{}`",
+            display_literal(value),
+            range_text(&range)
+        ));
+    }
+    Ok(Some(range))
+}
+
+/// Bounds as written in the generated code, e.g. `1..=512` or `..=512`
+fn range_text(range: &NumberRange) -> String {
+    let min = range.min.as_ref().map(display_literal);
+    match range.max.as_ref().map(display_literal) {
+        Some(max) => format!("{}..={max}", min.unwrap_or_default()),
+        None => format!("{}..", min.unwrap_or_default()),
+    }
+}
+
+/// Doc line listing the bounds of a number, e.g. ``Range: `1..=512` ``
+pub fn range_doc(range: &NumberRange) -> String {
+    format!("Range: `{}`", range_text(range))
+}
+
+/// Type of `value`, as accepted by the `type` attribute
+fn type_name(value: &NumberValue) -> &'static str {
+    match value {
+        NumberValue::Float(_) => "f64",
+        NumberValue::BigDecimal(_) => "bigdecimal",
+        NumberValue::Typed { ty, .. } => ty.as_str(),
+        NumberValue::Int(_) => "i64",
+    }
+}
+
+/// `text` parsed as a number of the type of `value`
+pub fn number_like(
+    value: &NumberValue,
+    text: &str,
+) -> Result<NumberValue, String> {
+    parse_number_value(text, Some(type_name(value)))
+}
+
+/// How `a` compares to `b`, `None` when one of them is NaN
+pub fn compare_numbers(
+    a: &NumberValue,
+    b: &NumberValue,
+) -> Option<std::cmp::Ordering> {
+    Some(magnitude(a)?.cmp(&magnitude(b)?))
+}
+
+/// Value as written in the generated code
+pub fn display_literal(value: &NumberValue) -> String {
+    match value {
+        NumberValue::Int(i) => i.to_string(),
+        NumberValue::Float(f) => format_float(*f),
+        NumberValue::BigDecimal(raw) => raw.clone(),
+        NumberValue::Typed { literal, .. } => literal.clone(),
+    }
+}
+
+/// Number on one scale for `min`/`max` checks; infinities are
+/// ordered around every finite value
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Magnitude {
+    NegInf,
+    Finite(bigdecimal::BigDecimal),
+    PosInf,
+}
+
+/// `None` for NaN, which has no place in a range
+fn magnitude(value: &NumberValue) -> Option<Magnitude> {
+    let finite = |text: &str| {
+        bigdecimal::BigDecimal::from_str(&strip_separators(text))
+            .ok()
+            .map(Magnitude::Finite)
+    };
+    let float = |f: f64| {
+        if f.is_nan() {
             None
+        } else if f.is_infinite() {
+            Some(if f > 0.0 {
+                Magnitude::PosInf
+            } else {
+                Magnitude::NegInf
+            })
+        } else {
+            finite(&format!("{f:?}"))
+        }
+    };
+    match value {
+        NumberValue::Int(i) => finite(&i.to_string()),
+        NumberValue::Float(f) => float(*f),
+        NumberValue::BigDecimal(raw) => finite(raw),
+        NumberValue::Typed { literal, ty } if ty.is_float() => {
+            float(float_value(literal)?)
+        }
+        NumberValue::Typed { literal, .. } => {
+            finite(&integer_value(literal)?.to_string())
         }
     }
 }
@@ -97,10 +442,40 @@ fn parse_number_value(
         return Err("Number literal cannot be empty".to_string());
     }
 
+    // `+3` is written `3` in Rust
+    let literal = literal.strip_prefix('+').unwrap_or(literal);
+
     if let Some(type_hint) = explicit_type {
         return parse_explicit_number(literal, type_hint);
     }
 
+    if let Some(value) = special_float(literal) {
+        return Ok(NumberValue::Float(value));
+    }
+
+    if let Some(int) = parse_int_literal(literal) {
+        let Ok(value) = i64::try_from(int.value) else {
+            return if int.radix == 10 {
+                parse_big_decimal(&strip_separators(literal))
+            } else {
+                Err(format!("'{literal}' does not fit in i64"))
+            };
+        };
+        // Hex, octal, binary and `_`-separated literals keep their form
+        return Ok(if int.rust == value.to_string() {
+            NumberValue::Int(value)
+        } else {
+            NumberValue::Typed {
+                literal: int.rust,
+                ty: NumberType::I64,
+            }
+        });
+    }
+
+    if literal.trim_start_matches('-').starts_with('_') {
+        return Err(format!("Invalid number literal '{literal}'"));
+    }
+    let literal = &strip_separators(literal);
     if looks_like_integer(literal) {
         return literal
             .parse::<i64>()
@@ -116,9 +491,12 @@ fn parse_number_value(
     }
 
     // Try f64 for numbers that fit within its precision
-    f64::from_str(literal)
-        .map(NumberValue::Float)
-        .or_else(|_| parse_big_decimal(literal))
+    match f64::from_str(literal) {
+        Ok(_) => {
+            parse_finite_float(literal, "f64").map(NumberValue::Float)
+        }
+        Err(_) => parse_big_decimal(literal),
+    }
 }
 
 fn parse_big_decimal(literal: &str) -> Result<NumberValue, String> {
@@ -131,8 +509,40 @@ fn parse_explicit_number(
     literal: &str,
     type_hint: &str,
 ) -> Result<NumberValue, String> {
+    let Some(ty) = parse_number_type(type_hint)? else {
+        return parse_big_decimal(literal);
+    };
+
+    let trimmed = literal.trim();
+    let formatted = match ty {
+        NumberType::F32 => match special_float(trimmed) {
+            Some(value) => format_float32(value as f32),
+            None => format_float32(parse_finite_float(trimmed, "f32")?),
+        },
+        NumberType::F64 => match special_float(trimmed) {
+            Some(value) => format_float64(value),
+            None => format_float64(parse_finite_float(trimmed, "f64")?),
+        },
+        _ => parse_int_literal(trimmed)
+            .filter(|int| int_range(&ty).contains(&int.value))
+            .map(|int| int.rust)
+            .ok_or_else(|| {
+                format!("'{trimmed}' does not fit in {}", ty.as_str())
+            })?,
+    };
+
+    Ok(NumberValue::Typed {
+        literal: formatted,
+        ty,
+    })
+}
+
+/// Type of a `type="..."` attribute, `None` for `bigdecimal`
+pub fn parse_number_type(
+    type_hint: &str,
+) -> Result<Option<NumberType>, String> {
     let type_name = type_hint.trim().to_ascii_lowercase();
-    let ty = match type_name.as_str() {
+    Ok(Some(match type_name.as_str() {
         "i8" => NumberType::I8,
         "i16" => NumberType::I16,
         "i32" => NumberType::I32,
@@ -141,72 +551,178 @@ fn parse_explicit_number(
         "u16" => NumberType::U16,
         "u32" => NumberType::U32,
         "u64" => NumberType::U64,
+        "isize" => NumberType::Isize,
+        "usize" => NumberType::Usize,
         "f32" => NumberType::F32,
         "f64" => NumberType::F64,
-        "bigdecimal" => return parse_big_decimal(literal),
+        "bigdecimal" => return Ok(None),
         other => {
             return Err(format!("Unsupported number type '{other}'"))
         }
-    };
+    }))
+}
 
-    let trimmed = literal.trim();
-    let formatted = match ty {
-        NumberType::I8 => trimmed
-            .parse::<i8>()
-            .map(|v| v.to_string())
-            .map_err(|_| format!("'{trimmed}' does not fit in i8"))?,
-        NumberType::I16 => {
-            trimmed.parse::<i16>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in i16"),
-            )?
-        }
-        NumberType::I32 => {
-            trimmed.parse::<i32>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in i32"),
-            )?
-        }
-        NumberType::I64 => {
-            trimmed.parse::<i64>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in i64"),
-            )?
-        }
-        NumberType::U8 => trimmed
-            .parse::<u8>()
-            .map(|v| v.to_string())
-            .map_err(|_| format!("'{trimmed}' does not fit in u8"))?,
-        NumberType::U16 => {
-            trimmed.parse::<u16>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in u16"),
-            )?
-        }
-        NumberType::U32 => {
-            trimmed.parse::<u32>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in u32"),
-            )?
-        }
-        NumberType::U64 => {
-            trimmed.parse::<u64>().map(|v| v.to_string()).map_err(
-                |_| format!("'{trimmed}' does not fit in u64"),
-            )?
-        }
-        NumberType::F32 => {
-            trimmed.parse::<f32>().map(format_float32).map_err(
-                |_| format!("'{trimmed}' is not a valid f32 literal"),
-            )?
-        }
-        NumberType::F64 => {
-            trimmed.parse::<f64>().map(format_float64).map_err(
-                |_| format!("'{trimmed}' is not a valid f64 literal"),
-            )?
-        }
+/// Pointer widths `BuildOptions::target_pointer_width` can name
+pub const POINTER_WIDTHS: [u32; 3] = [16, 32, 64];
+
+/// Width `isize` and `usize` values are checked against by default,
+/// so they fit every target of 32 bits or more
+pub const DEFAULT_POINTER_WIDTH: u32 = 32;
+
+/// Why the `isize` or `usize` `value` does not fit in a pointer of
+/// `bits` bits, if so
+pub fn pointer_width_error(
+    value: &NumberValue,
+    bits: u32,
+) -> Option<String> {
+    let NumberValue::Typed { literal, ty } = value else {
+        return None;
+    };
+    let half = 1i128 << (bits - 1);
+    let range = match ty {
+        NumberType::Isize => -half..=half - 1,
+        NumberType::Usize => 0..=2 * half - 1,
+        _ => return None,
     };
+    let int = integer_value(literal)?;
+    (!range.contains(&int)).then(|| {
+        format!(
+            "'{literal}' does not fit in {} on {bits}-bit targets",
+            ty.as_str()
+        )
+    })
+}
 
-    Ok(NumberValue::Typed {
-        literal: formatted,
+/// `value` as a constant of the integer type `ty`, `None` if it does
+/// not fit
+pub fn typed_integer(value: i128, ty: NumberType) -> Option<NumberValue> {
+    int_range(&ty).contains(&value).then(|| NumberValue::Typed {
+        literal: value.to_string(),
         ty,
     })
 }
 
+/// `value` as a constant of the float type `ty`
+pub fn typed_float(value: f64, ty: NumberType) -> NumberValue {
+    let literal = match ty {
+        NumberType::F32 => format_float32(value as f32),
+        _ => format_float64(value),
+    };
+    NumberValue::Typed { literal, ty }
+}
+
+/// Value of a float literal generated by `parse_number_value`,
+/// `f64::INFINITY` paths included
+pub fn float_value(literal: &str) -> Option<f64> {
+    match literal.rsplit("::").next()? {
+        "INFINITY" => Some(f64::INFINITY),
+        "NEG_INFINITY" => Some(f64::NEG_INFINITY),
+        "NAN" => Some(f64::NAN),
+        text => strip_separators(text).parse().ok(),
+    }
+}
+
+/// Integer literal, e.g. `-0xFF_FF`
+struct IntLiteral {
+    value: i128,
+    radix: u32,
+    /// Rust form of the literal, e.g. `-0xff_ff` or `42`
+    rust: String,
+}
+
+/// Value of an integer literal as written in a resource file (`_`
+/// separators, `0x`/`0o`/`0b` prefixes), for exports to other languages
+pub fn integer_value(literal: &str) -> Option<i128> {
+    parse_int_literal(literal).map(|int| int.value)
+}
+
+/// Parses an integer literal with optional `_` separators and a
+/// `0x`/`0o`/`0b` prefix; `None` if it isn't one or exceeds `i128`
+fn parse_int_literal(literal: &str) -> Option<IntLiteral> {
+    let (sign, unsigned) = match literal.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", literal.strip_prefix('+').unwrap_or(literal)),
+    };
+    let prefix = unsigned.get(..2).map(str::to_ascii_lowercase);
+    let (radix, prefix, digits) = match prefix.as_deref() {
+        Some("0x") => (16, "0x", &unsigned[2..]),
+        Some("0o") => (8, "0o", &unsigned[2..]),
+        Some("0b") => (2, "0b", &unsigned[2..]),
+        _ => (10, "", unsigned),
+    };
+    let leading_digit = digits.starts_with(|c: char| c.is_ascii_digit());
+    if radix == 10 && !leading_digit {
+        return None;
+    }
+    let clean = strip_separators(digits);
+    if clean.is_empty() || !clean.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    let magnitude =
+        i128::try_from(u128::from_str_radix(&clean, radix).ok()?).ok()?;
+    let value = if sign.is_empty() { magnitude } else { -magnitude };
+    let rust = if radix == 10 && !digits.contains('_') {
+        value.to_string()
+    } else {
+        format!("{sign}{prefix}{digits}")
+    };
+    Some(IntLiteral { value, radix, rust })
+}
+
+/// Values representable by an integer type
+fn int_range(ty: &NumberType) -> std::ops::RangeInclusive<i128> {
+    match ty {
+        NumberType::I8 => i8::MIN.into()..=i8::MAX.into(),
+        NumberType::I16 => i16::MIN.into()..=i16::MAX.into(),
+        NumberType::I32 => i32::MIN.into()..=i32::MAX.into(),
+        NumberType::U8 => 0..=u8::MAX.into(),
+        NumberType::U16 => 0..=u16::MAX.into(),
+        NumberType::U32 => 0..=u32::MAX.into(),
+        // `usize` as wide as on 64-bit targets, narrowed by
+        // `check_pointer_width`
+        NumberType::U64 | NumberType::Usize => 0..=u64::MAX.into(),
+        // I64 and Isize, and floats are never parsed as integers
+        _ => i64::MIN.into()..=i64::MAX.into(),
+    }
+}
+
+/// `inf`, `-inf`, `infinity` and `NaN` (case-insensitive)
+fn special_float(literal: &str) -> Option<f64> {
+    match literal.to_ascii_lowercase().as_str() {
+        "inf" | "infinity" => Some(f64::INFINITY),
+        "-inf" | "-infinity" => Some(f64::NEG_INFINITY),
+        "nan" => Some(f64::NAN),
+        _ => None,
+    }
+}
+
+/// `literal` as a `ty` float, rejecting values too large for it
+/// (which would parse to infinity) or too small (which would parse to
+/// zero); only [`special_float`] spellings name infinity and NaN
+fn parse_finite_float<T>(literal: &str, ty: &str) -> Result<T, String>
+where
+    T: FromStr + Into<f64> + Copy,
+{
+    let value = strip_separators(literal)
+        .parse::<T>()
+        .map_err(|_| format!("'{literal}' is not a valid {ty} literal"))?;
+    let wide: f64 = value.into();
+    let mantissa =
+        literal.split(['e', 'E']).next().unwrap_or_default();
+    let underflows = wide == 0.0
+        && mantissa.bytes().any(|b| (b'1'..=b'9').contains(&b));
+    if wide.is_infinite() || wide.is_nan() || underflows {
+        return Err(format!("'{literal}' does not fit in {ty}"));
+    }
+    Ok(value)
+}
+
+/// Removes `_` digit separators (`10_485_760` → `10485760`)
+fn strip_separators(literal: &str) -> String {
+    literal.replace('_', "")
+}
+
 fn looks_like_integer(literal: &str) -> bool {
     !(literal.contains('.')
         || literal.contains('e')
@@ -231,10 +747,8 @@ fn count_significant_digits(literal: &str) -> usize {
     
     for ch in literal.chars() {
         match ch {
-            '0'..='9' => {
-                if !in_exponent {
-                    digits += 1;
-                }
+            '0'..='9' if !in_exponent => {
+                digits += 1;
             }
             'e' | 'E' => {
                 in_exponent = true;
@@ -253,25 +767,35 @@ fn count_significant_digits(literal: &str) -> usize {
 }
 
 fn format_float(value: f64) -> String {
-    let s = value.to_string();
-    if s.contains('.') || s.contains('e') || s.contains('E') {
-        s
-    } else {
-        format!("{s}.0")
-    }
+    format_float64(value)
 }
 
 fn format_float32(value: f32) -> String {
-    let s = value.to_string();
-    if s.contains('.') || s.contains('e') || s.contains('E') {
-        s
+    special_float_literal("f32", value.into())
+        .unwrap_or_else(|| with_decimal_point(value.to_string()))
+}
+
+/// Literal for `value`, always with a decimal point or exponent
+pub(super) fn format_float64(value: f64) -> String {
+    special_float_literal("f64", value)
+        .unwrap_or_else(|| with_decimal_point(value.to_string()))
+}
+
+/// `f64::INFINITY`-style path for values without a literal form
+fn special_float_literal(ty: &str, value: f64) -> Option<String> {
+    let name = if value.is_nan() {
+        "NAN"
+    } else if value == f64::INFINITY {
+        "INFINITY"
+    } else if value == f64::NEG_INFINITY {
+        "NEG_INFINITY"
     } else {
-        format!("{s}.0")
-    }
+        return None;
+    };
+    Some(format!("{ty}::{name}"))
 }
 
-fn format_float64(value: f64) -> String {
-    let s = value.to_string();
+fn with_decimal_point(s: String) -> String {
     if s.contains('.') || s.contains('e') || s.contains('E') {
         s
     } else {
@@ -318,10 +842,10 @@ mod tests {
     // Test parse_number_value for floats
     #[test]
     fn test_parse_float() {
-        let result = parse_number_value("3.14", None).unwrap();
+        let result = parse_number_value("2.75", None).unwrap();
         assert!(matches!(result, NumberValue::Float(_)));
         if let NumberValue::Float(f) = result {
-            assert!((f - 3.14).abs() < 0.0001);
+            assert!((f - 2.75).abs() < 0.0001);
         }
     }
 
@@ -369,6 +893,176 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_integer_with_separators() {
+        let result = parse_number_value("10_485_760", None).unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, ty: NumberType::I64 }
+                if literal == "10_485_760"
+        ));
+
+        // Too big for i64: BigDecimal without the separators
+        let result =
+            parse_number_value("9_223_372_036_854_775_808", None).unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::BigDecimal(ref s) if s == "9223372036854775808"
+        ));
+
+        let result = parse_number_value("1_000.5", None).unwrap();
+        assert!(matches!(result, NumberValue::Float(f) if f == 1000.5));
+    }
+
+    #[test]
+    fn test_parse_prefixed_integers() {
+        let literal = |text: &str| match parse_number_value(text, None) {
+            Ok(NumberValue::Typed { literal, ty: NumberType::I64 }) => {
+                literal
+            }
+            other => panic!("{text}: {other:?}"),
+        };
+        assert_eq!(literal("0xDEADBEEF"), "0xDEADBEEF");
+        assert_eq!(literal("0XFF_FF"), "0xFF_FF");
+        assert_eq!(literal("0o755"), "0o755");
+        assert_eq!(literal("0b1010_0101"), "0b1010_0101");
+        assert_eq!(literal("-0x10"), "-0x10");
+        // Plain decimals stay Int
+        assert!(matches!(
+            parse_number_value("007", None).unwrap(),
+            NumberValue::Int(7)
+        ));
+
+        assert!(parse_number_value("0xFFFF_FFFF_FFFF_FFFF", None).is_err());
+        assert!(parse_number_value("0x", None).is_err());
+        assert!(parse_number_value("0b102", None).is_err());
+        assert!(parse_number_value("_1", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_explicit_prefixed_integers() {
+        let result = parse_explicit_number("0xDEADBEEF", "u32").unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, ty: NumberType::U32 }
+                if literal == "0xDEADBEEF"
+        ));
+        let result = parse_explicit_number("1_000", "u16").unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, .. } if literal == "1_000"
+        ));
+        let result = parse_explicit_number("-0x80", "i8").unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, .. } if literal == "-0x80"
+        ));
+        let result = parse_explicit_number("0xFFFF_FFFF_FFFF_FFFF", "u64");
+        assert!(result.is_ok());
+
+        assert_eq!(
+            parse_explicit_number("0x1FF", "u8").unwrap_err(),
+            "'0x1FF' does not fit in u8"
+        );
+        assert_eq!(
+            parse_explicit_number("0x1_0000_0000", "u32").unwrap_err(),
+            "'0x1_0000_0000' does not fit in u32"
+        );
+        let result = parse_explicit_number("1_000.25", "f32").unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, .. } if literal == "1000.25"
+        ));
+    }
+
+    #[test]
+    fn test_parse_plus_sign_and_negative_zero() {
+        assert!(matches!(
+            parse_number_value("+3", None).unwrap(),
+            NumberValue::Int(3)
+        ));
+        assert!(matches!(
+            parse_number_value("+2.5", None).unwrap(),
+            NumberValue::Float(f) if f == 2.5
+        ));
+        let result = parse_number_value("-0.0", None).unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Float(f) if f == 0.0 && f.is_sign_negative()
+        ));
+        assert_eq!(format_float(-0.0), "-0.0");
+        let result = parse_explicit_number("+0x10", "u8").unwrap();
+        assert!(matches!(
+            result,
+            NumberValue::Typed { ref literal, .. } if literal == "0x10"
+        ));
+    }
+
+    #[test]
+    fn test_parse_special_floats() {
+        let float = |text: &str| match parse_number_value(text, None) {
+            Ok(NumberValue::Float(f)) => f,
+            other => panic!("{text}: {other:?}"),
+        };
+        assert_eq!(float("inf"), f64::INFINITY);
+        assert_eq!(float("+Infinity"), f64::INFINITY);
+        assert_eq!(float("-inf"), f64::NEG_INFINITY);
+        assert!(float("NaN").is_nan());
+
+        assert_eq!(format_float(f64::INFINITY), "f64::INFINITY");
+        assert_eq!(format_float(f64::NEG_INFINITY), "f64::NEG_INFINITY");
+        assert_eq!(format_float(f64::NAN), "f64::NAN");
+
+        let literal = |text: &str, ty: &str| {
+            match parse_explicit_number(text, ty).unwrap() {
+                NumberValue::Typed { literal, .. } => literal,
+                other => panic!("{text}: {other:?}"),
+            }
+        };
+        assert_eq!(literal("-inf", "f32"), "f32::NEG_INFINITY");
+        assert_eq!(literal("nan", "f64"), "f64::NAN");
+        assert!(parse_explicit_number("inf", "i32").is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_floats_are_range_errors() {
+        assert_eq!(
+            parse_explicit_number("1e40", "f32").unwrap_err(),
+            "'1e40' does not fit in f32"
+        );
+        assert_eq!(
+            parse_explicit_number("-1e400", "f64").unwrap_err(),
+            "'-1e400' does not fit in f64"
+        );
+        assert_eq!(
+            parse_number_value("1e400", None).unwrap_err(),
+            "'1e400' does not fit in f64"
+        );
+        assert_eq!(
+            parse_number_value("0.1e-400", None).unwrap_err(),
+            "'0.1e-400' does not fit in f64"
+        );
+        assert_eq!(
+            parse_explicit_number("1e-50", "f32").unwrap_err(),
+            "'1e-50' does not fit in f32"
+        );
+        // Zero itself and subnormals are fine
+        assert!(parse_explicit_number("0.0e-400", "f64").is_ok());
+        assert!(parse_explicit_number("1e-40", "f32").is_ok());
+    }
+
+    #[test]
+    fn test_negative_unsigned_is_a_range_error() {
+        assert_eq!(
+            parse_explicit_number("-1", "u32").unwrap_err(),
+            "'-1' does not fit in u32"
+        );
+        assert_eq!(
+            parse_explicit_number("-0x1", "u8").unwrap_err(),
+            "'-0x1' does not fit in u8"
+        );
+    }
+
     // Test parse_explicit_number for all numeric types
     #[test]
     fn test_parse_explicit_i8() {
@@ -403,6 +1097,26 @@ mod tests {
         assert!(matches!(result, NumberValue::Typed { ty: NumberType::I64, .. }));
     }
 
+    #[test]
+    fn test_pointer_sized_integers_fit_the_target_width() {
+        let len = parse_explicit_number("5_000_000_000", "usize").unwrap();
+        assert!(matches!(len, NumberValue::Typed { ty: NumberType::Usize, .. }));
+        assert_eq!(
+            pointer_width_error(&len, 32).unwrap(),
+            "'5_000_000_000' does not fit in usize on 32-bit targets"
+        );
+        assert!(pointer_width_error(&len, 64).is_none());
+
+        let offset = parse_explicit_number("-2147483648", "isize").unwrap();
+        assert!(pointer_width_error(&offset, 32).is_none());
+        assert!(pointer_width_error(&offset, 16).is_some());
+        let below = parse_explicit_number("-2147483649", "isize").unwrap();
+        assert!(pointer_width_error(&below, 32).is_some());
+        // Beyond every target
+        assert!(parse_explicit_number("-1", "usize").is_err());
+        assert!(parse_explicit_number("0x1_0000_0000_0000_0000", "usize").is_err());
+    }
+
     #[test]
     fn test_parse_explicit_u8() {
         let result = parse_explicit_number("255", "u8").unwrap();
@@ -429,10 +1143,10 @@ mod tests {
 
     #[test]
     fn test_parse_explicit_f32() {
-        let result = parse_explicit_number("3.14", "f32").unwrap();
+        let result = parse_explicit_number("2.75", "f32").unwrap();
         assert!(matches!(result, NumberValue::Typed { ty: NumberType::F32, .. }));
         if let NumberValue::Typed { literal, ty: _ } = result {
-            assert!(literal.contains("3.14"));
+            assert!(literal.contains("2.75"));
         }
     }
 
@@ -455,7 +1169,7 @@ mod tests {
         assert!(looks_like_integer("42"));
         assert!(looks_like_integer("-42"));
         assert!(looks_like_integer("0"));
-        assert!(!looks_like_integer("3.14"));
+        assert!(!looks_like_integer("2.75"));
         assert!(!looks_like_integer("1.5e10"));
         assert!(!looks_like_integer("1E10"));
     }
@@ -487,7 +1201,7 @@ mod tests {
     // Test format_float
     #[test]
     fn test_format_float() {
-        assert_eq!(format_float(3.14), "3.14");
+        assert_eq!(format_float(2.75), "2.75");
         assert_eq!(format_float(42.0), "42.0");
         // For scientific notation, the format depends on the value and Rust's formatting
         let sci_result = format_float(1.5e10);
@@ -501,14 +1215,14 @@ mod tests {
     // Test format_float32
     #[test]
     fn test_format_float32() {
-        assert_eq!(format_float32(3.14f32), "3.14");
+        assert_eq!(format_float32(2.75f32), "2.75");
         assert_eq!(format_float32(42.0f32), "42.0");
     }
 
     // Test format_float64
     #[test]
     fn test_format_float64() {
-        assert_eq!(format_float64(3.14), "3.14");
+        assert_eq!(format_float64(2.75), "2.75");
         assert_eq!(format_float64(42.0), "42.0");
     }
 
@@ -540,14 +1254,14 @@ mod tests {
     fn test_emit_rust_int() {
         let handler = NumberTypeHandler;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "test_value".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Number,
-            value: ResourceValue::Number(NumberValue::Int(42)),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "test_value".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Number,
+            ResourceValue::Number(NumberValue::Int(42)),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const TEST_VALUE: i64 = 42;"));
@@ -558,18 +1272,18 @@ mod tests {
     fn test_emit_rust_float() {
         let handler = NumberTypeHandler;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "pi".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Number,
-            value: ResourceValue::Number(NumberValue::Float(3.14)),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "pi".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Number,
+            ResourceValue::Number(NumberValue::Float(2.75)),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const PI: f64"));
-        assert!(result.contains("3.14"));
+        assert!(result.contains("2.75"));
     }
 
     // Test emit_rust for BigDecimal
@@ -577,16 +1291,16 @@ mod tests {
     fn test_emit_rust_big_decimal() {
         let handler = NumberTypeHandler;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "big_number".to_string(),
+            namespace: vec![].into(),
+            name: "big_number".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Number,
-            value: ResourceValue::Number(NumberValue::BigDecimal(
+        let node = ResourceNode::new(
+            ModelResourceKind::Number,
+            ResourceValue::Number(NumberValue::BigDecimal(
                 "12345678901234567890.123456789".to_string(),
             )),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub static BIG_NUMBER"));
@@ -594,6 +1308,15 @@ mod tests {
         assert!(result.contains("BigDecimal"));
         assert!(result.contains("12345678901234567890.123456789"));
         assert!(result.contains("from_str"));
+
+        let lazy = LazyStatics::OnceLock;
+        let result = handler
+            .emit_rust_with_lazy(&key, &node, 4, lazy)
+            .unwrap();
+        assert!(result.contains(
+            "pub static BIG_NUMBER: r_resources::Lazy<r_resources::BigDecimal> = r_resources::Lazy::new("
+        ));
+        assert!(!result.contains("LazyLock"));
     }
 
     // Test emit_rust for Typed
@@ -601,17 +1324,17 @@ mod tests {
     fn test_emit_rust_typed() {
         let handler = NumberTypeHandler;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "small_int".to_string(),
+            namespace: vec![].into(),
+            name: "small_int".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Number,
-            value: ResourceValue::Number(NumberValue::Typed {
+        let node = ResourceNode::new(
+            ModelResourceKind::Number,
+            ResourceValue::Number(NumberValue::Typed {
                 literal: "127".to_string(),
                 ty: NumberType::I8,
             }),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub const SMALL_INT: i8 = 127;"));
@@ -628,6 +1351,7 @@ mod tests {
                 value: "42".to_string(),
                 explicit_type: None,
             },
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -649,6 +1373,7 @@ mod tests {
                 value: "127".to_string(),
                 explicit_type: Some("i8".to_string()),
             },
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -670,6 +1395,7 @@ mod tests {
                 value: "not_a_number".to_string(),
                 explicit_type: None,
             },
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -678,6 +1404,223 @@ mod tests {
     }
 
     // Test name and xml_tags
+    // Test min/max bounds
+    fn ranged(
+        value: &str,
+        explicit_type: Option<&str>,
+        min: Option<&str>,
+        max: Option<&str>,
+    ) -> ParsedResource {
+        ParsedResource {
+            name: "pool_size".to_string(),
+            kind: AstResourceKind::Number,
+            value: ScalarValue::Number {
+                value: value.to_string(),
+                explicit_type: explicit_type.map(str::to_string),
+            },
+            meta: crate::generator::parsing::ResourceMeta {
+                min: min.map(str::to_string),
+                max: max.map(str::to_string),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_value_outside_range_is_rejected() {
+        let handler = NumberTypeHandler;
+        let parsed = ranged("0", Some("u32"), Some("1"), Some("512"));
+        assert_eq!(
+            handler.validate(&parsed).unwrap_err(),
+            "value 0 is outside `1..=512`"
+        );
+        let parsed = ranged("513", Some("u32"), Some("1"), Some("512"));
+        assert!(handler.validate(&parsed).is_err());
+        let parsed = ranged("-0.5", None, Some("0.0"), None);
+        assert_eq!(
+            handler.validate(&parsed).unwrap_err(),
+            "value -0.5 is outside `0.0..`"
+        );
+    }
+
+    #[test]
+    fn test_bounds_use_the_number_rules() {
+        let handler = NumberTypeHandler;
+        for (value, min, max) in [
+            ("64", "1", "512"),
+            ("0x40", "0x01", "0x2_00"),
+            ("1_000", "1_000", "10_000"),
+        ] {
+            let parsed = ranged(value, Some("u32"), Some(min), Some(max));
+            assert!(handler.validate(&parsed).is_ok(), "{value}");
+        }
+        // Bounds take the type of the value
+        let parsed = ranged("64", Some("u8"), None, Some("300"));
+        assert_eq!(
+            handler.validate(&parsed).unwrap_err(),
+            "max '300': '300' does not fit in u8"
+        );
+        let parsed = ranged("2", None, Some("1.5"), None);
+        assert!(handler.validate(&parsed).is_err());
+        let parsed = ranged("0.5", None, Some("-inf"), Some("1"));
+        assert!(handler.validate(&parsed).is_ok());
+    }
+
+    #[test]
+    fn test_inverted_range_is_rejected() {
+        let handler = NumberTypeHandler;
+        let parsed = ranged("5", None, Some("10"), Some("1"));
+        assert_eq!(
+            handler.validate(&parsed).unwrap_err(),
+            "min is greater than max in `10..=1`"
+        );
+        let parsed = ranged("nan", None, Some("0.0"), None);
+        assert!(handler.validate(&parsed).is_err());
+    }
+
+    #[test]
+    fn test_bounds_get_companion_constants() {
+        let handler = NumberTypeHandler;
+        let parsed = ranged("64", Some("u32"), Some("1"), Some("512"));
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = handler.build_node(&parsed, origin).unwrap();
+        let range = node.meta.range.as_ref().unwrap();
+        assert_eq!(range_doc(range), "Range: `1..=512`");
+
+        let result = handler.emit_rust(&key_of(&parsed), &node, 0).unwrap();
+        assert_eq!(
+            result,
+            "pub const POOL_SIZE: u32 = 64;\n\
+             /// Lower bound of [`POOL_SIZE`]\n\
+             pub const POOL_SIZE_MIN: u32 = 1;\n\
+             /// Upper bound of [`POOL_SIZE`]\n\
+             pub const POOL_SIZE_MAX: u32 = 512;\n"
+        );
+    }
+
+    #[test]
+    fn test_unbounded_numbers_have_no_range() {
+        let handler = NumberTypeHandler;
+        let parsed = ranged("64", None, None, None);
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = handler.build_node(&parsed, origin).unwrap();
+        assert!(node.meta.range.is_none());
+        let result = handler.emit_rust(&key_of(&parsed), &node, 0).unwrap();
+        assert_eq!(result, "pub const POOL_SIZE: i64 = 64;\n");
+    }
+
+    fn widened(
+        value: &str,
+        explicit_type: Option<&str>,
+        widen: &str,
+    ) -> ParsedResource {
+        let mut parsed = ranged(value, explicit_type, None, None);
+        parsed.meta.widen = Some(widen.to_string());
+        parsed
+    }
+
+    #[test]
+    fn test_widenings_get_companion_constants() {
+        let handler = NumberTypeHandler;
+        let parsed = widened("0x200", Some("u32"), "usize, u64");
+        let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
+        let node = handler.build_node(&parsed, origin).unwrap();
+        let result = handler.emit_rust(&key_of(&parsed), &node, 0).unwrap();
+        assert_eq!(
+            result,
+            "pub const POOL_SIZE: u32 = 0x200;\n\
+             /// [`POOL_SIZE`] as a `usize`\n\
+             pub const POOL_SIZE_USIZE: usize = 0x200;\n\
+             /// [`POOL_SIZE`] as a `u64`\n\
+             pub const POOL_SIZE_U64: u64 = 0x200;\n"
+        );
+    }
+
+    #[test]
+    fn test_only_lossless_widenings_are_accepted() {
+        for (ty, targets) in [
+            ("u8", "u16,i16,usize,isize"),
+            ("u16", "u32,i32,usize,isize"),
+            ("u32", "u64,i64,usize"),
+            ("i8", "i16,i32,i64,isize"),
+            ("i32", "i64,isize"),
+            ("usize", "u64"),
+            ("isize", "i64"),
+        ] {
+            let parsed = widened("1", Some(ty), targets);
+            assert!(NumberTypeHandler.validate(&parsed).is_ok(), "{ty}");
+        }
+        for (ty, target) in [
+            ("u64", "usize"),
+            ("i64", "isize"),
+            ("u32", "isize"),
+            ("u32", "i32"),
+            ("i8", "u64"),
+            ("i32", "usize"),
+            ("usize", "u32"),
+            ("isize", "i32"),
+        ] {
+            let parsed = widened("1", Some(ty), target);
+            assert_eq!(
+                NumberTypeHandler.validate(&parsed).unwrap_err(),
+                format!(
+                    "{ty} does not widen losslessly to {target} on every target"
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_widen_needs_a_typed_integer() {
+        for (value, explicit_type, widen, message) in [
+            ("1", None, "u64", "`widen` only applies to numbers with an integer `type`"),
+            ("1.5", Some("f32"), "f64", "`widen` only applies to numbers with an integer `type`"),
+            ("1", Some("u32"), "u128", "'u128' is not an integer type"),
+            ("1", Some("u32"), "u32", "the value already is a u32"),
+        ] {
+            let parsed = widened(value, explicit_type, widen);
+            assert_eq!(
+                NumberTypeHandler.validate(&parsed).unwrap_err(),
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_global_widenings_skip_lossy_targets() {
+        let handler = NumberTypeHandler;
+        let mut graph = ResourceGraph::default();
+        for (name, parsed) in [
+            ("small", widened("1", Some("u16"), "u64")),
+            ("large", ranged("1", Some("u64"), None, None)),
+        ] {
+            let origin =
+                ResourceOrigin::new(PathBuf::from("test.xml"), false);
+            let node = handler.build_node(&parsed, origin).unwrap();
+            graph.insert(ResourceKey::from_path(name), node);
+        }
+        let targets = ["usize".to_string(), "u32".to_string()];
+        widen_graph(&mut graph, &targets).unwrap();
+        let widen = |name: &str| {
+            let key = ResourceKey::from_path(name);
+            graph.get(&key).unwrap().meta.widen.clone()
+        };
+        // Own widenings first, then the lossless global ones
+        assert_eq!(widen("small"), ["u64", "usize", "u32"]);
+        assert!(widen("large").is_empty());
+        assert_eq!(
+            widen_graph(&mut graph, &["u65".to_string()]),
+            Err("widen: 'u65' is not an integer type".to_string())
+        );
+    }
+
+    fn key_of(parsed: &ParsedResource) -> ResourceKey {
+        ResourceKey {
+            namespace: vec![].into(),
+            name: parsed.name.as_str().into(),
+        }
+    }
+
     #[test]
     fn test_handler_name() {
         let handler = NumberTypeHandler;
@@ -0,0 +1,170 @@
+//! `<regex>`, a regular expression checked at build time and generated
+//! as its pattern text.
+//!
+//! ```xml
+//! <regex name="username">^[a-z0-9_]{3,16}$</regex>
+//! <!--
+//! pub const USERNAME_PATTERN: &str = "^[a-z0-9_]{3,16}$";
+//! // with the `regex` feature
+//! pub static USERNAME: std::sync::LazyLock<::regex::Regex> = ...;
+//! // or `r_resources::Lazy` for an MSRV below 1.80
+//! -->
+//! ```
+//!
+//! The text is kept as written, surrounding whitespace included, and
+//! only XML entities are decoded. It is compiled with the parser of
+//! the `pattern` attribute: what neither it nor the `regex` crate
+//! accept is rejected, the `regex` syntax beyond the subset (`\b`,
+//! `(?i)`, `\p{L}`) is left to the `regex` crate, with a warning
+//! without the `regex` feature.
+
+use crate::generator::ir::pattern::{Pattern, PatternError};
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::options::LazyStatics;
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::{const_identifier, string_literal};
+
+pub struct RegexType;
+
+impl ResourceType for RegexType {
+    fn name(&self) -> &'static str {
+        "regex"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["regex"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Regex
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Regex(source) => match Pattern::new(source) {
+                Err(PatternError::Invalid(e)) => {
+                    Err(format!("invalid regex `{source}`: {e}"))
+                }
+                // Warned about by the analysis
+                Ok(_) | Err(PatternError::Unsupported(_)) => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Regex(source) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode::new(
+            ResourceKind::Regex,
+            ResourceValue::Regex(source.clone()),
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Regex(source) = &node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        Some(format!(
+            "{pad}pub const {name}_PATTERN: &str = {};\n",
+            string_literal(source)
+        ))
+    }
+}
+
+/// `lazy` static compiling the `_PATTERN` of `key`, for the `regex`
+/// feature
+pub fn regex_static(
+    key: &ResourceKey,
+    pad: &str,
+    lazy: LazyStatics,
+) -> String {
+    let name = const_identifier(&key.name);
+    let lazy = lazy.path();
+    format!(
+        "{pad}/// [`{name}_PATTERN`], compiled on first use\n\
+         {pad}pub static {name}: {lazy}<::regex::Regex> =\n\
+         {pad}    {lazy}::new(|| {{\n\
+         {pad}        ::regex::Regex::new({name}_PATTERN)\n\
+         {pad}            .expect(\"`<regex>` rejected by the regex crate\")\n\
+         {pad}    }});\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn regex(source: &str) -> ParsedResource {
+        ParsedResource {
+            name: "username".to_string(),
+            kind: AstResourceKind::Regex,
+            value: ScalarValue::Regex(source.to_string()),
+            meta: Default::default(),
+        }
+    }
+
+    #[test]
+    fn patterns_are_emitted_as_written() {
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = RegexType
+            .build_node(&regex(r#" ^"\d+"$ "#), origin)
+            .unwrap();
+        let key = ResourceKey::from_path("username");
+
+        assert_eq!(
+            RegexType.emit_rust(&key, &node, 4).unwrap(),
+            "    pub const USERNAME_PATTERN: &str = r#\" ^\"\\d+\"$ \"#;\n"
+        );
+        assert!(regex_static(&key, "    ", LazyStatics::LazyLock)
+            .contains(
+                "    pub static USERNAME: std::sync::LazyLock<::regex::Regex> =\n"
+            ));
+        let once = regex_static(&key, "    ", LazyStatics::OnceLock);
+        assert!(once.contains(
+            "    pub static USERNAME: r_resources::Lazy<::regex::Regex> =\n        r_resources::Lazy::new(|| {\n"
+        ));
+    }
+
+    #[test]
+    fn invalid_regexes_are_rejected() {
+        assert_eq!(
+            RegexType.validate(&regex("^[a-z0-9_{3,16}$")),
+            Err("invalid regex `^[a-z0-9_{3,16}$`: unclosed character class"
+                .into())
+        );
+        assert_eq!(
+            RegexType.validate(&regex("(?<=a)b")),
+            Err("invalid regex `(?<=a)b`: lookaround is not supported"
+                .into())
+        );
+        assert!(RegexType
+            .validate(&regex("^[a-z0-9_]{3,16}$"))
+            .is_ok());
+        // Left to the `regex` crate
+        assert!(RegexType.validate(&regex(r"(?i)\bada\b")).is_ok());
+    }
+}
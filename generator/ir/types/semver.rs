@@ -0,0 +1,236 @@
+//! `<semver>`, generated as an `r_resources::Version` constant and
+//! the version as written.
+//!
+//! ```xml
+//! <semver name="app_version">1.10.0-rc.1</semver>
+//! <!--
+//! pub const APP_VERSION: r_resources::Version =
+//!     r_resources::Version::with_pre(1, 10, 0, "rc.1");
+//! pub const APP_VERSION_STR: &str = "1.10.0-rc.1";
+//! -->
+//! ```
+//!
+//! Versions follow Semantic Versioning 2.0: three numbers without
+//! leading zeros, an optional `-pre.release` and optional `+build`
+//! metadata, which only `_STR` keeps.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::{const_identifier, escape_str};
+
+pub struct SemverType;
+
+impl ResourceType for SemverType {
+    fn name(&self) -> &'static str {
+        "semver"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["semver"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Semver
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Semver(text) => {
+                parse_semver(text).map(|_| ())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Semver(text) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode::new(
+            ResourceKind::Semver,
+            parse_semver(text).ok()?,
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Semver {
+            major,
+            minor,
+            patch,
+            pre,
+            text,
+        } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        let constructor = if pre.is_empty() {
+            format!("new({major}, {minor}, {patch})")
+        } else {
+            format!(
+                "with_pre({major}, {minor}, {patch}, \"{}\")",
+                escape_str(pre)
+            )
+        };
+        Some(format!(
+            "{pad}pub const {name}: r_resources::Version = r_resources::Version::{constructor};\n\
+             {pad}/// [`{name}`] as written\n\
+             {pad}pub const {name}_STR: &str = \"{}\";\n",
+            escape_str(text)
+        ))
+    }
+}
+
+/// Version of `text`, with the reason it is not a semantic version
+fn parse_semver(text: &str) -> Result<ResourceValue, String> {
+    let text = text.trim();
+    let invalid = |reason: String| {
+        format!("'{text}' is not a semantic version: {reason}")
+    };
+    let (version, build) = match text.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (text, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let numbers: Vec<&str> = core.split('.').collect();
+    let [major, minor, patch] = numbers[..] else {
+        return Err(invalid("expected major.minor.patch".into()));
+    };
+    let number = |part: &str| numeric(part).map_err(&invalid);
+    let (major, minor, patch) =
+        (number(major)?, number(minor)?, number(patch)?);
+    for identifier in pre.iter().flat_map(|pre| pre.split('.')) {
+        check_identifier(identifier).map_err(&invalid)?;
+        if identifier.bytes().all(|b| b.is_ascii_digit()) {
+            numeric(identifier).map_err(&invalid)?;
+        }
+    }
+    for identifier in build.iter().flat_map(|build| build.split('.'))
+    {
+        check_identifier(identifier).map_err(&invalid)?;
+    }
+    Ok(ResourceValue::Semver {
+        major,
+        minor,
+        patch,
+        pre: pre.unwrap_or_default().to_string(),
+        text: text.to_string(),
+    })
+}
+
+/// Number without leading zeros
+fn numeric(part: &str) -> Result<u64, String> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("'{part}' is not a number"));
+    }
+    if part.len() > 1 && part.starts_with('0') {
+        return Err(format!("'{part}' has a leading zero"));
+    }
+    part.parse()
+        .map_err(|_| format!("'{part}' does not fit in u64"))
+}
+
+/// Pre-release or build identifier, `[0-9A-Za-z-]+`
+fn check_identifier(identifier: &str) -> Result<(), String> {
+    if identifier.is_empty() {
+        return Err("empty identifier".into());
+    }
+    if !identifier
+        .bytes()
+        .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    {
+        return Err(format!(
+            "'{identifier}' may only contain [0-9A-Za-z-]"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn emit(text: &str) -> String {
+        let parsed = ParsedResource {
+            name: "app_version".to_string(),
+            kind: AstResourceKind::Semver,
+            value: ScalarValue::Semver(text.to_string()),
+            meta: Default::default(),
+        };
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = SemverType.build_node(&parsed, origin).unwrap();
+        let key = ResourceKey::from_path("app_version");
+        SemverType.emit_rust(&key, &node, 4).unwrap()
+    }
+
+    #[test]
+    fn versions_emit_a_const_and_the_text() {
+        assert_eq!(
+            emit("1.10.0"),
+            "    pub const APP_VERSION: r_resources::Version = r_resources::Version::new(1, 10, 0);\n    \
+             /// [`APP_VERSION`] as written\n    \
+             pub const APP_VERSION_STR: &str = \"1.10.0\";\n"
+        );
+        assert!(emit("1.10.0-rc.1+build.5").starts_with(
+            "    pub const APP_VERSION: r_resources::Version = r_resources::Version::with_pre(1, 10, 0, \"rc.1\");\n"
+        ));
+        assert!(emit("1.10.0-rc.1+build.5").ends_with(
+            "pub const APP_VERSION_STR: &str = \"1.10.0-rc.1+build.5\";\n"
+        ));
+    }
+
+    #[test]
+    fn malformed_versions_are_rejected() {
+        for (text, reason) in [
+            ("1.2", "expected major.minor.patch"),
+            ("1.2.3.4", "expected major.minor.patch"),
+            ("v1.2.3", "'v1' is not a number"),
+            ("1.02.3", "'02' has a leading zero"),
+            ("1.2.3-", "empty identifier"),
+            ("1.2.3-rc..1", "empty identifier"),
+            ("1.2.3-rc.01", "'01' has a leading zero"),
+            ("1.2.3-rc_1", "'rc_1' may only contain [0-9A-Za-z-]"),
+            ("1.2.3+", "empty identifier"),
+            (
+                "1.2.18446744073709551616",
+                "'18446744073709551616' does not fit in u64",
+            ),
+        ] {
+            assert_eq!(
+                parse_semver(text).unwrap_err(),
+                format!(
+                    "'{text}' is not a semantic version: {reason}"
+                )
+            );
+        }
+        for text in
+            ["0.0.0", "1.2.3-0.a-b.c", "1.2.3+001", "1.2.3-rc-1"]
+        {
+            assert!(parse_semver(text).is_ok(), "{text}");
+        }
+    }
+}
@@ -1,10 +1,13 @@
+use crate::generator::ir::assertion::assertion_items;
+use crate::generator::ir::pattern::Pattern;
+use crate::generator::ir::transform::Transform;
 use crate::generator::ir::types::ResourceType;
 use crate::generator::ir::{
-    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    NodeMeta, ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
 use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::utils::{const_identifier, string_literal};
 
 pub struct StringType;
 
@@ -21,6 +24,18 @@ impl ResourceType for StringType {
         ResourceKind::String
     }
 
+    fn validate(&self, parsed: &ParsedResource) -> Result<(), String> {
+        if let Some(chain) = &parsed.meta.transform {
+            Transform::parse_chain(chain)?;
+        }
+        match &parsed.meta.pattern {
+            Some(pattern) => Pattern::new(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("invalid pattern `{pattern}`: {e}")),
+            None => Ok(()),
+        }
+    }
+
     fn build_node(
         &self,
         parsed: &ParsedResource,
@@ -28,9 +43,23 @@ impl ResourceType for StringType {
     ) -> Option<ResourceNode> {
         if let ScalarValue::Text(value) = &parsed.value {
             Some(ResourceNode {
-                kind: ResourceKind::String,
-                value: ResourceValue::String(value.clone()),
-                origin,
+                meta: NodeMeta {
+                    pattern: parsed.meta.pattern.clone(),
+                    transforms: parsed
+                        .meta
+                        .transform
+                        .as_deref()
+                        .and_then(|chain| {
+                            Transform::parse_chain(chain).ok()
+                        })
+                        .unwrap_or_default(),
+                    ..NodeMeta::default()
+                },
+                ..ResourceNode::new(
+                    ResourceKind::String,
+                    ResourceValue::String(value.clone()),
+                    origin,
+                )
             })
         } else {
             None
@@ -46,9 +75,11 @@ impl ResourceType for StringType {
         if let ResourceValue::String(value) = &node.value {
             let pad = " ".repeat(indent);
             let const_name =
-                sanitize_identifier(&key.name).to_uppercase();
-            let escaped = value.escape_debug();
-            Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
+                const_identifier(&key.name);
+            let literal = string_literal(value);
+            let mut code = format!("{pad}pub const {const_name}: &str = {literal};\n");
+            code.push_str(&assertion_items(&pad, key, node));
+            Some(code)
         } else {
             None
         }
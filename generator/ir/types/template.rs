@@ -4,8 +4,10 @@ use crate::generator::ir::{
     ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
     ResourceValue,
 };
-use crate::generator::parsing::{ParsedResource, ScalarValue};
-use crate::generator::utils::sanitize_identifier;
+use crate::generator::parsing::{self, ParsedResource, ScalarValue};
+use crate::generator::utils::{
+    const_identifier, sanitize_identifier, string_literal,
+};
 
 pub struct TemplateType;
 
@@ -31,41 +33,16 @@ impl ResourceType for TemplateType {
             // Templates with parameters from <template> tags
             ScalarValue::Template { text, params } => {
                 // Convert ast::TemplateParam (with ScalarValue) to model::TemplateParam
-                let model_params: Vec<TemplateParam> = params
-                    .iter()
-                    .map(|p| {
-                        // Convert ScalarValue to TemplateParamValue
-                        let param_value = match &p.value {
-                            crate::generator::parsing::ScalarValue::Text(_) => {
-                                crate::generator::ir::model::TemplateParamValue::String
-                            }
-                            crate::generator::parsing::ScalarValue::Number { explicit_type, .. } => {
-                                crate::generator::ir::model::TemplateParamValue::Number {
-                                    explicit_type: explicit_type.clone(),
-                                }
-                            }
-                            crate::generator::parsing::ScalarValue::Bool(_) => {
-                                crate::generator::ir::model::TemplateParamValue::Bool
-                            }
-                            crate::generator::parsing::ScalarValue::Color(_) => {
-                                crate::generator::ir::model::TemplateParamValue::Color
-                            }
-                            _ => crate::generator::ir::model::TemplateParamValue::String,
-                        };
-                        TemplateParam {
-                            name: p.name.clone(),
-                            value: param_value,
-                        }
-                    })
-                    .collect();
-                Some(ResourceNode {
-                    kind: ResourceKind::Template,
-                    value: ResourceValue::Template {
+                let model_params: Vec<TemplateParam> =
+                    params.iter().map(convert_param).collect();
+                Some(ResourceNode::new(
+                    ResourceKind::Template,
+                    ResourceValue::Template {
                         text: text.clone(),
                         params: model_params,
                     },
                     origin,
-                })
+                ))
             }
             // Templates detected from placeholders in strings
             ScalarValue::Text(value) => {
@@ -74,16 +51,16 @@ impl ResourceType for TemplateType {
                     parsed.kind,
                     crate::generator::parsing::ResourceKind::Template
                 ) || contains_template_placeholders(value);
-                
+
                 if is_template {
-                    Some(ResourceNode {
-                        kind: ResourceKind::Template,
-                        value: ResourceValue::Template {
+                    Some(ResourceNode::new(
+                        ResourceKind::Template,
+                        ResourceValue::Template {
                             text: value.clone(),
                             params: Vec::new(),
                         },
                         origin,
-                    })
+                    ))
                 } else {
                     None
                 }
@@ -98,167 +75,234 @@ impl ResourceType for TemplateType {
         node: &ResourceNode,
         indent: usize,
     ) -> Option<String> {
-        if let ResourceValue::Template { text, params } = &node.value {
-            let pad = " ".repeat(indent);
-            let func_name = sanitize_identifier(&key.name);
-            
-            // If we have named parameters, use them
-            if !params.is_empty() {
-                // Generate function with named parameters
-                let param_defs: Vec<String> = params
-                    .iter()
-                    .map(|p| {
-                        let rust_type = match &p.value {
-                            TemplateParamValue::String => "&str",
-                            TemplateParamValue::Number { explicit_type } => {
-                                // Use explicit_type if provided, otherwise default to i64
-                                match explicit_type.as_deref() {
-                                    Some("bigdecimal") => "r_resources::BigDecimal",
-                                    Some("i8") => "i8",
-                                    Some("i16") => "i16",
-                                    Some("i32") => "i32",
-                                    Some("i64") => "i64",
-                                    Some("u8") => "u8",
-                                    Some("u16") => "u16",
-                                    Some("u32") => "u32",
-                                    Some("u64") => "u64",
-                                    Some("f32") => "f32",
-                                    Some("f64") => "f64",
-                                    _ => "i64", // Default for numbers
-                                }
-                            }
-                            TemplateParamValue::Bool => "bool",
-                            TemplateParamValue::Color => "&str",
-                        };
-                        format!("{}: {}", sanitize_identifier(&p.name), rust_type)
-                    })
-                    .collect();
-                let params_str = param_defs.join(", ");
-                
-                // Replace {name} with {} in format string
-                let mut format_str = text.clone();
-                for param in params {
-                    format_str = format_str.replace(&format!("{{{}}}", param.name), "{}");
-                }
-                let format_escaped = format_str.escape_debug();
-                
-                // Generate parameter names for format! macro
-                // For BigDecimal and other Display types, we can use them directly in format!
-                let param_names: Vec<String> = params
-                    .iter()
-                    .map(|p| sanitize_identifier(&p.name))
-                    .collect();
-                let param_names_str = param_names.join(", ");
-                
-                Some(format!(
-                    "{pad}pub fn {func_name}({params_str}) -> String {{\n\
-                    {pad}    format!(\"{format_escaped}\", {param_names_str})\n\
-                    {pad}}}\n"
-                ))
-            } else {
-                // No parameters, check for old-style placeholders or treat as constant
-                let placeholder_count = count_placeholders(text);
-                if placeholder_count == 0 {
-                    // No placeholders, treat as regular string (use uppercase for consts)
-                    let escaped = text.escape_debug();
-                    let const_name = func_name.to_uppercase();
-                    Some(format!("{pad}pub const {const_name}: &str = \"{escaped}\";\n"))
-                } else {
-                    // Old-style placeholders %1$s, %2$d, etc.
-                    let params: Vec<String> = (1..=placeholder_count)
-                        .map(|i| format!("arg{i}: &str"))
-                        .collect();
-                    let params_str = params.join(", ");
-                    
-                    // Generate the format string replacement
-                    let mut format_str = text.clone();
-                    for i in 1..=placeholder_count {
-                        // Replace %1$s, %2$s, etc. with {}
-                        format_str = format_str.replace(&format!("%{i}$s"), "{}");
-                        format_str = format_str.replace(&format!("%{i}$d"), "{}");
-                    }
-                    let format_escaped = format_str.escape_debug();
-                    
-                    Some(format!(
-                        "{pad}pub fn {func_name}({params_str}) -> String {{\n\
-                        {pad}    format!(\"{format_escaped}\", {})\n\
-                        {pad}}}\n",
-                        (1..=placeholder_count)
-                            .map(|i| format!("arg{i}"))
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ))
-                }
+        let ResourceValue::Template { text, params } = &node.value
+        else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let func_name = sanitize_identifier(&key.name);
+
+        let Some((signature, format_call)) =
+            template_format_call(text, params)
+        else {
+            // No placeholders, treat as regular string (use uppercase for consts)
+            let literal = string_literal(text);
+            let const_name = const_identifier(&key.name);
+            return Some(format!(
+                "{pad}pub const {const_name}: &str = {literal};\n"
+            ));
+        };
+        let params_str = signature.params;
+
+        // For BigDecimal and other Display types, we can use them directly in format!
+        Some(format!(
+            "{pad}pub fn {func_name}({params_str}) -> String {{\n\
+            {pad}    {format_call}\n\
+            {pad}}}\n"
+        ))
+    }
+}
+
+/// Signature of the function generated for a template and the
+/// `format!` call of its body, or `None` for a template emitted as a
+/// `&str` constant
+pub fn template_format_call(
+    text: &str,
+    params: &[TemplateParam],
+) -> Option<(TemplateSignature, String)> {
+    let signature = template_signature(text, params)?;
+    let format_str = if params.is_empty() {
+        positional_format_string(text, count_placeholders(text))
+    } else {
+        named_format_string(text, params)
+    };
+    let call = format!(
+        "format!({}, {})",
+        string_literal(&format_str),
+        signature.args
+    );
+    Some((signature, call))
+}
+
+/// Signature of the function generated for a template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSignature {
+    /// Parameter list, e.g. `name: &str, count: i64`
+    pub params: String,
+    /// Arguments forwarding those parameters, e.g. `name, count`
+    pub args: String,
+}
+
+/// Returns the signature of the function generated for a template, or
+/// `None` when it has no placeholders and is emitted as a `&str` constant
+pub fn template_signature(
+    text: &str,
+    params: &[TemplateParam],
+) -> Option<TemplateSignature> {
+    if !params.is_empty() {
+        // Named parameters take precedence over old-style placeholders
+        return Some(TemplateSignature {
+            params: params
+                .iter()
+                .map(|p| {
+                    format!(
+                        "{}: {}",
+                        sanitize_identifier(&p.name),
+                        param_rust_type(&p.value)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            args: params
+                .iter()
+                .map(|p| sanitize_identifier(&p.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+    }
+
+    let placeholder_count = count_placeholders(text);
+    (placeholder_count > 0).then(|| TemplateSignature {
+        params: (1..=placeholder_count)
+            .map(|i| format!("arg{i}: &str"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        args: (1..=placeholder_count)
+            .map(|i| format!("arg{i}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    })
+}
+
+/// Converts a parsed template parameter into its IR representation
+fn convert_param(param: &parsing::TemplateParam) -> TemplateParam {
+    let value = match &param.value {
+        ScalarValue::Number { explicit_type, .. } => {
+            TemplateParamValue::Number {
+                explicit_type: explicit_type.clone(),
             }
-        } else {
-            None
         }
+        ScalarValue::Bool(_) => TemplateParamValue::Bool,
+        ScalarValue::Color(_) => TemplateParamValue::Color,
+        _ => TemplateParamValue::String,
+    };
+    TemplateParam {
+        name: param.name.clone(),
+        value,
     }
 }
 
-/// Check if a string contains template placeholders (e.g., %1$s, %2$d)
-fn contains_template_placeholders(text: &str) -> bool {
-    // Pattern: % followed by number, $, and type specifier (s, d, etc.)
-    let mut chars = text.chars().peekable();
-    while let Some(ch) = chars.next() {
-        if ch == '%' {
-            // Check if followed by number
-            let mut has_number = false;
-            while let Some(&next) = chars.peek() {
-                if next.is_ascii_digit() {
-                    has_number = true;
-                    chars.next();
-                } else if next == '$' && has_number {
-                    chars.next();
-                    // Check for type specifier
-                    if let Some(&spec) = chars.peek() {
-                        if matches!(spec, 's' | 'd' | 'f' | 'x' | 'X') {
-                            return true;
-                        }
-                    }
-                    break;
-                } else {
-                    break;
-                }
+/// Rust type used for a template parameter in the generated signature
+fn param_rust_type(value: &TemplateParamValue) -> &'static str {
+    match value {
+        TemplateParamValue::String | TemplateParamValue::Color => {
+            "&str"
+        }
+        TemplateParamValue::Bool => "bool",
+        // Use explicit_type if provided, otherwise default to i64
+        TemplateParamValue::Number { explicit_type } => {
+            match explicit_type.as_deref() {
+                Some("bigdecimal") => "r_resources::BigDecimal",
+                Some("i8") => "i8",
+                Some("i16") => "i16",
+                Some("i32") => "i32",
+                Some("u8") => "u8",
+                Some("u16") => "u16",
+                Some("u32") => "u32",
+                Some("u64") => "u64",
+                Some("isize") => "isize",
+                Some("usize") => "usize",
+                Some("f32") => "f32",
+                Some("f64") => "f64",
+                _ => "i64", // Default for numbers
             }
         }
     }
-    false
+}
+
+/// Replaces `{name}` placeholders with `{}` for `format!`, or with
+/// the index of their parameter (`{1}`) when some are repeated or out
+/// of the order of the parameters
+fn named_format_string(text: &str, params: &[TemplateParam]) -> String {
+    let placeholders: Vec<String> =
+        params.iter().map(|p| format!("{{{}}}", p.name)).collect();
+    let in_order = text_placeholders(text, &placeholders)
+        .eq(0..params.len());
+    let mut format_str = text.to_string();
+    for (index, placeholder) in placeholders.iter().enumerate() {
+        let arg = if in_order {
+            "{}".to_string()
+        } else {
+            format!("{{{index}}}")
+        };
+        format_str = format_str.replace(placeholder, &arg);
+    }
+    format_str
+}
+
+/// Index in `placeholders` of each placeholder of `text`, in order
+fn text_placeholders<'a>(
+    text: &'a str,
+    placeholders: &'a [String],
+) -> impl Iterator<Item = usize> + 'a {
+    text.match_indices('{').filter_map(|(pos, _)| {
+        let rest = &text[pos..];
+        placeholders.iter().position(|p| rest.starts_with(p.as_str()))
+    })
+}
+
+/// Replaces `%1$s`, `%2$d`, etc. with `{}` for `format!`
+fn positional_format_string(
+    text: &str,
+    placeholder_count: usize,
+) -> String {
+    let mut format_str = text.to_string();
+    for i in 1..=placeholder_count {
+        format_str = format_str.replace(&format!("%{i}$s"), "{}");
+        format_str = format_str.replace(&format!("%{i}$d"), "{}");
+    }
+    format_str
+}
+
+/// Scans `%N$x` placeholders and returns their numbers (e.g., %1$s, %2$d)
+fn placeholder_numbers(text: &str) -> Vec<usize> {
+    let mut numbers = Vec::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find('%') {
+        rest = &rest[pos + 1..];
+        let digits_len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits_len == 0 {
+            continue;
+        }
+        let after = &rest[digits_len..];
+        let Some(spec) = after.strip_prefix('$') else {
+            continue;
+        };
+        // Check for type specifier
+        if !spec.starts_with(['s', 'd', 'f', 'x', 'X']) {
+            continue;
+        }
+        if let Ok(num) = rest[..digits_len].parse::<usize>() {
+            numbers.push(num);
+        }
+    }
+    numbers
+}
+
+/// Check if a string contains template placeholders (e.g., %1$s, %2$d)
+fn contains_template_placeholders(text: &str) -> bool {
+    !placeholder_numbers(text).is_empty()
 }
 
 /// Count the number of unique placeholders in a template string
 fn count_placeholders(template: &str) -> usize {
     use std::collections::HashSet;
-    let mut placeholders = HashSet::new();
-    let mut chars = template.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        if ch == '%' {
-            let mut number = String::new();
-            // Collect digits
-            while let Some(&next) = chars.peek() {
-                if next.is_ascii_digit() {
-                    number.push(next);
-                    chars.next();
-                } else if next == '$' && !number.is_empty() {
-                    chars.next();
-                    // Check for type specifier
-                    if let Some(&spec) = chars.peek() {
-                        if matches!(spec, 's' | 'd' | 'f' | 'x' | 'X') {
-                            if let Ok(num) = number.parse::<usize>() {
-                                placeholders.insert(num);
-                            }
-                        }
-                    }
-                    break;
-                } else {
-                    break;
-                }
-            }
-        }
-    }
-    
-    placeholders.len()
+    placeholder_numbers(template)
+        .into_iter()
+        .collect::<HashSet<_>>()
+        .len()
 }
 
 #[cfg(test)]
@@ -320,6 +364,7 @@ mod tests {
             name: "welcome_message".to_string(),
             kind: AstResourceKind::String, // Templates can be detected in strings
             value: ScalarValue::Text("Hello %1$s!".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -342,6 +387,7 @@ mod tests {
             name: "welcome_message".to_string(),
             kind: AstResourceKind::Template, // Explicit template tag
             value: ScalarValue::Text("Hello %1$s!".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -363,6 +409,7 @@ mod tests {
             name: "simple_string".to_string(),
             kind: AstResourceKind::String,
             value: ScalarValue::Text("Hello World".to_string()),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -378,6 +425,7 @@ mod tests {
             name: "not_template".to_string(),
             kind: AstResourceKind::Bool,
             value: ScalarValue::Bool(true),
+            meta: Default::default(),
         };
         let origin = ResourceOrigin::new(PathBuf::from("test.xml"), false);
 
@@ -390,17 +438,17 @@ mod tests {
     fn test_emit_rust_single_placeholder() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "welcome".to_string(),
+            namespace: vec![].into(),
+            name: "welcome".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::Template {
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::Template {
                 text: "Hello %1$s!".to_string(),
                 params: vec![],
             },
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub fn welcome"));
@@ -413,17 +461,17 @@ mod tests {
     fn test_emit_rust_multiple_placeholders() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "message".to_string(),
+            namespace: vec![].into(),
+            name: "message".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::Template {
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::Template {
                 text: "Name: %1$s, Age: %2$d".to_string(),
                 params: vec![],
             },
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub fn message") || result.contains("pub fn MESSAGE"));
@@ -437,17 +485,17 @@ mod tests {
     fn test_emit_rust_no_placeholders() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "simple".to_string(),
+            namespace: vec![].into(),
+            name: "simple".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::Template {
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::Template {
                 text: "Hello World".to_string(),
                 params: vec![],
             },
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         // For templates without placeholders, it should be a const (uppercase for consts)
@@ -459,14 +507,14 @@ mod tests {
     fn test_emit_rust_wrong_value_type() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "not_template".to_string(),
-        };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::String("Hello".to_string()),
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
+            namespace: vec![].into(),
+            name: "not_template".into(),
         };
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::String("Hello".to_string()),
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4);
         assert!(result.is_none());
@@ -477,17 +525,17 @@ mod tests {
     fn test_emit_rust_indentation() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec![],
-            name: "test_template".to_string(),
+            namespace: vec![].into(),
+            name: "test_template".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::Template {
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::Template {
                 text: "Hello %1$s!".to_string(),
                 params: vec![],
             },
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 8).unwrap();
         assert!(result.starts_with("        pub fn"));
@@ -498,20 +546,45 @@ mod tests {
     fn test_emit_rust_namespaced() {
         let handler = TemplateType;
         let key = ResourceKey {
-            namespace: vec!["ui".to_string(), "messages".to_string()],
-            name: "welcome".to_string(),
+            namespace: vec!["ui".to_string(), "messages".to_string()].into(),
+            name: "welcome".into(),
         };
-        let node = ResourceNode {
-            kind: ModelResourceKind::Template,
-            value: ResourceValue::Template {
+        let node = ResourceNode::new(
+            ModelResourceKind::Template,
+            ResourceValue::Template {
                 text: "Hello %1$s!".to_string(),
                 params: vec![],
             },
-            origin: ResourceOrigin::new(PathBuf::from("test.xml"), false),
-        };
+            ResourceOrigin::new(PathBuf::from("test.xml"), false),
+        );
 
         let result = handler.emit_rust(&key, &node, 4).unwrap();
         assert!(result.contains("pub fn welcome"));
     }
-}
 
+    // Test repeated and reordered named placeholders
+    #[test]
+    fn test_named_placeholders_index_their_parameter() {
+        let params = |names: &[&str]| -> Vec<TemplateParam> {
+            names
+                .iter()
+                .map(|name| TemplateParam {
+                    name: (*name).to_string(),
+                    value: TemplateParamValue::String,
+                })
+                .collect()
+        };
+        assert_eq!(
+            named_format_string("{a} and {b}", &params(&["a", "b"])),
+            "{} and {}"
+        );
+        assert_eq!(
+            named_format_string("{b} and {a}", &params(&["a", "b"])),
+            "{1} and {0}"
+        );
+        assert_eq!(
+            named_format_string("Re: {u} {u}", &params(&["u"])),
+            "Re: {0} {0}"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! `<uuid>`, generated as a `u128`, its bytes and its text.
+//!
+//! ```xml
+//! <uuid name="app_namespace">6ba7b810-9dad-11d1-80b4-00c04fd430c8</uuid>
+//! <!--
+//! pub const APP_NAMESPACE: u128 = 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8;
+//! pub const APP_NAMESPACE_BYTES: [u8; 16] = [0x6b, 0xa7, ...];
+//! pub const APP_NAMESPACE_STR: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+//! -->
+//! ```
+//!
+//! Only the hyphenated form is accepted, in either case; `_STR` is
+//! lowercase. The bytes are in the order of the text, as
+//! `Uuid::from_bytes` expects, and the number is what
+//! `Uuid::from_u128` expects.
+
+use crate::generator::ir::types::ResourceType;
+use crate::generator::ir::{
+    ResourceKey, ResourceKind, ResourceNode, ResourceOrigin,
+    ResourceValue,
+};
+use crate::generator::parsing::{ParsedResource, ScalarValue};
+use crate::generator::utils::const_identifier;
+
+/// Hex digits of each hyphen-separated group
+const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+
+pub struct UuidType;
+
+impl ResourceType for UuidType {
+    fn name(&self) -> &'static str {
+        "uuid"
+    }
+
+    fn xml_tags(&self) -> &'static [&'static str] {
+        &["uuid"]
+    }
+
+    fn resource_kind(&self) -> ResourceKind {
+        ResourceKind::Uuid
+    }
+
+    fn validate(
+        &self,
+        parsed: &ParsedResource,
+    ) -> Result<(), String> {
+        match &parsed.value {
+            ScalarValue::Uuid(text) => parse_uuid(text).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    fn build_node(
+        &self,
+        parsed: &ParsedResource,
+        origin: ResourceOrigin,
+    ) -> Option<ResourceNode> {
+        let ScalarValue::Uuid(text) = &parsed.value else {
+            return None;
+        };
+        Some(ResourceNode::new(
+            ResourceKind::Uuid,
+            ResourceValue::Uuid(parse_uuid(text).ok()?),
+            origin,
+        ))
+    }
+
+    fn emit_rust(
+        &self,
+        key: &ResourceKey,
+        node: &ResourceNode,
+        indent: usize,
+    ) -> Option<String> {
+        let ResourceValue::Uuid(value) = node.value else {
+            return None;
+        };
+        let pad = " ".repeat(indent);
+        let name = const_identifier(&key.name);
+        let text = uuid_text(value);
+        let bytes: Vec<String> = value
+            .to_be_bytes()
+            .iter()
+            .map(|byte| format!("{byte:#04x}"))
+            .collect();
+        Some(format!(
+            "{pad}pub const {name}: u128 = 0x{};\n\
+             {pad}/// [`{name}`] as bytes, in the order of the text\n\
+             {pad}pub const {name}_BYTES: [u8; 16] = [{}];\n\
+             {pad}/// [`{name}`] as hyphenated lowercase text\n\
+             {pad}pub const {name}_STR: &str = \"{text}\";\n",
+            text.replace('-', "_"),
+            bytes.join(", "),
+        ))
+    }
+}
+
+/// Hyphenated lowercase form of `value`
+pub fn uuid_text(value: u128) -> String {
+    let hex = format!("{value:032x}");
+    let mut text = String::with_capacity(36);
+    let mut start = 0;
+    for (i, len) in GROUPS.iter().enumerate() {
+        if i > 0 {
+            text.push('-');
+        }
+        text.push_str(&hex[start..start + len]);
+        start += len;
+    }
+    text
+}
+
+/// Value of a hyphenated UUID, uppercase digits included
+fn parse_uuid(text: &str) -> Result<u128, String> {
+    let text = text.trim();
+    let groups: Vec<&str> = text.split('-').collect();
+    let lengths: Vec<usize> =
+        groups.iter().map(|g| g.len()).collect();
+    if lengths != GROUPS {
+        return Err(format!(
+            "'{text}' is not a UUID: expected 8-4-4-4-12 hex digits"
+        ));
+    }
+    if let Some(c) =
+        text.chars().find(|c| *c != '-' && !c.is_ascii_hexdigit())
+    {
+        return Err(format!(
+            "'{text}' is not a UUID: '{c}' is not a hex digit"
+        ));
+    }
+    u128::from_str_radix(&groups.concat(), 16).map_err(|_| {
+        format!(
+            "'{text}' is not a UUID: expected 8-4-4-4-12 hex digits"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::parsing::ResourceKind as AstResourceKind;
+    use std::path::PathBuf;
+
+    fn emit(text: &str) -> String {
+        let parsed = ParsedResource {
+            name: "app_namespace".to_string(),
+            kind: AstResourceKind::Uuid,
+            value: ScalarValue::Uuid(text.to_string()),
+            meta: Default::default(),
+        };
+        let origin =
+            ResourceOrigin::new(PathBuf::from("values.xml"), false);
+        let node = UuidType.build_node(&parsed, origin).unwrap();
+        let key = ResourceKey::from_path("app_namespace");
+        UuidType.emit_rust(&key, &node, 4).unwrap()
+    }
+
+    #[test]
+    fn uuids_emit_a_number_bytes_and_text() {
+        assert_eq!(
+            emit("6ba7b810-9dad-11d1-80b4-00c04fd430c8"),
+            "    pub const APP_NAMESPACE: u128 = 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8;\n    \
+             /// [`APP_NAMESPACE`] as bytes, in the order of the text\n    \
+             pub const APP_NAMESPACE_BYTES: [u8; 16] = [0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];\n    \
+             /// [`APP_NAMESPACE`] as hyphenated lowercase text\n    \
+             pub const APP_NAMESPACE_STR: &str = \"6ba7b810-9dad-11d1-80b4-00c04fd430c8\";\n"
+        );
+        assert_eq!(
+            emit(" 6BA7B810-9DAD-11D1-80B4-00C04FD430C8 "),
+            emit("6ba7b810-9dad-11d1-80b4-00c04fd430c8")
+        );
+        assert_eq!(
+            uuid_text(1),
+            "00000000-0000-0000-0000-000000000001"
+        );
+    }
+
+    #[test]
+    fn malformed_uuids_are_rejected() {
+        for (text, reason) in [
+            (
+                "6ba7b8109dad11d180b400c04fd430c8",
+                "expected 8-4-4-4-12 hex digits",
+            ),
+            (
+                "6ba7b81-09dad-11d1-80b4-00c04fd430c8",
+                "expected 8-4-4-4-12 hex digits",
+            ),
+            (
+                "{6ba7b810-9dad-11d1-80b4-00c04fd430c8}",
+                "expected 8-4-4-4-12 hex digits",
+            ),
+            (
+                "6ba7b810-9dad-11d1-80b4-00c04fd430cg",
+                "'g' is not a hex digit",
+            ),
+            (
+                "+ba7b810-9dad-11d1-80b4-00c04fd430c8",
+                "'+' is not a hex digit",
+            ),
+        ] {
+            assert_eq!(
+                parse_uuid(text),
+                Err(format!("'{text}' is not a UUID: {reason}"))
+            );
+        }
+    }
+}
@@ -3,9 +3,10 @@
 //! This module implements a staged architecture for generating Rust code from XML resource files.
 //! The pipeline consists of five main stages:
 //!
-//! 1. **Input** (`input/`) - File discovery, scanning, and profile preprocessing
+//! 1. **Input** (`input/`) - File discovery and scanning
 //! 2. **Parsing** (`parsing/`) - XML parsing into structured AST
-//! 3. **IR** (`ir/`) - Intermediate Representation (`ResourceGraph`) for unified resource model
+//! 3. **IR** (`ir/`) - Intermediate Representation (`ResourceGraph`) for unified resource model,
+//!    including profile resolution
 //! 4. **Analysis** (`analysis/`) - Validations and structured error reporting
 //! 5. **Generation** (`generation/`) - Code generation from IR
 //!
@@ -13,20 +14,37 @@
 //! maintainable and extensible.
 
 pub mod analysis;
+pub mod color;
+pub mod diag;
+#[allow(dead_code)] // Used by the CLI, not by builds
+pub mod diff;
+#[allow(dead_code)] // Used by the CLI, not by builds
+pub mod format;
 pub mod generation;
+#[allow(dead_code)] // Used by the CLI, not by builds
+pub mod import;
 pub mod input;
 pub mod ir;
+pub mod options;
 pub mod parsing;
 pub mod pipeline;
 pub mod utils;
 
 pub use input::BuildPlan;
+pub use options::BuildOptions;
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use options::ReferenceStyle;
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use options::LazyStatics;
 
 #[derive(Debug)]
 pub enum BuildError {
     Pipeline(pipeline::PipelineError),
     Analysis(Vec<analysis::AnalysisError>),
     Generation(Vec<analysis::AnalysisError>),
+    Export(std::io::Error),
+    /// The sources checked for unused resources could not be read
+    Sources(std::io::Error),
 }
 
 impl std::fmt::Display for BuildError {
@@ -36,11 +54,18 @@ impl std::fmt::Display for BuildError {
     ) -> std::fmt::Result {
         match self {
             Self::Pipeline(err) => write!(f, "{err}"),
+            Self::Export(err) => {
+                write!(f, "failed to export resources: {err}")
+            }
+            Self::Sources(err) => {
+                write!(f, "failed to read sources: {err}")
+            }
             Self::Analysis(errors) | Self::Generation(errors) => {
-                for err in errors {
-                    writeln!(f, "{err:?}")?;
-                }
-                Ok(())
+                let lines: Vec<String> = errors
+                    .iter()
+                    .map(|err| err.to_diagnostic().to_string())
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
             }
         }
     }
@@ -48,109 +73,441 @@ impl std::fmt::Display for BuildError {
 
 impl std::error::Error for BuildError {}
 
+impl BuildError {
+    /// The errors of this build, for [`diag::render`]
+    pub fn diagnostics(&self) -> Vec<diag::Diagnostic> {
+        match self {
+            Self::Analysis(errors) | Self::Generation(errors) => errors
+                .iter()
+                .map(analysis::AnalysisError::to_diagnostic)
+                .collect(),
+            error => vec![diag::Diagnostic::error(error.to_string())],
+        }
+    }
+}
+
 #[allow(dead_code)] // Public API, may be used by consumers
 pub fn build_with_plan(
     plan: &BuildPlan,
 ) -> Result<generation::OutputArtifacts, BuildError> {
-    build_with_plan_and_options(
-        plan,
-        analysis::ValidationOptions::default(),
-    )
+    build_with_plan_and_options(plan, &BuildOptions::default())
 }
 
+/// Runs the pipeline for `plan`, applying the validation and export
+/// settings of `options` (its `profile` is ignored: the plan's wins)
 pub fn build_with_plan_and_options(
     plan: &BuildPlan,
-    validation_options: analysis::ValidationOptions,
+    options: &BuildOptions,
 ) -> Result<generation::OutputArtifacts, BuildError> {
-    let pipeline_output =
-        pipeline::build_graph_with_options(plan, validation_options)
-            .map_err(BuildError::Pipeline)?;
+    let mut rust = String::new();
+    let warnings = build_with_plan_into(&mut rust, plan, options)?;
+    Ok(generation::OutputArtifacts { rust, warnings })
+}
 
-    // Print warnings
-    for warning in &pipeline_output.analysis_result.warnings {
-        eprintln!("warning: {}", warning.message);
+/// Same as [`build_with_plan_and_options`], streaming the code into
+/// `code` and returning the warnings
+pub fn build_with_plan_into(
+    code: &mut dyn generation::CodeSink,
+    plan: &BuildPlan,
+    options: &BuildOptions,
+) -> Result<Vec<String>, BuildError> {
+    build_with_report(code, plan, options)
+        .map(|(warnings, _)| warnings)
+}
+
+/// Same as [`build_with_plan_into`], also returning the report of the
+/// generated code when `options.report` is set
+pub fn build_with_report(
+    code: &mut dyn generation::CodeSink,
+    plan: &BuildPlan,
+    options: &BuildOptions,
+) -> Result<
+    (Vec<String>, Option<generation::BuildReport>),
+    BuildError,
+> {
+    let pipeline_output = pipeline::build_graph_with_options(
+        plan,
+        options.validation_options(),
+        &options.file_namespaces(),
+    )
+    .map_err(BuildError::Pipeline)?;
+    let mut pipeline_output = pipeline_output;
+    if options.warn_unused {
+        let warnings = unused_warnings(
+            &pipeline_output.graph,
+            &options.unused_source_dir(&plan.resources_dir),
+        );
+        pipeline_output.analysis_result.warnings.extend(warnings);
     }
 
-    // Errors stop the build
+    // Warnings are printed as they are, errors stop the build once
+    // every stage has reported its own
+    for warning in &pipeline_output.analysis_result.warnings {
+        eprintln!("{}", warning.to_diagnostic());
+    }
     if !pipeline_output.analysis_result.errors.is_empty() {
         return Err(BuildError::Analysis(
             pipeline_output.analysis_result.errors,
         ));
     }
 
-    generation::emit(
-        &pipeline_output.graph,
-        &pipeline_output.analysis_result.warnings,
-    )
-    .map_err(BuildError::Generation)
+    if let Some(dir) = &options.export_fluent {
+        generation::fluent::export_fluent(&pipeline_output.graph, dir)
+            .map_err(BuildError::Export)?;
+    }
+    if let Some(path) = &options.export_markdown {
+        generation::markdown::export_markdown(
+            &pipeline_output.graph,
+            &plan.resources_dir,
+            options.ordering,
+            path,
+        )
+        .map_err(BuildError::Export)?;
+    }
+    if let Some(path) = &options.export_typescript {
+        generation::typescript::export_typescript(
+            &pipeline_output.graph,
+            path,
+        )
+        .map_err(BuildError::Export)?;
+    }
+    if let Some(path) = &options.export_kotlin {
+        generation::kotlin::export_kotlin(
+            &pipeline_output.graph,
+            options.kotlin_package.as_deref(),
+            path,
+        )
+        .map_err(BuildError::Export)?;
+    }
+    if let Some(path) = &options.export_c_header {
+        generation::c_header::export_c_header(
+            &pipeline_output.graph,
+            path,
+        )
+        .map_err(BuildError::Export)?;
+    }
+
+    let raw_assets = if options.disable_raw {
+        Vec::new()
+    } else {
+        input::raw_assets::load_raw_assets(
+            &plan.resources_dir,
+            options.raw_file_size_limit(),
+        )
+        .map_err(input_error)?
+    };
+
+    let mut source_files: Vec<String> = pipeline_output
+        .source_files
+        .iter()
+        .map(|file| utils::display_path(file, &plan.resources_dir))
+        .collect();
+    source_files.sort();
+
+    let mut graph = pipeline_output.graph;
+    if options.pseudolocalize
+        || plan.profile == ir::pseudo::PSEUDO_PROFILE
+    {
+        ir::pseudo::pseudolocalize_graph(&mut graph);
+    }
+    let option_error = |message| {
+        BuildError::Generation(vec![analysis::AnalysisError::new(
+            message, None,
+        )])
+    };
+    ir::types::widen_graph(&mut graph, &options.widen)
+        .map_err(option_error)?;
+    let lazy = options.lazy_statics().map_err(option_error)?;
+
+    let emit_options = generation::EmitOptions {
+        profile: &plan.profile,
+        lookup: !options.disable_lookup,
+        provenance_root: (!options.disable_provenance)
+            .then_some(plan.resources_dir.as_path()),
+        typed_modules: !options.disable_typed_modules,
+        raw_assets: &raw_assets,
+        hot_reload: cfg!(feature = "hot-reload")
+            && !options.disable_hot_reload,
+        registries: options.registries,
+        json: cfg!(feature = "serde") && !options.disable_json,
+        regex: cfg!(feature = "regex"),
+        source_files: &source_files,
+        generated_at: options.generated_at(),
+        ordering: options.ordering,
+        flatten_aliases: options.flatten_aliases.then(|| {
+            options.flatten_separator.as_deref().unwrap_or("_")
+        }),
+        reference_style: options.reference_style,
+        testable: options.testable,
+        lazy,
+    };
+    let warnings = &pipeline_output.analysis_result.warnings;
+    if !options.report {
+        let warnings = generation::emit_to(
+            code,
+            &graph,
+            warnings,
+            &emit_options,
+        );
+        return Ok((warnings, None));
+    }
+    // Counted as written, so the report matches the generated code
+    let mut counting = generation::CountingSink::new(code);
+    let warnings = generation::emit_to(
+        &mut counting,
+        &graph,
+        warnings,
+        &emit_options,
+    );
+    let report = generation::BuildReport::new(
+        &graph,
+        &raw_assets,
+        &plan.profile,
+        counting.finish(),
+    );
+    Ok((warnings, Some(report)))
+}
+
+fn input_error(e: input::LoaderError) -> BuildError {
+    BuildError::Pipeline(pipeline::PipelineError::Input(e))
+}
+
+/// Resources of `graph` never mentioned in `src_dir`, or why the
+/// sources could not be scanned
+fn unused_warnings(
+    graph: &ir::ResourceGraph,
+    src_dir: &std::path::Path,
+) -> Vec<analysis::AnalysisWarning> {
+    match analysis::unused::SourceTokens::scan_dir(src_dir) {
+        Ok(tokens) => analysis::unused::check_unused(graph, &tokens),
+        Err(e) => vec![analysis::AnalysisWarning::new(
+            format!(
+                "cannot check for unused resources in '{}': {e}",
+                src_dir.display()
+            ),
+            None,
+        )],
+    }
+}
+
+/// Unused-resource report of `plan` against the `.rs` files of
+/// `src_dir`, without generating code (`r-res lint-unused`)
+#[allow(dead_code)] // Public API, used by the CLI
+pub fn lint_unused(
+    plan: &BuildPlan,
+    src_dir: &std::path::Path,
+) -> Result<Vec<analysis::AnalysisWarning>, BuildError> {
+    let output = pipeline::build_graph(plan).map_err(BuildError::Pipeline)?;
+    if !output.analysis_result.errors.is_empty() {
+        return Err(BuildError::Analysis(output.analysis_result.errors));
+    }
+    let tokens = analysis::unused::SourceTokens::scan_dir(src_dir)
+        .map_err(BuildError::Sources)?;
+    Ok(analysis::unused::check_unused(&output.graph, &tokens))
+}
+
+/// Resources that differ from `old` to `new`, see [`diff`]
+/// (`r-res diff`)
+#[allow(dead_code)] // Public API, used by the CLI
+pub fn diff_plans(
+    old: &BuildPlan,
+    new: &BuildPlan,
+) -> Result<Vec<diff::Change>, BuildError> {
+    let graph = |plan| {
+        let output = pipeline::build_graph(plan)
+            .map_err(BuildError::Pipeline)?;
+        if output.analysis_result.errors.is_empty() {
+            Ok(output.graph)
+        } else {
+            Err(BuildError::Analysis(output.analysis_result.errors))
+        }
+    };
+    Ok(diff::diff_graphs(&graph(old)?, &graph(new)?))
 }
 
 /// Writes the generated code to `OUT_DIR/r_generated.rs`
+#[allow(dead_code)] // Public API, may be used by consumers
 pub fn write_generated_code(code: &str) -> std::io::Result<()> {
-    use std::fs;
-    use std::path::Path;
+    let path = generated_code_path()?;
+    write_generated_code_to(&path, code)?;
+    announce_generated_code(&path);
+    Ok(())
+}
+
+/// Variable locating the generated file for `include_resources!`, set
+/// for the crate of the build script
+pub const GENERATED_ENV_VAR: &str = "R_RESOURCES_GENERATED";
+
+/// Points `include_resources!` at `path`, for build scripts writing
+/// the generated code themselves
+pub fn announce_generated_code(path: &std::path::Path) {
+    println!("cargo:rustc-env={GENERATED_ENV_VAR}={}", path.display());
+}
+
+/// Points `include_resources!` at `OUT_DIR/r_generated.rs` when run
+/// by a build script, where the code returned by the
+/// `build_with_plan` entry points used to be included from
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn announce_default_generated_code() {
+    if let Ok(path) = generated_code_path() {
+        announce_generated_code(&path);
+    }
+}
 
+/// `OUT_DIR/r_generated.rs`, included by `include_resources!`
+fn generated_code_path() -> std::io::Result<std::path::PathBuf> {
     let out_dir = std::env::var("OUT_DIR").map_err(|_| {
         std::io::Error::new(
             std::io::ErrorKind::NotFound,
             "OUT_DIR environment variable not set",
         )
     })?;
-    let dest_path = Path::new(&out_dir).join("r_generated.rs");
-    fs::write(&dest_path, code)
+    Ok(std::path::Path::new(&out_dir).join("r_generated.rs"))
+}
+
+/// Writes the generated code to `path`, creating its parent
+/// directories
+pub fn write_generated_code_to(
+    path: &std::path::Path,
+    code: &str,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, code)
 }
 
 /// Main build function (equivalent to legacy `codegen::build()`)
 ///
 /// Scans `res/` and generates code in `OUT_DIR/r_generated.rs`
 pub fn build() {
+    build_with_options(&BuildOptions::default());
+}
+
+/// Same as [`build`], with explicit [`BuildOptions`].
+///
+/// The profile is resolved as: `options.profile` >
+/// `R_RESOURCES_PROFILE` > cargo `PROFILE`.
+pub fn build_with_options(options: &BuildOptions) {
+    if let Err(e) = try_build_with_options(options) {
+        diag::render::eprint(&e.diagnostics());
+        std::process::exit(1);
+    }
+}
+
+/// `cargo:rerun-if-*` lines of every build. Any of them turns off
+/// cargo's default of rerunning on any change of the package, so the
+/// resources (`res_dir`) and test resources (`tests_dir`, when it
+/// exists) are always watched, new files included
+fn rerun_directives(
+    res_dir: &std::path::Path,
+    tests_dir: &std::path::Path,
+) -> Vec<String> {
+    let mut directives = vec![
+        format!(
+            "cargo:rerun-if-env-changed={}",
+            options::PROFILE_ENV_VAR
+        ),
+        format!(
+            "cargo:rerun-if-env-changed={}",
+            options::SOURCE_DATE_EPOCH_ENV_VAR
+        ),
+        format!("cargo:rerun-if-changed={}", res_dir.display()),
+    ];
+    if tests_dir.exists() {
+        directives.push(format!(
+            "cargo:rerun-if-changed={}",
+            tests_dir.display()
+        ));
+    }
+    directives
+}
+
+/// Same as [`build_with_options`], returning the error instead of
+/// exiting the build script
+#[allow(dead_code)] // Public API, may be used by consumers
+pub fn try_build_with_options(
+    options: &BuildOptions,
+) -> Result<(), BuildError> {
     use std::path::Path;
 
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
         .expect("CARGO_MANIFEST_DIR environment variable not set");
-    let res_dir = Path::new(&manifest_dir).join("res");
+    let manifest_dir = Path::new(&manifest_dir);
+    let res_dir = options.resources_dir(manifest_dir);
     let tests_dir = res_dir.join("tests");
+    for directive in rerun_directives(&res_dir, &tests_dir) {
+        println!("{directive}");
+    }
 
-    let include_tests = tests_dir.exists()
-        && (std::env::var("CARGO_CFG_TEST").is_ok()
-            || std::env::var("R_RESOURCES_INCLUDE_TESTS").is_ok());
+    let mut options = options.clone();
+    if options.workspace_res_dir.is_some() {
+        println!(
+            "cargo:rerun-if-env-changed={}",
+            input::workspace::WORKSPACE_DIR_ENV_VAR
+        );
+        // The sources stay the crate's, not the workspace root's
+        if options.source_dir.is_none() {
+            options.source_dir = Some(manifest_dir.join("src"));
+        }
+    }
 
+    // `r_tests` is `#[cfg(test)]`, so it is always generated
     let plan = BuildPlan {
         resources_dir: res_dir,
-        tests_resources_dir: include_tests.then_some(tests_dir),
-        profile: std::env::var("PROFILE")
-            .unwrap_or_else(|_| "debug".to_string()),
+        tests_resources_dir: tests_dir.exists().then_some(tests_dir),
+        profile: options.resolve_profile(),
     };
 
+    if options.warn_unused {
+        println!(
+            "cargo:rerun-if-changed={}",
+            options.unused_source_dir(&plan.resources_dir).display()
+        );
+    }
+
+    if !options.disable_raw {
+        let raw_files =
+            input::raw_assets::raw_files(&plan.resources_dir)
+                .map_err(input_error)?;
+        for file in raw_files {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+    }
+
     // Check if we should treat duplicates as errors
-    let treat_duplicates_as_errors =
+    options.treat_duplicates_as_errors |=
         std::env::var("R_RESOURCES_DUPLICATES_AS_ERRORS")
-            .map(|v| v == "1" || v == "true")
-            .unwrap_or(false);
-
-    let validation_options = analysis::ValidationOptions {
-        treat_duplicates_as_errors,
-    };
+            .is_ok_and(|v| v == "1" || v == "true");
 
-    match build_with_plan_and_options(&plan, validation_options) {
-        Ok(artifacts) => {
-            // Print warnings if any
-            for warning in &artifacts.warnings {
-                eprintln!("warning: {warning}");
-            }
-            write_generated_code(&artifacts.rust)
-                .expect("Failed to write generated code");
-        }
-        Err(e) => {
-            eprintln!("error: {e}");
-            std::process::exit(1);
+    // The code is streamed to the file as it is generated
+    let path = generated_code_path().map_err(BuildError::Export)?;
+    let mut sink = generation::FileSink::create(&path)
+        .map_err(BuildError::Export)?;
+    let (warnings, report) =
+        build_with_report(&mut sink, &plan, &options)?;
+    sink.finish().map_err(BuildError::Export)?;
+    announce_generated_code(&path);
+    // Surface warnings in cargo's output
+    for warning in &warnings {
+        println!("cargo:warning={warning}");
+    }
+    if let Some(report) = report {
+        let lines = report.lines();
+        let report_path = path.with_file_name("r_report.txt");
+        std::fs::write(&report_path, lines.join("\n") + "\n")
+            .map_err(BuildError::Export)?;
+        for line in &lines {
+            println!("cargo:warning={line}");
         }
     }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ir::Ordering;
     use std::fs;
     use tempfile::tempdir;
 
@@ -161,6 +518,33 @@ mod tests {
         fs::write(path, contents).unwrap();
     }
 
+    #[test]
+    fn builds_rerun_when_the_resources_change() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let tests_dir = res_dir.join("tests");
+        let watched = |directives: Vec<String>| {
+            directives
+                .into_iter()
+                .filter_map(|line| {
+                    line.strip_prefix("cargo:rerun-if-changed=")
+                        .map(std::path::PathBuf::from)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            watched(rerun_directives(&res_dir, &tests_dir)),
+            std::slice::from_ref(&res_dir)
+        );
+        fs::create_dir_all(&tests_dir).unwrap();
+        let directives = rerun_directives(&res_dir, &tests_dir);
+        assert!(directives.contains(&format!(
+            "cargo:rerun-if-env-changed={}",
+            options::PROFILE_ENV_VAR
+        )));
+        assert_eq!(watched(directives), [res_dir, tests_dir]);
+    }
+
     #[test]
     fn build_with_plan_runs_pipeline_and_generation() {
         let tmp = tempdir().unwrap();
@@ -194,6 +578,366 @@ mod tests {
         assert!(artifacts.rust.contains("= true"));
     }
 
+    #[test]
+    fn build_with_plan_bakes_in_profile() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="api_url" profile="staging">staging</string>
+                <string name="api_url" profile="release">prod</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "staging");
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains("pub const BUILD_PROFILE: &str = \"staging\";"));
+        assert!(artifacts.rust.contains("\"staging\""));
+        assert!(!artifacts.rust.contains("\"prod\""));
+    }
+
+    #[test]
+    fn meta_lists_the_sources_of_the_build() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><ns name="ui"><bool name="dark">true</bool></ns></resources>"#,
+        );
+        write_file(
+            &res_dir.join("strings.xml"),
+            r#"<resources><string name="title">Demo</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests/values.xml"),
+            r#"<resources><string name="fake">Fake</string></resources>"#,
+        );
+        let plan = BuildPlan::new(
+            res_dir.clone(),
+            Some(res_dir.join("tests")),
+            "release",
+        );
+        let options = BuildOptions {
+            deterministic: true,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+
+        assert!(rust.contains("pub const GENERATED_AT_UNIX: i64 = 0;"));
+        assert!(rust.contains("pub const RESOURCE_COUNT: usize = 2;"));
+        assert!(rust.contains("pub const PROFILE: &str = \"release\";"));
+        assert!(rust.contains(
+            "pub const SOURCE_FILES: &[&str] = \
+             &[\"res/strings.xml\", \"res/values.xml\"];"
+        ));
+    }
+
+    #[test]
+    fn flatten_aliases_reexport_namespaced_items() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="ui"><ns name="colors"><color name="primary">#FF0000</color></ns></ns>
+                <ns name="auth"><template name="greeting"><string name="name"/>Hi {name}</template></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let options = BuildOptions {
+            flatten_aliases: true,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+        assert!(rust.contains(
+            "pub use self::ui::colors::PRIMARY as UI_COLORS_PRIMARY;"
+        ));
+        assert!(rust
+            .contains("pub use self::auth::greeting as auth_greeting;"));
+        let default = build_with_plan(&plan).unwrap().rust;
+        assert!(!default.contains("UI_COLORS_PRIMARY"));
+
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources><color name="ui_colors_primary">#00FF00</color></resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, &options)
+        else {
+            panic!("the alias collides with the root color");
+        };
+        let error = &errors[0].message;
+        assert!(
+            error.contains("all generate `UI_COLORS_PRIMARY`"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn copied_aliases_are_constants_of_their_own() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="http">
+                    <number name="timeout_ms" type="u32" min="1">500</number>
+                    <number name="rate">0.12345678901234567890</number>
+                    <bool name="legacy" deprecated="use http/v2">true</bool>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let options = BuildOptions {
+            flatten_aliases: true,
+            reference_style: ReferenceStyle::Copy,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+        for line in [
+            "    /// Copy of [`http::TIMEOUT_MS`]\n    pub const HTTP_TIMEOUT_MS: u32 = 500;",
+            "pub use self::http::TIMEOUT_MS_MIN as HTTP_TIMEOUT_MS_MIN;",
+            "pub use self::http::RATE as HTTP_RATE;",
+            "    #[deprecated(note = \"use http/v2\")]\n    pub const HTTP_LEGACY: bool = true;",
+        ] {
+            assert!(rust.contains(line), "missing `{line}`");
+        }
+    }
+
+    #[test]
+    fn struct_namespaces_generate_a_config_constant() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="http" struct="true">
+                    <number name="timeout_ms" type="u32">500</number>
+                    <string name="user_agent">demo/1.0</string>
+                    <bool name="legacy" deprecated="use http/v2">true</bool>
+                    <number name="rate">0.12345678901234567890</number>
+                    <ns name="tls"><bool name="strict">true</bool></ns>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = BuildOptions::default();
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+        for line in [
+            "    pub struct HttpConfig {\n        /// [`http::LEGACY`]\n        pub legacy: bool,",
+            "        pub timeout_ms: u32,",
+            "        pub user_agent: &'static str,\n    }",
+            "    #[allow(deprecated)]\n    pub const HTTP: HttpConfig = HttpConfig {\n        legacy: http::LEGACY,",
+            "        timeout_ms: http::TIMEOUT_MS,",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+        assert!(!artifacts.rust.contains("rate: "));
+        assert!(!artifacts.rust.contains("TlsConfig"));
+        for name in ["'http/rate'", "'http/tls'"] {
+            assert!(
+                artifacts.warnings.iter().any(|w| w.contains(name)),
+                "no warning about {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn source_ordering_keeps_the_declaration_order() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("a.xml"),
+            r#"<resources>
+                <string name="zebra">Z</string>
+                <number name="count">3</number>
+                <ns name="ui">
+                    <string name="title">T</string>
+                    <string name="body">B</string>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("b.xml"),
+            r#"<resources><string name="apple">A</string></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let build = |ordering| {
+            let options = BuildOptions {
+                ordering,
+                ..BuildOptions::default()
+            };
+            build_with_plan_and_options(&plan, &options)
+                .expect("build succeeds")
+                .rust
+        };
+        // Offsets of `items` in `rust`, which must be increasing
+        let in_order = |rust: &str, items: &[&str]| {
+            let offsets: Vec<usize> = items
+                .iter()
+                .map(|item| rust.find(item).expect(item))
+                .collect();
+            offsets.windows(2).all(|pair| pair[0] < pair[1])
+        };
+
+        let sorted = build(Ordering::Alphabetical);
+        assert!(in_order(
+            &sorted,
+            &[
+                "pub const BODY: &str",
+                "pub const TITLE: &str",
+                "pub const APPLE: &str",
+                "pub const COUNT: i64",
+                "pub const ZEBRA: &str",
+                "    pub use super::r::APPLE;\n    pub use super::r::ZEBRA;\n",
+            ]
+        ));
+        let declared = build(Ordering::Source);
+        assert!(in_order(
+            &declared,
+            &[
+                "pub const TITLE: &str",
+                "pub const BODY: &str",
+                "pub const ZEBRA: &str",
+                "pub const COUNT: i64",
+                "pub const APPLE: &str",
+                "    pub use super::r::ZEBRA;\n    pub use super::r::APPLE;\n",
+            ]
+        ));
+        assert_eq!(build(Ordering::Source), declared);
+    }
+
+    #[test]
+    fn build_with_profiles_keeps_escaped_content() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <string name="hint" profile="debug">a &lt; b</string>
+    <string name="hint" profile="default">fallback</string>
+    <string name="markup"><![CDATA[<i>"quoted"</i>]]></string>
+    <string name="party">🎉</string>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains(r#"pub const HINT: &str = "a < b";"#));
+        assert!(artifacts.rust.contains(
+            r##"pub const MARKUP: &str = r#"<i>"quoted"</i>"#;"##
+        ));
+        assert!(artifacts
+            .rust
+            .contains(r#"pub const PARTY: &str = "🎉";"#));
+    }
+
+    #[test]
+    fn build_with_locales_generates_localized_api() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="greeting">Hello</string>
+                <string name="farewell">Bye</string>
+                <template name="welcome">
+                    <string name="name"/>
+                    Welcome {name}
+                </template>
+                <ns name="auth">
+                    <string name="title">Login</string>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("values-fr.xml"),
+            r#"<resources>
+                <string name="greeting">Bonjour</string>
+                <string name="extra">Extra</string>
+                <template name="welcome">
+                    <string name="name"/>
+                    Bienvenue {name}
+                </template>
+                <ns name="auth">
+                    <string name="title">Connexion</string>
+                </ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+        let rust = &artifacts.rust;
+
+        assert!(rust.contains("pub enum Locale {"));
+        assert!(rust.contains("Self::Fr => \"fr\","));
+        assert!(rust.contains("pub mod fr {"));
+        assert!(rust.contains("pub const GREETING: &str = \"Bonjour\";"));
+        assert!(rust.contains(
+            "pub fn greeting(locale: super::Locale) -> &'static str {"
+        ));
+        assert!(rust.contains(
+            "super::Locale::Fr => super::locale::fr::GREETING,"
+        ));
+        assert!(rust.contains(
+            "pub fn welcome(locale: super::Locale, name: &str) -> String {"
+        ));
+        assert!(rust.contains(
+            "super::Locale::Fr => super::locale::fr::welcome(name),"
+        ));
+        assert!(rust.contains(
+            "super::super::Locale::Fr => super::super::locale::fr::auth::TITLE,"
+        ));
+        // Missing translation falls back to the default
+        assert!(rust.contains("    super::FAREWELL\n"));
+
+        assert!(artifacts.warnings.iter().any(|w| w.contains("'extra'")
+            && w.contains("no default definition")));
+        assert!(artifacts.warnings.iter().any(|w| {
+            w.contains("Locale 'fr' is missing 1 translation(s): farewell")
+        }));
+    }
+
+    #[test]
+    fn streamed_code_matches_the_string_output() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="title">Hello</string>
+                <color name="accent">#FF5722</color>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = BuildOptions::default();
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+
+        let path = tmp.path().join("out/r_generated.rs");
+        let mut sink = generation::FileSink::create(&path).unwrap();
+        build_with_plan_into(&mut sink, &plan, &options)
+            .expect("build succeeds");
+        sink.finish().unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), artifacts.rust);
+    }
+
     #[test]
     fn write_generated_code_creates_file() {
         let tmp = tempdir().unwrap();
@@ -269,10 +1013,11 @@ pub mod r {
             r#"<resources><string name="title">Second</string></resources>"#,
         );
         let plan = BuildPlan::new(res_dir, None, "debug");
-        let options = analysis::ValidationOptions {
+        let options = BuildOptions {
             treat_duplicates_as_errors: true,
+            ..Default::default()
         };
-        let result = build_with_plan_and_options(&plan, options);
+        let result = build_with_plan_and_options(&plan, &options);
 
         // Should fail with error
         assert!(result.is_err());
@@ -285,4 +1030,1527 @@ pub mod r {
             panic!("Expected Analysis error");
         }
     }
+
+    #[test]
+    fn build_with_fluent_export_writes_ftl_files() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="auth">
+                    <string name="title">Sign in</string>
+                </ns>
+                <template name="welcome">
+                    <string name="name"/>
+                    Hello {name}!
+                </template>
+                <number name="max_retries">3</number>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("values-fr.xml"),
+            r#"<resources>
+                <ns name="auth">
+                    <string name="title">Connexion</string>
+                </ns>
+            </resources>"#,
+        );
+        let export_dir = tmp.path().join("ftl");
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options =
+            BuildOptions::new().with_fluent_export(&export_dir);
+        build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+
+        let default =
+            fs::read_to_string(export_dir.join("default.ftl")).unwrap();
+        assert!(default.contains("auth-title = Sign in\n"));
+        assert!(default.contains("welcome = Hello { $name }!\n"));
+        assert!(!default.contains("max_retries"));
+
+        let fr = fs::read_to_string(export_dir.join("fr.ftl")).unwrap();
+        assert!(fr.contains("auth-title = Connexion\n"));
+        assert!(!fr.contains("welcome"));
+    }
+
+    #[test]
+    fn build_with_pseudo_profile_pseudolocalizes_strings() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="save">Save</string>
+                <template name="hello">
+                    <string name="name"/>
+                    Hi {name}
+                </template>
+                <color name="accent">#FF0000</color>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "pseudo");
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains(r#"pub const SAVE: &str = "[Šåṽé~~]";"#));
+        assert!(artifacts.rust.contains(r#""[Ĥî {}~]""#));
+        assert!(artifacts.rust.contains("\"#FF0000\""));
+    }
+
+    #[test]
+    fn lookup_tables_can_be_disabled() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="title">Hello</string></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+        assert!(artifacts.rust.contains("pub mod lookup {"));
+        assert!(artifacts
+            .rust
+            .contains("(\"title\", super::TITLE),"));
+
+        let options = BuildOptions {
+            disable_lookup: true,
+            ..Default::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+        assert!(!artifacts.rust.contains("pub mod lookup"));
+    }
+
+    #[test]
+    fn provenance_docs_can_be_disabled() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("config.xml"),
+            r#"<resources>
+                <ns name="ui"><color name="primary">#FF0000</color></ns>
+                <template name="hello"><string name="name"/>Hi {name}</template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        // Hot-reload accessors need the absolute path of the file
+        let options = BuildOptions {
+            disable_hot_reload: true,
+            ..BuildOptions::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+        assert!(artifacts.rust.contains(
+            "/// Defined in `res/config.xml` (namespace `ui`)\n        pub const PRIMARY"
+        ));
+        assert!(artifacts
+            .rust
+            .contains("/// Defined in `res/config.xml`\n    pub fn hello"));
+        assert!(!artifacts
+            .rust
+            .contains(&tmp.path().display().to_string()));
+
+        let options = BuildOptions {
+            disable_provenance: true,
+            ..Default::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+        assert!(!artifacts.rust.contains("Defined in"));
+    }
+
+    #[test]
+    fn deprecated_resources_get_the_attribute() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="old_api_url" deprecated="Use &quot;api_base&quot; instead">http://old</string>
+                <number name="old_limit" deprecated="Use limit">3</number>
+                <template name="greet" deprecated="Use welcome">
+                    <string name="name"/>
+                    Hi {name}
+                </template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        // Same items, without the attributes of the hot-reload accessors
+        let options = BuildOptions {
+            disable_hot_reload: true,
+            ..BuildOptions::default()
+        };
+        let artifacts = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds");
+
+        assert!(artifacts.rust.contains(
+            "    #[deprecated(note = \"Use \\\"api_base\\\" instead\")]\n    pub const OLD_API_URL"
+        ));
+        assert!(artifacts.rust.contains(
+            "    #[deprecated(note = \"Use limit\")]\n    pub const OLD_LIMIT"
+        ));
+        assert!(artifacts.rust.contains(
+            "    #[deprecated(note = \"Use welcome\")]\n    pub fn greet"
+        ));
+    }
+
+    #[test]
+    fn references_to_deprecated_resources_warn() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+  <string name="old_host" deprecated="use host">old.example</string>
+  <string name="url">https://@string/old_host/</string>
+  <string name="old_url" deprecated="use url">@string/old_host</string>
+  <number name="old_limit" deprecated="use limit">3</number>
+  <computed name="budget">@number/old_limit * 2</computed>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        let warnings: Vec<&String> = artifacts
+            .warnings
+            .iter()
+            .filter(|warning| warning.contains("deprecated"))
+            .collect();
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains(
+            "values.xml:6: 'budget' refers to the deprecated 'old_limit': use limit"
+        ));
+        assert!(warnings[1].contains(
+            "values.xml:3: 'url' refers to the deprecated 'old_host': use host"
+        ));
+    }
+
+    #[test]
+    fn keywords_generate_valid_identifiers() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <ns name="type">
+                    <string name="loop">strict keyword</string>
+                </ns>
+                <ns name="self">
+                    <string name="mod">non-raw keyword</string>
+                </ns>
+                <ns name="yield">
+                    <number name="try">1</number>
+                </ns>
+                <template name="match">
+                    <string name="type"/>
+                    Matched {type}
+                </template>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("values-in.xml"),
+            r#"<resources><ns name="type"><string name="loop">bahasa</string></ns></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        assert!(build_with_plan(&plan).is_err(), "`self` is reserved");
+        let options = BuildOptions {
+            allow_reserved_names: true,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+
+        assert!(rust.contains("pub mod r#type {"));
+        assert!(rust.contains("pub const LOOP: &str"));
+        assert!(rust.contains("pub mod self_ {"));
+        assert!(rust.contains("pub const MOD: &str"));
+        assert!(rust.contains("pub mod r#yield {"));
+        assert!(rust.contains("pub fn r#match(r#type: &str) -> String"));
+        assert!(rust.contains("pub mod r#in {"));
+        assert!(rust.contains("pub fn r#loop(locale: super::super::Locale)"));
+        assert!(rust.contains("super::super::locale::r#in::r#type::LOOP"));
+    }
+
+    #[test]
+    fn digit_leading_names_get_an_underscore() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="2fa_hint">Enter the code</string>
+                <number name="404">404</number>
+                <ns name="3d">
+                    <string name="title">3D</string>
+                </ns>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("values-fr.xml"),
+            r#"<resources><string name="2fa_hint">Code</string></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let output = build_with_plan(&plan).expect("build succeeds");
+        let rust = output.rust;
+
+        assert!(rust.contains("pub const _2FA_HINT: &str"));
+        assert!(rust.contains("pub const _404: i64"));
+        assert!(rust.contains("pub mod _3d {"));
+        assert!(rust.contains("(\"3d/title\", super::_3d::TITLE)"));
+        assert!(rust.contains("pub fn _2fa_hint(locale: super::Locale)"));
+        assert!(output
+            .warnings
+            .iter()
+            .any(|w| w.contains("Names starting with a digit")));
+    }
+
+    #[test]
+    fn non_ascii_strings_are_emitted_verbatim() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="cafe">Café ☕</string>
+                <string name="welcome_ja">ようこそ</string>
+                <string name="family">👩‍👩‍👧 "quoted" \o/</string>
+                <template name="greet">
+                    <string name="name"/>Bonjour {name} 🎉</template>
+                <string name="crème">Crème brûlée</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(r#"pub const CAFE: &str = "Café ☕";"#));
+        assert!(rust.contains(r#"pub const WELCOME_JA: &str = "ようこそ";"#));
+        assert!(rust.contains(
+            r##"pub const FAMILY: &str = r#"👩‍👩‍👧 "quoted" \o/"#;"##
+        ));
+        assert!(rust.contains(r#"format!("Bonjour {} 🎉", name)"#));
+        assert!(rust.contains(r#"pub const CRÈME: &str = "Crème brûlée";"#));
+        assert!(rust.contains(r#"("crème", super::CRÈME)"#));
+        assert!(!rust.contains("\\u{"), "{rust}");
+    }
+
+    #[test]
+    fn preserved_strings_emit_escaped_newlines() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            "<resources><string name=\"banner\" xml:space=\"preserve\">\n  *\n *** \n</string></resources>",
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(
+            r#"pub const BANNER: &str = "\n  *\n *** \n";"#
+        ));
+    }
+
+    #[test]
+    fn cdata_values_are_emitted_as_literals() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="json"><![CDATA[{"a": [1, 2]}]]></string>
+                <template name="heading">
+                    <string name="title"/>
+                    <![CDATA[<h1>{title}</h1>]]>
+                </template>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust
+            .contains(r##"pub const JSON: &str = r#"{"a": [1, 2]}"#;"##));
+        assert!(rust.contains(r#"format!("<h1>{}</h1>", title)"#));
+    }
+
+    #[test]
+    fn empty_strings_generate_empty_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="optional_suffix"></string>
+                <ns name="ui"><string name="placeholder"/></ns>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(r#"pub const OPTIONAL_SUFFIX: &str = "";"#));
+        assert!(rust.contains(r#"pub const PLACEHOLDER: &str = "";"#));
+    }
+
+    #[test]
+    fn prefixed_and_separated_numbers_keep_their_form() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <number name="max_file_size">10_485_760</number>
+                <number name="flags" type="u32">0xDEADBEEF</number>
+                <number name="mode" type="u16">0o755</number>
+                <number name="mask">0b1111_0000</number>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains("pub const MAX_FILE_SIZE: i64 = 10_485_760;"));
+        assert!(rust.contains("pub const FLAGS: u32 = 0xDEADBEEF;"));
+        assert!(rust.contains("pub const MODE: u16 = 0o755;"));
+        assert!(rust.contains("pub const MASK: i64 = 0b1111_0000;"));
+        assert!(rust.contains("(\"max_file_size\", super::MAX_FILE_SIZE),"));
+        assert!(rust.contains("(\"flags\", super::FLAGS as i64),"));
+    }
+
+    #[test]
+    fn special_float_values_use_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <number name="upper">inf</number>
+                <number name="lower" type="f32">-inf</number>
+                <number name="unknown">NaN</number>
+                <number name="signed">+3</number>
+                <number name="zero">-0.0</number>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains("pub const UPPER: f64 = f64::INFINITY;"));
+        assert!(rust.contains("pub const LOWER: f32 = f32::NEG_INFINITY;"));
+        assert!(rust.contains("pub const UNKNOWN: f64 = f64::NAN;"));
+        assert!(rust.contains("pub const SIGNED: i64 = 3;"));
+        assert!(rust.contains("pub const ZERO: f64 = -0.0;"));
+    }
+
+    #[test]
+    fn out_of_range_numbers_fail_with_location() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("limits.xml"),
+            r#"<resources><ns name="net"><number name="port" type="u16">-1</number></ns></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        assert_eq!(
+            errors[0].to_diagnostic().to_string(),
+            format!(
+                "error: {}:1: Invalid value for 'net/port': '-1' does not fit in u16",
+                res_dir.join("limits.xml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_colors_fail_with_location() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("colors.xml"),
+            "<resources>\n  <color name=\"ok\">#f00</color>\n  <color name=\"accent\">#GG5722</color>\n</resources>",
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        assert_eq!(
+            errors[0].to_diagnostic().to_string(),
+            format!(
+                "error: {}:3: Invalid value for 'accent': invalid color '#GG5722': 'G' is not a hex digit",
+                res_dir.join("colors.xml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn shorthand_colors_are_expanded() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><color name="accent">#F0a</color></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let output = build_with_plan(&plan).unwrap();
+        assert!(output
+            .rust
+            .contains(r##"pub const ACCENT: &str = "#FF00aa";"##));
+    }
+
+    #[test]
+    fn values_with_quotes_or_backslashes_use_raw_literals() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r##"<resources>
+                <string name="path">C:\Users\app</string>
+                <string name="tagged">"#hash"</string>
+                <string name="lines" preserve="true">a\
+"b"</string>
+                <template name="greet"><string name="who"/>Hello "{who}" \o/</template>
+            </resources>"##,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(r#"pub const PATH: &str = r"C:\Users\app";"#));
+        assert!(rust
+            .contains(r###"pub const TAGGED: &str = r##""#hash""##;"###));
+        // Newlines keep the escaped form
+        assert!(rust.contains(r#"pub const LINES: &str = "a\\\n\"b\"";"#));
+        assert!(rust.contains(r##"format!(r#"Hello "{}" \o/"#, who)"##));
+    }
+
+    #[test]
+    fn docs_are_emitted_as_doc_comments() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <ns name="auth">
+        <doc>Login and signup texts.</doc>
+        <doc>Title of the login screen.</doc>
+        <string name="title">Login</string>
+    </ns>
+    <doc>Dangling.</doc>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let output = build_with_plan(&plan).expect("build succeeds");
+        assert!(output.rust.contains(
+            "    pub mod auth {\n        //! Login and signup texts.\n"
+        ));
+        assert!(output.rust.contains(
+            "        /// Title of the login screen.\n        ///\n        /// Defined in `res/values.xml` (namespace `auth`)\n        pub const TITLE"
+        ));
+        let warning = format!(
+            "{}:7: `<doc>` is not followed by a resource or namespace and is ignored",
+            res_dir.join("values.xml").display()
+        );
+        assert!(output.warnings.contains(&warning));
+    }
+
+    #[test]
+    fn root_docs_of_every_file_document_r() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("a.xml"),
+            r#"<resources>
+    <doc module="true">Texts of the demo app.</doc>
+    <string name="title">Demo</string>
+</resources>"#,
+        );
+        write_file(
+            &res_dir.join("payments.xml"),
+            r#"<resources ns-from-file="true">
+    <!-- //! Checkout screens. -->
+    <string name="pay">Pay</string>
+</resources>"#,
+        );
+        write_file(
+            &res_dir.join("z.xml"),
+            r#"<resources>
+    <!-- //! Settings. -->
+    <bool name="dark">true</bool>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(
+            "pub mod r {\n    //! Texts of the demo app.\n    //!\n    //! Settings.\n"
+        ));
+        assert!(rust.contains(
+            "    pub mod payments {\n        //! Checkout screens.\n"
+        ));
+    }
+
+    #[test]
+    fn multi_line_templates_emit_line_breaks() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <template name="inbox">
+        <string name="name"/>
+        <number name="count" type="u32"/>
+        Hello {name},
+        You have {count} new messages.
+    </template>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust.contains(
+            r#"format!("Hello {},\nYou have {} new messages.", name, count)"#
+        ));
+    }
+
+    #[test]
+    fn references_use_the_overriding_definition() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("a_values.xml"),
+            r#"<resources>
+    <string name="welcome">Welcome to @string/app/name!</string>
+    <ns name="app"><string name="name">Demo</string></ns>
+</resources>"#,
+        );
+        write_file(
+            &res_dir.join("b_overrides.xml"),
+            r#"<resources>
+    <ns name="app"><string name="name" profile="release">Demo Pro</string></ns>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "release");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+
+        assert!(rust
+            .contains(r#"pub const WELCOME: &str = "Welcome to Demo Pro!";"#));
+    }
+
+    #[test]
+    fn unknown_references_fail() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="welcome">Hi @string/app_name</string></resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        assert_eq!(
+            errors[0].to_diagnostic().to_string(),
+            format!(
+                "error: {}:1: Invalid value for 'welcome': `@string/app_name` does not name a string",
+                res_dir.join("values.xml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn typed_modules_reexport_r_items() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r##"<resources>
+    <string name="old" deprecated="Use new">x</string>
+    <ns name="ui"><color name="accent">#FF5722</color></ns>
+    <number name="port" type="u16">8080</number>
+    <template name="greet"><string name="who"/>Hi {who}</template>
+</resources>"##,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+        assert!(rust.contains(
+            "pub mod string {\n    #[allow(deprecated)]\n    pub use super::r::OLD;\n}"
+        ));
+        assert!(rust.contains(
+            "pub mod color {\n    pub mod ui {\n        pub use super::super::r::ui::ACCENT;"
+        ));
+        assert!(rust.contains("pub mod int {\n    pub use super::r::PORT;"));
+        assert!(rust.contains("pub mod template {\n    pub use super::r::greet;"));
+
+        let options = BuildOptions {
+            disable_typed_modules: true,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+        assert!(!rust.contains("pub mod string"));
+    }
+
+    #[test]
+    fn test_resources_generate_cfg_test_module() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app_name">Demo</string>
+                <string name="api_url">https://api.example.com</string>
+            </resources>"#,
+        );
+        write_file(
+            &res_dir.join("tests/values.xml"),
+            r#"<resources>
+                <string name="api_url">http://localhost</string>
+                <string name="banner">@string/app_name tests</string>
+            </resources>"#,
+        );
+        let plan = BuildPlan::new(
+            res_dir.clone(),
+            Some(res_dir.join("tests")),
+            "debug",
+        );
+        let artifacts =
+            build_with_plan(&plan).expect("build succeeds");
+        let (main, tests) = artifacts
+            .rust
+            .split_once("#[cfg(test)]\npub mod r_tests {\n")
+            .expect("r_tests is generated");
+
+        assert!(main.contains(
+            "pub const API_URL: &str = \"https://api.example.com\";"
+        ));
+        assert!(!main.contains("localhost"));
+        assert!(tests.starts_with("    pub use super::r::*;\n"));
+        assert!(tests.contains(
+            "pub const API_URL: &str = \"http://localhost\";"
+        ));
+        assert!(tests
+            .contains("pub const BANNER: &str = \"Demo tests\";"));
+        // Shadowing a default resource is not a duplicate
+        assert!(artifacts.warnings.is_empty());
+    }
+
+    #[test]
+    fn out_of_range_coordinates_fail_the_build() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <latlng name="hq" lat="48.8566" lng="2.3522"/>
+    <latlng name="nowhere" lat="91" lng="0"/>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("'nowhere'"));
+        assert!(errors[0].to_diagnostic().to_string().contains("values.xml:3"));
+        assert!(errors[0]
+            .message
+            .contains("latitude 91 is out of range (-90 to 90)"));
+    }
+
+    #[test]
+    fn versions_generate_version_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <semver name="app_version">1.10.0-rc.1</semver>
+    <semver name="min_supported">1.2.3</semver>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const APP_VERSION: r_resources::Version = r_resources::Version::with_pre(1, 10, 0, \"rc.1\");",
+            "pub const APP_VERSION_STR: &str = \"1.10.0-rc.1\";",
+            "pub const MIN_SUPPORTED: r_resources::Version = r_resources::Version::new(1, 2, 3);",
+            "pub use super::r::APP_VERSION;",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <semver name="app_version">1.10</semver>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'app_version': '1.10' is not a semantic version: expected major.minor.patch"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:2"));
+    }
+
+    #[test]
+    fn uuids_generate_u128_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <uuid name="app_namespace">6BA7B810-9DAD-11D1-80B4-00C04FD430C8</uuid>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const APP_NAMESPACE: u128 = 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8;",
+            "pub const APP_NAMESPACE_BYTES: [u8; 16] = [0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];",
+            "pub const APP_NAMESPACE_STR: &str = \"6ba7b810-9dad-11d1-80b4-00c04fd430c8\";",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+        assert!(!artifacts.rust.contains("pub mod uuid"));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <uuid name="app_namespace">6ba7b8109-dad-11d1-80b4-00c04fd430c8</uuid>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'app_namespace': '6ba7b8109-dad-11d1-80b4-00c04fd430c8' is not a UUID: expected 8-4-4-4-12 hex digits"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn datetimes_generate_utc_seconds_and_dates() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <datetime name="promo_ends">2026-01-01T01:59:59+02:00</datetime>
+    <date name="launch">2024-02-29</date>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const PROMO_ENDS_UNIX: i64 = 1767225599;",
+            "pub const PROMO_ENDS_STR: &str = \"2026-01-01T01:59:59+02:00\";",
+            "pub const LAUNCH: r_resources::Date = r_resources::Date::new(2024, 2, 29);",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <date name="launch">2025-02-29</date>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'launch': '2025-02-29' is not a date: day 29 is out of range for 2025-02 (01 to 28)"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn money_generates_minor_units() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <money name="pro_plan" currency="USD" scale="2">9.99</money>
+    <money name="yen_plan" currency="JPY" scale="0">1500</money>
+    <money name="refund" currency="EUR" value="-0.5"/>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const PRO_PLAN: r_resources::Money = r_resources::Money::new(999, 2, \"USD\");",
+            "pub const YEN_PLAN: r_resources::Money = r_resources::Money::new(1500, 0, \"JPY\");",
+            "pub const REFUND: r_resources::Money = r_resources::Money::new(-50, 2, \"EUR\");",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <money name="pro_plan" currency="USD" scale="2">9.999</money>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'pro_plan': '9.999' has more decimal places than the scale of 2"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn chars_generate_char_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <char name="icon_search">\u{F002}</char>
+    <char name="icon_home" value="U+F015"/>
+    <char name="thumbs_up" grapheme="true">👍🏽</char>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const ICON_SEARCH: char = '\\u{F002}';",
+            "pub const ICON_HOME: char = '\\u{F015}';",
+            "pub const THUMBS_UP: &str = \"\\u{1F44D}\\u{1F3FD}\";",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <char name="icon_search">\u{D83D}</char>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'icon_search': U+D83D is a surrogate, not a Unicode scalar value"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn bytes_are_decoded_at_build_time() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <bytes name="signing_pubkey" encoding="base64">
+        MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7
+        lshQhqowtrbLDFw4rXAxZuE=
+    </bytes>
+    <bytes name="magic" encoding="hex" sha256="0f4636c78f65d3639ece5a064b5ae753e3408614a14fb18ab4d7540d2c248543">89504E47</bytes>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const SIGNING_PUBKEY: &[u8] = &[\n",
+            "0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,\n",
+            "pub const MAGIC: &[u8] = &[0x89, 0x50, 0x4e, 0x47];",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <bytes name="signing_pubkey" encoding="base64">
+        MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7
+        lshQhqowtrbLDFw4rXAxZu!=
+    </bytes>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'signing_pubkey': '!' at offset 67 is not a base64 character"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn integers_widen_per_resource_and_globally() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <int name="cache_size" type="u32" widen="usize,u64">512</int>
+    <int name="offset" type="i32">-4</int>
+    <int name="total" type="u64">7</int>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let rust = build_with_plan(&plan).unwrap().rust;
+        for line in [
+            "pub const CACHE_SIZE_USIZE: usize = 512;",
+            "pub const CACHE_SIZE_U64: u64 = 512;",
+        ] {
+            assert!(rust.contains(line), "missing `{line}`");
+        }
+        assert!(!rust.contains("OFFSET_"));
+
+        // Global widenings only where no value can be lost
+        let options = BuildOptions::default()
+            .with_widening(["isize", "usize", "i64"]);
+        let rust = build_with_plan_and_options(&plan, &options)
+            .unwrap()
+            .rust;
+        for line in [
+            "pub const CACHE_SIZE_USIZE: usize = 512;",
+            "pub const CACHE_SIZE_I64: i64 = 512;",
+            "pub const OFFSET_ISIZE: isize = -4;",
+            "pub const OFFSET_I64: i64 = -4;",
+        ] {
+            assert!(rust.contains(line), "missing `{line}`");
+        }
+        assert!(!rust.contains("CACHE_SIZE_ISIZE"));
+        assert!(!rust.contains("OFFSET_USIZE"));
+        assert!(!rust.contains("TOTAL_"));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <int name="total" type="u64" widen="usize">7</int>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'total': u64 does not widen losslessly to usize on every target"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn malformed_references_warn_or_fail_in_strict_mode() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <string name="app_name">Demo</string>
+    <string name="title">@strig/app_name</string>
+    <string name="handle">@@rresources</string>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(artifacts.rust.contains(
+            "pub const TITLE: &str = \"@strig/app_name\";"
+        ));
+        let message = "Suspicious value for 'title': `@strig/app_name` is not a reference; did you mean `@string/app_name`? (write `@@` for a literal `@`)";
+        assert!(
+            artifacts.warnings.iter().any(|w| w.contains(message)),
+            "{:?}",
+            artifacts.warnings
+        );
+        assert!(!artifacts
+            .warnings
+            .iter()
+            .any(|w| w.contains("handle")));
+
+        let options = BuildOptions {
+            strict_references: true,
+            ..BuildOptions::default()
+        };
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, &options)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(errors[0].message, message);
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn reports_describe_the_generated_code() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <string name="app_name">Demo</string>
+    <string name="url" profile="release">https://example.com</string>
+    <string name="url" profile="debug">http://localhost</string>
+    <string name="title">Welcome to @string/app_name</string>
+    <ns name="ui">
+        <color name="accent">#336699</color>
+        <int name="columns">3</int>
+    </ns>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = BuildOptions {
+            report: true,
+            ..BuildOptions::default()
+        };
+        let mut code = String::new();
+        let (_, report) =
+            build_with_report(&mut code, &plan, &options).unwrap();
+        let report = report.unwrap();
+        let lines = report.lines();
+        assert_eq!(
+            lines[1],
+            "resources: 5 (string: 3, color: 1, int: 1)"
+        );
+        assert_eq!(lines[2], "namespaces: r: 3, r::ui: 2");
+        // The debug URL and the resolved title, 4 + 16 + 15 bytes
+        assert_eq!(lines[3], "string data: 35 bytes");
+        assert!(lines[4].starts_with(
+            "largest: r::URL (16 bytes), r::TITLE (15 bytes)"
+        ));
+        let constants = code
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| {
+                line.starts_with("pub static ")
+                    || (line.starts_with("pub const ")
+                        && !line.starts_with("pub const fn "))
+            })
+            .count();
+        assert_eq!(report.constants, constants);
+
+        let mut code = String::new();
+        let options = BuildOptions::default();
+        let (_, report) =
+            build_with_report(&mut code, &plan, &options).unwrap();
+        assert!(report.is_none());
+    }
+
+    #[test]
+    fn pointer_sized_integers_fit_32_bits_unless_configured() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <int name="page_size" type="usize">4096</int>
+    <int name="arena_len" type="usize">5_000_000_000</int>
+    <template name="chunk"><number name="index" type="usize"/>Chunk {index}</template>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'arena_len': '5_000_000_000' does not fit in usize on 32-bit targets; set `BuildOptions::target_pointer_width` if the crate only builds for wider targets"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+
+        let options =
+            BuildOptions::default().with_target_pointer_width(64);
+        let rust = build_with_plan_and_options(&plan, &options)
+            .unwrap()
+            .rust;
+        for line in [
+            "pub const PAGE_SIZE: usize = 4096;",
+            "pub const ARENA_LEN: usize = 5_000_000_000;",
+            "pub fn chunk(index: usize) -> String",
+        ] {
+            assert!(rust.contains(line), "missing `{line}`");
+        }
+
+        let options =
+            BuildOptions::default().with_target_pointer_width(48);
+        let Err(BuildError::Analysis(errors)) =
+            build_with_plan_and_options(&plan, &options)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "target_pointer_width: 48 is not 16, 32 or 64 bits"
+        );
+    }
+
+    #[test]
+    fn regexes_generate_pattern_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <regex name="username">^[a-z0-9_]{3,16}$</regex>
+    <regex name="tag"> &lt;\w+&gt; </regex>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const USERNAME_PATTERN: &str = \"^[a-z0-9_]{3,16}$\";",
+            "pub const TAG_PATTERN: &str = r\" <\\w+> \";",
+            "pub use super::r::USERNAME_PATTERN;",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+        assert_eq!(
+            artifacts.rust.contains("pub static USERNAME: "),
+            cfg!(feature = "regex")
+        );
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <regex name="username">^[a-z0-9_{3,16}$</regex>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'username': invalid regex `^[a-z0-9_{3,16}$`: unclosed character class"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn regex_crate_syntax_is_left_to_the_regex_crate() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <regex name="word">(?i)\bada\b</regex>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).expect("build succeeds");
+
+        assert!(artifacts
+            .rust
+            .contains(r#"pub const WORD_PATTERN: &str = r"(?i)\bada\b";"#));
+        let warning = artifacts
+            .warnings
+            .iter()
+            .find(|warning| warning.contains("'word'"));
+        // Compiled by the `regex` crate with the feature
+        assert_eq!(warning.is_some(), !cfg!(feature = "regex"));
+        if let Some(warning) = warning {
+            assert!(warning.contains(
+                "values.xml:2: Regex 'word' is not checked at build time: inline flags and named groups are not supported"
+            ));
+        }
+    }
+
+    #[test]
+    fn addresses_generate_core_net_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <ipaddr name="dns_primary">1.1.1.1</ipaddr>
+    <socketaddr name="metrics_bind">0.0.0.0:9090</socketaddr>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        for line in [
+            "pub const DNS_PRIMARY: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::new(1, 1, 1, 1));",
+            "pub const METRICS_BIND: std::net::SocketAddr = std::net::SocketAddr::V4(std::net::SocketAddrV4::new(std::net::Ipv4Addr::new(0, 0, 0, 0), 9090));",
+            "pub mod ipaddr {",
+            "pub mod socketaddr {",
+        ] {
+            assert!(artifacts.rust.contains(line), "missing `{line}`");
+        }
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+
+    <socketaddr name="metrics_bind">0.0.0.0:90900</socketaddr>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'metrics_bind': port 90900 is out of range (0 to 65535)"
+        );
+        assert!(errors[0]
+            .to_diagnostic()
+            .to_string()
+            .contains("values.xml:3"));
+    }
+
+    #[test]
+    fn byte_sizes_generate_u64_constants() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <doc>Largest accepted upload</doc>
+    <bytesize name="max_upload" assert="le:1_000_000_000">25MiB</bytesize>
+    <ns name="cache">
+        <bytesize name="size">1.5GB</bytesize>
+    </ns>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+
+        assert!(artifacts.rust.contains(
+            "    /// Largest accepted upload\n    \
+             ///\n    \
+             /// `25MiB` in bytes\n"
+        ));
+        assert!(artifacts
+            .rust
+            .contains("pub const MAX_UPLOAD: u64 = 26_214_400;"));
+        assert!(artifacts
+            .rust
+            .contains("pub const SIZE: u64 = 1_500_000_000;"));
+        assert!(artifacts.rust.contains("pub use super::r::MAX_UPLOAD;"));
+
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <bytesize name="block">1.5B</bytesize>
+</resources>"#,
+        );
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected an analysis error");
+        };
+        assert_eq!(
+            errors[0].message,
+            "Invalid value for 'block': '1.5B' is not a whole number of bytes"
+        );
+    }
+
+    #[test]
+    fn warn_unused_reports_resources_missing_from_sources() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+    <string name="title">Title</string>
+    <string name="legacy">Old</string>
+    <string name="dynamic" keep="true">Looked up</string>
+</resources>"#,
+        );
+        write_file(
+            &tmp.path().join("src/main.rs"),
+            "fn main() { println!(\"{}\", r::TITLE); }",
+        );
+
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = BuildOptions {
+            warn_unused: true,
+            ..BuildOptions::default()
+        };
+        let artifacts =
+            build_with_plan_and_options(&plan, &options).unwrap();
+        let unused: Vec<&String> = artifacts
+            .warnings
+            .iter()
+            .filter(|w| w.contains("unused resource"))
+            .collect();
+        assert_eq!(unused.len(), 1, "{unused:?}");
+        assert!(unused[0].starts_with("1 unused resource(s) in "));
+        assert!(unused[0].contains("values.xml: legacy ("));
+
+        // Off by default
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(artifacts.warnings.is_empty());
+    }
+
+    #[test]
+    fn raw_files_are_embedded() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        write_file(&res_dir.join("raw/splash.png"), "PNG");
+        write_file(&res_dir.join("raw/config.json"), "{}");
+        let plan = BuildPlan::new(res_dir.clone(), None, "debug");
+
+        let rust = build_with_plan(&plan).expect("build succeeds").rust;
+        let splash = fs::canonicalize(res_dir.join("raw/splash.png"))
+            .unwrap();
+        assert!(rust.contains("    pub mod raw {"));
+        assert!(rust.contains(&format!(
+            "pub const SPLASH_PNG: &[u8] = include_bytes!({});",
+            utils::string_literal(&splash.to_string_lossy())
+        )));
+        assert!(rust.contains("pub const CONFIG_JSON_STR: &str"));
+        assert!(!rust.contains("SPLASH_PNG_STR"));
+
+        let options = BuildOptions {
+            disable_raw: true,
+            ..BuildOptions::default()
+        };
+        let rust = build_with_plan_and_options(&plan, &options)
+            .expect("build succeeds")
+            .rust;
+        assert!(!rust.contains("pub mod raw"));
+    }
+
+    #[test]
+    fn oversized_raw_files_fail() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(&res_dir.join("values.xml"), "<resources/>");
+        write_file(&res_dir.join("raw/intro.mp4"), "0123456789");
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let options = BuildOptions::new().with_max_raw_file_size(4);
+
+        let err = build_with_plan_and_options(&plan, &options)
+            .err()
+            .expect("build fails");
+        assert!(err.to_string().contains("intro.mp4"), "{err}");
+    }
+
+    #[test]
+    fn namespaces_shadowing_generated_items_fail() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+  <ns name="lookup">
+    <string name="a">A</string>
+  </ns>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        assert!(errors[0].message.contains("`r::lookup`"));
+        // At the `<ns>` element, not at its first resource
+        let diagnostic = errors[0].to_diagnostic();
+        let location = diagnostic.location.as_ref().unwrap();
+        assert_eq!(location.line, Some(2));
+        assert_eq!(diagnostic.highlight.as_deref(), Some("name=\"lookup\""));
+    }
+
+    #[test]
+    fn sanitized_name_collisions_fail() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources><string name="foo-bar">A</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("more.xml"),
+            r#"<resources>
+  <number name="level" type="u8">300</number>
+  <string name="foo.bar">B</string>
+</resources>"#,
+        );
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let Err(BuildError::Analysis(errors)) = build_with_plan(&plan)
+        else {
+            panic!("expected analysis errors");
+        };
+        // Reported with the other errors of the analysis
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        let collision = errors
+            .iter()
+            .find(|error| error.message.contains("`FOO_BAR`"))
+            .expect("the collision is reported");
+        let message = &collision.message;
+        assert!(message.contains("'foo-bar'"), "{message}");
+        assert!(message.contains("'foo.bar'"), "{message}");
+        assert!(message.contains("more.xml:3"), "{message}");
+        assert!(message.contains("values.xml:1"), "{message}");
+        let location = collision.location.as_ref().unwrap();
+        let values = plan.resources_dir.join("values.xml");
+        let expected = format!("{}:1", values.display());
+        assert_eq!(location.to_string(), expected);
+    }
 }
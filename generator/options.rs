@@ -0,0 +1,557 @@
+//! Build options for resource generation.
+//!
+//! `BuildOptions` lets a consumer's build.rs tweak how resources are
+//! generated without building a full `BuildPlan` by hand.
+
+use std::path::{Path, PathBuf};
+
+use crate::generator::analysis::ValidationOptions;
+use crate::generator::input;
+use crate::generator::ir::Ordering;
+use crate::generator::parsing::FileNamespaces;
+
+/// Environment variable overriding the resource profile.
+pub const PROFILE_ENV_VAR: &str = "R_RESOURCES_PROFILE";
+
+/// Environment variable fixing the build time of reproducible builds,
+/// in seconds since the Unix epoch.
+pub const SOURCE_DATE_EPOCH_ENV_VAR: &str = "SOURCE_DATE_EPOCH";
+
+/// How the flattened aliases of `BuildOptions::flatten_aliases` are
+/// generated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReferenceStyle {
+    /// `pub use self::ui::TITLE as UI_TITLE;`, with the path and docs
+    /// of the item
+    #[default]
+    Alias,
+    /// A `pub const` of its own holding the same value, with its own
+    /// doc line and `#[deprecated]`, for strings, numbers, bools and
+    /// colors; statics, functions and the other items keep a
+    /// `pub use`
+    Copy,
+}
+
+/// Type of the lazily initialized statics of the generated code
+/// (`BigDecimal` numbers, compiled regexes), picked from
+/// `BuildOptions::msrv`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LazyStatics {
+    /// `std::sync::LazyLock`, Rust 1.80 and later
+    #[default]
+    LazyLock,
+    /// `r_resources::Lazy`, built on `OnceLock` for Rust 1.70 and
+    /// later
+    OnceLock,
+}
+
+impl LazyStatics {
+    /// Path of the static type, e.g. `std::sync::LazyLock`
+    pub fn path(self) -> &'static str {
+        match self {
+            Self::LazyLock => "std::sync::LazyLock",
+            Self::OnceLock => "r_resources::Lazy",
+        }
+    }
+}
+
+/// Options accepted by `build_with_options`.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    /// Explicit resource profile (e.g. `staging`).
+    ///
+    /// Precedence: this option > `R_RESOURCES_PROFILE` > cargo `PROFILE`.
+    pub profile: Option<String>,
+    /// Fail the build when a locale lacks translations for some strings
+    /// or templates (instead of warning).
+    pub require_complete_translations: bool,
+    /// Fail the build on duplicate resources (instead of warning).
+    ///
+    /// Also enabled by `R_RESOURCES_DUPLICATES_AS_ERRORS=1`.
+    pub treat_duplicates_as_errors: bool,
+    /// Fail the build on strings starting with an `@` that is neither
+    /// a reference nor escaped, such as `@strig/app_name` (instead of
+    /// warning).
+    pub strict_references: bool,
+    /// Only warn about namespaces named after a generated module
+    /// (`string`, `r`), a primitive type (`str`, `u32`) or
+    /// `crate`/`self`/`super`, instead of failing the build.
+    pub allow_reserved_names: bool,
+    /// Directory receiving a Fluent (`.ftl`) export of strings and
+    /// templates: `default.ftl` plus one `<locale>.ftl` per locale.
+    pub export_fluent: Option<PathBuf>,
+    /// File receiving a Markdown catalog of the resources: one table
+    /// per type with values, docs, source files and profiles.
+    pub export_markdown: Option<PathBuf>,
+    /// File receiving TypeScript definitions of the resources
+    /// (`resources.ts`): one `as const` object mirroring `r::`.
+    pub export_typescript: Option<PathBuf>,
+    /// File receiving a Kotlin `object R` of the resources (`R.kt`),
+    /// with nested objects per namespace.
+    pub export_kotlin: Option<PathBuf>,
+    /// `package` of the Kotlin export; none when unset.
+    pub kotlin_package: Option<String>,
+    /// File receiving a C header of the resources (`resources.h`):
+    /// one `#define R_...` per resource.
+    pub export_c_header: Option<PathBuf>,
+    /// Pseudo-localize strings and templates (`[Ŝåṽé~~]`) to spot
+    /// hardcoded or truncated text. Also enabled by the `pseudo` profile.
+    pub pseudolocalize: bool,
+    /// Skip the `r::lookup` runtime tables for a minimal output.
+    pub disable_lookup: bool,
+    /// Omit the "Defined in `res/...`" doc line on generated items.
+    pub disable_provenance: bool,
+    /// Skip the type-organized modules (`string::`, `int::`, `color::`)
+    /// re-exporting the items of `r::`.
+    pub disable_typed_modules: bool,
+    /// Warn about resources that no `.rs` file of `source_dir` refers
+    /// to, grouped by resource file.
+    pub warn_unused: bool,
+    /// Sources scanned by `warn_unused`; defaults to the `src/`
+    /// directory next to the resources directory.
+    pub source_dir: Option<PathBuf>,
+    /// Largest file of `res/raw/` that may be embedded, in bytes;
+    /// defaults to 10 MiB. Larger files fail the build.
+    pub max_raw_file_size: Option<u64>,
+    /// Skip `r::raw` even when `res/raw/` has files.
+    pub disable_raw: bool,
+    /// Skip the string accessors of the `hot-reload` feature.
+    pub disable_hot_reload: bool,
+    /// Skip `r::to_json()` of the `serde` feature.
+    pub disable_json: bool,
+    /// Emit an `ALL` table of `(qualified name, value)` pairs in each
+    /// typed module and namespace (`string::ALL`, `int::ui::ALL`),
+    /// plus `r::meta::summary()`. Off by default: the tables roughly
+    /// double the generated code.
+    pub registries: bool,
+    /// Print a usage and size report of the generated code (resources
+    /// per type and namespace, string bytes, the largest resources,
+    /// constant count) as `cargo:warning=` lines, also written to
+    /// `OUT_DIR/r_report.txt`.
+    pub report: bool,
+    /// Put the resources of each file under a namespace named after
+    /// it (`res/payments.xml` → `r::payments::`), around the `<ns>`
+    /// of the file. `<resources ns-from-file="...">` overrides this
+    /// per file.
+    pub namespace_by_file: bool,
+    /// File stems left at the root by `namespace_by_file`; `values`
+    /// when empty.
+    pub root_file_stems: Vec<String>,
+    /// Also re-export every namespaced item at the root of `r::`,
+    /// named after its namespaces (`r::UI_COLORS_PRIMARY` for
+    /// `r::ui::colors::PRIMARY`, `r::auth_greeting()` for templates).
+    /// Aliases clashing with another root item fail the build.
+    pub flatten_aliases: bool,
+    /// Joins the namespaces of `flatten_aliases`, `_` when unset; `__`
+    /// keeps `a_b/c` and `a/b_c` apart.
+    pub flatten_separator: Option<String>,
+    /// Whether `flatten_aliases` re-exports the items or copies their
+    /// values into constants of their own.
+    pub reference_style: ReferenceStyle,
+    /// Bake `0` into `r::meta::GENERATED_AT_UNIX` instead of the
+    /// build time, so the generated code only depends on the inputs.
+    /// `SOURCE_DATE_EPOCH` replaces the build time when set.
+    pub deterministic: bool,
+    /// Order of the resources in each generated module and in the
+    /// Markdown catalog: by name (the default), or as declared with
+    /// `Ordering::Source`. Namespaces stay sorted by name, and so do
+    /// the lookup tables, key enums and `ALL` tables.
+    pub ordering: Ordering,
+    /// Resources directory relative to the workspace root (e.g.
+    /// `res`), instead of `res/` next to the crate's `Cargo.toml`.
+    pub workspace_res_dir: Option<PathBuf>,
+    /// Integer types (`usize`, `u64`) every typed integer is also
+    /// generated as, where no value can be lost: `CACHE_SIZE_USIZE`
+    /// for a `u32`, but nothing for an `i64`.
+    pub widen: Vec<String>,
+    /// Pointer width in bits (16, 32 or 64) that `type="usize"` and
+    /// `type="isize"` numbers must fit. Unset, they must fit in 32
+    /// bits, so the resources build for every target from there up.
+    pub target_pointer_width: Option<u32>,
+    /// Oldest Rust version the generated code must compile with, e.g.
+    /// `1.75`. Below 1.80, lazily initialized statics use
+    /// `r_resources::Lazy` instead of `std::sync::LazyLock`; 1.70 is
+    /// the oldest supported.
+    pub msrv: Option<String>,
+    /// Also generate the resources marked `overridable="true"` as a
+    /// getter (`r::max_retries()`) that tests can override on their
+    /// thread with `r::set_max_retries_for_test(1)`. The others stay
+    /// plain constants.
+    pub testable: bool,
+}
+
+impl BuildOptions {
+    #[allow(dead_code)] // Public API, may be used by consumers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets an explicit profile, overriding the environment.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_profile(
+        mut self,
+        profile: impl Into<String>,
+    ) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Exports strings and templates as Fluent files into `dir`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_fluent_export(
+        mut self,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        self.export_fluent = Some(dir.into());
+        self
+    }
+
+    /// Writes a Markdown catalog of the resources to `path`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_markdown_export(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.export_markdown = Some(path.into());
+        self
+    }
+
+    /// Writes TypeScript definitions of the resources to `path`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_typescript_export(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.export_typescript = Some(path.into());
+        self
+    }
+
+    /// Writes a Kotlin `object R` of the resources to `path`, in
+    /// `package`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_kotlin_export(
+        mut self,
+        path: impl Into<PathBuf>,
+        package: impl Into<String>,
+    ) -> Self {
+        self.export_kotlin = Some(path.into());
+        self.kotlin_package = Some(package.into());
+        self
+    }
+
+    /// Writes a C header of the resources to `path`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_c_header_export(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.export_c_header = Some(path.into());
+        self
+    }
+
+    /// Reads the resources from `path` under the workspace root, so
+    /// the members of a workspace can share one `res/`.
+    ///
+    /// The root is `CARGO_WORKSPACE_DIR` when set, otherwise the
+    /// topmost directory whose `Cargo.toml` has a `[workspace]` table;
+    /// a standalone crate is its own root.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn res_dir_relative_to_workspace(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.workspace_res_dir = Some(path.into());
+        self
+    }
+
+    /// Resources directory of the crate in `manifest_dir`
+    pub fn resources_dir(&self, manifest_dir: &Path) -> PathBuf {
+        match &self.workspace_res_dir {
+            Some(dir) => {
+                let env_root = std::env::var_os(
+                    input::workspace::WORKSPACE_DIR_ENV_VAR,
+                );
+                input::workspace::workspace_root(
+                    manifest_dir,
+                    env_root.as_deref().map(Path::new),
+                )
+                .join(dir)
+            }
+            None => manifest_dir.join("res"),
+        }
+    }
+
+    /// Also generates every typed integer as each of `types` it
+    /// widens to losslessly (`["usize", "u64"]`).
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_widening<I, S>(mut self, types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.widen = types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Accepts `usize` and `isize` numbers up to `bits` bits (16, 32
+    /// or 64) instead of 32, for crates that only build for such
+    /// targets.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_target_pointer_width(mut self, bits: u32) -> Self {
+        self.target_pointer_width = Some(bits);
+        self
+    }
+
+    /// Generates code compiling with Rust `version` (e.g. `1.75`) and
+    /// later.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_msrv(mut self, version: impl Into<String>) -> Self {
+        self.msrv = Some(version.into());
+        self
+    }
+
+    /// Lazy statics compiling with `msrv`, or why it is not a
+    /// supported Rust version
+    pub fn lazy_statics(&self) -> Result<LazyStatics, String> {
+        let Some(msrv) = &self.msrv else {
+            return Ok(LazyStatics::LazyLock);
+        };
+        let parts: Option<Vec<u32>> = msrv
+            .trim()
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect();
+        let version = match parts.as_deref() {
+            Some([major, minor] | [major, minor, _]) => {
+                (*major, *minor)
+            }
+            _ => {
+                return Err(format!(
+                    "msrv: '{msrv}' is not a Rust version such as `1.75`"
+                ))
+            }
+        };
+        match version {
+            version if version >= (1, 80) => {
+                Ok(LazyStatics::LazyLock)
+            }
+            version if version >= (1, 70) => {
+                Ok(LazyStatics::OnceLock)
+            }
+            _ => Err(format!(
+                "msrv: the generated code needs Rust 1.70 or later, not {msrv}"
+            )),
+        }
+    }
+
+    /// Raises (or lowers) the size limit of the files of `res/raw/`.
+    #[allow(dead_code)] // Public API, may be used by consumers
+    #[must_use]
+    pub fn with_max_raw_file_size(mut self, bytes: u64) -> Self {
+        self.max_raw_file_size = Some(bytes);
+        self
+    }
+
+    /// Size limit of the files of `res/raw/`, in bytes
+    pub fn raw_file_size_limit(&self) -> u64 {
+        self.max_raw_file_size
+            .unwrap_or(input::raw_assets::DEFAULT_MAX_RAW_FILE_SIZE)
+    }
+
+    /// Sources scanned by `warn_unused` for a resources directory
+    pub fn unused_source_dir(&self, resources_dir: &Path) -> PathBuf {
+        self.source_dir.clone().unwrap_or_else(|| {
+            resources_dir
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join("src")
+        })
+    }
+
+    /// Options forwarded to the analysis stage.
+    pub fn validation_options(&self) -> ValidationOptions {
+        ValidationOptions {
+            treat_duplicates_as_errors: self
+                .treat_duplicates_as_errors,
+            require_complete_translations: self
+                .require_complete_translations,
+            allow_reserved_names: self.allow_reserved_names,
+            target_pointer_width: self.target_pointer_width,
+            strict_references: self.strict_references,
+            registries: self.registries,
+            flatten_separator: self.flatten_aliases.then(|| {
+                let separator = self.flatten_separator.as_deref();
+                separator.unwrap_or("_").to_string()
+            }),
+            testable: self.testable,
+            hot_reload: cfg!(feature = "hot-reload")
+                && !self.disable_hot_reload,
+            regex: cfg!(feature = "regex"),
+        }
+    }
+
+    /// Options forwarded to the parsing stage.
+    pub fn file_namespaces(&self) -> FileNamespaces {
+        FileNamespaces {
+            enabled: self.namespace_by_file,
+            root_stems: self.root_file_stems.clone(),
+        }
+    }
+
+    /// Resolves the profile from the options and the build environment.
+    pub fn resolve_profile(&self) -> String {
+        select_profile(
+            self.profile.as_deref(),
+            std::env::var(PROFILE_ENV_VAR).ok().as_deref(),
+            std::env::var("PROFILE").ok().as_deref(),
+        )
+    }
+
+    /// Unix time baked into `r::meta::GENERATED_AT_UNIX`.
+    pub fn generated_at(&self) -> i64 {
+        select_timestamp(
+            self.deterministic,
+            std::env::var(SOURCE_DATE_EPOCH_ENV_VAR).ok().as_deref(),
+            || {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |elapsed| {
+                        i64::try_from(elapsed.as_secs()).unwrap_or(0)
+                    })
+            },
+        )
+    }
+}
+
+/// `0` for deterministic builds, else `SOURCE_DATE_EPOCH` when it is
+/// a number, else `now`.
+fn select_timestamp(
+    deterministic: bool,
+    source_date_epoch: Option<&str>,
+    now: impl FnOnce() -> i64,
+) -> i64 {
+    if deterministic {
+        return 0;
+    }
+    source_date_epoch
+        .and_then(|epoch| epoch.trim().parse().ok())
+        .unwrap_or_else(now)
+}
+
+/// Picks the first non-empty profile, falling back to `debug`.
+fn select_profile(
+    explicit: Option<&str>,
+    env_override: Option<&str>,
+    cargo_profile: Option<&str>,
+) -> String {
+    [explicit, env_override, cargo_profile]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|p| !p.is_empty())
+        .unwrap_or("debug")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_profile_wins() {
+        let profile = select_profile(
+            Some("staging"),
+            Some("qa"),
+            Some("release"),
+        );
+        assert_eq!(profile, "staging");
+    }
+
+    #[test]
+    fn env_override_beats_cargo_profile() {
+        let profile =
+            select_profile(None, Some("qa"), Some("release"));
+        assert_eq!(profile, "qa");
+    }
+
+    #[test]
+    fn falls_back_to_cargo_profile() {
+        let profile = select_profile(None, Some(""), Some("release"));
+        assert_eq!(profile, "release");
+    }
+
+    #[test]
+    fn defaults_to_debug() {
+        assert_eq!(select_profile(None, None, None), "debug");
+    }
+
+    #[test]
+    fn timestamps_can_be_fixed() {
+        assert_eq!(select_timestamp(true, Some("1700000000"), || 5), 0);
+        assert_eq!(
+            select_timestamp(false, Some(" 1700000000\n"), || 5),
+            1_700_000_000
+        );
+        assert_eq!(select_timestamp(false, Some("yesterday"), || 5), 5);
+        assert_eq!(select_timestamp(false, None, || 5), 5);
+    }
+
+    #[test]
+    fn with_profile_sets_explicit_profile() {
+        let options = BuildOptions::new().with_profile("staging");
+        assert_eq!(options.profile.as_deref(), Some("staging"));
+        assert_eq!(options.resolve_profile(), "staging");
+    }
+
+    #[test]
+    fn msrv_picks_the_lazy_statics() {
+        let lazy = |msrv: &str| {
+            BuildOptions::new().with_msrv(msrv).lazy_statics()
+        };
+        assert_eq!(
+            BuildOptions::new().lazy_statics(),
+            Ok(LazyStatics::LazyLock)
+        );
+        assert_eq!(lazy("1.80"), Ok(LazyStatics::LazyLock));
+        assert_eq!(lazy("1.75.0"), Ok(LazyStatics::OnceLock));
+        assert_eq!(lazy("1.70"), Ok(LazyStatics::OnceLock));
+        assert_eq!(
+            lazy("1.69"),
+            Err("msrv: the generated code needs Rust 1.70 or later, not 1.69".into())
+        );
+        assert_eq!(
+            lazy("stable"),
+            Err("msrv: 'stable' is not a Rust version such as `1.75`".into())
+        );
+    }
+
+    #[test]
+    fn resources_default_to_the_crate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\n")
+            .unwrap();
+        let member = root.join("crates/ui");
+
+        assert_eq!(
+            BuildOptions::new().resources_dir(&member),
+            member.join("res")
+        );
+        assert_eq!(
+            BuildOptions::new()
+                .res_dir_relative_to_workspace("res")
+                .resources_dir(&member),
+            root.join("res")
+        );
+    }
+}
@@ -4,7 +4,23 @@ use std::path::PathBuf;
 pub struct ParsedResourceFile {
     pub path: PathBuf,
     pub is_test: bool,
+    /// Locale of the file (`None` for default resources)
+    pub locale: Option<String>,
     pub resources: Vec<ParsedResource>,
+    /// `<doc>` text of each documented `<ns>`, keyed by namespace path
+    pub namespace_docs: Vec<(Vec<String>, String)>,
+    /// Paths of the `<ns struct="true">` elements, grouped into a
+    /// config struct
+    pub config_namespaces: Vec<Vec<String>>,
+    /// Path and line of each `<ns>` element
+    pub namespace_lines: Vec<(Vec<String>, usize)>,
+    /// Lines of `<doc>` elements no resource or namespace followed
+    pub unused_docs: Vec<usize>,
+    /// Elements the reader rejected and skipped, with their line
+    pub errors: Vec<(usize, String)>,
+    /// `ns-from-file` attribute of `<resources>`, overriding
+    /// `BuildOptions::namespace_by_file` for this file
+    pub ns_from_file: Option<bool>,
 }
 
 impl ParsedResourceFile {
@@ -16,9 +32,21 @@ impl ParsedResourceFile {
         Self {
             path,
             is_test,
+            locale: None,
             resources,
+            namespace_docs: Vec::new(),
+            config_namespaces: Vec::new(),
+            namespace_lines: Vec::new(),
+            unused_docs: Vec::new(),
+            errors: Vec::new(),
+            ns_from_file: None,
         }
     }
+
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +54,63 @@ pub struct ParsedResource {
     pub name: String,
     pub kind: ResourceKind,
     pub value: ScalarValue,
+    pub meta: ResourceMeta,
+}
+
+/// Element-level metadata captured alongside a parsed resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceMeta {
+    /// `profile` attributes from the enclosing `<ns>` elements down to
+    /// the resource element itself (outermost first).
+    pub profiles: Vec<String>,
+    /// `false` when marked `translatable="false"`
+    pub translatable: bool,
+    /// Note of a `deprecated="..."` attribute
+    pub deprecated: Option<String>,
+    /// `true` when marked `keep="true"`, never reported as unused
+    pub keep: bool,
+    /// `true` when marked `overridable="true"`, with a getter tests
+    /// can override under `BuildOptions::testable`
+    pub overridable: bool,
+    /// 1-based line of the resource element in its file
+    pub line: Option<usize>,
+    /// Text of the `<doc>` elements right before the resource
+    pub doc: Option<String>,
+    /// `min` attribute of a number, as written
+    pub min: Option<String>,
+    /// `max` attribute of a number, as written
+    pub max: Option<String>,
+    /// `pattern` attribute of a string
+    pub pattern: Option<String>,
+    /// `transform` chain of a string, as written (`trim,slug`)
+    pub transform: Option<String>,
+    /// `assert` checks of a number or string, as written (`gt:0`)
+    pub assert: Option<String>,
+    /// `widen` types of a typed integer, as written (`usize,u64`)
+    pub widen: Option<String>,
+    /// `cfg` predicate of the resource, as written (`unix`)
+    pub cfg: Option<String>,
+}
+
+impl Default for ResourceMeta {
+    fn default() -> Self {
+        Self {
+            profiles: Vec::new(),
+            translatable: true,
+            deprecated: None,
+            keep: false,
+            overridable: false,
+            line: None,
+            doc: None,
+            min: None,
+            max: None,
+            pattern: None,
+            transform: None,
+            assert: None,
+            widen: None,
+            cfg: None,
+        }
+    }
 }
 
 impl ParsedResource {
@@ -37,6 +122,7 @@ impl ParsedResource {
             name: name.into(),
             kind: ResourceKind::String,
             value: ScalarValue::Text(value.into()),
+            meta: ResourceMeta::default(),
         }
     }
 
@@ -52,6 +138,7 @@ impl ParsedResource {
                 value: value.into(),
                 explicit_type,
             },
+            meta: ResourceMeta::default(),
         }
     }
 
@@ -60,6 +147,7 @@ impl ParsedResource {
             name: name.into(),
             kind: ResourceKind::Bool,
             value: ScalarValue::Bool(value),
+            meta: ResourceMeta::default(),
         }
     }
 }
@@ -71,6 +159,34 @@ pub enum ResourceKind {
     Bool,
     Color,
     Template,
+    LatLng,
+    Position,
+    /// `<bytesize>`, a size in bytes with a unit suffix
+    ByteSize,
+    /// `<semver>`, a semantic version
+    Semver,
+    /// `<ipaddr>`, an IPv4 or IPv6 address
+    IpAddr,
+    /// `<socketaddr>`, an address and a port
+    SocketAddr,
+    /// `<regex>`, a regular expression
+    Regex,
+    /// `<uuid>`, a hyphenated UUID
+    Uuid,
+    /// `<datetime>`, an RFC 3339 date and time
+    DateTime,
+    /// `<date>`, a calendar date
+    Date,
+    /// `<money>`, an amount of a currency
+    Money,
+    /// `<char>`, a Unicode scalar value or a grapheme
+    Char,
+    /// `<bytes>`, base64 or hex decoded at build time
+    Bytes,
+    /// `<computed>`, a number evaluated from other numbers
+    Computed,
+    /// `<concat>`, a string joined from references and literals
+    Concat,
     // TODO: array, etc.
 }
 
@@ -87,6 +203,60 @@ pub enum ScalarValue {
         text: String,
         params: Vec<TemplateParam>,
     },
+    /// `lat` and `lng` attributes, checked by the type
+    LatLng { lat: String, lng: String },
+    /// `x` and `y` attributes, checked by the type
+    Position { x: String, y: String },
+    /// Text of a `<bytesize>` (`25MiB`), checked by the type
+    ByteSize(String),
+    /// Text of a `<semver>` (`1.10.0-rc.1`), checked by the type
+    Semver(String),
+    /// Text of an `<ipaddr>`, checked by the type
+    IpAddr(String),
+    /// Text of a `<socketaddr>`, checked by the type
+    SocketAddr(String),
+    /// Source of a `<regex>`, untrimmed
+    Regex(String),
+    /// Text of a `<uuid>`, checked by the type
+    Uuid(String),
+    /// Text of a `<datetime>`, checked by the type
+    DateTime(String),
+    /// Text of a `<date>`, checked by the type
+    Date(String),
+    /// Amount, `currency` and `scale` of a `<money>`, checked by the
+    /// type
+    Money {
+        amount: String,
+        currency: String,
+        scale: Option<String>,
+    },
+    /// Text of a `<char>`, escapes undecoded, and its `grapheme`
+    /// attribute
+    Char { text: String, grapheme: bool },
+    /// Encoded text of a `<bytes>`, with its `encoding` and `sha256`
+    /// attributes, checked by the type
+    Bytes {
+        text: String,
+        encoding: String,
+        sha256: Option<String>,
+    },
+    /// Expression of a `<computed>`, evaluated once the graph is
+    /// complete
+    Computed {
+        expression: String,
+        explicit_type: Option<String>,
+    },
+    /// Parts of a `<concat>`, in order
+    Concat(Vec<ConcatPart>),
+}
+
+/// Child of a `<concat>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConcatPart {
+    /// `<ref>@string/base_url</ref>`, the name without the prefix
+    Ref(String),
+    /// `<lit>/v2/users</lit>`, kept verbatim
+    Lit(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -104,6 +274,21 @@ impl ScalarValue {
             Self::Bool(_) => None,
             Self::Color(_) => None,
             Self::Template { text, .. } => Some(text.as_str()),
+            Self::LatLng { .. } | Self::Position { .. } => None,
+            Self::ByteSize(text)
+            | Self::Semver(text)
+            | Self::IpAddr(text)
+            | Self::SocketAddr(text)
+            | Self::Regex(text)
+            | Self::Uuid(text)
+            | Self::DateTime(text)
+            | Self::Date(text) => Some(text),
+            Self::Money { amount, .. } => Some(amount),
+            Self::Char { text, .. } | Self::Bytes { text, .. } => {
+                Some(text)
+            }
+            Self::Computed { expression, .. } => Some(expression),
+            Self::Concat(_) => None,
         }
     }
 
@@ -2,7 +2,13 @@ use std::path::PathBuf;
 
 #[derive(Debug)]
 pub enum ParserError {
-    Xml { path: PathBuf, message: String },
+    /// Malformed XML, or an element the reader rejects, at the 1-based
+    /// `line` when known
+    Xml {
+        path: PathBuf,
+        line: Option<usize>,
+        message: String,
+    },
 }
 
 impl std::fmt::Display for ParserError {
@@ -11,9 +17,16 @@ impl std::fmt::Display for ParserError {
         f: &mut std::fmt::Formatter<'_>,
     ) -> std::fmt::Result {
         match self {
-            Self::Xml { path, message } => {
-                write!(f, "{}: {message}", path.display())
-            }
+            Self::Xml {
+                path,
+                line: Some(line),
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+            Self::Xml {
+                path,
+                line: None,
+                message,
+            } => write!(f, "{}: {message}", path.display()),
         }
     }
 }
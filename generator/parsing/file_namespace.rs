@@ -0,0 +1,217 @@
+//! Namespaces derived from file names (`BuildOptions::namespace_by_file`).
+//!
+//! ```xml
+//! <!-- res/payments.xml -->
+//! <resources ns-from-file="true">
+//!     <string name="title">Pay</string>          <!-- payments/title -->
+//!     <ns name="errors">
+//!         <string name="declined">Declined</string> <!-- payments/errors/declined -->
+//!     </ns>
+//! </resources>
+//! ```
+//!
+//! The file stem, without its locale qualifier (`payments-fr.xml` is
+//! `payments` too), is lowercased and every character that cannot be
+//! in a module name becomes `_` (`api-errors.xml` → `api_errors`).
+//! The root stems (`values` by default) keep their resources at the
+//! root. `ns-from-file` on `<resources>` overrides the option per file.
+
+use super::ast::ParsedResourceFile;
+
+/// Stem of the files keeping their resources at the root by default
+pub const DEFAULT_ROOT_STEM: &str = "values";
+
+/// How files get their namespace
+#[derive(Debug, Clone, Default)]
+pub struct FileNamespaces {
+    /// Namespace the files without `ns-from-file` by their name
+    pub enabled: bool,
+    /// Stems staying at the root, `values` when empty
+    pub root_stems: Vec<String>,
+}
+
+impl FileNamespaces {
+    /// Moves the resources and namespace docs of each file under the
+    /// namespace named after it
+    pub fn apply(&self, files: &mut [ParsedResourceFile]) {
+        for file in files {
+            if !file.ns_from_file.unwrap_or(self.enabled) {
+                continue;
+            }
+            let Some(namespace) = self.namespace_of(file) else {
+                continue;
+            };
+            for resource in &mut file.resources {
+                resource.name =
+                    format!("{namespace}/{}", resource.name);
+            }
+            for (path, _) in &mut file.namespace_docs {
+                path.insert(0, namespace.clone());
+            }
+            for path in &mut file.config_namespaces {
+                path.insert(0, namespace.clone());
+            }
+            for (path, _) in &mut file.namespace_lines {
+                path.insert(0, namespace.clone());
+            }
+        }
+    }
+
+    /// Namespace of `file`, `None` for a root file
+    fn namespace_of(
+        &self,
+        file: &ParsedResourceFile,
+    ) -> Option<String> {
+        let stem = file.path.file_stem()?.to_str()?;
+        // The locale qualifier is what follows the first `-`
+        let stem = match &file.locale {
+            Some(_) => stem.split_once('-').map_or(stem, |(s, _)| s),
+            None => stem,
+        };
+        let is_root = if self.root_stems.is_empty() {
+            stem == DEFAULT_ROOT_STEM
+        } else {
+            self.root_stems.iter().any(|root| root == stem)
+        };
+        if is_root {
+            return None;
+        }
+        Some(module_name(stem))
+    }
+}
+
+/// `Api-Errors` → `api_errors`
+fn module_name(stem: &str) -> String {
+    stem.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::input::RawResourceFile;
+    use crate::generator::parsing::parse_raw_files;
+    use std::path::PathBuf;
+
+    fn parse(path: &str, xml: &str) -> ParsedResourceFile {
+        let raw = RawResourceFile::new(
+            PathBuf::from(path),
+            xml.into(),
+            false,
+        );
+        let locale = path.contains("-fr").then(|| "fr".to_string());
+        let (mut files, errors) =
+            parse_raw_files(&[raw.with_locale(locale)]);
+        assert!(errors.is_empty(), "{errors:?}");
+        files.remove(0)
+    }
+
+    /// Resource names of the file at `path` once namespaced
+    fn names_of(
+        namespaces: &FileNamespaces,
+        path: &str,
+        xml: &str,
+    ) -> Vec<String> {
+        let mut file = parse(path, xml);
+        namespaces.apply(std::slice::from_mut(&mut file));
+        file.resources.into_iter().map(|r| r.name).collect()
+    }
+
+    const PAYMENTS: &str = r#"<resources>
+        <string name="title">Pay</string>
+        <ns name="errors"><doc>Declines</doc>
+            <string name="declined">Declined</string>
+        </ns>
+    </resources>"#;
+
+    #[test]
+    fn files_namespace_their_resources() {
+        let enabled = FileNamespaces {
+            enabled: true,
+            ..FileNamespaces::default()
+        };
+        let mut file = parse("res/payments.xml", PAYMENTS);
+        enabled.apply(std::slice::from_mut(&mut file));
+
+        let names: Vec<&str> =
+            file.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["payments/title", "payments/errors/declined"]
+        );
+        assert_eq!(
+            file.namespace_docs,
+            [(
+                vec!["payments".to_string(), "errors".to_string()],
+                "Declines".to_string()
+            )]
+        );
+        assert_eq!(
+            names_of(&enabled, "res/values.xml", PAYMENTS)[0],
+            "title"
+        );
+        assert_eq!(
+            names_of(&enabled, "res/Api-Errors.xml", PAYMENTS)[0],
+            "api_errors/title"
+        );
+        assert_eq!(
+            names_of(&enabled, "res/payments-fr.xml", PAYMENTS)[0],
+            "payments/title"
+        );
+    }
+
+    #[test]
+    fn root_stems_are_configurable() {
+        let namespaces = FileNamespaces {
+            enabled: true,
+            root_stems: vec!["common".into()],
+        };
+        assert_eq!(
+            names_of(&namespaces, "res/common.xml", PAYMENTS)[0],
+            "title"
+        );
+        assert_eq!(
+            names_of(&namespaces, "res/values.xml", PAYMENTS)[0],
+            "values/title"
+        );
+    }
+
+    #[test]
+    fn files_opt_in_and_out() {
+        let opted_in = PAYMENTS.replacen(
+            "<resources>",
+            r#"<resources ns-from-file="true">"#,
+            1,
+        );
+        let opted_out = PAYMENTS.replacen(
+            "<resources>",
+            r#"<resources ns-from-file="false">"#,
+            1,
+        );
+        let disabled = FileNamespaces::default();
+        let enabled = FileNamespaces {
+            enabled: true,
+            ..FileNamespaces::default()
+        };
+
+        assert_eq!(
+            names_of(&disabled, "res/payments.xml", &opted_in)[0],
+            "payments/title"
+        );
+        assert_eq!(
+            names_of(&disabled, "res/payments.xml", PAYMENTS)[0],
+            "title"
+        );
+        assert_eq!(
+            names_of(&enabled, "res/payments.xml", &opted_out)[0],
+            "title"
+        );
+    }
+}
@@ -6,18 +6,46 @@
 
 mod ast;
 mod error;
+mod file_namespace;
 mod reader;
 
 pub use ast::{
-    ParsedResource, ParsedResourceFile, ResourceKind, ScalarValue,
+    ConcatPart, ParsedResource, ParsedResourceFile, ResourceKind,
+    ScalarValue, TemplateParam,
 };
+#[allow(unused_imports)] // Public API, may be used by consumers
+pub use ast::ResourceMeta;
 pub use error::ParserError;
+pub use file_namespace::FileNamespaces;
 
 use crate::generator::input::RawResourceFile;
 
 /// Parse a list of preprocessed raw files into structured resources.
+///
+/// Every file is parsed, and the errors of all of them are returned
+/// with the resources that could be read: a malformed file is skipped,
+/// a rejected element only loses its resource.
 pub fn parse_raw_files(
     raw_files: &[RawResourceFile],
-) -> Result<Vec<ParsedResourceFile>, ParserError> {
-    raw_files.iter().map(reader::parse_single_file).collect()
+) -> (Vec<ParsedResourceFile>, Vec<ParserError>) {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for raw in raw_files {
+        match reader::parse_single_file(raw) {
+            Ok(mut file) => {
+                let path = file.path.clone();
+                let rejected = std::mem::take(&mut file.errors);
+                errors.extend(rejected.into_iter().map(
+                    |(line, message)| ParserError::Xml {
+                        path: path.clone(),
+                        line: Some(line),
+                        message,
+                    },
+                ));
+                files.push(file);
+            }
+            Err(error) => errors.push(error),
+        }
+    }
+    (files, errors)
 }
@@ -1,42 +1,97 @@
-use quick_xml::events::{BytesEnd, BytesStart, BytesText};
+use quick_xml::events::{BytesEnd, BytesStart};
 
-use crate::generator::parsing::ast::{ParsedResource, TemplateParam};
+use crate::generator::parsing::ast::{
+    ConcatPart, ParsedResource, ResourceKind, ResourceMeta,
+    ScalarValue, TemplateParam,
+};
 
-use super::state::ParseState;
-use super::utils::{attr_value, text_to_string, to_string};
+use super::state::{DocLevel, ParseState};
+use super::utils::{
+    attr_value, comment_doc, comment_module_doc, doc_text, parse_bool,
+    tag_name, trim_indent,
+};
 
 pub(super) fn handle_start(
     state: &mut ParseState,
     e: &BytesStart<'_>,
 ) {
-    let tag = to_string(e.name().as_ref());
-    
-    state.current_tag = tag.clone();
+    let tag = tag_name(e.name().into_inner());
+    let tag: &str = &tag;
+    state.current_tag.clear();
+    state.current_tag.push_str(tag);
+
+    if tag == "doc" && !state.in_template {
+        state.in_doc = true;
+        state.module_doc =
+            attr_value(e, b"module").as_deref() == Some("true");
+        state.current_text.clear();
+        return;
+    }
+
+    if tag == "resources" {
+        state.ns_from_file = attr_value(e, b"ns-from-file")
+            .map(|value| value == "true");
+    }
 
     if tag == "ns" {
-        if let Some(ns_name) = attr_value(e, b"name") {
-            state.namespace_stack.push(ns_name);
+        // A doc right before the `<ns>` documents the module
+        let module_doc = state.take_pending_docs();
+        let name = attr_value(e, b"name");
+        if let Some(ns_name) = &name {
+            state.namespace_stack.push(ns_name.clone());
+            let path = state.namespace_stack.clone();
+            state.namespace_lines.push((path, state.element_line));
+            if attr_value(e, b"struct").as_deref() == Some("true") {
+                let path = state.namespace_stack.clone();
+                state.config_namespaces.push(path);
+            }
         }
+        state.doc_levels.push(DocLevel {
+            namespace: name.map(|_| state.namespace_stack.clone()),
+            module_doc,
+            ..DocLevel::default()
+        });
+        state.profile_stack.push(attr_value(e, b"profile"));
         state.current_name = None;
         return;
     }
 
+    if tag == "concat" {
+        state.in_concat = true;
+        state.concat_parts.clear();
+        state.concat_stray_text = false;
+        state.concat_rejected = false;
+    }
+    // The parts of a `<concat>` only collect their text
+    if state.in_concat && matches!(tag, "ref" | "lit") {
+        state.current_text.clear();
+        return;
+    }
+
     // Initialize template state FIRST (before processing parameters)
     if tag == "template" {
         state.in_template = true;
         state.template_params.clear();
         state.template_text.clear();
+        state.template_trim = attr_value(e, b"trim");
+        state.after_template_param = false;
+    }
+    if state.in_template {
+        // Text before a parameter is a separate template segment
+        flush_template_segment(state);
+    } else {
+        state.current_text.clear();
     }
     
     // Capture type attribute for numbers (reuse existing logic)
-    let number_type = if matches!(tag.as_str(), "number" | "int" | "float") {
+    let number_type = if matches!(tag, "number" | "int" | "float" | "computed") {
         attr_value(e, b"type")
     } else {
         None
     };
 
     let name_attr = attr_value(e, b"name");
-    let param_name = if let Some(name) = &name_attr {
+    let qualified_name = if let Some(name) = &name_attr {
         if state.namespace_stack.is_empty() {
             Some(name.clone())
         } else {
@@ -52,8 +107,9 @@ pub(super) fn handle_start(
     // Handle template parameters: if we're inside a template, treat standard tags as parameters
     // Reuse existing parsing logic by creating ScalarValue directly from attributes
     if state.in_template && tag != "template" {
-        if let Some(param_name_str) = &param_name {
-            let param_value = match tag.as_str() {
+        // Parameters are named in the template, not in its namespace
+        if let Some(param_name_str) = &name_attr {
+            let param_value = match tag {
                 "string" => Some(crate::generator::parsing::ScalarValue::Text(String::new())), // Empty for params
                 "number" | "int" | "float" => {
                     Some(crate::generator::parsing::ScalarValue::Number {
@@ -71,6 +127,9 @@ pub(super) fn handle_start(
                     name: param_name_str.clone(),
                     value,
                 });
+                // A parameter on a line of its own leaves no blank line
+                strip_blank_line_tail(&mut state.template_text);
+                state.after_template_param = true;
                 // Reset current_tag to "template" so text is captured correctly
                 state.current_tag = "template".to_string();
                 // Don't set current_name for template parameters
@@ -79,126 +138,608 @@ pub(super) fn handle_start(
         }
     }
 
+    if matches!(
+        tag,
+        "string"
+            | "number"
+            | "int"
+            | "float"
+            | "bool"
+            | "color"
+            | "template"
+            | "latlng"
+            | "position"
+            | "bytesize"
+            | "semver"
+            | "ipaddr"
+            | "socketaddr"
+            | "regex"
+            | "uuid"
+            | "datetime"
+            | "date"
+            | "money"
+            | "char"
+            | "bytes"
+            | "computed"
+            | "concat"
+    ) {
+        let docs = state.take_pending_docs();
+        state.current_doc = (!docs.is_empty()).then(|| docs.join("\n\n"));
+    }
+
     // Set state for normal resource processing
     state.current_number_type = number_type;
-    state.current_name = param_name;
+    state.current_number_range =
+        if matches!(tag, "number" | "int" | "float") {
+            [attr_value(e, b"min"), attr_value(e, b"max")]
+        } else {
+            [None, None]
+        };
+    state.current_widen =
+        if matches!(tag, "number" | "int" | "float") {
+            attr_value(e, b"widen")
+        } else {
+            None
+        };
+    state.current_coordinates = match tag {
+        "latlng" => [attr_value(e, b"lat"), attr_value(e, b"lng")],
+        "position" => [attr_value(e, b"x"), attr_value(e, b"y")],
+        _ => [None, None],
+    };
+    state.current_money = if tag == "money" {
+        [attr_value(e, b"currency"), attr_value(e, b"scale")]
+    } else {
+        [None, None]
+    };
+    state.current_grapheme = tag == "char"
+        && attr_value(e, b"grapheme").as_deref() == Some("true");
+    state.current_bytes = if tag == "bytes" {
+        [attr_value(e, b"encoding"), attr_value(e, b"sha256")]
+    } else {
+        [None, None]
+    };
+    state.current_name = qualified_name;
+    state.current_value = if matches!(
+        tag,
+        "string"
+            | "number"
+            | "int"
+            | "float"
+            | "bool"
+            | "color"
+            | "bytesize"
+            | "semver"
+            | "ipaddr"
+            | "socketaddr"
+            | "regex"
+            | "uuid"
+            | "datetime"
+            | "date"
+            | "money"
+            | "char"
+            | "bytes"
+    ) {
+        attr_value(e, b"value")
+    } else {
+        None
+    };
+    state.current_profile = attr_value(e, b"profile");
+    state.current_untranslatable =
+        attr_value(e, b"translatable").as_deref() == Some("false");
+    state.current_deprecated = attr_value(e, b"deprecated");
+    state.current_cfg = attr_value(e, b"cfg");
+    state.current_keep = attr_value(e, b"keep").as_deref() == Some("true");
+    state.current_overridable =
+        attr_value(e, b"overridable").as_deref() == Some("true");
+    state.current_assert = attr_value(e, b"assert");
+    state.current_line = Some(state.element_line);
+    // Regex sources are never trimmed
+    state.current_preserve = tag == "regex"
+        || (tag == "string"
+            && (attr_value(e, b"xml:space").as_deref()
+                == Some("preserve")
+                || attr_value(e, b"preserve").as_deref()
+                    == Some("true")));
+    (state.current_pattern, state.current_transform) = if tag == "string"
+    {
+        (attr_value(e, b"pattern"), attr_value(e, b"transform"))
+    } else {
+        (None, None)
+    };
+}
+
+/// Documents the next element with a marker comment (`<!--- ... -->`,
+/// `<!-- doc: ... -->` or `<!-- /// ... -->`) like a `<doc>` would;
+/// plain comments and comments inside a resource are ignored
+pub(super) fn handle_comment(state: &mut ParseState, comment: &str) {
+    if state.in_doc || state.in_template || state.current_name.is_some()
+    {
+        return;
+    }
+    if let Some(doc) = comment_module_doc(comment) {
+        state.push_module_doc(doc);
+    } else if let Some(doc) = comment_doc(comment) {
+        state.push_doc(doc, true);
+    }
+}
+
+/// Appends already-unescaped character data (text, entity, or CDATA)
+/// to the element currently being parsed
+pub(super) fn handle_text(state: &mut ParseState, text: &str) {
+    if state.in_doc {
+        state.current_text.push_str(text);
+        return;
+    }
+    // Only the parts of a `<concat>` have text
+    if state.in_concat
+        && !matches!(state.current_tag.as_str(), "ref" | "lit")
+    {
+        state.concat_stray_text |= !text.trim().is_empty();
+        return;
+    }
+    // Inside a template, only keep text that's directly inside the template tag
+    if state.in_template && state.current_tag != "template" {
+        return;
+    }
+    if state.in_template || state.current_name.is_some() {
+        state.current_text.push_str(text);
+    }
+}
+
+/// Moves the current template text segment into `template_text`,
+/// verbatim except for the rest of a parameter's line
+fn flush_template_segment(state: &mut ParseState) {
+    let mut segment = std::mem::take(&mut state.current_text);
+    if std::mem::take(&mut state.after_template_param) {
+        let blank = segment.len()
+            - segment.trim_start_matches([' ', '\t', '\r']).len();
+        if segment[blank..].starts_with('\n') {
+            segment.drain(..=blank);
+        }
+    }
+    state.template_text.push_str(&segment);
+}
+
+/// Drops the indentation ending `text` when its last line is blank
+fn strip_blank_line_tail(text: &mut String) {
+    let line_start = text.rfind('\n').map_or(0, |i| i + 1);
+    if text[line_start..].trim().is_empty() {
+        text.truncate(line_start);
+    }
+}
+
+/// Applies a template's `trim` mode: `indent` (default) strips the
+/// surrounding blank lines and the common indentation, `all` collapses
+/// every whitespace run to one space, `none` keeps the text verbatim
+fn trim_template(
+    name: &str,
+    text: &str,
+    mode: Option<&str>,
+) -> Result<String, String> {
+    match mode.unwrap_or("indent") {
+        "indent" => Ok(trim_indent(text)),
+        "all" => Ok(text.split_whitespace().collect::<Vec<_>>().join(" ")),
+        "none" => Ok(text.to_string()),
+        other => Err(format!(
+            "template '{name}' has invalid trim '{other}' (expected none, indent or all)"
+        )),
+    }
 }
 
-pub(super) fn handle_text(
+/// Builds the resource for a closing scalar tag from the accumulated
+/// text, or from its `value` attribute (`<bool name="x" value="on"/>`).
+///
+/// Empty strings are kept (`pub const X: &str = "";`); empty colors,
+/// unparseable bools and a `value` attribute next to text are errors.
+fn finish_scalar(
     state: &mut ParseState,
-    text: &BytesText<'_>,
-) -> Option<ParsedResource> {
-    // If we're inside a template, only accumulate text that's directly inside the template tag
-    if state.in_template {
-        if state.current_tag == "template" {
-            let trimmed = text_to_string(text).trim().to_string();
-            if !trimmed.is_empty() {
-                state.template_text.push_str(&trimmed);
-                state.template_text.push(' ');
-            }
+    tag: &str,
+) -> Result<Option<ParsedResource>, String> {
+    let Some(name) = state.current_name.clone() else {
+        return Ok(None);
+    };
+    let text = std::mem::take(&mut state.current_text);
+    let text = match state.current_value.take() {
+        Some(_) if !text.trim().is_empty() => {
+            return Err(format!(
+                "{tag} '{name}' has both a value attribute and text"
+            ));
         }
-        // Don't create resources from parameter tags inside templates (they're already handled in handle_start)
-        return None;
+        Some(value) => value,
+        None => text,
+    };
+    // Preserved strings and regexes keep their indentation and
+    // surrounding newlines
+    let trimmed = if state.current_preserve || text.trim() == text {
+        text
+    } else {
+        text.trim().to_string()
+    };
+    if trimmed.is_empty()
+        && !matches!(
+            tag,
+            "string" | "bool" | "latlng" | "position" | "concat"
+        )
+    {
+        return if matches!(
+            tag,
+            "color"
+                | "bytesize"
+                | "semver"
+                | "ipaddr"
+                | "socketaddr"
+                | "regex"
+                | "uuid"
+                | "datetime"
+                | "date"
+                | "money"
+                | "char"
+                | "bytes"
+                | "computed"
+        ) {
+            Err(format!("{tag} '{name}' has an empty value"))
+        } else {
+            Ok(None)
+        };
     }
-    
-    if let Some(name) = &state.current_name {
-        let trimmed = text_to_string(text).trim().to_string();
-        if trimmed.is_empty() {
-            return None;
+
+    let mut resource = match tag {
+        "latlng" | "position" => coordinates(state, tag, name)?,
+        "concat" => concat(state, name)?,
+        "string" => ParsedResource::string(name, trimmed),
+        "number" | "int" | "float" => ParsedResource::number(
+            name,
+            trimmed,
+            state.current_number_type.clone(),
+        ),
+        "bool" => {
+            let b = parse_bool(&trimmed).ok_or_else(|| {
+                format!(
+                    "bool '{name}' has invalid value '{trimmed}' (expected true/false, yes/no, on/off or 1/0)"
+                )
+            })?;
+            ParsedResource::bool(name, b)
         }
+        "computed" => ParsedResource {
+            name,
+            kind: ResourceKind::Computed,
+            value: ScalarValue::Computed {
+                expression: trimmed,
+                explicit_type: state.current_number_type.clone(),
+            },
+            meta: ResourceMeta::default(),
+        },
+        "bytesize" => ParsedResource {
+            name,
+            kind: ResourceKind::ByteSize,
+            value: ScalarValue::ByteSize(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "semver" => ParsedResource {
+            name,
+            kind: ResourceKind::Semver,
+            value: ScalarValue::Semver(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "ipaddr" => ParsedResource {
+            name,
+            kind: ResourceKind::IpAddr,
+            value: ScalarValue::IpAddr(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "socketaddr" => ParsedResource {
+            name,
+            kind: ResourceKind::SocketAddr,
+            value: ScalarValue::SocketAddr(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "datetime" => ParsedResource {
+            name,
+            kind: ResourceKind::DateTime,
+            value: ScalarValue::DateTime(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "date" => ParsedResource {
+            name,
+            kind: ResourceKind::Date,
+            value: ScalarValue::Date(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "money" => money(state, name, trimmed)?,
+        "bytes" => bytes(state, name, trimmed)?,
+        "char" => ParsedResource {
+            name,
+            kind: ResourceKind::Char,
+            value: ScalarValue::Char {
+                text: trimmed,
+                grapheme: std::mem::take(&mut state.current_grapheme),
+            },
+            meta: ResourceMeta::default(),
+        },
+        "uuid" => ParsedResource {
+            name,
+            kind: ResourceKind::Uuid,
+            value: ScalarValue::Uuid(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "regex" => ParsedResource {
+            name,
+            kind: ResourceKind::Regex,
+            value: ScalarValue::Regex(trimmed),
+            meta: ResourceMeta::default(),
+        },
+        "color" => ParsedResource {
+            name,
+            kind: crate::generator::parsing::ResourceKind::Color,
+            value: crate::generator::parsing::ScalarValue::Color(
+                trimmed,
+            ),
+            meta: ResourceMeta::default(),
+        },
+        _ => return Ok(None),
+    };
+    resource.meta = state.current_meta();
+    Ok(Some(resource))
+}
 
-        match state.current_tag.as_str() {
-            "string" => {
-                return Some(ParsedResource::string(name, trimmed))
-            }
-            "number" | "int" | "float" => {
-                return Some(ParsedResource::number(
-                    name,
-                    trimmed,
-                    state.current_number_type.clone(),
-                ));
-            }
-            "bool" => {
-                if let Ok(b) = trimmed.parse::<bool>() {
-                    return Some(ParsedResource::bool(name, b));
-                }
-            }
-            "color" => {
-                return Some(ParsedResource {
-                    name: name.clone(),
-                    kind:
-                        crate::generator::parsing::ResourceKind::Color,
-                    value:
-                        crate::generator::parsing::ScalarValue::Color(
-                            trimmed,
-                        ),
-                });
+/// `<concat>` from the parts collected since it opened
+fn concat(
+    state: &mut ParseState,
+    name: String,
+) -> Result<ParsedResource, String> {
+    let parts = std::mem::take(&mut state.concat_parts);
+    if std::mem::take(&mut state.concat_stray_text) {
+        return Err(format!(
+            "concat '{name}' has text outside its <ref> and <lit> parts"
+        ));
+    }
+    // A rejected part was reported, the rest is kept
+    if parts.is_empty() && !std::mem::take(&mut state.concat_rejected) {
+        return Err(format!("concat '{name}' has no parts"));
+    }
+    Ok(ParsedResource {
+        name,
+        kind: ResourceKind::Concat,
+        value: ScalarValue::Concat(parts),
+        meta: ResourceMeta::default(),
+    })
+}
+
+/// Closes a `<ref>` or `<lit>` of the current `<concat>`
+fn finish_concat_part(
+    state: &mut ParseState,
+    tag: &str,
+) -> Result<(), String> {
+    let text = std::mem::take(&mut state.current_text);
+    state.current_tag = "concat".to_string();
+    let part = if tag == "lit" {
+        ConcatPart::Lit(text)
+    } else {
+        let reference = text.trim();
+        match reference.strip_prefix("@string/") {
+            Some(path) if !path.is_empty() => {
+                ConcatPart::Ref(path.to_string())
             }
-            "template" => {
-                // Accumulate text for templates (may be called multiple times)
-                state.template_text.push_str(&trimmed);
-                state.template_text.push(' ');
+            _ => {
+                state.concat_rejected = true;
+                return Err(format!(
+                    "concat '{}' has invalid part <ref>{reference}</ref> (expected @string/name)",
+                    state.current_name.as_deref().unwrap_or_default()
+                ))
             }
-            _ => {}
         }
-    }
-    None
+    };
+    state.concat_parts.push(part);
+    Ok(())
+}
+
+/// `<money currency="" scale="">` from the attributes captured when
+/// the element opened; only `currency` is required
+fn money(
+    state: &mut ParseState,
+    name: String,
+    amount: String,
+) -> Result<ParsedResource, String> {
+    let [currency, scale] = std::mem::take(&mut state.current_money);
+    let currency = currency.ok_or_else(|| {
+        format!("money '{name}' is missing the 'currency' attribute")
+    })?;
+    Ok(ParsedResource {
+        name,
+        kind: ResourceKind::Money,
+        value: ScalarValue::Money {
+            amount,
+            currency,
+            scale,
+        },
+        meta: ResourceMeta::default(),
+    })
+}
+
+/// `<bytes encoding="" sha256="">` from the attributes captured when
+/// the element opened; only `encoding` is required, as hex digits are
+/// valid base64 too
+fn bytes(
+    state: &mut ParseState,
+    name: String,
+    text: String,
+) -> Result<ParsedResource, String> {
+    let [encoding, sha256] = std::mem::take(&mut state.current_bytes);
+    let encoding = encoding.ok_or_else(|| {
+        format!("bytes '{name}' is missing the 'encoding' attribute")
+    })?;
+    Ok(ParsedResource {
+        name,
+        kind: ResourceKind::Bytes,
+        value: ScalarValue::Bytes {
+            text,
+            encoding,
+            sha256,
+        },
+        meta: ResourceMeta::default(),
+    })
+}
+
+/// `<latlng lat="" lng=""/>` or `<position x="" y=""/>` from the
+/// attributes captured when the element opened
+fn coordinates(
+    state: &mut ParseState,
+    tag: &str,
+    name: String,
+) -> Result<ParsedResource, String> {
+    let (kind, attributes) = match tag {
+        "latlng" => (ResourceKind::LatLng, ["lat", "lng"]),
+        _ => (ResourceKind::Position, ["x", "y"]),
+    };
+    let [first, second] = std::mem::take(&mut state.current_coordinates);
+    let missing = |attribute: &str| {
+        format!("{tag} '{name}' is missing the '{attribute}' attribute")
+    };
+    let first = first.ok_or_else(|| missing(attributes[0]))?;
+    let second = second.ok_or_else(|| missing(attributes[1]))?;
+    let value = match kind {
+        ResourceKind::LatLng => ScalarValue::LatLng {
+            lat: first,
+            lng: second,
+        },
+        _ => ScalarValue::Position {
+            x: first,
+            y: second,
+        },
+    };
+    Ok(ParsedResource {
+        name,
+        kind,
+        value,
+        meta: ResourceMeta::default(),
+    })
 }
 
 pub(super) fn handle_end(
     state: &mut ParseState,
     e: &BytesEnd<'_>,
-) -> Option<ParsedResource> {
-    let tag = to_string(e.name().as_ref());
+) -> Result<Option<ParsedResource>, String> {
+    let tag = tag_name(e.name().into_inner());
+    let tag: &str = &tag;
+
+    if tag == "doc" && state.in_doc {
+        state.in_doc = false;
+        let text = std::mem::take(&mut state.current_text);
+        match doc_text(&text) {
+            Some(doc) if state.module_doc => state.push_module_doc(doc),
+            Some(doc) => state.push_doc(doc, false),
+            None => {}
+        }
+        return Ok(None);
+    }
+
+    if state.in_concat && matches!(tag, "ref" | "lit") {
+        finish_concat_part(state, tag)?;
+        return Ok(None);
+    }
 
     if tag == "ns" {
+        state.close_doc_level();
         state.namespace_stack.pop();
-        return None;
+        state.profile_stack.pop();
+        return Ok(None);
     }
 
     // Finalize template when closing tag is encountered
     if tag == "template" {
+        flush_template_segment(state);
         let name = state.current_name.clone();
         if let Some(name) = name {
-            let text = state.template_text.trim().to_string();
+            let text = trim_template(
+                &name,
+                &state.template_text,
+                state.template_trim.as_deref(),
+            );
             let params = state.template_params.clone();
+            let meta = state.current_meta();
             
-            // Reset template state
+            // Reset template state, even for a rejected template
             state.in_template = false;
             state.template_params.clear();
             state.template_text.clear();
             state.current_name = None;
+            state.current_profile = None;
             
-            return Some(ParsedResource {
+            let text = text?;
+            return Ok(Some(ParsedResource {
                 name,
                 kind: crate::generator::parsing::ResourceKind::Template,
                 value: crate::generator::parsing::ScalarValue::Template {
                     text,
                     params,
                 },
-            });
+                meta,
+            }));
         }
     }
     
     // When inside a template, don't process closing tags of parameter tags as resources
     // (they're already handled in handle_start)
-    if state.in_template {
-        if matches!(tag.as_str(), "string" | "number" | "int" | "float" | "bool" | "color") {
-            // These are template parameters, not resources - just clear current_name
-            state.current_name = None;
-            return None;
-        }
+    if state.in_template
+        && matches!(tag, "string" | "number" | "int" | "float" | "bool" | "color")
+    {
+        // These are template parameters, not resources - just clear current_name
+        state.current_name = None;
+        return Ok(None);
     }
 
+    let resource = finish_scalar(state, tag);
+
     if matches!(
-        tag.as_str(),
-        "string" | "number" | "int" | "float" | "bool" | "color" | "template"
+        tag,
+        "string"
+            | "number"
+            | "int"
+            | "float"
+            | "bool"
+            | "color"
+            | "template"
+            | "latlng"
+            | "position"
+            | "bytesize"
+            | "semver"
+            | "ipaddr"
+            | "socketaddr"
+            | "regex"
+            | "uuid"
+            | "datetime"
+            | "date"
+            | "money"
+            | "char"
+            | "bytes"
+            | "computed"
+            | "concat"
     ) {
         state.current_name = None;
+        state.current_profile = None;
+        state.current_preserve = false;
+        state.in_concat = false;
     }
-    if matches!(tag.as_str(), "number" | "int" | "float") {
+    if matches!(tag, "number" | "int" | "float" | "computed") {
         state.current_number_type = None;
+        state.current_number_range = [None, None];
+        state.current_widen = None;
     }
     state.current_tag.clear();
-    None
+    state.current_text.clear();
+    resource
+}
+
+/// Handles a self-closing tag: template parameters only start, other
+/// resources and namespaces also end right away
+pub(super) fn handle_empty(
+    state: &mut ParseState,
+    e: &BytesStart<'_>,
+) -> Result<Option<ParsedResource>, String> {
+    let param = state.in_template && e.name().as_ref() != b"template";
+    handle_start(state, e);
+    if param {
+        return Ok(None);
+    }
+    handle_end(state, &e.to_end())
 }
@@ -11,56 +11,108 @@ use crate::generator::input::RawResourceFile;
 
 use super::ast::ParsedResourceFile;
 use super::error::ParserError;
-use handlers::{handle_end, handle_start, handle_text};
+use handlers::{
+    handle_comment, handle_empty, handle_end, handle_start, handle_text,
+};
 use state::ParseState;
+use utils::{
+    check_attributes, decode_cdata, decode_text, reference_to_string,
+    to_string, LineCounter,
+};
 
 pub(super) fn parse_single_file(
     raw: &RawResourceFile,
 ) -> Result<ParsedResourceFile, ParserError> {
+    // Text is kept untrimmed so entities and CDATA join seamlessly;
+    // values are trimmed once the element closes
     let mut reader = Reader::from_str(&raw.contents);
-    reader.config_mut().trim_text(true);
 
     let mut buf = Vec::new();
     let mut state = ParseState::default();
     let mut resources = Vec::new();
+    // Rejected elements are skipped and reported with the file
+    let mut errors = Vec::new();
+    let mut lines = LineCounter::new(&raw.contents);
 
     loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(e)) => handle_start(&mut state, &e),
-            Ok(Event::Empty(e)) => {
-                // Handle self-closing tags like <param name="..." type="..."/>
+        // Entity and attribute errors point at the start of the event
+        let start = reader.buffer_position();
+        let event = reader.read_event_into(&mut buf);
+        let line = lines.line_at(start);
+        // Malformed markup leaves nothing reliable to read after it
+        let xml_error = |line: usize, position: u64, message: String| {
+            ParserError::Xml {
+                path: raw.path.clone(),
+                line: Some(line),
+                message: format!(
+                    "XML error at byte {position}: {message}"
+                ),
+            }
+        };
+        let event_error = |message| xml_error(line, start, message);
+        match event {
+            Ok(Event::Start(e)) => {
+                check_attributes(&e).map_err(event_error)?;
+                state.element_line = line;
                 handle_start(&mut state, &e);
             }
-            Ok(Event::Text(e)) => {
-                if let Some(res) = handle_text(&mut state, &e) {
-                    resources.push(res);
+            Ok(Event::Empty(e)) => {
+                check_attributes(&e).map_err(event_error)?;
+                state.element_line = line;
+                match handle_empty(&mut state, &e) {
+                    Ok(res) => resources.extend(res),
+                    Err(message) => {
+                        errors.push((state.error_line(), message));
+                    }
                 }
             }
-            Ok(Event::End(e)) => {
-                if let Some(res) = handle_end(&mut state, &e) {
-                    resources.push(res);
-                }
+            Ok(Event::Text(e)) => {
+                handle_text(&mut state, &decode_text(&e));
+            }
+            Ok(Event::CData(e)) => {
+                handle_text(&mut state, &decode_cdata(&e));
+            }
+            Ok(Event::Comment(e)) => {
+                state.element_line = line;
+                handle_comment(&mut state, &to_string(&e));
             }
+            Ok(Event::GeneralRef(e)) => match reference_to_string(&e) {
+                Ok(text) => handle_text(&mut state, &text),
+                Err(message) => errors.push((
+                    line,
+                    format!("XML error at byte {start}: {message}"),
+                )),
+            },
+            Ok(Event::End(e)) => match handle_end(&mut state, &e) {
+                Ok(res) => resources.extend(res),
+                Err(message) => {
+                    errors.push((state.error_line(), message));
+                }
+            },
             Ok(Event::Eof) => break,
             Err(err) => {
-                return Err(ParserError::Xml {
-                    path: raw.path.clone(),
-                    message: format!(
-                        "XML error at byte {}: {err}",
-                        reader.buffer_position()
-                    ),
-                });
+                let position = reader.buffer_position();
+                let line = lines.line_at(position);
+                return Err(xml_error(line, position, err.to_string()));
             }
             _ => {}
         }
         buf.clear();
     }
+    while !state.doc_levels.is_empty() {
+        state.close_doc_level();
+    }
 
-    Ok(ParsedResourceFile::new(
-        raw.path.clone(),
-        raw.is_test,
-        resources,
-    ))
+    let mut file =
+        ParsedResourceFile::new(raw.path.clone(), raw.is_test, resources)
+            .with_locale(raw.locale.clone());
+    file.namespace_docs = state.namespace_docs;
+    file.config_namespaces = state.config_namespaces;
+    file.namespace_lines = state.namespace_lines;
+    file.unused_docs = state.unused_docs;
+    file.ns_from_file = state.ns_from_file;
+    file.errors = errors;
+    Ok(file)
 }
 
 #[cfg(test)]
@@ -68,9 +120,19 @@ mod tests {
     use std::path::PathBuf;
 
     use crate::generator::input::RawResourceFile;
+    use crate::generator::parsing::{ConcatPart, ScalarValue};
 
     use super::parse_single_file;
 
+    /// First error of `raw`, as reported for the build
+    fn parse_error(raw: &RawResourceFile) -> String {
+        let (_, errors) =
+            crate::generator::parsing::parse_raw_files(
+                std::slice::from_ref(raw),
+            );
+        errors.first().expect("an error").to_string()
+    }
+
     #[test]
     fn parse_basic_string() {
         let raw = RawResourceFile::new(
@@ -307,4 +369,1405 @@ mod tests {
             panic!("Expected Template value");
         }
     }
+
+    #[test]
+    fn template_params_are_not_namespaced() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <ns name="mail">
+        <template name="subject"><string name="user"/>Re: {user}</template>
+    </ns>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(file.resources[0].name, "mail/subject");
+        let crate::generator::parsing::ScalarValue::Template {
+            params,
+            ..
+        } = &file.resources[0].value
+        else {
+            panic!("Expected Template value");
+        };
+        assert_eq!(params[0].name, "user");
+    }
+
+    #[test]
+    fn parse_records_profile_chain() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"
+<resources>
+    <string name="api_url" profile="default">http://localhost</string>
+    <ns name="debug_tools" profile="debug">
+        <bool name="enabled">true</bool>
+        <color name="accent" profile="default">#FF0000</color>
+    </ns>
+    <template name="greeting" profile="staging">
+        <string name="name"/>
+        Hi {name}
+    </template>
+    <number name="retries">3</number>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let profiles = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .meta
+                .profiles
+                .clone()
+        };
+
+        assert_eq!(profiles("api_url"), vec!["default"]);
+        assert_eq!(profiles("debug_tools/enabled"), vec!["debug"]);
+        assert_eq!(
+            profiles("debug_tools/accent"),
+            vec!["debug", "default"]
+        );
+        assert_eq!(profiles("greeting"), vec!["staging"]);
+        assert!(profiles("retries").is_empty());
+    }
+
+    #[test]
+    fn parse_preserves_entities_cdata_and_emoji() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<resources>
+    <!-- comment is ignored -->
+    <string name="less_than" profile="debug">a &lt; b &amp;&amp; c &#233;</string>
+    <string name="markup"><![CDATA[<b>bold</b> & co]]></string>
+    <string name="party" profile="release">Hi 🎉  there</string>
+</resources>
+"#
+            .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let text = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .value
+                .as_text()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(file.resources.len(), 3);
+        assert_eq!(text("less_than"), "a < b && c \u{e9}");
+        assert_eq!(text("markup"), "<b>bold</b> & co");
+        assert_eq!(text("party"), "Hi 🎉  there");
+    }
+
+    #[test]
+    fn parse_preserved_whitespace() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            "<resources>\n\
+             <string name=\"banner\" xml:space=\"preserve\">\n  /\\_/\\\n ( o.o )\n</string>\n\
+             <string name=\"help\" preserve=\"true\">  usage:\n    app &lt;file&gt;</string>\n\
+             <string name=\"trimmed\">\n  a\n  b\n</string>\n\
+             </resources>"
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let text = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .value
+                .as_text()
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(text("banner"), "\n  /\\_/\\\n ( o.o )\n");
+        assert_eq!(text("help"), "  usage:\n    app <file>");
+        // Without the attribute only the ends are trimmed
+        assert_eq!(text("trimmed"), "a\n  b");
+    }
+
+    #[test]
+    fn parse_cdata_markup_json_and_templates() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="eula"><![CDATA[<p><b>Terms</b> &amp; conditions</p>]]></string>
+    <string name="config"><![CDATA[{"theme": "dark", "tags": ["a", "b"]}]]></string>
+    <string name="mixed">Before <![CDATA[<i>inside</i>]]> &lt;after&gt;</string>
+    <template name="greeting">
+        <string name="name"/>
+        <![CDATA[<h1>Hello {name} & welcome</h1>]]>
+    </template>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let text = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .unwrap()
+                .value
+                .as_text()
+                .unwrap()
+                .to_string()
+        };
+
+        // CDATA is literal: `&amp;` is not unescaped
+        assert_eq!(text("eula"), "<p><b>Terms</b> &amp; conditions</p>");
+        assert_eq!(
+            text("config"),
+            r#"{"theme": "dark", "tags": ["a", "b"]}"#
+        );
+        assert_eq!(text("mixed"), "Before <i>inside</i> <after>");
+        assert_eq!(text("greeting"), "<h1>Hello {name} & welcome</h1>");
+    }
+
+    #[test]
+    fn parse_special_characters_in_text_and_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="company">AT&amp;T</string>
+    <string name="specials">&amp; &lt; &gt; &quot; &apos; &#169; &#x1F600;</string>
+    <string name="old" deprecated="use &quot;new&quot; &lt;here&gt;">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let find = |name: &str| {
+            file.resources.iter().find(|r| r.name == name).unwrap()
+        };
+        assert_eq!(find("company").value.as_text(), Some("AT&T"));
+        assert_eq!(
+            find("specials").value.as_text(),
+            Some("& < > \" ' \u{a9} \u{1f600}")
+        );
+        assert_eq!(
+            find("old").meta.deprecated.as_deref(),
+            Some(r#"use "new" <here>"#)
+        );
+    }
+
+    #[test]
+    fn unknown_entities_are_errors() {
+        let parse = |xml: &str| {
+            parse_error(&RawResourceFile::new(
+                PathBuf::from("values.xml"),
+                xml.into(),
+                false,
+            ))
+        };
+
+        let text = parse(
+            r#"<resources><string name="a">&nbsp;x</string></resources>"#,
+        );
+        assert_eq!(
+            text,
+            "values.xml:1: XML error at byte 28: unknown entity `&nbsp;`"
+        );
+
+        let attribute = parse(
+            r#"<resources><string name="a" deprecated="&bogus;">x</string></resources>"#,
+        );
+        assert!(attribute.contains("attribute `deprecated`"), "{attribute}");
+
+        let char_ref = parse(
+            r#"<resources><string name="a">&#xD800;</string></resources>"#,
+        );
+        assert!(
+            char_ref.contains("invalid character reference `&#xD800;`"),
+            "{char_ref}"
+        );
+    }
+
+    #[test]
+    fn parse_empty_and_self_closing_strings() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="optional_suffix"></string>
+    <string name="placeholder"/>
+    <string name="blank">   </string>
+    <ns name="empty"/>
+    <string name="after_ns">x</string>
+    <template name="t"><string name="p"/>{p}</template>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let names: Vec<(&str, Option<&str>)> = file
+            .resources
+            .iter()
+            .map(|r| (r.name.as_str(), r.value.as_text()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("optional_suffix", Some("")),
+                ("placeholder", Some("")),
+                ("blank", Some("")),
+                // The self-closing namespace is closed right away
+                ("after_ns", Some("x")),
+                ("t", Some("{p}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_colors_are_errors() {
+        for xml in [
+            r#"<resources><color name="accent"></color></resources>"#,
+            r#"<resources><color name="accent"/></resources>"#,
+        ] {
+            let err = parse_error(&RawResourceFile::new(
+                PathBuf::from("values.xml"),
+                xml.into(),
+                false,
+            ));
+            assert!(
+                err.ends_with("color 'accent' has an empty value"),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn split_text_events_form_one_resource() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="show">Tom &amp; Jerry</string>
+    <string name="commented">Hello <!-- not part of it --> world</string>
+    <string name="mixed"><![CDATA[<b>]]>bold<![CDATA[</b>]]> text</string>
+    <number name="limit"> 1<!-- c -->0 </number>
+    <template name="greet">
+        <string name="name"/>
+        Hi &lt;{name}&gt; <!-- c --> again
+    </template>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let values: Vec<(&str, String)> = file
+            .resources
+            .iter()
+            .map(|r| (r.name.as_str(), format!("{:?}", r.value)))
+            .collect();
+        assert_eq!(values.len(), 5, "{values:?}");
+
+        let text = |name: &str| {
+            file.resources
+                .iter()
+                .find(|r| r.name == name)
+                .and_then(|r| r.value.as_text())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(text("show"), "Tom & Jerry");
+        assert_eq!(text("commented"), "Hello  world");
+        assert_eq!(text("mixed"), "<b>bold</b> text");
+        assert_eq!(text("greet"), "Hi <{name}>  again");
+        assert!(values.contains(&(
+            "limit",
+            format!(
+                "{:?}",
+                crate::generator::parsing::ScalarValue::Number {
+                    value: "10".to_string(),
+                    explicit_type: None,
+                }
+            )
+        )));
+    }
+
+    #[test]
+    fn parse_lenient_bools() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <bool name="enabled">True</bool>
+    <bool name="telemetry">yes</bool>
+    <bool name="sound"> OFF </bool>
+    <bool name="beta">0</bool>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let bools: Vec<(&str, String)> = file
+            .resources
+            .iter()
+            .map(|r| (r.name.as_str(), format!("{:?}", r.value)))
+            .collect();
+        assert_eq!(
+            bools,
+            vec![
+                ("enabled", "Bool(true)".to_string()),
+                ("telemetry", "Bool(true)".to_string()),
+                ("sound", "Bool(false)".to_string()),
+                ("beta", "Bool(false)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_bools_are_errors() {
+        let err = parse_error(&RawResourceFile::new(
+            PathBuf::from("res/flags.xml"),
+            r#"<resources><bool name="dark_mode">maybe</bool></resources>"#
+                .into(),
+            false,
+        ));
+        assert!(err.starts_with("res/flags.xml:1: "), "{err}");
+        assert!(
+            err.ends_with("bool 'dark_mode' has invalid value 'maybe' (expected true/false, yes/no, on/off or 1/0)"),
+            "{err}"
+        );
+    }
+
+    fn parse_docs(xml: &str) -> crate::generator::parsing::ParsedResourceFile {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml.into(),
+            false,
+        );
+        parse_single_file(&raw).unwrap()
+    }
+
+    #[test]
+    fn docs_attach_to_the_next_sibling_in_a_namespace() {
+        let file = parse_docs(
+            r#"<resources>
+    <ns name="auth">
+        <string name="intro">Hi</string>
+        <doc>
+            Shown on the login screen.
+        </doc>
+        <template name="welcome"><string name="user"/>Hello {user}</template>
+        <string name="after">x</string>
+    </ns>
+</resources>"#,
+        );
+        let docs: Vec<_> = file
+            .resources
+            .iter()
+            .map(|r| (r.name.as_str(), r.meta.doc.as_deref()))
+            .collect();
+        assert_eq!(
+            docs,
+            vec![
+                ("auth/intro", None),
+                ("auth/welcome", Some("Shown on the login screen.")),
+                ("auth/after", None),
+            ]
+        );
+        assert!(file.namespace_docs.is_empty());
+    }
+
+    #[test]
+    fn docs_before_or_at_the_top_of_a_namespace_document_the_module() {
+        let file = parse_docs(
+            r#"<resources>
+    <doc>Authentication screens.</doc>
+    <ns name="auth">
+        <doc>Shared by the login and signup flows.</doc>
+        <doc>Title of the login screen.</doc>
+        <string name="title">Login</string>
+        <ns name="errors">
+            <doc>Error messages.</doc>
+            <string name="denied">Denied</string>
+        </ns>
+    </ns>
+</resources>"#,
+        );
+        assert_eq!(
+            file.namespace_docs,
+            vec![
+                (
+                    vec!["auth".to_string(), "errors".to_string()],
+                    "Error messages.".to_string()
+                ),
+                (
+                    vec!["auth".to_string()],
+                    "Authentication screens.\n\nShared by the login and signup flows."
+                        .to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            file.resources[0].meta.doc.as_deref(),
+            Some("Title of the login screen.")
+        );
+        assert_eq!(file.resources[1].meta.doc, None);
+    }
+
+    #[test]
+    fn struct_namespaces_are_recorded_with_their_path() {
+        let file = parse_docs(
+            r#"<resources>
+    <ns name="net" struct="true">
+        <ns name="http" struct="true">
+            <number name="timeout_ms">500</number>
+        </ns>
+        <ns name="dns" struct="false">
+            <string name="host">1.1.1.1</string>
+        </ns>
+    </ns>
+</resources>"#,
+        );
+        assert_eq!(
+            file.config_namespaces,
+            vec![
+                vec!["net".to_string()],
+                vec!["net".to_string(), "http".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn consecutive_docs_are_joined_as_paragraphs() {
+        let file = parse_docs(
+            r#"<resources>
+    <doc>First line
+         second line</doc>
+    <doc>Another paragraph.</doc>
+    <color name="accent">#FF5722</color>
+</resources>"#,
+        );
+        assert_eq!(
+            file.resources[0].meta.doc.as_deref(),
+            Some("First line\nsecond line\n\nAnother paragraph.")
+        );
+        assert!(file.unused_docs.is_empty());
+    }
+
+    #[test]
+    fn docs_without_a_following_element_are_reported() {
+        let file = parse_docs(
+            r#"<resources>
+    <ns name="ui">
+        <string name="a">A</string>
+        <doc>Nothing follows me here.</doc>
+    </ns>
+    <doc>Nor here.</doc>
+</resources>"#,
+        );
+        assert_eq!(file.unused_docs, vec![4, 6]);
+        assert_eq!(file.resources[0].meta.doc, None);
+    }
+
+    #[test]
+    fn marker_comments_are_docs() {
+        let file = parse_docs(
+            r#"<resources>
+    <!-- Plain comments are ignored -->
+    <!--- Retries before giving up. -->
+    <number name="max_retries">3</number>
+    <!-- doc: Base of every endpoint.
+              Without a trailing slash. -->
+    <string name="base_url">https://example.com</string>
+    <!-- /// Accent of the buttons,
+         /// also used for links. -->
+    <color name="accent">#FF5722</color>
+    <!-- - not a doc either -->
+    <string name="plain">x<!--- inside a value --></string>
+</resources>"#,
+        );
+        let docs: Vec<_> = file
+            .resources
+            .iter()
+            .map(|r| r.meta.doc.as_deref())
+            .collect();
+        assert_eq!(
+            docs,
+            vec![
+                Some("Retries before giving up."),
+                Some("Base of every endpoint.\nWithout a trailing slash."),
+                Some("Accent of the buttons,\nalso used for links."),
+                None,
+            ]
+        );
+        assert!(file.unused_docs.is_empty());
+    }
+
+    #[test]
+    fn marker_comments_come_before_doc_elements() {
+        let file = parse_docs(
+            r#"<resources>
+    <ns name="auth">
+        <!--- Authentication screens. -->
+        <doc>Title of the login screen.</doc>
+        <!--- Shown in the app bar. -->
+        <string name="title">Login</string>
+    </ns>
+    <!--- Nothing follows me. -->
+</resources>"#,
+        );
+        assert_eq!(
+            file.namespace_docs,
+            vec![(
+                vec!["auth".to_string()],
+                "Authentication screens.".to_string()
+            )]
+        );
+        assert_eq!(
+            file.resources[0].meta.doc.as_deref(),
+            Some("Shown in the app bar.\n\nTitle of the login screen.")
+        );
+        assert_eq!(file.unused_docs, vec![8]);
+    }
+
+    #[test]
+    fn module_docs_document_their_container() {
+        let file = parse_docs(
+            r#"<resources>
+    <doc module="true">Resources of the demo app.</doc>
+    <doc>Name of the app.</doc>
+    <string name="app_name">Demo</string>
+    <ns name="auth">
+        <string name="title">Login</string>
+        <!-- //! Authentication screens,
+             //! login and signup. -->
+    </ns>
+    <ns><!-- //! Nameless namespaces have no module. --></ns>
+</resources>"#,
+        );
+        assert_eq!(
+            file.namespace_docs,
+            vec![
+                (
+                    vec!["auth".to_string()],
+                    "Authentication screens,\nlogin and signup.".to_string()
+                ),
+                (Vec::new(), "Resources of the demo app.".to_string()),
+            ]
+        );
+        assert_eq!(
+            file.resources[0].meta.doc.as_deref(),
+            Some("Name of the app.")
+        );
+        assert!(file.unused_docs.is_empty());
+    }
+
+    fn template_text(xml: &str) -> String {
+        let file = parse_docs(xml);
+        file.resources[0].value.as_text().unwrap().to_string()
+    }
+
+    #[test]
+    fn multi_line_templates_keep_their_layout() {
+        let text = template_text(
+            r#"<resources>
+    <template name="inbox">
+        <string name="name"/>
+        <number name="count"/>
+        Hello {name},
+          You have {count} new messages.
+    </template>
+</resources>"#,
+        );
+        assert_eq!(text, "Hello {name},\n  You have {count} new messages.");
+    }
+
+    #[test]
+    fn template_spaces_around_placeholders_are_kept() {
+        let text = template_text(
+            r#"<resources><template name="total">Total:  <number name="n"/>{n} items</template></resources>"#,
+        );
+        assert_eq!(text, "Total:  {n} items");
+        let text = template_text(
+            r#"<resources><template name="glued">Hello<string name="who"/>{who}</template></resources>"#,
+        );
+        assert_eq!(text, "Hello{who}");
+    }
+
+    #[test]
+    fn template_trim_modes() {
+        let xml = |mode: &str| {
+            format!(
+                "<resources><template name=\"t\" trim=\"{mode}\">\n  <string name=\"x\"/>\n  a  {{x}}\n    b\n</template></resources>"
+            )
+        };
+        assert_eq!(template_text(&xml("indent")), "a  {x}\n  b");
+        assert_eq!(template_text(&xml("all")), "a {x} b");
+        assert_eq!(template_text(&xml("none")), "\n  a  {x}\n    b\n");
+
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            xml("some"),
+            false,
+        );
+        let err = parse_error(&raw);
+        assert!(err.contains(
+            "template 't' has invalid trim 'some' (expected none, indent or all)"
+        ));
+    }
+
+    #[test]
+    fn parse_latlng_and_position_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="map">
+        <latlng name="hq" lat="48.8566" lng="2.3522"/>
+        <position name="origin" x="0" y="-12.5"></position>
+    </ns>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(file.resources.len(), 2);
+        assert_eq!(file.resources[0].name, "map/hq");
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::LatLng {
+                lat: "48.8566".into(),
+                lng: "2.3522".into(),
+            }
+        );
+        assert_eq!(file.resources[0].meta.line, Some(3));
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Position {
+                x: "0".into(),
+                y: "-12.5".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn latlng_without_coordinates_is_an_error() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><latlng name="hq" lat="1"/></resources>"#
+                .into(),
+            false,
+        );
+
+        let err = parse_error(&raw);
+        assert!(err.contains("latlng 'hq' is missing the 'lng' attribute"));
+    }
+
+    #[test]
+    fn parse_address_texts() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ipaddr name="dns">::1</ipaddr>
+    <socketaddr name="bind" value="[::1]:8080"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::IpAddr("::1".into())
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::SocketAddr("[::1]:8080".into())
+        );
+    }
+
+    #[test]
+    fn parse_uuid_text_and_value() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <uuid name="a">
+        6ba7b810-9dad-11d1-80b4-00c04fd430c8
+    </uuid>
+    <uuid name="b" value="00000000-0000-0000-0000-000000000000"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Uuid("6ba7b810-9dad-11d1-80b4-00c04fd430c8".into())
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Uuid("00000000-0000-0000-0000-000000000000".into())
+        );
+    }
+
+    #[test]
+    fn parse_datetime_and_date_text() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <datetime name="a"> 2025-12-31T23:59:59Z </datetime>
+    <date name="b" value="2025-06-01"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::DateTime("2025-12-31T23:59:59Z".into())
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Date("2025-06-01".into())
+        );
+    }
+
+    #[test]
+    fn parse_money_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <money name="a" currency="USD" scale="2"> 9.99 </money>
+    <money name="b" currency="EUR" value="5"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Money {
+                amount: "9.99".into(),
+                currency: "USD".into(),
+                scale: Some("2".into()),
+            }
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Money {
+                amount: "5".into(),
+                currency: "EUR".into(),
+                scale: None,
+            }
+        );
+
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <money name="a">9.99</money>
+</resources>"#
+                .into(),
+            false,
+        );
+        assert_eq!(
+            parse_error(&raw),
+            "values.xml:2: money 'a' is missing the 'currency' attribute"
+        );
+    }
+
+    #[test]
+    fn parse_char_text_and_grapheme() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <char name="a"> \u{F002} </char>
+    <char name="b" grapheme="true">❤️</char>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Char {
+                text: "\\u{F002}".into(),
+                grapheme: false,
+            }
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Char {
+                text: "❤️".into(),
+                grapheme: true,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_bytes_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <bytes name="a" encoding="base64" sha256="ABC">
+        TWFu
+        TWFu
+    </bytes>
+    <bytes name="b" encoding="hex" value="00ff"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Bytes {
+                text: "TWFu\n        TWFu".into(),
+                encoding: "base64".into(),
+                sha256: Some("ABC".into()),
+            }
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Bytes {
+                text: "00ff".into(),
+                encoding: "hex".into(),
+                sha256: None,
+            }
+        );
+
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <bytes name="a">00ff</bytes>
+</resources>"#
+                .into(),
+            false,
+        );
+        assert_eq!(
+            parse_error(&raw),
+            "values.xml:2: bytes 'a' is missing the 'encoding' attribute"
+        );
+    }
+
+    #[test]
+    fn parse_regex_text_verbatim() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <regex name="quoted"> "[^"&amp;]*" </regex>
+    <regex name="digits"><![CDATA[^\d+&amp;$]]></regex>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Regex(r#" "[^"&]*" "#.into())
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Regex(r"^\d+&amp;$".into())
+        );
+
+        let empty = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><regex name="x"></regex></resources>"#.into(),
+            false,
+        );
+        assert!(parse_error(&empty)
+            .contains("regex 'x' has an empty value"));
+    }
+
+    #[test]
+    fn parse_semver_text() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <semver name="app_version">
+        1.10.0-rc.1
+    </semver>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Semver("1.10.0-rc.1".into())
+        );
+    }
+
+    #[test]
+    fn parse_bytesize_text_and_value() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <bytesize name="max_upload"> 25MiB </bytesize>
+    <bytesize name="chunk" value="64KiB"/>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::ByteSize("25MiB".into())
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::ByteSize("64KiB".into())
+        );
+
+        let empty = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><bytesize name="x"></bytesize></resources>"#
+                .into(),
+            false,
+        );
+        assert!(parse_error(&empty)
+            .contains("bytesize 'x' has an empty value"));
+    }
+
+    #[test]
+    fn parse_template_text_with_entities() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <template name="compare">
+        <number name="a"/>
+        <number name="b"/>
+        {a} &lt; {b}
+    </template>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(file.resources.len(), 1);
+        assert_eq!(
+            file.resources[0].value.as_text(),
+            Some("{a} < {b}")
+        );
+    }
+
+    #[test]
+    fn parse_translatable_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="brand" translatable="false">Acme</string>
+    <string name="title">Title</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert!(!file.resources[0].meta.translatable);
+        assert!(file.resources[1].meta.translatable);
+    }
+
+    #[test]
+    fn parse_keep_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="dynamic" keep="true">Looked up</string>
+    <number name="retries">3</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert!(file.resources[0].meta.keep);
+        assert!(!file.resources[1].meta.keep);
+    }
+
+    #[test]
+    fn parse_overridable_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <number name="retries" overridable="true">3</number>
+    <string name="title">Title</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert!(file.resources[0].meta.overridable);
+        assert!(!file.resources[1].meta.overridable);
+    }
+
+    #[test]
+    fn parse_deprecated_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="old_api_url" deprecated="Use api_base instead">http://old</string>
+    <template name="greet" deprecated="Use welcome">
+        <string name="name"/>
+        Hi {name}
+    </template>
+    <number name="retries">3</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].meta.deprecated.as_deref(),
+            Some("Use api_base instead")
+        );
+        assert_eq!(
+            file.resources[1].meta.deprecated.as_deref(),
+            Some("Use welcome")
+        );
+        assert_eq!(file.resources[2].meta.deprecated, None);
+    }
+
+    #[test]
+    fn parse_cfg_attribute() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="driver" cfg='all(unix, not(target_env = "musl"))'>alsa</string>
+    <number name="retries" cfg="feature = &quot;net&quot;">3</number>
+    <string name="title">Player</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].meta.cfg.as_deref(),
+            Some(r#"all(unix, not(target_env = "musl"))"#)
+        );
+        assert_eq!(
+            file.resources[1].meta.cfg.as_deref(),
+            Some(r#"feature = "net""#)
+        );
+        assert_eq!(file.resources[2].meta.cfg, None);
+    }
+
+    #[test]
+    fn parse_number_bounds() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <number name="pool_size" type="u32" min="1" max="0x200">64</number>
+    <float name="ratio" min="0.0">0.5</float>
+    <string name="label" min="1">x</string>
+    <number name="retries">3</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let bounds: Vec<_> = file
+            .resources
+            .iter()
+            .map(|r| (r.meta.min.as_deref(), r.meta.max.as_deref()))
+            .collect();
+        assert_eq!(
+            bounds,
+            vec![
+                (Some("1"), Some("0x200")),
+                (Some("0.0"), None),
+                (None, None),
+                (None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_string_patterns() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="default_region" pattern="[a-z]{2}-[a-z]+-\d">eu-west-1</string>
+    <number name="retries" pattern="\d">3</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].meta.pattern.as_deref(),
+            Some(r"[a-z]{2}-[a-z]+-\d")
+        );
+        assert_eq!(file.resources[1].meta.pattern, None);
+    }
+
+    #[test]
+    fn parse_assert_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <number name="cache_size" type="u32" assert="gt:0">256</number>
+    <string name="prefix" assert="len_le:8">sk_</string>
+    <string name="plain">x</string>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let asserts: Vec<Option<&str>> = file
+            .resources
+            .iter()
+            .map(|r| r.meta.assert.as_deref())
+            .collect();
+        assert_eq!(asserts, [Some("gt:0"), Some("len_le:8"), None]);
+    }
+
+    #[test]
+    fn parse_computed_numbers() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <computed name="retry_budget_ms" type="i64">
+        @number/timeout_ms * 3 + 500
+    </computed>
+    <computed name="half">@number/retries / 2</computed>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].kind,
+            crate::generator::parsing::ResourceKind::Computed
+        );
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Computed {
+                expression: "@number/timeout_ms * 3 + 500".into(),
+                explicit_type: Some("i64".into()),
+            }
+        );
+        assert_eq!(
+            file.resources[1].value,
+            ScalarValue::Computed {
+                expression: "@number/retries / 2".into(),
+                explicit_type: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_string_transforms() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <string name="app_slug" transform="trim,slug">@string/app_name</string>
+    <number name="retries" transform="upper">3</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(
+            file.resources[0].meta.transform.as_deref(),
+            Some("trim,slug")
+        );
+        assert_eq!(file.resources[1].meta.transform, None);
+    }
+
+    #[test]
+    fn parse_concat_parts() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <ns name="api">
+        <concat name="users_url">
+            <ref>@string/base_url</ref>
+            <lit>/v2/users?lang=</lit><ref> @string/i18n/lang </ref>
+            <lit/>
+        </concat>
+    </ns>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        assert_eq!(file.resources.len(), 1);
+        assert_eq!(file.resources[0].name, "api/users_url");
+        assert_eq!(
+            file.resources[0].value,
+            ScalarValue::Concat(vec![
+                ConcatPart::Ref("base_url".into()),
+                ConcatPart::Lit("/v2/users?lang=".into()),
+                ConcatPart::Ref("i18n/lang".into()),
+                ConcatPart::Lit(String::new()),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejected_elements_do_not_stop_the_file() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+                <color name="accent"></color>
+                <string name="title">Hello</string>
+                <bool name="dark" value="maybe"/>
+                <concat name="u"><ref>@number/port</ref></concat>
+                <number name="retries">3</number>
+            </resources>"#
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let names: Vec<&str> =
+            file.resources.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["title", "u", "retries"]);
+        let lines: Vec<usize> =
+            file.errors.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, [2, 4, 5]);
+    }
+
+    #[test]
+    fn parse_concat_errors() {
+        let error = |body: &str| {
+            let raw = RawResourceFile::new(
+                PathBuf::from("values.xml"),
+                format!("<resources>{body}</resources>"),
+                false,
+            );
+            parse_error(&raw)
+        };
+
+        assert!(error(
+            r#"<concat name="u"><ref>@number/port</ref></concat>"#
+        )
+        .contains(
+            "concat 'u' has invalid part <ref>@number/port</ref> (expected @string/name)"
+        ));
+        assert!(error(
+            r#"<concat name="u">https://<ref>@string/host</ref></concat>"#
+        )
+        .contains("concat 'u' has text outside its <ref> and <lit> parts"));
+        assert!(error(r#"<concat name="u"/>"#)
+            .contains("concat 'u' has no parts"));
+    }
+
+    #[test]
+    fn parse_value_attributes() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r##"<resources>
+    <string name="company" value="AT&amp;T"/>
+    <string name="welcome" value="Welcome to @string/app_name"/>
+    <number name="max_retries" type="u32" value="3"/>
+    <int name="mask" value="0xFF"></int>
+    <float name="ratio" value="0.5"/>
+    <bool name="debug" value="on"/>
+    <color name="accent" value="#FF5722"/>
+</resources>"##
+                .into(),
+            false,
+        );
+
+        let file = parse_single_file(&raw).unwrap();
+        let values: Vec<_> = file
+            .resources
+            .iter()
+            .map(|r| (r.name.as_str(), r.value.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                ("company", ScalarValue::Text("AT&T".into())),
+                (
+                    "welcome",
+                    ScalarValue::Text("Welcome to @string/app_name".into())
+                ),
+                (
+                    "max_retries",
+                    ScalarValue::Number {
+                        value: "3".into(),
+                        explicit_type: Some("u32".into()),
+                    }
+                ),
+                (
+                    "mask",
+                    ScalarValue::Number {
+                        value: "0xFF".into(),
+                        explicit_type: None,
+                    }
+                ),
+                (
+                    "ratio",
+                    ScalarValue::Number {
+                        value: "0.5".into(),
+                        explicit_type: None,
+                    }
+                ),
+                ("debug", ScalarValue::Bool(true)),
+                ("accent", ScalarValue::Color("#FF5722".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_attribute_and_text_are_exclusive() {
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources>
+    <number name="retries" value="3">4</number>
+</resources>"#
+                .into(),
+            false,
+        );
+
+        let err = parse_error(&raw);
+        assert!(err
+            .contains("number 'retries' has both a value attribute and text"));
+
+        // Invalid values are reported like invalid text
+        let raw = RawResourceFile::new(
+            PathBuf::from("values.xml"),
+            r#"<resources><bool name="b" value="maybe"/></resources>"#
+                .into(),
+            false,
+        );
+        let err = parse_error(&raw);
+        assert!(err.contains("bool 'b' has invalid value 'maybe'"));
+    }
 }
@@ -1,10 +1,161 @@
+use crate::generator::parsing::ast::ResourceMeta;
+
+/// `<doc>` bookkeeping for one open `<resources>` or `<ns>` element
+#[derive(Default)]
+pub(super) struct DocLevel {
+    pub(super) namespace: Option<Vec<String>>, // Path of the `<ns>`, `None` at the root
+    pub(super) module_doc: Vec<String>, // Docs before the `<ns>` or before its first element
+    pub(super) pending: Vec<String>, // Docs waiting for the next sibling element
+    pub(super) pending_comments: Vec<String>, // Marker comments waiting for the next sibling element
+    pub(super) pending_line: Option<usize>, // Line of the first pending doc
+    pub(super) seen_element: bool, // Whether an element or the module doc followed the opening tag
+}
+
 #[derive(Default)]
 pub(super) struct ParseState {
     pub(super) current_tag: String,
     pub(super) current_name: Option<String>,
     pub(super) namespace_stack: Vec<String>,
     pub(super) current_number_type: Option<String>, // For <number type="...">
+    pub(super) current_number_range: [Option<String>; 2], // `min` and `max` of <number>
+    pub(super) current_widen: Option<String>, // `widen="..."` on the current <number>
+    pub(super) current_coordinates: [Option<String>; 2], // `lat`/`lng` or `x`/`y` of <latlng> and <position>
+    pub(super) current_money: [Option<String>; 2], // `currency` and `scale` of <money>
+    pub(super) current_grapheme: bool, // `grapheme="true"` on the current <char>
+    pub(super) current_bytes: [Option<String>; 2], // `encoding` and `sha256` of <bytes>
     pub(super) template_params: Vec<crate::generator::parsing::ast::TemplateParam>, // For <template><param>
     pub(super) template_text: String, // Accumulated text for templates
     pub(super) in_template: bool, // Track if we're inside a <template> tag
+    pub(super) template_trim: Option<String>, // `trim` attribute of the current template
+    pub(super) after_template_param: bool, // Text that follows is on the line of a parameter
+    pub(super) in_concat: bool, // Inside a <concat> element
+    pub(super) concat_parts: Vec<crate::generator::parsing::ast::ConcatPart>, // <ref> and <lit> of the current <concat>
+    pub(super) concat_stray_text: bool, // Non-blank text outside the parts of the current <concat>
+    pub(super) concat_rejected: bool, // A part of the current <concat> was already reported
+    pub(super) profile_stack: Vec<Option<String>>, // `profile` of each open <ns>
+    pub(super) current_profile: Option<String>, // `profile` of the current resource
+    pub(super) current_text: String, // Unescaped text of the current element
+    pub(super) current_value: Option<String>, // `value="..."` short form of the current resource
+    pub(super) current_untranslatable: bool, // `translatable="false"` on the current resource
+    pub(super) current_deprecated: Option<String>, // `deprecated="..."` on the current resource
+    pub(super) current_cfg: Option<String>, // `cfg="..."` on the current resource
+    pub(super) current_keep: bool, // `keep="true"` on the current resource
+    pub(super) current_overridable: bool, // `overridable="true"` on the current resource
+    pub(super) current_preserve: bool, // `xml:space="preserve"` or `preserve="true"` on the current string
+    pub(super) current_pattern: Option<String>, // `pattern="..."` on the current string
+    pub(super) current_transform: Option<String>, // `transform="..."` on the current string
+    pub(super) current_assert: Option<String>, // `assert="..."` on the current resource
+    pub(super) element_line: usize, // 1-based line of the element being opened
+    pub(super) current_line: Option<usize>, // Line of the current resource element
+    pub(super) in_doc: bool, // Inside a <doc> element
+    pub(super) module_doc: bool, // The current <doc> has `module="true"`
+    pub(super) doc_levels: Vec<DocLevel>, // One per open <ns>, root level first
+    pub(super) current_doc: Option<String>, // `<doc>` text of the current resource
+    pub(super) namespace_docs: Vec<(Vec<String>, String)>, // Module docs of closed <ns>
+    pub(super) config_namespaces: Vec<Vec<String>>, // Paths of the <ns struct="true">
+    pub(super) namespace_lines: Vec<(Vec<String>, usize)>, // Path and line of every <ns>
+    pub(super) unused_docs: Vec<usize>, // Lines of docs nothing followed
+    pub(super) ns_from_file: Option<bool>, // `ns-from-file` on <resources>
+}
+
+impl ParseState {
+    /// Line an error of the current element is reported at: its
+    /// resource's, or the element's own outside of one
+    pub(super) fn error_line(&self) -> usize {
+        self.current_line.unwrap_or(self.element_line)
+    }
+
+    /// Metadata for the resource currently being parsed
+    pub(super) fn current_meta(&self) -> ResourceMeta {
+        let profiles = self
+            .profile_stack
+            .iter()
+            .chain(std::iter::once(&self.current_profile))
+            .flatten()
+            .cloned()
+            .collect();
+        ResourceMeta {
+            profiles,
+            translatable: !self.current_untranslatable,
+            deprecated: self.current_deprecated.clone(),
+            keep: self.current_keep,
+            overridable: self.current_overridable,
+            line: self.current_line,
+            doc: self.current_doc.clone(),
+            min: self.current_number_range[0].clone(),
+            max: self.current_number_range[1].clone(),
+            pattern: self.current_pattern.clone(),
+            transform: self.current_transform.clone(),
+            assert: self.current_assert.clone(),
+            widen: self.current_widen.clone(),
+            cfg: self.current_cfg.clone(),
+        }
+    }
+
+    /// Innermost open level, the root level being created on first use
+    pub(super) fn doc_level(&mut self) -> &mut DocLevel {
+        if self.doc_levels.is_empty() {
+            self.doc_levels.push(DocLevel::default());
+        }
+        let last = self.doc_levels.len() - 1;
+        &mut self.doc_levels[last]
+    }
+
+    /// Hands the pending docs of the current level to the element that
+    /// starts now
+    pub(super) fn take_pending_docs(&mut self) -> Vec<String> {
+        let level = self.doc_level();
+        level.seen_element = true;
+        level.pending_line = None;
+        // Marker comments come before `<doc>` elements
+        let mut docs = std::mem::take(&mut level.pending_comments);
+        docs.append(&mut level.pending);
+        docs
+    }
+
+    /// Stores the text of a closed `<doc>` or of a marker comment: the
+    /// module doc of the enclosing `<ns>` if it comes first there, the
+    /// next sibling's doc otherwise
+    pub(super) fn push_doc(&mut self, text: String, from_comment: bool) {
+        let line = self.element_line;
+        let level = self.doc_level();
+        if level.namespace.is_some() && !level.seen_element {
+            level.module_doc.push(text);
+            level.seen_element = true;
+        } else if from_comment {
+            level.pending_line.get_or_insert(line);
+            level.pending_comments.push(text);
+        } else {
+            level.pending_line.get_or_insert(line);
+            level.pending.push(text);
+        }
+    }
+
+    /// Stores the text of a `<doc module="true">` or of a `//!`
+    /// comment as the module doc of the enclosing `<ns>` or
+    /// `<resources>`, wherever it is
+    pub(super) fn push_module_doc(&mut self, text: String) {
+        let level = self.doc_level();
+        level.module_doc.push(text);
+    }
+
+    /// Closes the current level, recording its module doc and any doc
+    /// left without a following element. The module doc of the root
+    /// level, `<resources>`, is recorded for the empty namespace path.
+    pub(super) fn close_doc_level(&mut self) {
+        let Some(level) = self.doc_levels.pop() else {
+            return;
+        };
+        self.unused_docs.extend(level.pending_line);
+        let namespace = match level.namespace {
+            Some(namespace) => namespace,
+            None if self.doc_levels.is_empty() => Vec::new(),
+            // An `<ns>` without a name has no module
+            None => return,
+        };
+        if !level.module_doc.is_empty() {
+            self.namespace_docs
+                .push((namespace, level.module_doc.join("\n\n")));
+        }
+    }
 }
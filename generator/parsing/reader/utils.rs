@@ -1,4 +1,7 @@
-use quick_xml::events::{BytesStart, BytesText};
+use std::borrow::Cow;
+
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesCData, BytesRef, BytesStart, BytesText};
 
 pub(super) fn attr_value(
     e: &BytesStart<'_>,
@@ -6,16 +9,214 @@ pub(super) fn attr_value(
 ) -> Option<String> {
     for attr in e.attributes().flatten() {
         if attr.key.as_ref() == name {
-            return Some(to_string(attr.value.as_ref()));
+            // Resolve `&quot;`-style entities, keeping the raw value if invalid
+            return Some(attr.unescape_value().map_or_else(
+                |_| to_string(attr.value.as_ref()),
+                |value| value.into_owned(),
+            ));
         }
     }
     None
 }
 
+/// Parses a boolean leniently: `true`/`false`, `yes`/`no`, `on`/`off`
+/// and `1`/`0`, case-insensitive
+pub(super) fn parse_bool(text: &str) -> Option<bool> {
+    match text.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Maps byte offsets to 1-based line numbers, for offsets queried in
+/// increasing order
+pub(super) struct LineCounter<'a> {
+    contents: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> LineCounter<'a> {
+    pub(super) fn new(contents: &'a str) -> Self {
+        Self {
+            contents,
+            offset: 0,
+            line: 1,
+        }
+    }
+
+    pub(super) fn line_at(&mut self, position: u64) -> usize {
+        let position = (position as usize).min(self.contents.len());
+        if let Some(skipped) =
+            self.contents.as_bytes().get(self.offset..position)
+        {
+            self.line += skipped.iter().filter(|&&b| b == b'\n').count();
+            self.offset = position;
+        }
+        self.line
+    }
+}
+
+/// Trims every line of a `<doc>` body and drops surrounding blank
+/// lines; `None` when nothing is left
+pub(super) fn doc_text(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let start = lines.iter().position(|line| !line.is_empty())?;
+    let end = lines.iter().rposition(|line| !line.is_empty())?;
+    Some(lines[start..=end].join("\n"))
+}
+
+/// Doc text of a marker comment, `None` for a plain comment or an
+/// empty doc. Every line of a `///` comment may repeat the marker:
+///
+/// ```xml
+/// <!-- /// Retries before giving up
+///      /// (per request) -->
+/// ```
+pub(super) fn comment_doc(comment: &str) -> Option<String> {
+    if let Some(doc) = marked_lines(comment, "///") {
+        return doc;
+    }
+    // `<!---` but not `<!-- -`, a plain comment starting with a dash
+    let rest = comment
+        .strip_prefix('-')
+        .or_else(|| comment.trim_start().strip_prefix("doc:"))?;
+    doc_text(rest)
+}
+
+/// Doc text of a `<!-- //! ... -->` comment, which documents the
+/// enclosing `<ns>` or `<resources>` like a `<doc module="true">`
+pub(super) fn comment_module_doc(comment: &str) -> Option<String> {
+    marked_lines(comment, "//!").flatten()
+}
+
+/// Text of a comment starting with `marker`, which every line may
+/// repeat; `None` when the comment does not start with it
+fn marked_lines(comment: &str, marker: &str) -> Option<Option<String>> {
+    let rest = comment.trim_start().strip_prefix(marker)?;
+    let lines: Vec<&str> = rest
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            line.strip_prefix(marker).unwrap_or(line)
+        })
+        .collect();
+    Some(doc_text(&lines.join("\n")))
+}
+
+/// Strips leading and trailing blank lines and the indentation common
+/// to all non-blank lines, like Kotlin's `trimIndent`
+pub(super) fn trim_indent(text: &str) -> String {
+    let is_blank = |line: &&str| line.trim().is_empty();
+    let lines: Vec<&str> = text.lines().collect();
+    let Some(start) = lines.iter().position(|l| !is_blank(l)) else {
+        return String::new();
+    };
+    let end = lines.iter().rposition(|l| !is_blank(l)).unwrap_or(start);
+    let lines = &lines[start..=end];
+    let indent = lines
+        .iter()
+        .filter(|l| !is_blank(l))
+        .map(|l| l.len() - l.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| if is_blank(l) { "" } else { &l[indent..] })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub(super) fn to_string(bytes: &[u8]) -> String {
     String::from_utf8_lossy(bytes).to_string()
 }
 
-pub(super) fn text_to_string(text: &BytesText<'_>) -> String {
-    String::from_utf8_lossy(text.as_ref()).to_string()
+/// Name of an element, borrowed from the file unless it is not UTF-8
+pub(super) fn tag_name(name: &[u8]) -> Cow<'_, str> {
+    String::from_utf8_lossy(name)
+}
+
+/// Text of `text`, borrowed from the file unless it is not UTF-8
+pub(super) fn decode_text<'a>(
+    text: &'a BytesText<'_>,
+) -> Cow<'a, str> {
+    text.decode()
+        .unwrap_or_else(|_| String::from_utf8_lossy(text.as_ref()))
+}
+
+pub(super) fn decode_cdata<'a>(
+    cdata: &'a BytesCData<'_>,
+) -> Cow<'a, str> {
+    String::from_utf8_lossy(cdata.as_ref())
+}
+
+/// Resolves `&lt;`, `&#233;`, ... ; unknown entities are an error
+pub(super) fn reference_to_string(
+    reference: &BytesRef<'_>,
+) -> Result<String, String> {
+    let name = to_string(reference.as_ref());
+    match reference.resolve_char_ref() {
+        Ok(Some(ch)) => return Ok(ch.to_string()),
+        Ok(None) => {}
+        Err(err) => {
+            return Err(format!(
+                "invalid character reference `&{name};`: {err}"
+            ));
+        }
+    }
+    resolve_predefined_entity(&name)
+        .map(str::to_string)
+        .ok_or_else(|| format!("unknown entity `&{name};`"))
+}
+
+/// Checks that every attribute value of `e` unescapes cleanly
+pub(super) fn check_attributes(e: &BytesStart<'_>) -> Result<(), String> {
+    for attr in e.attributes() {
+        let attr = attr.map_err(|err| err.to_string())?;
+        attr.unescape_value().map_err(|err| {
+            format!(
+                "attribute `{}`: {err}",
+                to_string(attr.key.as_ref())
+            )
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bool, trim_indent, LineCounter};
+
+    #[test]
+    fn trim_indent_strips_common_indentation() {
+        assert_eq!(
+            trim_indent("\n    Hello {name},\n      indented\n\n    bye  \n  "),
+            "Hello {name},\n  indented\n\nbye  "
+        );
+        assert_eq!(trim_indent("one line"), "one line");
+        assert_eq!(trim_indent(" \n\t\n"), "");
+    }
+
+    #[test]
+    fn counts_lines_up_to_offsets() {
+        let mut lines = LineCounter::new("a\nbc\n\nd");
+        assert_eq!(lines.line_at(0), 1);
+        assert_eq!(lines.line_at(2), 2);
+        assert_eq!(lines.line_at(7), 4);
+        assert_eq!(lines.line_at(100), 4);
+    }
+
+    #[test]
+    fn parses_lenient_booleans() {
+        for text in ["true", "True", "YES", "on", "1"] {
+            assert_eq!(parse_bool(text), Some(true), "{text}");
+        }
+        for text in ["false", "FALSE", "no", "Off", "0"] {
+            assert_eq!(parse_bool(text), Some(false), "{text}");
+        }
+        for text in ["", "maybe", "2", "t", "yes please"] {
+            assert_eq!(parse_bool(text), None, "{text}");
+        }
+    }
 }
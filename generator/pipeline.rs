@@ -11,6 +11,8 @@ use crate::generator::parsing;
 pub struct PipelineOutput {
     pub graph: ResourceGraph,
     pub analysis_result: analysis::AnalysisResult,
+    /// Resource files read for the graph, not the tests', in load order
+    pub source_files: Vec<std::path::PathBuf>,
 }
 
 #[allow(dead_code)] // Reserved for future use
@@ -20,30 +22,46 @@ pub fn build_graph(
     build_graph_with_options(
         plan,
         analysis::ValidationOptions::default(),
+        &parsing::FileNamespaces::default(),
     )
 }
 
 pub fn build_graph_with_options(
     plan: &BuildPlan,
     validation_options: analysis::ValidationOptions,
+    file_namespaces: &parsing::FileNamespaces,
 ) -> Result<PipelineOutput, PipelineError> {
     let raw_files = input::load_resources(plan)?;
-    let parsed_files = parsing::parse_raw_files(&raw_files)?;
-    let graph =
-        ResourceGraphBuilder::from_parsed_files(&parsed_files);
-    let analysis_result =
+    // Parse errors are reported with the validation of what could be
+    // read, so one build lists every problem
+    let (mut parsed_files, parse_errors) =
+        parsing::parse_raw_files(&raw_files);
+    file_namespaces.apply(&mut parsed_files);
+    let graph = ResourceGraphBuilder::from_parsed_files_for_profile(
+        &parsed_files,
+        &plan.profile,
+    );
+    let mut analysis_result =
         analysis::validate_with_options(&graph, validation_options);
+    analysis_result.errors.splice(
+        0..0,
+        parse_errors.into_iter().map(analysis::AnalysisError::from),
+    );
 
     Ok(PipelineOutput {
         graph,
         analysis_result,
+        source_files: raw_files
+            .into_iter()
+            .filter(|file| !file.is_test)
+            .map(|file| file.path)
+            .collect(),
     })
 }
 
 #[derive(Debug)]
 pub enum PipelineError {
     Input(input::LoaderError),
-    Parsing(parsing::ParserError),
 }
 
 impl std::fmt::Display for PipelineError {
@@ -53,7 +71,6 @@ impl std::fmt::Display for PipelineError {
     ) -> std::fmt::Result {
         match self {
             Self::Input(err) => write!(f, "{err}"),
-            Self::Parsing(err) => write!(f, "{err}"),
         }
     }
 }
@@ -66,12 +83,6 @@ impl From<input::LoaderError> for PipelineError {
     }
 }
 
-impl From<parsing::ParserError> for PipelineError {
-    fn from(value: parsing::ParserError) -> Self {
-        Self::Parsing(value)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +121,122 @@ mod tests {
             _ => panic!("expected String value"),
         }
     }
+
+    #[test]
+    fn pipeline_reports_the_errors_of_every_file() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("broken.xml"),
+            "<resources>\n<string name=\"a\">x</bool>\n</resources>",
+        );
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <color name="accent"></color>
+                <number name="port" type="u16">-1</number>
+                <string name="title">Hello</string>
+            </resources>"#,
+        );
+
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let output = build_graph(&plan).expect("pipeline succeeds");
+
+        let errors: Vec<String> = output
+            .analysis_result
+            .errors
+            .iter()
+            .map(|e| {
+                let location = e.location.as_ref().unwrap();
+                let file = location.file.file_name().unwrap();
+                format!("{}:{:?}", file.to_string_lossy(), location.line)
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            ["broken.xml:Some(2)", "values.xml:Some(2)", "values.xml:Some(3)"]
+        );
+        let title =
+            crate::generator::ir::ResourceKey::from_path("title");
+        assert!(output.graph.get(&title).is_some());
+    }
+
+    #[test]
+    fn file_namespaces_merge_with_explicit_ones() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("payments.xml"),
+            r#"<resources><string name="title">Pay</string></resources>"#,
+        );
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="app">Demo</string>
+                <ns name="payments">
+                    <string name="title">Checkout</string>
+                    <string name="total">Total</string>
+                </ns>
+            </resources>"#,
+        );
+
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let output = build_graph_with_options(
+            &plan,
+            analysis::ValidationOptions::default(),
+            &parsing::FileNamespaces {
+                enabled: true,
+                ..parsing::FileNamespaces::default()
+            },
+        )
+        .expect("pipeline succeeds");
+
+        let keys: Vec<String> =
+            output.graph.nodes().keys().map(|k| k.full_name()).collect();
+        assert_eq!(keys, ["app", "payments/title", "payments/total"]);
+        // `payments/title` is defined by both files
+        let title =
+            crate::generator::ir::ResourceKey::from_path("payments/title");
+        assert!(output.graph.has_duplicates(&title));
+        assert!(output
+            .analysis_result
+            .warnings
+            .iter()
+            .any(|w| w.key.as_ref() == Some(&title)));
+    }
+
+    #[test]
+    fn pipeline_resolves_profiles_after_parsing() {
+        let tmp = tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        write_file(
+            &res_dir.join("values.xml"),
+            r#"<resources>
+                <string name="visible" profile="release">prod</string>
+                <string name="hidden" profile="debug">dbg</string>
+                <string name="api_url" profile="default">local</string>
+                <string name="api_url" profile="release">remote</string>
+            </resources>"#,
+        );
+
+        let plan = BuildPlan::new(res_dir, None, "release");
+        let output = build_graph(&plan).expect("pipeline succeeds");
+
+        // `visible` and `hidden` each miss a profile, `api_url` has a default
+        let warnings = &output.analysis_result.warnings;
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| !w.message.contains("api_url")));
+        assert_eq!(output.graph.nodes().len(), 2);
+        let hidden =
+            crate::generator::ir::ResourceKey::from_path("hidden");
+        assert!(output.graph.get(&hidden).is_none());
+        let key =
+            crate::generator::ir::ResourceKey::from_path("api_url");
+        match &output.graph.get(&key).unwrap().value {
+            crate::generator::ir::ResourceValue::String(value) => {
+                assert_eq!(value, "remote")
+            }
+            _ => panic!("expected String value"),
+        }
+    }
 }
@@ -1,20 +1,269 @@
 //! Utility functions for code generation.
 
+/// Rust keywords (strict and reserved, up to edition 2024)
+const KEYWORDS: [&str; 52] = [
+    "Self", "abstract", "as", "async", "await", "become", "box", "break",
+    "const", "continue", "crate", "do", "dyn", "else", "enum", "extern",
+    "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+    "pub", "ref", "return", "self", "static", "struct", "super", "trait",
+    "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be written as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 4] = ["Self", "crate", "self", "super"];
+
+/// Modules and types generated at the root of `r::`, which namespaces
+/// must not shadow
+pub const GENERATED_ROOT_TYPES: [&str; 11] = [
+    "lookup", "locale", "localized", "raw", "meta", "Locale",
+    "StringKey", "NumberKey", "FloatKey", "BoolKey", "ColorKey",
+];
+
+/// Constants generated at the root of `r::`
+pub const GENERATED_ROOT_CONSTS: [&str; 1] = ["BUILD_PROFILE"];
+
+/// Modules generated next to `r` (the typed modules), which a root
+/// namespace would be confused with
+pub const GENERATED_TOP_MODULES: [&str; 19] = [
+    "r", "r_tests", "string", "int", "float", "decimal", "boolean",
+    "color", "template", "latlng", "position", "semver", "ipaddr",
+    "socketaddr", "datetime", "date", "money", "character", "binary",
+];
+
+/// Primitive types, which a namespace module shadows in the code
+/// generated around it (`&str`, `u32`)
+pub const PRIMITIVE_TYPES: [&str; 17] = [
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+];
+
+/// Keywords starting a path, generated as `self_`, `super_`, `crate_`
+pub const PATH_KEYWORDS: [&str; 3] = ["crate", "self", "super"];
+
 /// Sanitizes an identifier to be a valid Rust identifier
 ///
-/// Replaces non-alphanumeric characters (except underscores) with underscores
+/// Replaces non-alphanumeric characters (except underscores) with underscores.
+/// Names starting with a digit get a leading underscore (`2fa` → `_2fa`).
+/// Keywords become raw identifiers (`type` → `r#type`), or get a `_`
+/// suffix when raw identifiers aren't allowed (`self` → `self_`).
 pub fn sanitize_identifier(s: &str) -> String {
-    s.chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '_' {
-                c
-            } else {
-                '_'
+    escape_keyword(sanitize_chars(s))
+}
+
+/// Sanitized `UPPER_CASE` name of a constant (never a keyword)
+pub fn const_identifier(s: &str) -> String {
+    sanitize_chars(s).to_uppercase()
+}
+
+/// Escapes `ident` if it is a Rust keyword
+pub fn escape_keyword(ident: String) -> String {
+    if NON_RAW_KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{ident}")
+    } else {
+        ident
+    }
+}
+
+fn sanitize_chars(s: &str) -> String {
+    prefix_digit(
+        s.chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Identifiers cannot start with a digit
+fn prefix_digit(ident: String) -> String {
+    if starts_with_digit(&ident) {
+        format!("_{ident}")
+    } else {
+        ident
+    }
+}
+
+/// Whether `name` gets a `_` prefix when sanitized
+pub fn starts_with_digit(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_numeric)
+}
+
+/// Escapes `s` for use inside a Rust string literal
+///
+/// Only `\\`, `"` and control characters are escaped, so printable
+/// Unicode stays readable in the generated code (`"Café ☕"` rather
+/// than `"Caf\u{e9} \u{2615}"`). Bidirectional overrides are escaped
+/// too, as rustc rejects them in literals.
+pub fn escape_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c.is_control() || is_bidi_control(c) => {
+                out.push_str(&format!("\\u{{{:x}}}", c as u32));
             }
-        })
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Codepoints of rustc's `text_direction_codepoint_in_literal` lint
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Appends `text` to `out` as a JSON string
+pub fn json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                out.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Quotes `s` as a Rust string literal
+///
+/// Values with backslashes or quotes but nothing else to escape (regexes,
+/// Windows paths, JSON) become raw literals with the fewest `#` that
+/// cannot close them early: `r"C:\dir"`, `r#"{"a": 1}"#`. Anything with
+/// a newline or control character keeps the escaped `"..."` form.
+pub fn string_literal(s: &str) -> String {
+    let needs_escapes = s.contains(['\\', '"']);
+    let raw_safe =
+        s.chars().all(|c| !c.is_control() && !is_bidi_control(c));
+    if !needs_escapes || !raw_safe {
+        return format!("\"{}\"", escape_str(s));
+    }
+    let hashes = "#".repeat(raw_hash_count(s));
+    format!("r{hashes}\"{s}\"{hashes}")
+}
+
+/// Smallest `#` count such that `"` followed by that many `#` does not
+/// occur in `s`
+fn raw_hash_count(s: &str) -> usize {
+    s.split('"')
+        .skip(1)
+        .map(|rest| rest.len() - rest.trim_start_matches('#').len() + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Converts a `snake_case` (or `a/b` path) name to `CamelCase`
+///
+/// The name is sanitized first, then each `_`-separated part is
+/// capitalized: `auth/error_title` becomes `AuthErrorTitle`.
+pub fn camel_case_identifier(s: &str) -> String {
+    escape_keyword(prefix_digit(
+        sanitize_chars(s)
+            .split('_')
+            .map(|part| {
+                let mut chars = part.chars();
+                chars.next().map_or_else(String::new, |first| {
+                    first.to_uppercase().chain(chars).collect()
+                })
+            })
+            .collect(),
+    ))
+}
+
+/// Converts a name to `lowerCamelCase` for other languages: `app_name`
+/// becomes `appName`, and any non-alphanumeric character separates
+/// words. An all-caps first word is lowered (`URL_path` → `urlPath`).
+///
+/// Unlike the Rust identifiers, nothing is escaped: keywords and
+/// leading digits are up to each export.
+pub fn lower_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let parts = name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty());
+    for (i, part) in parts.enumerate() {
+        let mut chars = part.chars();
+        let Some(first) = chars.next() else { continue };
+        if i > 0 {
+            out.extend(first.to_uppercase());
+            out.extend(chars);
+        } else if part.chars().all(|c| !c.is_lowercase()) {
+            out.push_str(&part.to_lowercase());
+        } else {
+            out.extend(first.to_lowercase());
+            out.extend(chars);
+        }
+    }
+    out
+}
+
+/// `base`, or `base2`, `base3`, ... when already in `used`; the result is
+/// added to `used`
+pub fn unique_suffixed(
+    base: String,
+    used: &mut std::collections::HashSet<String>,
+) -> String {
+    let mut name = base.clone();
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{base}{n}");
+        n += 1;
+    }
+    name
+}
+
+/// Assigns a unique `CamelCase` variant to each name, in order.
+///
+/// Names flattening to the same identifier get a numeric suffix
+/// (`AuthTitle`, `AuthTitle2`).
+pub fn unique_variants<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    names
+        .into_iter()
+        .map(|name| unique_suffixed(camel_case_identifier(name), &mut seen))
         .collect()
 }
 
+/// Path of `file` starting at the `root` directory name (`res/config.xml`),
+/// so no absolute path ends up in generated files
+pub fn display_path(
+    file: &std::path::Path,
+    root: &std::path::Path,
+) -> String {
+    let Ok(relative) = file.strip_prefix(root) else {
+        return file.display().to_string();
+    };
+    root.file_name()
+        .map(std::path::Path::new)
+        .into_iter()
+        .chain(relative.iter().map(std::path::Path::new))
+        .map(|part| part.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -26,4 +275,160 @@ mod tests {
         assert_eq!(sanitize_identifier("my_var"), "my_var");
         assert_eq!(sanitize_identifier("test123"), "test123");
     }
+
+    #[test]
+    fn leading_digits_get_an_underscore() {
+        assert_eq!(sanitize_identifier("2fa_hint"), "_2fa_hint");
+        assert_eq!(const_identifier("2fa_hint"), "_2FA_HINT");
+        assert_eq!(const_identifier("404"), "_404");
+        assert_eq!(sanitize_identifier("3d"), "_3d");
+        assert_eq!(camel_case_identifier("2fa_hint"), "_2faHint");
+        assert_eq!(camel_case_identifier("auth/2fa"), "Auth2fa");
+        // Only the first character matters
+        assert_eq!(const_identifier("v2"), "V2");
+        assert_eq!(sanitize_identifier("-2"), "_2");
+    }
+
+    #[test]
+    fn escape_str_keeps_printable_unicode() {
+        assert_eq!(escape_str("Café ☕"), "Café ☕");
+        assert_eq!(escape_str("ようこそ"), "ようこそ");
+        assert_eq!(escape_str("👩‍👩‍👧 🇫🇷"), "👩‍👩‍👧 🇫🇷");
+        assert_eq!(escape_str("e\u{301}"), "e\u{301}");
+    }
+
+    #[test]
+    fn escape_str_escapes_quotes_and_controls() {
+        assert_eq!(escape_str(r#"say "hi" \o/"#), r#"say \"hi\" \\o/"#);
+        assert_eq!(escape_str("a\nb\tc\r\0"), r"a\nb\tc\r\0");
+        assert_eq!(escape_str("\u{7}\u{85}"), r"\u{7}\u{85}");
+        assert_eq!(escape_str("\u{202e}abc"), r"\u{202e}abc");
+    }
+
+    #[test]
+    fn string_literal_picks_raw_form_when_it_helps() {
+        assert_eq!(string_literal("plain"), r#""plain""#);
+        assert_eq!(string_literal(r"C:\Users\me"), r#"r"C:\Users\me""#);
+        assert_eq!(string_literal(r"^\d+$"), r#"r"^\d+$""#);
+        assert_eq!(
+            string_literal(r#"{"a": 1}"#),
+            r##"r#"{"a": 1}"#"##
+        );
+        assert_eq!(string_literal(r##"a"#b"##), r###"r##"a"#b"##"###);
+        // Newlines and control characters need the escaped form
+        assert_eq!(string_literal("a\\\nb"), r#""a\\\nb""#);
+        assert_eq!(string_literal("\"\t"), r#""\"\t""#);
+    }
+
+    /// Reads back a literal produced by `string_literal`
+    fn unquote(literal: &str) -> String {
+        if let Some(raw) = literal.strip_prefix('r') {
+            let hashes = raw.len() - raw.trim_start_matches('#').len();
+            return raw[hashes + 1..raw.len() - hashes - 1].to_string();
+        }
+        let mut out = String::new();
+        let mut chars = literal[1..literal.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('0') => out.push('\0'),
+                Some('u') => {
+                    let hex: String = chars
+                        .by_ref()
+                        .skip(1)
+                        .take_while(|&c| c != '}')
+                        .collect();
+                    let code = u32::from_str_radix(&hex, 16).unwrap();
+                    out.push(char::from_u32(code).unwrap());
+                }
+                Some(c) => out.push(c),
+                None => panic!("dangling backslash in {literal}"),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn string_literal_round_trips_random_strings() {
+        const ALPHABET: &[char] = &[
+            'a', 'Z', ' ', '"', '#', '\\', '{', '}', '\n', '\t', '\r',
+            '\0', 'é', '☕', '\u{7}', '\u{202e}', 'r',
+        ];
+        // xorshift, so failures are reproducible
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let len = (seed % 12) as usize;
+            let value: String = (0..len)
+                .map(|i| {
+                    let index = (seed >> (i * 5)) as usize;
+                    ALPHABET[index % ALPHABET.len()]
+                })
+                .collect();
+            let literal = string_literal(&value);
+            assert_eq!(unquote(&literal), value, "{literal}");
+        }
+    }
+
+    #[test]
+    fn test_camel_case_identifier() {
+        assert_eq!(camel_case_identifier("app_name"), "AppName");
+        assert_eq!(camel_case_identifier("auth/title"), "AuthTitle");
+        assert_eq!(camel_case_identifier("pt-BR"), "PtBR");
+        assert_eq!(camel_case_identifier("a__b_"), "AB");
+    }
+
+    #[test]
+    fn test_lower_camel_case() {
+        assert_eq!(lower_camel_case("app_name"), "appName");
+        assert_eq!(lower_camel_case("URL_path"), "urlPath");
+        assert_eq!(lower_camel_case("error-title"), "errorTitle");
+        assert_eq!(lower_camel_case("Title"), "title");
+    }
+
+    #[test]
+    fn test_unique_variants() {
+        assert_eq!(
+            unique_variants(["auth/title_x", "auth_title/x", "auth/title/x"]),
+            vec!["AuthTitleX", "AuthTitleX2", "AuthTitleX3"]
+        );
+    }
+
+    #[test]
+    fn test_keywords_become_raw_identifiers() {
+        for keyword in ["type", "match", "loop", "mod", "fn", "async"] {
+            assert_eq!(sanitize_identifier(keyword), format!("r#{keyword}"));
+        }
+        // Reserved for future use
+        assert_eq!(sanitize_identifier("yield"), "r#yield");
+        assert_eq!(sanitize_identifier("gen"), "r#gen");
+        // Not keywords
+        assert_eq!(sanitize_identifier("types"), "types");
+        assert_eq!(sanitize_identifier("Type"), "Type");
+        assert_eq!(sanitize_identifier("union"), "union");
+    }
+
+    #[test]
+    fn test_non_raw_keywords_get_suffix() {
+        assert_eq!(sanitize_identifier("self"), "self_");
+        assert_eq!(sanitize_identifier("super"), "super_");
+        assert_eq!(sanitize_identifier("crate"), "crate_");
+        assert_eq!(sanitize_identifier("Self"), "Self_");
+        assert_eq!(camel_case_identifier("self"), "Self_");
+    }
+
+    #[test]
+    fn test_const_identifier_ignores_keywords() {
+        assert_eq!(const_identifier("type"), "TYPE");
+        assert_eq!(const_identifier("self"), "SELF");
+        assert_eq!(const_identifier("app-name"), "APP_NAME");
+    }
 }
@@ -0,0 +1,977 @@
+//! `r-res`, works on a `res/` directory without a cargo build.
+//!
+//! ```text
+//! r-res validate [options]
+//! r-res generate --out <file> [--module <name>] [--check] [options]
+//! r-res lint-unused [--src <dir>] [options]
+//! r-res fmt [--sort] [--check] [options]
+//! r-res diff [--to <dir>] [--to-profile <name>] [--json] [options]
+//! r-res export-markdown --out <file> [options]
+//! r-res export-typescript --out <file> [options]
+//! r-res export-kotlin --out <file> [--package <name>] [options]
+//! r-res export-c-header --out <file> [options]
+//! r-res import-android <android-res> [--out <dir>] [--force]
+//! ```
+//!
+//! `validate` runs the same checks as the build script and prints every
+//! error the same way, grouped by file with `file:line` when known. `generate` writes the
+//! code the build script would have generated, for crates that cannot
+//! use one; `--check` only compares it with the committed file.
+//! `lint-unused` lists the resources no `.rs` file of `--src` (default:
+//! `src`) refers to. `fmt` rewrites the resource files in their
+//! canonical layout, sorting resources by name with `--sort`;
+//! `--check` only lists the files it would change. `diff` compares
+//! the resources with those of `--to` (default: `--res-dir`) under
+//! `--to-profile` (default: `--profile`), printing what was added,
+//! removed or changed, as JSON with `--json`. `export-markdown`
+//! writes a catalog of the resources, one table per type,
+//! `export-typescript` their TypeScript definitions for web
+//! frontends, `export-kotlin` a Kotlin `object R` for Android shells
+//! and `export-c-header` a C header of `#define`s for FFI consumers.
+//! `import-android` converts the `values*/` files of an Android
+//! `res/` directory into `--out` (default: `res`), printing what
+//! could not be converted.
+//!
+//! They exit with 1 when the resources are invalid (or the file is
+//! stale, or resources are unused, or files are not formatted, or
+//! the resources differ) and 2
+//! on a usage error, so they fit pre-commit hooks and CI.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use r_resources::generator::{self, diag};
+use r_resources::{BuildOptions, BuildPlan, RError};
+
+const USAGE: &str = "\
+usage: r-res validate [options]
+       r-res generate --out <file> [--module <name>] [--check] [options]
+       r-res lint-unused [--src <dir>] [options]
+       r-res fmt [--sort] [--check] [options]
+       r-res diff [--to <dir>] [--to-profile <name>] [--json] [options]
+       r-res export-markdown --out <file> [options]
+       r-res export-typescript --out <file> [options]
+       r-res export-kotlin --out <file> [--package <name>] [options]
+       r-res export-c-header --out <file> [options]
+       r-res import-android <android-res> [--out <dir>] [--force]
+
+options:
+    --res-dir <dir>           resource directory (default: res)
+    --profile <name>          resource profile (default: debug)
+    --duplicates-as-errors    fail on duplicate resources
+    --require-translations    fail on missing translations
+    --strict-references       fail on strings that look like broken
+                              references (`@strig/name`)";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match parse_args(&args) {
+        Ok(Command::Validate(resources)) => run_validate(&resources),
+        Ok(Command::Generate(generate)) => run_generate(&generate),
+        Ok(Command::LintUnused(resources, src)) => {
+            run_lint_unused(&resources, &src)
+        }
+        Ok(Command::Fmt(fmt)) => run_fmt(&fmt),
+        Ok(Command::Diff(diff)) => run_diff(&diff),
+        Ok(Command::ExportMarkdown(resources, out)) => {
+            let options = BuildOptions {
+                export_markdown: Some(out.clone()),
+                ..resources.options()
+            };
+            run_export(&resources, &out, &options)
+        }
+        Ok(Command::ExportTypescript(resources, out)) => {
+            let options = BuildOptions {
+                export_typescript: Some(out.clone()),
+                ..resources.options()
+            };
+            run_export(&resources, &out, &options)
+        }
+        Ok(Command::ExportKotlin(kotlin)) => {
+            let options = BuildOptions {
+                export_kotlin: Some(kotlin.out.clone()),
+                kotlin_package: kotlin.package.clone(),
+                ..kotlin.resources.options()
+            };
+            run_export(&kotlin.resources, &kotlin.out, &options)
+        }
+        Ok(Command::ExportCHeader(resources, out)) => {
+            let options = BuildOptions {
+                export_c_header: Some(out.clone()),
+                ..resources.options()
+            };
+            run_export(&resources, &out, &options)
+        }
+        Ok(Command::ImportAndroid(import)) => {
+            run_import_android(&import)
+        }
+        Ok(Command::Help) => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n{USAGE}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Validate(Resources),
+    Generate(Generate),
+    /// Resources and the sources to search
+    LintUnused(Resources, PathBuf),
+    Fmt(Fmt),
+    Diff(Diff),
+    /// Resources and the Markdown file to write
+    ExportMarkdown(Resources, PathBuf),
+    /// Resources and the TypeScript file to write
+    ExportTypescript(Resources, PathBuf),
+    ExportKotlin(ExportKotlin),
+    /// Resources and the C header to write
+    ExportCHeader(Resources, PathBuf),
+    ImportAndroid(ImportAndroid),
+    Help,
+}
+
+/// Options shared by every command
+#[derive(Debug, PartialEq)]
+struct Resources {
+    res_dir: PathBuf,
+    profile: String,
+    duplicates_as_errors: bool,
+    require_translations: bool,
+    strict_references: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Generate {
+    resources: Resources,
+    out: PathBuf,
+    module: Option<String>,
+    check: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct Fmt {
+    resources: Resources,
+    sort: bool,
+    check: bool,
+}
+
+/// The old resources and where the new ones differ from them
+#[derive(Debug, PartialEq)]
+struct Diff {
+    resources: Resources,
+    to: Option<PathBuf>,
+    to_profile: Option<String>,
+    json: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct ExportKotlin {
+    resources: Resources,
+    out: PathBuf,
+    package: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ImportAndroid {
+    android_res: PathBuf,
+    out: PathBuf,
+    force: bool,
+}
+
+impl Resources {
+    fn plan(&self) -> BuildPlan {
+        let tests_dir = self.res_dir.join("tests");
+        BuildPlan::new(
+            self.res_dir.clone(),
+            tests_dir.exists().then_some(tests_dir),
+            self.profile.clone(),
+        )
+    }
+
+    fn options(&self) -> BuildOptions {
+        BuildOptions {
+            treat_duplicates_as_errors: self.duplicates_as_errors,
+            require_complete_translations: self.require_translations,
+            strict_references: self.strict_references,
+            ..BuildOptions::default()
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err("missing command".to_string());
+    };
+    match command.as_str() {
+        "validate" => {
+            let (resources, options) = parse_options(rest)?;
+            no_options(command, &options)?;
+            Ok(Command::Validate(resources))
+        }
+        "generate" => parse_generate(rest).map(Command::Generate),
+        "lint-unused" => {
+            let (resources, options) = parse_options(rest)?;
+            let src = match options.as_slice() {
+                [option, src] if option == "--src" => {
+                    PathBuf::from(src)
+                }
+                options => {
+                    no_options(command, options)?;
+                    PathBuf::from("src")
+                }
+            };
+            Ok(Command::LintUnused(resources, src))
+        }
+        "fmt" => parse_fmt(rest).map(Command::Fmt),
+        "diff" => parse_diff(rest).map(Command::Diff),
+        "export-markdown" => {
+            let (resources, out) = parse_export(command, rest)?;
+            Ok(Command::ExportMarkdown(resources, out))
+        }
+        "export-typescript" => {
+            let (resources, out) = parse_export(command, rest)?;
+            Ok(Command::ExportTypescript(resources, out))
+        }
+        "export-kotlin" => {
+            parse_export_kotlin(rest).map(Command::ExportKotlin)
+        }
+        "export-c-header" => {
+            let (resources, out) = parse_export(command, rest)?;
+            Ok(Command::ExportCHeader(resources, out))
+        }
+        "import-android" => {
+            parse_import_android(rest).map(Command::ImportAndroid)
+        }
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("unknown command '{other}'")),
+    }
+}
+
+/// Parses the options of an `export-*` command, which needs `--out`
+fn parse_export(
+    command: &str,
+    args: &[String],
+) -> Result<(Resources, PathBuf), String> {
+    let (resources, options) = parse_options(args)?;
+    match options.as_slice() {
+        [option, out] if option == "--out" => {
+            Ok((resources, PathBuf::from(out)))
+        }
+        options => {
+            no_options(command, options)?;
+            Err(format!("{command} needs --out <file>"))
+        }
+    }
+}
+
+/// Parses the shared options, returning the command-specific ones as
+/// given (`--out file` stays two entries)
+fn parse_options(
+    args: &[String],
+) -> Result<(Resources, Vec<String>), String> {
+    let mut resources = Resources {
+        res_dir: PathBuf::from("res"),
+        profile: "debug".to_string(),
+        duplicates_as_errors: false,
+        require_translations: false,
+        strict_references: false,
+    };
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("'{arg}' needs a value"))
+        };
+        match arg.as_str() {
+            "--res-dir" => {
+                resources.res_dir = PathBuf::from(value()?)
+            }
+            "--profile" => resources.profile = value()?,
+            "--duplicates-as-errors" => {
+                resources.duplicates_as_errors = true;
+            }
+            "--require-translations" => {
+                resources.require_translations = true;
+            }
+            "--strict-references" => {
+                resources.strict_references = true;
+            }
+            "--out" | "--module" | "--src" | "--package" | "--to"
+            | "--to-profile" => {
+                let value = value()?;
+                rest.extend([arg.clone(), value]);
+            }
+            "--check" | "--sort" | "--json" => {
+                rest.push(arg.clone());
+            }
+            other => return Err(format!("unknown option '{other}'")),
+        }
+    }
+    Ok((resources, rest))
+}
+
+fn parse_generate(args: &[String]) -> Result<Generate, String> {
+    let (resources, options) = parse_options(args)?;
+    let mut out = None;
+    let mut module = None;
+    let mut check = false;
+    let mut options = options.into_iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--out" => out = options.next().map(PathBuf::from),
+            "--module" => module = options.next(),
+            "--check" => check = true,
+            other => {
+                return Err(format!(
+                    "'{other}' is not an option of generate"
+                ))
+            }
+        }
+    }
+    if let Some(module) =
+        module.as_deref().filter(|m| !is_identifier(m))
+    {
+        return Err(format!("'{module}' is not a valid module name"));
+    }
+    Ok(Generate {
+        resources,
+        out: out.ok_or("generate needs --out <file>")?,
+        module,
+        check,
+    })
+}
+
+fn parse_fmt(args: &[String]) -> Result<Fmt, String> {
+    let (resources, options) = parse_options(args)?;
+    let mut fmt = Fmt {
+        resources,
+        sort: false,
+        check: false,
+    };
+    for option in options {
+        match option.as_str() {
+            "--sort" => fmt.sort = true,
+            "--check" => fmt.check = true,
+            other => {
+                return Err(format!(
+                    "'{other}' is not an option of fmt"
+                ))
+            }
+        }
+    }
+    Ok(fmt)
+}
+
+fn parse_diff(args: &[String]) -> Result<Diff, String> {
+    let (resources, options) = parse_options(args)?;
+    let mut diff = Diff {
+        resources,
+        to: None,
+        to_profile: None,
+        json: false,
+    };
+    let mut options = options.into_iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--to" => diff.to = options.next().map(PathBuf::from),
+            "--to-profile" => diff.to_profile = options.next(),
+            "--json" => diff.json = true,
+            other => {
+                return Err(format!(
+                    "'{other}' is not an option of diff"
+                ))
+            }
+        }
+    }
+    Ok(diff)
+}
+
+fn parse_export_kotlin(
+    args: &[String],
+) -> Result<ExportKotlin, String> {
+    let (resources, options) = parse_options(args)?;
+    let mut out = None;
+    let mut package = None;
+    let mut options = options.into_iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--out" => out = options.next().map(PathBuf::from),
+            "--package" => package = options.next(),
+            other => {
+                return Err(format!(
+                    "'{other}' is not an option of export-kotlin"
+                ))
+            }
+        }
+    }
+    if let Some(package) = package
+        .as_deref()
+        .filter(|p| !p.split('.').all(is_identifier))
+    {
+        return Err(format!(
+            "'{package}' is not a valid package name"
+        ));
+    }
+    Ok(ExportKotlin {
+        resources,
+        out: out.ok_or("export-kotlin needs --out <file>")?,
+        package,
+    })
+}
+
+/// Takes no shared option: the resource directory is the output
+fn parse_import_android(
+    args: &[String],
+) -> Result<ImportAndroid, String> {
+    let mut android_res = None;
+    let mut out = PathBuf::from("res");
+    let mut force = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => {
+                out = args
+                    .next()
+                    .map(PathBuf::from)
+                    .ok_or("'--out' needs a value")?;
+            }
+            "--force" => force = true,
+            option if option.starts_with("--") => {
+                return Err(format!(
+                    "'{option}' is not an option of import-android"
+                ))
+            }
+            path if android_res.is_none() => {
+                android_res = Some(PathBuf::from(path));
+            }
+            extra => {
+                return Err(format!("unexpected argument '{extra}'"))
+            }
+        }
+    }
+    Ok(ImportAndroid {
+        android_res: android_res
+            .ok_or("import-android needs an Android res directory")?,
+        out,
+        force,
+    })
+}
+
+/// Rejects the command-specific options `command` does not take
+fn no_options(
+    command: &str,
+    options: &[String],
+) -> Result<(), String> {
+    match options.first() {
+        Some(option) => {
+            Err(format!("'{option}' is not an option of {command}"))
+        }
+        None => Ok(()),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn run_validate(resources: &Resources) -> ExitCode {
+    match check(resources, &resources.options()) {
+        Ok(()) => {
+            println!(
+                "{}: resources are valid (profile '{}')",
+                resources.res_dir.display(),
+                resources.profile
+            );
+            ExitCode::SUCCESS
+        }
+        Err(code) => code,
+    }
+}
+
+/// Runs every check of a build with `options`, printing the errors as
+/// the build script does
+fn check(
+    resources: &Resources,
+    options: &BuildOptions,
+) -> Result<(), ExitCode> {
+    generator::build_with_plan_and_options(&resources.plan(), options)
+        .map(drop)
+        .map_err(|error| {
+            diag::render::eprint(&error.diagnostics());
+            ExitCode::FAILURE
+        })
+}
+
+fn run_generate(generate: &Generate) -> ExitCode {
+    let resources = &generate.resources;
+    let code = match r_resources::generate_with_plan(
+        &resources.plan(),
+        &resources.options(),
+        generate.module.as_deref(),
+    ) {
+        Ok(code) => code,
+        Err(error) => return report(error),
+    };
+    let out = generate.out.display();
+
+    if generate.check {
+        let committed = std::fs::read_to_string(&generate.out);
+        if committed.is_ok_and(|committed| committed == code) {
+            println!("{out} is up to date");
+            return ExitCode::SUCCESS;
+        }
+        eprintln!(
+            "error: {out} is stale, run `r-res generate` again"
+        );
+        return ExitCode::FAILURE;
+    }
+
+    match r_resources::generator::write_generated_code_to(
+        &generate.out,
+        &code,
+    ) {
+        Ok(()) => {
+            println!("wrote {out}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: failed to write {out}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_lint_unused(resources: &Resources, src: &Path) -> ExitCode {
+    let messages =
+        match r_resources::lint_unused(&resources.plan(), src) {
+            Ok(messages) => messages,
+            Err(error) => return report(error),
+        };
+    if messages.is_empty() {
+        println!(
+            "{}: every resource is used in {}",
+            resources.res_dir.display(),
+            src.display()
+        );
+        return ExitCode::SUCCESS;
+    }
+    for message in &messages {
+        eprintln!("warning: {message}");
+    }
+    ExitCode::FAILURE
+}
+
+fn run_fmt(fmt: &Fmt) -> ExitCode {
+    let options = r_resources::FormatOptions { sort: fmt.sort };
+    let files = match r_resources::format_resources(
+        &fmt.resources.plan(),
+        &options,
+    ) {
+        Ok(files) => files,
+        Err(error) => return report(error),
+    };
+    let changed: Vec<_> =
+        files.iter().filter(|f| f.changed).collect();
+    if fmt.check {
+        for file in &changed {
+            eprintln!(
+                "error: {} is not formatted, run `r-res fmt`",
+                file.path.display()
+            );
+        }
+        if !changed.is_empty() {
+            return ExitCode::FAILURE;
+        }
+    }
+    for file in &changed {
+        let path = file.path.display();
+        if let Err(e) = std::fs::write(&file.path, &file.formatted) {
+            eprintln!("error: failed to write {path}: {e}");
+            return ExitCode::FAILURE;
+        }
+        println!("formatted {path}");
+    }
+    if changed.is_empty() {
+        println!(
+            "{}: every file is formatted",
+            fmt.resources.res_dir.display()
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_diff(diff: &Diff) -> ExitCode {
+    let old = &diff.resources;
+    let new = Resources {
+        res_dir: diff
+            .to
+            .clone()
+            .unwrap_or_else(|| old.res_dir.clone()),
+        profile: diff
+            .to_profile
+            .clone()
+            .unwrap_or_else(|| old.profile.clone()),
+        ..*old
+    };
+    let changes =
+        match r_resources::diff_resources(&old.plan(), &new.plan()) {
+            Ok(changes) => changes,
+            Err(error) => return report(error),
+        };
+    if diff.json {
+        print!("{}", generator::diff::json(&changes));
+    } else {
+        print!("{}", generator::diff::text(&changes));
+    }
+    if changes.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Validates the resources with `options`, which name the export to
+/// write to `out`
+fn run_export(
+    resources: &Resources,
+    out: &Path,
+    options: &BuildOptions,
+) -> ExitCode {
+    match check(resources, options) {
+        Ok(()) => {
+            println!("wrote {}", out.display());
+            ExitCode::SUCCESS
+        }
+        Err(code) => code,
+    }
+}
+
+fn run_import_android(import: &ImportAndroid) -> ExitCode {
+    use r_resources::generator::import::android;
+
+    let android_res = import.android_res.display();
+    let converted = match android::import_dir(&import.android_res) {
+        Ok(converted) => converted,
+        Err(e) => {
+            eprintln!("error: failed to import {android_res}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for skipped in &converted.skipped {
+        eprintln!("warning: {skipped}");
+    }
+    for file in &converted.files {
+        for warning in &file.conversion.warnings {
+            eprintln!(
+                "warning: {}: {warning}",
+                file.source.display()
+            );
+        }
+        if file.conversion.resources == 0 {
+            eprintln!(
+                "warning: {}: nothing to import, not written",
+                file.source.display()
+            );
+        }
+    }
+    match converted.write_to(&import.out, import.force) {
+        Ok(written) => {
+            for (file, path) in converted
+                .files
+                .iter()
+                .filter(|file| file.conversion.resources > 0)
+                .zip(&written)
+            {
+                println!(
+                    "wrote {} ({} resource(s) from {})",
+                    path.display(),
+                    file.conversion.resources,
+                    file.source.display()
+                );
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            eprintln!("error: {e} (pass --force to overwrite)");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!(
+                "error: failed to write {}: {e}",
+                import.out.display()
+            );
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints every error of `error` like [`check`] does
+fn report(error: RError) -> ExitCode {
+    let errors = match error {
+        RError::Multiple(errors) => errors,
+        error => vec![error],
+    };
+    let diagnostics: Vec<diag::Diagnostic> = errors
+        .iter()
+        .map(|error| diag::Diagnostic::error(error.to_string()))
+        .collect();
+    diag::render::eprint(&diagnostics);
+    ExitCode::FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn validate_options() {
+        assert_eq!(
+            parse_args(&args(
+                "validate --res-dir assets --profile staging --duplicates-as-errors --strict-references"
+            )),
+            Ok(Command::Validate(Resources {
+                res_dir: PathBuf::from("assets"),
+                profile: "staging".to_string(),
+                duplicates_as_errors: true,
+                require_translations: false,
+                strict_references: true,
+            }))
+        );
+    }
+
+    #[test]
+    fn validate_defaults() {
+        let Ok(Command::Validate(resources)) =
+            parse_args(&args("validate"))
+        else {
+            panic!("expected the validate command");
+        };
+        assert_eq!(resources.res_dir, PathBuf::from("res"));
+        assert_eq!(resources.profile, "debug");
+    }
+
+    #[test]
+    fn generate_options() {
+        let Ok(Command::Generate(generate)) = parse_args(&args(
+            "generate --res-dir res --out src/res.rs --module res --check",
+        )) else {
+            panic!("expected the generate command");
+        };
+        assert_eq!(generate.out, PathBuf::from("src/res.rs"));
+        assert_eq!(generate.module.as_deref(), Some("res"));
+        assert!(generate.check);
+        assert_eq!(generate.resources.res_dir, PathBuf::from("res"));
+    }
+
+    #[test]
+    fn lint_unused_options() {
+        assert_eq!(
+            parse_args(&args(
+                "lint-unused --src app/src --res-dir app/res"
+            )),
+            Ok(Command::LintUnused(
+                Resources {
+                    res_dir: PathBuf::from("app/res"),
+                    profile: "debug".to_string(),
+                    duplicates_as_errors: false,
+                    require_translations: false,
+                    strict_references: false,
+                },
+                PathBuf::from("app/src"),
+            ))
+        );
+        let Ok(Command::LintUnused(_, src)) =
+            parse_args(&args("lint-unused"))
+        else {
+            panic!("expected the lint-unused command");
+        };
+        assert_eq!(src, PathBuf::from("src"));
+    }
+
+    #[test]
+    fn fmt_options() {
+        let Ok(Command::Fmt(fmt)) =
+            parse_args(&args("fmt --check --sort --res-dir assets"))
+        else {
+            panic!("expected the fmt command");
+        };
+        assert!(fmt.check && fmt.sort);
+        assert_eq!(fmt.resources.res_dir, PathBuf::from("assets"));
+        assert_eq!(
+            parse_args(&args("fmt --out res.xml")),
+            Err("'--out' is not an option of fmt".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("generate --out r.rs --sort")),
+            Err("'--sort' is not an option of generate".to_string())
+        );
+    }
+
+    #[test]
+    fn diff_options() {
+        let Ok(Command::Diff(diff)) = parse_args(&args(
+            "diff --profile debug --to-profile release --json",
+        )) else {
+            panic!("expected the diff command");
+        };
+        assert_eq!(diff.resources.profile, "debug");
+        assert_eq!(diff.to_profile.as_deref(), Some("release"));
+        assert_eq!(diff.to, None);
+        assert!(diff.json);
+        assert_eq!(
+            parse_args(&args("diff --to")),
+            Err("'--to' needs a value".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("fmt --json")),
+            Err("'--json' is not an option of fmt".to_string())
+        );
+    }
+
+    #[test]
+    fn export_markdown_options() {
+        let Ok(Command::ExportMarkdown(resources, out)) =
+            parse_args(&args(
+                "export-markdown --out docs/res.md --profile release",
+            ))
+        else {
+            panic!("expected the export-markdown command");
+        };
+        assert_eq!(out, PathBuf::from("docs/res.md"));
+        assert_eq!(resources.profile, "release");
+        assert_eq!(
+            parse_args(&args("export-markdown")),
+            Err("export-markdown needs --out <file>".to_string())
+        );
+    }
+
+    #[test]
+    fn export_typescript_options() {
+        let Ok(Command::ExportTypescript(_, out)) = parse_args(&args(
+            "export-typescript --out web/src/resources.ts",
+        )) else {
+            panic!("expected the export-typescript command");
+        };
+        assert_eq!(out, PathBuf::from("web/src/resources.ts"));
+        assert_eq!(
+            parse_args(&args("export-typescript --src src")),
+            Err("'--src' is not an option of export-typescript"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn export_kotlin_options() {
+        let Ok(Command::ExportKotlin(kotlin)) = parse_args(&args(
+            "export-kotlin --package com.example.res --out R.kt",
+        )) else {
+            panic!("expected the export-kotlin command");
+        };
+        assert_eq!(kotlin.out, PathBuf::from("R.kt"));
+        assert_eq!(
+            kotlin.package.as_deref(),
+            Some("com.example.res")
+        );
+        assert_eq!(
+            parse_args(&args(
+                "export-kotlin --package com.2x --out R.kt"
+            )),
+            Err("'com.2x' is not a valid package name".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("export-kotlin")),
+            Err("export-kotlin needs --out <file>".to_string())
+        );
+    }
+
+    #[test]
+    fn export_c_header_options() {
+        assert_eq!(
+            parse_args(&args(
+                "export-c-header --out include/resources.h"
+            )),
+            Ok(Command::ExportCHeader(
+                parse_options(&[]).unwrap().0,
+                PathBuf::from("include/resources.h")
+            ))
+        );
+        assert_eq!(
+            parse_args(&args("export-c-header")),
+            Err("export-c-header needs --out <file>".to_string())
+        );
+    }
+
+    #[test]
+    fn import_android_options() {
+        assert_eq!(
+            parse_args(&args(
+                "import-android app/src/main/res --force"
+            )),
+            Ok(Command::ImportAndroid(ImportAndroid {
+                android_res: PathBuf::from("app/src/main/res"),
+                out: PathBuf::from("res"),
+                force: true,
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("import-android --out assets")),
+            Err("import-android needs an Android res directory"
+                .to_string())
+        );
+        assert_eq!(
+            parse_args(&args("import-android a b")),
+            Err("unexpected argument 'b'".to_string())
+        );
+    }
+
+    #[test]
+    fn usage_errors() {
+        assert_eq!(
+            parse_args(&[]),
+            Err("missing command".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("build")),
+            Err("unknown command 'build'".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("validate --profile")),
+            Err("'--profile' needs a value".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("validate --verbose")),
+            Err("unknown option '--verbose'".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("validate --check")),
+            Err("'--check' is not an option of validate".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("generate --out r.rs --src src")),
+            Err("'--src' is not an option of generate".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("generate --module res")),
+            Err("generate needs --out <file>".to_string())
+        );
+        assert_eq!(
+            parse_args(&args("generate --out r.rs --module 2res")),
+            Err("'2res' is not a valid module name".to_string())
+        );
+    }
+}
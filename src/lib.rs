@@ -34,6 +34,16 @@
 //!
 //! - **Strings**: `<string name="key">value</string>` → `r::KEY`
 //! - **Numbers**: `<number name="key">value</number>` → `r::KEY` (auto-detected `i64`, `f64`, or `BigDecimal`)
+//! - **Byte sizes**: `<bytesize name="key">25MiB</bytesize>` → `r::KEY: u64` (`26_214_400`)
+//! - **Versions**: `<semver name="key">1.10.0-rc.1</semver>` → `r::KEY: Version` and `r::KEY_STR: &str`
+//! - **Dates**: `<datetime name="key">2025-12-31T23:59:59Z</datetime>` → `r::KEY_UNIX: i64` (UTC) and `r::KEY_STR: &str`, `<date name="key">2025-06-01</date>` → `r::KEY: Date`
+//! - **Money**: `<money name="key" currency="USD" scale="2">9.99</money>` → `r::KEY: Money` (`999` minor units), exact to the scale
+//! - **Characters**: `<char name="key">U+F002</char>` → `r::KEY: char`, or `&str` with `grapheme="true"`
+//! - **Bytes**: `<bytes name="key" encoding="base64">TWFu</bytes>` → `r::KEY: &[u8]`, decoded from base64 or hex at build time
+//! - **UUIDs**: `<uuid name="key">6ba7b810-9dad-11d1-80b4-00c04fd430c8</uuid>` → `r::KEY: u128`, `r::KEY_BYTES: [u8; 16]` and `r::KEY_STR: &str`
+//! - **Regexes**: `<regex name="key">^[a-z]+$</regex>` → `r::KEY_PATTERN: &str`, checked at build time, and `r::KEY: LazyLock<Regex>` with the `regex` feature
+//! - **Addresses**: `<ipaddr name="key">1.1.1.1</ipaddr>` → `r::KEY: IpAddr`, `<socketaddr name="key">[::1]:8080</socketaddr>` → `r::KEY: SocketAddr`
+//! - **Coordinates**: `<latlng name="key" lat="48.85" lng="2.35"/>` → `r::KEY: LatLng`, `<position name="key" x="0" y="0"/>` → `r::KEY: Position`
 //! - **String Arrays**: `<string-array name="key">...</string-array>` → `r::KEY`
 //! - **Integer Arrays**: `<int-array name="key">...</int-array>` → `r::KEY`
 //! - **Float Arrays**: `<float-array name="key">...</float-array>` → `r::KEY`
@@ -44,8 +54,15 @@
 //!
 //! ### Test-only resources
 //!
-//! Put XML files under `res/tests/` to generate a `r_tests::` namespace automatically during `cargo test`.
-//! Set the environment variable `R_RESOURCES_INCLUDE_TESTS=1` or call [`build_with_options`] to include them in other builds.
+//! Put XML files under `res/tests/` to generate a `#[cfg(test)]` `r_tests::` namespace, compiled by `cargo test` only.
+//! Test resources shadow default resources of the same name; the others are re-exported from `r::`.
+//! With `BuildOptions::testable`, resources marked `overridable="true"` also get a getter (`r::max_retries()`)
+//! that a test can override on its thread with `r::set_max_retries_for_test(1)`, see [`Override`].
+//!
+//! ### Older Rust versions
+//!
+//! `BigDecimal` and regex statics are `std::sync::LazyLock`s, stable since Rust 1.80.
+//! `BuildOptions::with_msrv("1.75")` generates [`Lazy`] statics instead, which need Rust 1.70.
 //!
 //! ## Features
 //!
@@ -91,17 +108,361 @@ pub fn build() {
     generator::build();
 }
 
+/// Build options (e.g. explicit resource profile)
+pub use generator::BuildOptions;
+
+/// Order of the generated items, see [`BuildOptions::ordering`]
+pub use generator::ir::Ordering;
+
+/// Form of the flattened aliases, see
+/// [`BuildOptions::reference_style`]
+pub use generator::ReferenceStyle;
+pub use generator::LazyStatics;
+
+/// Same as [`build`], with explicit [`BuildOptions`].
+///
+/// The resource profile is resolved with the following precedence:
+/// `options.profile` > `R_RESOURCES_PROFILE` env var > cargo `PROFILE`.
+/// The chosen profile is exposed at runtime as `r::BUILD_PROFILE`.
+pub fn build_with_options(options: &BuildOptions) {
+    generator::build_with_options(options);
+}
+
 /// Build plan for custom resource generation
 pub use generator::input::BuildPlan;
 
 /// Builds resources using a custom build plan (for CLI or advanced setups).
+///
+/// In a build script, `include_resources!` is pointed at
+/// `OUT_DIR/r_generated.rs`: write [`OutputArtifacts::rust`] there,
+/// or call [`announce_generated_code`] with the file it is written
+/// to.
+///
+/// [`OutputArtifacts::rust`]: generator::generation::OutputArtifacts::rust
 pub fn build_with_plan(
     plan: &BuildPlan,
 ) -> Result<
     generator::generation::OutputArtifacts,
     generator::BuildError,
 > {
-    generator::build_with_plan(plan)
+    let artifacts = generator::build_with_plan(plan)?;
+    generator::announce_default_generated_code();
+    Ok(artifacts)
+}
+
+/// Tells `include_resources!` to include `path`, for build scripts
+/// writing the generated code somewhere else than
+/// `OUT_DIR/r_generated.rs`
+pub use generator::announce_generated_code;
+
+/// Runs every check of a build on `plan` (parsing, values, references,
+/// duplicates, identifier collisions) without writing any code.
+///
+/// Warnings are printed to stderr. Used by `r-res validate`.
+pub fn validate_with_plan(
+    plan: &BuildPlan,
+    options: &BuildOptions,
+) -> Result<(), RError> {
+    generator::build_with_plan_and_options(plan, options)
+        .map(drop)
+        .map_err(RError::from)
+}
+
+/// Generates the code a build of `plan` would include, for projects
+/// committing it instead of using a build script (`r-res generate`).
+///
+/// With a `module`, the code is wrapped in `pub mod <module>` (skipped
+/// by rustfmt) so the file can be declared with `mod`; without one, it
+/// is meant for `include!`. Warnings are printed to stderr.
+///
+/// Files of `res/raw/` are skipped with a warning, and so are the
+/// `hot-reload` accessors: both need absolute paths, which only fit the
+/// `OUT_DIR` output of a build script. `r::to_json()` is skipped too,
+/// so the output does not depend on the features of `r-resources`, and
+/// `r::meta::GENERATED_AT_UNIX` is `0`, so regenerating unchanged
+/// resources gives the same file.
+pub fn generate_with_plan(
+    plan: &BuildPlan,
+    options: &BuildOptions,
+    module: Option<&str>,
+) -> Result<String, RError> {
+    let raw_files =
+        generator::input::raw_assets::raw_files(&plan.resources_dir)
+            .unwrap_or_default();
+    if !options.disable_raw && !raw_files.is_empty() {
+        eprintln!(
+            "warning: {} file(s) of res/raw/ are only embedded by build \
+             scripts, skipping them",
+            raw_files.len()
+        );
+    }
+    let options = BuildOptions {
+        disable_raw: true,
+        disable_hot_reload: true,
+        disable_json: true,
+        deterministic: true,
+        ..options.clone()
+    };
+    let artifacts =
+        generator::build_with_plan_and_options(plan, &options)?;
+    let mut code = String::from(
+        "// @generated by `r-res generate`, do not edit by hand\n",
+    );
+    match module {
+        Some(module) => {
+            code.push_str(&format!(
+                "\n#[rustfmt::skip]\npub mod {module} {{\n"
+            ));
+            code.push_str(&artifacts.rust);
+            code.push_str("}\n");
+        }
+        None => code.push_str(&artifacts.rust),
+    }
+    Ok(code)
+}
+
+/// Resources of `plan` that no `.rs` file under `src_dir` refers to,
+/// one message per resource file (`r-res lint-unused`).
+///
+/// Matching is token-based; see [`BuildOptions::warn_unused`] to get
+/// the same report as build warnings.
+pub fn lint_unused(
+    plan: &BuildPlan,
+    src_dir: &std::path::Path,
+) -> Result<Vec<String>, RError> {
+    let warnings = generator::lint_unused(plan, src_dir)?;
+    Ok(warnings.into_iter().map(|w| w.message).collect())
+}
+
+/// How [`format_resources`] lays out the files
+pub use generator::format::{FormatOptions, FormattedFile};
+
+/// Every resource file of `plan` in its canonical layout, see
+/// [`generator::format`] (`r-res fmt`).
+///
+/// Nothing is written: each file tells whether formatting changed it.
+/// A file is an error when it is not well-formed XML, when its
+/// formatted contents would hold other resources, or when it is
+/// declared in an encoding other than UTF-8, which formatted files
+/// are written in.
+pub fn format_resources(
+    plan: &BuildPlan,
+    options: &FormatOptions,
+) -> Result<Vec<FormattedFile>, RError> {
+    let raw_files = generator::input::load_resources(plan)
+        .map_err(|e| RError::InvalidResourceFile(e.to_string()))?;
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for raw in &raw_files {
+        let encoding = generator::input::loader::declared_encoding(
+            raw.contents.as_bytes(),
+        );
+        if let Some(encoding) = encoding.filter(|encoding| {
+            !matches!(
+                encoding.as_str(),
+                "utf-8" | "utf8" | "us-ascii" | "ascii"
+            )
+        }) {
+            errors.push(RError::ParseError {
+                path: raw.path.clone(),
+                line: Some(1),
+                message: format!(
+                    "declared as {encoding}, but formatted files are written in UTF-8; please convert the file to UTF-8 first"
+                ),
+            });
+            continue;
+        }
+        match generator::format::format_file(raw, options) {
+            Ok(formatted) => files.push(FormattedFile {
+                path: raw.path.clone(),
+                changed: formatted != raw.contents,
+                formatted,
+            }),
+            Err(generator::parsing::ParserError::Xml {
+                path,
+                line,
+                message,
+            }) => errors.push(RError::ParseError {
+                path,
+                line,
+                message,
+            }),
+        }
+    }
+    match errors.len() {
+        0 => Ok(files),
+        1 => Err(errors.remove(0)),
+        _ => Err(RError::Multiple(errors)),
+    }
+}
+
+/// What [`diff_resources`] finds for each resource that differs
+pub use generator::diff::{Change, ChangeKind, Entry};
+
+/// Resources added, removed or changed from `old` to `new`, two
+/// directories or one under two profiles, sorted by name; see
+/// [`generator::diff`] (`r-res diff`).
+///
+/// ```rust,no_run
+/// use r_resources::{diff_resources, BuildPlan};
+///
+/// let debug = BuildPlan::new("res".into(), None, "debug");
+/// let release = BuildPlan::new("res".into(), None, "release");
+/// for change in diff_resources(&debug, &release)? {
+///     println!("{} {}", change.kind.as_str(), change.name);
+/// }
+/// # Ok::<(), r_resources::RError>(())
+/// ```
+pub fn diff_resources(
+    old: &BuildPlan,
+    new: &BuildPlan,
+) -> Result<Vec<Change>, RError> {
+    Ok(generator::diff_plans(old, new)?)
+}
+
+/// Same as [`build_with_options`], returning the error instead of
+/// exiting the build script.
+///
+/// ```rust,ignore
+/// // build.rs
+/// if let Err(e) = r_resources::try_build_with_options(&Default::default()) {
+///     panic!("invalid resources: {e}");
+/// }
+/// ```
+pub fn try_build_with_options(
+    options: &BuildOptions,
+) -> Result<(), RError> {
+    generator::try_build_with_options(options).map_err(RError::from)
+}
+
+/// Errors of the build pipeline and of the generated `try_` lookups
+#[derive(Clone, Debug, PartialEq)]
+pub enum RError {
+    /// No resource has this qualified name (`auth/title`)
+    ResourceNotFound(String),
+    /// A resource directory or file that could not be read
+    InvalidResourceFile(String),
+    /// The resource exists, with another type than the one requested
+    TypeMismatch { name: String, expected: String },
+    /// Malformed XML, or a value rejected by its type
+    ParseError {
+        path: std::path::PathBuf,
+        /// 1-based line, when known
+        line: Option<usize>,
+        message: String,
+    },
+    /// `name` contains an `@string/` reference that names no string
+    UnresolvedReference { name: String, reference: String },
+    /// A key defined more than once, with duplicates as errors
+    DuplicateResource(String),
+    /// Any other build error (identifier collisions, profiles, ...)
+    Invalid(String),
+    /// Several errors, in the order they were reported
+    Multiple(Vec<RError>),
+}
+
+impl std::fmt::Display for RError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ResourceNotFound(name) => {
+                write!(f, "resource '{name}' not found")
+            }
+            Self::InvalidResourceFile(message) => {
+                write!(f, "invalid resource file: {message}")
+            }
+            Self::TypeMismatch { name, expected } => {
+                write!(f, "resource '{name}' is not a {expected}")
+            }
+            Self::ParseError {
+                path,
+                line,
+                message,
+            } => match line {
+                Some(line) => {
+                    write!(f, "{}:{line}: {message}", path.display())
+                }
+                None => write!(f, "{}: {message}", path.display()),
+            },
+            Self::UnresolvedReference { name, reference } => write!(
+                f,
+                "resource '{name}' references `{reference}`, which does not name a string"
+            ),
+            Self::DuplicateResource(name) => {
+                write!(f, "resource '{name}' is defined more than once")
+            }
+            Self::Invalid(message) => write!(f, "{message}"),
+            Self::Multiple(errors) => {
+                let lines: Vec<String> =
+                    errors.iter().map(ToString::to_string).collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for RError {}
+
+impl From<generator::BuildError> for RError {
+    fn from(error: generator::BuildError) -> Self {
+        use generator::pipeline::PipelineError;
+        use generator::BuildError;
+
+        match error {
+            BuildError::Pipeline(PipelineError::Input(e)) => {
+                Self::InvalidResourceFile(e.to_string())
+            }
+            BuildError::Export(e) => Self::InvalidResourceFile(format!(
+                "failed to write generated code: {e}"
+            )),
+            error @ BuildError::Sources(_) => {
+                Self::Invalid(error.to_string())
+            }
+            BuildError::Analysis(errors)
+            | BuildError::Generation(errors) => {
+                let mut errors: Vec<Self> =
+                    errors.into_iter().map(Self::from).collect();
+                if errors.len() == 1 {
+                    errors.remove(0)
+                } else {
+                    Self::Multiple(errors)
+                }
+            }
+        }
+    }
+}
+
+impl From<generator::analysis::AnalysisError> for RError {
+    fn from(error: generator::analysis::AnalysisError) -> Self {
+        use generator::analysis::AnalysisErrorKind;
+
+        let name = error.key.as_ref().map(|key| key.full_name());
+        match (error.kind, name) {
+            (AnalysisErrorKind::Duplicate, Some(name)) => {
+                Self::DuplicateResource(name)
+            }
+            (
+                AnalysisErrorKind::UnresolvedReference(reference),
+                Some(name),
+            ) => Self::UnresolvedReference { name, reference },
+            (
+                AnalysisErrorKind::InvalidValue { file, line, reason },
+                Some(name),
+            ) => Self::ParseError {
+                path: file,
+                line,
+                message: format!("invalid value for '{name}': {reason}"),
+            },
+            (AnalysisErrorKind::Parse, _) => match error.location {
+                Some(location) => Self::ParseError {
+                    path: location.file,
+                    line: location.line,
+                    message: error.message,
+                },
+                None => Self::Invalid(error.message),
+            },
+            _ => Self::Invalid(error.message),
+        }
+    }
 }
 
 /// Includes the generated resources from the build script.
@@ -109,18 +470,306 @@ pub fn build_with_plan(
 /// This macro must be called once in your code (typically in `main.rs` or `lib.rs`)
 /// to include the generated resource constants.
 ///
+/// The build script tells the macro where the code is through the
+/// `R_RESOURCES_GENERATED` variable; without one calling
+/// `r_resources::build()`, the compilation fails with the steps to add
+/// it instead of an unreadable `include!` error.
+///
+/// `include_resources!(as name)` wraps the items in a module `name`
+/// (`pub as name` for a public one), keeping `r`, `R`, `string`, ...
+/// out of the including scope.
+///
 /// # Example
 ///
 /// ```rust,ignore
 /// use r_resources::include_resources;
 /// include_resources!();
 /// let _ = r::APP_NAME;
+///
+/// include_resources!(as app_res);
+/// let _ = app_res::r::APP_NAME;
 /// ```
 #[macro_export]
 macro_rules! include_resources {
     () => {
-        include!(concat!(env!("OUT_DIR"), "/r_generated.rs"));
+        include!(env!(
+            "R_RESOURCES_GENERATED",
+            "r-resources: no generated resources to include, since no \
+             build script called `r_resources::build()`.\n\
+             To generate them:\n\
+             1. add `r-resources` to the `[build-dependencies]` of \
+             Cargo.toml\n\
+             2. create `build.rs` next to Cargo.toml with \
+             `fn main() { r_resources::build(); }`\n\
+             3. put the resources in `res/values.xml`\n\
+             (crates without a build script can commit the output of \
+             `r-res generate` instead)"
+        ));
     };
+    ($vis:vis as $name:ident) => {
+        // A private module makes every resource it does not read
+        // unreachable; `r-res lint-unused` reports those instead
+        #[allow(dead_code, unused_imports)]
+        $vis mod $name {
+            $crate::include_resources!();
+        }
+    };
+}
+
+/// Runtime of the `hot-reload` feature, called by the generated
+/// accessors (`r::app_name()`).
+///
+/// In debug builds with the feature, the XML file of a resource is
+/// parsed again whenever its modification time changes (no file
+/// watcher: the time is checked on each call) and the values are
+/// cached behind a `RwLock`. A missing file, invalid XML or a renamed
+/// resource returns the compiled value. Without the feature, or in
+/// release builds, the compiled value is returned directly.
+pub mod hot_reload {
+    use std::borrow::Cow;
+    use std::fmt::Display;
+
+    use crate::generator::ir::ResourceValue;
+
+    /// Current text of the string `name` (`auth/title`) of `file`,
+    /// `compiled` when unchanged or unreadable
+    #[inline]
+    pub fn string(
+        file: &str,
+        name: &str,
+        profile: &str,
+        compiled: &'static str,
+    ) -> Cow<'static, str> {
+        match current(file, name, profile) {
+            Some(
+                ResourceValue::String(text)
+                | ResourceValue::Template { text, .. },
+            ) if text != compiled => Cow::Owned(text),
+            _ => Cow::Borrowed(compiled),
+        }
+    }
+
+    /// Current text of the template `name` rendered with `args`, or
+    /// `None` to render the compiled one (also when the edited template
+    /// uses a parameter missing from `args`)
+    #[inline]
+    pub fn template(
+        file: &str,
+        name: &str,
+        profile: &str,
+        args: &[(&str, &dyn Display)],
+    ) -> Option<String> {
+        match current(file, name, profile)? {
+            ResourceValue::Template { text, params } => render(
+                &text,
+                params.iter().map(|p| p.name.as_str()),
+                args,
+            ),
+            _ => None,
+        }
+    }
+
+    /// Replaces the named (`{name}`) or, without `params`, positional
+    /// (`%1$s`, `%2$d`) placeholders of `text`
+    fn render<'a>(
+        text: &str,
+        params: impl ExactSizeIterator<Item = &'a str>,
+        args: &[(&str, &dyn Display)],
+    ) -> Option<String> {
+        let mut out = text.to_string();
+        if params.len() == 0 {
+            for (i, (_, value)) in args.iter().enumerate() {
+                let value = value.to_string();
+                out = out
+                    .replace(&format!("%{}$s", i + 1), &value)
+                    .replace(&format!("%{}$d", i + 1), &value);
+            }
+            return Some(out);
+        }
+        for param in params {
+            let (_, value) =
+                args.iter().find(|(name, _)| *name == param)?;
+            out = out
+                .replace(&format!("{{{param}}}"), &value.to_string());
+        }
+        Some(out)
+    }
+
+    #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+    #[inline(always)]
+    fn current(_: &str, _: &str, _: &str) -> Option<ResourceValue> {
+        None
+    }
+
+    /// Value of `name` in `file`, parsed again when the file changed
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn current(
+        file: &str,
+        name: &str,
+        profile: &str,
+    ) -> Option<ResourceValue> {
+        use std::collections::HashMap;
+        use std::sync::RwLock;
+        use std::time::SystemTime;
+
+        /// Values of a file, `None` when it did not parse
+        type Values = Option<HashMap<String, ResourceValue>>;
+        static FILES: crate::Lazy<
+            RwLock<HashMap<String, (SystemTime, Values)>>,
+        > = crate::Lazy::new(RwLock::default);
+
+        let modified = std::fs::metadata(file)
+            .and_then(|m| m.modified())
+            .ok()?;
+        if let Some((time, values)) = FILES.read().ok()?.get(file) {
+            if *time == modified {
+                return values.as_ref()?.get(name).cloned();
+            }
+        }
+        let values = parse(file, profile);
+        let value =
+            values.as_ref().and_then(|v| v.get(name).cloned());
+        FILES
+            .write()
+            .ok()?
+            .insert(file.to_string(), (modified, values));
+        value
+    }
+
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn parse(
+        file: &str,
+        profile: &str,
+    ) -> Option<std::collections::HashMap<String, ResourceValue>>
+    {
+        use crate::generator::input::{read_xml_file, RawResourceFile};
+        use crate::generator::ir::ResourceGraphBuilder;
+
+        let contents = read_xml_file(file.as_ref()).ok()?;
+        let raw = RawResourceFile::new(file.into(), contents, false);
+        let (parsed, errors) =
+            crate::generator::parsing::parse_raw_files(&[raw]);
+        if !errors.is_empty() {
+            return None;
+        }
+        let graph =
+            ResourceGraphBuilder::from_parsed_files_for_profile(
+                &parsed, profile,
+            );
+        Some(
+            graph
+                .nodes()
+                .iter()
+                .filter_map(|(key, nodes)| {
+                    Some((
+                        key.full_name(),
+                        nodes.first()?.value.clone(),
+                    ))
+                })
+                .collect(),
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn named_and_positional_placeholders_are_replaced() {
+            let name: &dyn Display = &"Ada";
+            let count: &dyn Display = &3;
+            assert_eq!(
+                render(
+                    "Hi {name}, {count} new",
+                    ["name", "count"].into_iter(),
+                    &[("name", name), ("count", count)],
+                )
+                .as_deref(),
+                Some("Hi Ada, 3 new")
+            );
+            assert_eq!(
+                render(
+                    "%1$s has %2$d",
+                    std::iter::empty(),
+                    &[("arg1", name), ("arg2", count)],
+                )
+                .as_deref(),
+                Some("Ada has 3")
+            );
+            // A parameter added to the file but not to the function
+            assert_eq!(
+                render(
+                    "{extra}",
+                    ["extra"].into_iter(),
+                    &[("name", name)]
+                ),
+                None
+            );
+        }
+
+        #[test]
+        fn unreadable_files_return_the_compiled_value() {
+            let text = string(
+                "/missing/values.xml",
+                "title",
+                "debug",
+                "Login",
+            );
+            assert!(matches!(text, Cow::Borrowed("Login")));
+            assert_eq!(
+                template("/missing/values.xml", "t", "debug", &[]),
+                None
+            );
+        }
+
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        #[test]
+        fn edited_files_are_reloaded() {
+            let tmp = tempfile::tempdir().unwrap();
+            let file = tmp.path().join("values.xml");
+            let file_str = file.to_str().unwrap();
+            let write =
+                |xml: &str| std::fs::write(&file, xml).unwrap();
+
+            write(
+                r#"<resources><ns name="auth"><string name="title">Login</string></ns></resources>"#,
+            );
+            assert_eq!(
+                string(file_str, "auth/title", "debug", "Login"),
+                "Login"
+            );
+
+            // Some filesystems only keep whole seconds
+            std::thread::sleep(std::time::Duration::from_millis(
+                1100,
+            ));
+            write(
+                r#"<resources>
+                    <ns name="auth"><string name="title">Sign in</string></ns>
+                    <template name="hi"><string name="name"/>Hello {name}!</template>
+                </resources>"#,
+            );
+            assert_eq!(
+                string(file_str, "auth/title", "debug", "Login"),
+                "Sign in"
+            );
+            let name: &dyn Display = &"Ada";
+            assert_eq!(
+                template(file_str, "hi", "debug", &[("name", name)])
+                    .as_deref(),
+                Some("Hello Ada!")
+            );
+
+            std::thread::sleep(std::time::Duration::from_millis(
+                1100,
+            ));
+            write("<resources><string name=");
+            assert_eq!(
+                string(file_str, "auth/title", "debug", "Login"),
+                "Login"
+            );
+        }
+    }
 }
 
 pub use bigdecimal::BigDecimal;
@@ -140,6 +789,65 @@ impl Color {
         Self { r, g, b, a }
     }
 
+    /// Parses `#RGB`, `#RRGGBB` or `#AARRGGBB` (alpha first, digits in
+    /// any case); `#RGB` and `#RRGGBB` are opaque.
+    ///
+    /// Usable in constants:
+    ///
+    /// ```
+    /// use r_resources::Color;
+    ///
+    /// const ACCENT: Color = match Color::from_hex("#FF5722") {
+    ///     Ok(color) => color,
+    ///     Err(_) => panic!("invalid color"),
+    /// };
+    /// assert_eq!(ACCENT, Color::new(0xFF, 0x57, 0x22, 0xFF));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ColorParseError`] when `s` does not start with `#`,
+    /// contains a non-hex character or has a digit count other than
+    /// 3, 6 or 8.
+    pub const fn from_hex(s: &str) -> Result<Self, ColorParseError> {
+        let bytes = s.as_bytes();
+        if bytes.is_empty() || bytes[0] != b'#' {
+            return Err(ColorParseError::MissingHash);
+        }
+        let mut i = 1;
+        while i < bytes.len() {
+            if hex_value(bytes[i]).is_none() {
+                return Err(ColorParseError::InvalidDigit(char_at(
+                    bytes, i,
+                )));
+            }
+            i += 1;
+        }
+
+        // `#RGB` repeats each digit: `f` is `ff`
+        match bytes.len() - 1 {
+            3 => Ok(Self::new(
+                hex_byte(bytes, 1, 1),
+                hex_byte(bytes, 2, 2),
+                hex_byte(bytes, 3, 3),
+                255,
+            )),
+            6 => Ok(Self::new(
+                hex_byte(bytes, 1, 2),
+                hex_byte(bytes, 3, 4),
+                hex_byte(bytes, 5, 6),
+                255,
+            )),
+            8 => Ok(Self::new(
+                hex_byte(bytes, 3, 4),
+                hex_byte(bytes, 5, 6),
+                hex_byte(bytes, 7, 8),
+                hex_byte(bytes, 1, 2),
+            )),
+            len => Err(ColorParseError::InvalidLength(len)),
+        }
+    }
+
     /// Returns the color as a hex string (e.g., "#FF5722" or "#AAFF5722")
     #[must_use]
     pub fn as_hex(&self) -> String {
@@ -164,14 +872,215 @@ impl Color {
             | ((self.g as u32) << 8)
             | (self.b as u32)
     }
+
+    /// Same color with alpha `a`
+    #[must_use]
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Raises the HSL lightness by `amount` (`0.0..=1.0`, clamped), as
+    /// Sass' `lighten`; alpha is kept
+    #[must_use]
+    pub fn lighten(self, amount: f32) -> Self {
+        self.shift_lightness(amount.clamp(0.0, 1.0))
+    }
+
+    /// Lowers the HSL lightness by `amount` (`0.0..=1.0`, clamped), as
+    /// Sass' `darken`; alpha is kept
+    #[must_use]
+    pub fn darken(self, amount: f32) -> Self {
+        self.shift_lightness(-amount.clamp(0.0, 1.0))
+    }
+
+    /// Linear mix of every channel, alpha included: `t = 0.0` gives
+    /// `self`, `t = 1.0` gives `other` (`t` is clamped)
+    #[must_use]
+    pub fn blend(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| {
+            channel(f32::from(from) + (f32::from(to) - f32::from(from)) * t)
+        };
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+
+    /// WCAG relative luminance, from `0.0` (black) to `1.0` (white);
+    /// alpha is ignored
+    #[must_use]
+    pub fn luminance(self) -> f32 {
+        let linear = |c: u8| {
+            let c = f32::from(c) / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r)
+            + 0.7152 * linear(self.g)
+            + 0.0722 * linear(self.b)
+    }
+
+    /// WCAG contrast ratio, from `1.0` (same luminance) to `21.0`
+    /// (black on white); the order of the colors does not matter
+    #[must_use]
+    pub fn contrast_ratio(self, other: Self) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    fn shift_lightness(self, delta: f32) -> Self {
+        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
+        let (r, g, b) = hsl_to_rgb(h, s, (l + delta).clamp(0.0, 1.0));
+        Self::new(r, g, b, self.a)
+    }
+}
+
+/// `0.0..=255.0` value rounded to a channel
+fn channel(value: f32) -> u8 {
+    // Clamped first, so the cast cannot truncate
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+/// Hue in `0.0..1.0` turns, saturation and lightness in `0.0..=1.0`
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let [r, g, b] = [r, g, b].map(|c| f32::from(c) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    (h / 6.0, s, l)
+}
+
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = h * 6.0;
+    let x = c * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let to_channel = |v: f32| channel((v + m) * 255.0);
+    (to_channel(r), to_channel(g), to_channel(b))
+}
+
+/// Prints `#AARRGGBB`, alpha included even when opaque
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{:02X}{:02X}{:02X}{:02X}",
+            self.a, self.r, self.g, self.b
+        )
+    }
+}
+
+impl std::str::FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+/// Why [`Color::from_hex`] rejected a value
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// The value does not start with `#`
+    MissingHash,
+    /// Number of hex digits other than 3, 6 or 8
+    InvalidLength(usize),
+    /// A character that is not a hex digit
+    InvalidDigit(char),
+}
+
+impl std::fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingHash => write!(f, "colors must start with '#'"),
+            Self::InvalidLength(len) => write!(
+                f,
+                "expected 3, 6 or 8 hex digits (#RGB, #RRGGBB or #AARRGGBB), found {len}"
+            ),
+            Self::InvalidDigit(c) => write!(f, "'{c}' is not a hex digit"),
+        }
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+const fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Byte made of the hex digits at `high` and `low`, which `from_hex`
+/// already checked
+const fn hex_byte(bytes: &[u8], high: usize, low: usize) -> u8 {
+    match (hex_value(bytes[high]), hex_value(bytes[low])) {
+        (Some(high), Some(low)) => high * 16 + low,
+        _ => 0,
+    }
+}
+
+/// Character starting at byte `i` of the UTF-8 `bytes`
+const fn char_at(bytes: &[u8], i: usize) -> char {
+    let lead = bytes[i];
+    let (len, mut code) = match lead {
+        0x00..=0x7F => return lead as char,
+        0xC0..=0xDF => (2, (lead & 0x1F) as u32),
+        0xE0..=0xEF => (3, (lead & 0x0F) as u32),
+        _ => (4, (lead & 0x07) as u32),
+    };
+    let mut j = 1;
+    while j < len {
+        code = (code << 6) | (bytes[i + j] & 0x3F) as u32;
+        j += 1;
+    }
+    match char::from_u32(code) {
+        Some(c) => c,
+        None => char::REPLACEMENT_CHARACTER,
+    }
 }
 
 /// Typed URL parts split at build-time.
+///
+/// IPv6 hosts are stored without their brackets (`::1`); [`full`]
+/// adds them back.
+///
+/// [`full`]: UrlParts::full
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct UrlParts {
     scheme: &'static str,
     host: &'static str,
+    port: Option<u16>,
     path: &'static str,
+    query: &'static str,
 }
 
 impl UrlParts {
@@ -181,7 +1090,26 @@ impl UrlParts {
         host: &'static str,
         path: &'static str,
     ) -> Self {
-        Self { scheme, host, path }
+        Self {
+            scheme,
+            host,
+            port: None,
+            path,
+            query: "",
+        }
+    }
+    /// Same URL with an explicit port
+    #[must_use]
+    pub const fn with_port(self, port: u16) -> Self {
+        Self {
+            port: Some(port),
+            ..self
+        }
+    }
+    /// Same URL with a query, without the leading `?`
+    #[must_use]
+    pub const fn with_query(self, query: &'static str) -> Self {
+        Self { query, ..self }
     }
     #[must_use]
     pub const fn scheme(&self) -> &'static str {
@@ -192,9 +1120,225 @@ impl UrlParts {
         self.host
     }
     #[must_use]
+    pub const fn port(&self) -> Option<u16> {
+        self.port
+    }
+    #[must_use]
     pub const fn path(&self) -> &'static str {
         self.path
     }
+    /// Query without the leading `?`, empty if there is none
+    #[must_use]
+    pub const fn query(&self) -> &'static str {
+        self.query
+    }
+    /// Reconstructed URL, e.g. `https://api.example.com:8443/v1?lang=en`
+    #[must_use]
+    pub fn full(&self) -> String {
+        format_url(
+            self.scheme,
+            self.host,
+            self.port,
+            self.path,
+            self.query,
+        )
+    }
+
+    /// Splits `url` at runtime; see [`UrlPartsOwned`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`UrlParseError`] for URLs without a scheme or host,
+    /// with an invalid port, with user info or with a fragment, none
+    /// of which can be represented.
+    pub fn parse(url: &str) -> Result<UrlPartsOwned, UrlParseError> {
+        let (scheme, rest) =
+            url.split_once("://").ok_or(UrlParseError::MissingScheme)?;
+        if !is_valid_scheme(scheme) {
+            return Err(UrlParseError::InvalidScheme(scheme.to_string()));
+        }
+        if let Some(at) = rest.find('#') {
+            let fragment = rest[at + 1..].to_string();
+            return Err(UrlParseError::Fragment(fragment));
+        }
+        let (rest, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (authority, path) = match rest.find('/') {
+            Some(slash) => rest.split_at(slash),
+            None => (rest, ""),
+        };
+        if authority.contains('@') {
+            return Err(UrlParseError::UserInfo);
+        }
+        let (host, port) = split_host_port(authority)?;
+        Ok(UrlPartsOwned {
+            scheme: scheme.to_string(),
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+            query: query.to_string(),
+        })
+    }
+}
+
+/// URL parts split at runtime by [`UrlParts::parse`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlPartsOwned {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    path: String,
+    query: String,
+}
+
+impl UrlPartsOwned {
+    #[must_use]
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+    #[must_use]
+    pub const fn port(&self) -> Option<u16> {
+        self.port
+    }
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Query without the leading `?`, empty if there is none
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+    /// Reconstructed URL
+    #[must_use]
+    pub fn full(&self) -> String {
+        format_url(
+            &self.scheme,
+            &self.host,
+            self.port,
+            &self.path,
+            &self.query,
+        )
+    }
+}
+
+/// Why [`UrlParts::parse`] rejected a URL
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UrlParseError {
+    /// No `scheme://` prefix
+    MissingScheme,
+    /// A scheme that is not a letter followed by letters, digits, `+`,
+    /// `-` or `.`
+    InvalidScheme(String),
+    /// Nothing between `://` and the path
+    MissingHost,
+    /// An IPv6 host without its closing `]`, or other characters after it
+    InvalidHost(String),
+    /// A port that is not a number from 0 to 65535
+    InvalidPort(String),
+    /// `user:password@` before the host
+    UserInfo,
+    /// A `#fragment`, holding the fragment text
+    Fragment(String),
+}
+
+impl std::fmt::Display for UrlParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingScheme => {
+                write!(f, "URLs must start with 'scheme://'")
+            }
+            Self::InvalidScheme(scheme) => {
+                write!(f, "'{scheme}' is not a valid URL scheme")
+            }
+            Self::MissingHost => write!(f, "the URL has no host"),
+            Self::InvalidHost(host) => {
+                write!(f, "'{host}' is not a valid host")
+            }
+            Self::InvalidPort(port) => {
+                write!(f, "'{port}' is not a valid port")
+            }
+            Self::UserInfo => {
+                write!(f, "user info ('user@host') is not supported")
+            }
+            Self::Fragment(fragment) => write!(
+                f,
+                "fragments ('#{fragment}') are not supported"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UrlParseError {}
+
+fn format_url(
+    scheme: &str,
+    host: &str,
+    port: Option<u16>,
+    path: &str,
+    query: &str,
+) -> String {
+    let mut url = format!("{scheme}://");
+    if host.contains(':') {
+        url.push('[');
+        url.push_str(host);
+        url.push(']');
+    } else {
+        url.push_str(host);
+    }
+    if let Some(port) = port {
+        url.push_str(&format!(":{port}"));
+    }
+    url.push_str(path);
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(query);
+    }
+    url
+}
+
+fn is_valid_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+}
+
+/// Splits `host[:port]` or `[ipv6][:port]`
+fn split_host_port(
+    authority: &str,
+) -> Result<(&str, Option<u16>), UrlParseError> {
+    let invalid = || UrlParseError::InvalidHost(authority.to_string());
+    let (host, port) = if let Some(ipv6) = authority.strip_prefix('[') {
+        let (host, after) = ipv6.split_once(']').ok_or_else(invalid)?;
+        if host.is_empty() {
+            return Err(invalid());
+        }
+        match after {
+            "" => (host, None),
+            _ => {
+                let port = after.strip_prefix(':').ok_or_else(invalid)?;
+                (host, Some(port))
+            }
+        }
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(UrlParseError::MissingHost);
+    }
+    let port = port
+        .map(|port| {
+            port.parse::<u16>()
+                .map_err(|_| UrlParseError::InvalidPort(port.to_string()))
+        })
+        .transpose()?;
+    Ok((host, port))
 }
 
 /// 2D position.
@@ -249,4 +1393,795 @@ impl LatLng {
     pub const fn lng(&self) -> f64 {
         self.lng
     }
+    /// Great-circle distance in meters (haversine formula, mean Earth
+    /// radius of 6,371 km)
+    #[must_use]
+    pub fn haversine_distance_m(&self, other: &Self) -> f64 {
+        let lat1 = self.lat.to_radians();
+        let lat2 = other.lat.to_radians();
+        let d_lat = lat2 - lat1;
+        let d_lng = (other.lng - self.lng).to_radians();
+        let h = (d_lat / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_M * h.sqrt().min(1.0).asin()
+    }
+    /// Initial bearing of the great-circle route to `other`, in degrees
+    /// clockwise from north (`0.0..360.0`)
+    #[must_use]
+    pub fn initial_bearing_deg(&self, other: &Self) -> f64 {
+        let lat1 = self.lat.to_radians();
+        let lat2 = other.lat.to_radians();
+        let d_lng = (other.lng - self.lng).to_radians();
+        let y = d_lng.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin()
+            - lat1.sin() * lat2.cos() * d_lng.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+}
+
+/// Mean Earth radius used by [`LatLng::haversine_distance_m`]
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Semantic version, generated from `<semver>`.
+///
+/// Versions compare by precedence: `major`, `minor` and `patch`
+/// numerically, then a pre-release sorts before its release
+/// (`1.0.0-rc.1 < 1.0.0`) and pre-releases compare identifier by
+/// identifier, numbers numerically and below alphanumerics
+/// (`alpha < alpha.1 < beta < rc.2 < rc.10`). Build metadata is not
+/// kept; the `_STR` constant next to the version has the full text.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release identifiers without the `-`, empty for a release
+    pub pre: &'static str,
+}
+
+impl Version {
+    /// Release `major.minor.patch`
+    #[must_use]
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self::with_pre(major, minor, patch, "")
+    }
+    /// Pre-release `major.minor.patch-pre`
+    #[must_use]
+    pub const fn with_pre(
+        major: u64,
+        minor: u64,
+        patch: u64,
+        pre: &'static str,
+    ) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre,
+        }
+    }
+    /// `true` when [`pre`](Self::pre) is not empty
+    #[must_use]
+    pub const fn is_pre_release(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let core = |v: &Self| (v.major, v.minor, v.patch);
+        core(self).cmp(&core(other)).then_with(|| {
+            match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => std::cmp::Ordering::Equal,
+                (true, false) => std::cmp::Ordering::Greater,
+                (false, true) => std::cmp::Ordering::Less,
+                (false, false) => self
+                    .pre
+                    .split('.')
+                    .map(PreIdentifier::new)
+                    .cmp(other.pre.split('.').map(PreIdentifier::new)),
+            }
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.is_pre_release() {
+            write!(f, "-{}", self.pre)?;
+        }
+        Ok(())
+    }
+}
+
+/// Pre-release identifier in precedence order: numbers (by length,
+/// then digits, as they have no leading zeros) before alphanumerics
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum PreIdentifier<'a> {
+    Numeric(usize, &'a str),
+    Alphanumeric(&'a str),
+}
+
+impl<'a> PreIdentifier<'a> {
+    fn new(identifier: &'a str) -> Self {
+        if identifier.bytes().all(|b| b.is_ascii_digit()) {
+            Self::Numeric(identifier.len(), identifier)
+        } else {
+            Self::Alphanumeric(identifier)
+        }
+    }
+}
+
+/// Calendar date, generated from `<date>`, checked at build time.
+///
+/// Dates order chronologically and display as `YYYY-MM-DD`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: u16,
+    /// `1` to `12`
+    pub month: u8,
+    /// `1` to the length of the month
+    pub day: u8,
+}
+
+impl Date {
+    /// `year-month-day`, not checked
+    #[must_use]
+    pub const fn new(year: u16, month: u8, day: u8) -> Self {
+        Self { year, month, day }
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Amount of a currency, generated from `<money>`, counted in whole
+/// minor units: `9.99 USD` is `999` at scale `2`.
+///
+/// Amounts compare only within the same currency, whatever their
+/// scale (`9.9 USD == 9.90 USD`); amounts of different currencies are
+/// neither equal nor ordered. `Display` prints `9.99 USD`.
+#[derive(Copy, Clone, Debug)]
+pub struct Money {
+    /// Amount in units of `10^-scale`
+    pub minor_units: i64,
+    /// Decimal places of the minor unit, `2` for cents
+    pub scale: u8,
+    /// ISO 4217 code, such as `"USD"`
+    pub currency: &'static str,
+}
+
+impl Money {
+    /// `minor_units` of `10^-scale` `currency`
+    #[must_use]
+    pub const fn new(
+        minor_units: i64,
+        scale: u8,
+        currency: &'static str,
+    ) -> Self {
+        Self {
+            minor_units,
+            scale,
+            currency,
+        }
+    }
+    /// Amount as a float, for interop; not exact beyond 2^53 minor
+    /// units or for most decimal fractions
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        self.minor_units as f64 / 10_f64.powi(i32::from(self.scale))
+    }
+    /// Minor units at a larger `scale`, `None` on overflow
+    fn units_at(&self, scale: u8) -> Option<i128> {
+        let shift = u32::from(scale - self.scale);
+        10_i128
+            .checked_pow(shift)?
+            .checked_mul(i128::from(self.minor_units))
+    }
+}
+
+impl PartialEq for Money {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self.currency != other.currency {
+            return None;
+        }
+        let scale = self.scale.max(other.scale);
+        Some(self.units_at(scale)?.cmp(&other.units_at(scale)?))
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scale = usize::from(self.scale);
+        // At least one digit before the point
+        let digits = format!(
+            "{:0>width$}",
+            self.minor_units.unsigned_abs(),
+            width = scale + 1
+        );
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+        if self.minor_units < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(whole)?;
+        if !fraction.is_empty() {
+            write!(f, ".{fraction}")?;
+        }
+        write!(f, " {}", self.currency)
+    }
+}
+
+/// Test override of a getter generated with `BuildOptions::testable`,
+/// held by a `thread_local!` of the generated code.
+///
+/// Generated code calls [`Override::get_or`] and [`Override::set`],
+/// tests only use the guard the setter returns:
+///
+/// ```rust,ignore
+/// let _guard = r::set_max_retries_for_test(1);
+/// assert_eq!(r::max_retries(), 1); // r::MAX_RETRIES once dropped
+/// ```
+pub struct Override<T: Copy + 'static>(std::cell::Cell<Option<T>>);
+
+impl<T: Copy + 'static> Override<T> {
+    /// No override, the getter returns its constant
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(std::cell::Cell::new(None))
+    }
+
+    /// Value set on this thread for `cell`, `value` otherwise
+    pub fn get_or(
+        cell: &'static std::thread::LocalKey<Self>,
+        value: T,
+    ) -> T {
+        cell.with(|cell| cell.0.get()).unwrap_or(value)
+    }
+
+    /// Overrides `cell` with `value` on this thread until the guard
+    /// is dropped
+    pub fn set(
+        cell: &'static std::thread::LocalKey<Self>,
+        value: T,
+    ) -> OverrideGuard<T> {
+        let previous = cell.with(|cell| cell.0.replace(Some(value)));
+        OverrideGuard {
+            cell,
+            previous,
+            _thread: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Copy + 'static> Default for Override<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Override of a getter on the current thread, restoring the previous
+/// value (usually the constant) when dropped; nested overrides unwind
+/// in order. Not `Send`: it resets the thread that set it.
+#[must_use = "the override ends as soon as the guard is dropped"]
+pub struct OverrideGuard<T: Copy + 'static> {
+    cell: &'static std::thread::LocalKey<Override<T>>,
+    previous: Option<T>,
+    _thread: std::marker::PhantomData<*const ()>,
+}
+
+impl<T: Copy + 'static> Drop for OverrideGuard<T> {
+    fn drop(&mut self) {
+        let previous = self.previous;
+        self.cell.with(|cell| cell.0.set(previous));
+    }
+}
+
+/// Static initialized on first access, used by the code generated
+/// for a `BuildOptions::msrv` older than Rust 1.80 instead of
+/// `std::sync::LazyLock`. Built on `OnceLock` (Rust 1.70):
+///
+/// ```rust,ignore
+/// pub static TAX_RATE: r_resources::Lazy<r_resources::BigDecimal> =
+///     r_resources::Lazy::new(|| /* parsed once */);
+/// let rate: &r_resources::BigDecimal = &r::TAX_RATE;
+/// ```
+pub struct Lazy<T> {
+    cell: std::sync::OnceLock<T>,
+    init: fn() -> T,
+}
+
+impl<T> Lazy<T> {
+    /// Static whose value `init` computes on first access
+    #[must_use]
+    pub const fn new(init: fn() -> T) -> Self {
+        Self {
+            cell: std::sync::OnceLock::new(),
+            init,
+        }
+    }
+}
+
+impl<T> std::ops::Deref for Lazy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.cell.get_or_init(self.init)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Lazy").field(&**self).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_parses_every_form() {
+        assert_eq!(
+            Color::from_hex("#FF5722"),
+            Ok(Color::new(0xFF, 0x57, 0x22, 0xFF))
+        );
+        assert_eq!(
+            Color::from_hex("#ff5722"),
+            Ok(Color::new(0xFF, 0x57, 0x22, 0xFF))
+        );
+        assert_eq!(
+            Color::from_hex("#80FF5722"),
+            Ok(Color::new(0xFF, 0x57, 0x22, 0x80))
+        );
+        assert_eq!(
+            Color::from_hex("#00000000"),
+            Ok(Color::new(0, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn shorthand_repeats_each_digit() {
+        assert_eq!(
+            Color::from_hex("#f0a"),
+            Ok(Color::new(0xFF, 0x00, 0xAA, 0xFF))
+        );
+        for (digit, value) in "0123456789abcdef".chars().zip(0u8..) {
+            let short = digit.to_string().repeat(3);
+            let long = digit.to_string().repeat(6);
+            let color = Color::from_hex(&format!("#{short}")).unwrap();
+            let byte = value * 17;
+            assert_eq!(color, Color::new(byte, byte, byte, 255));
+            assert_eq!(Color::from_hex(&format!("#{long}")), Ok(color));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        for len in [0, 1, 2, 4, 5, 7, 9, 12] {
+            assert_eq!(
+                Color::from_hex(&format!("#{}", "a".repeat(len))),
+                Err(ColorParseError::InvalidLength(len)),
+                "{len} digits"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_hex_characters() {
+        assert_eq!(
+            Color::from_hex("FF5722"),
+            Err(ColorParseError::MissingHash)
+        );
+        assert_eq!(Color::from_hex(""), Err(ColorParseError::MissingHash));
+        assert_eq!(
+            Color::from_hex("#GG5722"),
+            Err(ColorParseError::InvalidDigit('G'))
+        );
+        assert_eq!(
+            Color::from_hex("#FF 722"),
+            Err(ColorParseError::InvalidDigit(' '))
+        );
+        assert_eq!(
+            Color::from_hex("#FF57##"),
+            Err(ColorParseError::InvalidDigit('#'))
+        );
+        // Invalid characters win over the length
+        assert_eq!(
+            Color::from_hex("#fé"),
+            Err(ColorParseError::InvalidDigit('é'))
+        );
+        assert_eq!(
+            Color::from_hex("#ff5722☕"),
+            Err(ColorParseError::InvalidDigit('☕'))
+        );
+        assert_eq!(
+            Color::from_hex("#🎨"),
+            Err(ColorParseError::InvalidDigit('🎨'))
+        );
+    }
+
+    #[test]
+    fn from_str_and_display_round_trip() {
+        let color: Color = "#FF5722".parse().unwrap();
+        assert_eq!(color.to_string(), "#FFFF5722");
+        assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+        assert_eq!(
+            "#8000ff00".parse::<Color>().unwrap().to_string(),
+            "#8000FF00"
+        );
+        assert_eq!(
+            "red".parse::<Color>().unwrap_err().to_string(),
+            "colors must start with '#'"
+        );
+    }
+
+    fn hex(value: &str) -> Color {
+        Color::from_hex(value).unwrap()
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn luminance_matches_wcag_values() {
+        assert_close(hex("#FFFFFF").luminance(), 1.0);
+        assert_close(hex("#000000").luminance(), 0.0);
+        assert_close(hex("#FF0000").luminance(), 0.2126);
+        assert_close(hex("#00FF00").luminance(), 0.7152);
+        assert_close(hex("#0000FF").luminance(), 0.0722);
+        assert_close(hex("#808080").luminance(), 0.2159);
+        // Alpha does not change the luminance
+        assert_eq!(
+            hex("#80FF5722").luminance(),
+            hex("#FF5722").luminance()
+        );
+    }
+
+    #[test]
+    fn contrast_ratio_matches_wcag_values() {
+        let white = hex("#FFF");
+        let black = hex("#000");
+        assert_close(white.contrast_ratio(black), 21.0);
+        assert_close(black.contrast_ratio(white), 21.0);
+        assert_close(white.contrast_ratio(white), 1.0);
+        // The lightest gray passing AA (4.5:1) on white
+        assert_close(hex("#767676").contrast_ratio(white), 4.54);
+        assert_close(hex("#777777").contrast_ratio(white), 4.48);
+        assert_close(hex("#FF0000").contrast_ratio(white), 4.0);
+    }
+
+    #[test]
+    fn lighten_and_darken_shift_hsl_lightness() {
+        assert_eq!(hex("#000").lighten(0.5), hex("#808080"));
+        assert_eq!(hex("#FFF").darken(0.5), hex("#808080"));
+        assert_eq!(hex("#F00").lighten(0.25), hex("#FF8080"));
+        assert_eq!(hex("#F00").darken(0.25), hex("#800000"));
+        // Hue and saturation are kept (same results as Sass)
+        assert_eq!(hex("#3366FF").lighten(0.1), hex("#668CFF"));
+        assert_eq!(hex("#3366FF").darken(0.1), hex("#0040FF"));
+        // Clamped at white and black, alpha untouched
+        assert_eq!(hex("#80FF5722").lighten(2.0), hex("#80FFFFFF"));
+        assert_eq!(hex("#80FF5722").darken(2.0), hex("#80000000"));
+        assert_eq!(hex("#FF5722").lighten(0.0), hex("#FF5722"));
+        assert_eq!(hex("#FF5722").lighten(-1.0), hex("#FF5722"));
+    }
+
+    #[test]
+    fn blend_mixes_every_channel() {
+        let black = hex("#000");
+        let white = hex("#FFF");
+        assert_eq!(black.blend(white, 0.0), black);
+        assert_eq!(black.blend(white, 1.0), white);
+        assert_eq!(black.blend(white, 0.5), hex("#808080"));
+        assert_eq!(black.blend(white, 3.0), white);
+        assert_eq!(
+            hex("#00FF0000").blend(hex("#FF0000FF"), 0.25),
+            hex("#40BF0040")
+        );
+    }
+
+    #[test]
+    fn with_alpha_keeps_the_channels() {
+        const SCRIM: Color = Color::new(0, 0, 0, 255).with_alpha(0x80);
+        assert_eq!(SCRIM, hex("#80000000"));
+        assert_eq!(hex("#FF5722").with_alpha(0), hex("#00FF5722"));
+    }
+
+    #[test]
+    fn url_parts_reconstruct_the_url() {
+        const API: UrlParts =
+            UrlParts::new("https", "api.example.com", "/v1")
+                .with_port(8443)
+                .with_query("lang=en");
+        assert_eq!(API.port(), Some(8443));
+        assert_eq!(API.query(), "lang=en");
+        assert_eq!(API.full(), "https://api.example.com:8443/v1?lang=en");
+        assert_eq!(
+            UrlParts::new("https", "example.com", "").full(),
+            "https://example.com"
+        );
+        assert_eq!(
+            UrlParts::new("http", "::1", "/health").with_port(8080).full(),
+            "http://[::1]:8080/health"
+        );
+    }
+
+    #[test]
+    fn parse_splits_every_part() {
+        let url = UrlParts::parse(
+            "https://api.example.com:8443/v1/users?lang=en&page=2",
+        )
+        .unwrap();
+        assert_eq!(url.scheme(), "https");
+        assert_eq!(url.host(), "api.example.com");
+        assert_eq!(url.port(), Some(8443));
+        assert_eq!(url.path(), "/v1/users");
+        assert_eq!(url.query(), "lang=en&page=2");
+
+        let url = UrlParts::parse("https://example.com").unwrap();
+        assert_eq!(url.port(), None);
+        assert_eq!(url.path(), "");
+        assert_eq!(url.query(), "");
+
+        let url = UrlParts::parse("https://example.com?q=1").unwrap();
+        assert_eq!(url.path(), "");
+        assert_eq!(url.query(), "q=1");
+    }
+
+    #[test]
+    fn parse_handles_ipv6_hosts() {
+        let url = UrlParts::parse("http://[::1]:8080/health").unwrap();
+        assert_eq!(url.host(), "::1");
+        assert_eq!(url.port(), Some(8080));
+        assert_eq!(url.path(), "/health");
+
+        let url = UrlParts::parse("http://[2001:db8::7]/").unwrap();
+        assert_eq!(url.host(), "2001:db8::7");
+        assert_eq!(url.port(), None);
+
+        assert_eq!(
+            UrlParts::parse("http://[::1/x"),
+            Err(UrlParseError::InvalidHost("[::1".into()))
+        );
+        assert_eq!(
+            UrlParts::parse("http://[::1]8080"),
+            Err(UrlParseError::InvalidHost("[::1]8080".into()))
+        );
+    }
+
+    #[test]
+    fn parse_round_trips_through_full() {
+        for url in [
+            "https://api.example.com:8443/v1?lang=en",
+            "https://example.com",
+            "http://localhost:3000/",
+            "http://[::1]:8080/health?verbose",
+            "ftp://files.example.com/pub/file.txt",
+        ] {
+            assert_eq!(UrlParts::parse(url).unwrap().full(), url);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unrepresentable_urls() {
+        assert_eq!(
+            UrlParts::parse("example.com/path"),
+            Err(UrlParseError::MissingScheme)
+        );
+        assert_eq!(
+            UrlParts::parse("1http://example.com"),
+            Err(UrlParseError::InvalidScheme("1http".into()))
+        );
+        assert_eq!(
+            UrlParts::parse("https:///path"),
+            Err(UrlParseError::MissingHost)
+        );
+        assert_eq!(
+            UrlParts::parse("https://:8080"),
+            Err(UrlParseError::MissingHost)
+        );
+        assert_eq!(
+            UrlParts::parse("https://example.com:99999"),
+            Err(UrlParseError::InvalidPort("99999".into()))
+        );
+        assert_eq!(
+            UrlParts::parse("https://example.com:http"),
+            Err(UrlParseError::InvalidPort("http".into()))
+        );
+        assert_eq!(
+            UrlParts::parse("https://user:pw@example.com"),
+            Err(UrlParseError::UserInfo)
+        );
+        assert_eq!(
+            UrlParts::parse("https://example.com/docs#intro"),
+            Err(UrlParseError::Fragment("intro".into()))
+        );
+        assert_eq!(
+            UrlParseError::Fragment("intro".into()).to_string(),
+            "fragments ('#intro') are not supported"
+        );
+    }
+
+    #[test]
+    fn releases_compare_numerically() {
+        let v = Version::new;
+        assert!(v(1, 10, 0) > v(1, 9, 0));
+        assert!(v(2, 0, 0) > v(1, 99, 99));
+        assert!(v(1, 0, 10) > v(1, 0, 9));
+        assert_eq!(v(1, 2, 3).cmp(&v(1, 2, 3)), std::cmp::Ordering::Equal);
+        assert_eq!(v(1, 10, 0).to_string(), "1.10.0");
+        assert!(!v(1, 10, 0).is_pre_release());
+    }
+
+    #[test]
+    fn pre_releases_sort_before_their_release() {
+        let pre = |pre| Version::with_pre(1, 0, 0, pre);
+        let ordered = [
+            pre("alpha"),
+            pre("alpha.1"),
+            pre("alpha.beta"),
+            pre("beta"),
+            pre("beta.2"),
+            pre("beta.11"),
+            pre("rc.1"),
+            Version::new(1, 0, 0),
+            Version::with_pre(1, 0, 1, "0"),
+        ];
+        for pair in ordered.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        assert!(pre("1") < pre("a"));
+        assert!(pre("999") < pre("1000"));
+        assert_eq!(pre("rc.1").to_string(), "1.0.0-rc.1");
+    }
+
+    #[test]
+    fn dates_order_chronologically() {
+        let d = Date::new;
+        assert!(d(2025, 6, 1) < d(2025, 6, 2));
+        assert!(d(2025, 6, 30) < d(2025, 7, 1));
+        assert!(d(2024, 12, 31) < d(2025, 1, 1));
+        assert_eq!(d(2025, 6, 1).to_string(), "2025-06-01");
+        assert_eq!(d(987, 1, 9).to_string(), "0987-01-09");
+    }
+
+    #[test]
+    fn money_compares_within_a_currency() {
+        let usd = |units| Money::new(units, 2, "USD");
+        assert!(usd(999) < usd(1000));
+        assert_eq!(Money::new(99, 1, "USD"), usd(990));
+        assert!(Money::new(9_991, 3, "USD") > usd(999));
+        assert_ne!(usd(999), Money::new(999, 2, "EUR"));
+        assert_eq!(usd(999).partial_cmp(&Money::new(999, 2, "EUR")), None);
+        assert_eq!(
+            Money::new(i64::MAX, 0, "USD")
+                .partial_cmp(&Money::new(1, 18, "USD")),
+            Some(std::cmp::Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn money_displays_its_scale() {
+        assert_eq!(Money::new(999, 2, "USD").to_string(), "9.99 USD");
+        assert_eq!(Money::new(-50, 2, "USD").to_string(), "-0.50 USD");
+        assert_eq!(Money::new(1500, 0, "JPY").to_string(), "1500 JPY");
+        assert_eq!(Money::new(999, 2, "USD").to_f64(), 9.99);
+    }
+
+    const PARIS: LatLng = LatLng::new(48.8566, 2.3522);
+    const LONDON: LatLng = LatLng::new(51.5074, -0.1278);
+    const NEW_YORK: LatLng = LatLng::new(40.7128, -74.0060);
+    const LOS_ANGELES: LatLng = LatLng::new(34.0522, -118.2437);
+    const SYDNEY: LatLng = LatLng::new(-33.8688, 151.2093);
+    const TOKYO: LatLng = LatLng::new(35.6762, 139.6503);
+
+    fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn haversine_distance_between_cities() {
+        let km =
+            |a: &LatLng, b: &LatLng| a.haversine_distance_m(b) / 1000.0;
+        assert_near(km(&PARIS, &LONDON), 343.6, 0.1);
+        assert_near(km(&LONDON, &PARIS), 343.6, 0.1);
+        assert_near(km(&NEW_YORK, &LOS_ANGELES), 3935.7, 0.1);
+        assert_near(km(&NEW_YORK, &LONDON), 5570.2, 0.1);
+        assert_near(km(&SYDNEY, &TOKYO), 7825.8, 0.1);
+        assert_eq!(PARIS.haversine_distance_m(&PARIS), 0.0);
+        // Antipodes are half the circumference apart
+        let north = LatLng::new(90.0, 0.0);
+        let south = LatLng::new(-90.0, 0.0);
+        assert_near(
+            north.haversine_distance_m(&south),
+            std::f64::consts::PI * 6_371_000.0,
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn initial_bearing_between_cities() {
+        assert_near(PARIS.initial_bearing_deg(&LONDON), 330.02, 0.01);
+        assert_near(LONDON.initial_bearing_deg(&PARIS), 148.12, 0.01);
+        assert_near(
+            NEW_YORK.initial_bearing_deg(&LOS_ANGELES),
+            273.69,
+            0.01,
+        );
+        assert_near(SYDNEY.initial_bearing_deg(&TOKYO), 350.05, 0.01);
+        // Due north, east, south and west along the equator or a meridian
+        let origin = LatLng::new(0.0, 0.0);
+        for (lat, lng, bearing) in [
+            (1.0, 0.0, 0.0),
+            (0.0, 1.0, 90.0),
+            (-1.0, 0.0, 180.0),
+            (0.0, -1.0, 270.0),
+        ] {
+            let to = LatLng::new(lat, lng);
+            assert_near(origin.initial_bearing_deg(&to), bearing, 1e-9);
+        }
+    }
+
+    #[test]
+    fn override_guards_restore_the_previous_value() {
+        thread_local! {
+            static LIMIT: Override<u32> = const { Override::new() };
+        }
+        assert_eq!(Override::get_or(&LIMIT, 3), 3);
+        let outer = Override::set(&LIMIT, 1);
+        let inner = Override::set(&LIMIT, 2);
+        assert_eq!(Override::get_or(&LIMIT, 3), 2);
+        let other = std::thread::spawn(|| Override::get_or(&LIMIT, 3));
+        assert_eq!(other.join().unwrap(), 3);
+        drop(inner);
+        assert_eq!(Override::get_or(&LIMIT, 3), 1);
+        drop(outer);
+        assert_eq!(Override::get_or(&LIMIT, 3), 3);
+    }
+
+    #[test]
+    fn lazy_statics_initialize_once() {
+        static CALLS: std::sync::atomic::AtomicU32 =
+            std::sync::atomic::AtomicU32::new(0);
+        static VALUE: Lazy<u32> = Lazy::new(|| {
+            CALLS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            42
+        });
+        assert_eq!(*VALUE, 42);
+        assert_eq!(*VALUE + 1, 43);
+        let calls = CALLS.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn from_hex_is_const() {
+        const ACCENT: Color = match Color::from_hex("#0af") {
+            Ok(color) => color,
+            Err(_) => panic!("invalid color"),
+        };
+        assert_eq!(ACCENT.as_hex(), "#00AAFF");
+    }
 }
@@ -0,0 +1,96 @@
+//! `r-res import-android` against a fixture Android `res/` directory.
+
+#[cfg(test)]
+mod tests {
+    use r_resources::generator::import::android::import_dir;
+    use r_resources::{validate_with_plan, BuildOptions, BuildPlan};
+    use std::path::Path;
+
+    const FIXTURE: &str = "tests/fixtures/android";
+
+    #[test]
+    fn converted_files_match_the_expected_ones() {
+        let import =
+            import_dir(&Path::new(FIXTURE).join("res")).unwrap();
+        let targets: Vec<(&Path, &Path)> = import
+            .files
+            .iter()
+            .map(|file| {
+                (file.source.as_path(), file.target.as_path())
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                ("values/arrays.xml", "arrays.xml"),
+                ("values/config.xml", "config.xml"),
+                ("values/strings.xml", "strings.xml"),
+                ("values-fr/strings.xml", "strings-fr.xml"),
+                ("values-pt-rBR/strings.xml", "strings-pt-BR.xml"),
+            ]
+            .map(|(source, target)| (
+                Path::new(source),
+                Path::new(target)
+            ))
+        );
+        assert_eq!(
+            import.skipped,
+            ["values-night/ was not imported: only locale qualifiers are supported"]
+        );
+
+        for file in
+            import.files.iter().filter(|f| f.conversion.resources > 0)
+        {
+            let expected = std::fs::read_to_string(
+                Path::new(FIXTURE)
+                    .join("expected")
+                    .join(&file.target),
+            )
+            .unwrap();
+            assert_eq!(
+                file.conversion.xml, expected,
+                "{:?}",
+                file.target
+            );
+        }
+    }
+
+    #[test]
+    fn unsupported_constructs_are_reported() {
+        let import =
+            import_dir(&Path::new(FIXTURE).join("res")).unwrap();
+        let warnings: Vec<&str> = import
+            .files
+            .iter()
+            .flat_map(|file| &file.conversion.warnings)
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "string-array 'sort_orders' was not imported: arrays are not supported",
+                "integer 'animation_ms' was not imported: references another resource (@android:integer/config_shortAnimTime)",
+                "color 'accent' was not imported: references another resource (@color/primary)",
+                "dimen 'padding' was not imported: the element is not supported",
+                "string 'storage_used': `%1$.1f` becomes a plain parameter, its formatting is lost",
+                "string 'delete_confirm': <b> markup was dropped, its text is kept",
+                "string 'settings.title' is renamed to 'settings_title'",
+                "plurals 'notes_selected' was not imported: plurals are not supported",
+            ]
+        );
+    }
+
+    #[test]
+    fn imported_resources_build() {
+        let import =
+            import_dir(&Path::new(FIXTURE).join("res")).unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        let written = import.write_to(&res_dir, false).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(import.write_to(&res_dir, false).is_err());
+
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        validate_with_plan(&plan, &BuildOptions::default()).unwrap();
+    }
+}
@@ -0,0 +1,48 @@
+//! `assert` attributes, on a committed file so the generated
+//! `const _: () = assert!(...)` items compile in the test.
+
+#[path = "fixtures/assertions/resources.rs"]
+// One module deeper than an `include_resources!()`
+#[allow(dead_code, unused_imports, clippy::excessive_nesting)]
+mod resources;
+
+#[cfg(test)]
+mod tests {
+    use super::resources::res::r;
+    use r_resources::{generate_with_plan, BuildOptions, BuildPlan};
+
+    const RES_DIR: &str = "tests/fixtures/assertions/res";
+    const GOLDEN: &str = "tests/fixtures/assertions/resources.rs";
+
+    #[test]
+    fn assertions_match_golden_file() {
+        let plan = BuildPlan::new(RES_DIR.into(), None, "debug");
+        let code = generate_with_plan(
+            &plan,
+            &BuildOptions::default(),
+            Some("res"),
+        )
+        .expect("fixture resources are valid");
+        let expected = std::fs::read_to_string(GOLDEN).unwrap();
+        assert!(
+            code == expected,
+            "{GOLDEN} is stale, regenerate it with `generate_with_plan`"
+        );
+        assert!(code.contains(
+            "const _: () = assert!(CACHE_SIZE > 0, \"cache_size: assert `gt:0` failed\");"
+        ));
+        assert!(code.contains("const _: () = assert!(RATIO < 1.0,"));
+        assert!(code
+            .contains("const _: () = assert!(TIMEOUT_MS >= 1000,"));
+        assert!(code.contains(
+            "const _: () = assert!(!API_KEY_PREFIX.is_empty(),"
+        ));
+    }
+
+    #[test]
+    fn asserted_constants_keep_their_values() {
+        assert_eq!(r::CACHE_SIZE, 256);
+        assert_eq!(r::TIMEOUT_MS, 1500);
+        assert_eq!(r::ui::TITLE, "sk_");
+    }
+}
@@ -0,0 +1,22 @@
+//! Golden file of the C header of the example `res/` directory
+//! (`BuildOptions::export_c_header`).
+
+#[cfg(test)]
+mod tests {
+    use r_resources::{validate_with_plan, BuildOptions, BuildPlan};
+
+    const GOLDEN: &str = "tests/fixtures/c_header/resources.h";
+
+    #[test]
+    fn c_header_export_matches_golden_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("include/resources.h");
+        let plan = BuildPlan::new("res".into(), None, "debug");
+        let options = BuildOptions::new().with_c_header_export(&out);
+        validate_with_plan(&plan, &options).unwrap();
+
+        let exported = std::fs::read_to_string(out).unwrap();
+        let expected = std::fs::read_to_string(GOLDEN).unwrap();
+        assert_eq!(exported, expected);
+    }
+}
@@ -0,0 +1,26 @@
+//! The committed file of `examples/committed_resources` matches what
+//! `r-res generate` produces from its `res/`.
+
+use std::path::Path;
+
+use r_resources::{generate_with_plan, BuildOptions, BuildPlan};
+
+#[test]
+fn committed_example_is_up_to_date() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples/committed_resources");
+    let plan = BuildPlan::new(example.join("res"), None, "debug");
+    let code = generate_with_plan(
+        &plan,
+        &BuildOptions::default(),
+        Some("res"),
+    )
+    .expect("example resources are valid");
+    let committed =
+        std::fs::read_to_string(example.join("resources.rs"))
+            .unwrap();
+    assert!(
+        committed == code,
+        "examples/committed_resources/resources.rs is stale, regenerate it with `r-res generate`"
+    );
+}
@@ -0,0 +1,33 @@
+//! Snapshot of the rendered diagnostics of a build with a bad number,
+//! an unresolved reference and a duplicate name.
+
+#[cfg(test)]
+mod tests {
+    use r_resources::generator::diag::render::{render, Style};
+    use r_resources::generator::{
+        build_with_plan_and_options, BuildOptions, BuildPlan,
+    };
+
+    const RES: &str = "tests/fixtures/diagnostics/res";
+    const SNAPSHOT: &str = "tests/fixtures/diagnostics/errors.txt";
+
+    #[test]
+    fn rendered_errors_match_snapshot() {
+        let plan = BuildPlan::new(RES.into(), None, "debug");
+        let options = BuildOptions {
+            treat_duplicates_as_errors: true,
+            ..BuildOptions::default()
+        };
+        let Err(error) = build_with_plan_and_options(&plan, &options)
+        else {
+            panic!("the build should fail");
+        };
+
+        let rendered =
+            render(&error.diagnostics(), Style::PLAIN, |path| {
+                std::fs::read_to_string(path).ok()
+            });
+        let expected = std::fs::read_to_string(SNAPSHOT).unwrap();
+        assert_eq!(rendered, expected);
+    }
+}
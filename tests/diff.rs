@@ -0,0 +1,60 @@
+//! `r-res diff` between two fixture resource directories, and between
+//! two profiles of one.
+
+#[cfg(test)]
+mod tests {
+    use r_resources::generator::diff;
+    use r_resources::{diff_resources, BuildPlan, ChangeKind};
+    use std::path::Path;
+
+    const FIXTURE: &str = "tests/fixtures/diff";
+
+    fn plan(dir: &str, profile: &str) -> BuildPlan {
+        BuildPlan::new(
+            Path::new(FIXTURE).join(dir).join("res"),
+            None,
+            profile,
+        )
+    }
+
+    #[test]
+    fn directories_are_compared_by_resource() {
+        let changes = diff_resources(
+            &plan("old", "debug"),
+            &plan("new", "debug"),
+        )
+        .unwrap();
+        assert_eq!(
+            diff::text(&changes),
+            "+ accent = \"#3366FF\" (color)\n\
+             ! SIGNATURE CHANGED greeting: (name: &str) -> (name: &str, count: i64)\n\
+             - legacy/banner = \"Old banner\" (string)\n\
+             ! TYPE CHANGED max_retries: i64 -> f64 (\"3\" -> \"2.5\")\n\
+             ~ welcome: \"Welcome!\" -> \"Welcome back!\" (string)\n\
+             1 added, 1 removed, 3 changed\n"
+        );
+    }
+
+    #[test]
+    fn profiles_of_one_directory_are_compared() {
+        let changes = diff_resources(
+            &plan("old", "debug"),
+            &plan("old", "release"),
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 1, "{changes:?}");
+        assert_eq!(changes[0].kind, ChangeKind::Changed);
+        assert_eq!(
+            diff::json(&changes),
+            r#"[{"name":"api_url","change":"changed","old":{"type":"string","value":"http://localhost:8080"},"new":{"type":"string","value":"https://api.example.com"}}]"#
+                .to_string()
+                + "\n"
+        );
+        let same = diff_resources(
+            &plan("new", "debug"),
+            &plan("new", "debug"),
+        )
+        .unwrap();
+        assert_eq!(diff::text(&same), "no differences\n");
+    }
+}
@@ -0,0 +1,93 @@
+//! `RError`, from the `try_` lookups and from failed builds.
+
+r_resources::include_resources!();
+
+#[cfg(test)]
+mod tests {
+    use super::r;
+    use r_resources::{build_with_plan, BuildPlan, RError};
+    use std::fs;
+
+    #[test]
+    fn try_lookups_report_missing_and_mistyped_names() {
+        assert_eq!(r::lookup::try_number_i64("max_retries"), Ok(3));
+        assert_eq!(
+            r::lookup::try_string("nope"),
+            Err(RError::ResourceNotFound("nope".into()))
+        );
+        assert_eq!(
+            r::lookup::try_string("max_retries"),
+            Err(RError::TypeMismatch {
+                name: "max_retries".into(),
+                expected: "string".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn messages() {
+        assert_eq!(
+            RError::ResourceNotFound("auth/title".into()).to_string(),
+            "resource 'auth/title' not found"
+        );
+        assert_eq!(
+            RError::InvalidResourceFile("no XML files".into())
+                .to_string(),
+            "invalid resource file: no XML files"
+        );
+        assert_eq!(
+            RError::TypeMismatch {
+                name: "max_retries".into(),
+                expected: "string".into(),
+            }
+            .to_string(),
+            "resource 'max_retries' is not a string"
+        );
+        assert_eq!(
+            RError::ParseError {
+                path: "res/values.xml".into(),
+                line: Some(4),
+                message: "invalid color".into(),
+            }
+            .to_string(),
+            "res/values.xml:4: invalid color"
+        );
+    }
+
+    fn build_errors(xml: &str) -> RError {
+        let tmp = tempfile::tempdir().unwrap();
+        let res_dir = tmp.path().join("res");
+        fs::create_dir_all(&res_dir).unwrap();
+        fs::write(res_dir.join("values.xml"), xml).unwrap();
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        match build_with_plan(&plan) {
+            Ok(_) => panic!("the build should fail"),
+            Err(e) => RError::from(e),
+        }
+    }
+
+    #[test]
+    fn build_errors_convert_into_rerror() {
+        assert_eq!(
+            build_errors(
+                "<resources>\n\
+                 <string name=\"title\">Hi @string/missing</string>\n\
+                 </resources>"
+            ),
+            RError::UnresolvedReference {
+                name: "title".into(),
+                reference: "@string/missing".into(),
+            }
+        );
+
+        let RError::ParseError { path, line, .. } = build_errors(
+            "<resources>\n\
+             <color name=\"accent\">#GGG</color>\n\
+             </resources>",
+        ) else {
+            panic!("expected a parse error");
+        };
+        assert!(path.ends_with("values.xml"));
+        assert_eq!(line, Some(2));
+    }
+}
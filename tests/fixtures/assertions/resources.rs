@@ -0,0 +1,314 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod ui {
+        /// Defined in `res/values.xml` (namespace `ui`)
+        pub const TITLE: &str = "sk_";
+        const _: () = assert!(!TITLE.is_empty(), "ui/title: assert `len_ge:1` failed");
+    }
+    /// Defined in `res/values.xml`
+    pub const API_KEY_PREFIX: &str = "sk_";
+    const _: () = assert!(API_KEY_PREFIX.len() <= 8, "api_key_prefix: assert `len_le:8` failed");
+    const _: () = assert!(!API_KEY_PREFIX.is_empty(), "api_key_prefix: assert `non_empty` failed");
+    /// Defined in `res/values.xml`
+    pub const CACHE_SIZE: u32 = 256;
+    const _: () = assert!(CACHE_SIZE > 0, "cache_size: assert `gt:0` failed");
+    const _: () = assert!(CACHE_SIZE <= 4096, "cache_size: assert `le:4096` failed");
+    /// Defined in `res/values.xml`
+    pub const RATIO: f64 = 0.75;
+    const _: () = assert!(RATIO >= 0.0, "ratio: assert `ge:0` failed");
+    const _: () = assert!(RATIO < 1.0, "ratio: assert `lt:1` failed");
+    /// Defined in `res/values.xml`
+    pub const RETRIES: i64 = 3;
+    const _: () = assert!(RETRIES != 0, "retries: assert `ne:0` failed");
+    /// Defined in `res/values.xml`
+    pub const TIMEOUT_MS: i64 = 1500;
+    const _: () = assert!(TIMEOUT_MS >= 1000, "timeout_ms: assert `ge:1000` failed");
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        ApiKeyPrefix,
+        UiTitle,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::ApiKeyPrefix, Self::UiTitle];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::ApiKeyPrefix => API_KEY_PREFIX,
+                Self::UiTitle => ui::TITLE,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::ApiKeyPrefix => "api_key_prefix",
+                Self::UiTitle => "ui/title",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "api_key_prefix" => Some(Self::ApiKeyPrefix),
+                "ui/title" => Some(Self::UiTitle),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        CacheSize,
+        Retries,
+        TimeoutMs,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::CacheSize, Self::Retries, Self::TimeoutMs];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::CacheSize => CACHE_SIZE as i64,
+                Self::Retries => RETRIES,
+                Self::TimeoutMs => TIMEOUT_MS,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::CacheSize => "cache_size",
+                Self::Retries => "retries",
+                Self::TimeoutMs => "timeout_ms",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "cache_size" => Some(Self::CacheSize),
+                "retries" => Some(Self::Retries),
+                "timeout_ms" => Some(Self::TimeoutMs),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `f64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum FloatKey {
+        Ratio,
+    }
+
+    #[allow(deprecated)]
+    impl FloatKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Ratio];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> f64 {
+            match self {
+                Self::Ratio => RATIO,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Ratio => "ratio",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "ratio" => Some(Self::Ratio),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 2] = [
+            ("api_key_prefix", super::API_KEY_PREFIX),
+            ("ui/title", super::ui::TITLE),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 3] = [
+            ("cache_size", super::CACHE_SIZE as i64),
+            ("retries", super::RETRIES),
+            ("timeout_ms", super::TIMEOUT_MS),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 1] = [
+            ("ratio", super::RATIO),
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 6] = [
+            "api_key_prefix",
+            "cache_size",
+            "ratio",
+            "retries",
+            "timeout_ms",
+            "ui/title",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 6;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod string {
+    pub mod ui {
+        pub use super::super::r::ui::TITLE;
+    }
+    pub use super::r::API_KEY_PREFIX;
+}
+
+pub mod int {
+    pub use super::r::CACHE_SIZE;
+    pub use super::r::RETRIES;
+    pub use super::r::TIMEOUT_MS;
+}
+
+pub mod float {
+    pub use super::r::RATIO;
+}
+}
@@ -0,0 +1,95 @@
+/* Generated by r-resources. Do not edit by hand. */
+
+#ifndef R_RESOURCES_H
+#define R_RESOURCES_H
+
+#define R_ABOUT "Built with r-resources."
+#define R_APP_NAME "r-resources"
+/* R_AUTO_BIG_DECIMAL: BigDecimal, not exported */
+/* R_AUTO_BIG_NUMBER: BigDecimal, not exported */
+#define R_AUTO_NUMBER 123456
+#define R_AUTO_NUMBER_WITH_DECIMAL 123456.789
+/* R_BIG_NUMBER: BigDecimal, not exported */
+#define R_DEBUG_MODE 1
+/* R_ERROR_WITH_DETAILS: template, not exported */
+#define R_INSTALL_DIR "C:\\Program Files\\Demo"
+#define R_MAX_RETRIES 3
+#define R_RATE 0.75
+#define R_TAX_RATE 0.2
+#define R_TIMEOUT_MS 5000
+/* R_VALIDATION_ERROR: template, not exported */
+#define R_VERSION_PATTERN "^\"v\\d+\"$"
+/* R_WELCOME_MESSAGE: template, not exported */
+#define R_API_ERROR_AUTHENTICATION_FAILED_CODE 401
+#define R_API_ERROR_BAD_REQUEST_CODE 400
+#define R_API_ERROR_DATABASE_ERROR_CODE 500
+#define R_API_ERROR_FORBIDDEN_CODE 403
+#define R_API_ERROR_INSUFFICIENT_PERMISSIONS_CODE 403
+#define R_API_ERROR_INTERNAL_SERVER_ERROR_CODE 500
+#define R_API_ERROR_INVALID_FORMAT_CODE 400
+#define R_API_ERROR_INVALID_PARAMETER_CODE 400
+#define R_API_ERROR_INVALID_TOKEN_CODE 401
+#define R_API_ERROR_MISSING_PARAMETER_CODE 400
+#define R_API_ERROR_NETWORK_ERROR_CODE 503
+#define R_API_ERROR_NOT_FOUND_CODE 404
+#define R_API_ERROR_OPERATION_FAILED_CODE 500
+#define R_API_ERROR_RATE_LIMIT_EXCEEDED_CODE 429
+#define R_API_ERROR_RESOURCE_CONFLICT_CODE 409
+#define R_API_ERROR_SERVICE_UNAVAILABLE_CODE 503
+#define R_API_ERROR_TIMEOUT_CODE 408
+#define R_API_ERROR_TOKEN_EXPIRED_CODE 401
+#define R_API_ERROR_UNAUTHORIZED_CODE 401
+#define R_API_ERROR_VALIDATION_FAILED_CODE 422
+#define R_AUTH_TITLE "Login"
+#define R_AUTH_ERROR_CREDENTIALS "Invalid credentials"
+#define R_EN_API_ERROR_AUTHENTICATION_FAILED "Authentication failed"
+#define R_EN_API_ERROR_BAD_REQUEST "Bad request"
+#define R_EN_API_ERROR_DATABASE_ERROR "Database error"
+#define R_EN_API_ERROR_FORBIDDEN "Forbidden"
+#define R_EN_API_ERROR_INSUFFICIENT_PERMISSIONS "Insufficient permissions"
+#define R_EN_API_ERROR_INTERNAL_SERVER_ERROR "Internal server error"
+#define R_EN_API_ERROR_INVALID_FORMAT "Invalid format"
+#define R_EN_API_ERROR_INVALID_PARAMETER "Invalid parameter"
+#define R_EN_API_ERROR_INVALID_TOKEN "Invalid token"
+#define R_EN_API_ERROR_MISSING_PARAMETER "Missing parameter"
+#define R_EN_API_ERROR_NETWORK_ERROR "Network error"
+#define R_EN_API_ERROR_NOT_FOUND "Resource not found"
+#define R_EN_API_ERROR_OPERATION_FAILED "Operation failed"
+#define R_EN_API_ERROR_RATE_LIMIT_EXCEEDED "Rate limit exceeded"
+#define R_EN_API_ERROR_RESOURCE_CONFLICT "Resource conflict"
+#define R_EN_API_ERROR_SERVICE_UNAVAILABLE "Service unavailable"
+#define R_EN_API_ERROR_TIMEOUT "Request timeout"
+#define R_EN_API_ERROR_TOKEN_EXPIRED "Token expired"
+#define R_EN_API_ERROR_UNAUTHORIZED "Unauthorized"
+#define R_EN_API_ERROR_VALIDATION_FAILED "Validation failed"
+#define R_FR_API_ERROR_AUTHENTICATION_FAILED "\xc3\x89" "chec de l'authentification"
+#define R_FR_API_ERROR_BAD_REQUEST "Requ\xc3\xaate invalide"
+#define R_FR_API_ERROR_DATABASE_ERROR "Erreur de base de donn\xc3\xa9" "es"
+#define R_FR_API_ERROR_FORBIDDEN "Acc\xc3\xa8s interdit"
+#define R_FR_API_ERROR_INSUFFICIENT_PERMISSIONS "Permissions insuffisantes"
+#define R_FR_API_ERROR_INTERNAL_SERVER_ERROR "Erreur interne du serveur"
+#define R_FR_API_ERROR_INVALID_FORMAT "Format invalide"
+#define R_FR_API_ERROR_INVALID_PARAMETER "Param\xc3\xa8tre invalide"
+#define R_FR_API_ERROR_INVALID_TOKEN "Token invalide"
+#define R_FR_API_ERROR_MISSING_PARAMETER "Param\xc3\xa8tre manquant"
+#define R_FR_API_ERROR_NETWORK_ERROR "Erreur r\xc3\xa9seau"
+#define R_FR_API_ERROR_NOT_FOUND "Ressource non trouv\xc3\xa9" "e"
+#define R_FR_API_ERROR_OPERATION_FAILED "Op\xc3\xa9ration \xc3\xa9" "chou\xc3\xa9" "e"
+#define R_FR_API_ERROR_RATE_LIMIT_EXCEEDED "Limite de d\xc3\xa9" "bit d\xc3\xa9pass\xc3\xa9" "e"
+#define R_FR_API_ERROR_RESOURCE_CONFLICT "Conflit de ressource"
+#define R_FR_API_ERROR_SERVICE_UNAVAILABLE "Service indisponible"
+#define R_FR_API_ERROR_TIMEOUT "D\xc3\xa9lai d'attente d\xc3\xa9pass\xc3\xa9"
+#define R_FR_API_ERROR_TOKEN_EXPIRED "Token expir\xc3\xa9"
+#define R_FR_API_ERROR_UNAUTHORIZED "Non autoris\xc3\xa9"
+#define R_FR_API_ERROR_VALIDATION_FAILED "\xc3\x89" "chec de la validation"
+/** French café with a coffee emoji. */
+#define R_I18N_CAFE "Caf\xc3\xa9 \xe2\x98\x95"
+#define R_I18N_WELCOME_JA "\xe3\x82\x88\xe3\x81\x86\xe3\x81\x93\xe3\x81\x9d"
+#define R_MAP_LONDON_LAT 51.5074
+#define R_MAP_LONDON_LNG (-0.1278)
+#define R_MAP_ORIGIN_X 0.0
+#define R_MAP_ORIGIN_Y 0.0
+#define R_MAP_PARIS_LAT 48.8566
+#define R_MAP_PARIS_LNG 2.3522
+
+#endif /* R_RESOURCES_H */
@@ -0,0 +1,119 @@
+// Generated by r-resources. Do not edit by hand.
+@file:Suppress("ClassName")
+
+package com.example.res
+
+object R {
+    data class LatLng(val lat: Double, val lng: Double)
+    data class Position(val x: Double, val y: Double)
+    const val about = "Built with r-resources."
+    const val appName = "r-resources"
+    val autoBigDecimal = java.math.BigDecimal("31212120129092108928901289001982890120988902190812098.218128128191289012077198209812908")
+    val autoBigNumber = java.math.BigDecimal("12345678901234567890123456789012345678901234567890123456789012345678901234567890")
+    const val autoNumber = 123456L
+    const val autoNumberWithDecimal = 123456.789
+    val bigNumber = java.math.BigDecimal("1234567890123456789012345678901234567890")
+    const val debugMode = true
+    fun errorWithDetails(errorType: String, message: String, code: Int): String = "Error ${errorType}: ${message} (Code: ${code})"
+    const val installDir = "C:\\Program Files\\Demo"
+    const val maxRetries = 3L
+    const val rate = 0.75
+    const val taxRate = 0.2
+    const val timeoutMs = 5000L
+    fun validationError(field: String, reason: String): String = "Validation failed for field '${field}': ${reason}"
+    const val versionPattern = "^\"v\\d+\"\$"
+    fun welcomeMessage(name: String, count: java.math.BigDecimal): String = "Welcome to ${name}, you have ${count} messages!"
+    object api {
+        object error {
+            const val authenticationFailedCode = 401L
+            const val badRequestCode = 400L
+            const val databaseErrorCode = 500L
+            const val forbiddenCode = 403L
+            const val insufficientPermissionsCode = 403L
+            const val internalServerErrorCode = 500L
+            const val invalidFormatCode = 400L
+            const val invalidParameterCode = 400L
+            const val invalidTokenCode = 401L
+            const val missingParameterCode = 400L
+            const val networkErrorCode = 503L
+            const val notFoundCode = 404L
+            const val operationFailedCode = 500L
+            const val rateLimitExceededCode = 429L
+            const val resourceConflictCode = 409L
+            const val serviceUnavailableCode = 503L
+            const val timeoutCode = 408L
+            const val tokenExpiredCode = 401L
+            const val unauthorizedCode = 401L
+            const val validationFailedCode = 422L
+        }
+    }
+    object auth {
+        const val title = "Login"
+        object error {
+            const val credentials = "Invalid credentials"
+        }
+    }
+    object en {
+        object api {
+            object error {
+                const val authenticationFailed = "Authentication failed"
+                const val badRequest = "Bad request"
+                const val databaseError = "Database error"
+                const val forbidden = "Forbidden"
+                const val insufficientPermissions = "Insufficient permissions"
+                const val internalServerError = "Internal server error"
+                const val invalidFormat = "Invalid format"
+                const val invalidParameter = "Invalid parameter"
+                const val invalidToken = "Invalid token"
+                const val missingParameter = "Missing parameter"
+                const val networkError = "Network error"
+                const val notFound = "Resource not found"
+                const val operationFailed = "Operation failed"
+                const val rateLimitExceeded = "Rate limit exceeded"
+                const val resourceConflict = "Resource conflict"
+                const val serviceUnavailable = "Service unavailable"
+                const val timeout = "Request timeout"
+                const val tokenExpired = "Token expired"
+                const val unauthorized = "Unauthorized"
+                const val validationFailed = "Validation failed"
+            }
+        }
+    }
+    object fr {
+        object api {
+            object error {
+                const val authenticationFailed = "Échec de l'authentification"
+                const val badRequest = "Requête invalide"
+                const val databaseError = "Erreur de base de données"
+                const val forbidden = "Accès interdit"
+                const val insufficientPermissions = "Permissions insuffisantes"
+                const val internalServerError = "Erreur interne du serveur"
+                const val invalidFormat = "Format invalide"
+                const val invalidParameter = "Paramètre invalide"
+                const val invalidToken = "Token invalide"
+                const val missingParameter = "Paramètre manquant"
+                const val networkError = "Erreur réseau"
+                const val notFound = "Ressource non trouvée"
+                const val operationFailed = "Opération échouée"
+                const val rateLimitExceeded = "Limite de débit dépassée"
+                const val resourceConflict = "Conflit de ressource"
+                const val serviceUnavailable = "Service indisponible"
+                const val timeout = "Délai d'attente dépassé"
+                const val tokenExpired = "Token expiré"
+                const val unauthorized = "Non autorisé"
+                const val validationFailed = "Échec de la validation"
+            }
+        }
+    }
+    /** Strings showing non-ASCII text is kept verbatim. */
+    object i18n {
+        /** French café with a coffee emoji. */
+        const val cafe = "Café ☕"
+        const val welcomeJa = "ようこそ"
+    }
+    object map {
+        val london = LatLng(51.5074, -0.1278)
+        val origin = Position(0.0, 0.0)
+        val paris = LatLng(48.8566, 2.3522)
+    }
+}
@@ -0,0 +1,672 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod auth {
+        /// Defined in `res/values.xml` (namespace `auth`)
+        #[deprecated(note = "use auth/title")]
+        pub const OLD_TITLE: &str = "Sign in";
+        /// Defined in `res/values.xml` (namespace `auth`)
+        pub const TITLE: &str = "Login";
+    }
+    pub mod map {
+        /// Defined in `res/values.xml` (namespace `map`)
+        pub const ORIGIN: r_resources::Position = r_resources::Position::new(0.0, 0.0);
+        /// Defined in `res/values.xml` (namespace `map`)
+        pub const PARIS: r_resources::LatLng = r_resources::LatLng::new(48.8566, 2.3522);
+    }
+    pub mod net {
+        /// Defined in `res/values.xml` (namespace `net`)
+        pub const ADMIN: std::net::SocketAddr = std::net::SocketAddr::V6(std::net::SocketAddrV6::new(std::net::Ipv6Addr::new(0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1), 8080, 0, 0));
+        /// Defined in `res/values.xml` (namespace `net`)
+        pub const DNS: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::new(1, 1, 1, 1));
+    }
+    /// Resources of [`net`] as one value, see [`NET`]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct NetConfig {
+        /// [`net::ADMIN`]
+        pub admin: std::net::SocketAddr,
+        /// [`net::DNS`]
+        pub dns: std::net::IpAddr,
+    }
+
+    /// Resources of [`net`]
+    pub const NET: NetConfig = NetConfig {
+        admin: net::ADMIN,
+        dns: net::DNS,
+    };
+    pub mod ui {
+        /// Defined in `res/values.xml` (namespace `ui`)
+        pub const ACCENT: &str = "#3366FF";
+    }
+    /// Defined in `res/values.xml`
+    pub const APP_NAME: &str = "Demo";
+    /// Defined in `res/values.xml`
+    pub const APP_VERSION: r_resources::Version = r_resources::Version::with_pre(1, 10, 0, "rc.1");
+    /// [`APP_VERSION`] as written
+    pub const APP_VERSION_STR: &str = "1.10.0-rc.1";
+    /// Defined in `res/values.xml`
+    pub const DEBUG: bool = true;
+    /// Defined in `res/values.xml`
+    pub fn greeting(name: &str) -> String {
+        format!("Hello {}!", name)
+    }
+    /// Defined in `res/values.xml`
+    pub static HUGE: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("123456789012345678901234567890.5").expect("valid decimal literal")
+    });
+    /// Defined in `res/values.xml`
+    pub const ICON_SEARCH: char = '\u{F002}';
+    /// Defined in `res/values.xml`
+    pub const INSTALL_NAMESPACE: u128 = 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8;
+    /// [`INSTALL_NAMESPACE`] as bytes, in the order of the text
+    pub const INSTALL_NAMESPACE_BYTES: [u8; 16] = [0x6b, 0xa7, 0xb8, 0x10, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8];
+    /// [`INSTALL_NAMESPACE`] as hyphenated lowercase text
+    pub const INSTALL_NAMESPACE_STR: &str = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+    /// Defined in `res/values.xml`
+    pub const LAUNCH: r_resources::Date = r_resources::Date::new(2025, 6, 1);
+    /// Defined in `res/values.xml`
+    pub const MAX_RETRIES: i64 = 3;
+    /// Defined in `res/values.xml`
+    pub const PI: f64 = 3.25;
+    /// Defined in `res/values.xml`
+    pub const PORT: u16 = 8080;
+    /// [`PORT`] as a `u32`
+    pub const PORT_U32: u32 = 8080;
+    /// [`PORT`] as a `usize`
+    pub const PORT_USIZE: usize = 8080;
+    /// Defined in `res/values.xml`
+    pub const PRO_PLAN: r_resources::Money = r_resources::Money::new(999, 2, "USD");
+    /// Defined in `res/values.xml`
+    pub const PROMO_ENDS_UNIX: i64 = 1767225599;
+    /// [`PROMO_ENDS_UNIX`] as written
+    pub const PROMO_ENDS_STR: &str = "2026-01-01T01:59:59+02:00";
+    /// Defined in `res/values.xml`
+    pub const RATIO: f32 = 0.5;
+    /// Defined in `res/values.xml`
+    pub const SIGNING_PUBKEY: &[u8] = &[
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+        0x19, 0xbf, 0x44, 0x09, 0x69, 0x84, 0xcd, 0xfe, 0x85, 0x41, 0xba, 0xc1,
+        0x67, 0xdc, 0x3b, 0x96, 0xc8, 0x50, 0x86, 0xaa, 0x30, 0xb6, 0xb6, 0xcb,
+        0x0c, 0x5c, 0x38, 0xad, 0x70, 0x31, 0x66, 0xe1,
+    ];
+    /// Defined in `res/values.xml`
+    pub const WAVE: &str = "\u{1F44B}\u{1F3FD}";
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        AppName,
+        AuthOldTitle,
+        AuthTitle,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::AppName, Self::AuthOldTitle, Self::AuthTitle];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::AppName => APP_NAME,
+                Self::AuthOldTitle => auth::OLD_TITLE,
+                Self::AuthTitle => auth::TITLE,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::AppName => "app_name",
+                Self::AuthOldTitle => "auth/old_title",
+                Self::AuthTitle => "auth/title",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "app_name" => Some(Self::AppName),
+                "auth/old_title" => Some(Self::AuthOldTitle),
+                "auth/title" => Some(Self::AuthTitle),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        MaxRetries,
+        Port,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::MaxRetries, Self::Port];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::MaxRetries => MAX_RETRIES,
+                Self::Port => PORT as i64,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::MaxRetries => "max_retries",
+                Self::Port => "port",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "max_retries" => Some(Self::MaxRetries),
+                "port" => Some(Self::Port),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `f64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum FloatKey {
+        Pi,
+        Ratio,
+    }
+
+    #[allow(deprecated)]
+    impl FloatKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Pi, Self::Ratio];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> f64 {
+            match self {
+                Self::Pi => PI,
+                Self::Ratio => RATIO as f64,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Pi => "pi",
+                Self::Ratio => "ratio",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "pi" => Some(Self::Pi),
+                "ratio" => Some(Self::Ratio),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `bool` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum BoolKey {
+        Debug,
+    }
+
+    #[allow(deprecated)]
+    impl BoolKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Debug];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> bool {
+            match self {
+                Self::Debug => DEBUG,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Debug => "debug",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "debug" => Some(Self::Debug),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ColorKey {
+        UiAccent,
+    }
+
+    #[allow(deprecated)]
+    impl ColorKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::UiAccent];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::UiAccent => ui::ACCENT,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::UiAccent => "ui/accent",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "ui/accent" => Some(Self::UiAccent),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 3] = [
+            ("app_name", super::APP_NAME),
+            ("auth/old_title", super::auth::OLD_TITLE),
+            ("auth/title", super::auth::TITLE),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 2] = [
+            ("max_retries", super::MAX_RETRIES),
+            ("port", super::PORT as i64),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 2] = [
+            ("pi", super::PI),
+            ("ratio", super::RATIO as f64),
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 1] = [
+            ("debug", super::DEBUG),
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 1] = [
+            ("ui/accent", super::ui::ACCENT),
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 23] = [
+            "app_name",
+            "app_version",
+            "auth/old_title",
+            "auth/title",
+            "debug",
+            "greeting",
+            "huge",
+            "icon_search",
+            "install_namespace",
+            "launch",
+            "map/origin",
+            "map/paris",
+            "max_retries",
+            "net/admin",
+            "net/dns",
+            "pi",
+            "port",
+            "pro_plan",
+            "promo_ends",
+            "ratio",
+            "signing_pubkey",
+            "ui/accent",
+            "wave",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 23;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+        /// Number of resources per type, named like the typed modules
+        /// (`("string", 12)`), in a fixed order
+        #[must_use]
+        pub const fn summary() -> &'static [(&'static str, usize)] {
+            &[("string", 4), ("int", 2), ("float", 2), ("decimal", 1), ("boolean", 1), ("color", 1), ("template", 1), ("latlng", 1), ("position", 1), ("semver", 1), ("ipaddr", 1), ("socketaddr", 1), ("datetime", 1), ("date", 1), ("money", 1), ("character", 1), ("binary", 1), ("uuid", 1)]
+        }
+    }
+}
+
+pub mod string {
+    pub mod auth {
+        #[allow(deprecated)]
+        pub use super::super::r::auth::OLD_TITLE;
+        pub use super::super::r::auth::TITLE;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, &str)] = &[
+            ("auth/old_title", OLD_TITLE),
+            ("auth/title", TITLE),
+        ];
+    }
+    pub use super::r::APP_NAME;
+    pub use super::r::WAVE;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, &str)] = &[
+        ("app_name", APP_NAME),
+        ("auth/old_title", auth::OLD_TITLE),
+        ("auth/title", auth::TITLE),
+        ("wave", WAVE),
+    ];
+}
+
+pub mod int {
+    pub use super::r::MAX_RETRIES;
+    pub use super::r::PORT;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, i128)] = &[
+        ("max_retries", MAX_RETRIES as i128),
+        ("port", PORT as i128),
+    ];
+}
+
+pub mod float {
+    pub use super::r::PI;
+    pub use super::r::RATIO;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, f64)] = &[
+        ("pi", PI),
+        ("ratio", RATIO as f64),
+    ];
+}
+
+pub mod decimal {
+    pub use super::r::HUGE;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub static ALL: &[(&str, &std::sync::LazyLock<r_resources::BigDecimal>)] = &[
+        ("huge", &HUGE),
+    ];
+}
+
+pub mod boolean {
+    pub use super::r::DEBUG;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, bool)] = &[
+        ("debug", DEBUG),
+    ];
+}
+
+pub mod color {
+    pub mod ui {
+        pub use super::super::r::ui::ACCENT;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, &str)] = &[
+            ("ui/accent", ACCENT),
+        ];
+    }
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, &str)] = &[
+        ("ui/accent", ui::ACCENT),
+    ];
+}
+
+pub mod template {
+    pub use super::r::greeting;
+    /// Qualified names of the templates of this module
+    pub const ALL: &[&str] = &[
+        "greeting",
+    ];
+}
+
+pub mod latlng {
+    pub mod map {
+        pub use super::super::r::map::PARIS;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, r_resources::LatLng)] = &[
+            ("map/paris", PARIS),
+        ];
+    }
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, r_resources::LatLng)] = &[
+        ("map/paris", map::PARIS),
+    ];
+}
+
+pub mod position {
+    pub mod map {
+        pub use super::super::r::map::ORIGIN;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, r_resources::Position)] = &[
+            ("map/origin", ORIGIN),
+        ];
+    }
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, r_resources::Position)] = &[
+        ("map/origin", map::ORIGIN),
+    ];
+}
+
+pub mod semver {
+    pub use super::r::APP_VERSION;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, r_resources::Version)] = &[
+        ("app_version", APP_VERSION),
+    ];
+}
+
+pub mod ipaddr {
+    pub mod net {
+        pub use super::super::r::net::DNS;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, std::net::IpAddr)] = &[
+            ("net/dns", DNS),
+        ];
+    }
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, std::net::IpAddr)] = &[
+        ("net/dns", net::DNS),
+    ];
+}
+
+pub mod socketaddr {
+    pub mod net {
+        pub use super::super::r::net::ADMIN;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, std::net::SocketAddr)] = &[
+            ("net/admin", ADMIN),
+        ];
+    }
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, std::net::SocketAddr)] = &[
+        ("net/admin", net::ADMIN),
+    ];
+}
+
+pub mod datetime {
+    pub use super::r::PROMO_ENDS_UNIX;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, i64)] = &[
+        ("promo_ends", PROMO_ENDS_UNIX),
+    ];
+}
+
+pub mod date {
+    pub use super::r::LAUNCH;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, r_resources::Date)] = &[
+        ("launch", LAUNCH),
+    ];
+}
+
+pub mod money {
+    pub use super::r::PRO_PLAN;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, r_resources::Money)] = &[
+        ("pro_plan", PRO_PLAN),
+    ];
+}
+
+pub mod character {
+    pub use super::r::ICON_SEARCH;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, char)] = &[
+        ("icon_search", ICON_SEARCH),
+    ];
+}
+
+pub mod binary {
+    pub use super::r::SIGNING_PUBKEY;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, &[u8])] = &[
+        ("signing_pubkey", SIGNING_PUBKEY),
+    ];
+}
+}
@@ -0,0 +1,291 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod audio {
+        /// Defined in `res/values.xml` (namespace `audio`)
+        #[cfg(target_pointer_width = "64")]
+        pub const BACKEND: &str = "x64";
+        /// Defined in `res/values.xml` (namespace `audio`)
+        pub const CHANNELS: i64 = 2;
+    }
+    /// Range: `1..`
+    ///
+    /// Defined in `res/values.xml`
+    #[cfg(all(unix, not(target_env = "musl")))]
+    pub const BUFFER: u32 = 256;
+    /// Lower bound of [`BUFFER`]
+    #[cfg(all(unix, not(target_env = "musl")))]
+    pub const BUFFER_MIN: u32 = 1;
+    /// [`BUFFER`] as a `usize`
+    #[cfg(all(unix, not(target_env = "musl")))]
+    pub const BUFFER_USIZE: usize = 256;
+    /// Defined in `res/values.xml`
+    #[cfg(unix)]
+    pub const DRIVER: &str = "alsa";
+    /// Defined in `res/values.xml`
+    #[cfg(not(unix))]
+    pub const DRIVER: &str = "wasapi";
+    /// Defined in `res/values.xml`
+    pub const TITLE: &str = "Player";
+
+    // Namespaced items under their flattened names
+    #[cfg(target_pointer_width = "64")] pub use self::audio::BACKEND as AUDIO_BACKEND;
+    pub use self::audio::CHANNELS as AUDIO_CHANNELS;
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        Title,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Title];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::Title => TITLE,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Title => "title",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "title" => Some(Self::Title),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        AudioChannels,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::AudioChannels];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::AudioChannels => audio::CHANNELS,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::AudioChannels => "audio/channels",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "audio/channels" => Some(Self::AudioChannels),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 1] = [
+            ("title", super::TITLE),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 1] = [
+            ("audio/channels", super::audio::CHANNELS),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 2] = [
+            "audio/channels",
+            "title",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 5;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+        /// Number of resources per type, named like the typed modules
+        /// (`("string", 12)`), in a fixed order
+        #[must_use]
+        pub const fn summary() -> &'static [(&'static str, usize)] {
+            &[("string", 3), ("int", 2), ("float", 0), ("decimal", 0), ("boolean", 0), ("color", 0), ("template", 0), ("latlng", 0), ("position", 0), ("semver", 0), ("ipaddr", 0), ("socketaddr", 0), ("datetime", 0), ("date", 0), ("money", 0), ("character", 0), ("binary", 0), ("uuid", 0)]
+        }
+    }
+}
+
+pub mod string {
+    pub mod audio {
+        #[cfg(target_pointer_width = "64")]
+        pub use super::super::r::audio::BACKEND;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, &str)] = &[
+        ];
+    }
+    #[cfg(any(unix, not(unix)))]
+    pub use super::r::DRIVER;
+    pub use super::r::TITLE;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, &str)] = &[
+        ("title", TITLE),
+    ];
+}
+
+pub mod int {
+    pub mod audio {
+        pub use super::super::r::audio::CHANNELS;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub const ALL: &[(&str, i128)] = &[
+            ("audio/channels", CHANNELS as i128),
+        ];
+    }
+    #[cfg(all(unix, not(target_env = "musl")))]
+    pub use super::r::BUFFER;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, i128)] = &[
+        ("audio/channels", audio::CHANNELS as i128),
+    ];
+}
+}
@@ -0,0 +1,267 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod finance {
+        /// Defined in `res/values.xml` (namespace `finance`)
+        pub static FEE: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+            <r_resources::BigDecimal as std::str::FromStr>::from_str("1.999999999999999999999").expect("valid decimal literal")
+        });
+    }
+    /// Defined in `res/values.xml`
+    pub const COUNT: u16 = 42;
+    /// Defined in `res/values.xml`
+    pub static HUGE: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("123456789012345678901234567890").expect("valid decimal literal")
+    });
+    /// Defined in `res/values.xml`
+    pub static NEGATIVE: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("-0.000000000000000000001").expect("valid decimal literal")
+    });
+    /// Defined in `res/values.xml`
+    pub static PI: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("3.14159265358979323846264338327950288").expect("valid decimal literal")
+    });
+    /// Defined in `res/values.xml`
+    pub const RATIO: f64 = 0.5;
+    /// Defined in `res/values.xml`
+    pub static TAX_RATE: std::sync::LazyLock<r_resources::BigDecimal> = std::sync::LazyLock::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("0.0825").expect("valid decimal literal")
+    });
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        Count,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Count];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::Count => COUNT as i64,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Count => "count",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "count" => Some(Self::Count),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `f64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum FloatKey {
+        Ratio,
+    }
+
+    #[allow(deprecated)]
+    impl FloatKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Ratio];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> f64 {
+            match self {
+                Self::Ratio => RATIO,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Ratio => "ratio",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "ratio" => Some(Self::Ratio),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 1] = [
+            ("count", super::COUNT as i64),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 1] = [
+            ("ratio", super::RATIO),
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 7] = [
+            "count",
+            "finance/fee",
+            "huge",
+            "negative",
+            "pi",
+            "ratio",
+            "tax_rate",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 7;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod int {
+    pub use super::r::COUNT;
+}
+
+pub mod float {
+    pub use super::r::RATIO;
+}
+
+pub mod decimal {
+    pub mod finance {
+        pub use super::super::r::finance::FEE;
+    }
+    pub use super::r::HUGE;
+    pub use super::r::NEGATIVE;
+    pub use super::r::PI;
+    pub use super::r::TAX_RATE;
+}
+}
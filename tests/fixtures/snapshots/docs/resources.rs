@@ -0,0 +1,318 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod auth {
+        //! Authentication screens.
+        //!
+        //! Shared by login and signup.
+        /// Title of the login screen.
+        ///
+        /// Shown in `h1`.
+        ///
+        /// Defined in `res/values.xml` (namespace `auth`)
+        pub const TITLE: &str = "Login";
+    }
+    /// Accent of the buttons,
+    /// also used for links.
+    ///
+    /// Defined in `res/values.xml`
+    pub const ACCENT: &str = "#FF5722";
+    /// Defined in `res/values.xml`
+    #[deprecated(note = "use \"auth/title\"")]
+    pub const LEGACY: &str = "Old";
+    /// Retries before giving up.
+    ///
+    /// Range: `1..=10`
+    ///
+    /// Defined in `res/values.xml`
+    pub const MAX_RETRIES: i64 = 3;
+    /// Lower bound of [`MAX_RETRIES`]
+    pub const MAX_RETRIES_MIN: i64 = 1;
+    /// Upper bound of [`MAX_RETRIES`]
+    pub const MAX_RETRIES_MAX: i64 = 10;
+    /// Pattern: `[a-z]+`
+    ///
+    /// Defined in `res/values.xml`
+    pub const USERNAME: &str = "admin";
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        AuthTitle,
+        Legacy,
+        Username,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::AuthTitle, Self::Legacy, Self::Username];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::AuthTitle => auth::TITLE,
+                Self::Legacy => LEGACY,
+                Self::Username => USERNAME,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::AuthTitle => "auth/title",
+                Self::Legacy => "legacy",
+                Self::Username => "username",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "auth/title" => Some(Self::AuthTitle),
+                "legacy" => Some(Self::Legacy),
+                "username" => Some(Self::Username),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        MaxRetries,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::MaxRetries];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::MaxRetries => MAX_RETRIES,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::MaxRetries => "max_retries",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "max_retries" => Some(Self::MaxRetries),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ColorKey {
+        Accent,
+    }
+
+    #[allow(deprecated)]
+    impl ColorKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Accent];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::Accent => ACCENT,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Accent => "accent",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "accent" => Some(Self::Accent),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 3] = [
+            ("auth/title", super::auth::TITLE),
+            ("legacy", super::LEGACY),
+            ("username", super::USERNAME),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 1] = [
+            ("max_retries", super::MAX_RETRIES),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 1] = [
+            ("accent", super::ACCENT),
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 5] = [
+            "accent",
+            "auth/title",
+            "legacy",
+            "max_retries",
+            "username",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 5;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod string {
+    pub mod auth {
+        pub use super::super::r::auth::TITLE;
+    }
+    #[allow(deprecated)]
+    pub use super::r::LEGACY;
+    pub use super::r::USERNAME;
+}
+
+pub mod int {
+    pub use super::r::MAX_RETRIES;
+}
+
+pub mod color {
+    pub use super::r::ACCENT;
+}
+}
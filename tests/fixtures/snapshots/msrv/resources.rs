@@ -0,0 +1,263 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod finance {
+        /// Defined in `res/values.xml` (namespace `finance`)
+        pub static FEE: r_resources::Lazy<r_resources::BigDecimal> = r_resources::Lazy::new(|| {
+            <r_resources::BigDecimal as std::str::FromStr>::from_str("1.999999999999999999999").expect("valid decimal literal")
+        });
+    }
+    /// Defined in `res/values.xml`
+    pub const DNS_PRIMARY: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::new(1, 1, 1, 1));
+    /// Defined in `res/values.xml`
+    pub const LIMIT: u16 = 42;
+    /// Defined in `res/values.xml`
+    pub const METRICS_BIND: std::net::SocketAddr = std::net::SocketAddr::V6(std::net::SocketAddrV6::new(std::net::Ipv6Addr::new(0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1), 9090, 0, 0));
+    /// Defined in `res/values.xml`
+    pub static TAX_RATE: r_resources::Lazy<r_resources::BigDecimal> = r_resources::Lazy::new(|| {
+        <r_resources::BigDecimal as std::str::FromStr>::from_str("0.0825").expect("valid decimal literal")
+    });
+    /// Defined in `res/values.xml`
+    pub const USERNAME_PATTERN: &str = "^[a-z0-9_]{3,16}$";
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        Limit,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::Limit];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::Limit => LIMIT as i64,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::Limit => "limit",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "limit" => Some(Self::Limit),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 1] = [
+            ("limit", super::LIMIT as i64),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 6] = [
+            "dns_primary",
+            "finance/fee",
+            "limit",
+            "metrics_bind",
+            "tax_rate",
+            "username",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 6;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+        /// Number of resources per type, named like the typed modules
+        /// (`("string", 12)`), in a fixed order
+        #[must_use]
+        pub const fn summary() -> &'static [(&'static str, usize)] {
+            &[("string", 1), ("int", 1), ("float", 0), ("decimal", 2), ("boolean", 0), ("color", 0), ("template", 0), ("latlng", 0), ("position", 0), ("semver", 0), ("ipaddr", 1), ("socketaddr", 1), ("datetime", 0), ("date", 0), ("money", 0), ("character", 0), ("binary", 0), ("uuid", 0)]
+        }
+    }
+}
+
+pub mod string {
+    pub use super::r::USERNAME_PATTERN;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, &str)] = &[
+        ("username", USERNAME_PATTERN),
+    ];
+}
+
+pub mod int {
+    pub use super::r::LIMIT;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, i128)] = &[
+        ("limit", LIMIT as i128),
+    ];
+}
+
+pub mod decimal {
+    pub mod finance {
+        pub use super::super::r::finance::FEE;
+        /// Resources of this module, by qualified name
+        #[allow(deprecated)]
+        pub static ALL: &[(&str, &r_resources::Lazy<r_resources::BigDecimal>)] = &[
+            ("finance/fee", &FEE),
+        ];
+    }
+    pub use super::r::TAX_RATE;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub static ALL: &[(&str, &r_resources::Lazy<r_resources::BigDecimal>)] = &[
+        ("finance/fee", &finance::FEE),
+        ("tax_rate", &TAX_RATE),
+    ];
+}
+
+pub mod ipaddr {
+    pub use super::r::DNS_PRIMARY;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, std::net::IpAddr)] = &[
+        ("dns_primary", DNS_PRIMARY),
+    ];
+}
+
+pub mod socketaddr {
+    pub use super::r::METRICS_BIND;
+    /// Resources of this module, by qualified name
+    #[allow(deprecated)]
+    pub const ALL: &[(&str, std::net::SocketAddr)] = &[
+        ("metrics_bind", METRICS_BIND),
+    ];
+}
+}
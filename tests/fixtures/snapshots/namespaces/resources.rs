@@ -0,0 +1,308 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod _3d {
+        /// Defined in `res/values.xml` (namespace `3d`)
+        pub const DEPTH: i64 = 4;
+    }
+    pub mod auth {
+        pub mod errors {
+            /// Defined in `res/values.xml` (namespace `auth/errors`)
+            pub const INVALID_CREDENTIALS: &str = r#"Invalid "credentials""#;
+        }
+        /// Defined in `res/values.xml` (namespace `auth`)
+        pub const TITLE: &str = "Login";
+    }
+    pub mod r#type {
+        /// Defined in `res/values.xml` (namespace `type`)
+        pub const ACCENT: &str = "#3366FF";
+    }
+    /// Defined in `res/values.xml`
+    pub const APP_NAME: &str = "Demo";
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        AppName,
+        AuthErrorsInvalidCredentials,
+        AuthTitle,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::AppName, Self::AuthErrorsInvalidCredentials, Self::AuthTitle];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::AppName => APP_NAME,
+                Self::AuthErrorsInvalidCredentials => auth::errors::INVALID_CREDENTIALS,
+                Self::AuthTitle => auth::TITLE,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::AppName => "app_name",
+                Self::AuthErrorsInvalidCredentials => "auth/errors/invalid-credentials",
+                Self::AuthTitle => "auth/title",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "app_name" => Some(Self::AppName),
+                "auth/errors/invalid-credentials" => Some(Self::AuthErrorsInvalidCredentials),
+                "auth/title" => Some(Self::AuthTitle),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        _3dDepth,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::_3dDepth];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::_3dDepth => _3d::DEPTH,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::_3dDepth => "3d/depth",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "3d/depth" => Some(Self::_3dDepth),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ColorKey {
+        TypeAccent,
+    }
+
+    #[allow(deprecated)]
+    impl ColorKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::TypeAccent];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::TypeAccent => r#type::ACCENT,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::TypeAccent => "type/accent",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "type/accent" => Some(Self::TypeAccent),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 3] = [
+            ("app_name", super::APP_NAME),
+            ("auth/errors/invalid-credentials", super::auth::errors::INVALID_CREDENTIALS),
+            ("auth/title", super::auth::TITLE),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 1] = [
+            ("3d/depth", super::_3d::DEPTH),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 1] = [
+            ("type/accent", super::r#type::ACCENT),
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 5] = [
+            "3d/depth",
+            "app_name",
+            "auth/errors/invalid-credentials",
+            "auth/title",
+            "type/accent",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 5;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod string {
+    pub mod auth {
+        pub mod errors {
+            pub use super::super::super::r::auth::errors::INVALID_CREDENTIALS;
+        }
+        pub use super::super::r::auth::TITLE;
+    }
+    pub use super::r::APP_NAME;
+}
+
+pub mod int {
+    pub mod _3d {
+        pub use super::super::r::_3d::DEPTH;
+    }
+}
+
+pub mod color {
+    pub mod r#type {
+        pub use super::super::r::r#type::ACCENT;
+    }
+}
+}
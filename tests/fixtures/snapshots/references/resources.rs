@@ -0,0 +1,253 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod auth {
+        /// Defined in `res/values.xml` (namespace `auth`)
+        pub const TITLE: &str = "Sign in to Café & Crème";
+    }
+    /// Defined in `res/values.xml`
+    pub const APP_NAME: &str = "Café & Crème";
+    /// Defined in `res/values.xml`
+    pub const BASE_URL: &str = "https://api.example.com";
+    /// Defined in `res/values.xml`
+    pub const ESCAPED: &str = "@string/app_name";
+    /// Defined in `res/values.xml`
+    pub const HANDLE: &str = "Follow @rresources";
+    /// Defined in `res/values.xml`
+    pub const LOGIN_TITLE: &str = "Sign in to Café & Crème";
+    /// Defined in `res/values.xml`
+    pub const USERS_URL: &str = "https://api.example.com/v2/users";
+    /// Defined in `res/values.xml`
+    pub const WELCOME: &str = "Welcome to Café & Crème!";
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        AppName,
+        AuthTitle,
+        BaseUrl,
+        Escaped,
+        Handle,
+        LoginTitle,
+        UsersUrl,
+        Welcome,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::AppName, Self::AuthTitle, Self::BaseUrl, Self::Escaped, Self::Handle, Self::LoginTitle, Self::UsersUrl, Self::Welcome];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::AppName => APP_NAME,
+                Self::AuthTitle => auth::TITLE,
+                Self::BaseUrl => BASE_URL,
+                Self::Escaped => ESCAPED,
+                Self::Handle => HANDLE,
+                Self::LoginTitle => LOGIN_TITLE,
+                Self::UsersUrl => USERS_URL,
+                Self::Welcome => WELCOME,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::AppName => "app_name",
+                Self::AuthTitle => "auth/title",
+                Self::BaseUrl => "base_url",
+                Self::Escaped => "escaped",
+                Self::Handle => "handle",
+                Self::LoginTitle => "login_title",
+                Self::UsersUrl => "users_url",
+                Self::Welcome => "welcome",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "app_name" => Some(Self::AppName),
+                "auth/title" => Some(Self::AuthTitle),
+                "base_url" => Some(Self::BaseUrl),
+                "escaped" => Some(Self::Escaped),
+                "handle" => Some(Self::Handle),
+                "login_title" => Some(Self::LoginTitle),
+                "users_url" => Some(Self::UsersUrl),
+                "welcome" => Some(Self::Welcome),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 8] = [
+            ("app_name", super::APP_NAME),
+            ("auth/title", super::auth::TITLE),
+            ("base_url", super::BASE_URL),
+            ("escaped", super::ESCAPED),
+            ("handle", super::HANDLE),
+            ("login_title", super::LOGIN_TITLE),
+            ("users_url", super::USERS_URL),
+            ("welcome", super::WELCOME),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 0] = [
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 8] = [
+            "app_name",
+            "auth/title",
+            "base_url",
+            "escaped",
+            "handle",
+            "login_title",
+            "users_url",
+            "welcome",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 8;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod string {
+    pub mod auth {
+        pub use super::super::r::auth::TITLE;
+    }
+    pub use super::r::APP_NAME;
+    pub use super::r::BASE_URL;
+    pub use super::r::ESCAPED;
+    pub use super::r::HANDLE;
+    pub use super::r::LOGIN_TITLE;
+    pub use super::r::USERS_URL;
+    pub use super::r::WELCOME;
+}
+}
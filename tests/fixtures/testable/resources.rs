@@ -0,0 +1,365 @@
+// @generated by `r-res generate`, do not edit by hand
+
+#[rustfmt::skip]
+pub mod res {
+
+pub struct R;
+
+impl Default for R {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl R {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+pub mod r {
+    /// Build profile the resources were generated for
+    pub const BUILD_PROFILE: &str = "debug";
+    pub mod api {
+        /// Defined in `res/values.xml` (namespace `api`)
+        pub const BASE_URL: &str = "https://api.example.com";
+        #[cfg(test)]
+        thread_local! {
+            static BASE_URL_OVERRIDE: r_resources::Override<&'static str> = const { r_resources::Override::new() };
+        }
+        /// [`BASE_URL`], or its override in tests (`set_base_url_for_test`)
+        #[cfg(not(test))]
+        #[must_use]
+        #[allow(deprecated)]
+        pub const fn base_url() -> &'static str {
+            BASE_URL
+        }
+        /// [`BASE_URL`], or its override on this thread
+        #[cfg(test)]
+        #[must_use]
+        #[allow(deprecated, clippy::excessive_nesting)]
+        pub fn base_url() -> &'static str {
+            r_resources::Override::get_or(&BASE_URL_OVERRIDE, BASE_URL)
+        }
+        /// Makes [`base_url`] return `value` on this thread until the guard is dropped
+        #[cfg(test)]
+        #[allow(clippy::excessive_nesting)]
+        pub fn set_base_url_for_test(value: &'static str) -> r_resources::OverrideGuard<&'static str> {
+            r_resources::Override::set(&BASE_URL_OVERRIDE, value)
+        }
+        /// Defined in `res/values.xml` (namespace `api`)
+        #[deprecated(note = "use api/v2")]
+        pub const LEGACY: bool = false;
+        #[cfg(test)]
+        thread_local! {
+            static LEGACY_OVERRIDE: r_resources::Override<bool> = const { r_resources::Override::new() };
+        }
+        /// [`LEGACY`], or its override in tests (`set_legacy_for_test`)
+        #[deprecated(note = "use api/v2")]
+        #[cfg(not(test))]
+        #[must_use]
+        #[allow(deprecated)]
+        pub const fn legacy() -> bool {
+            LEGACY
+        }
+        /// [`LEGACY`], or its override on this thread
+        #[deprecated(note = "use api/v2")]
+        #[cfg(test)]
+        #[must_use]
+        #[allow(deprecated, clippy::excessive_nesting)]
+        pub fn legacy() -> bool {
+            r_resources::Override::get_or(&LEGACY_OVERRIDE, LEGACY)
+        }
+        /// Makes [`legacy`] return `value` on this thread until the guard is dropped
+        #[cfg(test)]
+        #[allow(clippy::excessive_nesting)]
+        pub fn set_legacy_for_test(value: bool) -> r_resources::OverrideGuard<bool> {
+            r_resources::Override::set(&LEGACY_OVERRIDE, value)
+        }
+    }
+    /// Defined in `res/values.xml`
+    pub const MAX_RETRIES: u32 = 3;
+    #[cfg(test)]
+    thread_local! {
+        static MAX_RETRIES_OVERRIDE: r_resources::Override<u32> = const { r_resources::Override::new() };
+    }
+    /// [`MAX_RETRIES`], or its override in tests (`set_max_retries_for_test`)
+    #[cfg(not(test))]
+    #[must_use]
+    #[allow(deprecated)]
+    pub const fn max_retries() -> u32 {
+        MAX_RETRIES
+    }
+    /// [`MAX_RETRIES`], or its override on this thread
+    #[cfg(test)]
+    #[must_use]
+    #[allow(deprecated, clippy::excessive_nesting)]
+    pub fn max_retries() -> u32 {
+        r_resources::Override::get_or(&MAX_RETRIES_OVERRIDE, MAX_RETRIES)
+    }
+    /// Makes [`max_retries`] return `value` on this thread until the guard is dropped
+    #[cfg(test)]
+    #[allow(clippy::excessive_nesting)]
+    pub fn set_max_retries_for_test(value: u32) -> r_resources::OverrideGuard<u32> {
+        r_resources::Override::set(&MAX_RETRIES_OVERRIDE, value)
+    }
+    /// Defined in `res/values.xml`
+    pub const TIMEOUT_MS: i64 = 500;
+    /// Type-safe keys of the `&'static str` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StringKey {
+        ApiBaseUrl,
+    }
+
+    #[allow(deprecated)]
+    impl StringKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::ApiBaseUrl];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn as_str(&self) -> &'static str {
+            match self {
+                Self::ApiBaseUrl => api::BASE_URL,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::ApiBaseUrl => "api/base_url",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "api/base_url" => Some(Self::ApiBaseUrl),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `i64` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum NumberKey {
+        MaxRetries,
+        TimeoutMs,
+    }
+
+    #[allow(deprecated)]
+    impl NumberKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::MaxRetries, Self::TimeoutMs];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> i64 {
+            match self {
+                Self::MaxRetries => MAX_RETRIES as i64,
+                Self::TimeoutMs => TIMEOUT_MS,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::MaxRetries => "max_retries",
+                Self::TimeoutMs => "timeout_ms",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "max_retries" => Some(Self::MaxRetries),
+                "timeout_ms" => Some(Self::TimeoutMs),
+                _ => None,
+            }
+        }
+    }
+    /// Type-safe keys of the `bool` resources
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum BoolKey {
+        ApiLegacy,
+    }
+
+    #[allow(deprecated)]
+    impl BoolKey {
+        /// Every key, sorted by resource name
+        pub const ALL: &'static [Self] = &[Self::ApiLegacy];
+
+        /// Value of the resource
+        #[must_use]
+        pub const fn value(&self) -> bool {
+            match self {
+                Self::ApiLegacy => api::LEGACY,
+            }
+        }
+
+        /// Qualified resource name (`auth/title`)
+        #[must_use]
+        pub const fn name(&self) -> &'static str {
+            match self {
+                Self::ApiLegacy => "api/legacy",
+            }
+        }
+
+        /// Key for a qualified resource name
+        #[must_use]
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "api/legacy" => Some(Self::ApiLegacy),
+                _ => None,
+            }
+        }
+    }
+    /// Runtime lookup of resources by qualified name (`auth/title`)
+    #[allow(deprecated)]
+    pub mod lookup {
+        static STRINGS: [(&str, &str); 1] = [
+            ("api/base_url", super::api::BASE_URL),
+        ];
+
+        #[must_use]
+        pub fn string(name: &str) -> Option<&'static str> {
+            find(&STRINGS, name)
+        }
+
+        /// Same as `string`, with the reason of a failed lookup
+        pub fn try_string(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            string(name).ok_or_else(|| not_found(name, "string"))
+        }
+
+        static NUMBERS_I64: [(&str, i64); 2] = [
+            ("max_retries", super::MAX_RETRIES as i64),
+            ("timeout_ms", super::TIMEOUT_MS),
+        ];
+
+        #[must_use]
+        pub fn number_i64(name: &str) -> Option<i64> {
+            find(&NUMBERS_I64, name)
+        }
+
+        /// Same as `number_i64`, with the reason of a failed lookup
+        pub fn try_number_i64(
+            name: &str,
+        ) -> Result<i64, r_resources::RError> {
+            number_i64(name).ok_or_else(|| not_found(name, "i64 number"))
+        }
+
+        static NUMBERS_F64: [(&str, f64); 0] = [
+        ];
+
+        #[must_use]
+        pub fn number_f64(name: &str) -> Option<f64> {
+            find(&NUMBERS_F64, name)
+        }
+
+        /// Same as `number_f64`, with the reason of a failed lookup
+        pub fn try_number_f64(
+            name: &str,
+        ) -> Result<f64, r_resources::RError> {
+            number_f64(name).ok_or_else(|| not_found(name, "f64 number"))
+        }
+
+        static BOOLS: [(&str, bool); 1] = [
+            ("api/legacy", super::api::LEGACY),
+        ];
+
+        #[must_use]
+        pub fn bool(name: &str) -> Option<bool> {
+            find(&BOOLS, name)
+        }
+
+        /// Same as `bool`, with the reason of a failed lookup
+        pub fn try_bool(
+            name: &str,
+        ) -> Result<bool, r_resources::RError> {
+            bool(name).ok_or_else(|| not_found(name, "bool"))
+        }
+
+        static COLORS: [(&str, &str); 0] = [
+        ];
+
+        #[must_use]
+        pub fn color(name: &str) -> Option<&'static str> {
+            find(&COLORS, name)
+        }
+
+        /// Same as `color`, with the reason of a failed lookup
+        pub fn try_color(
+            name: &str,
+        ) -> Result<&'static str, r_resources::RError> {
+            color(name).ok_or_else(|| not_found(name, "color"))
+        }
+
+        static NAMES: [&str; 4] = [
+            "api/base_url",
+            "api/legacy",
+            "max_retries",
+            "timeout_ms",
+        ];
+
+        /// Every resource name, sorted
+        #[must_use]
+        pub fn names() -> &'static [&'static str] {
+            &NAMES
+        }
+
+        fn find<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+            table
+                .binary_search_by(|(key, _)| (*key).cmp(name))
+                .ok()
+                .map(|index| table[index].1)
+        }
+
+        fn not_found(name: &str, expected: &str) -> r_resources::RError {
+            let name = name.to_string();
+            if NAMES.binary_search(&name.as_str()).is_err() {
+                return r_resources::RError::ResourceNotFound(name);
+            }
+            let expected = expected.to_string();
+            r_resources::RError::TypeMismatch { name, expected }
+        }
+    }
+
+    /// How these resources were generated
+    pub mod meta {
+        /// Unix time of the build, `0` for deterministic builds
+        pub const GENERATED_AT_UNIX: i64 = 0;
+        /// Number of resources, counted once per qualified name
+        pub const RESOURCE_COUNT: usize = 4;
+        /// Profile the resources were selected for
+        pub const PROFILE: &str = "debug";
+        /// Resource files read, relative and sorted
+        pub const SOURCE_FILES: &[&str] = &["res/values.xml"];
+        /// Version of `r-resources` that generated the code
+        pub const GENERATOR_VERSION: &str = "0.9.0";
+    }
+}
+
+pub mod string {
+    pub mod api {
+        pub use super::super::r::api::BASE_URL;
+    }
+}
+
+pub mod int {
+    pub use super::r::MAX_RETRIES;
+    pub use super::r::TIMEOUT_MS;
+}
+
+pub mod boolean {
+    pub mod api {
+        #[allow(deprecated)]
+        pub use super::super::r::api::LEGACY;
+    }
+}
+}
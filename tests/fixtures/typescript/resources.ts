@@ -0,0 +1,122 @@
+// Generated by r-resources. Do not edit by hand.
+
+export const r = {
+  about: "Built with r-resources.",
+  appName: "r-resources",
+  autoBigDecimal: "31212120129092108928901289001982890120988902190812098.218128128191289012077198209812908", // BigDecimal
+  autoBigNumber: "12345678901234567890123456789012345678901234567890123456789012345678901234567890", // BigDecimal
+  autoNumber: 123456,
+  autoNumberWithDecimal: 123456.789,
+  bigNumber: "1234567890123456789012345678901234567890", // BigDecimal
+  debugMode: true,
+  errorWithDetails(errorType: string, message: string, code: number): string {
+    return `Error ${errorType}: ${message} (Code: ${code})`;
+  },
+  installDir: "C:\\Program Files\\Demo",
+  maxRetries: 3,
+  rate: 0.75,
+  taxRate: 0.2,
+  timeoutMs: 5000,
+  validationError(field: string, reason: string): string {
+    return `Validation failed for field '${field}': ${reason}`;
+  },
+  versionPattern: "^\"v\\d+\"$",
+  welcomeMessage(name: string, count: string): string {
+    return `Welcome to ${name}, you have ${count} messages!`;
+  },
+  api: {
+    error: {
+      authenticationFailedCode: 401,
+      badRequestCode: 400,
+      databaseErrorCode: 500,
+      forbiddenCode: 403,
+      insufficientPermissionsCode: 403,
+      internalServerErrorCode: 500,
+      invalidFormatCode: 400,
+      invalidParameterCode: 400,
+      invalidTokenCode: 401,
+      missingParameterCode: 400,
+      networkErrorCode: 503,
+      notFoundCode: 404,
+      operationFailedCode: 500,
+      rateLimitExceededCode: 429,
+      resourceConflictCode: 409,
+      serviceUnavailableCode: 503,
+      timeoutCode: 408,
+      tokenExpiredCode: 401,
+      unauthorizedCode: 401,
+      validationFailedCode: 422,
+    },
+  },
+  auth: {
+    title: "Login",
+    error: {
+      credentials: "Invalid credentials",
+    },
+  },
+  en: {
+    api: {
+      error: {
+        authenticationFailed: "Authentication failed",
+        badRequest: "Bad request",
+        databaseError: "Database error",
+        forbidden: "Forbidden",
+        insufficientPermissions: "Insufficient permissions",
+        internalServerError: "Internal server error",
+        invalidFormat: "Invalid format",
+        invalidParameter: "Invalid parameter",
+        invalidToken: "Invalid token",
+        missingParameter: "Missing parameter",
+        networkError: "Network error",
+        notFound: "Resource not found",
+        operationFailed: "Operation failed",
+        rateLimitExceeded: "Rate limit exceeded",
+        resourceConflict: "Resource conflict",
+        serviceUnavailable: "Service unavailable",
+        timeout: "Request timeout",
+        tokenExpired: "Token expired",
+        unauthorized: "Unauthorized",
+        validationFailed: "Validation failed",
+      },
+    },
+  },
+  fr: {
+    api: {
+      error: {
+        authenticationFailed: "Échec de l'authentification",
+        badRequest: "Requête invalide",
+        databaseError: "Erreur de base de données",
+        forbidden: "Accès interdit",
+        insufficientPermissions: "Permissions insuffisantes",
+        internalServerError: "Erreur interne du serveur",
+        invalidFormat: "Format invalide",
+        invalidParameter: "Paramètre invalide",
+        invalidToken: "Token invalide",
+        missingParameter: "Paramètre manquant",
+        networkError: "Erreur réseau",
+        notFound: "Ressource non trouvée",
+        operationFailed: "Opération échouée",
+        rateLimitExceeded: "Limite de débit dépassée",
+        resourceConflict: "Conflit de ressource",
+        serviceUnavailable: "Service indisponible",
+        timeout: "Délai d'attente dépassé",
+        tokenExpired: "Token expiré",
+        unauthorized: "Non autorisé",
+        validationFailed: "Échec de la validation",
+      },
+    },
+  },
+  /** Strings showing non-ASCII text is kept verbatim. */
+  i18n: {
+    /** French café with a coffee emoji. */
+    cafe: "Café ☕",
+    welcomeJa: "ようこそ",
+  },
+  map: {
+    london: { lat: 51.5074, lng: -0.1278 },
+    origin: { x: 0.0, y: 0.0 },
+    paris: { lat: 48.8566, lng: 2.3522 },
+  },
+} as const;
+
+export type R = typeof r;
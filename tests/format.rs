@@ -0,0 +1,69 @@
+//! `r-res fmt` against a fixture resource directory.
+
+#[cfg(test)]
+mod tests {
+    use r_resources::{format_resources, BuildPlan, FormatOptions};
+    use std::path::Path;
+
+    const FIXTURE: &str = "tests/fixtures/format";
+
+    fn format(dir: &str, sort: bool) -> Vec<(String, bool)> {
+        let plan = BuildPlan::new(
+            Path::new(FIXTURE).join(dir),
+            None,
+            "debug",
+        );
+        format_resources(&plan, &FormatOptions { sort })
+            .unwrap()
+            .into_iter()
+            .map(|file| (file.formatted, file.changed))
+            .collect()
+    }
+
+    fn expected(name: &str) -> String {
+        std::fs::read_to_string(
+            Path::new(FIXTURE).join("expected").join(name),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn files_match_the_expected_layout() {
+        assert_eq!(
+            format("res", false),
+            [(expected("values.xml"), true)]
+        );
+        assert_eq!(
+            format("res", true),
+            [(expected("values-sorted.xml"), true)]
+        );
+    }
+
+    #[test]
+    fn files_in_other_encodings_are_not_rewritten() {
+        let res = "tests/fixtures/snapshots/encodings/res";
+        let plan = BuildPlan::new(res.into(), None, "debug");
+        let error = format_resources(&plan, &FormatOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("latin1.xml:1: declared as iso-8859-1"),
+            "{error}"
+        );
+        assert!(error.contains("utf16.xml:1: declared as utf-16"));
+        assert!(!error.contains("bom.xml"));
+    }
+
+    #[test]
+    fn formatted_files_are_left_unchanged() {
+        let plan = BuildPlan::new(
+            Path::new(FIXTURE).join("expected"),
+            None,
+            "debug",
+        );
+        let files =
+            format_resources(&plan, &FormatOptions::default())
+                .unwrap();
+        assert!(files.iter().all(|file| !file.changed), "{files:?}");
+    }
+}
@@ -0,0 +1,25 @@
+//! Accessors of the `hot-reload` feature; the file is unchanged, so
+//! they return the compiled values.
+#![cfg(feature = "hot-reload")]
+
+r_resources::include_resources!();
+
+#[cfg(test)]
+mod tests {
+    use super::r;
+
+    #[test]
+    fn accessors_match_the_constants() {
+        assert_eq!(r::app_name(), r::APP_NAME);
+        assert_eq!(r::auth::title(), r::auth::TITLE);
+    }
+
+    #[test]
+    fn templates_render_the_file() {
+        let count = "3".parse().unwrap();
+        assert_eq!(
+            r::welcome_message("Ada", count),
+            "Welcome to Ada, you have 3 messages!"
+        );
+    }
+}
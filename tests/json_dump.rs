@@ -0,0 +1,170 @@
+//! `r::to_json()` of the `serde` feature, parsed back and checked on
+//! known resources of `res/`.
+#![cfg(feature = "serde")]
+
+r_resources::include_resources!();
+
+#[cfg(test)]
+mod tests {
+    use super::r;
+    use std::collections::BTreeMap;
+
+    /// Parsed JSON value, enough for the generated document
+    #[derive(Debug, PartialEq)]
+    enum Json {
+        Bool(bool),
+        Number(f64),
+        String(String),
+        Object(BTreeMap<String, Json>),
+    }
+
+    impl Json {
+        /// Member at the `/`-separated `path`
+        fn at(&self, path: &str) -> &Json {
+            path.split('/').fold(self, |value, name| match value {
+                Json::Object(members) => &members[name],
+                _ => panic!("{name} is not in an object"),
+            })
+        }
+    }
+
+    /// Parses `text`, panicking on anything but one JSON value
+    fn parse(text: &str) -> Json {
+        let mut chars = text.chars().peekable();
+        let value = parse_value(&mut chars);
+        assert!(chars.next().is_none(), "trailing characters");
+        value
+    }
+
+    fn parse_value(
+        chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    ) -> Json {
+        match chars.peek().copied() {
+            Some('{') => {
+                chars.next();
+                Json::Object(parse_members(chars))
+            }
+            Some('"') => {
+                chars.next();
+                Json::String(parse_string(chars))
+            }
+            Some('t' | 'f') => {
+                let word =
+                    take_while(chars, char::is_ascii_alphabetic);
+                Json::Bool(word == "true")
+            }
+            _ => {
+                let number = take_while(chars, |c| {
+                    c.is_ascii_digit() || "+-.eE".contains(*c)
+                });
+                Json::Number(number.parse().expect("a JSON number"))
+            }
+        }
+    }
+
+    /// Members of an object after its opening brace
+    fn parse_members(
+        chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    ) -> BTreeMap<String, Json> {
+        let mut members = BTreeMap::new();
+        if chars.next_if_eq(&'}').is_some() {
+            return members;
+        }
+        loop {
+            let Json::String(name) = parse_value(chars) else {
+                panic!("member names are strings");
+            };
+            assert_eq!(chars.next(), Some(':'));
+            let value = parse_value(chars);
+            assert!(
+                members.insert(name, value).is_none(),
+                "duplicate member"
+            );
+            match chars.next() {
+                Some(',') => {}
+                Some('}') => return members,
+                other => panic!("unexpected {other:?}"),
+            }
+        }
+    }
+
+    /// Characters up to the first one not matching `accept`, which is
+    /// left in `chars`
+    fn take_while(
+        chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+        accept: impl Fn(&char) -> bool,
+    ) -> String {
+        let mut text = String::new();
+        while let Some(c) = chars.next_if(&accept) {
+            text.push(c);
+        }
+        text
+    }
+
+    /// String after its opening quote
+    fn parse_string(
+        chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    ) -> String {
+        let mut text = String::new();
+        loop {
+            match chars.next().expect("closed string") {
+                '"' => return text,
+                '\\' => match chars.next() {
+                    Some('n') => text.push('\n'),
+                    Some('r') => text.push('\r'),
+                    Some('t') => text.push('\t'),
+                    Some('u') => {
+                        let hex: String =
+                            chars.by_ref().take(4).collect();
+                        let code =
+                            u32::from_str_radix(&hex, 16).unwrap();
+                        text.push(char::from_u32(code).unwrap());
+                    }
+                    Some(c) => text.push(c),
+                    None => panic!("unfinished escape"),
+                },
+                c => text.push(c),
+            }
+        }
+    }
+
+    fn string(text: &str) -> Json {
+        Json::String(text.to_string())
+    }
+
+    #[test]
+    fn dump_parses_back_to_the_resources() {
+        let json = parse(&r::to_json());
+
+        assert_eq!(json.at("app_name"), &string(r::APP_NAME));
+        assert_eq!(json.at("max_retries"), &Json::Number(3.0));
+        assert_eq!(json.at("rate"), &Json::Number(0.75));
+        assert_eq!(json.at("debug_mode"), &Json::Bool(true));
+        assert_eq!(json.at("auth/title"), &string("Login"));
+        assert_eq!(
+            json.at("auth/error/credentials"),
+            &string("Invalid credentials")
+        );
+        assert_eq!(json.at("i18n/cafe"), &string("Café ☕"));
+        assert_eq!(json.at("i18n/welcome_ja"), &string("ようこそ"));
+        assert_eq!(
+            json.at("install_dir"),
+            &string("C:\\Program Files\\Demo")
+        );
+        assert_eq!(
+            json.at("version_pattern"),
+            &string(r#"^"v\d+"$"#)
+        );
+        assert_eq!(json.at("map/paris/lat"), &Json::Number(48.8566));
+        assert_eq!(json.at("map/london/lng"), &Json::Number(-0.1278));
+        assert_eq!(json.at("map/origin/x"), &Json::Number(0.0));
+        assert_eq!(
+            json.at("big_number"),
+            &string("1234567890123456789012345678901234567890")
+        );
+        assert_eq!(
+            json.at("welcome_message/params"),
+            &string("name: &str, count: r_resources::BigDecimal")
+        );
+    }
+}
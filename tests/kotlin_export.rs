@@ -0,0 +1,23 @@
+//! Snapshot of the Kotlin `object R` of the example `res/` directory
+//! (`BuildOptions::export_kotlin`).
+
+#[cfg(test)]
+mod tests {
+    use r_resources::{validate_with_plan, BuildOptions, BuildPlan};
+
+    const SNAPSHOT: &str = "tests/fixtures/kotlin/R.kt";
+
+    #[test]
+    fn kotlin_export_matches_snapshot() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("app/R.kt");
+        let plan = BuildPlan::new("res".into(), None, "debug");
+        let options = BuildOptions::new()
+            .with_kotlin_export(&out, "com.example.res");
+        validate_with_plan(&plan, &options).unwrap();
+
+        let exported = std::fs::read_to_string(out).unwrap();
+        let expected = std::fs::read_to_string(SNAPSHOT).unwrap();
+        assert_eq!(exported, expected);
+    }
+}
@@ -0,0 +1,29 @@
+//! Snapshot of the Markdown catalog (`BuildOptions::export_markdown`).
+
+#[cfg(test)]
+mod tests {
+    use r_resources::{validate_with_plan, BuildOptions, BuildPlan};
+    use std::path::Path;
+
+    const FIXTURE: &str = "tests/fixtures/markdown";
+
+    #[test]
+    fn markdown_export_matches_snapshot() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("docs/resources.md");
+        let plan = BuildPlan::new(
+            Path::new(FIXTURE).join("res"),
+            None,
+            "debug",
+        );
+        let options = BuildOptions::new().with_markdown_export(&out);
+        validate_with_plan(&plan, &options).unwrap();
+
+        let exported = std::fs::read_to_string(out).unwrap();
+        let expected = std::fs::read_to_string(
+            Path::new(FIXTURE).join("resources.md"),
+        )
+        .unwrap();
+        assert_eq!(exported, expected);
+    }
+}
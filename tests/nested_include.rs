@@ -0,0 +1,38 @@
+//! The generated code only uses relative paths, so it also compiles
+//! when included below the crate root.
+
+// Resource namespaces add to the nesting of the including modules;
+// `pub` keeps items this test does not read from being unused
+#[allow(clippy::excessive_nesting)]
+pub mod app {
+    pub mod resources {
+        r_resources::include_resources!();
+    }
+}
+
+use app::resources::{int, r, string};
+
+#[test]
+fn paths_resolve_inside_nested_modules() {
+    assert_eq!(string::APP_NAME, r::APP_NAME);
+    assert_eq!(string::auth::TITLE, r::auth::TITLE);
+    assert_eq!(int::MAX_RETRIES, r::MAX_RETRIES);
+    assert_eq!(r::lookup::string("auth/title"), Some(r::auth::TITLE));
+    assert_eq!(r::StringKey::AuthTitle.as_str(), r::auth::TITLE);
+}
+
+mod wrapped {
+    r_resources::include_resources!(as app_res);
+    r_resources::include_resources!(pub as public_res);
+
+    #[test]
+    fn items_are_wrapped_in_the_named_module() {
+        assert_eq!(app_res::r::APP_NAME, public_res::r::APP_NAME);
+        assert_eq!(app_res::string::auth::TITLE, "Login");
+    }
+}
+
+#[test]
+fn the_build_script_locates_the_generated_file() {
+    assert!(env!("R_RESOURCES_GENERATED").ends_with("r_generated.rs"));
+}
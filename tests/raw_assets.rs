@@ -0,0 +1,23 @@
+//! Files of `res/raw/` are embedded in `r::raw`.
+
+r_resources::include_resources!();
+
+#[cfg(test)]
+mod tests {
+    use super::r;
+
+    #[test]
+    fn binary_files_are_byte_slices() {
+        let bytes: &[u8] = r::raw::SAMPLE_BIN;
+        assert_eq!(bytes, [0x00, 0x01, 0x02, 0xFF]);
+    }
+
+    #[test]
+    fn text_files_are_also_strings() {
+        assert_eq!(r::raw::GREETING_TXT_STR, "Hello from res/raw!\n");
+        assert_eq!(
+            r::raw::GREETING_TXT,
+            r::raw::GREETING_TXT_STR.as_bytes()
+        );
+    }
+}
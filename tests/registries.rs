@@ -0,0 +1,181 @@
+//! `ALL` registries and `r::meta` (`BuildOptions::registries`), on a
+//! committed file so the generated tables compile in the test.
+
+#[path = "fixtures/registries/resources.rs"]
+// One module deeper than an `include_resources!()`
+#[allow(dead_code, unused_imports, clippy::excessive_nesting)]
+mod resources;
+
+#[cfg(test)]
+mod tests {
+    use super::resources::res::{
+        binary, boolean, character, date, datetime, decimal, float,
+        int, ipaddr, latlng, money, r, semver, socketaddr, string,
+        template,
+    };
+    use r_resources::{generate_with_plan, BuildOptions, BuildPlan};
+
+    const RES_DIR: &str = "tests/fixtures/registries/res";
+    const GOLDEN: &str = "tests/fixtures/registries/resources.rs";
+
+    #[test]
+    fn registries_match_golden_file() {
+        let plan = BuildPlan::new(RES_DIR.into(), None, "debug");
+        let options = BuildOptions {
+            registries: true,
+            ..BuildOptions::default()
+        };
+        let code = generate_with_plan(&plan, &options, Some("res"))
+            .expect("fixture resources are valid");
+        let expected = std::fs::read_to_string(GOLDEN).unwrap();
+        assert!(
+            code == expected,
+            "{GOLDEN} is stale, regenerate it with `generate_with_plan`"
+        );
+    }
+
+    #[test]
+    fn tables_list_every_resource_by_name() {
+        assert_eq!(
+            string::ALL,
+            &[
+                ("app_name", "Demo"),
+                ("auth/old_title", "Sign in"),
+                ("auth/title", "Login"),
+                ("wave", "\u{1F44B}\u{1F3FD}"),
+            ]
+        );
+        assert_eq!(
+            string::auth::ALL,
+            &[("auth/old_title", "Sign in"), ("auth/title", "Login")]
+        );
+        assert_eq!(int::ALL, &[("max_retries", 3), ("port", 8080)]);
+        assert_eq!(float::ALL, &[("pi", 3.25), ("ratio", 0.5)]);
+        assert_eq!(boolean::ALL, &[("debug", true)]);
+        assert_eq!(template::ALL, &["greeting"]);
+        assert_eq!(latlng::ALL[0].1.lat(), 48.8566);
+        assert_eq!(
+            semver::ALL,
+            &[(
+                "app_version",
+                r_resources::Version::with_pre(1, 10, 0, "rc.1")
+            )]
+        );
+        assert_eq!(
+            decimal::ALL[0].1.to_string(),
+            "123456789012345678901234567890.5"
+        );
+    }
+
+    fn expect_u32(_: u32) {}
+    fn expect_usize(_: usize) {}
+
+    #[test]
+    fn integers_are_widened_without_casts() {
+        expect_u32(r::PORT_U32);
+        expect_usize(r::PORT_USIZE);
+        assert_eq!(r::PORT_USIZE, 8080);
+    }
+
+    #[test]
+    fn addresses_are_const_constructed() {
+        assert_eq!(
+            ipaddr::ALL,
+            &[("net/dns", std::net::IpAddr::from([1, 1, 1, 1]))]
+        );
+        assert_eq!(
+            socketaddr::net::ALL[0].1,
+            "[::1]:8080".parse::<std::net::SocketAddr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn struct_namespaces_group_their_resources() {
+        const NET: r::NetConfig = r::NET;
+        assert_eq!(NET.dns, r::net::DNS);
+        assert_eq!(NET.admin.port(), 8080);
+        assert_eq!(NET, r::NET);
+    }
+
+    #[test]
+    fn dates_are_listed_with_their_utc_seconds() {
+        assert_eq!(datetime::ALL, &[("promo_ends", 1_767_225_599)]);
+        assert_eq!(r::PROMO_ENDS_STR, "2026-01-01T01:59:59+02:00");
+        assert_eq!(
+            date::ALL,
+            &[("launch", r_resources::Date::new(2025, 6, 1))]
+        );
+    }
+
+    #[test]
+    fn prices_are_listed_in_minor_units() {
+        assert_eq!(
+            money::ALL,
+            &[("pro_plan", r_resources::Money::new(999, 2, "USD"))]
+        );
+        assert_eq!(money::ALL[0].1.minor_units, 999);
+    }
+
+    #[test]
+    fn chars_are_listed_apart_from_graphemes() {
+        assert_eq!(character::ALL, &[("icon_search", '\u{F002}')]);
+        assert_eq!(r::WAVE, "\u{1F44B}\u{1F3FD}");
+    }
+
+    #[test]
+    fn bytes_are_listed_decoded() {
+        let (name, key) = binary::ALL[0];
+        assert_eq!(name, "signing_pubkey");
+        assert_eq!(key.len(), 44);
+        assert_eq!(&key[..4], &[0x30, 0x2a, 0x30, 0x05]);
+    }
+
+    #[test]
+    fn uuids_have_no_typed_module() {
+        assert_eq!(
+            r::INSTALL_NAMESPACE,
+            0x6ba7b810_9dad_11d1_80b4_00c04fd430c8
+        );
+        assert_eq!(
+            u128::from_be_bytes(r::INSTALL_NAMESPACE_BYTES),
+            r::INSTALL_NAMESPACE
+        );
+        assert_eq!(
+            r::INSTALL_NAMESPACE_STR,
+            "6ba7b810-9dad-11d1-80b4-00c04fd430c8"
+        );
+        assert!(string::ALL
+            .iter()
+            .all(|(name, _)| *name != "install_namespace"));
+    }
+
+    #[test]
+    fn summary_counts_each_type() {
+        assert_eq!(
+            r::meta::summary(),
+            &[
+                ("string", 4),
+                ("int", 2),
+                ("float", 2),
+                ("decimal", 1),
+                ("boolean", 1),
+                ("color", 1),
+                ("template", 1),
+                ("latlng", 1),
+                ("position", 1),
+                ("semver", 1),
+                ("ipaddr", 1),
+                ("socketaddr", 1),
+                ("datetime", 1),
+                ("date", 1),
+                ("money", 1),
+                ("character", 1),
+                ("binary", 1),
+                ("uuid", 1),
+            ]
+        );
+        assert_eq!(r::meta::RESOURCE_COUNT, 23);
+        assert_eq!(r::meta::SOURCE_FILES, &["res/values.xml"]);
+        assert_eq!(r::meta::GENERATED_AT_UNIX, 0);
+    }
+}
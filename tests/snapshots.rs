@@ -0,0 +1,174 @@
+//! Generated code of each `tests/fixtures/snapshots/<name>/res`,
+//! compared with the committed `<name>/resources.rs`, which is also
+//! compiled here so invalid Rust fails this crate instead of a
+//! downstream one.
+//!
+//! After an intended change to the output, rewrite the snapshots with
+//! `R_RESOURCES_BLESS=1 cargo test --test snapshots` and review the
+//! diff. `msrv` is generated for Rust 1.75, which clippy checks with
+//! its `incompatible_msrv` lint.
+
+macro_rules! snapshots {
+    ($($(#[$attr:meta])* $name:ident: $path:literal),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[path = $path]
+            #[allow(
+                dead_code,
+                deprecated,
+                unused_imports,
+                clippy::excessive_nesting
+            )]
+            mod $name;
+        )*
+
+        /// Every snapshot, each compiled as a module of this crate
+        const SNAPSHOTS: &[&str] = &[$(stringify!($name)),*];
+    };
+}
+
+snapshots!(
+    cfg: "fixtures/snapshots/cfg/resources.rs",
+    decimals: "fixtures/snapshots/decimals/resources.rs",
+    docs: "fixtures/snapshots/docs/resources.rs",
+    encodings: "fixtures/snapshots/encodings/resources.rs",
+    #[clippy::msrv = "1.75"]
+    msrv: "fixtures/snapshots/msrv/resources.rs",
+    namespaces: "fixtures/snapshots/namespaces/resources.rs",
+    profiles: "fixtures/snapshots/profiles/resources.rs",
+    references: "fixtures/snapshots/references/resources.rs",
+    templates: "fixtures/snapshots/templates/resources.rs",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::SNAPSHOTS;
+    use r_resources::{generate_with_plan, BuildOptions, BuildPlan};
+    use std::path::Path;
+
+    const DIR: &str = "tests/fixtures/snapshots";
+    /// Set to rewrite the snapshots instead of comparing them
+    const BLESS: &str = "R_RESOURCES_BLESS";
+
+    /// Options of the snapshot `name`
+    fn options(name: &str) -> BuildOptions {
+        match name {
+            "cfg" => BuildOptions {
+                registries: true,
+                flatten_aliases: true,
+                ..BuildOptions::default()
+            },
+            "msrv" => BuildOptions {
+                registries: true,
+                ..BuildOptions::default().with_msrv("1.75")
+            },
+            _ => BuildOptions::default(),
+        }
+    }
+
+    /// Code generated from the `res/` of the snapshot `name`
+    fn generate(name: &str) -> String {
+        let res = Path::new(DIR).join(name).join("res");
+        let plan = BuildPlan::new(res, None, "debug");
+        generate_with_plan(&plan, &options(name), Some("res"))
+            .unwrap_or_else(|e| panic!("{name}: {e}"))
+    }
+
+    // The snapshots are of builds without the statics of the `regex`
+    // feature, which need the `regex` crate
+    #[test]
+    #[cfg_attr(feature = "regex", ignore)]
+    fn snapshots_match_the_generated_code() {
+        let bless = std::env::var_os(BLESS).is_some();
+        let mut stale = Vec::new();
+        for name in SNAPSHOTS {
+            let code = generate(name);
+            let path = Path::new(DIR).join(name).join("resources.rs");
+            if bless {
+                std::fs::write(&path, &code).unwrap();
+            } else if std::fs::read_to_string(&path).ok() != Some(code) {
+                stale.push(path.display().to_string());
+            }
+        }
+        assert!(
+            stale.is_empty(),
+            "stale snapshots: {}; rerun with {BLESS}=1 and review the diff",
+            stale.join(", ")
+        );
+    }
+
+    #[test]
+    fn every_fixture_is_compiled() {
+        let mut fixtures: Vec<String> = std::fs::read_dir(DIR)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        fixtures.sort();
+        assert_eq!(
+            fixtures, SNAPSHOTS,
+            "list new fixtures in the `snapshots!` of tests/snapshots.rs"
+        );
+    }
+
+    #[test]
+    fn snapshots_hold_their_values() {
+        use super::*;
+        assert_eq!(
+            namespaces::res::r::auth::errors::INVALID_CREDENTIALS,
+            "Invalid \"credentials\""
+        );
+        assert_eq!(namespaces::res::r::r#type::ACCENT, "#3366FF");
+        assert_eq!(
+            references::res::r::WELCOME,
+            "Welcome to Café & Crème!"
+        );
+        assert_eq!(
+            references::res::r::USERS_URL,
+            "https://api.example.com/v2/users"
+        );
+        assert_eq!(
+            profiles::res::r::STATUS,
+            "Using http://localhost:8080"
+        );
+        assert_eq!(
+            templates::res::r::greeting("Ada", 3),
+            "Hello Ada, you have 3 messages!"
+        );
+        assert_eq!(
+            templates::res::r::mail::subject("Ada"),
+            r#"Re: "Ada" \ Ada"#
+        );
+        assert_eq!(
+            decimals::res::r::TAX_RATE.to_string(),
+            "0.0825"
+        );
+        assert_eq!(docs::res::r::MAX_RETRIES_MAX, 10);
+        assert_eq!(encodings::res::r::GREETING, "Grüß Gott");
+        assert_eq!(encodings::res::r::FAREWELL, "さようなら");
+        assert_eq!(encodings::res::r::DESSERT, "Crème brûlée");
+        assert_eq!(
+            msrv::res::r::finance::FEE.to_string(),
+            "1.999999999999999999999"
+        );
+        assert_eq!(msrv::res::decimal::ALL.len(), 2);
+        #[cfg(unix)]
+        assert_eq!(cfg::res::string::DRIVER, "alsa");
+        #[cfg(not(unix))]
+        assert_eq!(cfg::res::string::DRIVER, "wasapi");
+        // Tables only list the resources that always exist
+        assert_eq!(cfg::res::string::ALL, [("title", "Player")]);
+    }
+
+    #[test]
+    fn msrv_snapshot_avoids_lazy_lock() {
+        let code = generate("msrv");
+        assert!(!code.contains("LazyLock"));
+        // `core::net` is only stable since Rust 1.77
+        assert!(!code.contains("core::net"));
+        assert_eq!(
+            code.contains("pub static USERNAME: r_resources::Lazy<"),
+            cfg!(feature = "regex")
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Synthetic resource files shared by the integration tests and the
+//! benchmarks (`benches/pipeline.rs` includes this module by path).
+//!
+//! Every generator returns a whole `values.xml` document with unique
+//! names, so the files build without warnings.
+
+// Each test crate only uses some of the generators
+#![allow(dead_code)]
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// `n` strings, `s_0` to `s_{n-1}`
+pub fn strings(n: usize) -> String {
+    document(|xml| {
+        for i in 0..n {
+            let _ = writeln!(
+                xml,
+                r#"    <string name="s_{i}">Value number {i}</string>"#
+            );
+        }
+    })
+}
+
+/// `n` mixed scalars: strings, numbers, bools and colors in turn
+pub fn scalars(n: usize) -> String {
+    document(|xml| {
+        for i in 0..n {
+            let _ = match i % 4 {
+                0 => writeln!(
+                    xml,
+                    r#"    <string name="v_{i}">Value {i}</string>"#
+                ),
+                1 => writeln!(
+                    xml,
+                    r#"    <number name="v_{i}">{i}</number>"#
+                ),
+                2 => {
+                    writeln!(
+                        xml,
+                        r#"    <bool name="v_{i}">true</bool>"#
+                    )
+                }
+                _ => writeln!(
+                    xml,
+                    r##"    <color name="v_{i}">#{:06X}</color>"##,
+                    i & 0xFF_FFFF
+                ),
+            };
+        }
+    })
+}
+
+/// Namespace tree `depth` levels deep with `width` children per level;
+/// every namespace holds one string
+pub fn namespaces(depth: usize, width: usize) -> String {
+    document(|xml| namespace_level(xml, depth, width, 1))
+}
+
+fn namespace_level(
+    xml: &mut String,
+    depth: usize,
+    width: usize,
+    level: usize,
+) {
+    if depth == 0 {
+        return;
+    }
+    let indent = "    ".repeat(level);
+    for i in 0..width {
+        let _ = writeln!(xml, r#"{indent}<ns name="n{level}_{i}">"#);
+        let _ = writeln!(
+            xml,
+            r#"{indent}    <string name="label">Level {level}</string>"#
+        );
+        namespace_level(xml, depth - 1, width, level + 1);
+        let _ = writeln!(xml, "{indent}</ns>");
+    }
+}
+
+/// `n` templates with `params` parameters each, alternating string and
+/// number parameters, over a few lines of text
+pub fn templates(n: usize, params: usize) -> String {
+    document(|xml| {
+        for i in 0..n {
+            template(xml, &format!("t_{i}"), params);
+        }
+    })
+}
+
+fn template(xml: &mut String, name: &str, params: usize) {
+    let _ = writeln!(xml, r#"    <template name="{name}">"#);
+    let mut text = String::from("Template {p0}");
+    let _ = writeln!(xml, r#"        <string name="p0"/>"#);
+    for p in 1..params {
+        let kind = if p % 2 == 0 { "string" } else { "number" };
+        let _ = writeln!(xml, r#"        <{kind} name="p{p}"/>"#);
+        let _ = write!(text, "\n        line {p}: {{p{p}}}");
+    }
+    let _ = writeln!(xml, "        {text}\n    </template>");
+}
+
+/// Writes `files` (name, XML) into `dir/res` and returns the resource
+/// directory
+pub fn write_res_dir(
+    dir: &Path,
+    files: &[(&str, String)],
+) -> PathBuf {
+    let res_dir = dir.join("res");
+    std::fs::create_dir_all(&res_dir).unwrap();
+    for (name, xml) in files {
+        std::fs::write(res_dir.join(name), xml).unwrap();
+    }
+    res_dir
+}
+
+fn document(body: impl FnOnce(&mut String)) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n",
+    );
+    body(&mut xml);
+    xml.push_str("</resources>\n");
+    xml
+}
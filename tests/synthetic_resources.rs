@@ -0,0 +1,49 @@
+//! Builds of the synthetic resources of `tests/support`, also used by
+//! the benchmarks.
+
+mod support;
+
+#[cfg(test)]
+mod tests {
+    use super::support;
+    use r_resources::{build_with_plan, BuildPlan};
+
+    fn build(files: &[(&str, String)]) -> String {
+        let tmp = tempfile::tempdir().unwrap();
+        let res_dir = support::write_res_dir(tmp.path(), files);
+        let plan = BuildPlan::new(res_dir, None, "debug");
+        let artifacts = build_with_plan(&plan).unwrap();
+        assert!(
+            artifacts.warnings.is_empty(),
+            "{:?}",
+            artifacts.warnings
+        );
+        artifacts.rust
+    }
+
+    #[test]
+    fn strings_and_scalars_build() {
+        let rust = build(&[
+            ("values.xml", support::strings(500)),
+            ("scalars.xml", support::scalars(500)),
+        ]);
+        assert!(rust.contains("pub const S_499: &str"));
+        assert!(rust.contains("pub const V_1: i64"));
+        assert!(rust.contains("pub const V_2: bool"));
+    }
+
+    #[test]
+    fn deep_namespaces_build() {
+        let rust =
+            build(&[("values.xml", support::namespaces(5, 2))]);
+        assert!(rust.contains("pub mod n5_1 {"));
+        assert_eq!(rust.matches("pub const LABEL: &str").count(), 62);
+    }
+
+    #[test]
+    fn templates_build() {
+        let rust =
+            build(&[("values.xml", support::templates(50, 4))]);
+        assert!(rust.contains("pub fn t_49("));
+    }
+}
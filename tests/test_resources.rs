@@ -0,0 +1,35 @@
+//! `res/tests/` resources are available as `r_tests::` in test builds,
+//! without any environment variable.
+
+r_resources::include_resources!();
+
+#[cfg(test)]
+mod tests {
+    use super::{r, r_tests};
+
+    #[test]
+    fn test_resources_shadow_defaults() {
+        assert_eq!(r_tests::MAX_RETRIES, 0);
+        assert_eq!(r::MAX_RETRIES, 3);
+        assert_eq!(r_tests::auth::TITLE, "Test login");
+        assert_eq!(r::auth::TITLE, "Login");
+    }
+
+    #[test]
+    fn defaults_stay_reachable() {
+        assert_eq!(r_tests::APP_NAME, r::APP_NAME);
+        assert_eq!(
+            r_tests::auth::error::CREDENTIALS,
+            r::auth::error::CREDENTIALS
+        );
+    }
+
+    #[test]
+    fn test_only_resources() {
+        assert_eq!(r_tests::fixtures::USER_EMAIL, "alice@example.com");
+        assert_eq!(
+            r_tests::fixtures::GREETING,
+            "Hello from r-resources"
+        );
+    }
+}
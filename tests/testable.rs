@@ -0,0 +1,73 @@
+//! Overridable getters (`BuildOptions::testable`), on a committed file
+//! so the `#[cfg(test)]` setters compile in this test.
+
+#[path = "fixtures/testable/resources.rs"]
+#[allow(dead_code, unused_imports, clippy::excessive_nesting)]
+mod resources;
+
+#[cfg(test)]
+mod tests {
+    use super::resources::res::r;
+    use r_resources::{generate_with_plan, BuildOptions, BuildPlan};
+
+    const RES_DIR: &str = "tests/fixtures/testable/res";
+    const GOLDEN: &str = "tests/fixtures/testable/resources.rs";
+
+    /// Attempts of a loop bounded by `r::max_retries()`
+    fn attempts() -> u32 {
+        (0..r::max_retries()).count() as u32
+    }
+
+    #[test]
+    fn testable_matches_golden_file() {
+        let plan = BuildPlan::new(RES_DIR.into(), None, "debug");
+        let options = BuildOptions {
+            testable: true,
+            ..BuildOptions::default()
+        };
+        let code = generate_with_plan(&plan, &options, Some("res"))
+            .expect("fixture resources are valid");
+        let expected = std::fs::read_to_string(GOLDEN).unwrap();
+        assert!(
+            code == expected,
+            "{GOLDEN} is stale, regenerate it with `generate_with_plan`"
+        );
+        // Resources without `overridable` stay plain constants
+        assert!(!code.contains("fn timeout_ms"));
+    }
+
+    #[test]
+    fn overrides_apply_until_the_guard_drops() {
+        {
+            let _guard = r::set_max_retries_for_test(1);
+            assert_eq!(attempts(), 1);
+            let _url =
+                r::api::set_base_url_for_test("http://localhost:8080");
+            assert_eq!(r::api::base_url(), "http://localhost:8080");
+        }
+        assert_eq!(attempts(), r::MAX_RETRIES);
+        assert_eq!(r::api::base_url(), r::api::BASE_URL);
+    }
+
+    #[test]
+    fn overrides_do_not_leak_into_other_tests() {
+        // Runs alongside `overrides_apply_until_the_guard_drops`, on
+        // another thread
+        for _ in 0..1000 {
+            assert_eq!(r::max_retries(), 3);
+        }
+        let _guard = r::set_max_retries_for_test(5);
+        std::thread::spawn(|| assert_eq!(r::max_retries(), 3))
+            .join()
+            .unwrap();
+        assert_eq!(r::max_retries(), 5);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_resources_keep_their_attribute() {
+        let _guard = r::api::set_legacy_for_test(true);
+        assert!(r::api::legacy());
+        assert_ne!(r::api::legacy(), r::api::LEGACY);
+    }
+}
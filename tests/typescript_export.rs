@@ -0,0 +1,23 @@
+//! Snapshot of the TypeScript definitions of the example `res/`
+//! directory (`BuildOptions::export_typescript`).
+
+#[cfg(test)]
+mod tests {
+    use r_resources::{validate_with_plan, BuildOptions, BuildPlan};
+
+    const SNAPSHOT: &str = "tests/fixtures/typescript/resources.ts";
+
+    #[test]
+    fn typescript_export_matches_snapshot() {
+        let tmp = tempfile::tempdir().unwrap();
+        let out = tmp.path().join("web/resources.ts");
+        let plan = BuildPlan::new("res".into(), None, "debug");
+        let options =
+            BuildOptions::new().with_typescript_export(&out);
+        validate_with_plan(&plan, &options).unwrap();
+
+        let exported = std::fs::read_to_string(out).unwrap();
+        let expected = std::fs::read_to_string(SNAPSHOT).unwrap();
+        assert_eq!(exported, expected);
+    }
+}